@@ -1,74 +1,439 @@
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
+use serde::Serialize;
 use tracing::{info, warn, error};
 use std::io::{self, Write};
 
-use filemover_types::MovePlan;
+use indicatif::{ProgressBar, ProgressStyle};
+
+use filemover_types::{
+    MovePlan, CancellationToken, Config, Conflict, JournalEntry, JournalFormat, JournalStore, Localize, OpKind, PlanNode,
+    ErrorPolicy, PlanNodeId, ResultKind, ValidationDelta,
+};
+use filemover_planner::{PlanValidator, subset_plan};
 use crate::config_manager::ConfigManager;
+use crate::error::CliError;
+use super::plan::{plan_filter, print_preservation_losses, PlanFilterOptions};
+
+/// Which operations of the plan `apply` runs (the others are recorded as skipped in the journal)
+/// and how failures are handled
+#[derive(Debug, Clone, Default)]
+pub struct ApplyOptions {
+    /// Rule IDs or labels
+    pub rules: Vec<String>,
+    /// Glob matched against the source path
+    pub path: Option<String>,
+    /// Leave out operations that have conflicts (including conflicts of merged items)
+    pub only_conflict_free: bool,
+    /// Stop the batch after this many operations (merged items count individually)
+    pub max_ops: Option<usize>,
+    /// Stop the batch after this many bytes (operations of unknown size count as 0)
+    pub max_bytes: Option<u64>,
+    /// Journal of an earlier batch; only the operations it left for the next batch are run
+    pub resume: Option<PathBuf>,
+    /// Overrides the profile's `execution.on_error`
+    pub on_error: Option<ErrorPolicy>,
+    /// Remove source folders left empty, inside the configured roots
+    pub prune_empty: bool,
+    /// Go through the whole run without touching the file system, writing a simulated journal
+    pub dry_run: bool,
+}
+
+impl ApplyOptions {
+    /// Any rule, path or conflict filter (batch limits are separate)
+    pub fn has_filters(&self) -> bool {
+        !self.rules.is_empty() || self.path.is_some() || self.only_conflict_free
+    }
+}
+
+/// Journal message of operations left out because the batch was full; `--resume` picks them up
+const BATCH_LIMIT_MESSAGE: &str = "Left for the next batch";
+
+/// Journal message of operations that `--dry-run` only went through
+const SIMULATED_MESSAGE: &str = "Simulated";
+
+/// Simulated journals get their own prefix so journal retention and `journal list` leave them alone
+const DRY_RUN_JOURNAL_PREFIX: &str = "filemover_dry_run_";
 
 pub async fn apply_command(
     plan_file: PathBuf,
     journal_file: Option<PathBuf>,
+    journal_format: JournalFormat,
     skip_confirmation: bool,
-    _config_manager: &ConfigManager,
+    accept_changes: bool,
+    options: ApplyOptions,
+    config_manager: &ConfigManager,
 ) -> Result<()> {
     info!("Starting plan execution");
     
+    // Nothing is changed, so there is nothing to confirm
+    let skip_confirmation = skip_confirmation || options.dry_run;
+    
+    // Prompts would mix with the result that scripts read from stdout
+    if !skip_confirmation && !crate::output::is_pretty() {
+        anyhow::bail!("Confirmation prompts need pretty output - pass --yes when using --quiet or --output json or minimal");
+    }
+    
     // Load move plan
     if !plan_file.exists() {
-        anyhow::bail!("Plan file does not exist: {}", plan_file.display());
+        return Err(CliError::file_operation(plan_file, "Plan file does not exist").into());
     }
     
-    let plan = load_move_plan(&plan_file)
+    let mut plan = load_move_plan(&plan_file)
         .context("Failed to load move plan")?;
+    // Hashed before any filtering so the journal links back to the file as saved
+    let plan_hash = plan.content_hash();
     
     info!("Loaded move plan with {} operations from {}", 
           plan.nodes.len(), 
           plan_file.display());
     
+    // Operations finished by earlier batches no longer have a source, so drop them before revalidating
+    if let Some(journal) = &options.resume {
+        let remaining = remaining_batch_sources(journal)?;
+        plan = subset_plan(&plan, |node| remaining.contains(&node.path_before));
+        if plan.roots.is_empty() {
+            pretty_println!("Nothing left to run - {} did not leave any operations for a next batch.", journal.display());
+            return Ok(());
+        }
+        pretty_println!("⏩ Resuming with the {} operations left by {}", plan.roots.len(), journal.display());
+    }
+    
     // Pre-execution validation
     validate_plan_for_execution(&plan)?;
     
+    // The file system may have changed since the plan was created
+    let delta = PlanValidator::new().revalidate(&mut plan)
+        .context("Failed to revalidate move plan")?;
+    if !delta.affected_nodes.is_empty() {
+        print_plan_changes(&plan, &delta);
+        if !accept_changes && !options.dry_run {
+            if skip_confirmation {
+                anyhow::bail!("The plan no longer matches the file system - review the changes and rerun with --accept-changes");
+            }
+            if !confirm("\nContinue with the changed plan? (y/N): ")? {
+                pretty_println!("Execution cancelled by user.");
+                return Ok(());
+            }
+        }
+    }
+    skip_missing_sources(&mut plan);
+    
+    let mut left_out = Vec::new();
+    if options.has_filters() {
+        let rules = config_manager.load_config(None)
+            .map(|config| config.rules)
+            .unwrap_or_default();
+        let total = plan.roots.len();
+        let (selected, skipped) = apply_filter(&plan, &options, &rules)?;
+        if selected.roots.is_empty() {
+            return Err(CliError::invalid_args("No operations in the plan match the filters").into());
+        }
+        pretty_println!("🔎 Running {} of {} operations; {} are left for a later run",
+                        selected.roots.len(), total, skipped.len());
+        plan = selected;
+        left_out = skipped;
+    }
+    
+    let mut remaining = 0;
+    if options.max_ops.is_some() || options.max_bytes.is_some() {
+        let (batch, rest) = limit_batch(&plan, options.max_ops, options.max_bytes);
+        if !rest.is_empty() {
+            pretty_println!("📦 Running a batch of {} operations; {} are left for the next batch",
+                            batch.roots.len(), rest.len());
+        }
+        remaining = rest.len();
+        plan = batch;
+        left_out.extend(rest);
+    }
+    
     // Show execution summary and get confirmation
     if !skip_confirmation {
         print_execution_summary(&plan);
         if !get_user_confirmation()? {
-            println!("Execution cancelled by user.");
+            pretty_println!("Execution cancelled by user.");
             return Ok(());
         }
     }
     
-    // Determine journal file path
-    let journal_path = journal_file.unwrap_or_else(|| {
-        PathBuf::from(format!(
-            "filemover_journal_{}.jsonl",
-            chrono::Utc::now().format("%Y%m%d_%H%M%S")
-        ))
-    });
+    // Determine journal file path, using the configured journal directory if any
+    let config = config_manager.load_config(None).unwrap_or_default();
+    let on_error = options.on_error.unwrap_or(config.execution.on_error);
+    
+    let journal_path = if options.dry_run {
+        simulated_journal_path(journal_file, journal_format, &config)?
+    } else {
+        resolve_journal_path(journal_file, journal_format, &config)?
+    };
     
     info!("Journal will be written to: {}", journal_path.display());
+    if options.dry_run {
+        pretty_println!("🧪 Dry run - the file system is not changed");
+    }
     
     // Execute plan (currently a stub - would integrate with executor module)
-    execute_plan_stub(&plan, &journal_path).await?;
+    let cancel_token = super::ctrl_c_cancellation_token();
+    let progress = crate::progress::new_bar(0);
+    progress.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    let execute_options = ExecuteOptions { on_error, dry_run: options.dry_run, plan_hash: Some(plan_hash) };
+    let outcome = execute_plan_stub(&plan, &left_out, &journal_path, journal_format, execute_options, &cancel_token, &progress).await
+        .map_err(|e| CliError::execution(format!("{:#}", e)))?;
+    
+    if !options.dry_run {
+        super::enforce_journal_retention(&config);
+    }
+    
+    if outcome.stopped {
+        pretty_println!("\n❌ Execution stopped after a failed operation ({} completed before it).", outcome.executed);
+        if outcome.rolled_back > 0 || outcome.rollback_failed > 0 {
+            pretty_println!("↩️  Rolled back {} completed operations; see {}",
+                            outcome.rolled_back, filemover_journal::undo_journal_path(&journal_path).display());
+            if outcome.rollback_failed > 0 {
+                pretty_println!("⚠️  {} operations could not be rolled back and are still at their destination",
+                                outcome.rollback_failed);
+            }
+        } else if outcome.executed > 0 {
+            pretty_println!("🔄 To undo them: filemover undo --journal {}", journal_path.display());
+        }
+        pretty_println!("📄 Journal: {}", journal_path.display());
+        return Err(CliError::execution(format!("An operation failed and --on-error {} stopped the run", match on_error {
+            ErrorPolicy::RollbackBatch => "rollback-batch",
+            _ => "stop",
+        })).into());
+    }
+    
+    let cancelled = cancel_token.is_cancelled();
+    if options.dry_run {
+        pretty_println!("\n🧪 Dry run finished: {} operations would run, {} would fail. Nothing was changed.",
+                        outcome.executed, outcome.failed);
+        pretty_println!("📄 Simulated journal: {}", journal_path.display());
+        if outcome.failed > 0 {
+            crate::error::record_warning(format!("{} operations would fail", outcome.failed));
+        }
+    } else if cancelled {
+        pretty_println!("\n⏹️  Execution cancelled - remaining operations were not started.");
+        pretty_println!("📄 Journal of completed operations: {}", journal_path.display());
+        pretty_println!("🔄 To undo them: filemover undo --journal {}", journal_path.display());
+    } else {
+        if outcome.failed > 0 {
+            pretty_println!("\n⚠️  Execution completed with {} failed operations (recorded in the journal)", outcome.failed);
+            crate::error::record_warning(format!("{} operations failed", outcome.failed));
+        } else {
+            pretty_println!("\n✅ Execution completed successfully!");
+        }
+        pretty_println!("📄 Journal saved to: {}", journal_path.display());
+        pretty_println!("🔄 To undo this operation: filemover undo --journal {}", journal_path.display());
+    }
+    let mut pruned_folders = 0;
+    if options.prune_empty && !cancelled && !options.dry_run {
+        pruned_folders = prune_empty_after_apply(&journal_path, &config.roots);
+    }
+    if remaining > 0 && !cancelled {
+        pretty_println!("⏭️  {} operations remain. Continue with: filemover apply --plan {} --resume {}",
+                        remaining, plan_file.display(), journal_path.display());
+    }
     
-    println!("\n✅ Execution completed successfully!");
-    println!("📄 Journal saved to: {}", journal_path.display());
-    println!("🔄 To undo this operation: filemover undo --journal {}", journal_path.display());
+    crate::output::emit_result("apply", &ApplyResult {
+        plan: plan_file,
+        journal: journal_path,
+        executed: outcome.executed,
+        failed: outcome.failed,
+        skipped: plan.nodes.values().filter(|node| node.kind == OpKind::Skip).count(),
+        filtered_out: left_out.len() - remaining,
+        remaining,
+        pruned_folders,
+        cancelled,
+        dry_run: options.dry_run,
+    })?;
     
     Ok(())
 }
 
+/// Result of `apply` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct ApplyResult {
+    plan: PathBuf,
+    journal: PathBuf,
+    executed: usize,
+    failed: usize,
+    skipped: usize,
+    /// Operations left out by the apply filters
+    filtered_out: usize,
+    /// Operations left for the next batch
+    remaining: usize,
+    /// Empty source folders removed with `--prune-empty`
+    pruned_folders: usize,
+    cancelled: bool,
+    /// Nothing was changed; the journal only shows what would have happened
+    dry_run: bool,
+}
+
+/// `--prune-empty`: problems are reported as warnings because the moves themselves succeeded
+fn prune_empty_after_apply(journal_path: &Path, roots: &[PathBuf]) -> usize {
+    if roots.is_empty() {
+        pretty_println!("⚠️  --prune-empty needs roots in the configuration - no folders were removed");
+        crate::error::record_warning("--prune-empty skipped: no roots configured");
+        return 0;
+    }
+    match super::clean_empty::prune_journal_sources(journal_path, roots, false) {
+        Ok(report) => {
+            if !report.skipped.is_empty() {
+                crate::error::record_warning(format!("{} empty folder(s) could not be removed", report.skipped.len()));
+            }
+            report.removed.len()
+        }
+        Err(e) => {
+            warn!("Failed to remove empty source folders: {:#}", e);
+            crate::error::record_warning(format!("Failed to remove empty source folders: {:#}", e));
+            0
+        }
+    }
+}
+
+/// Split the plan into the operations selected by the `options` filters and journal entries for the rest
+fn apply_filter(plan: &MovePlan, options: &ApplyOptions, rules: &[filemover_types::Rule]) -> Result<(MovePlan, Vec<JournalEntry>)> {
+    let filter_options = PlanFilterOptions {
+        rules: options.rules.clone(),
+        path: options.path.clone(),
+        min_size: None,
+    };
+    let matches = plan_filter(&filter_options, rules)?;
+    let selected = subset_plan(plan, |node| {
+        matches(node) && (!options.only_conflict_free || is_conflict_free(plan, node))
+    });
+
+    let left_out = plan.roots.iter()
+        .filter(|id| !selected.roots.contains(id))
+        .filter_map(|id| plan.nodes.get(id))
+        .map(|node| skipped_entry(node, "Skipped by apply filter"))
+        .collect();
+    Ok((selected, left_out))
+}
+
+/// Take operations in execution order until the next one would exceed a limit (the first one
+/// always runs so a single huge folder is not stuck), leaving the rest for the next batch
+fn limit_batch(plan: &MovePlan, max_ops: Option<usize>, max_bytes: Option<u64>) -> (MovePlan, Vec<JournalEntry>) {
+    let roots: HashSet<PlanNodeId> = plan.roots.iter().copied().collect();
+    let mut ordered: Vec<PlanNodeId> = filemover_planner::nodes_in_execution_order(plan).iter()
+        .map(|node| node.id)
+        .filter(|id| roots.contains(id))
+        .collect();
+    // Skipped roots are not in the execution order but still belong to the plan
+    let in_order: HashSet<PlanNodeId> = ordered.iter().copied().collect();
+    ordered.extend(plan.roots.iter().filter(|id| !in_order.contains(id)));
+    
+    let mut in_batch = HashSet::new();
+    let (mut ops, mut bytes) = (0usize, 0u64);
+    for id in ordered {
+        let subtree = subtree_nodes(plan, id);
+        let node_ops = subtree.len();
+        let node_bytes = plan.nodes.get(&id).and_then(|node| node.size_bytes).unwrap_or(0);
+        let full = max_ops.is_some_and(|max| ops + node_ops > max)
+            || max_bytes.is_some_and(|max| bytes + node_bytes > max);
+        if full && !in_batch.is_empty() {
+            break;
+        }
+        ops += node_ops;
+        bytes += node_bytes;
+        in_batch.insert(id);
+    }
+    
+    let batch = subset_plan(plan, |node| in_batch.contains(&node.id));
+    let rest = plan.roots.iter()
+        .filter(|id| !in_batch.contains(id))
+        .filter_map(|id| plan.nodes.get(id))
+        .map(|node| skipped_entry(node, BATCH_LIMIT_MESSAGE))
+        .collect();
+    (batch, rest)
+}
+
+/// The node and everything merged below it
+fn subtree_nodes(plan: &MovePlan, id: PlanNodeId) -> Vec<PlanNodeId> {
+    let mut nodes = Vec::new();
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        if let Some(node) = plan.nodes.get(&id) {
+            nodes.push(id);
+            stack.extend(node.children.iter().copied());
+        }
+    }
+    nodes
+}
+
+/// Sources of the operations an earlier batch left for the next one
+fn remaining_batch_sources(journal_path: &Path) -> Result<HashSet<PathBuf>> {
+    if !journal_path.exists() {
+        return Err(CliError::file_operation(journal_path.to_path_buf(), "Journal file does not exist").into());
+    }
+    let report = filemover_journal::open_journal(journal_path)
+        .and_then(|journal| journal.read_checked())
+        .with_context(|| format!("Failed to read journal {}", journal_path.display()))?;
+    Ok(report.entries.into_iter()
+        .filter(|entry| entry.result == ResultKind::Skip && entry.message.as_deref() == Some(BATCH_LIMIT_MESSAGE))
+        .map(|entry| entry.source)
+        .collect())
+}
+
+fn skipped_entry(node: &PlanNode, message: &str) -> JournalEntry {
+    JournalEntry::new(node.path_before.clone(), node.path_after.clone(), node.kind)
+        .with_rule_id(node.rule_id)
+        .with_tags(node.tags.clone())
+        .skipped(message)
+}
+
+fn is_conflict_free(plan: &MovePlan, node: &PlanNode) -> bool {
+    node.conflicts.is_empty()
+        && node.children.iter()
+            .filter_map(|id| plan.nodes.get(id))
+            .all(|child| is_conflict_free(plan, child))
+}
+
+/// Journal for `--dry-run`: the given path, or a dry-run name next to where the journal would go
+fn simulated_journal_path(
+    journal_file: Option<PathBuf>,
+    journal_format: JournalFormat,
+    config: &Config,
+) -> Result<PathBuf> {
+    if let Some(path) = journal_file {
+        return Ok(path);
+    }
+    let path = resolve_journal_path(None, journal_format, config)?;
+    let name = filemover_journal::journal_file_name(journal_format, chrono::Utc::now())
+        .replacen(filemover_journal::JOURNAL_FILE_PREFIX, DRY_RUN_JOURNAL_PREFIX, 1);
+    Ok(path.with_file_name(name))
+}
+
+/// The given journal file, or a new file in the configured journal directory (current directory if none)
+pub(crate) fn resolve_journal_path(
+    journal_file: Option<PathBuf>,
+    journal_format: JournalFormat,
+    config: &Config,
+) -> Result<PathBuf> {
+    if let Some(path) = journal_file {
+        return Ok(path);
+    }
+    
+    let file_name = filemover_journal::journal_file_name(journal_format, chrono::Utc::now());
+    super::artifact_path(config.journal_dir.as_deref(), file_name)
+}
+
 fn load_move_plan(path: &PathBuf) -> Result<MovePlan> {
     let content = std::fs::read_to_string(path)
         .context("Failed to read move plan file")?;
     
-    let plan: MovePlan = serde_json::from_str(&content)
+    let plan: MovePlan = filemover_types::from_versioned_json(&content)
         .context("Failed to parse move plan JSON")?;
     
     Ok(plan)
 }
 
-fn validate_plan_for_execution(plan: &MovePlan) -> Result<()> {
+pub(crate) fn validate_plan_for_execution(plan: &MovePlan) -> Result<()> {
     // Check if there are any operations to execute
     if plan.nodes.is_empty() {
         anyhow::bail!("Move plan is empty - no operations to execute");
@@ -90,49 +455,85 @@ fn validate_plan_for_execution(plan: &MovePlan) -> Result<()> {
     
     if critical_conflicts > 0 {
         warn!("⚠️  {} operations have unresolved conflicts", critical_conflicts);
-        println!("These conflicts will be handled according to the configured conflict policy.");
+        crate::error::record_warning(format!("{} operations have unresolved conflicts", critical_conflicts));
+        pretty_println!("These conflicts will be handled according to the configured conflict policy.");
     }
     
-    // Validate source paths exist
-    let mut missing_sources = 0;
-    for node in plan.nodes.values() {
-        if !matches!(node.kind, filemover_types::OpKind::Skip | filemover_types::OpKind::None) {
-            if !node.path_before.exists() {
-                warn!("Source path no longer exists: {}", node.path_before.display());
-                missing_sources += 1;
-            }
-        }
+    Ok(())
+}
+
+fn print_plan_changes(plan: &MovePlan, delta: &ValidationDelta) {
+    pretty_println!("\n=== Changes since the plan was created ===");
+    
+    let mut affected: Vec<_> = delta.affected_nodes.iter()
+        .filter_map(|id| plan.nodes.get(id))
+        .collect();
+    affected.sort_by(|a, b| a.path_before.cmp(&b.path_before));
+    
+    for node in affected.iter().take(10) {
+        let problems: Vec<_> = node.conflicts.iter().map(describe_conflict).collect();
+        let status = if problems.is_empty() { "now OK".to_string() } else { problems.join(", ") };
+        pretty_println!("  {} -> {}: {}", node.path_before.display(), node.path_after.display(), status);
+    }
+    if affected.len() > 10 {
+        pretty_println!("  ... and {} more operations", affected.len() - 10);
     }
     
-    if missing_sources > 0 {
-        warn!("⚠️  {} source paths no longer exist", missing_sources);
-        println!("These operations will be skipped during execution.");
+    if !delta.resolved_conflicts.is_empty() {
+        pretty_println!("Resolved conflicts: {}", delta.resolved_conflicts.len());
+    }
+    if !delta.new_conflicts.is_empty() {
+        pretty_println!("⚠️  New conflicts: {}", delta.new_conflicts.len());
+    }
+}
+
+/// In the language chosen with `--lang`
+pub(crate) fn describe_conflict(conflict: &Conflict) -> String {
+    conflict.localize(crate::output::locale())
+}
+
+/// Operations whose source disappeared are skipped instead of failing
+pub(crate) fn skip_missing_sources(plan: &mut MovePlan) {
+    let mut skipped = 0;
+    for node in plan.nodes.values_mut() {
+        if node.conflicts.contains(&Conflict::SourceMissing) && node.kind != OpKind::Skip {
+            node.kind = OpKind::Skip;
+            skipped += 1;
+        }
     }
     
-    Ok(())
+    if skipped > 0 {
+        warn!("⚠️  {} source paths no longer exist", skipped);
+        crate::error::record_warning(format!("{} operations were skipped because their source no longer exists", skipped));
+        pretty_println!("These operations will be skipped during execution.");
+    }
 }
 
 fn print_execution_summary(plan: &MovePlan) {
-    println!("\n=== Execution Summary ===");
+    pretty_println!("\n=== Execution Summary ===");
     
     let executable_ops: Vec<_> = plan.nodes.values()
         .filter(|node| !matches!(node.kind, filemover_types::OpKind::Skip | filemover_types::OpKind::None))
         .collect();
     
-    println!("📊 Operations to execute: {}", executable_ops.len());
+    pretty_println!("📊 Operations to execute: {}", executable_ops.len());
     
     if let Some(total_bytes) = plan.summary.total_bytes {
-        println!("💾 Total data size: {} bytes ({:.2} MB)", 
+        pretty_println!("💾 Total data size: {} bytes ({:.2} MB)", 
                  total_bytes, 
                  total_bytes as f64 / (1024.0 * 1024.0));
     }
     
     if plan.summary.cross_volume > 0 {
-        println!("🔄 Cross-volume operations: {} (slower)", plan.summary.cross_volume);
+        pretty_println!("🔄 Cross-volume operations: {} (slower)", plan.summary.cross_volume);
+    }
+    
+    if !plan.summary.preservation.is_empty() {
+        print_preservation_losses(&plan.summary.preservation, "⚠️  ");
     }
     
     if plan.summary.conflicts > 0 {
-        println!("⚠️  Operations with conflicts: {}", plan.summary.conflicts);
+        pretty_println!("⚠️  Operations with conflicts: {}", plan.summary.conflicts);
     }
     
     // Show operation types breakdown
@@ -141,7 +542,7 @@ fn print_execution_summary(plan: &MovePlan) {
         *op_counts.entry(&node.kind).or_insert(0) += 1;
     }
     
-    println!("\n📋 Operations breakdown:");
+    pretty_println!("\n📋 Operations breakdown:");
     for (op_kind, count) in op_counts {
         let description = match op_kind {
             filemover_types::OpKind::Move => "Fast move within same volume",
@@ -149,11 +550,11 @@ fn print_execution_summary(plan: &MovePlan) {
             filemover_types::OpKind::Rename => "Rename in place",
             _ => "Other",
         };
-        println!("  {:?}: {} operations ({})", op_kind, count, description);
+        pretty_println!("  {:?}: {} operations ({})", op_kind, count, description);
     }
     
     // Show a few example operations
-    println!("\n📝 First few operations:");
+    pretty_println!("\n📝 First few operations:");
     for (i, node) in executable_ops.iter().take(5).enumerate() {
         let conflict_info = if !node.conflicts.is_empty() {
             format!(" [⚠️ {} conflicts]", node.conflicts.len())
@@ -161,7 +562,7 @@ fn print_execution_summary(plan: &MovePlan) {
             String::new()
         };
         
-        println!("  {}. {} -> {}{}",
+        pretty_println!("  {}. {} -> {}{}",
                  i + 1,
                  node.path_before.display(),
                  node.path_after.display(),
@@ -170,14 +571,18 @@ fn print_execution_summary(plan: &MovePlan) {
     }
     
     if executable_ops.len() > 5 {
-        println!("  ... and {} more operations", executable_ops.len() - 5);
+        pretty_println!("  ... and {} more operations", executable_ops.len() - 5);
     }
 }
 
 fn get_user_confirmation() -> Result<bool> {
-    println!("\n⚠️  WARNING: This will permanently move/modify your files!");
-    println!("Make sure you have backups of important data.");
-    print!("\nDo you want to proceed? (y/N): ");
+    pretty_println!("\n⚠️  WARNING: This will permanently move/modify your files!");
+    pretty_println!("Make sure you have backups of important data.");
+    confirm("\nDo you want to proceed? (y/N): ")
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{}", prompt);
     
     io::stdout().flush()?;
     
@@ -188,37 +593,87 @@ fn get_user_confirmation() -> Result<bool> {
     Ok(input == "y" || input == "yes")
 }
 
+/// How `execute_plan_stub` runs the plan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ExecuteOptions {
+    pub on_error: ErrorPolicy,
+    /// Run every step except the file system changes; journal entries are marked as simulated
+    pub dry_run: bool,
+    /// `MovePlan::content_hash` of the plan file, recorded in the journal's summary record
+    pub plan_hash: Option<String>,
+}
+
+/// What happened during `execute_plan_stub`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ExecutionOutcome {
+    pub executed: usize,
+    pub failed: usize,
+    /// Completed operations undone again by `ErrorPolicy::RollbackBatch`
+    pub rolled_back: usize,
+    /// Completed operations `ErrorPolicy::RollbackBatch` could not undo (journaled as failed)
+    pub rollback_failed: usize,
+    /// A failure stopped the run before all operations were attempted
+    pub stopped: bool,
+}
+
 // Stub implementation for plan execution
 // In the real implementation, this would use the executor module
-async fn execute_plan_stub(plan: &MovePlan, journal_path: &PathBuf) -> Result<()> {
-    use indicatif::{ProgressBar, ProgressStyle};
+/// Run the plan, reporting the attempted operations on `progress` (its length is set to the operation count).
+/// `left_out` entries (operations not part of this run) are journaled first; failed operations are
+/// journaled as failed and then handled according to `options.on_error`.
+pub(crate) async fn execute_plan_stub(
+    plan: &MovePlan,
+    left_out: &[JournalEntry],
+    journal_path: &Path,
+    journal_format: JournalFormat,
+    options: ExecuteOptions,
+    cancel_token: &CancellationToken,
+    progress: &ProgressBar,
+) -> Result<ExecutionOutcome> {
     use std::time::Duration;
     use tokio::time::sleep;
     
-    let executable_ops: Vec<_> = plan.nodes.values()
-        .filter(|node| !matches!(node.kind, filemover_types::OpKind::Skip | filemover_types::OpKind::None))
-        .collect();
-    
-    let progress = ProgressBar::new(executable_ops.len() as u64);
-    progress.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}")
-            .unwrap()
-            .progress_chars("#>-"),
-    );
+    // Dependency order from the planner (skipped and merged-folder nodes are not included)
+    let executable_ops = filemover_planner::nodes_in_execution_order(plan);
+    progress.set_length(executable_ops.len() as u64);
     
     // Create journal file
-    std::fs::write(journal_path, "")?; // Create empty journal file
+    let mut journal = filemover_journal::create_journal(journal_path, journal_format)
+        .context("Failed to create journal")?;
+    if let Some(plan_hash) = &options.plan_hash {
+        journal.link_plan(plan_hash.clone());
+    }
+    for entry in left_out {
+        journal.append(entry)?;
+    }
     
+    let mut outcome = ExecutionOutcome::default();
+    let mut completed = Vec::new();
     for (i, node) in executable_ops.iter().enumerate() {
+        // Stop between operations so the journal always reflects completed work
+        if cancel_token.is_cancelled() {
+            journal.finish()?;
+            progress.abandon_with_message("Cancelled");
+            return Ok(outcome);
+        }
+        
         progress.set_message(format!("Processing: {}", 
             node.path_before.file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown")
         ));
+        progress.set_position(i as u64 + 1);
+        
+        // Operations skipped after planning (e.g. missing sources) are only recorded
+        if matches!(node.kind, OpKind::Skip | OpKind::None) {
+            journal.append(&skipped_entry(node, "Skipped"))?;
+            continue;
+        }
         
         // Simulate operation execution
-        sleep(Duration::from_millis(100)).await;
+        if !options.dry_run {
+            sleep(Duration::from_millis(100)).await;
+        }
         
         // In real implementation, this would:
         // 1. Execute the file operation using Windows APIs
@@ -232,37 +687,103 @@ async fn execute_plan_stub(plan: &MovePlan, journal_path: &PathBuf) -> Result<()
               node.path_before.display(), 
               node.path_after.display());
         
-        // Simulate writing journal entry
-        append_journal_entry(journal_path, node)?;
+        if let Err(message) = check_operation(node) {
+            warn!("Operation failed: {} -> {}: {}", node.path_before.display(), node.path_after.display(), message);
+            journal.append(&JournalEntry::new(node.path_before.clone(), node.path_after.clone(), node.kind)
+                .with_rule_id(node.rule_id)
+                .with_tags(node.tags.clone())
+                .failed(message))?;
+            outcome.failed += 1;
+            
+            match options.on_error {
+                ErrorPolicy::Continue => continue,
+                ErrorPolicy::Stop => {}
+                ErrorPolicy::RollbackBatch => {
+                    (outcome.rolled_back, outcome.rollback_failed) =
+                        rollback_batch(&completed, journal_path, journal_format, options.dry_run)?;
+                }
+            }
+            outcome.stopped = true;
+            journal.finish()?;
+            progress.abandon_with_message("Stopped after a failed operation");
+            return Ok(outcome);
+        }
         
-        progress.set_position(i as u64 + 1);
+        // Simulate writing journal entry
+        completed.push(append_journal_entry(journal.as_mut(), node, options.dry_run)?);
+        outcome.executed += 1;
     }
     
+    journal.finish()?;
     progress.finish_with_message("All operations completed");
     
+    Ok(outcome)
+}
+
+/// Failures the stub can detect: the source has to be there when its turn comes
+fn check_operation(node: &PlanNode) -> std::result::Result<(), String> {
+    if !node.path_before.exists() {
+        return Err("Source no longer exists".to_string());
+    }
     Ok(())
 }
 
-fn append_journal_entry(journal_path: &PathBuf, node: &filemover_types::PlanNode) -> Result<()> {
-    use std::fs::OpenOptions;
-    use std::io::Write;
+/// Undo the run's completed operations, newest first, recording them in the undo journal
+/// just like `filemover undo` does. Returns how many were undone and how many could not be.
+fn rollback_batch(completed: &[JournalEntry], journal_path: &Path, journal_format: JournalFormat, dry_run: bool) -> Result<(usize, usize)> {
+    if completed.is_empty() {
+        return Ok((0, 0));
+    }
     
-    let entry = filemover_types::JournalEntry::new(
+    let undo_path = filemover_journal::undo_journal_path(journal_path);
+    let mut undo_journal = filemover_journal::create_or_open_journal(&undo_path, journal_format)
+        .context("Failed to create undo journal")?;
+    let (mut rolled_back, mut failed) = (0, 0);
+    for entry in completed.iter().rev() {
+        let record = if dry_run {
+            info!("Would roll back {} -> {}", entry.dest.display(), entry.source.display());
+            JournalEntry::new(entry.dest.clone(), entry.source.clone(), entry.op)
+                .with_rule_id(entry.rule_id)
+                .with_tags(entry.tags.clone())
+                .with_message("Simulated rollback after a failed operation")
+        } else {
+            filemover_journal::undo_entry(entry)
+        };
+        if record.result == ResultKind::Failed {
+            warn!("Failed to roll back {} -> {}: {}", entry.dest.display(), entry.source.display(),
+                  record.message.as_deref().unwrap_or("unknown error"));
+            failed += 1;
+        } else {
+            rolled_back += 1;
+        }
+        undo_journal.append(&record)?;
+    }
+    undo_journal.finish()?;
+    Ok((rolled_back, failed))
+}
+
+fn append_journal_entry(journal: &mut dyn JournalStore, node: &filemover_types::PlanNode, simulated: bool) -> Result<JournalEntry> {
+    let mut entry = filemover_types::JournalEntry::new(
         node.path_before.clone(),
         node.path_after.clone(),
         node.kind
-    );
-    
-    let json_line = serde_json::to_string(&entry)?;
+    ).with_rule_id(node.rule_id).with_tags(node.tags.clone());
     
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(journal_path)?;
+    // Record the pre-operation state so undo can restore it
+    if let Ok(metadata) = filemover_types::EntryMetadata::capture(&node.path_before) {
+        let metadata = match node.size_bytes {
+            Some(size) => metadata.with_size(size),
+            None => metadata,
+        };
+        entry = entry.with_metadata(metadata);
+    }
+    if simulated {
+        entry = entry.with_message(SIMULATED_MESSAGE);
+    }
     
-    writeln!(file, "{}", json_line)?;
+    journal.append(&entry)?;
     
-    Ok(())
+    Ok(entry)
 }
 
 #[cfg(test)]
@@ -271,6 +792,7 @@ mod tests {
     use tempfile::TempDir;
     use filemover_types::{PlanNode, PlanNodeId, PlanSummary, OpKind};
     use std::collections::HashMap;
+    use crate::commands::undo::load_checked_journal;
 
     #[tokio::test]
     async fn test_apply_command_with_missing_plan() {
@@ -279,7 +801,10 @@ mod tests {
         let result = apply_command(
             PathBuf::from("nonexistent_plan.json"),
             None,
+            JournalFormat::Jsonl,
             true, // skip confirmation for test
+            false,
+            ApplyOptions::default(),
             &config_manager
         ).await;
         
@@ -292,6 +817,7 @@ mod tests {
             roots: vec![],
             nodes: HashMap::new(),
             summary: PlanSummary::default(),
+            execution_order: Vec::new(),
         };
         
         let result = validate_plan_for_execution(&plan);
@@ -315,6 +841,7 @@ mod tests {
             conflicts: vec![],
             children: vec![],
             rule_id: None,
+            tags: Vec::new(),
         };
         nodes.insert(node_id, node);
         
@@ -322,12 +849,214 @@ mod tests {
             roots: vec![node_id],
             nodes,
             summary: PlanSummary::default(),
+            execution_order: Vec::new(),
         };
         
         let result = validate_plan_for_execution(&plan);
         assert!(result.is_err());
     }
     
+    #[test]
+    fn test_apply_filter() {
+        let node = |path: &str, conflicts: Vec<Conflict>| PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: String::new(),
+            path_before: PathBuf::from(path),
+            name_after: String::new(),
+            path_after: PathBuf::from("/archive").join(path.trim_start_matches('/')),
+            kind: OpKind::Move,
+            size_bytes: None,
+            warnings: vec![],
+            conflicts,
+            children: vec![],
+            rule_id: None,
+            tags: Vec::new(),
+        };
+        let clean = node("/data/2019_report", vec![]);
+        let conflicting = node("/data/2019_photos", vec![Conflict::SourceMissing]);
+        let other = node("/data/2020_report", vec![]);
+        let plan = MovePlan {
+            roots: vec![clean.id, conflicting.id, other.id],
+            nodes: [&clean, &conflicting, &other].into_iter().map(|node| (node.id, node.clone())).collect(),
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+        };
+        
+        let filter = ApplyOptions {
+            path: Some("/data/2019*".to_string()),
+            only_conflict_free: true,
+            ..Default::default()
+        };
+        let (selected, left_out) = apply_filter(&plan, &filter, &[]).unwrap();
+        assert_eq!(selected.roots, vec![clean.id]);
+        assert_eq!(left_out.len(), 2);
+        assert!(left_out.iter().all(|entry| entry.result == filemover_types::ResultKind::Skip));
+        assert!(left_out.iter().any(|entry| entry.source == conflicting.path_before));
+    }
+    
+    #[test]
+    fn test_planned_node_without_conflicts_is_conflict_free() {
+        use filemover_planner::MovePlanner;
+        use filemover_types::{FolderHit, PatternSpec, PlanOptions, Rule};
+
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("data").join("2019_report");
+        std::fs::create_dir_all(&source).unwrap();
+        let rule = Rule::new(PatternSpec::new_glob("2019*"), temp_dir.path().join("archive"), "{name}".to_string());
+        let hit = FolderHit {
+            path: source.clone(),
+            name: "2019_report".to_string(),
+            matched_rule: Some(rule.id),
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: Vec::new(),
+            size_bytes: Some(0),
+            item_count: None,
+            is_dir: true,
+        };
+        let plan = MovePlanner::new().create_plan(&[hit], &[rule], PlanOptions::default()).unwrap();
+
+        let filter = ApplyOptions { only_conflict_free: true, ..Default::default() };
+        let (selected, left_out) = apply_filter(&plan, &filter, &[]).unwrap();
+        assert_eq!(selected.roots, plan.roots);
+        assert!(left_out.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_limit_batch() {
+        let node = |path: &str, size: u64| PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: String::new(),
+            path_before: PathBuf::from(path),
+            name_after: String::new(),
+            path_after: PathBuf::from("/archive").join(path.trim_start_matches('/')),
+            kind: OpKind::Move,
+            size_bytes: Some(size),
+            warnings: vec![],
+            conflicts: vec![],
+            children: vec![],
+            rule_id: None,
+            tags: Vec::new(),
+        };
+        let nodes = [node("/data/a", 600), node("/data/b", 300), node("/data/c", 300)];
+        let plan = MovePlan {
+            roots: nodes.iter().map(|node| node.id).collect(),
+            nodes: nodes.iter().map(|node| (node.id, node.clone())).collect(),
+            summary: PlanSummary::default(),
+            execution_order: nodes.iter().map(|node| node.id).collect(),
+        };
+        
+        let (batch, rest) = limit_batch(&plan, None, Some(1000));
+        assert_eq!(batch.roots, vec![nodes[0].id, nodes[1].id]);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].source, PathBuf::from("/data/c"));
+        assert_eq!(rest[0].message.as_deref(), Some(BATCH_LIMIT_MESSAGE));
+        
+        // The batch journal tells the next batch what is left
+        let temp_dir = TempDir::new().unwrap();
+        let journal_path = temp_dir.path().join("batch.jsonl");
+        execute_plan_stub(&batch, &rest, &journal_path, JournalFormat::Jsonl, ExecuteOptions::default(), &CancellationToken::new(), &ProgressBar::hidden()).await.unwrap();
+        assert_eq!(remaining_batch_sources(&journal_path).unwrap(), HashSet::from([PathBuf::from("/data/c")]));
+        
+        // The first operation runs even when it is larger than the limit
+        let (batch, rest) = limit_batch(&plan, Some(2), Some(100));
+        assert_eq!(batch.roots, vec![nodes[0].id]);
+        assert_eq!(rest.len(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_execute_plan_stub_error_policies() {
+        let temp_dir = TempDir::new().unwrap();
+        let sources = ["first", "missing", "last"].map(|name| temp_dir.path().join(name));
+        std::fs::create_dir_all(&sources[0]).unwrap();
+        std::fs::create_dir_all(&sources[2]).unwrap();
+        let nodes: Vec<PlanNode> = sources.iter().map(|source| PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: String::new(),
+            path_before: source.clone(),
+            name_after: String::new(),
+            path_after: temp_dir.path().join("archive").join(source.file_name().unwrap()),
+            kind: OpKind::Move,
+            size_bytes: None,
+            warnings: vec![],
+            conflicts: vec![],
+            children: vec![],
+            rule_id: None,
+            tags: Vec::new(),
+        }).collect();
+        let plan = MovePlan {
+            roots: nodes.iter().map(|node| node.id).collect(),
+            nodes: nodes.iter().map(|node| (node.id, node.clone())).collect(),
+            summary: PlanSummary::default(),
+            execution_order: nodes.iter().map(|node| node.id).collect(),
+        };
+        
+        let run = |options: ExecuteOptions, name: &str| {
+            let journal_path = temp_dir.path().join(name);
+            let plan = plan.clone();
+            async move {
+                let outcome = execute_plan_stub(&plan, &[], &journal_path, JournalFormat::Jsonl, options, &CancellationToken::new(), &ProgressBar::hidden())
+                    .await
+                    .unwrap();
+                (outcome, journal_path)
+            }
+        };
+        
+        let policy = |on_error| ExecuteOptions { on_error, dry_run: false, plan_hash: None };
+        let (outcome, _) = run(policy(ErrorPolicy::Continue), "continue.jsonl").await;
+        assert_eq!(outcome, ExecutionOutcome { executed: 2, failed: 1, stopped: false, ..Default::default() });
+        
+        let (outcome, _) = run(policy(ErrorPolicy::Stop), "stop.jsonl").await;
+        assert_eq!(outcome, ExecutionOutcome { executed: 1, failed: 1, stopped: true, ..Default::default() });
+        
+        // The stub moves nothing, so there is nothing at the destination to move back
+        let (outcome, journal_path) = run(policy(ErrorPolicy::RollbackBatch), "rollback.jsonl").await;
+        assert_eq!(outcome, ExecutionOutcome { executed: 1, failed: 1, rolled_back: 0, rollback_failed: 1, stopped: true });
+        let undo_entries = load_checked_journal(&filemover_journal::undo_journal_path(&journal_path)).unwrap().entries;
+        assert_eq!(undo_entries[0].result, ResultKind::Failed);
+        
+        // A dry run takes the same path and says so in its journals
+        let options = ExecuteOptions { on_error: ErrorPolicy::RollbackBatch, dry_run: true, plan_hash: None };
+        let (outcome, journal_path) = run(options, "dry_run.jsonl").await;
+        assert_eq!(outcome, ExecutionOutcome { executed: 1, failed: 1, rolled_back: 1, rollback_failed: 0, stopped: true });
+        let entries = load_checked_journal(&journal_path).unwrap().entries;
+        assert_eq!(entries[0].message.as_deref(), Some(SIMULATED_MESSAGE));
+        let undo_entries = load_checked_journal(&filemover_journal::undo_journal_path(&journal_path)).unwrap().entries;
+        assert!(undo_entries[0].message.as_deref().unwrap().starts_with("Simulated"));
+    }
+    
+    #[test]
+    fn test_rollback_batch_moves_completed_operations_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = temp_dir.path().join("archive");
+        let (moved, gone) = (temp_dir.path().join("moved"), temp_dir.path().join("gone"));
+        std::fs::create_dir_all(&archive).unwrap();
+        std::fs::create_dir_all(archive.join("moved")).unwrap();
+        std::fs::write(archive.join("moved").join("a.txt"), "alpha").unwrap();
+        let completed = vec![
+            JournalEntry::new(moved.clone(), archive.join("moved"), OpKind::Move),
+            // Removed from the destination after it was moved there
+            JournalEntry::new(gone.clone(), archive.join("gone"), OpKind::Move),
+        ];
+        let journal_path = temp_dir.path().join("batch.jsonl");
+        
+        let (rolled_back, failed) = rollback_batch(&completed, &journal_path, JournalFormat::Jsonl, false).unwrap();
+        assert_eq!((rolled_back, failed), (1, 1));
+        assert_eq!(std::fs::read_to_string(moved.join("a.txt")).unwrap(), "alpha");
+        assert!(!archive.join("moved").exists());
+        assert!(!gone.exists());
+        
+        // Newest first, with the real outcome of each
+        let undo_entries = load_checked_journal(&filemover_journal::undo_journal_path(&journal_path)).unwrap().entries;
+        assert_eq!(undo_entries[0].source, archive.join("gone"));
+        assert_eq!(undo_entries[0].result, ResultKind::Failed);
+        assert_eq!(undo_entries[1].result, ResultKind::Ok);
+    }
+    
     #[tokio::test]
     async fn test_execute_plan_stub() {
         let temp_dir = TempDir::new().unwrap();
@@ -348,6 +1077,7 @@ mod tests {
             conflicts: vec![],
             children: vec![],
             rule_id: None,
+            tags: Vec::new(),
         };
         nodes.insert(node_id, node);
         
@@ -355,9 +1085,10 @@ mod tests {
             roots: vec![node_id],
             nodes,
             summary: PlanSummary::default(),
+            execution_order: Vec::new(),
         };
         
-        let result = execute_plan_stub(&plan, &journal_path).await;
+        let result = execute_plan_stub(&plan, &[], &journal_path, JournalFormat::Jsonl, ExecuteOptions::default(), &CancellationToken::new(), &ProgressBar::hidden()).await;
         assert!(result.is_ok());
         
         // Check that journal file was created
@@ -366,4 +1097,47 @@ mod tests {
         let journal_content = std::fs::read_to_string(&journal_path).unwrap();
         assert!(!journal_content.is_empty());
     }
+    
+    #[tokio::test]
+    async fn test_execute_plan_stub_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal_path = temp_dir.path().join("test_journal.jsonl");
+        
+        let mut nodes = HashMap::new();
+        let node_id = PlanNodeId::new();
+        let node = PlanNode {
+            id: node_id,
+            is_dir: true,
+            name_before: "test".to_string(),
+            path_before: PathBuf::from("C:\\Test"),
+            name_after: "test".to_string(),
+            path_after: PathBuf::from("D:\\Archive\\test"),
+            kind: OpKind::Move,
+            size_bytes: None,
+            warnings: vec![],
+            conflicts: vec![],
+            children: vec![],
+            rule_id: None,
+            tags: Vec::new(),
+        };
+        nodes.insert(node_id, node);
+        
+        let plan = MovePlan {
+            roots: vec![node_id],
+            nodes,
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+        };
+        
+        let token = CancellationToken::new();
+        token.cancel();
+        
+        let result = execute_plan_stub(&plan, &[], &journal_path, JournalFormat::Jsonl, ExecuteOptions::default(), &token, &ProgressBar::hidden()).await;
+        assert!(result.is_ok());
+        
+        // No operation should have been journaled, but the journal is still closed cleanly
+        let report = filemover_journal::open_journal(&journal_path).unwrap().read_checked().unwrap();
+        assert!(report.entries.is_empty());
+        assert_eq!(report.summary.map(|s| s.entry_count), Some(0));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use serde::Serialize;
+use tracing::info;
+
+use filemover_journal::PruneEmptyReport;
+use crate::config_manager::ConfigManager;
+use crate::error::CliError;
+use super::undo::{load_checked_journal, print_journal_problems};
+
+/// Remove source folders left empty by the operations in a journal
+pub async fn clean_empty_command(
+    journal_file: PathBuf,
+    roots: Vec<PathBuf>,
+    profile: Option<String>,
+    dry_run: bool,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    info!("Removing empty source folders");
+
+    if !journal_file.exists() {
+        return Err(CliError::file_operation(journal_file, "Journal file does not exist").into());
+    }
+
+    // Only folders inside the scan roots are touched
+    let roots = if roots.is_empty() {
+        config_manager.load_config(profile.as_deref())?.roots
+    } else {
+        roots
+    };
+    if roots.is_empty() {
+        return Err(CliError::invalid_args(
+            "No roots to stay inside - pass --root or configure roots in the profile"
+        ).into());
+    }
+
+    let report = prune_journal_sources(&journal_file, &roots, dry_run)?;
+
+    crate::output::emit_result("clean-empty", &CleanEmptyResult {
+        journal: journal_file,
+        dry_run,
+        removed: report.removed,
+        skipped: report.skipped.len(),
+    })
+}
+
+/// Result of `clean-empty` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct CleanEmptyResult {
+    journal: PathBuf,
+    dry_run: bool,
+    removed: Vec<PathBuf>,
+    skipped: usize,
+}
+
+/// Prune the empty parents of the journal's moved sources and print what happened
+pub(crate) fn prune_journal_sources(journal_file: &Path, roots: &[PathBuf], dry_run: bool) -> Result<PruneEmptyReport> {
+    let journal = load_checked_journal(journal_file)
+        .context("Failed to load journal file")?;
+    if !journal.is_intact() {
+        print_journal_problems(&journal.problems);
+    }
+
+    let report = filemover_journal::prune_empty_sources(&journal.entries, roots, dry_run)
+        .context("Failed to remove empty folders")?;
+    print_prune_report(&report, dry_run);
+    Ok(report)
+}
+
+fn print_prune_report(report: &PruneEmptyReport, dry_run: bool) {
+    if report.removed.is_empty() {
+        pretty_println!("\nNo empty source folders to remove.");
+    } else {
+        pretty_println!("\n🧹 {} {} empty source folder(s):",
+                        if dry_run { "Would remove" } else { "Removed" }, report.removed.len());
+        for dir in &report.removed {
+            pretty_println!("  - {}", dir.display());
+        }
+    }
+
+    if !report.skipped.is_empty() {
+        pretty_println!("\n⏭️  Left in place:");
+        for (dir, reason) in &report.skipped {
+            pretty_println!("  - {}: {}", dir.display(), reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::{Config, JournalEntry, JournalFormat, OpKind};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_clean_empty_uses_profile_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("data");
+        std::fs::create_dir_all(root.join("2024/q1")).unwrap();
+
+        let journal_file = temp_dir.path().join("run.jsonl");
+        let mut journal = filemover_journal::create_journal(&journal_file, JournalFormat::Jsonl).unwrap();
+        journal.append(&JournalEntry::new(root.join("2024/q1/project"), temp_dir.path().join("archive/project"), OpKind::Move)).unwrap();
+        journal.finish().unwrap();
+
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config/config.json"))).unwrap();
+        config_manager.save_config("work", &Config { roots: vec![root.clone()], ..Default::default() }).unwrap();
+
+        // The default profile has no roots
+        assert!(clean_empty_command(journal_file.clone(), Vec::new(), None, false, &config_manager).await.is_err());
+
+        clean_empty_command(journal_file, Vec::new(), Some("work".to_string()), false, &config_manager).await.unwrap();
+        assert!(!root.join("2024").exists());
+        assert!(root.exists());
+    }
+}
@@ -1,8 +1,15 @@
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
+use serde::Serialize;
 use tracing::info;
 
-use filemover_types::{Config, Rule, PatternSpec, ScanOptions};
-use crate::{ConfigAction, config_manager::ConfigManager};
+use filemover_types::{Config, ProfileBundle, RuleConditions, PatternExpr, PatternSpec};
+use crate::{ConfigAction, config_manager::ConfigManager, error::CliError};
+use super::rules::{check_rules, describe_rule, IssueSeverity};
+
+/// Unchanged lines shown around each change in the edit diff
+const DIFF_CONTEXT_LINES: usize = 2;
+const BUNDLE_EXTENSION: &str = "fmprofile";
 
 pub async fn config_command(
     action: ConfigAction,
@@ -13,6 +20,11 @@ pub async fn config_command(
         ConfigAction::Show { profile } => show_profile(profile, config_manager).await,
         ConfigAction::Create { profile, from } => create_profile(profile, from, config_manager).await,
         ConfigAction::Delete { profile } => delete_profile(profile, config_manager).await,
+        ConfigAction::Edit { profile } => edit_profile(profile, config_manager).await,
+        ConfigAction::Export { profile, output } => export_profile(profile, output, config_manager).await,
+        ConfigAction::Import { file, name, overwrite } => {
+            import_profile(file, name, overwrite, config_manager, prompt_line).await
+        }
     }
 }
 
@@ -23,31 +35,36 @@ async fn list_profiles(config_manager: &ConfigManager) -> Result<()> {
         .context("Failed to list profiles")?;
     
     if profiles.is_empty() {
-        println!("No configuration profiles found.");
-        println!("Create a new profile with: filemover config create <name>");
-        return Ok(());
-    }
-    
-    println!("📋 Available Configuration Profiles:");
-    for (i, profile) in profiles.iter().enumerate() {
-        let is_default = profile == "default";
-        let marker = if is_default { " (default)" } else { "" };
-        println!("  {}. {}{}", i + 1, profile, marker);
+        pretty_println!("No configuration profiles found.");
+        pretty_println!("Create a new profile with: filemover config create <name>");
+    } else {
+        pretty_println!("📋 Available Configuration Profiles:");
+        for (i, profile) in profiles.iter().enumerate() {
+            let is_default = profile == "default";
+            let marker = if is_default { " (default)" } else { "" };
+            pretty_println!("  {}. {}{}", i + 1, profile, marker);
+        }
+        
+        pretty_println!("\nUse 'filemover config show <profile>' to view profile details.");
     }
     
-    println!("\nUse 'filemover config show <profile>' to view profile details.");
-    
-    Ok(())
+    crate::output::emit_result("config", &ConfigListResult { profiles })
+}
+
+/// Result of `config list` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct ConfigListResult {
+    profiles: Vec<String>,
 }
 
 async fn show_profile(profile_name: String, config_manager: &ConfigManager) -> Result<()> {
     info!("Showing profile: {}", profile_name);
     
-    let config = config_manager.load_config(Some(&profile_name))
+    let config = config_manager.load_config_without_packs(Some(&profile_name))
         .with_context(|| format!("Failed to load profile '{}'", profile_name))?;
     
     println!("📄 Profile: {}", profile_name);
-    println!("=" .repeat(50));
+    println!("{}", "=".repeat(50));
     
     // Show roots
     println!("\n📁 Scan Roots ({}):", config.roots.len());
@@ -75,12 +92,60 @@ async fn show_profile(profile_name: String, config_manager: &ConfigManager) -> R
                      format_pattern(&rule.pattern),
                      exclude_marker
             );
-            println!("     → {} / {}", 
-                     rule.dest_root.display(), 
-                     rule.template);
+            if rule.is_tag_only() {
+                println!("     → (tags only)");
+            } else {
+                println!("     → {} / {}", 
+                         rule.dest_root.display(), 
+                         rule.template);
+            }
             if let Some(label) = &rule.label {
                 println!("     Label: {}", label);
             }
+            if rule.normalization.is_some() {
+                println!("     Normalization: rule-specific");
+            }
+            if !rule.tags.is_empty() {
+                println!("     Tags: {}", rule.tags.join(", "));
+            }
+            if !rule.date_formats.is_empty() {
+                println!("     Date formats: {}", rule.date_formats.join(", "));
+            }
+            if let Some(expression) = &rule.expression {
+                println!("     And: {}", format_expression(expression));
+            }
+            if !rule.conditions.is_empty() {
+                println!("     Conditions: {}", format_conditions(&rule.conditions));
+            }
+        }
+    }
+    
+    // Show rule packs
+    if !config.rule_packs.is_empty() {
+        println!("\n📦 Rule Packs ({}):", config.rule_packs.len());
+        match config_manager.load_rule_packs(&config) {
+            Ok(packs) => {
+                for (i, (path, pack)) in config.rule_packs.iter().zip(&packs).enumerate() {
+                    let version = pack.version.as_deref().map(|v| format!(" v{}", v)).unwrap_or_default();
+                    println!("  {}. {} ({}{}, {} rules)",
+                             i + 1,
+                             path.display(),
+                             pack.name.as_deref().unwrap_or("unnamed"),
+                             version,
+                             pack.rules.len());
+                }
+            }
+            Err(e) => println!("  ⚠️  {:#}", e),
+        }
+    }
+    
+    // Show exclude patterns
+    println!("\n🚫 Exclude Patterns ({}):", config.exclude_patterns.len());
+    if config.exclude_patterns.is_empty() {
+        println!("  (none configured)");
+    } else {
+        for (i, pattern) in config.exclude_patterns.iter().enumerate() {
+            println!("  {}. {}", i + 1, format_pattern(pattern));
         }
     }
     
@@ -92,6 +157,9 @@ async fn show_profile(profile_name: String, config_manager: &ConfigManager) -> R
              if config.options.system_protections { "Enabled" } else { "Disabled" });
     println!("  Max depth: {}", 
              config.options.max_depth.map(|d| d.to_string()).unwrap_or("Unlimited".to_string()));
+    println!("  Multiple matches: {:?}{}", 
+             config.options.tie_break,
+             if config.options.collect_all_matches { " (all matches recorded)" } else { "" });
     
     if !config.options.excluded_paths.is_empty() {
         println!("  Excluded paths:");
@@ -100,6 +168,13 @@ async fn show_profile(profile_name: String, config_manager: &ConfigManager) -> R
         }
     }
     
+    if !config.options.excluded_patterns.is_empty() {
+        println!("  Excluded patterns:");
+        for pattern in &config.options.excluded_patterns {
+            println!("    - {}", pattern);
+        }
+    }
+    
     // Show normalization options
     println!("\n🔤 Text Normalization:");
     println!("  Unicode normalization: {}", 
@@ -110,20 +185,74 @@ async fn show_profile(profile_name: String, config_manager: &ConfigManager) -> R
              if config.options.normalization.strip_diacritics { "Enabled" } else { "Disabled" });
     println!("  Case normalization: {}", 
              if config.options.normalization.normalize_case { "Enabled" } else { "Disabled" });
+    println!("  Half-width kana folding: {}", 
+             if config.options.normalization.fold_halfwidth_kana { "Enabled" } else { "Disabled" });
+    println!("  Hiragana/katakana folding: {}", 
+             if config.options.normalization.fold_hiragana_katakana { "Enabled" } else { "Disabled" });
     
     Ok(())
 }
 
-fn format_pattern(pattern: &PatternSpec) -> String {
+pub(crate) fn format_pattern(pattern: &PatternSpec) -> String {
     let kind_str = match pattern.kind {
         filemover_types::PatternKind::Glob => "Glob",
         filemover_types::PatternKind::Regex => "Regex", 
         filemover_types::PatternKind::Contains => "Contains",
+        filemover_types::PatternKind::Fuzzy => "Fuzzy",
+        filemover_types::PatternKind::Extension => "Extension",
     };
     
     let case_str = if pattern.case_insensitive { " (case-insensitive)" } else { "" };
+    let word_str = if pattern.whole_word { " (whole word)" } else { "" };
+    let target_str = match pattern.match_target {
+        filemover_types::MatchTarget::Name => "",
+        filemover_types::MatchTarget::FullPath => " [full path]",
+        filemover_types::MatchTarget::RelativePathFromRoot => " [relative path]",
+    };
     
-    format!("{}: \"{}\"{}",  kind_str, pattern.value, case_str)
+    format!("{}: \"{}\"{}{}{}",  kind_str, pattern.value, case_str, word_str, target_str)
+}
+
+fn format_expression(expression: &PatternExpr) -> String {
+    let join = |exprs: &[PatternExpr], op: &str| {
+        let parts: Vec<String> = exprs.iter().map(format_expression).collect();
+        format!("({})", parts.join(op))
+    };
+    match expression {
+        PatternExpr::Pattern(pattern) => format_pattern(pattern),
+        PatternExpr::All(exprs) => join(exprs, " AND "),
+        PatternExpr::Any(exprs) => join(exprs, " OR "),
+        PatternExpr::Not(expr) => format!("NOT {}", format_expression(expr)),
+    }
+}
+
+fn format_conditions(conditions: &RuleConditions) -> String {
+    let mut parts = Vec::new();
+    if let Some(min) = conditions.min_size_bytes {
+        parts.push(format!("size >= {} bytes", min));
+    }
+    if let Some(max) = conditions.max_size_bytes {
+        parts.push(format!("size <= {} bytes", max));
+    }
+    if let Some(before) = conditions.last_modified_before {
+        parts.push(format!("modified before {}", before.format("%Y-%m-%d")));
+    }
+    if let Some(days) = conditions.older_than_days {
+        parts.push(format!("unmodified for {}+ days", days));
+    }
+    if let Some(min) = conditions.min_item_count {
+        parts.push(format!("items >= {}", min));
+    }
+    if let Some(max) = conditions.max_item_count {
+        parts.push(format!("items <= {}", max));
+    }
+    if let Some(files) = &conditions.contains_files {
+        parts.push(format!(
+            "contains >= {} \"{}\" (depth {})",
+            files.min_count, files.pattern, files.max_depth
+        ));
+    }
+    parts.join(", ")
 }
 
 async fn create_profile(
@@ -141,7 +270,7 @@ async fn create_profile(
     // Load base configuration
     let base_config = if let Some(from) = from_profile {
         println!("📋 Copying from profile: {}", from);
-        config_manager.load_config(Some(&from))
+        config_manager.load_config_without_packs(Some(&from))
             .with_context(|| format!("Failed to load base profile '{}'", from))?
     } else {
         println!("📋 Creating new profile with default settings");
@@ -192,11 +321,334 @@ async fn delete_profile(profile_name: String, config_manager: &ConfigManager) ->
     Ok(())
 }
 
+/// Open the profile in `$EDITOR` and save it only once it validates and the change is confirmed
+async fn edit_profile(profile_name: String, config_manager: &ConfigManager) -> Result<()> {
+    info!("Editing profile: {}", profile_name);
+
+    // Fails for unknown profiles (and creates the default one on first use)
+    config_manager.load_config_without_packs(Some(&profile_name))?;
+    let profile_path = config_manager.get_profile_path(&profile_name);
+    let original = std::fs::read_to_string(&profile_path)
+        .with_context(|| format!("Failed to read profile: {}", profile_path.display()))?;
+
+    // Edit a copy so an invalid profile never replaces the working one
+    let edit_path = std::env::temp_dir().join(format!("filemover-{}-{}.json", profile_name, std::process::id()));
+    std::fs::write(&edit_path, &original)
+        .with_context(|| format!("Failed to create {}", edit_path.display()))?;
+    let result = edit_until_valid(&profile_name, &original, &edit_path, config_manager);
+    let _ = std::fs::remove_file(&edit_path);
+    result
+}
+
+fn edit_until_valid(
+    profile_name: &str,
+    original: &str,
+    edit_path: &Path,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    loop {
+        launch_editor(edit_path)?;
+        let edited = std::fs::read_to_string(edit_path)
+            .with_context(|| format!("Failed to read {}", edit_path.display()))?;
+        if edited == original {
+            println!("No changes made to profile '{}'.", profile_name);
+            return Ok(());
+        }
+
+        let (config, warnings) = match validate_profile(&edited) {
+            Ok(validated) => validated,
+            Err(errors) => {
+                println!("❌ The edited profile is invalid:");
+                for error in &errors {
+                    println!("  - {}", error);
+                }
+                if confirm("Edit again? (Y/n): ", true)? {
+                    continue;
+                }
+                return Err(CliError::config(format!("Profile '{}' was not saved ({} errors)", profile_name, errors.len())).into());
+            }
+        };
+
+        for warning in &warnings {
+            println!("⚠️  {}", warning);
+        }
+
+        // Saved in the canonical layout, so that is what the diff shows
+        let updated = serde_json::to_string_pretty(&config)
+            .context("Failed to serialize configuration")?;
+        let diff = diff_lines(original, &updated);
+        if diff.is_empty() {
+            println!("Only formatting changed; profile '{}' left as is.", profile_name);
+            return Ok(());
+        }
+        println!("\n📝 Changes to profile '{}':", profile_name);
+        for line in &diff {
+            println!("{}", line);
+        }
+        println!();
+
+        if !confirm("Save these changes? (y/N): ", false)? {
+            println!("Changes discarded.");
+            return Ok(());
+        }
+        config_manager.save_config(profile_name, &config)
+            .with_context(|| format!("Failed to save profile '{}'", profile_name))?;
+        println!("✅ Profile '{}' saved", profile_name);
+        return Ok(());
+    }
+}
+
+/// Run `$VISUAL` / `$EDITOR` (which may include arguments, e.g. `code --wait`) and wait for it to exit
+fn launch_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL").ok()
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad".to_string() } else { "vi".to_string() });
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{}'", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(())
+}
+
+fn confirm(prompt: &str, default: bool) -> Result<bool> {
+    let input = prompt_line(prompt)?;
+    Ok(match input.trim().to_lowercase().as_str() {
+        "" => default,
+        answer => answer == "y" || answer == "yes",
+    })
+}
+
+/// Check edited profile JSON against the configuration types and the rule lint.
+/// Returns the parsed profile and its warnings, or every error found.
+fn validate_profile(text: &str) -> std::result::Result<(Config, Vec<String>), Vec<String>> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| vec![format!("Invalid JSON: {}", e)])?;
+    // Wrong types and unknown pattern kinds or policies
+    let config: Config = serde_json::from_str(text)
+        .map_err(|e| vec![e.to_string()])?;
+
+    let mut errors = Vec::new();
+    let known = serde_json::to_value(&config)
+        .map_err(|e| vec![e.to_string()])?;
+    for field in unknown_fields(&value, &known, "") {
+        errors.push(format!("Unknown field `{}`", field));
+    }
+
+    let mut warnings = Vec::new();
+    for issue in check_rules(&config.rules, &config.options.normalization) {
+        let message = format!("Rule #{} {} {}", issue.rule_index + 1, describe_rule(&config.rules[issue.rule_index]), issue.message);
+        match issue.severity {
+            IssueSeverity::Error => errors.push(message),
+            IssueSeverity::Warning => warnings.push(message),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok((config, warnings))
+    } else {
+        Err(errors)
+    }
+}
+
+/// Fields of `value` that did not survive a round trip through the typed configuration
+/// (serde silently ignores them, so a misspelt option would otherwise be lost)
+fn unknown_fields(value: &serde_json::Value, known: &serde_json::Value, path: &str) -> Vec<String> {
+    use serde_json::Value;
+
+    match (value, known) {
+        (Value::Object(fields), Value::Object(known_fields)) => fields.iter()
+            // Optional fields that are left out when empty may be written as null
+            .filter(|(_, value)| !value.is_null())
+            .flat_map(|(key, value)| {
+                let field_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                match known_fields.get(key) {
+                    Some(known) => unknown_fields(value, known, &field_path),
+                    None => vec![field_path],
+                }
+            })
+            .collect(),
+        (Value::Array(items), Value::Array(known_items)) => items.iter()
+            .zip(known_items)
+            .enumerate()
+            .flat_map(|(i, (item, known))| unknown_fields(item, known, &format!("{}[{}]", path, i)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Line diff (`-` removed, `+` added) with a few unchanged lines around each change
+fn diff_lines(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence, filled from the end
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', old[i]));
+            i += 1;
+        } else {
+            ops.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = ops.iter().enumerate()
+        .filter(|(_, (op, _))| *op != ' ')
+        .map(|(index, _)| index)
+        .collect();
+    let mut lines = Vec::new();
+    let mut last_shown: Option<usize> = None;
+    for (index, (op, text)) in ops.iter().enumerate() {
+        let near_change = changed.iter()
+            .any(|&c| index + DIFF_CONTEXT_LINES >= c && index <= c + DIFF_CONTEXT_LINES);
+        if !near_change {
+            continue;
+        }
+        if last_shown.is_some_and(|last| index > last + 1) {
+            lines.push("  ...".to_string());
+        }
+        lines.push(format!("{} {}", op, text));
+        last_shown = Some(index);
+    }
+    lines
+}
+
+async fn export_profile(
+    profile_name: String,
+    output: Option<PathBuf>,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    info!("Exporting profile: {}", profile_name);
+
+    // Rule packs stay references; they are shared on their own
+    let config = config_manager.load_config_without_packs(Some(&profile_name))?;
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("{}.{}", profile_name, BUNDLE_EXTENSION)));
+    let bundle = ProfileBundle {
+        name: profile_name.clone(),
+        exported_utc: Some(chrono::Utc::now()),
+        config,
+    };
+    let json = filemover_types::to_versioned_json(&bundle, true)?;
+    std::fs::write(&output, json)
+        .with_context(|| format!("Failed to write bundle: {}", output.display()))?;
+
+    println!("📦 Exported profile '{}' to {}", profile_name, output.display());
+    println!("   {} rules, {} exclude patterns, {} roots",
+             bundle.config.rules.len(), bundle.config.exclude_patterns.len(), bundle.config.roots.len());
+    Ok(())
+}
+
+/// Import a bundle (or a plain profile file), asking through `ask` when the name is taken
+async fn import_profile<F>(
+    file: PathBuf,
+    name: Option<String>,
+    overwrite: bool,
+    config_manager: &ConfigManager,
+    mut ask: F,
+) -> Result<()>
+where
+    F: FnMut(&str) -> Result<String>,
+{
+    info!("Importing profile from: {}", file.display());
+
+    let content = std::fs::read_to_string(&file)
+        .map_err(|e| CliError::file_operation(file.clone(), e.to_string()))?;
+    let mut bundle: ProfileBundle = filemover_types::from_versioned_json(&content)
+        .with_context(|| format!("Failed to read profile bundle: {}", file.display()))?;
+    let migrated = bundle.config.migrate_exclude_rules();
+    if migrated > 0 {
+        info!("Moved {} exclude rules to exclude patterns", migrated);
+    }
+
+    let mut profile_name = name
+        .or_else(|| Some(bundle.name.clone()).filter(|name| !name.is_empty()))
+        .or_else(|| file.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .unwrap_or_default();
+    loop {
+        if !is_valid_profile_name(&profile_name) {
+            return Err(CliError::invalid_args(format!("'{}' is not a valid profile name", profile_name)).into());
+        }
+        if overwrite || !config_manager.profile_exists(&profile_name)? {
+            break;
+        }
+
+        let answer = ask(&format!("⚠️  Profile '{}' already exists. [o]verwrite, [r]ename or [c]ancel? (c): ", profile_name))?;
+        match answer.trim().to_lowercase().as_str() {
+            "o" | "overwrite" => break,
+            "r" | "rename" => profile_name = ask("New profile name: ")?.trim().to_string(),
+            _ => {
+                println!("Import cancelled.");
+                return Ok(());
+            }
+        }
+    }
+
+    config_manager.save_config(&profile_name, &bundle.config)
+        .with_context(|| format!("Failed to save profile '{}'", profile_name))?;
+    println!("✅ Imported profile '{}' ({} rules, {} exclude patterns)",
+             profile_name, bundle.config.rules.len(), bundle.config.exclude_patterns.len());
+
+    // Destinations and roots usually differ between machines
+    let issues = check_rules(&bundle.config.rules, &bundle.config.options.normalization);
+    for issue in &issues {
+        println!("⚠️  Rule #{} {} {}", issue.rule_index + 1, describe_rule(&bundle.config.rules[issue.rule_index]), issue.message);
+    }
+    let missing_roots: Vec<&PathBuf> = bundle.config.roots.iter().filter(|root| !root.is_dir()).collect();
+    for root in &missing_roots {
+        println!("⚠️  Root {} does not exist on this machine", root.display());
+    }
+    if !issues.is_empty() || !missing_roots.is_empty() {
+        println!("Adjust the profile with: filemover config edit {}", profile_name);
+    }
+    Ok(())
+}
+
+/// Profile names become file names in the config directory
+fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "config"
+        && !name.chars().any(|c| matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+}
+
+fn prompt_line(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    std::io::Write::flush(&mut std::io::stdout())?;
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
-    use std::path::PathBuf;
+    use filemover_types::Rule;
 
     #[tokio::test]
     async fn test_list_empty_profiles() {
@@ -260,4 +712,104 @@ mod tests {
         assert!(formatted2.contains("Regex"));
         assert!(formatted2.contains("case-insensitive"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_format_expression() {
+        let expression = PatternExpr::All(vec![
+            PatternSpec::new_glob("*backup*").into(),
+            PatternExpr::Not(Box::new(PatternSpec::new_contains("keep").into())),
+        ]);
+        assert_eq!(
+            format_expression(&expression),
+            "(Glob: \"*backup*\" (case-insensitive) AND NOT Contains: \"keep\" (case-insensitive))"
+        );
+    }
+
+    #[test]
+    fn test_format_conditions() {
+        let conditions = RuleConditions {
+            min_size_bytes: Some(5 * 1024 * 1024 * 1024),
+            older_than_days: Some(365),
+            min_item_count: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_conditions(&conditions),
+            "size >= 5368709120 bytes, unmodified for 365+ days, items >= 1"
+        );
+    }
+
+    #[test]
+    fn test_validate_profile_reports_unknown_fields() {
+        let mut value = serde_json::to_value(Config::default()).unwrap();
+        value["optons"] = serde_json::json!({});
+        value["options"]["max_dpth"] = serde_json::json!(3);
+        let errors = validate_profile(&value.to_string()).unwrap_err();
+        assert_eq!(errors, vec!["Unknown field `options.max_dpth`", "Unknown field `optons`"]);
+    }
+
+    #[test]
+    fn test_validate_profile_checks_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.rules.push(Rule::new(PatternSpec::new_glob("*"), temp_dir.path().to_path_buf(), "{unknown}".to_string()));
+        let errors = validate_profile(&serde_json::to_string(&config).unwrap()).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("invalid template"));
+
+        let mut value = serde_json::to_value(&config).unwrap();
+        value["rules"][0]["pattern"]["kind"] = serde_json::json!("Globb");
+        let errors = validate_profile(&value.to_string()).unwrap_err();
+        assert!(errors[0].contains("unknown variant `Globb`"));
+
+        config.rules[0].template = "{name}".to_string();
+        assert!(validate_profile(&serde_json::to_string(&config).unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj";
+        assert_eq!(
+            diff_lines(old, new),
+            vec!["  a", "- b", "+ B", "  c", "  d", "  ...", "  h", "  i", "+ j"]
+        );
+        assert!(diff_lines(old, old).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_and_import_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config/config.json"))).unwrap();
+        let mut config = Config::default();
+        config.rules.push(Rule::new(PatternSpec::new_glob("project_*"), temp_dir.path().to_path_buf(), "{name}".to_string()));
+        config.exclude_patterns.push(PatternSpec::new_glob("node_modules"));
+        config_manager.save_config("work", &config).unwrap();
+
+        let bundle = temp_dir.path().join("work.fmprofile");
+        export_profile("work".to_string(), Some(bundle.clone()), &config_manager).await.unwrap();
+
+        let no_prompt = |_: &str| -> Result<String> { panic!("unexpected prompt") };
+        import_profile(bundle.clone(), Some("shared".to_string()), false, &config_manager, no_prompt).await.unwrap();
+        assert_eq!(config_manager.load_config(Some("shared")).unwrap(), config);
+
+        // The bundle's own name is taken, so the import is renamed
+        let mut answers = vec!["r".to_string(), "work_copy".to_string()].into_iter();
+        import_profile(bundle, None, false, &config_manager, |_| Ok(answers.next().unwrap())).await.unwrap();
+        assert_eq!(config_manager.load_config(Some("work_copy")).unwrap(), config);
+    }
+
+    #[tokio::test]
+    async fn test_import_plain_profile_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config/config.json"))).unwrap();
+        let file = temp_dir.path().join("colleague.json");
+        std::fs::write(&file, serde_json::to_string(&Config::default()).unwrap()).unwrap();
+
+        import_profile(file.clone(), None, false, &config_manager, |_| Ok("c".to_string())).await.unwrap();
+        assert!(config_manager.profile_exists("colleague").unwrap());
+
+        let result = import_profile(file, Some("../escape".to_string()), false, &config_manager, |_| Ok("c".to_string())).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,435 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{Result, Context};
+use indicatif::{ProgressBar, ProgressDrawTarget};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use filemover_types::{CancellationToken, JournalFormat};
+use filemover_scanner::{FolderScanner, ScanEvent};
+use filemover_planner::PlanValidator;
+use crate::DaemonAction;
+use crate::config_manager::ConfigManager;
+use crate::ipc::{daemon_endpoint, send_request, serve, DaemonRequest, DaemonResponse, JobKind, JobState, JobStatus};
+use super::apply::{execute_plan_stub, resolve_journal_path, ExecuteOptions, skip_missing_sources, validate_plan_for_execution};
+use super::plan::load_move_plan;
+use super::scan::format_scan_progress;
+use super::scan_output::{write_scan_results, ScanOutputFormat};
+
+pub async fn daemon_command(action: Option<DaemonAction>, config_manager: &ConfigManager) -> Result<()> {
+    let endpoint = daemon_endpoint(config_manager);
+    let request = match action {
+        None => return run_daemon(config_manager.clone()).await,
+        Some(DaemonAction::Scan { roots, profile, output }) => {
+            let output = match output {
+                Some(output) => output,
+                None => {
+                    let output_dir = config_manager.load_config(profile.as_deref())?.scan_output_dir;
+                    let file_name = format!("scan_results_{}.json", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+                    super::artifact_path(output_dir.as_deref(), file_name)?
+                }
+            };
+            DaemonRequest::Scan {
+                roots: roots.iter().map(std::path::absolute).collect::<std::io::Result<_>>()?,
+                profile,
+                output: std::path::absolute(output)?,
+            }
+        }
+        Some(DaemonAction::Apply { plan, profile, journal, journal_format }) => DaemonRequest::Apply {
+            plan: std::path::absolute(plan)?,
+            profile,
+            journal: journal.map(std::path::absolute).transpose()?,
+            journal_format,
+        },
+        Some(DaemonAction::Stop) => DaemonRequest::Shutdown,
+    };
+
+    match send_request(&endpoint, &request).await? {
+        DaemonResponse::Started { id } => {
+            println!("🚀 Started job #{} in the daemon", id);
+            println!("   Follow it with `filemover status`, cancel it with `filemover status --cancel {}`", id);
+        }
+        DaemonResponse::Ok => println!("Daemon is shutting down"),
+        DaemonResponse::Error { message } => anyhow::bail!("{}", message),
+        DaemonResponse::Jobs { .. } => anyhow::bail!("Unexpected response from the daemon"),
+    }
+    Ok(())
+}
+
+pub async fn status_command(cancel: Option<u64>, config_manager: &ConfigManager) -> Result<()> {
+    let endpoint = daemon_endpoint(config_manager);
+
+    if let Some(id) = cancel {
+        return match send_request(&endpoint, &DaemonRequest::Cancel { id }).await? {
+            DaemonResponse::Ok => {
+                println!("Cancellation requested for job #{}", id);
+                Ok(())
+            }
+            DaemonResponse::Error { message } => anyhow::bail!("{}", message),
+            _ => anyhow::bail!("Unexpected response from the daemon"),
+        };
+    }
+
+    let jobs = match send_request(&endpoint, &DaemonRequest::Status).await? {
+        DaemonResponse::Jobs { jobs } => jobs,
+        DaemonResponse::Error { message } => anyhow::bail!("{}", message),
+        _ => anyhow::bail!("Unexpected response from the daemon"),
+    };
+
+    if jobs.is_empty() {
+        println!("The daemon has no jobs.");
+        return Ok(());
+    }
+    println!("{:<5} {:<6} {:<10} {:<16} JOB", "ID", "KIND", "STATE", "PROGRESS");
+    for job in &jobs {
+        println!("{}", format_job(job));
+        if !job.message.is_empty() && job.state == JobState::Running {
+            println!("      {}", job.message);
+        }
+        if let JobState::Failed { message } = &job.state {
+            println!("      {}", message);
+        }
+    }
+    Ok(())
+}
+
+fn format_job(job: &JobStatus) -> String {
+    let kind = match job.kind {
+        JobKind::Scan => "scan",
+        JobKind::Apply => "apply",
+    };
+    let state = match job.state {
+        JobState::Running => "running",
+        JobState::Completed => "completed",
+        JobState::Cancelled => "cancelled",
+        JobState::Failed { .. } => "failed",
+    };
+    let progress = match job.total {
+        Some(total) if total > 0 => format!("{}/{} ({:.0}%)", job.done, total, job.done as f64 / total as f64 * 100.0),
+        Some(_) => "-".to_string(),
+        None => format!("{} folders", job.done),
+    };
+    format!("#{:<4} {:<6} {:<10} {:<16} {}", job.id, kind, state, progress, job.description)
+}
+
+/// A job started through the daemon
+struct Job {
+    status: JobStatus,
+    cancel: CancellationToken,
+    /// Hidden bar the job reports its progress on
+    progress: ProgressBar,
+    /// Task running the job, awaited when the daemon shuts down
+    task: Option<JoinHandle<()>>,
+}
+
+/// How long a shutdown waits for cancelled jobs to reach a safe point
+const JOB_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct DaemonState {
+    config_manager: ConfigManager,
+    jobs: Mutex<Vec<Job>>,
+    next_id: AtomicU64,
+    shutdown: Arc<Notify>,
+}
+
+impl DaemonState {
+    fn new(config_manager: ConfigManager) -> Self {
+        Self {
+            config_manager,
+            jobs: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+            shutdown: Arc::new(Notify::new()),
+        }
+    }
+
+    fn start_job(&self, kind: JobKind, description: String) -> (u64, CancellationToken, ProgressBar) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let cancel = CancellationToken::new();
+        // Scans do not know their total up front
+        let length = match kind {
+            JobKind::Scan => None,
+            JobKind::Apply => Some(0),
+        };
+        let progress = ProgressBar::with_draw_target(length, ProgressDrawTarget::hidden());
+
+        self.jobs.lock().unwrap().push(Job {
+            status: JobStatus {
+                id,
+                kind,
+                description,
+                state: JobState::Running,
+                started_utc: chrono::Utc::now(),
+                finished_utc: None,
+                done: 0,
+                total: length,
+                message: String::new(),
+            },
+            cancel: cancel.clone(),
+            progress: progress.clone(),
+            task: None,
+        });
+        (id, cancel, progress)
+    }
+
+    fn attach_task(&self, id: u64, task: JoinHandle<()>) {
+        if let Some(job) = self.jobs.lock().unwrap().iter_mut().find(|job| job.status.id == id) {
+            job.task = Some(task);
+        }
+    }
+
+    fn finish_job(&self, id: u64, result: Result<()>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        let Some(job) = jobs.iter_mut().find(|job| job.status.id == id) else {
+            return;
+        };
+        job.status.state = match result {
+            Ok(()) if job.cancel.is_cancelled() => JobState::Cancelled,
+            Ok(()) => JobState::Completed,
+            Err(e) => JobState::Failed { message: format!("{:#}", e) },
+        };
+        job.status.finished_utc = Some(chrono::Utc::now());
+        info!("Job #{} finished: {:?}", id, job.status.state);
+    }
+
+    fn cancel(&self, id: u64) -> DaemonResponse {
+        let jobs = self.jobs.lock().unwrap();
+        match jobs.iter().find(|job| job.status.id == id) {
+            Some(job) if job.status.state == JobState::Running => {
+                job.cancel.cancel();
+                DaemonResponse::Ok
+            }
+            Some(_) => DaemonResponse::Error { message: format!("Job #{} is not running", id) },
+            None => DaemonResponse::Error { message: format!("No job #{}", id) },
+        }
+    }
+
+    fn snapshot(&self) -> Vec<JobStatus> {
+        self.jobs.lock().unwrap().iter()
+            .map(|job| JobStatus {
+                done: job.progress.position(),
+                total: job.progress.length(),
+                message: job.progress.message(),
+                ..job.status.clone()
+            })
+            .collect()
+    }
+
+    /// Cancel every job and hand back the tasks still to be awaited
+    fn cancel_all(&self) -> Vec<JoinHandle<()>> {
+        self.jobs.lock().unwrap().iter_mut()
+            .filter_map(|job| {
+                job.cancel.cancel();
+                job.task.take()
+            })
+            .collect()
+    }
+}
+
+async fn run_daemon(config_manager: ConfigManager) -> Result<()> {
+    let endpoint = daemon_endpoint(&config_manager);
+    let state = Arc::new(DaemonState::new(config_manager));
+
+    // Ctrl+C stops the daemon as well as `filemover daemon stop`
+    let signal_shutdown = state.shutdown.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            signal_shutdown.notify_one();
+        }
+    });
+
+    println!("🛰️  Daemon listening on {}", endpoint.display());
+    println!("   Submit work with `filemover daemon scan|apply`, follow it with `filemover status`");
+
+    let handler_state = state.clone();
+    let handler = move |request: DaemonRequest| {
+        let state = handler_state.clone();
+        async move { handle_request(state, request) }
+    };
+    serve(&endpoint, handler, state.shutdown.clone()).await?;
+
+    // Running jobs stop between operations, so their journals stay usable for undo
+    let tasks = state.cancel_all();
+    let wait = async {
+        for task in tasks {
+            if let Err(e) = task.await {
+                warn!("Daemon job task failed: {}", e);
+            }
+        }
+    };
+    if tokio::time::timeout(JOB_SHUTDOWN_TIMEOUT, wait).await.is_err() {
+        warn!("Jobs did not stop within {:?}; their journals may be incomplete", JOB_SHUTDOWN_TIMEOUT);
+    }
+    println!("Daemon stopped");
+    Ok(())
+}
+
+fn handle_request(state: Arc<DaemonState>, request: DaemonRequest) -> DaemonResponse {
+    match request {
+        DaemonRequest::Status => DaemonResponse::Jobs { jobs: state.snapshot() },
+        DaemonRequest::Cancel { id } => state.cancel(id),
+        DaemonRequest::Shutdown => {
+            state.shutdown.notify_one();
+            DaemonResponse::Ok
+        }
+        DaemonRequest::Scan { roots, profile, output } => {
+            let description = if roots.is_empty() {
+                format!("profile {}", profile.as_deref().unwrap_or("default"))
+            } else {
+                roots.iter().map(|root| root.display().to_string()).collect::<Vec<_>>().join(", ")
+            };
+            let (id, cancel, progress) = state.start_job(JobKind::Scan, description);
+            let job_state = state.clone();
+            let task = tokio::spawn(async move {
+                let result = run_scan_job(&job_state.config_manager, roots, profile, output, cancel, progress).await;
+                job_state.finish_job(id, result);
+            });
+            state.attach_task(id, task);
+            DaemonResponse::Started { id }
+        }
+        DaemonRequest::Apply { plan, profile, journal, journal_format } => {
+            let (id, cancel, progress) = state.start_job(JobKind::Apply, plan.display().to_string());
+            let job_state = state.clone();
+            let task = tokio::spawn(async move {
+                let result = run_apply_job(&job_state.config_manager, plan, profile, journal, journal_format, cancel, progress).await;
+                job_state.finish_job(id, result);
+            });
+            state.attach_task(id, task);
+            DaemonResponse::Started { id }
+        }
+    }
+}
+
+async fn run_scan_job(
+    config_manager: &ConfigManager,
+    roots: Vec<PathBuf>,
+    profile: Option<String>,
+    output: PathBuf,
+    cancel: CancellationToken,
+    progress: ProgressBar,
+) -> Result<()> {
+    let config = config_manager.load_config(profile.as_deref())?;
+    let roots = if roots.is_empty() { config.roots.clone() } else { roots };
+    if roots.is_empty() {
+        anyhow::bail!("No root directories specified. Pass roots or configure them in the profile.");
+    }
+
+    let scanner = FolderScanner::from_config(&config)
+        .context("Failed to initialize folder scanner")?
+        .with_cancellation(cancel)
+        .with_progress(Arc::new(move |event: &ScanEvent| {
+            if let ScanEvent::Progress(update) = event {
+                progress.set_position(update.dirs_visited);
+                progress.set_message(format_scan_progress(update));
+            }
+        }));
+    let outcome = tokio::task::spawn_blocking(move || scanner.scan(&roots))
+        .await?
+        .context("Failed to scan directories")?;
+
+    // Cancelled scans keep what they found so far
+    write_scan_results(&outcome.hits, &output, ScanOutputFormat::Json)
+        .context("Failed to save scan results")?;
+    info!("Scan results saved to: {}", output.display());
+    Ok(())
+}
+
+async fn run_apply_job(
+    config_manager: &ConfigManager,
+    plan_file: PathBuf,
+    profile: Option<String>,
+    journal_file: Option<PathBuf>,
+    journal_format: JournalFormat,
+    cancel: CancellationToken,
+    progress: ProgressBar,
+) -> Result<()> {
+    let config = config_manager.load_config(profile.as_deref())?;
+    let mut plan = load_move_plan(&plan_file)?;
+    let plan_hash = plan.content_hash();
+    validate_plan_for_execution(&plan)?;
+
+    // Nobody can confirm a changed plan here
+    let delta = PlanValidator::new().revalidate(&mut plan)
+        .context("Failed to revalidate move plan")?;
+    if !delta.affected_nodes.is_empty() {
+        anyhow::bail!("The plan no longer matches the file system - review the changes with `filemover apply`");
+    }
+    skip_missing_sources(&mut plan);
+
+    let journal_path = resolve_journal_path(journal_file, journal_format, &config)?;
+    let options = ExecuteOptions { on_error: config.execution.on_error, dry_run: false, plan_hash: Some(plan_hash) };
+    let outcome = execute_plan_stub(&plan, &[], &journal_path, journal_format, options, &cancel, &progress).await?;
+    super::enforce_journal_retention(&config);
+    if outcome.stopped {
+        anyhow::bail!("Stopped after a failed operation - see {}", journal_path.display());
+    }
+    if outcome.failed > 0 {
+        progress.set_message(format!("{} failed - journal: {}", outcome.failed, journal_path.display()));
+        return Ok(());
+    }
+
+    progress.set_message(format!("Journal: {}", journal_path.display()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_job_lifecycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config.json"))).unwrap();
+        let state = DaemonState::new(config_manager);
+
+        let (scan_id, _, scan_progress) = state.start_job(JobKind::Scan, "C:\\Data".to_string());
+        let (apply_id, apply_cancel, apply_progress) = state.start_job(JobKind::Apply, "plan.json".to_string());
+        scan_progress.set_position(120);
+        apply_progress.set_length(10);
+        apply_progress.set_position(4);
+
+        let jobs = state.snapshot();
+        assert_eq!((jobs[0].done, jobs[0].total), (120, None));
+        assert_eq!((jobs[1].done, jobs[1].total), (4, Some(10)));
+        assert_eq!(format_job(&jobs[1]), format!("#{:<4} apply  running    4/10 (40%)       plan.json", apply_id));
+
+        assert_eq!(state.cancel(apply_id), DaemonResponse::Ok);
+        assert!(apply_cancel.is_cancelled());
+        state.finish_job(apply_id, Ok(()));
+        state.finish_job(scan_id, Err(anyhow::anyhow!("disk gone")));
+
+        let jobs = state.snapshot();
+        assert_eq!(jobs[0].state, JobState::Failed { message: "disk gone".to_string() });
+        assert_eq!(jobs[1].state, JobState::Cancelled);
+        assert!(matches!(state.cancel(apply_id), DaemonResponse::Error { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_apply_job_with_unknown_profile_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config.json"))).unwrap();
+        let state = Arc::new(DaemonState::new(config_manager));
+
+        let response = handle_request(state.clone(), DaemonRequest::Apply {
+            plan: temp_dir.path().join("plan.json"),
+            profile: Some("missing".to_string()),
+            journal: None,
+            journal_format: JournalFormat::Jsonl,
+        });
+        assert_eq!(response, DaemonResponse::Started { id: 1 });
+
+        // Shutdown waits for the job instead of dropping it
+        for task in state.cancel_all() {
+            task.await.unwrap();
+        }
+        let jobs = state.snapshot();
+        let JobState::Failed { message } = &jobs[0].state else {
+            panic!("job did not fail: {:?}", jobs[0].state);
+        };
+        assert!(message.contains("missing"), "{}", message);
+        assert!(jobs[0].finished_utc.is_some());
+    }
+}
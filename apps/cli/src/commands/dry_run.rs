@@ -1,21 +1,36 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
+use serde::Serialize;
 use tracing::{info, warn};
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::ProgressStyle;
 
-use filemover_types::{MovePlan, OpKind};
-use filemover_planner::{MovePlanner, SimulationReport};
+use filemover_types::{exceeds_max_path, MovePlan, OpKind, PlanSummary, Warning, MAX_PATH};
+use filemover_planner::{MovePlanner, SimulationReport, ThroughputModel, ThroughputSource};
 use crate::config_manager::ConfigManager;
+use crate::error::CliError;
+use super::journal::journal_directory;
+use super::undo::load_checked_journal;
+use super::plan::print_preservation_losses;
+
+/// Where the throughput for the duration estimate comes from
+#[derive(Debug, Clone, Default)]
+pub struct ThroughputOptions {
+    /// Write a probe file on the destination volume
+    pub measure: bool,
+    /// Journal with recorded operations; the newest journal is used when not set
+    pub journal: Option<PathBuf>,
+}
 
 pub async fn dry_run_command(
     plan_file: PathBuf,
-    _config_manager: &ConfigManager,
+    throughput_options: ThroughputOptions,
+    config_manager: &ConfigManager,
 ) -> Result<()> {
     info!("Starting dry-run simulation");
     
     // Load move plan
     if !plan_file.exists() {
-        anyhow::bail!("Plan file does not exist: {}", plan_file.display());
+        return Err(CliError::file_operation(plan_file, "Plan file does not exist").into());
     }
     
     let plan = load_move_plan(&plan_file)
@@ -26,7 +41,7 @@ pub async fn dry_run_command(
           plan_file.display());
     
     // Create progress bar
-    let progress = ProgressBar::new_spinner();
+    let progress = crate::progress::new_spinner();
     progress.set_style(
         ProgressStyle::default_spinner()
             .template("{spinner:.green} {msg}")
@@ -34,8 +49,10 @@ pub async fn dry_run_command(
     );
     progress.set_message("Running simulation...");
     
+    let throughput = resolve_throughput(&plan, &throughput_options, config_manager)?;
+    
     // Run simulation
-    let planner = MovePlanner::new();
+    let planner = MovePlanner::new().with_throughput(throughput);
     let simulation = planner.simulate_plan(&plan)
         .context("Failed to run plan simulation")?;
     
@@ -50,85 +67,180 @@ pub async fn dry_run_command(
     // Provide recommendations
     provide_recommendations(&plan, &simulation);
     
+    crate::output::emit_result("dry-run", &DryRunResult {
+        plan: plan_file,
+        summary: plan.summary.clone(),
+        simulation,
+    })?;
+    
     Ok(())
 }
 
+/// Result of `dry-run` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct DryRunResult {
+    plan: PathBuf,
+    summary: PlanSummary,
+    #[serde(flatten)]
+    simulation: SimulationReport,
+}
+
 fn load_move_plan(path: &PathBuf) -> Result<MovePlan> {
     let content = std::fs::read_to_string(path)
         .context("Failed to read move plan file")?;
     
-    let plan: MovePlan = serde_json::from_str(&content)
+    let plan: MovePlan = filemover_types::from_versioned_json(&content)
         .context("Failed to parse move plan JSON")?;
     
     Ok(plan)
 }
 
+fn resolve_throughput(
+    plan: &MovePlan,
+    options: &ThroughputOptions,
+    config_manager: &ConfigManager,
+) -> Result<ThroughputModel> {
+    if options.measure {
+        let Some(dir) = copy_destination(plan) else {
+            info!("No cross-volume operations to measure, using default throughput");
+            return Ok(ThroughputModel::default());
+        };
+        return ThroughputModel::measure(&dir)
+            .with_context(|| format!("Failed to measure throughput in {}", dir.display()));
+    }
+    
+    if let Some(journal) = &options.journal {
+        let report = load_checked_journal(journal)?;
+        return ThroughputModel::from_journal(&report.entries)
+            .with_context(|| format!("Journal has no completed operations to take throughput from: {}", journal.display()));
+    }
+    
+    // Fall back to the newest journal in the configured directory, then to the defaults
+    let config = config_manager.load_config(None).unwrap_or_default();
+    let newest = filemover_journal::list_journals(&journal_directory(&config))
+        .ok()
+        .and_then(|journals| journals.into_iter().next());
+    let recorded = newest.and_then(|info| match load_checked_journal(&info.path) {
+        Ok(report) => ThroughputModel::from_journal(&report.entries),
+        Err(e) => {
+            warn!("Failed to read journal {}: {}", info.path.display(), e);
+            None
+        }
+    });
+    
+    Ok(recorded.unwrap_or_default())
+}
+
+/// Nearest existing directory on the destination of the first cross-volume operation
+fn copy_destination(plan: &MovePlan) -> Option<PathBuf> {
+    let mut copies = plan.nodes.values()
+        .filter(|node| node.kind == OpKind::CopyDelete)
+        .collect::<Vec<_>>();
+    copies.sort_by(|a, b| a.path_before.cmp(&b.path_before));
+    
+    copies.into_iter()
+        .find_map(|node| node.path_after.ancestors().skip(1).find(|dir| dir.is_dir()).map(Path::to_path_buf))
+}
+
+pub(super) fn format_duration(secs: f64) -> String {
+    let secs = secs.ceil() as u64;
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+fn format_mb(bytes: f64) -> String {
+    format!("{:.2} MB", bytes / (1024.0 * 1024.0))
+}
+
 fn print_simulation_results(plan: &MovePlan, simulation: &SimulationReport) {
-    println!("\n=== Dry-Run Simulation Results ===");
+    pretty_println!("\n=== Dry-Run Simulation Results ===");
     
     // Overall statistics
-    println!("📊 Execution Forecast:");
-    println!("  ✅ Expected successful operations: {}", simulation.success_estimate);
-    println!("  ⚠️  Operations with conflicts: {}", simulation.conflicts_remaining);
-    println!("  ⏭️  Operations to skip: {}", simulation.skipped_count);
-    println!("  ⏱️  Estimated duration: {:?}", simulation.estimated_duration);
-    
-    // Operation breakdown
-    let mut op_stats = std::collections::HashMap::new();
-    for node in plan.nodes.values() {
-        *op_stats.entry(&node.kind).or_insert(0) += 1;
+    pretty_println!("📊 Execution Forecast:");
+    pretty_println!("  ✅ Expected successful operations: {}", simulation.success_estimate);
+    pretty_println!("  ⚠️  Operations with conflicts: {}", simulation.conflicts_remaining);
+    pretty_println!("  ⏭️  Operations to skip: {}", simulation.skipped_count);
+    pretty_println!("  ⏱️  Estimated duration: {}", format_duration(simulation.estimated_duration_secs as f64));
+    
+    let source = match simulation.throughput.source {
+        ThroughputSource::Default => "default",
+        ThroughputSource::Measured => "measured",
+        ThroughputSource::Recorded => "recorded",
+    };
+    pretty_println!("     └─ Copy throughput: {}/s ({}), {:.2}s per operation",
+             format_mb(simulation.throughput.copy_bytes_per_sec),
+             source,
+             simulation.throughput.per_item_secs);
+    if simulation.unknown_size_count > 0 {
+        pretty_println!("     └─ {} operations have unknown sizes and only count their fixed cost",
+                 simulation.unknown_size_count);
     }
     
-    println!("\n📋 Operation Types:");
-    for (op_kind, count) in &op_stats {
-        let icon = match op_kind {
+    // Operation breakdown with the time each type is expected to take
+    pretty_println!("\n📋 Operation Types:");
+    for operation in &simulation.by_operation {
+        let icon = match operation.kind {
             OpKind::Move => "📁",
             OpKind::CopyDelete => "📂",
             OpKind::Rename => "✏️",
             OpKind::Skip => "⏭️",
             OpKind::None => "❌",
         };
-        println!("  {} {:?}: {} operations", icon, op_kind, count);
+        pretty_println!("  {} {:?}: {} operations, {}, ~{}",
+                 icon,
+                 operation.kind,
+                 operation.count,
+                 format_mb(operation.bytes as f64),
+                 format_duration(operation.estimated_secs));
+    }
+    if simulation.skipped_count > 0 {
+        pretty_println!("  ⏭️  Skipped or unchanged: {} operations", simulation.skipped_count);
     }
     
     // Data transfer info
-    if let Some(total_bytes) = plan.summary.total_bytes {
-        println!("\n💾 Data Transfer:");
-        println!("  Total size: {} bytes ({:.2} MB)", 
-                 total_bytes, 
-                 total_bytes as f64 / (1024.0 * 1024.0));
+    if simulation.total_bytes > 0 {
+        pretty_println!("\n💾 Data Transfer:");
+        pretty_println!("  Total size: {} bytes ({})",
+                 simulation.total_bytes,
+                 format_mb(simulation.total_bytes as f64));
         
         if plan.summary.cross_volume > 0 {
-            println!("  Cross-volume operations: {} (slower)", plan.summary.cross_volume);
+            pretty_println!("  Cross-volume operations: {} (slower)", plan.summary.cross_volume);
+            pretty_println!("  Copied across volumes: {} ({:.0}% of the data)",
+                     format_mb(simulation.copied_bytes as f64),
+                     simulation.cross_volume_ratio * 100.0);
         }
     }
 }
 
 fn analyze_potential_issues(plan: &MovePlan) {
-    println!("\n🔍 Potential Issues Analysis:");
+    pretty_println!("\n🔍 Potential Issues Analysis:");
     
     let mut has_issues = false;
     
     // Check for conflicts
     if plan.summary.conflicts > 0 {
-        println!("  ⚠️  {} operations have unresolved conflicts", plan.summary.conflicts);
+        pretty_println!("  ⚠️  {} operations have unresolved conflicts", plan.summary.conflicts);
         
         // Show some conflict examples
         let mut conflict_examples = 0;
         for node in plan.nodes.values() {
             if !node.conflicts.is_empty() && conflict_examples < 3 {
-                println!("     - {}: {} conflicts", 
+                pretty_println!("     - {}: {} conflicts", 
                          node.path_before.display(), 
                          node.conflicts.len());
                 for conflict in &node.conflicts {
-                    println!("       └─ {:?}", conflict);
+                    pretty_println!("       └─ {:?}", conflict);
                 }
                 conflict_examples += 1;
             }
         }
         
         if plan.summary.conflicts as usize > conflict_examples {
-            println!("     ... and {} more conflicts", 
+            pretty_println!("     ... and {} more conflicts", 
                      plan.summary.conflicts as usize - conflict_examples);
         }
         has_issues = true;
@@ -136,7 +248,7 @@ fn analyze_potential_issues(plan: &MovePlan) {
     
     // Check for warnings
     if plan.summary.warnings > 0 {
-        println!("  ⚠️  {} operations have warnings", plan.summary.warnings);
+        pretty_println!("  ⚠️  {} operations have warnings", plan.summary.warnings);
         
         let mut warning_types = std::collections::HashMap::new();
         for node in plan.nodes.values() {
@@ -146,36 +258,42 @@ fn analyze_potential_issues(plan: &MovePlan) {
         }
         
         for (warning_type, count) in warning_types {
-            println!("     - {}: {} operations", warning_type, count);
+            pretty_println!("     - {}: {} operations", warning_type, count);
         }
         has_issues = true;
     }
     
     // Check for cross-volume operations
     if plan.summary.cross_volume > 0 {
-        println!("  ℹ️  {} cross-volume operations (will be slower)", plan.summary.cross_volume);
+        pretty_println!("  ℹ️  {} cross-volume operations (will be slower)", plan.summary.cross_volume);
+    }
+    
+    // Check for metadata the destination file system cannot keep
+    if !plan.summary.preservation.is_empty() {
+        print_preservation_losses(&plan.summary.preservation, "  ⚠️  ");
+        has_issues = true;
     }
     
-    // Check for long paths
+    // Check for long paths, including the contents of folders once they are moved
     let mut long_paths = 0;
     for node in plan.nodes.values() {
-        if node.path_after.to_string_lossy().len() > 260 {
+        if node.warnings.contains(&Warning::LongPath) || exceeds_max_path(&node.path_after) {
             long_paths += 1;
         }
     }
     
     if long_paths > 0 {
-        println!("  ⚠️  {} operations result in long paths (>260 chars)", long_paths);
+        pretty_println!("  ⚠️  {} operations result in long paths (>{} chars)", long_paths, MAX_PATH);
         has_issues = true;
     }
     
     if !has_issues {
-        println!("  ✅ No significant issues detected");
+        pretty_println!("  ✅ No significant issues detected");
     }
 }
 
 fn provide_recommendations(plan: &MovePlan, simulation: &SimulationReport) {
-    println!("\n💡 Recommendations:");
+    pretty_println!("\n💡 Recommendations:");
     
     let total_ops = plan.nodes.len();
     let success_rate = if total_ops > 0 {
@@ -185,43 +303,43 @@ fn provide_recommendations(plan: &MovePlan, simulation: &SimulationReport) {
     };
     
     if success_rate < 90.0 {
-        println!("  ⚠️  Success rate is {:.1}% - consider resolving conflicts first", success_rate);
-        println!("     └─ Use plan editing tools to resolve conflicts before execution");
+        pretty_println!("  ⚠️  Success rate is {:.1}% - consider resolving conflicts first", success_rate);
+        pretty_println!("     └─ Use plan editing tools to resolve conflicts before execution");
     } else if success_rate < 100.0 {
-        println!("  ✅ Success rate is {:.1}% - mostly ready for execution", success_rate);
+        pretty_println!("  ✅ Success rate is {:.1}% - mostly ready for execution", success_rate);
     } else {
-        println!("  ✅ All operations should succeed - plan looks good!");
+        pretty_println!("  ✅ All operations should succeed - plan looks good!");
     }
     
     if simulation.conflicts_remaining > 0 {
-        println!("  🔧 To resolve conflicts:");
-        println!("     └─ Edit move plan to rename conflicting destinations");
-        println!("     └─ Set conflict policy to 'AutoRename' for automatic resolution");
-        println!("     └─ Mark problematic operations as 'Skip' to exclude them");
+        pretty_println!("  🔧 To resolve conflicts:");
+        pretty_println!("     └─ Edit move plan to rename conflicting destinations");
+        pretty_println!("     └─ Set conflict policy to 'AutoRename' for automatic resolution");
+        pretty_println!("     └─ Mark problematic operations as 'Skip' to exclude them");
     }
     
     if plan.summary.cross_volume > 0 {
-        println!("  ⏱️  Cross-volume operations detected:");
-        println!("     └─ These will copy then delete (slower than move)");
-        println!("     └─ Ensure sufficient disk space on destination volumes");
+        pretty_println!("  ⏱️  Cross-volume operations detected:");
+        pretty_println!("     └─ These will copy then delete (slower than move)");
+        pretty_println!("     └─ Ensure sufficient disk space on destination volumes");
     }
     
-    if simulation.estimated_duration.as_secs() > 300 { // 5 minutes
-        println!("  ⏱️  Estimated duration is long ({:?})", simulation.estimated_duration);
-        println!("     └─ Consider running in batches for better control");
-        println!("     └─ Ensure system won't sleep/hibernate during execution");
+    if simulation.estimated_duration_secs > 300 { // 5 minutes
+        pretty_println!("  ⏱️  Estimated duration is long ({})", format_duration(simulation.estimated_duration_secs as f64));
+        pretty_println!("     └─ Consider running in batches for better control");
+        pretty_println!("     └─ Ensure system won't sleep/hibernate during execution");
     }
     
-    println!("\n🚀 Next Steps:");
+    pretty_println!("\n🚀 Next Steps:");
     if simulation.conflicts_remaining == 0 {
-        println!("  1. Review the plan summary above");
-        println!("  2. Run: filemover apply --plan {}", 
+        pretty_println!("  1. Review the plan summary above");
+        pretty_println!("  2. Run: filemover apply --plan {}", 
                  "move_plan.json"); // This would be the actual plan file path
-        println!("  3. Monitor progress and check logs");
+        pretty_println!("  3. Monitor progress and check logs");
     } else {
-        println!("  1. Resolve {} conflicts in the plan", simulation.conflicts_remaining);
-        println!("  2. Re-run dry-run to verify fixes");
-        println!("  3. Execute when ready with: filemover apply --plan [plan-file]");
+        pretty_println!("  1. Resolve {} conflicts in the plan", simulation.conflicts_remaining);
+        pretty_println!("  2. Re-run dry-run to verify fixes");
+        pretty_println!("  3. Execute when ready with: filemover apply --plan [plan-file]");
     }
 }
 
@@ -238,6 +356,7 @@ mod tests {
         
         let result = dry_run_command(
             PathBuf::from("nonexistent_plan.json"),
+            ThroughputOptions::default(),
             &config_manager
         ).await;
         
@@ -265,6 +384,7 @@ mod tests {
             conflicts: vec![],
             children: vec![],
             rule_id: None,
+            tags: Vec::new(),
         };
         nodes.insert(node_id, node);
         
@@ -278,7 +398,9 @@ mod tests {
                 cross_volume: 1,
                 conflicts: 0,
                 warnings: 1,
+                preservation: Default::default(),
             },
+            execution_order: Vec::new(),
         };
         
         let json = serde_json::to_string_pretty(&plan).unwrap();
@@ -306,6 +428,7 @@ mod tests {
             conflicts: vec![Conflict::NameExists { existing_path: PathBuf::from("D:\\Archive\\test") }],
             children: vec![],
             rule_id: None,
+            tags: Vec::new(),
         };
         nodes.insert(node_id, node);
         
@@ -319,10 +442,19 @@ mod tests {
                 cross_volume: 1,
                 conflicts: 1,
                 warnings: 1,
+                preservation: Default::default(),
             },
+            execution_order: Vec::new(),
         };
         
         // This should not panic and should identify issues
         analyze_potential_issues(&plan);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0.2), "1s");
+        assert_eq!(format_duration(125.0), "2m 5s");
+        assert_eq!(format_duration(7300.0), "2h 1m");
+    }
+}
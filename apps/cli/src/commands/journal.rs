@@ -0,0 +1,377 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use clap::ValueEnum;
+use serde::Serialize;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use filemover_types::{
+    Config, JournalEntry, JournalProblem, JournalQuery, JournalSummary, OpKind, ResultKind, RetentionPolicy,
+};
+use crate::{JournalAction, config_manager::ConfigManager};
+use super::run_report::rule_names;
+use super::undo::{load_checked_journal, print_journal_problems};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum JournalSortKey {
+    /// Order in which operations were recorded
+    #[default]
+    Time,
+    Source,
+    Dest,
+    Result,
+    Op,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JournalShowOptions {
+    pub query: JournalQuery,
+    pub op: Option<OpKind>,
+    pub sort: JournalSortKey,
+    pub reverse: bool,
+}
+
+pub async fn journal_command(
+    action: JournalAction,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    match action {
+        JournalAction::Show { file, result, op, rule, tag, path, sort, reverse, limit } => {
+            let options = JournalShowOptions {
+                query: JournalQuery {
+                    path,
+                    rule_id: rule,
+                    tag,
+                    result,
+                    limit,
+                    ..Default::default()
+                },
+                op,
+                sort,
+                reverse,
+            };
+            // Rule labels are optional decoration, so a missing profile is not an error
+            let rules = config_manager.load_config(None)
+                .map(|config| config.rules)
+                .unwrap_or_default();
+            show_journal(&file, &options, &rule_names(&rules))
+        }
+        JournalAction::Prune { dir, keep, older_than, dry_run } => {
+            let config = config_manager.load_config(None).unwrap_or_default();
+            let settings = &config.journal;
+            
+            // Command-line values override the configured policy
+            let policy = RetentionPolicy {
+                keep_last: keep.or(settings.retention.keep_last),
+                max_age_days: older_than.or(settings.retention.max_age_days),
+            };
+            let dir = dir.unwrap_or_else(|| journal_directory(&config));
+            
+            prune_journals(&dir, &policy, dry_run)
+        }
+    }
+}
+
+/// Directory that holds automatically named journals
+pub(crate) fn journal_directory(config: &Config) -> PathBuf {
+    config.journal_dir.clone().unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Apply the configured retention policy; failures are only logged
+pub(crate) fn enforce_journal_retention(config: &Config) {
+    let settings = &config.journal;
+    if !settings.retention.is_enabled() {
+        return;
+    }
+    
+    let dir = journal_directory(config);
+    match filemover_journal::prune_journals(&dir, &settings.retention, false) {
+        Ok(removed) if !removed.is_empty() => {
+            pretty_println!("🧹 Removed {} old journal(s) from {}", removed.len(), dir.display());
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to apply journal retention in {}: {}", dir.display(), e),
+    }
+}
+
+fn prune_journals(dir: &Path, policy: &RetentionPolicy, dry_run: bool) -> Result<()> {
+    if !policy.is_enabled() {
+        anyhow::bail!(
+            "No retention policy configured - use --keep or --older-than, or set journal.retention in the configuration"
+        );
+    }
+    
+    let pruned = filemover_journal::prune_journals(dir, policy, dry_run)
+        .with_context(|| format!("Failed to prune journals in {}", dir.display()))?;
+    
+    if pruned.is_empty() {
+        pretty_println!("No journals to prune in {}", dir.display());
+    } else {
+        let verb = if dry_run { "Would remove" } else { "Removed" };
+        pretty_println!("🧹 {} {} journal(s):", verb, pruned.len());
+        for path in &pruned {
+            pretty_println!("  {}", path.display());
+        }
+    }
+    
+    crate::output::emit_result("journal", &JournalPruneResult {
+        dir: dir.to_path_buf(),
+        dry_run,
+        removed: pruned,
+    })
+}
+
+/// Result of `journal prune` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct JournalPruneResult {
+    dir: PathBuf,
+    dry_run: bool,
+    removed: Vec<PathBuf>,
+}
+
+fn show_journal(file: &Path, options: &JournalShowOptions, rule_names: &BTreeMap<Uuid, String>) -> Result<()> {
+    info!("Showing journal: {}", file.display());
+
+    if !file.exists() {
+        anyhow::bail!("Journal file does not exist: {}", file.display());
+    }
+
+    let report = load_checked_journal(file)
+        .context("Failed to load journal file")?;
+
+    let total = report.entries.len();
+    let entries = select_entries(report.entries, options);
+
+    pretty_println!("📄 Journal: {}", file.display());
+    pretty_println!("{}", "=".repeat(50));
+
+    print_statistics(&entries, total, rule_names);
+
+    if let Some(summary) = &report.summary {
+        pretty_println!("\n🏁 Completed: {}", summary.completed_utc.format("%Y-%m-%d %H:%M:%S UTC"));
+        if let Some(plan_hash) = &summary.plan_hash {
+            pretty_println!("📋 Plan hash: {}", plan_hash);
+        }
+    }
+
+    if !report.problems.is_empty() {
+        print_journal_problems(&report.problems);
+    }
+
+    pretty_println!("\n📝 Entries ({}):", entries.len());
+    if entries.is_empty() {
+        pretty_println!("  (no matching entries)");
+    }
+    for entry in &entries {
+        print_entry(entry);
+    }
+
+    crate::output::emit_result("journal", &JournalShowResult {
+        journal: file.to_path_buf(),
+        total_entries: total,
+        shown: entries.len(),
+        summary: report.summary,
+        integrity_problems: report.problems,
+        entries,
+    })
+}
+
+/// Result of `journal show` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct JournalShowResult {
+    journal: PathBuf,
+    total_entries: usize,
+    /// Entries left after the filters and the limit
+    shown: usize,
+    summary: Option<JournalSummary>,
+    integrity_problems: Vec<JournalProblem>,
+    entries: Vec<JournalEntry>,
+}
+
+/// Apply filters, sorting and the limit in that order
+fn select_entries(entries: Vec<JournalEntry>, options: &JournalShowOptions) -> Vec<JournalEntry> {
+    let filter = JournalQuery {
+        limit: None,
+        ..options.query.clone()
+    };
+
+    let mut selected: Vec<JournalEntry> = entries.into_iter()
+        .filter(|entry| filter.matches(entry))
+        .filter(|entry| options.op.is_none_or(|op| entry.op == op))
+        .collect();
+
+    // Stable sort keeps recording order among equal keys
+    match options.sort {
+        JournalSortKey::Time => selected.sort_by_key(|e| e.when_utc),
+        JournalSortKey::Source => selected.sort_by(|a, b| a.source.cmp(&b.source)),
+        JournalSortKey::Dest => selected.sort_by(|a, b| a.dest.cmp(&b.dest)),
+        JournalSortKey::Result => selected.sort_by_key(|e| result_label(e.result)),
+        JournalSortKey::Op => selected.sort_by_key(|e| format!("{:?}", e.op)),
+    }
+
+    if options.reverse {
+        selected.reverse();
+    }
+
+    if let Some(limit) = options.query.limit {
+        selected.truncate(limit);
+    }
+
+    selected
+}
+
+fn print_statistics(entries: &[JournalEntry], total: usize, rule_names: &BTreeMap<Uuid, String>) {
+    pretty_println!("\n📊 Statistics ({} of {} entries):", entries.len(), total);
+
+    let mut by_result: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut by_op: BTreeMap<String, usize> = BTreeMap::new();
+    let mut by_rule: BTreeMap<String, usize> = BTreeMap::new();
+    let mut by_tag: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut total_bytes = 0u64;
+
+    for entry in entries {
+        *by_result.entry(result_label(entry.result)).or_insert(0) += 1;
+        *by_op.entry(format!("{:?}", entry.op)).or_insert(0) += 1;
+        let rule = match entry.rule_id {
+            Some(id) => rule_names.get(&id).cloned().unwrap_or_else(|| id.to_string()),
+            None => "(none)".to_string(),
+        };
+        *by_rule.entry(rule).or_insert(0) += 1;
+        for tag in &entry.tags {
+            *by_tag.entry(tag).or_insert(0) += 1;
+        }
+        total_bytes += entry.metadata.as_ref().and_then(|m| m.size_bytes).unwrap_or(0);
+    }
+
+    pretty_println!("  By result:");
+    for (result, count) in &by_result {
+        pretty_println!("    {}: {}", result, count);
+    }
+
+    pretty_println!("  By operation:");
+    for (op, count) in &by_op {
+        pretty_println!("    {}: {}", op, count);
+    }
+
+    if by_rule.len() > 1 || !by_rule.contains_key("(none)") {
+        pretty_println!("  By rule:");
+        for (rule, count) in &by_rule {
+            pretty_println!("    {}: {}", rule, count);
+        }
+    }
+
+    if !by_tag.is_empty() {
+        pretty_println!("  By tag:");
+        for (tag, count) in &by_tag {
+            pretty_println!("    {}: {}", tag, count);
+        }
+    }
+
+    if total_bytes > 0 {
+        pretty_println!("  💾 Recorded size: {} bytes ({:.2} MB)",
+                 total_bytes,
+                 total_bytes as f64 / (1024.0 * 1024.0));
+    }
+
+    if let (Some(first), Some(last)) = (
+        entries.iter().map(|e| e.when_utc).min(),
+        entries.iter().map(|e| e.when_utc).max(),
+    ) {
+        pretty_println!("  🕐 Time range: {} - {} ({}s)",
+                 first.format("%Y-%m-%d %H:%M:%S"),
+                 last.format("%Y-%m-%d %H:%M:%S"),
+                 (last - first).num_seconds());
+    }
+}
+
+fn print_entry(entry: &JournalEntry) {
+    let icon = match entry.result {
+        ResultKind::Ok => "✅",
+        ResultKind::Skip => "⏭️ ",
+        ResultKind::Failed => "❌",
+    };
+
+    pretty_println!("  {} [{}] {:?}: {} -> {}",
+             icon,
+             entry.when_utc.format("%H:%M:%S"),
+             entry.op,
+             entry.source.display(),
+             entry.dest.display());
+
+    if let Some(message) = &entry.message {
+        pretty_println!("       {}", message);
+    }
+}
+
+fn result_label(result: ResultKind) -> &'static str {
+    match result {
+        ResultKind::Ok => "ok",
+        ResultKind::Skip => "skip",
+        ResultKind::Failed => "failed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn sample_entries() -> Vec<JournalEntry> {
+        vec![
+            JournalEntry::new(PathBuf::from("/src/b"), PathBuf::from("/dst/b"), OpKind::Move),
+            JournalEntry::new(PathBuf::from("/src/a"), PathBuf::from("/dst/a"), OpKind::CopyDelete)
+                .failed("Access denied"),
+            JournalEntry::new(PathBuf::from("/other/c"), PathBuf::from("/dst/c"), OpKind::Move),
+        ]
+    }
+
+    #[test]
+    fn test_select_entries_filters() {
+        let options = JournalShowOptions {
+            query: JournalQuery {
+                path: Some(PathBuf::from("/src")),
+                ..Default::default()
+            },
+            op: Some(OpKind::Move),
+            ..Default::default()
+        };
+
+        let selected = select_entries(sample_entries(), &options);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].source, PathBuf::from("/src/b"));
+    }
+
+    #[test]
+    fn test_select_entries_sort_and_limit() {
+        let options = JournalShowOptions {
+            query: JournalQuery {
+                limit: Some(2),
+                ..Default::default()
+            },
+            sort: JournalSortKey::Source,
+            ..Default::default()
+        };
+
+        let selected = select_entries(sample_entries(), &options);
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0].source, PathBuf::from("/other/c"));
+        assert_eq!(selected[1].source, PathBuf::from("/src/a"));
+    }
+
+    #[test]
+    fn test_select_entries_by_result_reversed() {
+        let options = JournalShowOptions {
+            query: JournalQuery {
+                result: Some(ResultKind::Ok),
+                ..Default::default()
+            },
+            reverse: true,
+            ..Default::default()
+        };
+
+        let selected = select_entries(sample_entries(), &options);
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0].source, PathBuf::from("/other/c"));
+    }
+}
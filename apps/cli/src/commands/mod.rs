@@ -1,18 +1,70 @@
 pub mod scan;
+pub mod scan_diff;
+pub mod scan_output;
 pub mod plan;
+pub mod plan_report;
 pub mod dry_run;
 pub mod apply;
 pub mod undo;
 pub mod config;
+pub mod daemon;
+pub mod verify;
+pub mod journal;
+pub mod rules;
+pub mod run;
+pub mod watch;
+pub mod clean_empty;
+pub mod run_report;
+pub mod schedule;
 
 pub use scan::*;
+pub use scan_diff::*;
+pub use scan_output::*;
 pub use plan::*;
+pub use plan_report::*;
 pub use dry_run::*;
 pub use apply::*;
 pub use undo::*;
 pub use config::*;
+pub use daemon::*;
+pub use verify::*;
+pub use journal::*;
+pub use rules::*;
+pub use run::*;
+pub use watch::*;
+pub use clean_empty::*;
+pub use run_report::*;
+pub use schedule::*;
 
 use crate::ConfigAction;
 use crate::config_manager::ConfigManager;
-use std::path::PathBuf;
-use anyhow::Result;
\ No newline at end of file
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use filemover_types::CancellationToken;
+
+/// An automatically named file in the configured directory (created if needed), or in the current directory
+pub(crate) fn artifact_path(dir: Option<&Path>, file_name: String) -> Result<PathBuf> {
+    match dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+            Ok(dir.join(file_name))
+        }
+        None => Ok(PathBuf::from(file_name)),
+    }
+}
+
+/// Create a cancellation token that is triggered by Ctrl+C
+pub(crate) fn ctrl_c_cancellation_token() -> CancellationToken {
+    let token = CancellationToken::new();
+    let signal_token = token.clone();
+
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            tracing::warn!("Cancellation requested, stopping after the current operation...");
+            signal_token.cancel();
+        }
+    });
+
+    token
+}
@@ -1,11 +1,15 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
-use tracing::{info, debug};
-use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+use tracing::info;
+use indicatif::ProgressStyle;
 
-use filemover_types::{FolderHit, MovePlan, Rule, PlanOptions};
-use filemover_planner::MovePlanner;
-use crate::config_manager::ConfigManager;
+use filemover_types::{
+    FolderHit, MatchTarget, MovePlan, NormalizationOptions, PatternSpec, PlanNode, PlanSummary, PreservationSummary, Rule, PlanOptions,
+};
+use filemover_matcher::{GlobMatcher, PatternMatcher, TextNormalizer};
+use filemover_planner::{MergeReport, MovePlanner, merge_plans, subset_plan};
+use crate::{PlanAction, config_manager::ConfigManager, error::CliError};
 
 pub async fn plan_command(
     input_file: Option<PathBuf>,
@@ -21,7 +25,7 @@ pub async fn plan_command(
     });
     
     if !input_path.exists() {
-        anyhow::bail!("Input file does not exist: {}", input_path.display());
+        return Err(CliError::file_operation(input_path, "Input file does not exist").into());
     }
     
     let folder_hits = load_scan_results(&input_path)
@@ -30,23 +34,26 @@ pub async fn plan_command(
     info!("Loaded {} folder hits from {}", folder_hits.len(), input_path.display());
     
     // Load rules
-    let rules = if let Some(rules_path) = rules_file {
-        load_rules_from_file(&rules_path)
-            .context("Failed to load rules file")?
+    let (rules, config) = if let Some(rules_path) = rules_file {
+        let rules = load_rules_from_file(&rules_path)
+            .context("Failed to load rules file")?;
+        // The profile still decides how auto-renamed names look and where the plan goes
+        (rules, config_manager.load_config(None).unwrap_or_default())
     } else {
         // Load from config
         let config = config_manager.load_config(None)?;
-        config.rules
+        (config.rules.clone(), config)
     };
+    let rename_suffix = config.rename_suffix;
     
     if rules.is_empty() {
-        anyhow::bail!("No rules configured. Specify --rules file or configure in profile.");
+        return Err(CliError::config("No rules configured. Specify --rules file or configure in profile.").into());
     }
     
     info!("Using {} rules for plan generation", rules.len());
     
     // Create progress bar
-    let progress = ProgressBar::new_spinner();
+    let progress = crate::progress::new_spinner();
     progress.set_style(
         ProgressStyle::default_spinner()
             .template("{spinner:.green} {msg}")
@@ -55,8 +62,12 @@ pub async fn plan_command(
     progress.set_message("Generating move plan...");
     
     // Create planner and generate plan
-    let mut planner = MovePlanner::new();
-    let plan_options = PlanOptions::default();
+    let mut planner = MovePlanner::new()
+        .with_cancellation(super::ctrl_c_cancellation_token());
+    let plan_options = PlanOptions {
+        rename_suffix,
+        ..Default::default()
+    };
     
     let plan = planner.create_plan(&folder_hits, &rules, plan_options)
         .context("Failed to generate move plan")?;
@@ -65,28 +76,226 @@ pub async fn plan_command(
     
     // Display plan summary
     print_plan_summary(&plan);
+    if plan.summary.conflicts > 0 {
+        crate::error::record_warning(format!("{} operations have unresolved conflicts", plan.summary.conflicts));
+    }
     
     // Save plan
-    let output_path = output_file.unwrap_or_else(|| {
-        PathBuf::from(format!(
-            "move_plan_{}.json",
-            chrono::Utc::now().format("%Y%m%d_%H%M%S")
-        ))
-    });
+    let output_path = match output_file {
+        Some(path) => path,
+        None => {
+            let file_name = format!("move_plan_{}.json", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+            super::artifact_path(config.plan_output_dir.as_deref(), file_name)?
+        }
+    };
     
     save_move_plan(&plan, &output_path)
         .context("Failed to save move plan")?;
     
     info!("Move plan saved to: {}", output_path.display());
     
+    crate::output::emit_result("plan", &PlanResult {
+        input: input_path,
+        output: output_path,
+        operations: plan.nodes.len(),
+        summary: plan.summary.clone(),
+    })?;
+    
+    Ok(())
+}
+
+/// Result of `plan` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct PlanResult {
+    input: PathBuf,
+    output: PathBuf,
+    operations: usize,
+    #[serde(flatten)]
+    summary: PlanSummary,
+}
+
+/// Which operations `plan filter` keeps; every given filter must match
+#[derive(Debug, Clone, Default)]
+pub struct PlanFilterOptions {
+    /// Rule IDs or labels
+    pub rules: Vec<String>,
+    /// Glob matched against the source path
+    pub path: Option<String>,
+    pub min_size: Option<u64>,
+}
+
+pub async fn plan_action_command(action: PlanAction, config_manager: &ConfigManager) -> Result<()> {
+    match action {
+        PlanAction::Merge { plans, output } => merge_plan_files(&plans, &output),
+        PlanAction::Filter { plan, rule, path, min_size, profile, output } => {
+            let options = PlanFilterOptions { rules: rule, path, min_size };
+            let rules = config_manager.load_config(profile.as_deref())?.rules;
+            filter_plan_file(&plan, &options, &rules, &output)
+        }
+        PlanAction::Report { plan, format, output, profile } => {
+            // Rule labels are optional decoration, so a missing profile is not an error
+            let rules = config_manager.load_config(profile.as_deref())
+                .map(|config| config.rules)
+                .unwrap_or_default();
+            super::plan_report::plan_report_command(&plan, format, output.as_deref(), &rules)
+        }
+    }
+}
+
+fn filter_plan_file(
+    path: &Path,
+    options: &PlanFilterOptions,
+    rules: &[Rule],
+    output_path: &PathBuf,
+) -> Result<()> {
+    let plan = load_move_plan(path)
+        .with_context(|| format!("Failed to load plan {}", path.display()))?;
+    let keep = plan_filter(options, rules)?;
+    
+    let subset = subset_plan(&plan, keep);
+    if subset.roots.is_empty() {
+        pretty_println!("No operations match the filters");
+    }
+    pretty_println!("Kept {} of {} operations", subset.roots.len(), plan.roots.len());
+    print_plan_summary(&subset);
+    
+    save_move_plan(&subset, output_path)
+        .context("Failed to save filtered plan")?;
+    
+    info!("Filtered plan saved to: {}", output_path.display());
+    
+    crate::output::emit_result("plan", &PlanFilterResult {
+        input: path.to_path_buf(),
+        output: output_path.clone(),
+        kept: subset.roots.len(),
+        total: plan.roots.len(),
+        summary: subset.summary.clone(),
+    })
+}
+
+/// Result of `plan filter` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct PlanFilterResult {
+    input: PathBuf,
+    output: PathBuf,
+    /// Operations kept, out of `total`
+    kept: usize,
+    total: usize,
+    #[serde(flatten)]
+    summary: PlanSummary,
+}
+
+/// Build the predicate for `plan filter`, resolving rule labels against `rules`
+pub(crate) fn plan_filter(options: &PlanFilterOptions, rules: &[Rule]) -> Result<impl Fn(&PlanNode) -> bool> {
+    let mut rule_ids = Vec::new();
+    for value in &options.rules {
+        if let Ok(id) = value.parse::<uuid::Uuid>() {
+            rule_ids.push(id);
+            continue;
+        }
+        let matching = rules.iter()
+            .filter(|rule| rule.label.as_deref().is_some_and(|label| label.eq_ignore_ascii_case(value)))
+            .map(|rule| rule.id)
+            .collect::<Vec<_>>();
+        if matching.is_empty() {
+            anyhow::bail!("No rule with ID or label '{}'", value);
+        }
+        rule_ids.extend(matching);
+    }
+    
+    let path_matcher = options.path.as_ref()
+        .map(|pattern| {
+            let mut spec = PatternSpec::new_glob(pattern);
+            spec.match_target = MatchTarget::FullPath;
+            GlobMatcher::new(&[spec], TextNormalizer::new(NormalizationOptions::default()))
+        })
+        .transpose()
+        .context("Invalid --path pattern")?;
+    let min_size = options.min_size;
+    
+    Ok(move |node: &PlanNode| {
+        let rule_ok = rule_ids.is_empty() || node.rule_id.is_some_and(|id| rule_ids.contains(&id));
+        let path_ok = path_matcher.as_ref().is_none_or(|matcher| {
+            matcher.is_match(&node.path_before.to_string_lossy()).unwrap_or(false)
+        });
+        let size_ok = min_size.is_none_or(|min| node.size_bytes.is_some_and(|size| size >= min));
+        rule_ok && path_ok && size_ok
+    })
+}
+
+/// Parse sizes such as `1024`, `500KB`, `1.5GB` (binary units)
+pub fn parse_size(value: &str) -> std::result::Result<u64, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse()
+        .map_err(|_| format!("Invalid size '{}'", value))?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" => 1 << 10,
+        "M" | "MB" => 1 << 20,
+        "G" | "GB" => 1 << 30,
+        "T" | "TB" => 1 << 40,
+        other => return Err(format!("Unknown size unit '{}' (expected B, KB, MB, GB or TB)", other)),
+    };
+    Ok((number * multiplier as f64) as u64)
+}
+
+fn merge_plan_files(paths: &[PathBuf], output_path: &PathBuf) -> Result<()> {
+    let plans = paths.iter()
+        .map(|path| load_move_plan(path)
+            .with_context(|| format!("Failed to load plan {}", path.display())))
+        .collect::<Result<Vec<_>>>()?;
+    
+    let (merged, report) = merge_plans(&plans);
+    
+    print_plan_summary(&merged);
+    print_merge_report(&report);
+    
+    save_move_plan(&merged, output_path)
+        .context("Failed to save merged plan")?;
+    
+    info!("Merged plan saved to: {}", output_path.display());
+    
     Ok(())
 }
 
-fn load_scan_results(path: &PathBuf) -> Result<Vec<FolderHit>> {
+fn print_merge_report(report: &MergeReport) {
+    pretty_println!("\n=== Merge ===");
+    pretty_println!("Plans merged: {}", report.plans);
+    if report.duplicates_removed > 0 {
+        pretty_println!("Duplicate operations removed: {}", report.duplicates_removed);
+    }
+    if report.ids_reassigned > 0 {
+        pretty_println!("Node IDs reassigned: {}", report.ids_reassigned);
+    }
+    if report.destination_conflicts > 0 {
+        pretty_println!("⚠️  Destinations claimed by more than one plan: {}", report.destination_conflicts);
+    }
+}
+
+pub(crate) fn load_move_plan(path: &Path) -> Result<MovePlan> {
+    let content = std::fs::read_to_string(path)
+        .context("Failed to read plan file")?;
+    
+    filemover_types::from_versioned_json(&content)
+        .context("Failed to parse plan JSON")
+}
+
+pub(crate) fn load_scan_results(path: &Path) -> Result<Vec<FolderHit>> {
     let content = std::fs::read_to_string(path)
         .context("Failed to read scan results file")?;
     
-    let hits: Vec<FolderHit> = serde_json::from_str(&content)
+    // NDJSON output from `scan --format ndjson` has one hit per line
+    let is_ndjson = path.extension().is_some_and(|ext| ext == "ndjson" || ext == "jsonl");
+    if is_ndjson {
+        return content.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).context("Failed to parse scan results NDJSON"))
+            .collect();
+    }
+    
+    let hits: Vec<FolderHit> = filemover_types::from_versioned_json(&content)
         .context("Failed to parse scan results JSON")?;
     
     Ok(hits)
@@ -102,27 +311,49 @@ fn load_rules_from_file(path: &PathBuf) -> Result<Vec<Rule>> {
     Ok(rules)
 }
 
-fn print_plan_summary(plan: &MovePlan) {
-    println!("\n=== Move Plan Summary ===");
-    println!("Total operations: {}", plan.nodes.len());
-    println!("Directories to move: {}", plan.summary.count_dirs);
+/// Print what the destination file systems cannot keep, one line per kind of metadata
+pub(crate) fn print_preservation_losses(preservation: &PreservationSummary, prefix: &str) {
+    let losses = [
+        (preservation.acl_lost, "lose their ACLs"),
+        (preservation.timestamps_degraded, "lose timestamp precision"),
+        (preservation.streams_lost, "lose alternate data streams"),
+        (preservation.compression_lost, "lose the compression attribute"),
+    ];
+    for (count, description) in losses {
+        if count > 0 {
+            pretty_println!("{}{} operations {} on the destination file system", prefix, count, description);
+        }
+    }
+}
+
+pub(crate) fn print_plan_summary(plan: &MovePlan) {
+    pretty_println!("\n=== Move Plan Summary ===");
+    pretty_println!("Total operations: {}", plan.nodes.len());
+    pretty_println!("Directories to move: {}", plan.summary.count_dirs);
+    if plan.summary.count_files > 0 {
+        pretty_println!("Files to move: {}", plan.summary.count_files);
+    }
     
     if let Some(total_bytes) = plan.summary.total_bytes {
-        println!("Total data size: {} bytes ({:.2} MB)", 
+        pretty_println!("Total data size: {} bytes ({:.2} MB)", 
                  total_bytes, 
                  total_bytes as f64 / (1024.0 * 1024.0));
     }
     
     if plan.summary.cross_volume > 0 {
-        println!("Cross-volume operations: {}", plan.summary.cross_volume);
+        pretty_println!("Cross-volume operations: {}", plan.summary.cross_volume);
     }
     
     if plan.summary.conflicts > 0 {
-        println!("⚠️  Conflicts detected: {}", plan.summary.conflicts);
+        pretty_println!("⚠️  Conflicts detected: {}", plan.summary.conflicts);
     }
     
     if plan.summary.warnings > 0 {
-        println!("⚠️  Warnings: {}", plan.summary.warnings);
+        pretty_println!("⚠️  Warnings: {}", plan.summary.warnings);
+    }
+    
+    if !plan.summary.preservation.is_empty() {
+        print_preservation_losses(&plan.summary.preservation, "⚠️  ");
     }
     
     // Show operation breakdown
@@ -131,13 +362,13 @@ fn print_plan_summary(plan: &MovePlan) {
         *op_counts.entry(format!("{:?}", node.kind)).or_insert(0) += 1;
     }
     
-    println!("\nOperations breakdown:");
+    pretty_println!("\nOperations breakdown:");
     for (op_type, count) in op_counts {
-        println!("  {}: {}", op_type, count);
+        pretty_println!("  {}: {}", op_type, count);
     }
     
     // Show first few operations
-    println!("\nFirst 10 operations:");
+    pretty_println!("\nFirst 10 operations:");
     let mut shown = 0;
     for node in plan.nodes.values() {
         if shown >= 10 {
@@ -150,7 +381,7 @@ fn print_plan_summary(plan: &MovePlan) {
             String::new()
         };
         
-        println!("  {}. {:?}: {} -> {}{}",
+        pretty_println!("  {}. {:?}: {} -> {}{}",
                  shown + 1,
                  node.kind,
                  node.path_before.display(),
@@ -161,12 +392,12 @@ fn print_plan_summary(plan: &MovePlan) {
     }
     
     if plan.nodes.len() > 10 {
-        println!("  ... and {} more operations", plan.nodes.len() - 10);
+        pretty_println!("  ... and {} more operations", plan.nodes.len() - 10);
     }
 }
 
-fn save_move_plan(plan: &MovePlan, output_path: &PathBuf) -> Result<()> {
-    let json = serde_json::to_string_pretty(plan)
+pub(crate) fn save_move_plan(plan: &MovePlan, output_path: &PathBuf) -> Result<()> {
+    let json = filemover_types::to_versioned_json(plan, true)
         .context("Failed to serialize move plan")?;
     
     std::fs::write(output_path, json)
@@ -179,7 +410,7 @@ fn save_move_plan(plan: &MovePlan, output_path: &PathBuf) -> Result<()> {
 mod tests {
     use super::*;
     use tempfile::TempDir;
-    use filemover_types::{PatternSpec, ConflictPolicy, Warning};
+    use filemover_types::{PatternSpec, Warning};
 
     #[tokio::test]
     async fn test_plan_command_with_missing_input() {
@@ -206,9 +437,13 @@ mod tests {
                 path: PathBuf::from("C:\\Test\\folder1"),
                 name: "folder1".to_string(),
                 matched_rule: None,
+                matched_rules: Vec::new(),
+                tags: Vec::new(),
                 dest_preview: Some(PathBuf::from("D:\\Archive\\folder1")),
                 warnings: vec![Warning::LongPath],
                 size_bytes: Some(1024),
+                item_count: None,
+                is_dir: true,
             }
         ];
         
@@ -241,4 +476,95 @@ mod tests {
         assert_eq!(loaded.len(), 1);
         assert_eq!(loaded[0].template, "{name}");
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024"), Ok(1024));
+        assert_eq!(parse_size("1GB"), Ok(1 << 30));
+        assert_eq!(parse_size("1.5 kb"), Ok(1536));
+        assert!(parse_size("12 parsecs").is_err());
+    }
+    
+    #[test]
+    fn test_plan_filter() {
+        let archive_rule = Rule::new(PatternSpec::new_glob("*"), PathBuf::from("D:\\Archive"), "{name}".to_string())
+            .with_label("Client archive".to_string());
+        let node_for = |path: &str, size: u64, rule_id: Option<uuid::Uuid>| PlanNode {
+            id: filemover_types::PlanNodeId::new(),
+            is_dir: true,
+            name_before: String::new(),
+            path_before: PathBuf::from(path),
+            name_after: String::new(),
+            path_after: PathBuf::from("archive"),
+            kind: filemover_types::OpKind::Move,
+            size_bytes: Some(size),
+            warnings: Vec::new(),
+            conflicts: Vec::new(),
+            children: Vec::new(),
+            rule_id,
+            tags: Vec::new(),
+        };
+        let options = PlanFilterOptions {
+            rules: vec!["client archive".to_string()],
+            path: Some("data/2019*".to_string()),
+            min_size: Some(1000),
+        };
+        let keep = plan_filter(&options, std::slice::from_ref(&archive_rule)).unwrap();
+        
+        assert!(keep(&node_for("data/2019-taxes", 5000, Some(archive_rule.id))));
+        assert!(!keep(&node_for("data/2019-taxes", 5000, None)));
+        assert!(!keep(&node_for("data/2020-taxes", 5000, Some(archive_rule.id))));
+        assert!(!keep(&node_for("data/2019-taxes", 10, Some(archive_rule.id))));
+        
+        let unknown = PlanFilterOptions { rules: vec!["Downloads".to_string()], ..Default::default() };
+        assert!(plan_filter(&unknown, &[archive_rule]).is_err());
+    }
+    
+    #[test]
+    fn test_merge_plan_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let node = filemover_types::PlanNode {
+            id: filemover_types::PlanNodeId::new(),
+            is_dir: true,
+            name_before: "folder1".to_string(),
+            path_before: PathBuf::from("C:\\Test\\folder1"),
+            name_after: "folder1".to_string(),
+            path_after: PathBuf::from("D:\\Archive\\folder1"),
+            kind: filemover_types::OpKind::Move,
+            size_bytes: Some(1024),
+            warnings: Vec::new(),
+            conflicts: Vec::new(),
+            children: Vec::new(),
+            rule_id: None,
+            tags: Vec::new(),
+        };
+        let plan = MovePlan {
+            roots: vec![node.id],
+            nodes: [(node.id, node)].into(),
+            summary: Default::default(),
+            execution_order: Vec::new(),
+        };
+        
+        let inputs = ["a.json", "b.json"].map(|name| temp_dir.path().join(name));
+        for input in &inputs {
+            save_move_plan(&plan, input).unwrap();
+        }
+        let output = temp_dir.path().join("merged.json");
+        merge_plan_files(&inputs, &output).unwrap();
+        
+        // The same operation in both plans is kept once
+        let merged = load_move_plan(&output).unwrap();
+        assert_eq!(merged.nodes.len(), 1);
+        assert_eq!(merged.summary.count_dirs, 1);
+    }
+    
+    #[test]
+    fn test_plan_from_newer_version_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let plan_file = temp_dir.path().join("future.json");
+        std::fs::write(&plan_file, r#"{"format_version": 99, "data": {"roots": [], "nodes": {}}}"#).unwrap();
+        
+        let error = load_move_plan(&plan_file).unwrap_err();
+        assert!(format!("{:#}", error).contains("newer version of FileMover"));
+    }
+}
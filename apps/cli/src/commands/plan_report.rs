@@ -0,0 +1,374 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use anyhow::{Result, Context};
+use clap::ValueEnum;
+use uuid::Uuid;
+
+use filemover_types::{Localize, MovePlan, OpKind, PlanNode, Rule};
+use filemover_planner::nodes_in_execution_order;
+use super::apply::describe_conflict;
+use super::scan_output::csv_escape;
+
+/// Document format for `plan report`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum PlanReportFormat {
+    #[default]
+    Markdown,
+    /// A single self-contained HTML page
+    Html,
+    /// The operation list only, one row per operation
+    Csv,
+}
+
+/// Operations that share a rule
+struct RuleGroup {
+    name: String,
+    operations: u64,
+    bytes: u64,
+    conflicts: u64,
+}
+
+/// Everything the report shows, gathered once for all formats
+struct PlanReport<'a> {
+    plan: &'a MovePlan,
+    /// Executed operations in execution order, then skipped ones by source path
+    operations: Vec<&'a PlanNode>,
+    groups: Vec<RuleGroup>,
+    rule_names: BTreeMap<Uuid, String>,
+}
+
+impl<'a> PlanReport<'a> {
+    fn new(plan: &'a MovePlan, rules: &[Rule]) -> Self {
+        let rule_names: BTreeMap<Uuid, String> = rules.iter()
+            .map(|rule| (rule.id, rule.label.clone().unwrap_or_else(|| rule.id.to_string())))
+            .collect();
+
+        let mut operations = nodes_in_execution_order(plan);
+        let mut inactive = plan.nodes.values()
+            .filter(|node| matches!(node.kind, OpKind::Skip | OpKind::None))
+            .collect::<Vec<_>>();
+        inactive.sort_by(|a, b| a.path_before.cmp(&b.path_before));
+        operations.extend(inactive);
+
+        let mut groups: BTreeMap<String, RuleGroup> = BTreeMap::new();
+        for node in operations.iter().filter(|node| is_active(node)) {
+            let name = rule_name(&rule_names, node);
+            let group = groups.entry(name.clone()).or_insert_with(|| RuleGroup {
+                name,
+                operations: 0,
+                bytes: 0,
+                conflicts: 0,
+            });
+            group.operations += 1;
+            group.bytes += node.size_bytes.unwrap_or(0);
+            group.conflicts += node.conflicts.len() as u64;
+        }
+
+        Self {
+            plan,
+            operations,
+            groups: groups.into_values().collect(),
+            rule_names,
+        }
+    }
+
+    fn rule(&self, node: &PlanNode) -> String {
+        rule_name(&self.rule_names, node)
+    }
+
+    fn conflicted(&self) -> impl Iterator<Item = &&'a PlanNode> {
+        self.operations.iter().filter(|node| !node.conflicts.is_empty())
+    }
+
+    /// Label and value rows of the summary section
+    fn summary_rows(&self) -> Vec<(&'static str, String)> {
+        let summary = &self.plan.summary;
+        let mut rows = vec![
+            ("Operations", self.operations.iter().filter(|node| is_active(node)).count().to_string()),
+            ("Skipped", self.operations.iter().filter(|node| !is_active(node)).count().to_string()),
+            ("Directories", summary.count_dirs.to_string()),
+            ("Files", summary.count_files.to_string()),
+            ("Total size", summary.total_bytes.map(format_size).unwrap_or_else(|| "unknown".to_string())),
+            ("Cross-volume operations", summary.cross_volume.to_string()),
+            ("Conflicts", summary.conflicts.to_string()),
+            ("Warnings", summary.warnings.to_string()),
+        ];
+        let preservation = &summary.preservation;
+        for (label, count) in [
+            ("ACLs lost", preservation.acl_lost),
+            ("Timestamp precision lost", preservation.timestamps_degraded),
+            ("Alternate data streams lost", preservation.streams_lost),
+            ("Compression attribute lost", preservation.compression_lost),
+        ] {
+            if count > 0 {
+                rows.push((label, count.to_string()));
+            }
+        }
+        rows
+    }
+}
+
+pub fn plan_report_command(
+    plan_file: &Path,
+    format: PlanReportFormat,
+    output: Option<&Path>,
+    rules: &[Rule],
+) -> Result<()> {
+    let plan = super::plan::load_move_plan(plan_file)
+        .with_context(|| format!("Failed to load plan {}", plan_file.display()))?;
+
+    match output {
+        Some(path) if path != Path::new("-") => {
+            let file = File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let mut out = BufWriter::new(file);
+            write_plan_report(&plan, rules, format, &mut out)?;
+            out.flush().context("Failed to write plan report")?;
+            println!("📝 Plan report written to {}", path.display());
+        }
+        _ => {
+            let mut out = std::io::stdout().lock();
+            write_plan_report(&plan, rules, format, &mut out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Render the report; `rules` supplies readable names for the rules in the plan
+pub fn write_plan_report(plan: &MovePlan, rules: &[Rule], format: PlanReportFormat, out: &mut dyn Write) -> Result<()> {
+    let report = PlanReport::new(plan, rules);
+    match format {
+        PlanReportFormat::Markdown => write_markdown(&report, out),
+        PlanReportFormat::Html => write_html(&report, out),
+        PlanReportFormat::Csv => write_csv(&report, out),
+    }
+}
+
+fn write_markdown(report: &PlanReport, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "# Move Plan Review\n")?;
+
+    writeln!(out, "## Summary\n")?;
+    writeln!(out, "| | |\n|---|---|")?;
+    for (label, value) in report.summary_rows() {
+        writeln!(out, "| {} | {} |", label, value)?;
+    }
+
+    writeln!(out, "\n## By Rule\n")?;
+    writeln!(out, "| Rule | Operations | Size | Conflicts |\n|---|---:|---:|---:|")?;
+    for group in &report.groups {
+        writeln!(out, "| {} | {} | {} | {} |",
+                 markdown_escape(&group.name), group.operations, format_size(group.bytes), group.conflicts)?;
+    }
+
+    writeln!(out, "\n## Conflicts\n")?;
+    let mut any = false;
+    for node in report.conflicted() {
+        any = true;
+        writeln!(out, "- `{}` → `{}`", node.path_before.display(), node.path_after.display())?;
+        for conflict in &node.conflicts {
+            writeln!(out, "  - {}", markdown_escape(&describe_conflict(conflict)))?;
+        }
+    }
+    if !any {
+        writeln!(out, "No conflicts.")?;
+    }
+
+    writeln!(out, "\n## Operations\n")?;
+    writeln!(out, "| # | Operation | Source | Destination | Size | Rule | Notes |\n|---:|---|---|---|---:|---|---|")?;
+    for (i, node) in report.operations.iter().enumerate() {
+        writeln!(out, "| {} | {:?} | {} | {} | {} | {} | {} |",
+                 i + 1,
+                 node.kind,
+                 markdown_escape(&node.path_before.display().to_string()),
+                 markdown_escape(&node.path_after.display().to_string()),
+                 node.size_bytes.map(format_size).unwrap_or_default(),
+                 markdown_escape(&report.rule(node)),
+                 markdown_escape(&notes(node)))?;
+    }
+
+    Ok(())
+}
+
+fn write_html(report: &PlanReport, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Move Plan Review</title>")?;
+    writeln!(out, "<style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse;margin-bottom:1.5em}}\
+th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}th{{background:#f3f3f3}}\
+tr.conflict td{{background:#fdecea}}tr.skipped td{{color:#888}}</style>")?;
+    writeln!(out, "</head>\n<body>\n<h1>Move Plan Review</h1>")?;
+
+    writeln!(out, "<h2>Summary</h2>\n<table>")?;
+    for (label, value) in report.summary_rows() {
+        writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", label, html_escape(&value))?;
+    }
+    writeln!(out, "</table>")?;
+
+    writeln!(out, "<h2>By Rule</h2>\n<table>\n<tr><th>Rule</th><th>Operations</th><th>Size</th><th>Conflicts</th></tr>")?;
+    for group in &report.groups {
+        writeln!(out, "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                 html_escape(&group.name), group.operations, format_size(group.bytes), group.conflicts)?;
+    }
+    writeln!(out, "</table>")?;
+
+    writeln!(out, "<h2>Conflicts</h2>")?;
+    let conflicted = report.conflicted().collect::<Vec<_>>();
+    if conflicted.is_empty() {
+        writeln!(out, "<p>No conflicts.</p>")?;
+    } else {
+        writeln!(out, "<ul>")?;
+        for node in conflicted {
+            writeln!(out, "<li><code>{}</code> &rarr; <code>{}</code><ul>",
+                     html_escape(&node.path_before.display().to_string()),
+                     html_escape(&node.path_after.display().to_string()))?;
+            for conflict in &node.conflicts {
+                writeln!(out, "<li>{}</li>", html_escape(&describe_conflict(conflict)))?;
+            }
+            writeln!(out, "</ul></li>")?;
+        }
+        writeln!(out, "</ul>")?;
+    }
+
+    writeln!(out, "<h2>Operations</h2>\n<table>\n<tr><th>#</th><th>Operation</th><th>Source</th><th>Destination</th><th>Size</th><th>Rule</th><th>Notes</th></tr>")?;
+    for (i, node) in report.operations.iter().enumerate() {
+        let class = if !node.conflicts.is_empty() {
+            " class=\"conflict\""
+        } else if !is_active(node) {
+            " class=\"skipped\""
+        } else {
+            ""
+        };
+        writeln!(out, "<tr{}><td>{}</td><td>{:?}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                 class,
+                 i + 1,
+                 node.kind,
+                 html_escape(&node.path_before.display().to_string()),
+                 html_escape(&node.path_after.display().to_string()),
+                 node.size_bytes.map(format_size).unwrap_or_default(),
+                 html_escape(&report.rule(node)),
+                 html_escape(&notes(node)))?;
+    }
+    writeln!(out, "</table>\n</body>\n</html>")?;
+
+    Ok(())
+}
+
+fn write_csv(report: &PlanReport, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "order,op,source,dest,size_bytes,rule,conflicts,warnings")?;
+    for (i, node) in report.operations.iter().enumerate() {
+        let conflicts: Vec<String> = node.conflicts.iter().map(describe_conflict).collect();
+        let warnings: Vec<String> = node.warnings.iter().map(|w| format!("{:?}", w)).collect();
+        let fields = [
+            (i + 1).to_string(),
+            format!("{:?}", node.kind),
+            node.path_before.display().to_string(),
+            node.path_after.display().to_string(),
+            node.size_bytes.map(|s| s.to_string()).unwrap_or_default(),
+            report.rule(node),
+            conflicts.join(";"),
+            warnings.join(";"),
+        ];
+        writeln!(out, "{}", fields.iter().map(|field| csv_escape(field)).collect::<Vec<_>>().join(","))?;
+    }
+    Ok(())
+}
+
+fn is_active(node: &PlanNode) -> bool {
+    !matches!(node.kind, OpKind::Skip | OpKind::None)
+}
+
+fn rule_name(names: &BTreeMap<Uuid, String>, node: &PlanNode) -> String {
+    match node.rule_id {
+        Some(id) => names.get(&id).cloned().unwrap_or_else(|| id.to_string()),
+        None => "(no rule)".to_string(),
+    }
+}
+
+/// Conflicts and warnings of one operation in a single cell
+fn notes(node: &PlanNode) -> String {
+    node.conflicts.iter().map(describe_conflict)
+        .chain(node.warnings.iter().map(|w| w.localize(crate::output::locale())))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub(super) fn format_size(bytes: u64) -> String {
+    format!("{:.2} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+pub(super) fn markdown_escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+pub(super) fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use filemover_types::{Conflict, PatternSpec, PlanNodeId, PlanSummary};
+
+    fn node(name: &str, rule_id: Option<Uuid>) -> PlanNode {
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: name.to_string(),
+            path_before: PathBuf::from("/data").join(name),
+            name_after: name.to_string(),
+            path_after: PathBuf::from("/archive").join(name),
+            kind: OpKind::Move,
+            size_bytes: Some(1024 * 1024),
+            warnings: Vec::new(),
+            conflicts: Vec::new(),
+            children: Vec::new(),
+            rule_id,
+            tags: Vec::new(),
+        }
+    }
+
+    fn render(plan: &MovePlan, rules: &[Rule], format: PlanReportFormat) -> String {
+        let mut out = Vec::new();
+        write_plan_report(plan, rules, format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_plan_report_formats() {
+        let mut rule = Rule::new(PatternSpec::new_glob("*"), PathBuf::from("/archive"), "{name}".to_string());
+        rule.label = Some("Projects".to_string());
+
+        let first = node("a<b", Some(rule.id));
+        let mut second = node("c", Some(rule.id));
+        second.conflicts.push(Conflict::NameExists { existing_path: PathBuf::from("/archive/c") });
+        let mut skipped = node("d", None);
+        skipped.kind = OpKind::Skip;
+        let nodes = [first, second, skipped];
+        let plan = MovePlan {
+            roots: nodes.iter().map(|node| node.id).collect(),
+            nodes: nodes.into_iter().map(|node| (node.id, node)).collect(),
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+        };
+        let rules = vec![rule];
+
+        let markdown = render(&plan, &rules, PlanReportFormat::Markdown);
+        assert!(markdown.contains("| Projects | 2 | 2.00 MB | 1 |"));
+        assert!(markdown.contains("/archive/c already exists"));
+
+        let html = render(&plan, &rules, PlanReportFormat::Html);
+        assert!(html.contains("/data/a&lt;b"));
+        assert!(html.contains("class=\"skipped\""));
+
+        let csv = render(&plan, &rules, PlanReportFormat::Csv);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        // Skipped operations follow the execution order
+        assert!(lines[3].starts_with("3,Skip,/data/d"));
+    }
+}
@@ -0,0 +1,392 @@
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use serde::Serialize;
+use tracing::info;
+
+use filemover_types::{is_valid_date_format, NormalizationOptions, Rule};
+use filemover_matcher::{lint_rules, MatchContext, MatchExplanation, MatchingEngine, RuleLint, RuleLintKind, RuleOutcome};
+use filemover_planner::TemplateEngine;
+use crate::{RulesAction, config_manager::ConfigManager, error::CliError};
+use super::config::format_pattern;
+
+pub async fn rules_command(
+    action: RulesAction,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    match action {
+        RulesAction::Test { names, profile } => test_rules(names, profile, config_manager).await,
+        RulesAction::Lint { profile } => lint_profile_rules(profile, config_manager).await,
+    }
+}
+
+async fn test_rules(
+    names: Vec<String>,
+    profile: Option<String>,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    if names.is_empty() {
+        anyhow::bail!("No folder names given to test");
+    }
+
+    let config = config_manager.load_config(profile.as_deref())?;
+    info!("Testing {} names against {} rules", names.len(), config.rules.len());
+
+    let engine = MatchingEngine::with_exclude_patterns(
+        config.rules.clone(),
+        config.exclude_patterns.clone(),
+        config.options.normalization.clone(),
+    )
+        .context("Failed to compile rules")?;
+    let mut template_engine = TemplateEngine::new();
+
+    let mut results = Vec::new();
+    for name in &names {
+        // Inputs with separators are also matched against full-path patterns
+        let path = Path::new(name);
+        let folder_name = path.file_name().and_then(|n| n.to_str()).unwrap_or(name);
+        let context = MatchContext {
+            path: (path.components().count() > 1).then_some(path),
+            ..MatchContext::from_name(folder_name)
+        };
+
+        let explanation = engine.explain_for(&context)
+            .with_context(|| format!("Failed to match '{}'", name))?;
+        print_explanation(name, &explanation);
+
+        let destination = match explanation.matched_rule {
+            Some(rule) => {
+                let destination = template_engine.expand_template(rule, path)
+                    .with_context(|| format!("Failed to expand template for '{}'", name))?;
+                pretty_println!("  Destination: {}", destination.display());
+                Some(destination)
+            }
+            None => None,
+        };
+        results.push(RuleTestResult { name: name.clone(), explanation, destination });
+    }
+
+    crate::output::emit_result("rules", &RulesTestResult {
+        tested: results.len(),
+        matched: results.iter().filter(|result| result.explanation.matched_rule.is_some()).count(),
+        results,
+    })
+}
+
+/// Result of `rules test` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct RulesTestResult<'a> {
+    tested: usize,
+    matched: usize,
+    results: Vec<RuleTestResult<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct RuleTestResult<'a> {
+    name: String,
+    explanation: MatchExplanation<'a>,
+    destination: Option<PathBuf>,
+}
+
+async fn lint_profile_rules(
+    profile: Option<String>,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    let config = config_manager.load_config(profile.as_deref())?;
+    info!("Linting {} rules", config.rules.len());
+
+    let issues = check_rules(&config.rules, &config.options.normalization);
+    let errors = issues.iter().filter(|issue| issue.severity == IssueSeverity::Error).count();
+    if !issues.is_empty() {
+        pretty_println!("{} {} problems in {} rules:", if errors > 0 { "❌" } else { "⚠️ " }, issues.len(), config.rules.len());
+        for issue in &issues {
+            let marker = match issue.severity {
+                IssueSeverity::Error => "error",
+                IssueSeverity::Warning => "warning",
+            };
+            pretty_println!("  {}: {} {}", marker, describe_rule(&config.rules[issue.rule_index]), issue.message);
+            if issue.severity == IssueSeverity::Warning {
+                crate::error::record_warning(issue.message.clone());
+            }
+        }
+        pretty_println!();
+    }
+
+    // Overlaps can only be checked between rules that compile
+    let usable: Vec<Rule> = config.rules.iter().enumerate()
+        .filter(|(i, _)| !issues.iter().any(|issue| issue.rule_index == *i && issue.severity == IssueSeverity::Error))
+        .map(|(_, rule)| rule.clone())
+        .collect();
+    let lints = lint_rules(&usable, &config.options.normalization)
+        .context("Failed to compile rules")?;
+
+    if lints.is_empty() {
+        pretty_println!("✅ No conflicts found between {} rules", usable.len());
+    } else {
+        pretty_println!("⚠️  {} rule conflicts found:", lints.len());
+        for lint in &lints {
+            print_lint(lint);
+            crate::error::record_warning(format!("{} overlaps {}", describe_rule(lint.rule), describe_rule(lint.other)));
+        }
+    }
+
+    let result = RulesLintResult {
+        rules: config.rules.len(),
+        errors,
+        issues,
+        conflicts: lints,
+    };
+    if errors > 0 {
+        let error = CliError::config(format!("{} rules have structural problems", errors)).into();
+        return Err(crate::output::emit_failure("rules", &result, error));
+    }
+    crate::output::emit_result("rules", &result)
+}
+
+/// Result of `rules lint` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct RulesLintResult<'a> {
+    rules: usize,
+    errors: usize,
+    issues: Vec<RuleIssue>,
+    conflicts: Vec<RuleLint<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum IssueSeverity {
+    /// The rule cannot be used as configured
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct RuleIssue {
+    pub rule_index: usize,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+/// Structural checks of each rule: patterns, templates, date formats and destinations
+pub(crate) fn check_rules(rules: &[Rule], normalization: &NormalizationOptions) -> Vec<RuleIssue> {
+    let mut issues = Vec::new();
+    let mut issue = |rule_index, severity, message: String| issues.push(RuleIssue { rule_index, severity, message });
+
+    for (i, rule) in rules.iter().enumerate() {
+        if let Some(first) = rules[..i].iter().position(|other| other.id == rule.id) {
+            issue(i, IssueSeverity::Error, format!("has the same ID as rule #{}", first + 1));
+        } else if let Some(first) = rules[..i].iter().position(|other| same_definition(other, rule)) {
+            issue(i, IssueSeverity::Warning, format!("duplicates rule #{}", first + 1));
+        }
+        if !rule.enabled {
+            issue(i, IssueSeverity::Warning, "is disabled".to_string());
+        }
+
+        // Compiles the pattern, expression and conditions the same way scans do
+        if let Err(e) = MatchingEngine::new(vec![rule.clone()], normalization.clone()) {
+            issue(i, IssueSeverity::Error, format!("does not compile: {}", e));
+        }
+        for format in &rule.date_formats {
+            if !is_valid_date_format(format) {
+                issue(i, IssueSeverity::Error, format!("has an invalid date format \"{}\"", format));
+            }
+        }
+
+        // Exclusions and tag-only rules do not move anything
+        if rule.pattern.is_exclude || rule.is_tag_only() {
+            continue;
+        }
+        if let Err(e) = TemplateEngine::validate_template(&rule.template) {
+            issue(i, IssueSeverity::Error, format!("has an invalid template \"{}\": {}", rule.template, e));
+        }
+        if let Some((severity, message)) = check_dest_root(&rule.dest_root) {
+            issue(i, severity, message);
+        }
+    }
+
+    issues
+}
+
+/// Same matching and destination, ignoring ID, label and priority
+fn same_definition(a: &Rule, b: &Rule) -> bool {
+    a.pattern == b.pattern
+        && a.expression == b.expression
+        && a.conditions == b.conditions
+        && a.dest_root == b.dest_root
+        && a.template == b.template
+}
+
+fn check_dest_root(dest_root: &Path) -> Option<(IssueSeverity, String)> {
+    if dest_root.as_os_str().is_empty() {
+        return Some((IssueSeverity::Error, "has no destination".to_string()));
+    }
+    if !dest_root.is_absolute() {
+        return Some((IssueSeverity::Error, format!("has a relative destination {}", dest_root.display())));
+    }
+    if dest_root.is_dir() {
+        return None;
+    }
+    if dest_root.exists() {
+        return Some((IssueSeverity::Error, format!("has a destination that is not a directory: {}", dest_root.display())));
+    }
+    // Missing folders are created when the plan runs, but the drive or share has to be there
+    match dest_root.ancestors().skip(1).find(|dir| dir.is_dir()) {
+        Some(_) => Some((IssueSeverity::Warning, format!("has a destination that does not exist yet: {}", dest_root.display()))),
+        None => Some((IssueSeverity::Error, format!("has an unreachable destination: {}", dest_root.display()))),
+    }
+}
+
+fn print_lint(lint: &RuleLint) {
+    let summary = match lint.kind {
+        RuleLintKind::AmbiguousPriority => "share a priority and both match",
+        RuleLintKind::Shadowed => "is always matched before",
+        RuleLintKind::CatchAllAbove => "matches everything, ahead of",
+    };
+    pretty_println!("\n  {} {}", describe_rule(lint.rule), summary);
+    pretty_println!("  {}", describe_rule(lint.other));
+    pretty_println!("    e.g. \"{}\"", lint.example);
+}
+
+pub(crate) fn print_explanation(name: &str, explanation: &MatchExplanation) {
+    pretty_println!("\n=== {} ===", name);
+
+    if let Some(pattern) = explanation.excluded_by_pattern {
+        pretty_println!("  🚫 Excluded by pattern {}", format_pattern(pattern));
+    } else if let Some(rule) = explanation.excluded_by {
+        pretty_println!("  🚫 Excluded by {}", describe_rule(rule));
+    } else if let Some(rule) = explanation.matched_rule {
+        pretty_println!("  ✅ Matched {}", describe_rule(rule));
+    } else {
+        pretty_println!("  ❌ No rule matched");
+    }
+
+    for evaluation in &explanation.evaluations {
+        let marker = match evaluation.outcome {
+            RuleOutcome::Matched => "✓",
+            RuleOutcome::Disabled => "·",
+            _ => "-",
+        };
+        pretty_println!("    {} {}: {}", marker, describe_rule(evaluation.rule), describe_outcome(evaluation.outcome));
+        if let Some(compared) = &evaluation.compared {
+            pretty_println!("        compared \"{}\" as \"{}\"", compared.original, compared.normalized);
+        }
+    }
+}
+
+pub(crate) fn describe_rule(rule: &Rule) -> String {
+    let kind = if rule.pattern.is_exclude { "exclude" } else { "priority" };
+    let label = rule.label.as_deref().map(|l| format!(" \"{}\"", l)).unwrap_or_default();
+    format!("[{} {}]{} {}", kind, rule.priority, label, format_pattern(&rule.pattern))
+}
+
+fn describe_outcome(outcome: RuleOutcome) -> &'static str {
+    match outcome {
+        RuleOutcome::Matched => "matched",
+        RuleOutcome::ConditionsNotMet => "pattern matched, but conditions not met",
+        RuleOutcome::NotMatched => "no match",
+        RuleOutcome::SkippedByExclusion => "skipped (folder is excluded)",
+        RuleOutcome::SkippedByPriority => "skipped (a higher-priority rule matched)",
+        RuleOutcome::Disabled => "disabled",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::{Config, PatternSpec};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_rules_test_with_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config.json"))).unwrap();
+
+        let config = Config {
+            rules: vec![
+                Rule::new(PatternSpec::new_contains("invoice"), PathBuf::from("/archive/invoices"), "{name}".to_string()),
+            ],
+            ..Default::default()
+        };
+        config_manager.save_config("work", &config).unwrap();
+
+        let names = vec!["Invoices_2023".to_string(), "temp_build".to_string()];
+        let result = test_rules(names, Some("work".to_string()), &config_manager).await;
+        assert!(result.is_ok());
+
+        let result = test_rules(Vec::new(), Some("work".to_string()), &config_manager).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rules_lint_with_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config.json"))).unwrap();
+
+        let config = Config {
+            rules: vec![
+                Rule::new(PatternSpec::new_glob("*"), PathBuf::from("/archive"), "{name}".to_string()),
+                Rule::new(PatternSpec::new_contains("invoice"), PathBuf::from("/archive/invoices"), "{name}".to_string())
+                    .with_priority(1),
+            ],
+            ..Default::default()
+        };
+        config_manager.save_config("work", &config).unwrap();
+
+        assert!(lint_profile_rules(Some("work".to_string()), &config_manager).await.is_ok());
+        assert!(lint_profile_rules(Some("missing".to_string()), &config_manager).await.is_err());
+    }
+
+    #[test]
+    fn test_check_rules_structure() {
+        let temp_dir = TempDir::new().unwrap();
+        let dest = temp_dir.path().to_path_buf();
+        let archive = Rule::new(PatternSpec::new_glob("project_*"), dest.clone(), "{yyyy}/{name}".to_string());
+        let mut disabled = Rule::new(PatternSpec::new_contains("old"), dest.clone(), "{name}".to_string());
+        disabled.enabled = false;
+        let mut bad_dates = Rule::new(PatternSpec::new_contains("report"), dest.join("reports"), "{name}".to_string());
+        bad_dates.date_formats = vec!["%Y-%m".to_string(), "no digits".to_string()];
+        let rules = vec![
+            archive.clone(),
+            Rule::new(PatternSpec::new_regex("(unclosed"), dest.clone(), "{name}".to_string()),
+            Rule::new(PatternSpec::new_glob("*.bak"), dest.clone(), "{unknown}".to_string()),
+            Rule::new(PatternSpec::new_glob("*.log"), PathBuf::from("logs"), "{name}".to_string()),
+            Rule { id: uuid::Uuid::new_v4(), priority: 5, ..archive },
+            disabled,
+            bad_dates,
+        ];
+
+        let issues = check_rules(&rules, &NormalizationOptions::default());
+        let summary: Vec<_> = issues.iter().map(|issue| (issue.rule_index, issue.severity)).collect();
+        assert_eq!(summary, vec![
+            (1, IssueSeverity::Error),
+            (2, IssueSeverity::Error),
+            (3, IssueSeverity::Error),
+            (4, IssueSeverity::Warning),
+            (5, IssueSeverity::Warning),
+            (6, IssueSeverity::Error),
+            (6, IssueSeverity::Warning),
+        ]);
+        assert!(issues[6].message.contains("does not exist yet"));
+    }
+
+    #[tokio::test]
+    async fn test_rules_lint_fails_on_structural_problems() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config.json"))).unwrap();
+
+        let config = Config {
+            rules: vec![
+                Rule::new(PatternSpec::new_glob("*"), temp_dir.path().to_path_buf(), "{name".to_string()),
+            ],
+            ..Default::default()
+        };
+        config_manager.save_config("work", &config).unwrap();
+
+        assert!(lint_profile_rules(Some("work".to_string()), &config_manager).await.is_err());
+    }
+
+    #[test]
+    fn test_describe_rule() {
+        let rule = Rule::new(PatternSpec::new_glob("*.tmp").exclude(), PathBuf::from("/trash"), "{name}".to_string());
+        assert_eq!(describe_rule(&rule), "[exclude 0] Glob: \"*.tmp\" (case-insensitive)");
+    }
+}
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+use anyhow::{Result, Context};
+use tracing::info;
+
+use filemover_types::{JournalFormat, PlanOptions};
+use filemover_planner::MovePlanner;
+use crate::config_manager::ConfigManager;
+use super::apply::{apply_command, ApplyOptions};
+use super::plan::{load_scan_results, print_plan_summary, save_move_plan};
+use super::scan::{scan_command, ScanCommandOptions};
+
+const SCAN_FILE_NAME: &str = "scan_results.json";
+const PLAN_FILE_NAME: &str = "move_plan.json";
+
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// Directory for the scan results, plan and journal (defaults to a new directory under the config directory)
+    pub run_dir: Option<PathBuf>,
+    pub journal_format: JournalFormat,
+    /// Apply without asking for confirmation
+    pub skip_confirmation: bool,
+}
+
+/// Scan, plan and apply in one go, keeping every intermediate file in the run directory
+pub async fn run_command(
+    roots: Vec<PathBuf>,
+    profile: Option<String>,
+    options: RunOptions,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    let config = config_manager.load_config(profile.as_deref())?;
+    if config.rules.is_empty() {
+        anyhow::bail!("No rules configured in the profile.");
+    }
+
+    let run_dir = options.run_dir.clone().unwrap_or_else(|| default_run_dir(config_manager));
+    std::fs::create_dir_all(&run_dir)
+        .with_context(|| format!("Failed to create run directory: {}", run_dir.display()))?;
+    pretty_println!("📁 Run directory: {}", run_dir.display());
+
+    // Scan
+    let cancel_token = super::ctrl_c_cancellation_token();
+    let scan_path = run_dir.join(SCAN_FILE_NAME);
+    scan_command(roots, Some(scan_path.clone()), profile, ScanCommandOptions::default(), config_manager)
+        .await
+        .context("Scan failed")?;
+    if cancel_token.is_cancelled() {
+        pretty_println!("⏹️  Run cancelled after an incomplete scan - nothing was moved.");
+        return Ok(());
+    }
+
+    // Plan
+    let folder_hits = load_scan_results(&scan_path)
+        .context("Failed to load scan results")?;
+    let plan_options = PlanOptions {
+        rename_suffix: config.rename_suffix.clone(),
+        ..Default::default()
+    };
+    let plan = MovePlanner::new()
+        .with_cancellation(cancel_token.clone())
+        .create_plan(&folder_hits, &config.rules, plan_options)
+        .context("Failed to generate move plan")?;
+    let plan_path = run_dir.join(PLAN_FILE_NAME);
+    save_move_plan(&plan, &plan_path)
+        .context("Failed to save move plan")?;
+    print_plan_summary(&plan);
+    info!("Move plan saved to: {}", plan_path.display());
+
+    if filemover_planner::nodes_in_execution_order(&plan).is_empty() {
+        pretty_println!("\nNothing to move.");
+        return Ok(());
+    }
+
+    // Apply (asks for confirmation unless --yes)
+    let journal_path = run_dir.join(filemover_journal::journal_file_name(options.journal_format, chrono::Utc::now()));
+    apply_command(
+        plan_path,
+        Some(journal_path),
+        options.journal_format,
+        options.skip_confirmation,
+        false,
+        ApplyOptions::default(),
+        config_manager,
+    ).await
+}
+
+fn default_run_dir(config_manager: &ConfigManager) -> PathBuf {
+    config_manager.get_config_dir()
+        .join("runs")
+        .join(chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::{Config, PatternSpec, Rule};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_run_writes_all_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("data");
+        std::fs::create_dir_all(root.join("project_a")).unwrap();
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+
+        let config_manager = ConfigManager::new(Some(temp_dir.path().join("config/config.json"))).unwrap();
+        let config = Config {
+            roots: vec![root.clone()],
+            rules: vec![Rule::new(PatternSpec::new_glob("project_*"), temp_dir.path().join("archive"), "{name}".to_string())],
+            ..Default::default()
+        };
+        config_manager.save_config("work", &config).unwrap();
+
+        let run_dir = temp_dir.path().join("run");
+        let options = RunOptions {
+            run_dir: Some(run_dir.clone()),
+            journal_format: JournalFormat::Jsonl,
+            skip_confirmation: true,
+        };
+        run_command(Vec::new(), Some("work".to_string()), options, &config_manager).await.unwrap();
+
+        assert_eq!(load_scan_results(&run_dir.join(SCAN_FILE_NAME)).unwrap().len(), 1);
+        assert!(run_dir.join(PLAN_FILE_NAME).exists());
+        let journals = filemover_journal::list_journals(&run_dir).unwrap();
+        assert_eq!(journals.len(), 1);
+    }
+}
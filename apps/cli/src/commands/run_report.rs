@@ -0,0 +1,412 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+use uuid::Uuid;
+
+use filemover_types::{JournalEntry, JournalReadReport, ResultKind, Rule};
+use super::dry_run::format_duration;
+use super::plan_report::{format_size, html_escape, markdown_escape};
+use super::undo::load_checked_journal;
+
+/// Operations shown in the slowest-operations list
+const SLOWEST_COUNT: usize = 5;
+
+/// Document format for `report`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum RunReportFormat {
+    #[default]
+    Markdown,
+    /// A single self-contained HTML page
+    Html,
+}
+
+/// Operation counts of one rule or destination volume
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    ok: u64,
+    failed: u64,
+    skipped: u64,
+    /// Known sizes of the completed operations
+    bytes: u64,
+    /// Completed operations without a recorded size
+    unknown_size: u64,
+}
+
+impl Tally {
+    fn record(&mut self, entry: &JournalEntry) {
+        match entry.result {
+            ResultKind::Ok => {
+                self.ok += 1;
+                match entry.metadata.as_ref().and_then(|metadata| metadata.size_bytes) {
+                    Some(size) => self.bytes += size,
+                    None => self.unknown_size += 1,
+                }
+            }
+            ResultKind::Failed => self.failed += 1,
+            ResultKind::Skip => self.skipped += 1,
+        }
+    }
+
+    fn size(&self) -> String {
+        match self.unknown_size {
+            0 => format_size(self.bytes),
+            unknown => format!("{} (+{} unknown)", format_size(self.bytes), unknown),
+        }
+    }
+}
+
+/// Everything the report shows, gathered once for both formats
+struct RunReport<'a> {
+    journal_path: &'a Path,
+    journal: &'a JournalReadReport,
+    total: Tally,
+    by_rule: BTreeMap<String, Tally>,
+    by_volume: BTreeMap<String, Tally>,
+    started: Option<DateTime<Utc>>,
+    finished: Option<DateTime<Utc>>,
+    /// Time since the previous entry, for executed operations (the first entry has none)
+    durations: Vec<(f64, &'a JournalEntry)>,
+}
+
+impl<'a> RunReport<'a> {
+    fn new(journal_path: &'a Path, journal: &'a JournalReadReport, rules: &[Rule]) -> Self {
+        let rule_names = rule_names(rules);
+
+        let mut total = Tally::default();
+        let mut by_rule: BTreeMap<String, Tally> = BTreeMap::new();
+        let mut by_volume: BTreeMap<String, Tally> = BTreeMap::new();
+        for entry in &journal.entries {
+            total.record(entry);
+            let rule = match entry.rule_id {
+                Some(id) => rule_names.get(&id).cloned().unwrap_or_else(|| id.to_string()),
+                None => "(no rule)".to_string(),
+            };
+            by_rule.entry(rule).or_default().record(entry);
+            by_volume.entry(volume_of(&entry.dest)).or_default().record(entry);
+        }
+
+        // Entries are written when an operation finishes, so the gap to the previous one is its duration
+        let mut durations: Vec<(f64, &JournalEntry)> = journal.entries.windows(2)
+            .filter(|pair| pair[1].result != ResultKind::Skip)
+            .map(|pair| ((pair[1].when_utc - pair[0].when_utc).num_milliseconds().max(0) as f64 / 1000.0, &pair[1]))
+            .collect();
+        durations.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        Self {
+            journal_path,
+            journal,
+            total,
+            by_rule,
+            by_volume,
+            started: journal.entries.first().map(|entry| entry.when_utc),
+            finished: journal.summary.as_ref().map(|summary| summary.completed_utc)
+                .or_else(|| journal.entries.last().map(|entry| entry.when_utc)),
+            durations,
+        }
+    }
+
+    fn failures(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.journal.entries.iter().filter(|entry| entry.result == ResultKind::Failed)
+    }
+
+    /// Label and value rows of the summary section
+    fn summary_rows(&self) -> Vec<(&'static str, String)> {
+        let time = |time: Option<DateTime<Utc>>| {
+            time.map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string()).unwrap_or_else(|| "-".to_string())
+        };
+        let mut rows = vec![
+            ("Journal", self.journal_path.display().to_string()),
+            ("Started", time(self.started)),
+            ("Finished", time(self.finished)),
+            ("Completed", self.total.ok.to_string()),
+            ("Failed", self.total.failed.to_string()),
+            ("Skipped", self.total.skipped.to_string()),
+            ("Size moved", self.total.size()),
+        ];
+        if self.journal.summary.is_none() {
+            rows.push(("Note", "The journal has no completion record - the run may have been interrupted".to_string()));
+        }
+        if !self.journal.is_intact() {
+            rows.push(("Integrity problems", self.journal.problems.len().to_string()));
+        }
+        rows
+    }
+
+    /// Label and value rows of the duration section
+    fn duration_rows(&self) -> Vec<(&'static str, String)> {
+        let mut rows = Vec::new();
+        if let (Some(started), Some(finished)) = (self.started, self.finished) {
+            let elapsed = (finished - started).num_milliseconds().max(0) as f64 / 1000.0;
+            rows.push(("Total", format_duration(elapsed)));
+        }
+        if !self.durations.is_empty() {
+            let mut secs: Vec<f64> = self.durations.iter().map(|(secs, _)| *secs).collect();
+            secs.sort_by(f64::total_cmp);
+            rows.push(("Average per operation", format_duration(secs.iter().sum::<f64>() / secs.len() as f64)));
+            rows.push(("Median", format_duration(secs[secs.len() / 2])));
+            rows.push(("Longest", format_duration(secs[secs.len() - 1])));
+        }
+        rows
+    }
+
+    fn slowest(&self) -> &[(f64, &'a JournalEntry)] {
+        &self.durations[..self.durations.len().min(SLOWEST_COUNT)]
+    }
+
+    fn undo_command(&self) -> String {
+        format!("filemover undo --journal \"{}\"", self.journal_path.display())
+    }
+}
+
+/// Readable name of each rule: its label, or its id when it has none
+pub(crate) fn rule_names(rules: &[Rule]) -> BTreeMap<Uuid, String> {
+    rules.iter()
+        .map(|rule| (rule.id, rule.label.clone().unwrap_or_else(|| rule.id.to_string())))
+        .collect()
+}
+
+/// Drive or share of a destination, read from the path so journals from other machines work too
+fn volume_of(path: &Path) -> String {
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) => prefix.as_os_str().to_string_lossy().into_owned(),
+        Some(Component::RootDir) => "/".to_string(),
+        _ => "(relative)".to_string(),
+    }
+}
+
+pub async fn report_command(
+    journal_file: PathBuf,
+    format: RunReportFormat,
+    output: Option<PathBuf>,
+    rules: &[Rule],
+) -> Result<()> {
+    let journal = load_checked_journal(&journal_file)
+        .with_context(|| format!("Failed to load journal {}", journal_file.display()))?;
+
+    let mut document = None;
+    let output = output.filter(|path| path != Path::new("-"));
+    match &output {
+        Some(path) => {
+            let file = File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let mut out = BufWriter::new(file);
+            write_run_report(&journal_file, &journal, rules, format, &mut out)?;
+            out.flush().context("Failed to write run report")?;
+            pretty_println!("📝 Run report written to {}", path.display());
+        }
+        // Only pretty output leaves stdout to the report itself; JSON carries it in the result
+        None if crate::output::is_pretty() => {
+            let mut out = std::io::stdout().lock();
+            write_run_report(&journal_file, &journal, rules, format, &mut out)?;
+        }
+        None if crate::output::output_mode() == crate::output::OutputMode::Json => {
+            let mut out = Vec::new();
+            write_run_report(&journal_file, &journal, rules, format, &mut out)?;
+            document = Some(String::from_utf8(out).context("Run report is not valid UTF-8")?);
+        }
+        None => {}
+    }
+
+    let report = RunReport::new(&journal_file, &journal, rules);
+    crate::output::emit_result("report", &RunReportResult {
+        journal: journal_file.clone(),
+        output,
+        completed: report.total.ok,
+        failed: report.total.failed,
+        skipped: report.total.skipped,
+        bytes: report.total.bytes,
+        integrity_problems: journal.problems.len(),
+        document,
+    })
+}
+
+/// Result of `report` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct RunReportResult {
+    journal: PathBuf,
+    /// File the report was written to; none when it went to stdout
+    output: Option<PathBuf>,
+    completed: u64,
+    failed: u64,
+    skipped: u64,
+    bytes: u64,
+    integrity_problems: usize,
+    /// The rendered report when JSON output replaces it on stdout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    document: Option<String>,
+}
+
+/// Render the report; `rules` supplies readable names for the rules in the journal
+pub fn write_run_report(
+    journal_path: &Path,
+    journal: &JournalReadReport,
+    rules: &[Rule],
+    format: RunReportFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report = RunReport::new(journal_path, journal, rules);
+    match format {
+        RunReportFormat::Markdown => write_markdown(&report, out),
+        RunReportFormat::Html => write_html(&report, out),
+    }
+}
+
+fn write_markdown(report: &RunReport, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "# Run Report\n")?;
+
+    writeln!(out, "## Summary\n")?;
+    writeln!(out, "| | |\n|---|---|")?;
+    for (label, value) in report.summary_rows() {
+        writeln!(out, "| {} | {} |", label, markdown_escape(&value))?;
+    }
+
+    for (title, tallies) in [("By Rule", &report.by_rule), ("By Destination Volume", &report.by_volume)] {
+        writeln!(out, "\n## {}\n", title)?;
+        writeln!(out, "| | Completed | Failed | Skipped | Size |\n|---|---:|---:|---:|---:|")?;
+        for (name, tally) in tallies {
+            writeln!(out, "| {} | {} | {} | {} | {} |",
+                     markdown_escape(name), tally.ok, tally.failed, tally.skipped, tally.size())?;
+        }
+    }
+
+    writeln!(out, "\n## Failures\n")?;
+    let mut any = false;
+    for entry in report.failures() {
+        any = true;
+        writeln!(out, "- `{}` → `{}`: {}",
+                 entry.source.display(), entry.dest.display(),
+                 markdown_escape(entry.message.as_deref().unwrap_or("unknown error")))?;
+    }
+    if !any {
+        writeln!(out, "No failures.")?;
+    }
+
+    writeln!(out, "\n## Duration\n")?;
+    writeln!(out, "| | |\n|---|---|")?;
+    for (label, value) in report.duration_rows() {
+        writeln!(out, "| {} | {} |", label, value)?;
+    }
+    if !report.slowest().is_empty() {
+        writeln!(out, "\nSlowest operations:\n")?;
+        for (secs, entry) in report.slowest() {
+            writeln!(out, "- {} `{}` ({:?})", format_duration(*secs), entry.source.display(), entry.op)?;
+        }
+    }
+
+    writeln!(out, "\n## Undo\n")?;
+    writeln!(out, "```\n{}\n```", report.undo_command())?;
+
+    Ok(())
+}
+
+fn write_html(report: &RunReport, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Run Report</title>")?;
+    writeln!(out, "<style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse;margin-bottom:1.5em}}\
+th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}th{{background:#f3f3f3}}\
+li.failed{{color:#b00020}}pre{{background:#f3f3f3;padding:8px}}</style>")?;
+    writeln!(out, "</head>\n<body>\n<h1>Run Report</h1>")?;
+
+    writeln!(out, "<h2>Summary</h2>\n<table>")?;
+    for (label, value) in report.summary_rows() {
+        writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", label, html_escape(&value))?;
+    }
+    writeln!(out, "</table>")?;
+
+    for (title, tallies) in [("By Rule", &report.by_rule), ("By Destination Volume", &report.by_volume)] {
+        writeln!(out, "<h2>{}</h2>\n<table>\n<tr><th></th><th>Completed</th><th>Failed</th><th>Skipped</th><th>Size</th></tr>", title)?;
+        for (name, tally) in tallies {
+            writeln!(out, "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                     html_escape(name), tally.ok, tally.failed, tally.skipped, tally.size())?;
+        }
+        writeln!(out, "</table>")?;
+    }
+
+    writeln!(out, "<h2>Failures</h2>")?;
+    let failures = report.failures().collect::<Vec<_>>();
+    if failures.is_empty() {
+        writeln!(out, "<p>No failures.</p>")?;
+    } else {
+        writeln!(out, "<ul>")?;
+        for entry in failures {
+            writeln!(out, "<li class=\"failed\"><code>{}</code> &rarr; <code>{}</code>: {}</li>",
+                     html_escape(&entry.source.display().to_string()),
+                     html_escape(&entry.dest.display().to_string()),
+                     html_escape(entry.message.as_deref().unwrap_or("unknown error")))?;
+        }
+        writeln!(out, "</ul>")?;
+    }
+
+    writeln!(out, "<h2>Duration</h2>\n<table>")?;
+    for (label, value) in report.duration_rows() {
+        writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", label, value)?;
+    }
+    writeln!(out, "</table>")?;
+    if !report.slowest().is_empty() {
+        writeln!(out, "<p>Slowest operations:</p>\n<ol>")?;
+        for (secs, entry) in report.slowest() {
+            writeln!(out, "<li>{} <code>{}</code> ({:?})</li>",
+                     format_duration(*secs), html_escape(&entry.source.display().to_string()), entry.op)?;
+        }
+        writeln!(out, "</ol>")?;
+    }
+
+    writeln!(out, "<h2>Undo</h2>\n<pre>{}</pre>", html_escape(&report.undo_command()))?;
+    writeln!(out, "</body>\n</html>")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use filemover_types::{EntryMetadata, OpKind, PatternSpec};
+
+    fn entry(source: &str, dest: &str, rule_id: Option<Uuid>, offset_secs: i64) -> JournalEntry {
+        let mut entry = JournalEntry::new(PathBuf::from(source), PathBuf::from(dest), OpKind::Move)
+            .with_rule_id(rule_id)
+            .with_metadata(EntryMetadata::default().with_size(1024 * 1024));
+        entry.when_utc = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap() + Duration::seconds(offset_secs);
+        entry
+    }
+
+    #[test]
+    fn test_run_report_formats() {
+        let mut rule = Rule::new(PatternSpec::new_glob("*"), PathBuf::from("/archive"), "{name}".to_string());
+        rule.label = Some("Projects".to_string());
+
+        let journal = JournalReadReport {
+            entries: vec![
+                entry("/data/a", "/archive/a", Some(rule.id), 0),
+                entry("/data/b<c", "/archive/b", Some(rule.id), 90),
+                entry("/data/d", "/archive/d", None, 100).failed("Access denied"),
+            ],
+            summary: None,
+            problems: Vec::new(),
+        };
+        let render = |format| {
+            let mut out = Vec::new();
+            write_run_report(Path::new("run.jsonl"), &journal, std::slice::from_ref(&rule), format, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let markdown = render(RunReportFormat::Markdown);
+        assert!(markdown.contains("| Projects | 2 | 0 | 0 | 2.00 MB |"));
+        assert!(markdown.contains("| (no rule) | 0 | 1 | 0 | 0.00 MB |"));
+        assert!(markdown.contains("| / | 2 | 1 | 0 | 2.00 MB |"));
+        assert!(markdown.contains("- `/data/d` → `/archive/d`: Access denied"));
+        assert!(markdown.contains("| Total | 1m 40s |"));
+        assert!(markdown.contains("- 1m 30s `/data/b<c` (Move)"));
+        assert!(markdown.contains("filemover undo --journal \"run.jsonl\""));
+        assert!(markdown.contains("no completion record"));
+
+        let html = render(RunReportFormat::Html);
+        assert!(html.contains("<code>/data/b&lt;c</code>"));
+        assert!(html.contains("<li class=\"failed\">"));
+    }
+}
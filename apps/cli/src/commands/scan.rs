@@ -1,27 +1,75 @@
-use std::path::PathBuf;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
+use serde::Serialize;
 use tracing::{info, debug};
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::ProgressStyle;
 
-use filemover_types::{ScanOptions, Rule, FolderHit};
-use filemover_scanner::FolderScanner;
+use std::sync::{Arc, Mutex};
+use filemover_types::{ResultOrder, ScanProgress, Rule, FolderHit, TieBreakStrategy};
+use filemover_scanner::{CheckpointedScanner, FolderScanner, RootWarning, RuleStats, ScanCheckpoint, ScanEvent, ScanOutcome};
 use crate::config_manager::ConfigManager;
+use crate::error::CliError;
+use super::scan_output::{write_scan_results, ScanOutputFormat, ScanResultWriter};
+
+#[derive(Debug, Clone, Default)]
+pub struct ScanCommandOptions {
+    pub include_files: bool,
+    pub calculate_sizes: bool,
+    pub exclude_patterns: Vec<String>,
+    pub incremental: bool,
+    pub state_file: Option<PathBuf>,
+    pub resume: Option<PathBuf>,
+    pub format: ScanOutputFormat,
+    pub explain: bool,
+    /// File with one root per line ("-" for stdin), added to the roots given directly
+    pub roots_from: Option<PathBuf>,
+}
 
 pub async fn scan_command(
     roots: Vec<PathBuf>,
     output_file: Option<PathBuf>,
     profile: Option<String>,
+    options: ScanCommandOptions,
     config_manager: &ConfigManager,
 ) -> Result<()> {
     info!("Starting folder scan");
     
     // Load configuration
-    let config = config_manager.load_config(profile.as_deref())?;
+    let mut config = config_manager.load_config(profile.as_deref())?;
+    
+    // Command-line flags can only turn these options on
+    config.options.include_files |= options.include_files;
+    config.options.calculate_sizes |= options.calculate_sizes;
+    config.options.excluded_patterns.extend(options.exclude_patterns);
+    
+    // Keep folder sizes between runs unless a cache location is configured
+    if config.options.calculate_sizes && config.options.size_cache.is_none() {
+        config.options.size_cache = Some(config_manager.get_config_dir().join("size_cache.json"));
+    }
+    
+    // A resumed scan continues with the roots stored in its checkpoint
+    let resumed = match &options.resume {
+        Some(path) => Some(ScanCheckpoint::load(path)
+            .with_context(|| format!("Failed to load scan checkpoint {}", path.display()))?),
+        None => None,
+    };
+    
+    let mut roots = roots;
+    if let Some(list) = &options.roots_from {
+        let listed = read_root_list_file(list)?;
+        if listed.is_empty() && roots.is_empty() {
+            return Err(CliError::invalid_args(format!("No usable root directories in {}", list.display())).into());
+        }
+        roots.extend(listed);
+    }
     
     // Use provided roots or fall back to config
-    let scan_roots = if roots.is_empty() {
+    let scan_roots = if let Some(checkpoint) = &resumed {
+        checkpoint.roots.clone()
+    } else if roots.is_empty() {
         if config.roots.is_empty() {
-            anyhow::bail!("No root directories specified. Use --roots or configure in profile.");
+            return Err(CliError::invalid_args("No root directories specified. Use --roots or configure in profile.").into());
         }
         config.roots.clone()
     } else {
@@ -36,7 +84,7 @@ pub async fn scan_command(
     // Validate roots
     for root in &scan_roots {
         if !root.exists() {
-            anyhow::bail!("Root directory does not exist: {}", root.display());
+            return Err(CliError::file_operation(root.clone(), "Root directory does not exist").into());
         }
         if !root.is_dir() {
             anyhow::bail!("Root path is not a directory: {}", root.display());
@@ -44,7 +92,7 @@ pub async fn scan_command(
     }
     
     // Create progress bar
-    let progress = ProgressBar::new_spinner();
+    let progress = crate::progress::new_spinner();
     progress.set_style(
         ProgressStyle::default_spinner()
             .template("{spinner:.green} {msg}")
@@ -52,76 +100,380 @@ pub async fn scan_command(
     );
     progress.set_message("Initializing scanner...");
     
+    let output_path = match output_file {
+        Some(path) => path,
+        None => {
+            let file_name = format!(
+                "scan_results_{}.{}",
+                chrono::Utc::now().format("%Y%m%d_%H%M%S"),
+                options.format.extension()
+            );
+            super::artifact_path(config.scan_output_dir.as_deref(), file_name)?
+        }
+    };
+    let to_stdout = output_path == Path::new("-");
+    
+    // Hits can be written as they are found unless they have to be sorted, merged or resolved first
+    let interactive = config.options.tie_break == TieBreakStrategy::Interactive
+        && !to_stdout
+        && crate::output::is_pretty()
+        && io::stdin().is_terminal();
+    let streaming = !options.incremental
+        && !interactive
+        && config.options.result_order == ResultOrder::Discovery;
+    let writer = if streaming {
+        let mut writer = ScanResultWriter::create(&output_path, options.format)?;
+        // Hits found before the interruption are not reported again by the scanner
+        for hit in resumed.iter().flat_map(|checkpoint| &checkpoint.hits) {
+            writer.write_hit(hit)?;
+        }
+        Some(writer)
+    } else {
+        None
+    };
+    let writer = Arc::new(Mutex::new(writer));
+    let write_error = Arc::new(Mutex::new(None));
+    
     // Initialize scanner
-    let scanner = FolderScanner::new(config.rules.clone(), config.options.clone())
-        .context("Failed to initialize folder scanner")?;
+    let progress_bar = progress.clone();
+    let hit_writer = writer.clone();
+    let hit_write_error = write_error.clone();
+    let scanner = FolderScanner::from_config(&config)
+        .context("Failed to initialize folder scanner")?
+        .with_cancellation(super::ctrl_c_cancellation_token())
+        .with_progress(Arc::new(move |event: &ScanEvent| match event {
+            ScanEvent::Progress(update) => {
+                progress_bar.set_message(format_scan_progress(update));
+            }
+            ScanEvent::Hit(hit) => {
+                if let Some(writer) = hit_writer.lock().unwrap().as_mut() {
+                    if let Err(e) = writer.write_hit(hit) {
+                        hit_write_error.lock().unwrap().get_or_insert(e);
+                    }
+                }
+            }
+        }));
     
     progress.set_message("Scanning directories...");
     
     // Perform scan
-    let scan_results = scanner.scan_roots(&scan_roots)
-        .context("Failed to scan directories")?;
+    let outcome = if options.incremental {
+        let state_file = options.state_file.clone()
+            .unwrap_or_else(|| config_manager.get_config_dir().join("scan_state.json"));
+        let hits = incremental_scan(&scanner, &scan_roots, &state_file)?;
+        ScanOutcome {
+            rule_stats: RuleStats::from_hits(&hits),
+            hits,
+            cancelled: false,
+            ..Default::default()
+        }
+    } else {
+        let checkpoint_path = options.resume.clone().unwrap_or_else(|| {
+            std::env::temp_dir().join(format!(
+                "filemover-scan-{}.json",
+                chrono::Utc::now().format("%Y%m%d_%H%M%S")
+            ))
+        });
+        let mut checkpoint = resumed.unwrap_or_else(|| ScanCheckpoint::new(scan_roots.clone()));
+        let outcome = CheckpointedScanner::new(&scanner, &checkpoint_path)
+            .scan(&mut checkpoint);
+        match &outcome {
+            Ok(outcome) if !outcome.cancelled => {
+                if let Err(e) = std::fs::remove_file(&checkpoint_path) {
+                    debug!("Failed to remove scan checkpoint {}: {}", checkpoint_path.display(), e);
+                }
+            }
+            _ => print_resume_hint(&checkpoint_path),
+        }
+        outcome.context("Failed to scan directories")?
+    };
+    let mut scan_results = outcome.hits;
+    let mut rule_stats = outcome.rule_stats;
+    
+    if outcome.cancelled {
+        progress.abandon_with_message("Scan cancelled");
+        eprintln!("⚠️  Scan was cancelled - the results below are incomplete");
+    } else {
+        progress.finish_with_message("Scan completed");
+    }
     
-    progress.finish_with_message("Scan completed");
+    for root_warning in &outcome.root_warnings {
+        eprintln!("⚠️  Skipped {}: {}", root_warning.root.display(), root_warning.message);
+        crate::error::record_warning(format!("Skipped root {}: {}", root_warning.root.display(), root_warning.message));
+    }
     
-    // Display results summary
-    print_scan_summary(&scan_results);
+    if interactive {
+        resolve_multiple_matches(&scanner, &config.rules, &mut scan_results, &mut rule_stats)?;
+    }
     
     // Save results
-    let output_path = output_file.unwrap_or_else(|| {
-        PathBuf::from(format!(
-            "scan_results_{}.json",
-            chrono::Utc::now().format("%Y%m%d_%H%M%S")
-        ))
-    });
+    if let Some(e) = write_error.lock().unwrap().take() {
+        return Err(e.context("Failed to save scan results"));
+    }
+    let writer = writer.lock().unwrap().take();
+    match writer {
+        Some(writer) => {
+            writer.finish().context("Failed to save scan results")?;
+        }
+        None => {
+            write_scan_results(&scan_results, &output_path, options.format)
+                .context("Failed to save scan results")?;
+        }
+    }
     
-    save_scan_results(&scan_results, &output_path)
-        .context("Failed to save scan results")?;
+    // Keep stdout clean for the results when they are piped
+    if !to_stdout {
+        print_scan_summary(&scan_results, &rule_stats, &config.rules);
+        if options.explain {
+            print_hit_explanations(&scanner, &scan_roots, &scan_results);
+        }
+    }
     
     info!("Scan results saved to: {}", output_path.display());
     
+    if !to_stdout {
+        crate::output::emit_result("scan", &ScanResult::new(&output_path, &scan_results, &outcome.root_warnings, outcome.cancelled))?;
+    }
+    
+    Ok(())
+}
+
+/// Result of `scan` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct ScanResult {
+    output: PathBuf,
+    folders: usize,
+    files: usize,
+    multiple_matches: usize,
+    cancelled: bool,
+    skipped_roots: Vec<SkippedRoot>,
+}
+
+#[derive(Debug, Serialize)]
+struct SkippedRoot {
+    root: PathBuf,
+    message: String,
+}
+
+impl ScanResult {
+    fn new(output: &Path, hits: &[FolderHit], root_warnings: &[RootWarning], cancelled: bool) -> Self {
+        let files = hits.iter().filter(|hit| !hit.is_dir).count();
+        Self {
+            output: output.to_path_buf(),
+            folders: hits.len() - files,
+            files,
+            multiple_matches: hits.iter().filter(|hit| hit.matched_rules.len() > 1).count(),
+            cancelled,
+            skipped_roots: root_warnings.iter()
+                .map(|warning| SkippedRoot { root: warning.root.clone(), message: warning.message.clone() })
+                .collect(),
+        }
+    }
+}
+
+fn read_root_list_file(list: &Path) -> Result<Vec<PathBuf>> {
+    if list == Path::new("-") {
+        return read_root_list(io::stdin().lock(), "stdin");
+    }
+    let file = std::fs::File::open(list)
+        .map_err(|e| CliError::file_operation(list.to_path_buf(), e.to_string()))?;
+    read_root_list(io::BufReader::new(file), &list.display().to_string())
+}
+
+/// One root per line; blank lines and `#` comments are ignored. Lines that are not existing
+/// directories are reported and skipped so one bad entry does not stop the scan.
+fn read_root_list(reader: impl BufRead, source: &str) -> Result<Vec<PathBuf>> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read root list {}", source))?;
+        // Paths copied from Explorer ("Copy as path") come with quotes
+        let entry = line.trim().trim_matches('"');
+        if entry.is_empty() || entry.starts_with('#') {
+            continue;
+        }
+
+        let root = PathBuf::from(entry);
+        let problem = if !root.exists() {
+            Some("does not exist")
+        } else if !root.is_dir() {
+            Some("is not a directory")
+        } else if roots.contains(&root) {
+            Some("is listed twice")
+        } else {
+            None
+        };
+        match problem {
+            Some(problem) => {
+                let message = format!("{}:{}: {} {}", source, index + 1, root.display(), problem);
+                eprintln!("⚠️  Skipped root {}", message);
+                crate::error::record_warning(format!("Skipped root {}", message));
+            }
+            None => roots.push(root),
+        }
+    }
+    Ok(roots)
+}
+
+fn print_hit_explanations(scanner: &FolderScanner, roots: &[PathBuf], hits: &[FolderHit]) {
+    pretty_println!("\n=== Match Explanations ===");
+    for hit in hits {
+        let Some(root) = roots.iter().find(|root| hit.path.starts_with(root)) else {
+            continue;
+        };
+        match scanner.explain(root, &hit.path) {
+            Ok(explanation) => super::rules::print_explanation(&hit.path.display().to_string(), &explanation),
+            // The folder may have been moved or deleted since it was scanned
+            Err(e) => eprintln!("⚠️  Cannot explain {}: {}", hit.path.display(), e),
+        }
+    }
+}
+
+/// Ask which rule to use for each folder that several rules matched
+fn resolve_multiple_matches(
+    scanner: &FolderScanner,
+    rules: &[Rule],
+    hits: &mut [FolderHit],
+    rule_stats: &mut RuleStats,
+) -> Result<()> {
+    let pending = hits.iter().filter(|hit| hit.has_multiple_matches()).count();
+    if pending == 0 {
+        return Ok(());
+    }
+    pretty_println!("\n🔀 {} folders matched more than one rule", pending);
+    
+    for hit in hits.iter_mut().filter(|hit| hit.has_multiple_matches()) {
+        let candidates: Vec<&Rule> = hit.matched_rules.iter()
+            .filter_map(|id| rules.iter().find(|rule| rule.id == *id))
+            .collect();
+        
+        pretty_println!("\n{}", hit.path.display());
+        for (i, rule) in candidates.iter().enumerate() {
+            let current = if hit.matched_rule == Some(rule.id) { " (current)" } else { "" };
+            pretty_println!("  {}. {}{}", i + 1, super::rules::describe_rule(rule), current);
+        }
+        print!("Use rule [1-{}, Enter keeps current]: ", candidates.len());
+        io::stdout().flush()?;
+        
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+        let chosen = input.parse::<usize>().ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| candidates.get(i));
+        match (chosen, hit.matched_rule) {
+            (Some(rule), Some(previous)) => {
+                scanner.reassign_rule(hit, rule)
+                    .with_context(|| format!("Failed to reassign {}", hit.path.display()))?;
+                rule_stats.reassign(hit, previous);
+            }
+            _ => pretty_println!("Invalid choice, keeping the current rule"),
+        }
+    }
+    
     Ok(())
 }
 
-fn print_scan_summary(results: &[FolderHit]) {
-    println!("\n=== Scan Results ===");
-    println!("Total folders found: {}", results.len());
+fn print_resume_hint(checkpoint_path: &Path) {
+    if checkpoint_path.exists() {
+        eprintln!("💾 Progress saved. Resume with: filemover scan --resume {}", checkpoint_path.display());
+    }
+}
+
+pub(crate) fn format_scan_progress(progress: &ScanProgress) -> String {
+    let current = progress.current_path.as_ref()
+        .map(|p| format!(" - {}", p.display()))
+        .unwrap_or_default();
+    format!("Scanning: {} folders visited, {} matches{}",
+            progress.dirs_visited, progress.hits_found, current)
+}
+
+#[cfg(windows)]
+fn incremental_scan(scanner: &FolderScanner, roots: &[PathBuf], state_file: &Path) -> Result<Vec<FolderHit>> {
+    use filemover_scanner::{IncrementalScanner, IncrementalScanState, UsnChangeJournal};
+    
+    let mut state = IncrementalScanState::load(state_file)
+        .context("Failed to load incremental scan state")?;
+    
+    let hits = IncrementalScanner::new(scanner, UsnChangeJournal::new())
+        .scan_roots(roots, &mut state)
+        .context("Failed to scan directories incrementally")?;
+    
+    state.save(state_file)
+        .context("Failed to save incremental scan state")?;
+    debug!("Incremental scan state saved to: {}", state_file.display());
+    
+    Ok(hits)
+}
+
+#[cfg(not(windows))]
+fn incremental_scan(_scanner: &FolderScanner, _roots: &[PathBuf], _state_file: &Path) -> Result<Vec<FolderHit>> {
+    anyhow::bail!("Incremental scans use the NTFS change journal and are only available on Windows")
+}
+
+fn print_scan_summary(results: &[FolderHit], rule_stats: &RuleStats, rules: &[Rule]) {
+    pretty_println!("\n=== Scan Results ===");
+    let file_count = results.iter().filter(|hit| !hit.is_dir).count();
+    pretty_println!("Total folders found: {}", results.len() - file_count);
+    if file_count > 0 {
+        pretty_println!("Total files found: {}", file_count);
+    }
+    let empty_count = results.iter().filter(|hit| hit.is_empty_dir()).count();
+    if empty_count > 0 {
+        pretty_println!("Empty folders: {}", empty_count);
+    }
+    let multi_count = results.iter().filter(|hit| hit.has_multiple_matches()).count();
+    if multi_count > 0 {
+        pretty_println!("Matched by more than one rule: {}", multi_count);
+    }
+    
+    // Exclude rules can be worth reporting even when nothing matched
+    let rule_lines = format_rule_stats(rule_stats, rules);
+    if !rule_lines.is_empty() {
+        pretty_println!("\nBy rule:");
+        for line in &rule_lines {
+            pretty_println!("  {}", line);
+        }
+    }
     
     if results.is_empty() {
-        println!("No matching folders found.");
+        pretty_println!("No matching folders found.");
         return;
     }
     
-    // Group by matched rule
-    let mut rule_counts = std::collections::HashMap::new();
     let mut warning_counts = std::collections::HashMap::new();
+    let mut tag_counts = std::collections::BTreeMap::new();
     
     for hit in results {
-        if let Some(rule_id) = &hit.matched_rule {
-            *rule_counts.entry(*rule_id).or_insert(0) += 1;
-        }
-        
         for warning in &hit.warnings {
             *warning_counts.entry(format!("{:?}", warning)).or_insert(0) += 1;
         }
+        
+        for tag in &hit.tags {
+            *tag_counts.entry(tag.as_str()).or_insert(0) += 1;
+        }
     }
     
-    println!("\nMatched by rules:");
-    for (rule_id, count) in rule_counts {
-        println!("  Rule {}: {} folders", rule_id, count);
+    if !tag_counts.is_empty() {
+        pretty_println!("\nTagged:");
+        for (tag, count) in tag_counts {
+            pretty_println!("  {}: {} folders", tag, count);
+        }
     }
     
     if !warning_counts.is_empty() {
-        println!("\nWarnings:");
+        pretty_println!("\nWarnings:");
         for (warning, count) in warning_counts {
-            println!("  {}: {} folders", warning, count);
+            pretty_println!("  {}: {} folders", warning, count);
         }
     }
     
     // Show first few matches
-    println!("\nFirst 10 matches:");
+    pretty_println!("\nFirst 10 matches:");
     for (i, hit) in results.iter().take(10).enumerate() {
-        println!("  {}. {} -> {:?}", 
+        pretty_println!("  {}. {} -> {:?}", 
                  i + 1, 
                  hit.path.display(),
                  hit.dest_preview.as_ref().map(|p| p.display().to_string()).unwrap_or("(no preview)".to_string())
@@ -129,18 +481,37 @@ fn print_scan_summary(results: &[FolderHit]) {
     }
     
     if results.len() > 10 {
-        println!("  ... and {} more", results.len() - 10);
+        pretty_println!("  ... and {} more", results.len() - 10);
     }
 }
 
-fn save_scan_results(results: &[FolderHit], output_path: &PathBuf) -> Result<()> {
-    let json = serde_json::to_string_pretty(results)
-        .context("Failed to serialize scan results")?;
+/// One line per rule that matched or excluded something, in the order the rules are defined
+fn format_rule_stats(rule_stats: &RuleStats, rules: &[Rule]) -> Vec<String> {
+    let mut lines: Vec<String> = rules.iter()
+        .filter_map(|rule| {
+            let stats = rule_stats.get(&rule.id)?;
+            let mut counts = Vec::new();
+            if stats.folders_matched > 0 {
+                counts.push(format!("{} folders", stats.folders_matched));
+            }
+            if stats.total_bytes > 0 {
+                counts.push(format!("{:.2} MB", stats.total_bytes as f64 / 1024.0 / 1024.0));
+            }
+            if stats.folders_excluded > 0 {
+                counts.push(format!("{} folders excluded", stats.folders_excluded));
+            }
+            (!counts.is_empty()).then(|| format!("{}: {}", super::rules::describe_rule(rule), counts.join(", ")))
+        })
+        .collect();
     
-    std::fs::write(output_path, json)
-        .context("Failed to write scan results file")?;
-    
-    Ok(())
+    // Rules removed from the profile since the scan started
+    let unknown = rule_stats.iter()
+        .filter(|(id, stats)| stats.folders_matched > 0 && !rules.iter().any(|rule| rule.id == **id))
+        .count();
+    if unknown > 0 {
+        lines.push(format!("{} unknown rules", unknown));
+    }
+    lines
 }
 
 #[cfg(test)]
@@ -162,7 +533,7 @@ mod tests {
         let config_manager = ConfigManager::new(Some(config_path)).unwrap();
         
         // Should fail with empty roots
-        let result = scan_command(vec![], None, None, &config_manager).await;
+        let result = scan_command(vec![], None, None, ScanCommandOptions::default(), &config_manager).await;
         assert!(result.is_err());
     }
     
@@ -172,8 +543,135 @@ mod tests {
         let config_manager = ConfigManager::new(None).unwrap();
         
         let nonexistent = PathBuf::from("/nonexistent/path");
-        let result = scan_command(vec![nonexistent], None, None, &config_manager).await;
+        let result = scan_command(vec![nonexistent], None, None, ScanCommandOptions::default(), &config_manager).await;
         
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_scan_command_resumes_from_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("root");
+        std::fs::create_dir_all(root.join("done")).unwrap();
+        std::fs::create_dir_all(root.join("todo")).unwrap();
+        
+        let mut config = Config::default();
+        config.rules.push(Rule::new(
+            PatternSpec::new_glob("*"),
+            PathBuf::from("/dest"),
+            "{name}".to_string(),
+        ));
+        let config_path = temp_dir.path().join("config.json");
+        std::fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        let config_manager = ConfigManager::new(Some(config_path)).unwrap();
+        
+        let checkpoint_path = temp_dir.path().join("checkpoint.json");
+        let mut checkpoint = ScanCheckpoint::new(vec![root.clone()]);
+        checkpoint.completed_dirs.insert(root.join("done"));
+        checkpoint.save(&checkpoint_path).unwrap();
+        
+        let output = temp_dir.path().join("scan.json");
+        let options = ScanCommandOptions {
+            resume: Some(checkpoint_path.clone()),
+            ..Default::default()
+        };
+        scan_command(vec![], Some(output.clone()), None, options, &config_manager).await.unwrap();
+        
+        let hits: Vec<FolderHit> = filemover_types::from_versioned_json(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert!(hits.iter().any(|hit| hit.name == "todo"));
+        // A finished scan no longer needs its checkpoint
+        assert!(!checkpoint_path.exists());
+    }
+    
+    #[tokio::test]
+    async fn test_scan_command_writes_to_scan_output_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("root");
+        std::fs::create_dir_all(root.join("project")).unwrap();
+        
+        let output_dir = temp_dir.path().join("artifacts/scans");
+        let mut config = Config {
+            roots: vec![root],
+            scan_output_dir: Some(output_dir.clone()),
+            ..Default::default()
+        };
+        config.rules.push(Rule::new(
+            PatternSpec::new_glob("*"),
+            PathBuf::from("/dest"),
+            "{name}".to_string(),
+        ));
+        let config_path = temp_dir.path().join("config.json");
+        std::fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        let config_manager = ConfigManager::new(Some(config_path)).unwrap();
+        
+        scan_command(vec![], None, None, ScanCommandOptions::default(), &config_manager).await.unwrap();
+        
+        let written: Vec<_> = std::fs::read_dir(&output_dir).unwrap().collect();
+        assert_eq!(written.len(), 1);
+    }
+    
+    #[test]
+    fn test_read_root_list_skips_bad_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        std::fs::write(temp_dir.path().join("file.txt"), "").unwrap();
+
+        let list = format!(
+            "# roots\n{}\n\n\"{}\"\r\n{}\n{}\n{}\n",
+            first.display(),
+            second.display(),
+            temp_dir.path().join("missing").display(),
+            temp_dir.path().join("file.txt").display(),
+            first.display(),
+        );
+        let roots = read_root_list(io::Cursor::new(list), "list.txt").unwrap();
+        assert_eq!(roots, vec![first, second]);
+    }
+    
+    #[test]
+    fn test_format_scan_progress() {
+        let progress = ScanProgress {
+            dirs_visited: 120,
+            hits_found: 3,
+            current_path: Some(PathBuf::from("/data/projects")),
+        };
+        
+        assert_eq!(
+            format_scan_progress(&progress),
+            "Scanning: 120 folders visited, 3 matches - /data/projects"
+        );
+    }
+    
+    #[test]
+    fn test_format_rule_stats() {
+        let photos = Rule::new(PatternSpec::new_glob("*photo*"), PathBuf::from("/dest"), "{name}".to_string())
+            .with_label("Photos".to_string());
+        let temp = Rule::new(PatternSpec::new_glob("tmp*").exclude(), PathBuf::new(), String::new());
+        let unused = Rule::new(PatternSpec::new_glob("*.bak"), PathBuf::from("/dest"), "{name}".to_string());
+        
+        let mut stats = RuleStats::new();
+        let hit = FolderHit {
+            path: PathBuf::from("/data/photos"),
+            name: "photos".to_string(),
+            matched_rule: Some(photos.id),
+            matched_rules: vec![photos.id],
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: Vec::new(),
+            size_bytes: Some(2 * 1024 * 1024),
+            item_count: None,
+            is_dir: true,
+        };
+        stats.record_hit(&hit);
+        stats.record_excluded(temp.id);
+        stats.record_excluded(temp.id);
+        
+        let lines = format_rule_stats(&stats, &[photos, temp, unused]);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("\"Photos\"") && lines[0].ends_with("1 folders, 2.00 MB"));
+        assert!(lines[1].ends_with("2 folders excluded"));
+    }
+}
@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+use anyhow::{Result, Context};
+use serde::Serialize;
+use tracing::info;
+
+use filemover_scanner::{diff_scans, ScanDiff};
+use super::plan::load_scan_results;
+
+pub async fn scan_diff_command(
+    old_file: PathBuf,
+    new_file: PathBuf,
+    report_file: Option<PathBuf>,
+) -> Result<()> {
+    info!("Comparing scan results {} and {}", old_file.display(), new_file.display());
+
+    let old = load_scan_results(&old_file)
+        .with_context(|| format!("Failed to load {}", old_file.display()))?;
+    let new = load_scan_results(&new_file)
+        .with_context(|| format!("Failed to load {}", new_file.display()))?;
+
+    let diff = diff_scans(&old, &new);
+    print_scan_diff(&diff);
+
+    if let Some(path) = &report_file {
+        let json = serde_json::to_string_pretty(&diff)
+            .context("Failed to serialize scan diff")?;
+        std::fs::write(path, json)
+            .context("Failed to write scan diff report")?;
+        pretty_println!("\n📄 Report saved to: {}", path.display());
+    }
+
+    crate::output::emit_result("scan-diff", &ScanDiffResult {
+        old: old_file,
+        new: new_file,
+        report: report_file,
+        diff: &diff,
+    })
+}
+
+/// Result of `scan-diff` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct ScanDiffResult<'a> {
+    old: PathBuf,
+    new: PathBuf,
+    report: Option<PathBuf>,
+    #[serde(flatten)]
+    diff: &'a ScanDiff,
+}
+
+fn print_scan_diff(diff: &ScanDiff) {
+    pretty_println!("\n=== Scan Diff ===");
+
+    if diff.is_empty() {
+        pretty_println!("No changes between the two scans.");
+        return;
+    }
+
+    pretty_println!("Added: {}  Removed: {}  Renamed: {}  Size changed: {}",
+             diff.added.len(), diff.removed.len(), diff.renamed.len(), diff.size_changed.len());
+
+    if !diff.added.is_empty() {
+        pretty_println!("\n➕ Added:");
+        for hit in &diff.added {
+            pretty_println!("  {}", hit.path.display());
+        }
+    }
+
+    if !diff.removed.is_empty() {
+        pretty_println!("\n➖ Removed:");
+        for hit in &diff.removed {
+            pretty_println!("  {}", hit.path.display());
+        }
+    }
+
+    if !diff.renamed.is_empty() {
+        pretty_println!("\n🔀 Renamed:");
+        for renamed in &diff.renamed {
+            pretty_println!("  {} → {}", renamed.from.display(), renamed.to.display());
+        }
+    }
+
+    if !diff.size_changed.is_empty() {
+        pretty_println!("\n📏 Size changed:");
+        for change in &diff.size_changed {
+            pretty_println!("  {}: {} → {}", change.path.display(),
+                     format_size(change.old_size), format_size(change.new_size));
+        }
+    }
+}
+
+fn format_size(size: Option<u64>) -> String {
+    size.map(|bytes| format!("{} bytes", bytes))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::FolderHit;
+    use tempfile::TempDir;
+
+    fn write_scan(path: &std::path::Path, paths: &[&str]) {
+        let hits: Vec<FolderHit> = paths.iter().map(|p| FolderHit {
+            path: PathBuf::from(p),
+            name: p.rsplit('/').next().unwrap().to_string(),
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: Vec::new(),
+            size_bytes: None,
+            item_count: None,
+            is_dir: true,
+        }).collect();
+        std::fs::write(path, serde_json::to_string(&hits).unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scan_diff_writes_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let old_file = temp_dir.path().join("old.json");
+        let new_file = temp_dir.path().join("new.json");
+        let report = temp_dir.path().join("diff.json");
+        write_scan(&old_file, &["/data/a", "/data/b"]);
+        write_scan(&new_file, &["/data/b", "/data/c"]);
+
+        scan_diff_command(old_file, new_file, Some(report.clone())).await.unwrap();
+
+        let diff: ScanDiff = serde_json::from_str(&std::fs::read_to_string(report).unwrap()).unwrap();
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed.len(), 1);
+    }
+}
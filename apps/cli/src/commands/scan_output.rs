@@ -0,0 +1,187 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use anyhow::{Result, Context};
+use clap::ValueEnum;
+
+use filemover_types::{FolderHit, versioned_json_prefix};
+
+/// File format for scan results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ScanOutputFormat {
+    /// A single JSON array in a versioned envelope
+    #[default]
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+impl ScanOutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ScanOutputFormat::Json => "json",
+            ScanOutputFormat::Ndjson => "ndjson",
+            ScanOutputFormat::Csv => "csv",
+        }
+    }
+}
+
+const CSV_HEADER: &str = "path,name,is_dir,matched_rule,dest_preview,size_bytes,item_count,warnings";
+
+/// Writes scan hits one at a time so large scans never need to be serialized in one piece
+pub struct ScanResultWriter {
+    format: ScanOutputFormat,
+    out: Box<dyn Write + Send>,
+    written: usize,
+}
+
+impl ScanResultWriter {
+    /// Create a writer for `path`, or for stdout when the path is `-`
+    pub fn create(path: &Path, format: ScanOutputFormat) -> Result<Self> {
+        let out: Box<dyn Write + Send> = if path == Path::new("-") {
+            Box::new(BufWriter::new(std::io::stdout()))
+        } else {
+            let file = File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            Box::new(BufWriter::new(file))
+        };
+        Self::new(out, format)
+    }
+
+    fn new(mut out: Box<dyn Write + Send>, format: ScanOutputFormat) -> Result<Self> {
+        match format {
+            ScanOutputFormat::Json => {
+                out.write_all(versioned_json_prefix::<Vec<FolderHit>>().as_bytes())?;
+                out.write_all(b"[")?;
+            }
+            ScanOutputFormat::Csv => writeln!(out, "{}", CSV_HEADER)?,
+            ScanOutputFormat::Ndjson => {}
+        }
+        Ok(Self { format, out, written: 0 })
+    }
+
+    pub fn write_hit(&mut self, hit: &FolderHit) -> Result<()> {
+        match self.format {
+            ScanOutputFormat::Json => {
+                if self.written > 0 {
+                    self.out.write_all(b",")?;
+                }
+                self.out.write_all(b"\n  ")?;
+                serde_json::to_writer(&mut self.out, hit)
+                    .context("Failed to serialize scan result")?;
+            }
+            ScanOutputFormat::Ndjson => {
+                serde_json::to_writer(&mut self.out, hit)
+                    .context("Failed to serialize scan result")?;
+                self.out.write_all(b"\n")?;
+            }
+            ScanOutputFormat::Csv => {
+                writeln!(self.out, "{}", csv_row(hit))?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Close the output and return the number of hits written
+    pub fn finish(mut self) -> Result<usize> {
+        if self.format == ScanOutputFormat::Json {
+            self.out.write_all(if self.written > 0 { b"\n]}\n" } else { b"]}\n" })?;
+        }
+        self.out.flush().context("Failed to write scan results")?;
+        Ok(self.written)
+    }
+}
+
+/// Write all hits to `path` in the given format
+pub fn write_scan_results(hits: &[FolderHit], path: &Path, format: ScanOutputFormat) -> Result<()> {
+    let mut writer = ScanResultWriter::create(path, format)?;
+    for hit in hits {
+        writer.write_hit(hit)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn csv_row(hit: &FolderHit) -> String {
+    let warnings: Vec<String> = hit.warnings.iter().map(|w| format!("{:?}", w)).collect();
+    let fields = [
+        hit.path.display().to_string(),
+        hit.name.clone(),
+        hit.is_dir.to_string(),
+        hit.matched_rule.map(|id| id.to_string()).unwrap_or_default(),
+        hit.dest_preview.as_ref().map(|p| p.display().to_string()).unwrap_or_default(),
+        hit.size_bytes.map(|s| s.to_string()).unwrap_or_default(),
+        hit.item_count.map(|c| c.to_string()).unwrap_or_default(),
+        warnings.join(";"),
+    ];
+    fields.iter().map(|field| csv_escape(field)).collect::<Vec<_>>().join(",")
+}
+
+pub(crate) fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+    use filemover_types::{Warning, from_versioned_json};
+
+    fn create_hit(path: &str) -> FolderHit {
+        FolderHit {
+            path: PathBuf::from(path),
+            name: path.rsplit('/').next().unwrap().to_string(),
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: vec![Warning::LongPath, Warning::Hidden],
+            size_bytes: Some(10),
+            item_count: None,
+            is_dir: true,
+        }
+    }
+
+    #[test]
+    fn test_json_and_ndjson_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let hits = vec![create_hit("/data/a"), create_hit("/data/b")];
+
+        let json_path = temp_dir.path().join("scan.json");
+        write_scan_results(&hits, &json_path, ScanOutputFormat::Json).unwrap();
+        let parsed: Vec<FolderHit> = from_versioned_json(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(parsed.len(), 2);
+
+        let empty_path = temp_dir.path().join("empty.json");
+        write_scan_results(&[], &empty_path, ScanOutputFormat::Json).unwrap();
+        let parsed: Vec<FolderHit> = from_versioned_json(&std::fs::read_to_string(&empty_path).unwrap()).unwrap();
+        assert!(parsed.is_empty());
+
+        let ndjson_path = temp_dir.path().join("scan.ndjson");
+        write_scan_results(&hits, &ndjson_path, ScanOutputFormat::Ndjson).unwrap();
+        let content = std::fs::read_to_string(&ndjson_path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        let first: FolderHit = serde_json::from_str(content.lines().next().unwrap()).unwrap();
+        assert_eq!(first.path, PathBuf::from("/data/a"));
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("scan.csv");
+        write_scan_results(&[create_hit("/data/a, \"b\"")], &path, ScanOutputFormat::Csv).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], "\"/data/a, \"\"b\"\"\",\"a, \"\"b\"\"\",true,,,10,,LongPath;Hidden");
+    }
+}
@@ -0,0 +1,222 @@
+use std::path::Path;
+use anyhow::{Result, Context};
+use chrono::NaiveTime;
+use serde::Serialize;
+use tracing::info;
+
+use crate::ScheduleAction;
+use crate::config_manager::ConfigManager;
+use crate::error::CliError;
+
+/// Task Scheduler folder that holds the tasks created by `schedule create`
+const TASK_FOLDER: &str = "FileMover";
+
+/// Longest command line `schtasks /TR` accepts
+const MAX_TASK_COMMAND_LENGTH: usize = 261;
+
+/// A registered `filemover run` task
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScheduledTask {
+    pub profile: String,
+    pub task_name: String,
+    pub next_run: String,
+    pub status: String,
+}
+
+pub async fn schedule_command(action: ScheduleAction, config_manager: &ConfigManager) -> Result<()> {
+    match action {
+        ScheduleAction::Create { profile, daily, force } => create_task(&profile, daily, force, config_manager),
+        ScheduleAction::List => list_tasks(),
+        ScheduleAction::Remove { profile } => remove_task(&profile),
+    }
+}
+
+/// Parse a time of day such as `02:00` for `--daily`
+pub fn parse_time(value: &str) -> std::result::Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time '{}' (expected HH:MM, e.g. 02:00)", value))
+}
+
+fn create_task(profile: &str, daily: NaiveTime, force: bool, config_manager: &ConfigManager) -> Result<()> {
+    if !config_manager.profile_exists(profile)? {
+        return Err(CliError::profile_not_found(profile).into());
+    }
+
+    let task_name = task_name(profile);
+    if !force && query_task(&task_name)?.is_some() {
+        return Err(CliError::invalid_args(format!(
+            "A scheduled task for profile '{}' already exists - pass --force to replace it", profile
+        )).into());
+    }
+
+    let exe = std::env::current_exe().context("Failed to locate the filemover executable")?;
+    let config_file = std::path::absolute(config_manager.get_config_file())?;
+    let log_file = config_manager.get_config_dir().join("logs").join(format!("{}.log", profile));
+    let command = task_command(&exe, &config_file, &log_file, profile);
+    if command.len() > MAX_TASK_COMMAND_LENGTH {
+        return Err(CliError::invalid_args(format!(
+            "The task command is {} characters long, Task Scheduler allows {} - use a shorter configuration path",
+            command.len(), MAX_TASK_COMMAND_LENGTH
+        )).into());
+    }
+
+    run_schtasks(&create_args(&task_name, &command, daily))?;
+    info!("Registered scheduled task {}", task_name);
+
+    pretty_println!("⏰ Scheduled profile '{}' daily at {}", profile, daily.format("%H:%M"));
+    pretty_println!("   Task: \\{}", task_name);
+    pretty_println!("   Runs: {}", command);
+    pretty_println!("   Log:  {}", log_file.display());
+    crate::output::emit_result("schedule", &serde_json::json!({
+        "profile": profile,
+        "task_name": task_name,
+        "daily": daily.format("%H:%M").to_string(),
+        "command": command,
+    }))
+}
+
+fn list_tasks() -> Result<()> {
+    let output = run_schtasks(&["/Query".to_string(), "/FO".to_string(), "CSV".to_string(), "/NH".to_string()])?;
+    let tasks = parse_task_list(&output);
+
+    if tasks.is_empty() {
+        pretty_println!("No scheduled runs. Create one with `filemover schedule create --profile <name> --daily 02:00`.");
+    } else {
+        pretty_println!("{:<20} {:<24} STATUS", "PROFILE", "NEXT RUN");
+        for task in &tasks {
+            pretty_println!("{:<20} {:<24} {}", task.profile, task.next_run, task.status);
+        }
+    }
+    crate::output::emit_result("schedule", &serde_json::json!({ "tasks": tasks }))
+}
+
+fn remove_task(profile: &str) -> Result<()> {
+    let task_name = task_name(profile);
+    if query_task(&task_name)?.is_none() {
+        return Err(CliError::invalid_args(format!("No scheduled task for profile '{}'", profile)).into());
+    }
+
+    run_schtasks(&["/Delete".to_string(), "/TN".to_string(), task_name.clone(), "/F".to_string()])?;
+    pretty_println!("🗑️  Removed the scheduled run of profile '{}'", profile);
+    crate::output::emit_result("schedule", &serde_json::json!({ "profile": profile, "removed": task_name }))
+}
+
+fn task_name(profile: &str) -> String {
+    format!("{}\\{}", TASK_FOLDER, profile)
+}
+
+/// The unattended `run`: no prompts, one result line, and a log file for the details
+fn task_command(exe: &Path, config_file: &Path, log_file: &Path, profile: &str) -> String {
+    format!(
+        "\"{}\" --config \"{}\" --quiet --log-file \"{}\" run --profile \"{}\" --yes",
+        exe.display(), config_file.display(), log_file.display(), profile
+    )
+}
+
+fn create_args(task_name: &str, command: &str, daily: NaiveTime) -> Vec<String> {
+    [
+        "/Create", "/TN", task_name, "/TR", command,
+        "/SC", "DAILY", "/ST", &daily.format("%H:%M").to_string(), "/F",
+    ].iter().map(|arg| arg.to_string()).collect()
+}
+
+fn query_task(task_name: &str) -> Result<Option<ScheduledTask>> {
+    let output = run_schtasks(&["/Query".to_string(), "/FO".to_string(), "CSV".to_string(), "/NH".to_string()])?;
+    Ok(parse_task_list(&output).into_iter().find(|task| task.task_name == format!("\\{}", task_name)))
+}
+
+/// Our tasks from `schtasks /Query /FO CSV /NH` (`"TaskName","Next Run Time","Status"` rows)
+fn parse_task_list(output: &str) -> Vec<ScheduledTask> {
+    let prefix = format!("\\{}\\", TASK_FOLDER);
+    output.lines()
+        .filter_map(|line| {
+            let fields = parse_csv_line(line);
+            let task_name = fields.first()?;
+            let profile = task_name.strip_prefix(&prefix)?;
+            Some(ScheduledTask {
+                profile: profile.to_string(),
+                task_name: task_name.clone(),
+                next_run: fields.get(1).cloned().unwrap_or_default(),
+                status: fields.get(2).cloned().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn run_schtasks(args: &[String]) -> Result<String> {
+    if !cfg!(windows) {
+        return Err(CliError::invalid_args("Scheduled runs use the Windows Task Scheduler and are only available on Windows").into());
+    }
+
+    let output = std::process::Command::new("schtasks")
+        .args(args)
+        .output()
+        .context("Failed to run schtasks")?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr);
+        return Err(CliError::execution(format!("schtasks failed: {}", message.trim())).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_task_definition() {
+        let daily = parse_time("02:00").unwrap();
+        assert!(parse_time("25:00").is_err());
+
+        let command = task_command(
+            &PathBuf::from("C:\\Tools\\filemover.exe"),
+            &PathBuf::from("C:\\Users\\me\\AppData\\Roaming\\FileMover\\config.json"),
+            &PathBuf::from("C:\\Users\\me\\AppData\\Roaming\\FileMover\\logs\\work.log"),
+            "work",
+        );
+        assert!(command.ends_with("--quiet --log-file \"C:\\Users\\me\\AppData\\Roaming\\FileMover\\logs\\work.log\" run --profile \"work\" --yes"));
+
+        let args = create_args(&task_name("work"), &command, daily);
+        assert_eq!(args[..3], ["/Create", "/TN", "FileMover\\work"]);
+        assert_eq!(args[5..], ["/SC", "DAILY", "/ST", "02:00", "/F"]);
+    }
+
+    #[test]
+    fn test_parse_task_list() {
+        let output = "\"\\FileMover\\work\",\"2024/03/06 2:00:00\",\"Ready\"\r\n\
+                      \"\\Microsoft\\Windows\\Defrag\\ScheduledDefrag\",\"N/A\",\"Ready\"\r\n\
+                      \"\\FileMover\\photos, old\",\"N/A\",\"Disabled\"\r\n";
+
+        let tasks = parse_task_list(output);
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0], ScheduledTask {
+            profile: "work".to_string(),
+            task_name: "\\FileMover\\work".to_string(),
+            next_run: "2024/03/06 2:00:00".to_string(),
+            status: "Ready".to_string(),
+        });
+        assert_eq!(tasks[1].profile, "photos, old");
+        assert_eq!(tasks[1].status, "Disabled");
+    }
+}
@@ -1,29 +1,54 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
-use tracing::{info, warn, error};
+use serde::Serialize;
+use tracing::{info, error};
 use std::io::{self, Write};
 
-use filemover_types::{JournalEntry, ResultKind, OpKind};
+use filemover_types::{JournalEntry, JournalProblem, JournalReadReport, Localize, ResultKind, OpKind};
 use crate::config_manager::ConfigManager;
+use crate::error::CliError;
 
 pub async fn undo_command(
     journal_file: PathBuf,
+    skip_confirmation: bool,
+    interactive: bool,
     _config_manager: &ConfigManager,
 ) -> Result<()> {
     info!("Starting undo operation");
     
+    // Prompts would mix with the result that scripts read from stdout
+    if !skip_confirmation && !crate::output::is_pretty() {
+        anyhow::bail!("Confirmation prompts need pretty output - pass --yes when using --quiet or --output json or minimal");
+    }
+    
     // Validate journal file exists
     if !journal_file.exists() {
-        anyhow::bail!("Journal file does not exist: {}", journal_file.display());
+        return Err(CliError::file_operation(journal_file, "Journal file does not exist").into());
     }
     
-    // Load journal entries
-    let entries = load_journal_entries(&journal_file)
+    // Load journal entries, collecting integrity problems instead of failing on them
+    let report = load_checked_journal(&journal_file)
         .context("Failed to load journal file")?;
     
+    if !report.is_intact() {
+        print_journal_problems(&report.problems);
+    }
+    
+    let entries = report.entries;
+    let mut result = UndoResult {
+        journal: journal_file.clone(),
+        undo_journal: None,
+        undone: 0,
+        failed: 0,
+        declined: 0,
+        not_undoable: 0,
+        integrity_problems: report.problems.len(),
+        issues: Vec::new(),
+    };
+    
     if entries.is_empty() {
-        println!("Journal file is empty - nothing to undo.");
-        return Ok(());
+        pretty_println!("Journal file is empty - nothing to undo.");
+        return crate::output::emit_result("undo", &result);
     }
     
     info!("Loaded {} journal entries from {}", 
@@ -35,44 +60,82 @@ pub async fn undo_command(
     
     // Show undo summary
     print_undo_summary(&entries, &undoable, &issues);
+    result.not_undoable = issues.len();
+    result.issues = issues;
     
     if undoable.is_empty() {
-        println!("❌ No operations can be undone.");
-        return Ok(());
+        pretty_println!("❌ No operations can be undone.");
+        return crate::output::emit_result("undo", &result);
     }
     
-    // Get user confirmation
-    if !get_undo_confirmation(&undoable)? {
-        println!("Undo cancelled by user.");
-        return Ok(());
-    }
+    // Get user confirmation, for all operations at once or one at a time
+    let selected = if interactive {
+        let selected = select_entries_interactively(&undoable, read_answer)?;
+        result.declined = undoable.len() - selected.len();
+        if selected.is_empty() {
+            pretty_println!("\nNo operations selected - nothing was undone.");
+            return crate::output::emit_result("undo", &result);
+        }
+        if result.declined > 0 {
+            pretty_println!("\n{} operation(s) stay as they are; undo them later with the same journal.", result.declined);
+        }
+        selected
+    } else {
+        if !skip_confirmation && !get_undo_confirmation(&undoable)? {
+            pretty_println!("Undo cancelled by user.");
+            return Ok(());
+        }
+        undoable
+    };
     
     // Execute undo operations
-    execute_undo_operations(&undoable).await?;
+    let outcome = execute_undo_operations(&selected, &journal_file).await?;
+    result.undone = outcome.undone;
+    result.failed = outcome.failed;
+    result.undo_journal = Some(outcome.undo_journal);
     
-    println!("\n✅ Undo operation completed!");
+    pretty_println!("\n✅ Undo operation completed!");
     
-    Ok(())
+    crate::output::emit_result("undo", &result)
+}
+
+/// Result of `undo` for `--output json|minimal`
+#[derive(Debug, Serialize)]
+struct UndoResult {
+    journal: PathBuf,
+    undo_journal: Option<PathBuf>,
+    undone: usize,
+    failed: usize,
+    /// Operations left alone in `--interactive` mode
+    declined: usize,
+    not_undoable: usize,
+    integrity_problems: usize,
+    issues: Vec<String>,
+}
+
+struct UndoOutcome {
+    undone: usize,
+    failed: usize,
+    undo_journal: PathBuf,
 }
 
-fn load_journal_entries(path: &PathBuf) -> Result<Vec<JournalEntry>> {
-    let content = std::fs::read_to_string(path)
-        .context("Failed to read journal file")?;
+pub(crate) fn load_checked_journal(path: &Path) -> Result<JournalReadReport> {
+    // Detects JSONL or SQLite journals and accepts all journal format versions
+    let journal = filemover_journal::open_journal(path)
+        .context("Failed to open journal file")?;
     
-    let mut entries = Vec::new();
+    let report = journal.read_checked()
+        .context("Failed to read journal entries")?;
     
-    for (line_num, line) in content.lines().enumerate() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        
-        let entry: JournalEntry = serde_json::from_str(line)
-            .with_context(|| format!("Failed to parse journal entry at line {}", line_num + 1))?;
-        
-        entries.push(entry);
+    Ok(report)
+}
+
+pub(crate) fn print_journal_problems(problems: &[JournalProblem]) {
+    pretty_println!("\n⚠️  Journal integrity problems detected:");
+    for (i, problem) in problems.iter().enumerate() {
+        pretty_println!("  {}. {}", i + 1, problem.localize(crate::output::locale()));
     }
-    
-    Ok(entries)
+    pretty_println!("Only entries that passed verification will be used.");
 }
 
 fn analyze_undo_feasibility(entries: &[JournalEntry]) -> (Vec<&JournalEntry>, Vec<String>) {
@@ -114,7 +177,7 @@ fn print_undo_summary(
     undoable: &[&JournalEntry],
     issues: &[String]
 ) {
-    println!("\n=== Undo Analysis ===");
+    pretty_println!("\n=== Undo Analysis ===");
     
     let successful_ops = all_entries.iter()
         .filter(|e| matches!(e.result, ResultKind::Ok))
@@ -128,24 +191,24 @@ fn print_undo_summary(
         .filter(|e| matches!(e.result, ResultKind::Failed))
         .count();
     
-    println!("📊 Original operation results:");
-    println!("  ✅ Successful: {}", successful_ops);
-    println!("  ⏭️  Skipped: {}", skipped_ops);
-    println!("  ❌ Failed: {}", failed_ops);
+    pretty_println!("📊 Original operation results:");
+    pretty_println!("  ✅ Successful: {}", successful_ops);
+    pretty_println!("  ⏭️  Skipped: {}", skipped_ops);
+    pretty_println!("  ❌ Failed: {}", failed_ops);
     
-    println!("\n🔄 Undo feasibility:");
-    println!("  ✅ Can be undone: {}", undoable.len());
-    println!("  ❌ Cannot be undone: {}", successful_ops - undoable.len());
+    pretty_println!("\n🔄 Undo feasibility:");
+    pretty_println!("  ✅ Can be undone: {}", undoable.len());
+    pretty_println!("  ❌ Cannot be undone: {}", successful_ops - undoable.len());
     
     if !issues.is_empty() {
-        println!("\n⚠️  Issues preventing undo:");
+        pretty_println!("\n⚠️  Issues preventing undo:");
         for (i, issue) in issues.iter().enumerate() {
-            println!("  {}. {}", i + 1, issue);
+            pretty_println!("  {}. {}", i + 1, issue);
         }
     }
     
     if !undoable.is_empty() {
-        println!("\n📝 Operations to undo (in reverse order):");
+        pretty_println!("\n📝 Operations to undo (in reverse order):");
         for (i, entry) in undoable.iter().take(10).enumerate() {
             let op_description = match entry.op {
                 OpKind::Move => "Move back",
@@ -154,7 +217,7 @@ fn print_undo_summary(
                 _ => "Reverse",
             };
             
-            println!("  {}. {}: {} <- {}",
+            pretty_println!("  {}. {}: {} <- {}",
                      i + 1,
                      op_description,
                      entry.source.display(),
@@ -163,14 +226,14 @@ fn print_undo_summary(
         }
         
         if undoable.len() > 10 {
-            println!("  ... and {} more operations", undoable.len() - 10);
+            pretty_println!("  ... and {} more operations", undoable.len() - 10);
         }
     }
 }
 
 fn get_undo_confirmation(undoable: &[&JournalEntry]) -> Result<bool> {
-    println!("\n⚠️  WARNING: This will reverse {} file operations!", undoable.len());
-    println!("Files will be moved back to their original locations.");
+    pretty_println!("\n⚠️  WARNING: This will reverse {} file operations!", undoable.len());
+    pretty_println!("Files will be moved back to their original locations.");
     print!("\nDo you want to proceed with undo? (y/N): ");
     
     io::stdout().flush()?;
@@ -182,12 +245,101 @@ fn get_undo_confirmation(undoable: &[&JournalEntry]) -> Result<bool> {
     Ok(input == "y" || input == "yes")
 }
 
-async fn execute_undo_operations(entries: &[&JournalEntry]) -> Result<()> {
-    use indicatif::{ProgressBar, ProgressStyle};
-    use std::time::Duration;
-    use tokio::time::sleep;
+/// Answer to the per-operation prompt of `undo --interactive`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UndoChoice {
+    Yes,
+    No,
+    /// This operation and all remaining ones
+    All,
+    /// Stop asking and leave the remaining operations alone
+    Quit,
+}
+
+impl UndoChoice {
+    fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => Some(Self::Yes),
+            "n" | "no" => Some(Self::No),
+            "a" | "all" => Some(Self::All),
+            "q" | "quit" => Some(Self::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Ask about each operation in undo order; `ask` shows a prompt and returns the answer
+fn select_entries_interactively<'a>(
+    undoable: &[&'a JournalEntry],
+    mut ask: impl FnMut(&str) -> Result<String>,
+) -> Result<Vec<&'a JournalEntry>> {
+    let mut selected = Vec::new();
+    
+    for (i, entry) in undoable.iter().enumerate() {
+        pretty_println!("\n[{}/{}] {:?}", i + 1, undoable.len(), entry.op);
+        pretty_println!("  Restore: {}", entry.source.display());
+        pretty_println!("  From:    {}", entry.dest.display());
+        for issue in entry_issues(entry) {
+            pretty_println!("  ⚠️  {}", issue);
+        }
+        
+        let choice = loop {
+            let answer = ask("Undo this operation? (y)es/(n)o/(a)ll/(q)uit: ")?;
+            match UndoChoice::parse(&answer) {
+                Some(choice) => break choice,
+                None => pretty_println!("Please answer y, n, a or q."),
+            }
+        };
+        match choice {
+            UndoChoice::Yes => selected.push(*entry),
+            UndoChoice::No => {}
+            UndoChoice::All => {
+                selected.extend_from_slice(&undoable[i..]);
+                break;
+            }
+            UndoChoice::Quit => break,
+        }
+    }
     
-    let progress = ProgressBar::new(entries.len() as u64);
+    Ok(selected)
+}
+
+/// Problems with restoring this entry as the file system looks now
+fn entry_issues(entry: &JournalEntry) -> Vec<String> {
+    let mut issues = Vec::new();
+    
+    if !entry.dest.exists() {
+        issues.push(format!("Destination no longer exists: {}", entry.dest.display()));
+    }
+    if entry.source.exists() {
+        issues.push(format!("Original location is occupied: {}", entry.source.display()));
+    } else if let Some(parent) = entry.source.parent().filter(|parent| !parent.exists()) {
+        issues.push(format!("Parent folder will be recreated: {}", parent.display()));
+    }
+    let modified = std::fs::metadata(&entry.dest).and_then(|metadata| metadata.modified()).ok();
+    if modified.is_some_and(|modified| chrono::DateTime::<chrono::Utc>::from(modified) > entry.when_utc) {
+        issues.push("Destination changed after the operation; the changes are moved back too".to_string());
+    }
+    
+    issues
+}
+
+fn read_answer(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        // End of input: treat like quit
+        return Ok("q".to_string());
+    }
+    Ok(input)
+}
+
+async fn execute_undo_operations(entries: &[&JournalEntry], journal_file: &Path) -> Result<UndoOutcome> {
+    use indicatif::ProgressStyle;
+    
+    let progress = crate::progress::new_bar(entries.len() as u64);
     progress.set_style(
         ProgressStyle::default_bar()
             .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}")
@@ -195,6 +347,12 @@ async fn execute_undo_operations(entries: &[&JournalEntry]) -> Result<()> {
             .progress_chars("#>-"),
     );
     
+    // Record what was restored next to the original journal, in the same format
+    let undo_journal_path = filemover_journal::undo_journal_path(journal_file);
+    let format = filemover_journal::detect_format(journal_file)?;
+    let mut undo_journal = filemover_journal::create_or_open_journal(&undo_journal_path, format)
+        .context("Failed to create undo journal")?;
+    
     let mut successful_undos = 0;
     let mut failed_undos = 0;
     
@@ -205,73 +363,60 @@ async fn execute_undo_operations(entries: &[&JournalEntry]) -> Result<()> {
                 .unwrap_or("unknown")
         ));
         
-        // Simulate undo operation
-        sleep(Duration::from_millis(50)).await;
-        
-        // In real implementation, this would:
-        // 1. Reverse the original operation (move dest back to source)
-        // 2. Handle errors and edge cases
-        // 3. Verify the undo was successful
-        
-        // Stub: Just log what would happen
-        match execute_single_undo(entry).await {
-            Ok(_) => {
+        let record = execute_single_undo(entry).await;
+        match record.result {
+            ResultKind::Ok => {
                 successful_undos += 1;
                 info!("Undone: {} <- {}", entry.source.display(), entry.dest.display());
             }
-            Err(e) => {
+            _ => {
                 failed_undos += 1;
-                error!("Failed to undo {}: {}", entry.dest.display(), e);
+                error!("Failed to undo {}: {}",
+                       entry.dest.display(),
+                       record.message.as_deref().unwrap_or("unknown error"));
             }
         }
+        undo_journal.append(&record)?;
         
         progress.set_position(i as u64 + 1);
     }
     
+    undo_journal.finish()?;
     progress.finish_with_message("Undo operations completed");
     
     // Print final results
-    println!("\n📊 Undo Results:");
-    println!("  ✅ Successfully undone: {}", successful_undos);
+    pretty_println!("\n📊 Undo Results:");
+    pretty_println!("  ✅ Successfully undone: {}", successful_undos);
     if failed_undos > 0 {
-        println!("  ❌ Failed to undo: {}", failed_undos);
+        pretty_println!("  ❌ Failed to undo: {}", failed_undos);
     }
     
     if failed_undos == 0 {
-        println!("\n🎉 All operations were successfully undone!");
+        pretty_println!("\n🎉 All operations were successfully undone!");
     } else {
-        println!("\n⚠️  Some operations could not be undone. Check the logs for details.");
+        pretty_println!("\n⚠️  Some operations could not be undone. Check the undo journal for details.");
     }
+    pretty_println!("📄 Undo journal saved to: {}", undo_journal_path.display());
     
-    Ok(())
+    Ok(UndoOutcome {
+        undone: successful_undos,
+        failed: failed_undos,
+        undo_journal: undo_journal_path,
+    })
 }
 
-async fn execute_single_undo(entry: &JournalEntry) -> Result<()> {
-    // Stub implementation for single undo operation
-    // In real implementation, this would:
-    
-    match entry.op {
-        OpKind::Move => {
-            // Move dest back to source
-            info!("Would move {} back to {}", entry.dest.display(), entry.source.display());
-        }
-        OpKind::CopyDelete => {
-            // Copy dest back to source, then delete dest
-            info!("Would copy {} back to {} and delete dest", entry.dest.display(), entry.source.display());
-        }
-        OpKind::Rename => {
-            // Rename dest back to source name
-            info!("Would rename {} back to {}", entry.dest.display(), entry.source.display());
-        }
-        _ => {
-            warn!("Unsupported operation type for undo: {:?}", entry.op);
-        }
-    }
-    
-    // Simulate some processing time
-    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
-    
-    Ok(())
+/// Reverse a single operation and return the record for the undo journal
+async fn execute_single_undo(entry: &JournalEntry) -> JournalEntry {
+    let entry = entry.clone();
+    let dest = entry.dest.clone();
+    
+    // File system work is blocking; keep it off the async worker threads
+    tokio::task::spawn_blocking(move || filemover_journal::undo_entry(&entry))
+        .await
+        .unwrap_or_else(|e| {
+            JournalEntry::new(dest, PathBuf::new(), OpKind::None)
+                .failed(format!("Undo task panicked: {}", e))
+        })
 }
 
 #[cfg(test)]
@@ -286,6 +431,8 @@ mod tests {
         
         let result = undo_command(
             PathBuf::from("nonexistent_journal.jsonl"),
+            false,
+            false,
             &config_manager
         ).await;
         
@@ -319,7 +466,7 @@ mod tests {
         
         std::fs::write(&journal_file, journal_content).unwrap();
         
-        let loaded = load_journal_entries(&journal_file).unwrap();
+        let loaded = load_checked_journal(&journal_file).unwrap().entries;
         assert_eq!(loaded.len(), 2);
         assert_eq!(loaded[0].op, OpKind::Move);
         assert_eq!(loaded[1].result, ResultKind::Failed);
@@ -344,24 +491,100 @@ mod tests {
                 PathBuf::from("D:\\also_nonexistent"),
                 OpKind::Move
             ).with_result(ResultKind::Failed),
+            JournalEntry::new(
+                temp_dir.path().join("moved_away"),
+                temp_dir.path().join("gone"), // doesn't exist anymore
+                OpKind::Move
+            ).with_result(ResultKind::Ok),
         ];
         
         let (undoable, issues) = analyze_undo_feasibility(&entries);
         
         assert_eq!(undoable.len(), 1); // Only the first entry can be undone
-        assert!(!issues.is_empty()); // Should have issues with the second entry
+        // Failed operations need no undo; only the vanished destination is reported
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].contains("Destination no longer exists"));
     }
     
     #[tokio::test]
     async fn test_execute_single_undo() {
-        let entry = JournalEntry::new(
-            PathBuf::from("C:\\Source\\test"),
-            PathBuf::from("D:\\Dest\\test"),
-            OpKind::Move
-        );
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("Source").join("test");
+        let dest = temp_dir.path().join("Dest").join("test");
+        std::fs::create_dir_all(&dest).unwrap();
+        std::fs::write(dest.join("file.txt"), "content").unwrap();
         
-        // Should not fail (it's a stub implementation)
-        let result = execute_single_undo(&entry).await;
-        assert!(result.is_ok());
+        let entry = JournalEntry::new(source.clone(), dest.clone(), OpKind::Move);
+        
+        let record = execute_single_undo(&entry).await;
+        assert_eq!(record.result, ResultKind::Ok);
+        assert!(source.join("file.txt").exists());
+        assert!(!dest.exists());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_execute_undo_operations_writes_undo_journal() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal_file = temp_dir.path().join("run.jsonl");
+        let source = temp_dir.path().join("a");
+        let dest = temp_dir.path().join("b");
+        std::fs::create_dir(&dest).unwrap();
+        
+        let mut journal = filemover_journal::create_journal(&journal_file, filemover_types::JournalFormat::Jsonl).unwrap();
+        let entry = JournalEntry::new(source.clone(), dest, OpKind::Rename);
+        journal.append(&entry).unwrap();
+        journal.finish().unwrap();
+        
+        execute_undo_operations(&[&entry], &journal_file).await.unwrap();
+        
+        let undo_report = load_checked_journal(&temp_dir.path().join("run.undo.jsonl")).unwrap();
+        assert!(undo_report.is_intact());
+        assert_eq!(undo_report.entries.len(), 1);
+        assert_eq!(undo_report.entries[0].dest, source);
+    }
+    
+    #[test]
+    fn test_select_entries_interactively() {
+        let entries: Vec<JournalEntry> = (1..=5)
+            .map(|i| JournalEntry::new(
+                PathBuf::from(format!("C:\\Source\\{}", i)),
+                PathBuf::from(format!("D:\\Dest\\{}", i)),
+                OpKind::Move,
+            ))
+            .collect();
+        let undoable: Vec<&JournalEntry> = entries.iter().collect();
+        
+        let scripted = |answers: &'static [&'static str]| {
+            let mut answers = answers.iter();
+            move |_: &str| Ok(answers.next().expect("more prompts than answers").to_string())
+        };
+        
+        // Unknown answers are asked again
+        let selected = select_entries_interactively(&undoable, scripted(&["y", "n", "what", "a"])).unwrap();
+        let selected: Vec<_> = selected.iter().map(|entry| entry.source.clone()).collect();
+        assert_eq!(selected, vec![entries[0].source.clone(), entries[2].source.clone(), entries[3].source.clone(), entries[4].source.clone()]);
+        
+        let selected = select_entries_interactively(&undoable, scripted(&["n", "Yes", "q"])).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].source, entries[1].source);
+    }
+    
+    #[test]
+    fn test_entry_issues() {
+        let temp_dir = TempDir::new().unwrap();
+        let dest = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest).unwrap();
+        
+        let mut entry = JournalEntry::new(temp_dir.path().join("gone/source"), dest, OpKind::Move);
+        entry.when_utc = Utc::now() + chrono::Duration::hours(1);
+        let issues = entry_issues(&entry);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].starts_with("Parent folder will be recreated"));
+        
+        entry.when_utc = Utc::now() - chrono::Duration::hours(1);
+        entry.source = temp_dir.path().to_path_buf();
+        let issues = entry_issues(&entry);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].starts_with("Original location is occupied"));
+    }
+}
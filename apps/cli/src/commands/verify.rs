@@ -0,0 +1,433 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use tracing::{info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use chrono::{DateTime, Utc};
+
+use filemover_types::{JournalEntry, JournalProblem, MovePlan, OpKind, ResultKind};
+use crate::config_manager::ConfigManager;
+use super::undo::load_checked_journal;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerifyOptions {
+    /// Compare destination sizes against the sizes recorded in the plan
+    pub check_sizes: bool,
+    /// Compare content hashes of leftover sources against their destinations
+    pub check_hashes: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    pub journal: PathBuf,
+    pub verified_at: DateTime<Utc>,
+    pub total_entries: usize,
+    pub checked: usize,
+    pub passed: usize,
+    pub skipped: usize,
+    pub journal_problems: Vec<JournalProblem>,
+    pub issues: Vec<VerifyIssue>,
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty() && self.journal_problems.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyIssue {
+    pub source: PathBuf,
+    pub dest: PathBuf,
+    pub op: OpKind,
+    pub kind: VerifyIssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum VerifyIssueKind {
+    DestinationMissing,
+    SourceLeftover { matches_destination: Option<bool> },
+    SizeMismatch { expected: u64, actual: u64 },
+    Unreadable { message: String },
+}
+
+pub async fn verify_command(
+    journal_file: PathBuf,
+    plan_file: Option<PathBuf>,
+    options: VerifyOptions,
+    report_file: Option<PathBuf>,
+    _config_manager: &ConfigManager,
+) -> Result<()> {
+    info!("Starting verification of {}", journal_file.display());
+
+    if !journal_file.exists() {
+        anyhow::bail!("Journal file does not exist: {}", journal_file.display());
+    }
+
+    let journal = load_checked_journal(&journal_file)
+        .context("Failed to load journal file")?;
+
+    // Expected sizes come from the plan that produced the journal
+    let expected_sizes = match &plan_file {
+        Some(path) => {
+            let plan = load_move_plan(path).context("Failed to load move plan")?;
+            expected_sizes_from_plan(&plan)
+        }
+        None => {
+            if options.check_sizes {
+                warn!("--sizes requires --plan; size comparison will be skipped");
+            }
+            HashMap::new()
+        }
+    };
+
+    let mut report = verify_entries(&journal_file, &journal.entries, &expected_sizes, options);
+    report.journal_problems = journal.problems;
+
+    print_verify_report(&report);
+
+    if let Some(report_path) = report_file {
+        let json = serde_json::to_string_pretty(&report)
+            .context("Failed to serialize verification report")?;
+        std::fs::write(&report_path, json)
+            .with_context(|| format!("Failed to write report to {}", report_path.display()))?;
+        pretty_println!("📄 Report saved to: {}", report_path.display());
+    }
+
+    if !report.is_clean() {
+        let error = anyhow::anyhow!(
+            "Verification found {} issue(s)",
+            report.issues.len() + report.journal_problems.len()
+        );
+        return Err(crate::output::emit_failure("verify", &report, error));
+    }
+
+    crate::output::emit_result("verify", &report)
+}
+
+fn load_move_plan(path: &PathBuf) -> Result<MovePlan> {
+    let content = std::fs::read_to_string(path)
+        .context("Failed to read move plan file")?;
+
+    let plan: MovePlan = filemover_types::from_versioned_json(&content)
+        .context("Failed to parse move plan JSON")?;
+
+    Ok(plan)
+}
+
+fn expected_sizes_from_plan(plan: &MovePlan) -> HashMap<PathBuf, u64> {
+    plan.nodes.values()
+        .filter_map(|node| node.size_bytes.map(|size| (node.path_before.clone(), size)))
+        .collect()
+}
+
+fn verify_entries(
+    journal_file: &Path,
+    entries: &[JournalEntry],
+    expected_sizes: &HashMap<PathBuf, u64>,
+    options: VerifyOptions,
+) -> VerifyReport {
+    let mut report = VerifyReport {
+        journal: journal_file.to_path_buf(),
+        verified_at: Utc::now(),
+        total_entries: entries.len(),
+        checked: 0,
+        passed: 0,
+        skipped: 0,
+        journal_problems: Vec::new(),
+        issues: Vec::new(),
+    };
+
+    for entry in entries {
+        // Only operations that reported success have something to verify
+        if entry.result != ResultKind::Ok || matches!(entry.op, OpKind::Skip | OpKind::None) {
+            report.skipped += 1;
+            continue;
+        }
+
+        report.checked += 1;
+        let issues = verify_entry(entry, expected_sizes, options);
+
+        if issues.is_empty() {
+            report.passed += 1;
+        }
+
+        report.issues.extend(issues.into_iter().map(|kind| VerifyIssue {
+            source: entry.source.clone(),
+            dest: entry.dest.clone(),
+            op: entry.op,
+            kind,
+        }));
+    }
+
+    report
+}
+
+fn verify_entry(
+    entry: &JournalEntry,
+    expected_sizes: &HashMap<PathBuf, u64>,
+    options: VerifyOptions,
+) -> Vec<VerifyIssueKind> {
+    let mut issues = Vec::new();
+
+    if !entry.dest.exists() {
+        issues.push(VerifyIssueKind::DestinationMissing);
+        return issues;
+    }
+
+    // Every supported operation removes the source once it completes
+    if entry.source.exists() {
+        let matches_destination = if options.check_hashes {
+            match (hash_path(&entry.source), hash_path(&entry.dest)) {
+                (Ok(source_hash), Ok(dest_hash)) => Some(source_hash == dest_hash),
+                (Err(e), _) | (_, Err(e)) => {
+                    issues.push(VerifyIssueKind::Unreadable { message: e.to_string() });
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        issues.push(VerifyIssueKind::SourceLeftover { matches_destination });
+    }
+
+    if options.check_sizes {
+        if let Some(&expected) = expected_sizes.get(&entry.source) {
+            match path_size(&entry.dest) {
+                Ok(actual) if actual != expected => {
+                    issues.push(VerifyIssueKind::SizeMismatch { expected, actual });
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    issues.push(VerifyIssueKind::Unreadable { message: e.to_string() });
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+/// Total size in bytes of a file, or of all files below a directory
+fn path_size(path: &Path) -> std::io::Result<u64> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        total += path_size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+/// SHA-256 over relative paths and contents, so identical trees hash equal
+///
+/// Every path and file body is preceded by its length, and every entry by a file/dir tag,
+/// so a name running into the following contents cannot collide with another tree.
+fn hash_path(path: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    hash_into(path, Path::new(""), &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn hash_into(path: &Path, relative: &Path, hasher: &mut Sha256) -> std::io::Result<()> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    let name = relative.as_os_str().as_encoded_bytes();
+    hasher.update(if metadata.is_dir() { b"d" } else { b"f" });
+    hasher.update((name.len() as u64).to_le_bytes());
+    hasher.update(name);
+
+    if !metadata.is_dir() {
+        let mut file = std::fs::File::open(path)?;
+        hasher.update(metadata.len().to_le_bytes());
+        std::io::copy(&mut file, hasher)?;
+        return Ok(());
+    }
+
+    // Sort children so the hash does not depend on directory enumeration order
+    let mut children: Vec<_> = std::fs::read_dir(path)?
+        .collect::<std::io::Result<Vec<_>>>()?;
+    children.sort_by_key(|entry| entry.file_name());
+
+    for child in children {
+        hash_into(&child.path(), &relative.join(child.file_name()), hasher)?;
+    }
+    Ok(())
+}
+
+fn print_verify_report(report: &VerifyReport) {
+    pretty_println!("\n=== Verification Results ===");
+    pretty_println!("📄 Journal: {}", report.journal.display());
+    pretty_println!("📊 Entries: {} (checked {}, skipped {})",
+             report.total_entries, report.checked, report.skipped);
+    pretty_println!("  ✅ Passed: {}", report.passed);
+    pretty_println!("  ❌ With issues: {}", report.checked - report.passed);
+
+    if !report.journal_problems.is_empty() {
+        pretty_println!("\n⚠️  Journal integrity problems:");
+        for (i, problem) in report.journal_problems.iter().enumerate() {
+            pretty_println!("  {}. {}", i + 1, problem);
+        }
+    }
+
+    if report.is_clean() {
+        pretty_println!("\n🎉 All completed operations verified successfully!");
+        return;
+    }
+
+    if report.issues.is_empty() {
+        return;
+    }
+
+    pretty_println!("\n⚠️  Issues found:");
+    for (i, issue) in report.issues.iter().take(20).enumerate() {
+        let description = match &issue.kind {
+            VerifyIssueKind::DestinationMissing => {
+                format!("Destination missing: {}", issue.dest.display())
+            }
+            VerifyIssueKind::SourceLeftover { matches_destination: Some(true) } => {
+                format!("Source left behind (identical to destination): {}", issue.source.display())
+            }
+            VerifyIssueKind::SourceLeftover { matches_destination: Some(false) } => {
+                format!("Source left behind (differs from destination): {}", issue.source.display())
+            }
+            VerifyIssueKind::SourceLeftover { matches_destination: None } => {
+                format!("Source left behind: {}", issue.source.display())
+            }
+            VerifyIssueKind::SizeMismatch { expected, actual } => {
+                format!("Size mismatch at {}: expected {} bytes, found {} bytes",
+                        issue.dest.display(), expected, actual)
+            }
+            VerifyIssueKind::Unreadable { message } => {
+                format!("Could not read {}: {}", issue.dest.display(), message)
+            }
+        };
+        pretty_println!("  {}. {}", i + 1, description);
+    }
+
+    if report.issues.len() > 20 {
+        pretty_println!("  ... and {} more issues", report.issues.len() - 20);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_verify_command_with_missing_journal() {
+        let config_manager = ConfigManager::new(None).unwrap();
+
+        let result = verify_command(
+            PathBuf::from("nonexistent_journal.jsonl"),
+            None,
+            VerifyOptions::default(),
+            None,
+            &config_manager
+        ).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_verify_entries_detects_missing_and_leftover() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let moved_dest = temp_dir.path().join("moved");
+        std::fs::write(&moved_dest, "content").unwrap();
+
+        let leftover_source = temp_dir.path().join("leftover_src");
+        let leftover_dest = temp_dir.path().join("leftover_dst");
+        std::fs::write(&leftover_source, "same").unwrap();
+        std::fs::write(&leftover_dest, "same").unwrap();
+
+        let entries = vec![
+            JournalEntry::new(temp_dir.path().join("gone"), moved_dest, OpKind::Move),
+            JournalEntry::new(temp_dir.path().join("a"), temp_dir.path().join("missing"), OpKind::Move),
+            JournalEntry::new(leftover_source, leftover_dest, OpKind::CopyDelete),
+            JournalEntry::new(temp_dir.path().join("b"), temp_dir.path().join("c"), OpKind::Move)
+                .failed("Access denied"),
+        ];
+
+        let options = VerifyOptions { check_sizes: false, check_hashes: true };
+        let report = verify_entries(Path::new("run.jsonl"), &entries, &HashMap::new(), options);
+
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.issues.len(), 2);
+        assert_eq!(report.issues[0].kind, VerifyIssueKind::DestinationMissing);
+        assert_eq!(
+            report.issues[1].kind,
+            VerifyIssueKind::SourceLeftover { matches_destination: Some(true) }
+        );
+    }
+
+    #[test]
+    fn test_verify_entries_size_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("src");
+        let dest = temp_dir.path().join("dst");
+        std::fs::create_dir(&dest).unwrap();
+        std::fs::write(dest.join("a.txt"), "12345").unwrap();
+
+        let entries = vec![JournalEntry::new(source.clone(), dest, OpKind::Move)];
+        let expected_sizes = HashMap::from([(source, 10)]);
+
+        let options = VerifyOptions { check_sizes: true, check_hashes: false };
+        let report = verify_entries(Path::new("run.jsonl"), &entries, &expected_sizes, options);
+
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.issues[0].kind,
+            VerifyIssueKind::SizeMismatch { expected: 10, actual: 5 }
+        );
+    }
+
+    #[test]
+    fn test_hash_path_is_order_independent() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+
+        for dir in [&first, &second] {
+            std::fs::create_dir_all(dir.join("sub")).unwrap();
+        }
+        std::fs::write(first.join("b.txt"), "b").unwrap();
+        std::fs::write(first.join("sub").join("a.txt"), "a").unwrap();
+        std::fs::write(second.join("sub").join("a.txt"), "a").unwrap();
+        std::fs::write(second.join("b.txt"), "b").unwrap();
+
+        assert_eq!(hash_path(&first).unwrap(), hash_path(&second).unwrap());
+
+        std::fs::write(second.join("b.txt"), "changed").unwrap();
+        assert_ne!(hash_path(&first).unwrap(), hash_path(&second).unwrap());
+    }
+
+    #[test]
+    fn test_hash_path_separates_names_from_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        for dir in [&first, &second] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+
+        // "a" + "bc" and "ab" + "c" would run together without length prefixes
+        std::fs::write(first.join("a"), "bc").unwrap();
+        std::fs::write(second.join("ab"), "c").unwrap();
+        assert_ne!(hash_path(&first).unwrap(), hash_path(&second).unwrap());
+
+        // An empty file and an empty directory of the same name differ by their tag
+        std::fs::remove_file(first.join("a")).unwrap();
+        std::fs::remove_file(second.join("ab")).unwrap();
+        std::fs::write(first.join("x"), "").unwrap();
+        std::fs::create_dir(second.join("x")).unwrap();
+        assert_ne!(hash_path(&first).unwrap(), hash_path(&second).unwrap());
+    }
+}
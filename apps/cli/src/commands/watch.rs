@@ -0,0 +1,261 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::{Result, Context};
+use tracing::{info, warn};
+
+use filemover_types::{Config, FolderHit, JournalFormat, PlanOptions};
+use filemover_scanner::{FileChangeWatcher, FolderScanner, FolderWatcher, SettledFolder};
+use filemover_planner::MovePlanner;
+use crate::config_manager::ConfigManager;
+use super::apply::{apply_command, ApplyOptions};
+use super::plan::{load_scan_results, save_move_plan};
+use super::scan_output::{write_scan_results, ScanOutputFormat};
+
+/// How often the watch loop checks for Ctrl+C while nothing happens
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Editors often save in several writes; the profile is reloaded once they stop for this long
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Time without changes before a new folder is evaluated
+    pub settle_delay: Duration,
+    /// Move matching folders right away instead of queueing them
+    pub auto_apply: bool,
+    /// Scan results file that collects matches for later review
+    pub queue_file: Option<PathBuf>,
+}
+
+pub async fn watch_command(
+    roots: Vec<PathBuf>,
+    profile: Option<String>,
+    options: WatchOptions,
+    config_manager: &ConfigManager,
+) -> Result<()> {
+    let mut config = config_manager.load_config(profile.as_deref())?;
+
+    let roots = if roots.is_empty() { config.roots.clone() } else { roots };
+    if roots.is_empty() {
+        anyhow::bail!("No root directories specified. Pass roots or configure them in the profile.");
+    }
+    // Watch events report absolute paths
+    let roots = roots.iter()
+        .map(|root| root.canonicalize().with_context(|| format!("Cannot watch {}", root.display())))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut scanner = FolderScanner::from_config(&config)
+        .context("Failed to create scanner")?;
+    let mut config_watcher = watch_config_files(config_manager, profile.as_deref(), &config);
+    let mut watcher = FolderWatcher::new(&roots, options.settle_delay)
+        .context("Failed to start watching")?;
+    let queue_file = options.queue_file.clone()
+        .unwrap_or_else(|| config_manager.get_config_dir().join("watch_queue.json"));
+
+    pretty_println!("👀 Watching {} root(s); new folders are evaluated after {}s without changes",
+             roots.len(), options.settle_delay.as_secs());
+    if options.auto_apply {
+        pretty_println!("   Matching folders are moved automatically");
+    } else {
+        pretty_println!("   Matching folders are queued in {}", queue_file.display());
+    }
+    pretty_println!("   Rule changes in the profile are picked up automatically; press Ctrl+C to stop");
+
+    let cancel_token = super::ctrl_c_cancellation_token();
+    while !cancel_token.is_cancelled() {
+        let settled = tokio::task::block_in_place(|| watcher.wait(POLL_INTERVAL))
+            .context("File system watch failed")?;
+
+        if config_changed(&mut config_watcher) {
+            match reload_config(config_manager, profile.as_deref()) {
+                Ok((reloaded, reloaded_scanner)) => {
+                    pretty_println!("🔄 Reloaded the profile ({} rules)", reloaded.rules.len());
+                    if reloaded.roots != config.roots {
+                        pretty_println!("   Changed roots take effect when watching is restarted");
+                    }
+                    config_watcher = watch_config_files(config_manager, profile.as_deref(), &reloaded);
+                    config = reloaded;
+                    scanner = reloaded_scanner;
+                }
+                Err(e) => warn!("Keeping the previous rules - failed to reload the profile: {:#}", e),
+            }
+        }
+
+        for folder in settled {
+            let Some(hit) = evaluate_folder(&scanner, &config, &folder) else {
+                continue;
+            };
+
+            let result = if options.auto_apply {
+                apply_hit(&hit, &config, config_manager).await
+            } else {
+                queue_hit(&hit, &queue_file)
+            };
+            if let Err(e) = result {
+                warn!("Failed to handle {}: {:#}", hit.path.display(), e);
+            }
+        }
+    }
+
+    pretty_println!("Stopped watching ({} folder(s) were still settling)", watcher.pending_count());
+    Ok(())
+}
+
+/// Watch the profile and its rule packs; without it the rules stay as loaded at startup
+fn watch_config_files(config_manager: &ConfigManager, profile: Option<&str>, config: &Config) -> Option<FileChangeWatcher> {
+    let files = config_manager.config_files(profile, config);
+    match FileChangeWatcher::new(&files, RELOAD_DEBOUNCE) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            warn!("Profile changes will not be picked up: {}", e);
+            None
+        }
+    }
+}
+
+fn config_changed(config_watcher: &mut Option<FileChangeWatcher>) -> bool {
+    let Some(watcher) = config_watcher else {
+        return false;
+    };
+    match tokio::task::block_in_place(|| watcher.wait(Duration::ZERO)) {
+        Ok(changed) => changed,
+        Err(e) => {
+            warn!("Stopped watching the profile: {}", e);
+            *config_watcher = None;
+            false
+        }
+    }
+}
+
+/// Load the profile again and rebuild the scanner (and with it the matching engine) from it
+fn reload_config(config_manager: &ConfigManager, profile: Option<&str>) -> Result<(Config, FolderScanner)> {
+    let config = config_manager.load_config(profile)?;
+    let scanner = FolderScanner::from_config(&config)
+        .context("Failed to create scanner")?;
+    info!("Reloaded profile with {} rules", config.rules.len());
+    Ok((config, scanner))
+}
+
+/// Match a settled folder against the rules; folders inside a rule's destination are ignored
+/// so moved folders are not picked up again
+fn evaluate_folder(scanner: &FolderScanner, config: &Config, folder: &SettledFolder) -> Option<FolderHit> {
+    if is_inside_destination(&folder.path, config) {
+        info!("Ignoring {} inside a rule destination", folder.path.display());
+        return None;
+    }
+
+    match scanner.evaluate(&folder.root, &folder.path) {
+        Ok(Some(hit)) => Some(hit),
+        Ok(None) => {
+            info!("No rule matches {}", folder.path.display());
+            None
+        }
+        Err(e) => {
+            // The folder may already be gone again
+            warn!("Failed to evaluate {}: {}", folder.path.display(), e);
+            None
+        }
+    }
+}
+
+fn is_inside_destination(path: &Path, config: &Config) -> bool {
+    config.rules.iter()
+        .filter(|rule| !rule.dest_root.as_os_str().is_empty())
+        .any(|rule| {
+            let dest_root = rule.dest_root.canonicalize().unwrap_or_else(|_| rule.dest_root.clone());
+            path.starts_with(dest_root)
+        })
+}
+
+async fn apply_hit(hit: &FolderHit, config: &Config, config_manager: &ConfigManager) -> Result<()> {
+    let plan_options = PlanOptions {
+        rename_suffix: config.rename_suffix.clone(),
+        ..Default::default()
+    };
+    let plan = MovePlanner::new()
+        .create_plan(std::slice::from_ref(hit), &config.rules, plan_options)
+        .context("Failed to create move plan")?;
+    if plan.summary.conflicts > 0 {
+        pretty_println!("⚠️  {} has conflicts and was not moved", hit.path.display());
+        return Ok(());
+    }
+
+    let plan_dir = config_manager.get_config_dir().join("watch_plans");
+    std::fs::create_dir_all(&plan_dir)
+        .with_context(|| format!("Failed to create {}", plan_dir.display()))?;
+    let plan_file = plan_dir.join(format!(
+        "watch_plan_{}.json",
+        chrono::Utc::now().format("%Y%m%d_%H%M%S%3f")
+    ));
+    save_move_plan(&plan, &plan_file)?;
+
+    pretty_println!("📦 Moving {}", hit.path.display());
+    apply_command(plan_file, None, JournalFormat::Jsonl, true, false, ApplyOptions::default(), config_manager).await
+}
+
+/// Add the hit to the queue file, replacing an earlier entry for the same folder
+fn queue_hit(hit: &FolderHit, queue_file: &Path) -> Result<()> {
+    let mut queued = if queue_file.exists() {
+        load_scan_results(queue_file)
+            .with_context(|| format!("Failed to read queue {}", queue_file.display()))?
+    } else {
+        Vec::new()
+    };
+    queued.retain(|queued_hit| queued_hit.path != hit.path);
+    queued.push(hit.clone());
+
+    write_scan_results(&queued, queue_file, ScanOutputFormat::Json)?;
+
+    let dest = hit.dest_preview.as_ref().map(|p| p.display().to_string()).unwrap_or_default();
+    pretty_println!("📥 Queued {} → {} ({} waiting; review with `filemover plan --input {}`)",
+             hit.path.display(), dest, queued.len(), queue_file.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::{PatternSpec, Rule};
+    use tempfile::TempDir;
+
+    fn hit(path: &Path) -> FolderHit {
+        FolderHit {
+            path: path.to_path_buf(),
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: Some(PathBuf::from("/archive/project")),
+            warnings: Vec::new(),
+            size_bytes: None,
+            item_count: None,
+            is_dir: true,
+        }
+    }
+
+    #[test]
+    fn test_queue_replaces_existing_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let queue = temp_dir.path().join("queue.json");
+
+        queue_hit(&hit(Path::new("/data/project")), &queue).unwrap();
+        queue_hit(&hit(Path::new("/data/other")), &queue).unwrap();
+        queue_hit(&hit(Path::new("/data/project")), &queue).unwrap();
+
+        let queued = load_scan_results(&queue).unwrap();
+        assert_eq!(queued.len(), 2);
+        assert_eq!(queued[1].path, PathBuf::from("/data/project"));
+    }
+
+    #[test]
+    fn test_folders_in_destinations_are_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config {
+            rules: vec![Rule::new(PatternSpec::new_glob("*"), temp_dir.path().join("archive"), "{name}".to_string())],
+            ..Default::default()
+        };
+
+        assert!(is_inside_destination(&temp_dir.path().join("archive/project"), &config));
+        assert!(!is_inside_destination(&temp_dir.path().join("incoming/project"), &config));
+    }
+}
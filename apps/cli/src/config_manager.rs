@@ -3,8 +3,13 @@ use std::fs;
 use anyhow::{Result, Context};
 use tracing::{debug, info};
 
-use filemover_types::Config;
+use filemover_types::{Config, RulePack};
+use crate::error::CliError;
 
+/// Rule pack files are named `<name>.rules.json`
+const RULE_PACK_SUFFIX: &str = ".rules";
+
+#[derive(Clone)]
 pub struct ConfigManager {
     config_dir: PathBuf,
     default_config_path: PathBuf,
@@ -61,7 +66,19 @@ impl ConfigManager {
         }
     }
 
+    /// Load a profile with the rules of its rule packs merged in
     pub fn load_config(&self, profile: Option<&str>) -> Result<Config> {
+        let mut config = self.load_config_without_packs(profile)?;
+        if !config.rule_packs.is_empty() {
+            let packs = self.load_rule_packs(&config)?;
+            let merged = config.merge_rule_packs(&packs);
+            debug!("Merged {} rules from {} rule packs", merged, packs.len());
+        }
+        Ok(config)
+    }
+
+    /// Load a profile as stored, without rule pack rules (for editing and copying the profile itself)
+    pub fn load_config_without_packs(&self, profile: Option<&str>) -> Result<Config> {
         let config_path = match profile {
             Some(name) => self.get_profile_path(name),
             None => self.default_config_path.clone(),
@@ -70,8 +87,8 @@ impl ConfigManager {
         debug!("Loading configuration from: {}", config_path.display());
 
         if !config_path.exists() {
-            if profile.is_some() {
-                anyhow::bail!("Profile '{}' not found at: {}", profile.unwrap(), config_path.display());
+            if let Some(profile) = profile {
+                return Err(CliError::profile_not_found(profile).into());
             } else {
                 // Create default config if it doesn't exist
                 let default_config = Config::default();
@@ -84,12 +101,38 @@ impl ConfigManager {
         let content = fs::read_to_string(&config_path)
             .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
 
-        let config: Config = serde_json::from_str(&content)
+        let mut config: Config = serde_json::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?;
 
+        let migrated = config.migrate_exclude_rules();
+        if migrated > 0 {
+            info!("Moved {} exclude rules to exclude patterns in {}", migrated, config_path.display());
+        }
+
         Ok(config)
     }
 
+    /// Read the rule packs a profile references; relative paths are resolved against the configuration directory
+    pub fn load_rule_packs(&self, config: &Config) -> Result<Vec<RulePack>> {
+        config.rule_packs.iter()
+            .map(|path| {
+                let path = self.config_dir.join(path);
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| CliError::config(format!("Failed to read rule pack {}: {}", path.display(), e)))?;
+                let pack = parse_rule_pack(&content)
+                    .map_err(|e| CliError::config(format!("Failed to parse rule pack {}: {}", path.display(), e)))?;
+                Ok(pack)
+            })
+            .collect()
+    }
+
+    /// Files a loaded profile was read from: the profile itself and its rule packs
+    pub fn config_files(&self, profile: Option<&str>, config: &Config) -> Vec<PathBuf> {
+        let mut files = vec![self.get_profile_path(profile.unwrap_or("default"))];
+        files.extend(config.rule_packs.iter().map(|path| self.config_dir.join(path)));
+        files
+    }
+
     pub fn save_config(&self, profile: &str, config: &Config) -> Result<()> {
         let config_path = self.get_profile_path(profile);
         self.save_config_to_path(&config_path, config)
@@ -139,7 +182,8 @@ impl ConfigManager {
 
                 if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
                     if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                        if stem != "config" { // Skip default config
+                        // Skip the default config and rule packs kept next to the profiles
+                        if stem != "config" && !stem.ends_with(RULE_PACK_SUFFIX) {
                             profiles.push(stem.to_string());
                         }
                     }
@@ -177,6 +221,20 @@ impl ConfigManager {
     pub fn get_config_dir(&self) -> &Path {
         &self.config_dir
     }
+    
+    pub fn get_config_file(&self) -> &Path {
+        &self.default_config_path
+    }
+}
+
+/// A pack document, or a plain rule list like the files `plan --rules` reads
+fn parse_rule_pack(content: &str) -> serde_json::Result<RulePack> {
+    let value: serde_json::Value = serde_json::from_str(content)?;
+    if value.is_array() {
+        Ok(RulePack { rules: serde_json::from_value(value)?, ..Default::default() })
+    } else {
+        serde_json::from_value(value)
+    }
 }
 
 #[cfg(test)]
@@ -236,6 +294,42 @@ mod tests {
         assert_eq!(loaded_config.roots[0], PathBuf::from("C:\\Test"));
     }
 
+    #[test]
+    fn test_load_config_merges_rule_packs() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ConfigManager::new(Some(temp_dir.path().join("config.json"))).unwrap();
+        
+        let own = Rule::new(PatternSpec::new_glob("*_old"), PathBuf::from("D:\\Archive"), "{name}".to_string());
+        let shared = Rule::new(PatternSpec::new_glob("IMG_*"), PathBuf::from("D:\\Photos"), "{name}".to_string());
+        let mut overridden = Rule::new(PatternSpec::new_glob("node_modules"), PathBuf::from("D:\\Trash"), "{name}".to_string());
+        let pack = RulePack {
+            name: Some("photos".to_string()),
+            version: Some("1.2".to_string()),
+            rules: vec![shared.clone(), overridden.clone()],
+        };
+        std::fs::write(temp_dir.path().join("photos.rules.json"), serde_json::to_string(&pack).unwrap()).unwrap();
+        // A plain rule list works too; the first pack wins for rules with the same ID
+        std::fs::write(temp_dir.path().join("dev.rules.json"), serde_json::to_string(&vec![shared.clone()]).unwrap()).unwrap();
+        
+        // The profile turns off one of the pack's rules by keeping a disabled copy
+        overridden.enabled = false;
+        let config = Config {
+            rules: vec![own.clone(), overridden.clone()],
+            rule_packs: vec![PathBuf::from("photos.rules.json"), PathBuf::from("dev.rules.json")],
+            ..Default::default()
+        };
+        manager.save_config("work", &config).unwrap();
+        
+        let loaded = manager.load_config(Some("work")).unwrap();
+        assert_eq!(loaded.rules, vec![own, overridden, shared]);
+        assert_eq!(manager.load_config_without_packs(Some("work")).unwrap(), config);
+        assert_eq!(manager.list_profiles().unwrap(), vec!["work".to_string()]);
+        
+        // A missing pack is a configuration error rather than silently fewer rules
+        std::fs::remove_file(temp_dir.path().join("dev.rules.json")).unwrap();
+        assert!(manager.load_config(Some("work")).is_err());
+    }
+    
     #[test]
     fn test_list_profiles() {
         let temp_dir = TempDir::new().unwrap();
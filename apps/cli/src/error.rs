@@ -1,5 +1,15 @@
 use thiserror::Error;
 use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Process exit codes; clap exits with 2 on invalid arguments and `CliError::error_code` values (10-40)
+/// are used for categorized failures
+pub const EXIT_FAILURE: i32 = 1;
+/// The command finished, but with warnings and `--strict` was given
+pub const EXIT_STRICT_WARNINGS: i32 = 3;
+
+/// Warnings that make the command fail under `--strict`
+static STRICT_WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
 
 #[derive(Error, Debug)]
 pub enum CliError {
@@ -221,6 +231,34 @@ impl CliError {
     }
 }
 
+/// Exit code for a failed command: the first categorized error in the chain, otherwise `EXIT_FAILURE`
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    error.chain()
+        .find_map(|cause| {
+            if let Some(cli_error) = cause.downcast_ref::<CliError>() {
+                Some(cli_error.error_code())
+            } else if cause.is::<std::io::Error>() {
+                Some(30)
+            } else if cause.is::<serde_json::Error>() {
+                Some(31)
+            } else if cause.is::<filemover_types::FileMoverError>() {
+                Some(40)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(EXIT_FAILURE)
+}
+
+/// Record a warning such as an unresolved conflict; with `--strict` the command exits with `EXIT_STRICT_WARNINGS`
+pub fn record_warning<S: Into<String>>(message: S) {
+    STRICT_WARNINGS.lock().unwrap().push(message.into());
+}
+
+pub fn take_warnings() -> Vec<String> {
+    std::mem::take(&mut *STRICT_WARNINGS.lock().unwrap())
+}
+
 // Helper function to display error with suggestions
 pub fn display_error_with_help(error: &CliError) {
     eprintln!("{}", error.user_message());
@@ -281,6 +319,17 @@ mod tests {
         assert!(suggestions.iter().any(|s| s.contains("verbose")));
     }
 
+    #[test]
+    fn test_exit_code() {
+        use anyhow::Context;
+        
+        let error = Err::<(), _>(CliError::profile_not_found("work")).context("Failed to load configuration").unwrap_err();
+        assert_eq!(exit_code(&error), 13);
+        let error = Err::<(), _>(std::io::Error::other("disk gone")).context("Failed to save").unwrap_err();
+        assert_eq!(exit_code(&error), 30);
+        assert_eq!(exit_code(&anyhow::anyhow!("something else")), EXIT_FAILURE);
+    }
+
     #[test]
     fn test_from_conversions() {
         let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "File not found");
@@ -0,0 +1,245 @@
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::Notify;
+use tracing::{debug, warn};
+
+use filemover_types::JournalFormat;
+use crate::config_manager::ConfigManager;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    /// List running and finished jobs
+    Status,
+    /// Start a scan; paths must be absolute because the daemon has its own working directory
+    Scan {
+        roots: Vec<PathBuf>,
+        profile: Option<String>,
+        output: PathBuf,
+    },
+    /// Start applying a plan
+    Apply {
+        plan: PathBuf,
+        profile: Option<String>,
+        journal: Option<PathBuf>,
+        journal_format: JournalFormat,
+    },
+    Cancel { id: u64 },
+    Shutdown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonResponse {
+    Jobs { jobs: Vec<JobStatus> },
+    Started { id: u64 },
+    Ok,
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Scan,
+    Apply,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum JobState {
+    Running,
+    Completed,
+    Cancelled,
+    Failed { message: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub id: u64,
+    pub kind: JobKind,
+    /// Plan file or scanned roots
+    pub description: String,
+    pub state: JobState,
+    pub started_utc: DateTime<Utc>,
+    pub finished_utc: Option<DateTime<Utc>>,
+    /// Completed operations, or folders visited for scans
+    pub done: u64,
+    /// Total operations (unknown for scans)
+    pub total: Option<u64>,
+    pub message: String,
+}
+
+/// Where the daemon of this configuration listens: a named pipe on Windows, a Unix domain socket
+/// in the configuration directory elsewhere
+pub fn daemon_endpoint(config_manager: &ConfigManager) -> PathBuf {
+    #[cfg(windows)]
+    {
+        let _ = config_manager;
+        let user = std::env::var("USERNAME").unwrap_or_else(|_| "default".to_string());
+        PathBuf::from(format!(r"\\.\pipe\filemover-{}", user))
+    }
+    #[cfg(not(windows))]
+    {
+        config_manager.get_config_dir().join("daemon.sock")
+    }
+}
+
+/// Send one request to the daemon and wait for its response (one JSON line each way per connection)
+pub async fn send_request(endpoint: &Path, request: &DaemonRequest) -> Result<DaemonResponse> {
+    let stream = connect(endpoint).await
+        .with_context(|| format!("No daemon is running at {} - start one with `filemover daemon`", endpoint.display()))?;
+    let (reader, mut writer) = tokio::io::split(stream);
+
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await?;
+
+    let mut response = String::new();
+    BufReader::new(reader).read_line(&mut response).await?;
+    if response.is_empty() {
+        anyhow::bail!("The daemon closed the connection without responding");
+    }
+    serde_json::from_str(&response).context("Invalid response from the daemon")
+}
+
+/// Answer requests with `handler` until `shutdown` is notified
+pub async fn serve<H, F>(endpoint: &Path, handler: H, shutdown: Arc<Notify>) -> Result<()>
+where
+    H: Fn(DaemonRequest) -> F + Clone + Send + Sync + 'static,
+    F: Future<Output = DaemonResponse> + Send + 'static,
+{
+    #[cfg(windows)]
+    {
+        use tokio::net::windows::named_pipe::ServerOptions;
+
+        let mut server = ServerOptions::new()
+            .first_pipe_instance(true)
+            .create(endpoint)
+            .with_context(|| format!("Failed to create {} - is another daemon running?", endpoint.display()))?;
+        loop {
+            tokio::select! {
+                connected = server.connect() => {
+                    connected?;
+                    // The next client needs a fresh pipe instance
+                    let client = std::mem::replace(&mut server, ServerOptions::new().create(endpoint)?);
+                    tokio::spawn(handle_connection(client, handler.clone()));
+                }
+                _ = shutdown.notified() => break,
+            }
+        }
+        Ok(())
+    }
+    #[cfg(unix)]
+    {
+        use tokio::net::UnixListener;
+
+        if endpoint.exists() {
+            if connect(endpoint).await.is_ok() {
+                anyhow::bail!("A daemon is already running at {}", endpoint.display());
+            }
+            // Left behind by a daemon that did not shut down cleanly
+            std::fs::remove_file(endpoint)
+                .with_context(|| format!("Failed to remove stale socket {}", endpoint.display()))?;
+        }
+        let listener = UnixListener::bind(endpoint)
+            .with_context(|| format!("Failed to listen on {}", endpoint.display()))?;
+        let result = loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        tokio::spawn(handle_connection(stream, handler.clone()));
+                    }
+                    Err(e) => break Err(e.into()),
+                },
+                _ = shutdown.notified() => break Ok(()),
+            }
+        };
+        let _ = std::fs::remove_file(endpoint);
+        result
+    }
+}
+
+async fn handle_connection<S, H, F>(stream: S, handler: H)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    H: Fn(DaemonRequest) -> F,
+    F: Future<Output = DaemonResponse>,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut line = String::new();
+    if let Err(e) = BufReader::new(reader).read_line(&mut line).await {
+        debug!("Failed to read daemon request: {}", e);
+        return;
+    }
+
+    let response = match serde_json::from_str::<DaemonRequest>(&line) {
+        Ok(request) => handler(request).await,
+        Err(e) => DaemonResponse::Error { message: format!("Invalid request: {}", e) },
+    };
+
+    let mut reply = match serde_json::to_string(&response) {
+        Ok(reply) => reply,
+        Err(e) => {
+            warn!("Failed to serialize daemon response: {}", e);
+            return;
+        }
+    };
+    reply.push('\n');
+    if let Err(e) = writer.write_all(reply.as_bytes()).await {
+        debug!("Failed to send daemon response: {}", e);
+    }
+    let _ = writer.flush().await;
+}
+
+#[cfg(windows)]
+async fn connect(endpoint: &Path) -> std::io::Result<tokio::net::windows::named_pipe::NamedPipeClient> {
+    tokio::net::windows::named_pipe::ClientOptions::new().open(endpoint)
+}
+
+#[cfg(unix)]
+async fn connect(endpoint: &Path) -> std::io::Result<tokio::net::UnixStream> {
+    tokio::net::UnixStream::connect(endpoint).await
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_request_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let endpoint = temp_dir.path().join("daemon.sock");
+        let shutdown = Arc::new(Notify::new());
+
+        let handler = |request: DaemonRequest| async move {
+            match request {
+                DaemonRequest::Cancel { id } => DaemonResponse::Started { id },
+                _ => DaemonResponse::Ok,
+            }
+        };
+        let server = tokio::spawn({
+            let endpoint = endpoint.clone();
+            let shutdown = shutdown.clone();
+            async move { serve(&endpoint, handler, shutdown).await }
+        });
+        while !endpoint.exists() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let response = send_request(&endpoint, &DaemonRequest::Cancel { id: 7 }).await.unwrap();
+        assert_eq!(response, DaemonResponse::Started { id: 7 });
+        // A second daemon on the same endpoint is refused
+        assert!(serve(&endpoint, handler, Arc::new(Notify::new())).await.is_err());
+
+        shutdown.notify_one();
+        server.await.unwrap().unwrap();
+        assert!(!endpoint.exists());
+    }
+}
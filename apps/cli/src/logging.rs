@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use chrono::{DateTime, Utc};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+
+use filemover_types::{LogRotation, LogSettings};
+
+/// Set up tracing on stderr and, when `settings.file` is set, in a rotating log file.
+/// Returns the file the log is currently written to.
+pub fn init_logging(verbose: bool, settings: &LogSettings) -> Result<Option<PathBuf>> {
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
+
+    let log_level = if verbose {
+        tracing::Level::DEBUG
+    } else {
+        tracing::Level::INFO
+    };
+
+    let env_filter = |default: String| {
+        tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(default))
+    };
+
+    // Blocking writer: commands leave through `std::process::exit`, which would drop a background writer's buffer.
+    // The file also gets the core crates' messages (their targets start with `filemover_` too).
+    let file_layer = match &settings.file {
+        Some(file) => Some(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(file_appender(file, settings)?)
+                .with_filter(env_filter(format!("filemover={}", log_level))),
+        ),
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        // Keep stdout for the command's result when it is read by scripts
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(env_filter(format!("filemover_cli={}", log_level)))
+        )
+        .with(file_layer)
+        .init();
+
+    Ok(settings.file.as_deref().map(|file| current_log_file(file, settings.rotation, Utc::now())))
+}
+
+fn file_appender(file: &Path, settings: &LogSettings) -> Result<RollingFileAppender> {
+    let (directory, prefix, suffix) = split_log_path(file);
+    let mut builder = RollingFileAppender::builder()
+        .rotation(rotation(settings.rotation))
+        .filename_prefix(prefix);
+    if let Some(suffix) = suffix {
+        builder = builder.filename_suffix(suffix);
+    }
+    if let Some(keep_files) = settings.keep_files {
+        builder = builder.max_log_files(keep_files.max(1));
+    }
+    builder.build(&directory)
+        .with_context(|| format!("Failed to open log file in {}", directory.display()))
+}
+
+fn rotation(rotation: LogRotation) -> Rotation {
+    match rotation {
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+        LogRotation::Never => Rotation::NEVER,
+    }
+}
+
+/// `logs/filemover.log` → (`logs`, `filemover`, `log`); the rotation date goes between name and extension
+fn split_log_path(file: &Path) -> (PathBuf, String, Option<String>) {
+    let directory = match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let prefix = file.file_stem().unwrap_or(file.as_os_str()).to_string_lossy().into_owned();
+    let suffix = file.extension().map(|extension| extension.to_string_lossy().into_owned());
+    (directory, prefix, suffix)
+}
+
+/// The file the appender writes to at `now` (it names rotated files after the UTC date)
+pub fn current_log_file(file: &Path, rotation: LogRotation, now: DateTime<Utc>) -> PathBuf {
+    let (directory, prefix, suffix) = split_log_path(file);
+    let name = match rotation {
+        LogRotation::Never => prefix,
+        LogRotation::Daily => format!("{}.{}", prefix, now.format("%Y-%m-%d")),
+        LogRotation::Hourly => format!("{}.{}", prefix, now.format("%Y-%m-%d-%H")),
+    };
+    match suffix {
+        Some(suffix) => directory.join(format!("{}.{}", name, suffix)),
+        None => directory.join(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_current_log_file() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 5, 7, 30, 0).unwrap();
+        let file = Path::new("logs/filemover.log");
+
+        assert_eq!(current_log_file(file, LogRotation::Daily, now), PathBuf::from("logs/filemover.2024-03-05.log"));
+        assert_eq!(current_log_file(file, LogRotation::Hourly, now), PathBuf::from("logs/filemover.2024-03-05-07.log"));
+        assert_eq!(current_log_file(file, LogRotation::Never, now), PathBuf::from("logs/filemover.log"));
+        assert_eq!(current_log_file(Path::new("filemover"), LogRotation::Daily, now), PathBuf::from("./filemover.2024-03-05"));
+    }
+}
@@ -1,15 +1,22 @@
+#[macro_use]
+mod output;
 mod commands;
 mod config_manager;
 mod progress;
 mod error;
+mod ipc;
+mod logging;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use std::path::PathBuf;
 use tracing::{info, error};
 use anyhow::Result;
+use filemover_types::{ErrorPolicy, JournalFormat, Locale, OpKind, ResultKind};
+use uuid::Uuid;
 
 use crate::commands::*;
 use crate::config_manager::ConfigManager;
+use crate::output::OutputMode;
 
 #[derive(Parser)]
 #[command(name = "filemover")]
@@ -28,8 +35,28 @@ struct Cli {
     verbose: bool,
 
     /// Output format (json, pretty, minimal)
-    #[arg(short, long, default_value = "pretty")]
-    output: String,
+    #[arg(short, long, value_enum, default_value = "pretty")]
+    output: OutputMode,
+
+    /// Print only one final `key=value` line per command: no progress bars, summaries or prompts
+    #[arg(short, long, conflicts_with = "output")]
+    quiet: bool,
+
+    /// Do not draw progress bars (summaries are still printed)
+    #[arg(long)]
+    no_progress: bool,
+
+    /// Also write the log to this file, rotated as set in the configuration (daily by default)
+    #[arg(long, value_name = "FILE", global = true)]
+    log_file: Option<PathBuf>,
+
+    /// Exit with code 3 when the command finishes with warnings such as unresolved conflicts or missing sources
+    #[arg(long)]
+    strict: bool,
+
+    /// Language of conflict, warning and journal messages (en, ja); defaults to the configuration, then the system language
+    #[arg(long, value_name = "LANG", global = true)]
+    lang: Option<Locale>,
 }
 
 #[derive(Subcommand)]
@@ -39,17 +66,70 @@ enum Commands {
         /// Root directories to scan
         roots: Vec<PathBuf>,
         
-        /// Output file for scan results
+        /// Read more root directories from a file, one per line ("-" for stdin)
+        #[arg(long, value_name = "FILE", conflicts_with = "resume")]
+        roots_from: Option<PathBuf>,
+        
+        /// Output file for scan results ("-" for stdout)
         #[arg(short, long)]
         output: Option<PathBuf>,
         
+        /// Output file format
+        #[arg(long, value_enum, default_value = "json")]
+        format: ScanOutputFormat,
+        
         /// Profile name to use
         #[arg(short, long)]
         profile: Option<String>,
+        
+        /// Match rules against individual files as well as folders
+        #[arg(long)]
+        include_files: bool,
+        
+        /// Calculate the size of each matched folder
+        #[arg(long)]
+        calculate_sizes: bool,
+        
+        /// Skip folders matching this pattern (glob, or regex:<expr>); can be repeated
+        #[arg(long = "exclude", value_name = "PATTERN")]
+        exclude: Vec<String>,
+        
+        /// Only revisit folders changed since the previous scan (Windows, NTFS)
+        #[arg(long)]
+        incremental: bool,
+        
+        /// State file for incremental scans
+        #[arg(long, requires = "incremental")]
+        state: Option<PathBuf>,
+        
+        /// Continue an interrupted scan from its checkpoint file
+        #[arg(long, value_name = "CHECKPOINT", conflicts_with_all = ["roots", "incremental"])]
+        resume: Option<PathBuf>,
+        
+        /// Print which rules were evaluated for each match and why it matched
+        #[arg(long)]
+        explain: bool,
+    },
+    
+    /// Compare two scan result files
+    ScanDiff {
+        /// Earlier scan results
+        old: PathBuf,
+        
+        /// Later scan results
+        new: PathBuf,
+        
+        /// Write the differences as JSON to this file
+        #[arg(short, long)]
+        report: Option<PathBuf>,
     },
     
     /// Create move plan from scan results
+    #[command(args_conflicts_with_subcommands = true)]
     Plan {
+        #[command(subcommand)]
+        action: Option<PlanAction>,
+        
         /// Input scan results file
         #[arg(short, long)]
         input: Option<PathBuf>,
@@ -68,6 +148,14 @@ enum Commands {
         /// Plan file to simulate
         #[arg(short, long)]
         plan: PathBuf,
+        
+        /// Measure write throughput on the destination volume for the time estimate
+        #[arg(long)]
+        measure: bool,
+        
+        /// Journal to take recorded throughput from (defaults to the newest journal)
+        #[arg(short, long, conflicts_with = "measure")]
+        journal: Option<PathBuf>,
     },
     
     /// Execute move plan
@@ -80,9 +168,53 @@ enum Commands {
         #[arg(short, long)]
         journal: Option<PathBuf>,
         
+        /// Journal storage format (jsonl, sqlite)
+        #[arg(long, default_value = "jsonl")]
+        journal_format: JournalFormat,
+        
         /// Skip confirmation prompt
         #[arg(long)]
         yes: bool,
+        
+        /// Proceed even if the file system changed since the plan was created
+        #[arg(long)]
+        accept_changes: bool,
+        
+        /// Only run operations whose source path matches this glob
+        #[arg(long, value_name = "GLOB")]
+        filter: Option<String>,
+        
+        /// Only run operations created by this rule (ID or label); can be repeated
+        #[arg(long = "rule", value_name = "RULE")]
+        rules: Vec<String>,
+        
+        /// Leave out operations that have conflicts
+        #[arg(long)]
+        only_conflict_free: bool,
+        
+        /// Run at most this many operations; the rest is left for the next batch
+        #[arg(long, value_name = "N")]
+        max_ops: Option<usize>,
+        
+        /// Move at most this much data (e.g. 500MB, 1GB); the rest is left for the next batch
+        #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+        max_bytes: Option<u64>,
+        
+        /// Continue with the operations an earlier batch left (journal of that batch)
+        #[arg(long, value_name = "JOURNAL")]
+        resume: Option<PathBuf>,
+        
+        /// What to do when an operation fails (continue, stop, rollback-batch); defaults to the profile setting
+        #[arg(long, value_name = "POLICY")]
+        on_error: Option<ErrorPolicy>,
+        
+        /// Afterwards remove source folders left empty (only inside the configured roots)
+        #[arg(long)]
+        prune_empty: bool,
+        
+        /// Go through the whole run without changing any files, writing a simulated journal
+        #[arg(long)]
+        dry_run: bool,
     },
     
     /// Undo previous operation
@@ -90,6 +222,81 @@ enum Commands {
         /// Journal file from previous operation
         #[arg(short, long)]
         journal: PathBuf,
+        
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+        
+        /// Confirm each operation separately: (y)es, (n)o, (a)ll remaining or (q)uit
+        #[arg(short, long, conflicts_with = "yes")]
+        interactive: bool,
+    },
+    
+    /// Summarize a completed run from its journal
+    Report {
+        /// Journal of the run
+        #[arg(short, long)]
+        journal: PathBuf,
+        
+        /// Document format
+        #[arg(short, long, value_enum, default_value = "markdown")]
+        format: RunReportFormat,
+        
+        /// Output file (stdout when omitted or `-`)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// Profile whose rule labels name the rule groups
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    
+    /// Remove source folders left empty by the operations in a journal
+    CleanEmpty {
+        /// Journal of the operations
+        #[arg(short, long)]
+        journal: PathBuf,
+        
+        /// Only remove folders inside this root (repeatable; defaults to the profile's roots)
+        #[arg(long = "root", value_name = "DIR")]
+        roots: Vec<PathBuf>,
+        
+        /// Profile whose roots to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        
+        /// Only list the folders that would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Verify the results of a completed operation
+    Verify {
+        /// Journal file from the operation to verify
+        #[arg(short, long)]
+        journal: PathBuf,
+        
+        /// Plan file used for the operation (enables size checks)
+        #[arg(short, long)]
+        plan: Option<PathBuf>,
+        
+        /// Compare destination sizes with the plan
+        #[arg(long)]
+        sizes: bool,
+        
+        /// Compare content hashes of leftover sources with their destinations
+        #[arg(long)]
+        hashes: bool,
+        
+        /// Write a JSON report to this file
+        #[arg(short, long)]
+        report: Option<PathBuf>,
+    },
+    
+    /// Inspect operation journals
+    Journal {
+        #[command(subcommand)]
+        action: JournalAction,
     },
     
     /// Manage configuration profiles
@@ -97,6 +304,281 @@ enum Commands {
         #[command(subcommand)]
         action: ConfigAction,
     },
+    
+    /// Inspect and try out rules
+    Rules {
+        #[command(subcommand)]
+        action: RulesAction,
+    },
+    
+    /// Scan, plan and apply in one step, keeping the scan results, plan and journal in a run directory
+    Run {
+        /// Root directories to scan (defaults to the profile's roots)
+        roots: Vec<PathBuf>,
+        
+        /// Profile name to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        
+        /// Directory for the run's files
+        #[arg(long)]
+        run_dir: Option<PathBuf>,
+        
+        /// Journal storage format (jsonl, sqlite)
+        #[arg(long, default_value = "jsonl")]
+        journal_format: JournalFormat,
+        
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    
+    /// Run a background daemon that executes scans and applies independently of the terminal
+    #[command(args_conflicts_with_subcommands = true)]
+    Daemon {
+        #[command(subcommand)]
+        action: Option<DaemonAction>,
+    },
+    
+    /// List the daemon's scans and applies with their progress
+    Status {
+        /// Cancel the job with this ID
+        #[arg(long, value_name = "ID")]
+        cancel: Option<u64>,
+    },
+    
+    /// Watch roots for new folders and handle matches once they stop changing
+    Watch {
+        /// Root directories to watch (defaults to the profile's roots)
+        roots: Vec<PathBuf>,
+        
+        /// Profile name to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        
+        /// Seconds without changes before a new folder is evaluated
+        #[arg(long, default_value_t = 30)]
+        settle: u64,
+        
+        /// Move matching folders immediately instead of queueing them
+        #[arg(long)]
+        auto_apply: bool,
+        
+        /// Scan results file that collects matches for confirmation
+        #[arg(short, long, conflicts_with = "auto_apply")]
+        queue: Option<PathBuf>,
+    },
+    
+    /// Register recurring runs with the Windows Task Scheduler
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum PlanAction {
+    /// Merge several plan files into one
+    Merge {
+        /// Plan files to merge, in order of precedence
+        #[arg(required = true, num_args = 2..)]
+        plans: Vec<PathBuf>,
+        
+        /// Output plan file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    
+    /// Write the part of a plan that matches the given filters to a new plan file
+    Filter {
+        /// Plan file to filter
+        plan: PathBuf,
+        
+        /// Only keep operations produced by this rule (ID or label); can be repeated
+        #[arg(long, value_name = "ID|LABEL")]
+        rule: Vec<String>,
+        
+        /// Only keep operations whose source path matches this glob
+        #[arg(long, value_name = "GLOB")]
+        path: Option<String>,
+        
+        /// Only keep operations at least this large (e.g. 500MB, 1GB)
+        #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+        min_size: Option<u64>,
+        
+        /// Profile whose rule labels are used for --rule
+        #[arg(long)]
+        profile: Option<String>,
+        
+        /// Output plan file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    
+    /// Render a plan as a document for review before applying it
+    Report {
+        /// Plan file to report on
+        plan: PathBuf,
+        
+        /// Document format
+        #[arg(short, long, value_enum, default_value = "markdown")]
+        format: PlanReportFormat,
+        
+        /// Output file (stdout when omitted or `-`)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// Profile whose rule labels name the rule groups
+        #[arg(long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum JournalAction {
+    /// Show journal entries with filtering, sorting and statistics
+    Show {
+        /// Journal file to inspect
+        file: PathBuf,
+        
+        /// Only show entries with this result (ok, skip, failed)
+        #[arg(long)]
+        result: Option<ResultKind>,
+        
+        /// Only show entries with this operation (move, copy-delete, rename)
+        #[arg(long)]
+        op: Option<OpKind>,
+        
+        /// Only show entries produced by this rule ID
+        #[arg(long)]
+        rule: Option<Uuid>,
+        
+        /// Only show entries tagged with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        
+        /// Only show entries whose source or destination is under this path
+        #[arg(long)]
+        path: Option<PathBuf>,
+        
+        /// Sort order
+        #[arg(long, value_enum, default_value = "time")]
+        sort: JournalSortKey,
+        
+        /// Reverse the sort order
+        #[arg(long)]
+        reverse: bool,
+        
+        /// Maximum number of entries to list
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    
+    /// Delete old journals according to the retention policy
+    Prune {
+        /// Journal directory (defaults to the configured directory)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        
+        /// Keep only the newest N journals
+        #[arg(long)]
+        keep: Option<usize>,
+        
+        /// Delete journals older than this many days
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u32>,
+        
+        /// Only list the journals that would be deleted
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RulesAction {
+    /// Show which rule each folder name would match, and where it would go
+    Test {
+        /// Folder names (or paths) to try
+        names: Vec<String>,
+        
+        /// Profile name to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    
+    /// Check every rule for structural problems and find rules that overlap or can never match
+    Lint {
+        /// Profile name to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonAction {
+    /// Start a scan in the running daemon
+    Scan {
+        /// Root directories to scan (defaults to the profile's roots)
+        roots: Vec<PathBuf>,
+        
+        /// Profile name to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        
+        /// Output file for scan results (JSON)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    
+    /// Apply a plan in the running daemon (without confirmation)
+    Apply {
+        /// Plan file to execute
+        #[arg(short, long)]
+        plan: PathBuf,
+        
+        /// Profile whose execution and journal settings to use
+        #[arg(long)]
+        profile: Option<String>,
+        
+        /// Journal file for undo operations
+        #[arg(short, long)]
+        journal: Option<PathBuf>,
+        
+        /// Journal storage format (jsonl, sqlite)
+        #[arg(long, default_value = "jsonl")]
+        journal_format: JournalFormat,
+    },
+    
+    /// Stop the running daemon
+    Stop,
+}
+
+#[derive(Subcommand)]
+enum ScheduleAction {
+    /// Register a task that runs `filemover run` for a profile
+    Create {
+        /// Profile to run
+        #[arg(short, long)]
+        profile: String,
+        
+        /// Run every day at this time
+        #[arg(long, value_name = "HH:MM", value_parser = parse_time)]
+        daily: chrono::NaiveTime,
+        
+        /// Replace an existing task for the profile
+        #[arg(long)]
+        force: bool,
+    },
+    
+    /// List the registered tasks with their next run time
+    List,
+    
+    /// Remove the task of a profile
+    Remove {
+        /// Profile whose task to remove
+        #[arg(short, long)]
+        profile: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -125,74 +607,179 @@ enum ConfigAction {
         /// Profile name
         profile: String,
     },
+
+    /// Open profile in $EDITOR and validate it before saving
+    Edit {
+        /// Profile name
+        profile: String,
+    },
+
+    /// Export profile as a portable bundle
+    Export {
+        /// Profile name
+        profile: String,
+
+        /// Bundle file (defaults to <profile>.fmprofile)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Import profile from a bundle or profile file
+    Import {
+        /// Bundle file
+        file: PathBuf,
+
+        /// Profile name (defaults to the name stored in the bundle)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Replace an existing profile without asking
+        #[arg(long)]
+        overwrite: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let command_name = matches.subcommand_name().unwrap_or_default().to_string();
+    
+    output::set_output_mode(cli.output);
+    output::set_quiet(cli.quiet);
+    progress::set_progress_enabled(!cli.quiet && !cli.no_progress);
+    
+    // Load configuration (it holds the log settings)
+    let config_manager = ConfigManager::new(cli.config.clone())?;
     
     // Initialize logging
-    init_logging(cli.verbose)?;
+    let mut log_settings = config_manager.load_config(None).map(|config| config.log).unwrap_or_default();
+    match cli.log_file.clone() {
+        Some(file) => log_settings.file = Some(file),
+        // Relative to the configuration directory, as scheduled runs start in an arbitrary directory
+        None => log_settings.file = log_settings.file.map(|file| config_manager.get_config_dir().join(file)),
+    }
+    let configured_locale = config_manager.load_config(None).ok().and_then(|config| config.locale);
+    output::set_locale(cli.lang.or(configured_locale).unwrap_or_else(Locale::from_env));
+    let log_file = logging::init_logging(cli.verbose, &log_settings)?;
+    if let Some(log_file) = &log_file {
+        eprintln!("📝 Logging to {}", log_file.display());
+    }
     
     info!("FileMover CLI v{} starting", env!("CARGO_PKG_VERSION"));
     
-    // Load configuration
-    let config_manager = ConfigManager::new(cli.config.clone())?;
-    
     // Execute command
     let result = match cli.command {
-        Commands::Scan { roots, output, profile } => {
-            scan_command(roots, output, profile, &config_manager).await
+        Commands::Scan { roots, roots_from, output, format, profile, include_files, calculate_sizes, exclude, incremental, state, resume, explain } => {
+            let options = ScanCommandOptions {
+                include_files,
+                calculate_sizes,
+                exclude_patterns: exclude,
+                incremental,
+                state_file: state,
+                resume,
+                format,
+                explain,
+                roots_from,
+            };
+            scan_command(roots, output, profile, options, &config_manager).await
+        }
+        Commands::ScanDiff { old, new, report } => {
+            scan_diff_command(old, new, report).await
+        }
+        Commands::Plan { action: Some(action), .. } => {
+            plan_action_command(action, &config_manager).await
         }
-        Commands::Plan { input, output, rules } => {
+        Commands::Plan { action: None, input, output, rules } => {
             plan_command(input, output, rules, &config_manager).await
         }
-        Commands::DryRun { plan } => {
-            dry_run_command(plan, &config_manager).await
+        Commands::DryRun { plan, measure, journal } => {
+            let options = ThroughputOptions { measure, journal };
+            dry_run_command(plan, options, &config_manager).await
+        }
+        Commands::Apply {
+            plan, journal, journal_format, yes, accept_changes, filter, rules, only_conflict_free, max_ops, max_bytes, resume, on_error,
+            prune_empty, dry_run,
+        } => {
+            let options = ApplyOptions {
+                rules, path: filter, only_conflict_free, max_ops, max_bytes, resume, on_error, prune_empty, dry_run,
+            };
+            apply_command(plan, journal, journal_format, yes, accept_changes, options, &config_manager).await
+        }
+        Commands::Undo { journal, yes, interactive } => {
+            undo_command(journal, yes, interactive, &config_manager).await
         }
-        Commands::Apply { plan, journal, yes } => {
-            apply_command(plan, journal, yes, &config_manager).await
+        Commands::Report { journal, format, output, profile } => {
+            // Rule labels are optional decoration, so a missing profile is not an error
+            let rules = config_manager.load_config(profile.as_deref())
+                .map(|config| config.rules)
+                .unwrap_or_default();
+            report_command(journal, format, output, &rules).await
         }
-        Commands::Undo { journal } => {
-            undo_command(journal, &config_manager).await
+        Commands::CleanEmpty { journal, roots, profile, dry_run } => {
+            clean_empty_command(journal, roots, profile, dry_run, &config_manager).await
+        }
+        Commands::Verify { journal, plan, sizes, hashes, report } => {
+            let options = VerifyOptions { check_sizes: sizes, check_hashes: hashes };
+            verify_command(journal, plan, options, report, &config_manager).await
+        }
+        Commands::Journal { action } => {
+            journal_command(action, &config_manager).await
         }
         Commands::Config { action } => {
             config_command(action, &config_manager).await
         }
+        Commands::Rules { action } => {
+            rules_command(action, &config_manager).await
+        }
+        Commands::Run { roots, profile, run_dir, journal_format, yes } => {
+            let options = RunOptions { run_dir, journal_format, skip_confirmation: yes };
+            run_command(roots, profile, options, &config_manager).await
+        }
+        Commands::Daemon { action } => {
+            daemon_command(action, &config_manager).await
+        }
+        Commands::Status { cancel } => {
+            status_command(cancel, &config_manager).await
+        }
+        Commands::Watch { roots, profile, settle, auto_apply, queue } => {
+            let options = WatchOptions {
+                settle_delay: std::time::Duration::from_secs(settle),
+                auto_apply,
+                queue_file: queue,
+            };
+            watch_command(roots, profile, options, &config_manager).await
+        }
+        Commands::Schedule { action } => {
+            schedule_command(action, &config_manager).await
+        }
     };
     
     match result {
         Ok(_) => {
+            let warnings = error::take_warnings();
+            if cli.strict && !warnings.is_empty() {
+                eprintln!("⚠️  Finished with {} warning(s) in strict mode:", warnings.len());
+                for warning in &warnings {
+                    eprintln!("  - {}", warning);
+                }
+                std::process::exit(error::EXIT_STRICT_WARNINGS);
+            }
             info!("Command completed successfully");
             Ok(())
         }
         Err(e) => {
             error!("Command failed: {}", e);
-            std::process::exit(1);
+            output::emit_error(&command_name, &e);
+            if output::is_pretty() {
+                if let Some(cli_error) = e.chain().find_map(|cause| cause.downcast_ref::<error::CliError>()) {
+                    error::display_error_with_help(cli_error);
+                }
+            }
+            if let Some(log_file) = &log_file {
+                eprintln!("See the log for details: {}", log_file.display());
+            }
+            std::process::exit(error::exit_code(&e));
         }
     }
 }
-
-fn init_logging(verbose: bool) -> Result<()> {
-    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-    
-    let log_level = if verbose {
-        tracing::Level::DEBUG
-    } else {
-        tracing::Level::INFO
-    };
-    
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| {
-                    tracing_subscriber::EnvFilter::new(
-                        format!("filemover_cli={}", log_level)
-                    )
-                })
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
-    
-    Ok(())
-}
\ No newline at end of file
@@ -0,0 +1,232 @@
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use anyhow::Result;
+use clap::ValueEnum;
+use filemover_types::Locale;
+use serde::Serialize;
+
+/// Version of the JSON documents written with `--output json`; bump it when fields change meaning or go away
+pub const OUTPUT_SCHEMA_VERSION: u32 = 1;
+
+/// How commands report their results on stdout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputMode {
+    /// Human-readable text with summaries
+    #[default]
+    Pretty,
+    /// One JSON document with the command's result
+    Json,
+    /// `key=value` lines with the main figures
+    Minimal,
+}
+
+static OUTPUT_MODE: OnceLock<OutputMode> = OnceLock::new();
+static QUIET: OnceLock<bool> = OnceLock::new();
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+/// Set by `emit_failure`, whose document already reports the error `emit_error` is then called with
+static FAILURE_EMITTED: AtomicBool = AtomicBool::new(false);
+
+/// Set once at startup from `--output`
+pub fn set_output_mode(mode: OutputMode) {
+    let _ = OUTPUT_MODE.set(mode);
+}
+
+pub fn output_mode() -> OutputMode {
+    OUTPUT_MODE.get().copied().unwrap_or_default()
+}
+
+/// Set once at startup from `--quiet`
+pub fn set_quiet(quiet: bool) {
+    let _ = QUIET.set(quiet);
+}
+
+/// Pretty mode reduced to one `key=value` line per command for cron and Task Scheduler logs
+pub fn is_quiet() -> bool {
+    QUIET.get().copied().unwrap_or_default()
+}
+
+/// Set once at startup from `--lang`, the configuration or the environment
+pub fn set_locale(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+/// Language of conflict, warning and journal messages; English until set
+pub fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+pub fn is_pretty() -> bool {
+    output_mode() == OutputMode::Pretty && !is_quiet()
+}
+
+/// `println!` that only prints in pretty mode, so stdout stays machine-readable otherwise
+macro_rules! pretty_println {
+    ($($arg:tt)*) => {
+        if $crate::output::is_pretty() {
+            println!($($arg)*);
+        }
+    };
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, T: Serialize> {
+    schema_version: u32,
+    command: &'a str,
+    status: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<&'a T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Report a command's result in JSON or minimal mode (pretty mode has printed it already)
+pub fn emit_result<T: Serialize>(command: &str, result: &T) -> Result<()> {
+    match output_mode() {
+        OutputMode::Pretty if is_quiet() => {
+            let fields = minimal_lines(&serde_json::to_value(result)?);
+            println!("{}", quiet_line(command, "ok", &fields));
+        }
+        OutputMode::Pretty => {}
+        OutputMode::Json => {
+            let envelope = Envelope {
+                schema_version: OUTPUT_SCHEMA_VERSION,
+                command,
+                status: "ok",
+                result: Some(result),
+                error: None,
+            };
+            println!("{}", serde_json::to_string_pretty(&envelope)?);
+        }
+        OutputMode::Minimal => {
+            for line in minimal_lines(&serde_json::to_value(result)?) {
+                println!("{}", line);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Report a failed command; JSON mode writes an error document to stdout
+pub fn emit_error(command: &str, error: &anyhow::Error) {
+    if FAILURE_EMITTED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    if output_mode() == OutputMode::Pretty && is_quiet() {
+        let fields = [format!("error={:#}", error)];
+        println!("{}", quiet_line(command, "error", &fields));
+        return;
+    }
+    if output_mode() != OutputMode::Json {
+        return;
+    }
+    let envelope = Envelope::<()> {
+        schema_version: OUTPUT_SCHEMA_VERSION,
+        command,
+        status: "error",
+        result: None,
+        error: Some(format!("{:#}", error)),
+    };
+    if let Ok(json) = serde_json::to_string_pretty(&envelope) {
+        println!("{}", json);
+    }
+}
+
+/// Report a command that failed after producing a result (e.g. verification issues) in one
+/// error document that carries both; returns `error` for the command to return
+pub fn emit_failure<T: Serialize>(command: &str, result: &T, error: anyhow::Error) -> anyhow::Error {
+    let Ok(value) = serde_json::to_value(result) else {
+        return error;
+    };
+    FAILURE_EMITTED.store(true, Ordering::SeqCst);
+    let message = format!("error={:#}", error);
+    match output_mode() {
+        OutputMode::Pretty if is_quiet() => {
+            let mut fields = minimal_lines(&value);
+            fields.push(message);
+            println!("{}", quiet_line(command, "error", &fields));
+        }
+        OutputMode::Pretty => {}
+        OutputMode::Json => {
+            let envelope = Envelope {
+                schema_version: OUTPUT_SCHEMA_VERSION,
+                command,
+                status: "error",
+                result: Some(&value),
+                error: Some(format!("{:#}", error)),
+            };
+            if let Ok(json) = serde_json::to_string_pretty(&envelope) {
+                println!("{}", json);
+            }
+        }
+        OutputMode::Minimal => {
+            for line in minimal_lines(&value) {
+                println!("{}", line);
+            }
+            println!("{}", message);
+        }
+    }
+    error
+}
+
+/// Top-level scalar fields as `key=value`; lists are reported by their length
+fn minimal_lines(value: &serde_json::Value) -> Vec<String> {
+    let Some(fields) = value.as_object() else {
+        return Vec::new();
+    };
+    fields.iter()
+        .filter_map(|(key, value)| match value {
+            serde_json::Value::Null | serde_json::Value::Object(_) => None,
+            serde_json::Value::String(text) => Some(format!("{}={}", key, text)),
+            serde_json::Value::Array(items) => Some(format!("{}={}", key, items.len())),
+            other => Some(format!("{}={}", key, other)),
+        })
+        .collect()
+}
+
+/// `command=scan status=ok key=value ...` on one line; values with spaces or quotes are quoted
+fn quiet_line(command: &str, status: &str, fields: &[String]) -> String {
+    let mut parts = vec![format!("command={}", command), format!("status={}", status)];
+    for field in fields {
+        let (key, value) = field.split_once('=').unwrap_or((field, ""));
+        if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+            parts.push(format!("{}={:?}", key, value));
+        } else {
+            parts.push(field.clone());
+        }
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_lines() {
+        let value = serde_json::json!({
+            "output": "plan.json",
+            "operations": 3,
+            "cancelled": false,
+            "journal": null,
+            "issues": ["a", "b"],
+            "summary": { "conflicts": 0 },
+        });
+        assert_eq!(
+            minimal_lines(&value),
+            vec!["cancelled=false", "issues=2", "operations=3", "output=plan.json"]
+        );
+    }
+
+    #[test]
+    fn test_quiet_line() {
+        let fields = vec![
+            "moved=3".to_string(),
+            "journal=D:\\My Journals\\j.jsonl".to_string(),
+            "note=".to_string(),
+        ];
+        assert_eq!(
+            quiet_line("apply", "ok", &fields),
+            r#"command=apply status=ok moved=3 journal="D:\\My Journals\\j.jsonl" note="""#
+        );
+    }
+}
@@ -1,5 +1,34 @@
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
-use indicatif::{ProgressBar, ProgressStyle, ProgressState, ProgressFinish};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+static SHOW_PROGRESS: OnceLock<bool> = OnceLock::new();
+
+/// Set once at startup; `--no-progress` and `--quiet` turn the bars off
+pub fn set_progress_enabled(enabled: bool) {
+    let _ = SHOW_PROGRESS.set(enabled);
+}
+
+pub fn progress_enabled() -> bool {
+    SHOW_PROGRESS.get().copied().unwrap_or(true)
+}
+
+/// Progress bar that stays hidden when progress is turned off (it still counts, for the summaries)
+pub fn new_bar(len: u64) -> ProgressBar {
+    if progress_enabled() {
+        ProgressBar::new(len)
+    } else {
+        ProgressBar::with_draw_target(Some(len), ProgressDrawTarget::hidden())
+    }
+}
+
+pub fn new_spinner() -> ProgressBar {
+    if progress_enabled() {
+        ProgressBar::new_spinner()
+    } else {
+        ProgressBar::with_draw_target(None, ProgressDrawTarget::hidden())
+    }
+}
 
 pub struct ProgressReporter {
     bar: ProgressBar,
@@ -9,7 +38,7 @@ pub struct ProgressReporter {
 
 impl ProgressReporter {
     pub fn new(total: u64, task_name: &str) -> Self {
-        let bar = ProgressBar::new(total);
+        let bar = new_bar(total);
         
         bar.set_style(
             ProgressStyle::default_bar()
@@ -30,7 +59,7 @@ impl ProgressReporter {
     }
 
     pub fn new_spinner(task_name: &str) -> Self {
-        let bar = ProgressBar::new_spinner();
+        let bar = new_spinner();
         
         bar.set_style(
             ProgressStyle::default_spinner()
@@ -120,7 +149,11 @@ impl MultiProgressReporter {
     pub fn new() -> Self {
         Self {
             bars: Vec::new(),
-            multi: indicatif::MultiProgress::new(),
+            multi: if progress_enabled() {
+                indicatif::MultiProgress::new()
+            } else {
+                indicatif::MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+            },
         }
     }
 
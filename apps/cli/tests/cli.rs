@@ -0,0 +1,247 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use filemover_planner::MovePlanner;
+use filemover_types::{Config, FolderHit, PatternSpec, PlanOptions, Rule};
+use tempfile::TempDir;
+
+/// Run the binary with its configuration and home kept inside `dir`
+fn filemover(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_filemover"))
+        .arg("--config")
+        .arg(dir.join("config").join("config.json"))
+        .args(args)
+        .env("HOME", dir)
+        .env("APPDATA", dir)
+        .output()
+        .expect("failed to run filemover")
+}
+
+fn folder_hit(path: &Path, rule: &Rule) -> FolderHit {
+    FolderHit {
+        path: path.to_path_buf(),
+        name: path.file_name().unwrap().to_string_lossy().into_owned(),
+        matched_rule: Some(rule.id),
+        matched_rules: Vec::new(),
+        tags: Vec::new(),
+        dest_preview: None,
+        warnings: Vec::new(),
+        size_bytes: Some(0),
+        item_count: None,
+        is_dir: true,
+    }
+}
+
+/// A profile with one rule, scan results, a plan and the journal of applying it
+struct Fixture {
+    dir: TempDir,
+    scan: PathBuf,
+    plan: PathBuf,
+    journal: PathBuf,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("data").join("2019_report");
+        std::fs::create_dir_all(&source).unwrap();
+
+        let mut rule = Rule::new(PatternSpec::new_glob("2019*"), dir.path().join("archive"), "{name}".to_string());
+        rule.label = Some("Reports".to_string());
+        let config = Config { rules: vec![rule.clone()], ..Default::default() };
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(dir.path().join("config").join("config.json"), serde_json::to_string_pretty(&config).unwrap()).unwrap();
+
+        let hits = vec![folder_hit(&source, &rule)];
+        let scan = dir.path().join("scan.json");
+        std::fs::write(&scan, filemover_types::to_versioned_json(&hits, true).unwrap()).unwrap();
+
+        let plan = MovePlanner::new().create_plan(&hits, &[rule], PlanOptions::default()).unwrap();
+        let plan_file = dir.path().join("plan.json");
+        std::fs::write(&plan_file, filemover_types::to_versioned_json(&plan, true).unwrap()).unwrap();
+
+        let journal = dir.path().join("journal.jsonl");
+        let output = filemover(dir.path(), &[
+            "apply", "--yes",
+            "--plan", plan_file.to_str().unwrap(),
+            "--journal", journal.to_str().unwrap(),
+        ]);
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+        Self { dir, scan, plan: plan_file, journal }
+    }
+
+    fn path(&self, name: &str) -> String {
+        self.dir.path().join(name).to_string_lossy().into_owned()
+    }
+
+    /// Run with `--output json` and parse stdout, which has to be exactly one JSON document
+    fn json(&self, args: &[&str]) -> serde_json::Value {
+        let output = filemover(self.dir.path(), &[&["--output", "json"], args].concat());
+        let document: serde_json::Value = serde_json::from_slice(&output.stdout)
+            .unwrap_or_else(|e| panic!("{:?} did not print one JSON document ({}):\n{}",
+                                       args, e, String::from_utf8_lossy(&output.stdout)));
+        assert_eq!(document["schema_version"], 1);
+        document
+    }
+
+    /// Run with `--quiet`; stdout has to be exactly one `key=value` line
+    fn quiet(&self, args: &[&str]) -> String {
+        let output = filemover(self.dir.path(), &[&["--quiet"], args].concat());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(stdout.lines().count(), 1, "{:?} printed:\n{}", args, stdout);
+        assert!(stdout.starts_with("command="), "{:?} printed:\n{}", args, stdout);
+        stdout
+    }
+}
+
+#[test]
+fn test_strict_apply_of_clean_plan_succeeds() {
+    let temp_dir = TempDir::new().unwrap();
+    let source = temp_dir.path().join("data").join("2019_report");
+    let archive = temp_dir.path().join("archive");
+    std::fs::create_dir_all(&source).unwrap();
+
+    let rule = Rule::new(PatternSpec::new_glob("2019*"), archive.clone(), "{name}".to_string());
+    let plan = MovePlanner::new()
+        .create_plan(&[folder_hit(&source, &rule)], &[rule], PlanOptions::default())
+        .unwrap();
+    let plan_file = temp_dir.path().join("plan.json");
+    std::fs::write(&plan_file, filemover_types::to_versioned_json(&plan, true).unwrap()).unwrap();
+    let journal = temp_dir.path().join("journal.jsonl");
+
+    let output = filemover(temp_dir.path(), &[
+        "--strict", "apply", "--yes",
+        "--plan", plan_file.to_str().unwrap(),
+        "--journal", journal.to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(journal.exists());
+}
+
+#[test]
+fn test_verify_json() {
+    let fixture = Fixture::new();
+    // The journal was written without moving anything, so the destination is missing
+    let document = fixture.json(&["verify", "--journal", fixture.journal.to_str().unwrap()]);
+    assert_eq!(document["status"], "error");
+    assert_eq!(document["result"]["issues"][0]["kind"]["type"], "DestinationMissing");
+}
+
+#[test]
+fn test_journal_show_json() {
+    let fixture = Fixture::new();
+    let document = fixture.json(&["journal", "show", fixture.journal.to_str().unwrap()]);
+    assert_eq!(document["status"], "ok");
+    assert_eq!(document["result"]["shown"], 1);
+}
+
+#[test]
+fn test_report_json() {
+    let fixture = Fixture::new();
+    let document = fixture.json(&["report", "--journal", fixture.journal.to_str().unwrap()]);
+    assert_eq!(document["result"]["completed"], 1);
+    assert!(document["result"]["document"].as_str().unwrap().contains("Reports"));
+}
+
+#[test]
+fn test_rules_lint_json() {
+    let fixture = Fixture::new();
+    let document = fixture.json(&["rules", "lint"]);
+    assert_eq!(document["status"], "ok");
+    assert_eq!(document["result"]["rules"], 1);
+}
+
+#[test]
+fn test_rules_test_json() {
+    let fixture = Fixture::new();
+    let document = fixture.json(&["rules", "test", "2019_report", "notes"]);
+    assert_eq!(document["result"]["tested"], 2);
+    assert_eq!(document["result"]["matched"], 1);
+}
+
+#[test]
+fn test_scan_diff_json() {
+    let fixture = Fixture::new();
+    let later = fixture.path("later.json");
+    std::fs::write(&later, filemover_types::to_versioned_json(&Vec::<FolderHit>::new(), true).unwrap()).unwrap();
+    let document = fixture.json(&["scan-diff", fixture.scan.to_str().unwrap(), &later]);
+    assert_eq!(document["result"]["removed"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_plan_filter_json() {
+    let fixture = Fixture::new();
+    let filtered = fixture.path("filtered.json");
+    let document = fixture.json(&["plan", "filter", fixture.plan.to_str().unwrap(), "--rule", "Reports", "--output", &filtered]);
+    assert_eq!(document["result"]["kept"], 1);
+}
+
+#[test]
+fn test_config_list_json() {
+    let fixture = Fixture::new();
+    let document = fixture.json(&["config", "list"]);
+    assert_eq!(document["result"]["profiles"], serde_json::json!(["default"]));
+}
+
+#[test]
+fn test_verify_quiet() {
+    let fixture = Fixture::new();
+    let line = fixture.quiet(&["verify", "--journal", fixture.journal.to_str().unwrap()]);
+    assert!(line.contains("status=error"));
+    assert!(line.contains("issues=1"));
+}
+
+#[test]
+fn test_journal_show_quiet() {
+    let fixture = Fixture::new();
+    let line = fixture.quiet(&["journal", "show", fixture.journal.to_str().unwrap()]);
+    assert!(line.contains("shown=1"));
+}
+
+#[test]
+fn test_report_quiet() {
+    let fixture = Fixture::new();
+    let line = fixture.quiet(&["report", "--journal", fixture.journal.to_str().unwrap()]);
+    assert!(line.contains("completed=1"));
+}
+
+#[test]
+fn test_rules_lint_quiet() {
+    let fixture = Fixture::new();
+    let line = fixture.quiet(&["rules", "lint"]);
+    assert!(line.contains("status=ok"));
+    assert!(line.contains("rules=1"));
+}
+
+#[test]
+fn test_rules_test_quiet() {
+    let fixture = Fixture::new();
+    let line = fixture.quiet(&["rules", "test", "2019_report", "notes"]);
+    assert!(line.contains("matched=1"));
+}
+
+#[test]
+fn test_scan_diff_quiet() {
+    let fixture = Fixture::new();
+    let later = fixture.path("later.json");
+    std::fs::write(&later, filemover_types::to_versioned_json(&Vec::<FolderHit>::new(), true).unwrap()).unwrap();
+    let line = fixture.quiet(&["scan-diff", fixture.scan.to_str().unwrap(), &later]);
+    assert!(line.contains("removed=1"));
+}
+
+#[test]
+fn test_plan_filter_quiet() {
+    let fixture = Fixture::new();
+    let filtered = fixture.path("filtered.json");
+    let line = fixture.quiet(&["plan", "filter", fixture.plan.to_str().unwrap(), "--rule", "Reports", "--output", &filtered]);
+    assert!(line.contains("kept=1"));
+}
+
+#[test]
+fn test_config_list_quiet() {
+    let fixture = Fixture::new();
+    let line = fixture.quiet(&["config", "list"]);
+    assert!(line.contains("profiles=1"));
+}
@@ -1,8 +1,10 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::Path;
+use serde::Serialize;
 use tauri::State;
 use tracing::{info, debug};
 
-use filemover_types::Config;
+use filemover_types::{catalog, Config, Locale, MessageId};
 use crate::state::AppState;
 use crate::error::{GuiResult, GuiError, gui_error};
 
@@ -13,14 +15,20 @@ pub async fn load_config(
 ) -> GuiResult<Config> {
     info!("Loading configuration profile: {:?}", profile);
     
-    let profile_name = profile.unwrap_or_else(|| {
-        state.current_profile.lock()
-            .map(|p| p.clone())
-            .unwrap_or_else(|_| "default".to_string())
-    });
+    let profile_name = profile.unwrap_or_else(|| state.current_profile_name());
+    
+    // Profiles written by the CLI; without one the current config from state is used
+    let profile_path = state.profile_path(&profile_name);
+    if profile_path.exists() {
+        let config = read_profile(&profile_path)?;
+        *state.config.lock()
+            .map_err(|_| gui_error!(config, "Failed to access configuration"))? = config.clone();
+        *state.current_profile.lock()
+            .map_err(|_| gui_error!(config, "Failed to update current profile"))? = profile_name;
+        debug!("Configuration loaded from {}", profile_path.display());
+        return Ok(config);
+    }
     
-    // TODO: Implement actual file loading
-    // For now, return the current config from state
     let config = state.config.lock()
         .map_err(|_| gui_error!(config, "Failed to access configuration"))?
         .clone();
@@ -29,6 +37,15 @@ pub async fn load_config(
     Ok(config)
 }
 
+/// Read a profile file, migrating older layouts the same way the CLI does
+pub(crate) fn read_profile(path: &Path) -> GuiResult<Config> {
+    let content = std::fs::read_to_string(path)?;
+    let mut config: Config = serde_json::from_str(&content)
+        .map_err(|e| gui_error!(config, format!("Failed to parse {}: {}", path.display(), e)))?;
+    config.migrate_exclude_rules();
+    Ok(config)
+}
+
 #[tauri::command]
 pub async fn save_config(
     profile: String,
@@ -111,11 +128,54 @@ pub async fn delete_profile(name: String) -> GuiResult<()> {
     Ok(())
 }
 
+/// Templates of every message in one language; `{name}` placeholders are filled from a message's args
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageCatalog {
+    pub locale: Locale,
+    pub messages: HashMap<MessageId, &'static str>,
+}
+
+/// Catalog for the given language, or for the current one (see `current_locale`)
+#[tauri::command]
+pub async fn get_message_catalog(
+    locale: Option<Locale>,
+    state: State<'_, AppState>,
+) -> GuiResult<MessageCatalog> {
+    let locale = match locale {
+        Some(locale) => locale,
+        None => current_locale(&state)?,
+    };
+    let messages = MessageId::ALL.into_iter().map(|id| (id, catalog(locale, id))).collect();
+    Ok(MessageCatalog { locale, messages })
+}
+
+/// The app setting, then the profile's `locale`, then the system language
+pub(crate) fn current_locale(state: &AppState) -> GuiResult<Locale> {
+    let from_settings = state.settings.lock()
+        .map_err(|_| gui_error!(config, "Failed to access settings"))?
+        .language;
+    let from_profile = state.config.lock()
+        .map_err(|_| gui_error!(config, "Failed to access configuration"))?
+        .locale;
+    Ok(from_settings.or(from_profile).unwrap_or_else(Locale::from_env))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use uuid::Uuid;
 
+    #[tokio::test]
+    async fn test_message_catalog_follows_profile_locale() {
+        let state = AppState::new();
+        state.config.lock().unwrap().locale = Some(Locale::Ja);
+
+        let catalog = get_message_catalog(None, State::from(&state)).await.unwrap();
+        assert_eq!(catalog.locale, Locale::Ja);
+        assert_eq!(catalog.messages.len(), MessageId::ALL.len());
+        assert_eq!(catalog.messages[&MessageId::ConflictSourceMissing], "移動元がなくなっています");
+    }
+
     #[tokio::test]
     async fn test_load_config() {
         let state = AppState::new();
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::info;
+
+use filemover_types::{ConflictPolicy, NodeChange, PlanNodeId, ValidationDelta};
+use crate::state::AppState;
+use crate::error::{GuiResult, GuiError, gui_error};
+use crate::plan_index::{PlanNodeFilter, PlanNodePage, PlanNodeSort};
+use super::{apply_node_change, with_plan_index};
+
+/// Conflicts shown per page of the wizard when the frontend does not ask for a size
+const DEFAULT_CONFLICT_PAGE_SIZE: usize = 50;
+
+/// How the user chose to settle a node's conflict in the wizard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConflictResolution {
+    /// Move under this name instead
+    RenameTo(String),
+    Skip,
+    Overwrite,
+    /// Move the folder's contents into the existing folder
+    Merge,
+}
+
+impl ConflictResolution {
+    fn into_change(self, node_id: PlanNodeId) -> NodeChange {
+        match self {
+            ConflictResolution::RenameTo(name) => NodeChange::RenameNode(node_id, name),
+            ConflictResolution::Skip => NodeChange::SetSkip(node_id, true),
+            ConflictResolution::Overwrite => NodeChange::SetConflictPolicy(node_id, ConflictPolicy::Overwrite),
+            ConflictResolution::Merge => NodeChange::SetConflictPolicy(node_id, ConflictPolicy::Merge),
+        }
+    }
+}
+
+/// Nodes that still have conflicts, in path order, one page at a time
+#[tauri::command]
+pub async fn list_conflicts(
+    plan_session_id: String,
+    page: usize,
+    page_size: Option<usize>,
+    state: State<'_, AppState>,
+) -> GuiResult<PlanNodePage> {
+    let page_size = page_size.unwrap_or(DEFAULT_CONFLICT_PAGE_SIZE).max(1);
+    let filter = PlanNodeFilter { conflicts_only: true, ..PlanNodeFilter::default() };
+    with_plan_index(&state, &plan_session_id, |plan, index| {
+        index.page(plan, &filter, PlanNodeSort::default(), page.saturating_mul(page_size), page_size)
+    })
+}
+
+/// Apply the user's choice for one conflicted node; the delta tells the wizard what was
+/// resolved and whether the choice caused new conflicts
+#[tauri::command]
+pub async fn resolve_conflict(
+    plan_session_id: String,
+    node_id: PlanNodeId,
+    resolution: ConflictResolution,
+    state: State<'_, AppState>,
+) -> GuiResult<ValidationDelta> {
+    if let ConflictResolution::RenameTo(name) = &resolution {
+        validate_new_name(name)?;
+    }
+
+    let delta = apply_node_change(&state, &plan_session_id, resolution.clone().into_change(node_id))?;
+    info!("Conflict on node {} resolved with {:?}: {} resolved, {} new",
+          node_id, resolution, delta.resolved_conflicts.len(), delta.new_conflicts.len());
+    Ok(delta)
+}
+
+/// A new name replaces the last path component only
+fn validate_new_name(name: &str) -> GuiResult<()> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed == "." || trimmed == ".." {
+        return Err(gui_error!(invalid_op, format!("Invalid name: '{}'", name)));
+    }
+    if name.contains(['/', '\\']) {
+        return Err(gui_error!(invalid_op, format!("Name must not contain a path separator: '{}'", name)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use filemover_types::{Conflict, MovePlan, OpKind, PlanNode, PlanSummary};
+
+    fn node(name: &str, conflicts: Vec<Conflict>) -> PlanNode {
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: name.to_string(),
+            path_before: PathBuf::from("/source").join(name),
+            name_after: name.to_string(),
+            path_after: PathBuf::from("/dest").join(name),
+            kind: OpKind::Move,
+            size_bytes: None,
+            warnings: vec![],
+            conflicts,
+            children: vec![],
+            rule_id: None,
+            tags: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conflicts_are_listed_and_resolved() {
+        let state = AppState::new();
+        let taken = || vec![Conflict::NameExists { existing_path: PathBuf::from("/dest/x") }];
+        let nodes = [node("a", taken()), node("b", taken()), node("c", vec![])];
+        let (a, b) = (nodes[0].id, nodes[1].id);
+        let plan = MovePlan {
+            roots: nodes.iter().map(|n| n.id).collect(),
+            nodes: nodes.into_iter().map(|n| (n.id, n)).collect(),
+            summary: PlanSummary::default(),
+            execution_order: vec![],
+        };
+        let session_id = state.create_plan_session(None);
+        state.update_plan_session(session_id, |session| session.plan = Some(plan));
+        let session = session_id.to_string();
+
+        let page = list_conflicts(session.clone(), 0, Some(1), State::from(&state)).await.unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.nodes[0].id, a);
+        let page = list_conflicts(session.clone(), 1, Some(1), State::from(&state)).await.unwrap();
+        assert_eq!(page.nodes[0].id, b);
+
+        let delta = resolve_conflict(session.clone(), a, ConflictResolution::Overwrite, State::from(&state)).await.unwrap();
+        assert_eq!(delta.resolved_conflicts.len(), 1);
+        resolve_conflict(session.clone(), b, ConflictResolution::Skip, State::from(&state)).await.unwrap();
+        assert_eq!(list_conflicts(session.clone(), 0, None, State::from(&state)).await.unwrap().total, 0);
+
+        let rename = ConflictResolution::RenameTo("../escape".to_string());
+        assert!(resolve_conflict(session, b, rename, State::from(&state)).await.is_err());
+    }
+}
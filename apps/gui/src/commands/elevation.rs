@@ -0,0 +1,267 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::Utc;
+use serde::Serialize;
+use tauri::State;
+use tracing::{info, debug, warn};
+use uuid::Uuid;
+
+use filemover_types::{Conflict, JournalFormat, MovePlan, OpKind, PlanNode, Progress};
+use filemover_planner::subset_plan;
+use crate::state::{AppState, SessionStatus};
+use crate::error::{GuiResult, GuiError, gui_error};
+use crate::notifications::{notify_task_finished, TaskFinishedEvent};
+use super::emit_execution_progress;
+
+/// Folder in the config directory for the plans handed to the elevated helper
+const ELEVATION_DIR: &str = "elevated";
+
+/// How often the helper's journal is read to report its progress
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ElevationStarted {
+    /// Follow this execution session (and `execute://progress`) for the helper's progress
+    pub execution_session_id: Uuid,
+    pub operations: usize,
+    pub plan_path: PathBuf,
+    pub journal_path: PathBuf,
+}
+
+/// Run the operations of a plan that need administrator rights in an elevated copy of the CLI,
+/// keeping this session open and reporting the helper's progress as a normal execution
+#[tauri::command]
+pub async fn request_elevation(
+    plan_session_id: String,
+    state: State<'_, AppState>,
+) -> GuiResult<ElevationStarted> {
+    let plan_id = Uuid::parse_str(&plan_session_id)
+        .map_err(|_| gui_error!(execution, "Invalid plan session ID format"))?;
+    let plan = state.get_plan_session(plan_id)
+        .ok_or_else(|| gui_error!(session_not_found, plan_session_id))?
+        .plan
+        .ok_or_else(|| gui_error!(execution, "Plan session has no plan"))?;
+
+    let elevated = elevated_subset(&plan)
+        .ok_or_else(|| gui_error!(execution, "No operations in the plan need administrator rights"))?;
+    let operations = elevated.nodes.values().filter(|node| !matches!(node.kind, OpKind::Skip | OpKind::None)).count();
+    let helper = helper_path()?;
+
+    // The helper reads the plan from disk and writes its own journal
+    let dir = state.config_dir.join(ELEVATION_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| gui_error!(execution, format!("Failed to create {}: {}", dir.display(), e)))?;
+    let plan_path = dir.join(format!("{}.plan.json", Uuid::new_v4()));
+    let json = filemover_types::to_versioned_json(&elevated, true)
+        .map_err(|e| gui_error!(execution, format!("Failed to serialize plan: {}", e)))?;
+    std::fs::write(&plan_path, json)
+        .map_err(|e| gui_error!(execution, format!("Failed to write {}: {}", plan_path.display(), e)))?;
+
+    let journal_path = state.journal_dir().join(filemover_journal::journal_file_name(JournalFormat::Jsonl, Utc::now()));
+
+    let execution_session_id = state.create_execution_session(plan_id);
+    let total_bytes = elevated.summary.total_bytes;
+    let session_journal = journal_path.clone();
+    state.update_execution_session(execution_session_id, move |session| {
+        session.status = SessionStatus::Running;
+        session.journal_path = Some(session_journal);
+        session.progress = Some(Progress::new(operations as u64, total_bytes));
+    });
+
+    let state_clone = state.inner().clone();
+    let args = helper_args(&plan_path, &journal_path);
+    let helper_journal = journal_path.clone();
+    tokio::spawn(async move {
+        run_elevated(&state_clone, execution_session_id, helper, args, helper_journal).await;
+    });
+
+    info!("Started elevated helper for {} operations (session {})", operations, execution_session_id);
+    Ok(ElevationStarted { execution_session_id, operations, plan_path, journal_path })
+}
+
+/// Plan with only the root operations that need administrator rights, or None when there are none
+fn elevated_subset(plan: &MovePlan) -> Option<MovePlan> {
+    let subset = subset_plan(plan, |node| needs_elevation(plan, node));
+    (!subset.roots.is_empty()).then_some(subset)
+}
+
+/// A permission conflict on the node or anywhere below it (merged folders run as one operation)
+fn needs_elevation(plan: &MovePlan, node: &PlanNode) -> bool {
+    node.conflicts.iter().any(|conflict| matches!(conflict, Conflict::Permission { .. }))
+        || node.children.iter()
+            .filter_map(|id| plan.nodes.get(id))
+            .any(|child| needs_elevation(plan, child))
+}
+
+/// The CLI is installed next to the GUI
+fn helper_path() -> GuiResult<PathBuf> {
+    let exe = std::env::current_exe()
+        .map_err(|e| gui_error!(execution, format!("Failed to locate the application: {}", e)))?;
+    let helper = exe.with_file_name(if cfg!(windows) { "filemover.exe" } else { "filemover" });
+    if helper.is_file() {
+        Ok(helper)
+    } else {
+        Err(gui_error!(execution, format!("The command-line helper was not found at {}", helper.display())))
+    }
+}
+
+fn helper_args(plan_path: &Path, journal_path: &Path) -> Vec<String> {
+    vec![
+        "--quiet".to_string(),
+        "apply".to_string(),
+        "--plan".to_string(),
+        plan_path.display().to_string(),
+        "--journal".to_string(),
+        journal_path.display().to_string(),
+        "--yes".to_string(),
+        // With administrator rights the permission conflicts are expected to clear on revalidation
+        "--accept-changes".to_string(),
+    ]
+}
+
+async fn run_elevated(state: &AppState, session_id: Uuid, helper: PathBuf, args: Vec<String>, journal_path: PathBuf) {
+    let mut helper_run = tokio::task::spawn_blocking(move || run_as_administrator(&helper, &args));
+
+    // The helper appends to its journal as it goes, so its progress can be read from there
+    let result = loop {
+        tokio::select! {
+            result = &mut helper_run => break result,
+            _ = tokio::time::sleep(PROGRESS_INTERVAL) => update_progress(state, session_id, &journal_path),
+        }
+    };
+    update_progress(state, session_id, &journal_path);
+
+    let error = match result {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e),
+        Err(e) => Some(format!("The elevated helper stopped unexpectedly: {}", e)),
+    };
+    state.update_execution_session(session_id, |session| {
+        session.status = if error.is_some() { SessionStatus::Failed } else { SessionStatus::Completed };
+        session.error = error.clone();
+    });
+    match &error {
+        Some(e) => warn!("Elevated helper failed for session {}: {}", session_id, e),
+        None => info!("Elevated helper finished for session {}", session_id),
+    }
+
+    emit_execution_progress(state, session_id);
+    if let Some(session) = state.get_execution_session(session_id) {
+        notify_task_finished(state, TaskFinishedEvent::apply(&session));
+    }
+}
+
+fn update_progress(state: &AppState, session_id: Uuid, journal_path: &Path) {
+    if !journal_path.exists() {
+        return;
+    }
+    let entries = match filemover_journal::open_journal(journal_path).and_then(|journal| journal.read_checked()) {
+        Ok(report) => report.entries,
+        Err(e) => {
+            debug!("Cannot read {} yet: {}", journal_path.display(), e);
+            return;
+        }
+    };
+
+    state.update_execution_session(session_id, |session| {
+        let progress = session.progress.get_or_insert_with(|| Progress::new(0, None));
+        progress.completed_ops = entries.len() as u64;
+        progress.bytes_processed = entries.iter()
+            .filter_map(|entry| entry.metadata.as_ref().and_then(|metadata| metadata.size_bytes))
+            .sum();
+        progress.current_item = entries.last().map(|entry| entry.source.display().to_string());
+    });
+    emit_execution_progress(state, session_id);
+}
+
+/// Start-Process -Verb RunAs shows the UAC prompt; declining it fails the run like any other error
+#[cfg(windows)]
+fn run_as_administrator(program: &Path, args: &[String]) -> Result<(), String> {
+    let script = format!(
+        "$p = Start-Process -FilePath {} -ArgumentList {} -Verb RunAs -WindowStyle Hidden -Wait -PassThru; exit $p.ExitCode",
+        powershell_quote(&program.display().to_string()),
+        powershell_quote(&argument_list(args)),
+    );
+    let status = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .status()
+        .map_err(|e| format!("Failed to start PowerShell: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "The elevated helper did not finish (exit code {}); the administrator prompt may have been declined",
+            status.code().unwrap_or(-1)
+        ))
+    }
+}
+
+#[cfg(not(windows))]
+fn run_as_administrator(_program: &Path, _args: &[String]) -> Result<(), String> {
+    Err("Running operations as administrator is only supported on Windows".to_string())
+}
+
+/// Start-Process joins its arguments with spaces, so each one is wrapped in double quotes
+#[cfg_attr(not(windows), allow(dead_code))]
+fn argument_list(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| format!("\"{}\"", arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn powershell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::{Permission, PlanNodeId, PlanSummary};
+
+    fn node(name: &str, conflicts: Vec<Conflict>) -> PlanNode {
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: name.to_string(),
+            path_before: PathBuf::from("/source").join(name),
+            name_after: name.to_string(),
+            path_after: PathBuf::from("/protected").join(name),
+            kind: OpKind::Move,
+            size_bytes: None,
+            warnings: vec![],
+            conflicts,
+            children: vec![],
+            rule_id: None,
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn test_elevated_subset_keeps_protected_operations() {
+        let protected = node("protected", vec![Conflict::Permission { required: Permission::Administrator }]);
+        let open = node("open", vec![]);
+        let mut plan = MovePlan {
+            roots: vec![protected.id, open.id],
+            nodes: [protected.clone(), open].into_iter().map(|n| (n.id, n)).collect(),
+            summary: PlanSummary::default(),
+            execution_order: vec![],
+        };
+
+        let subset = elevated_subset(&plan).unwrap();
+        assert_eq!(subset.roots, vec![protected.id]);
+
+        plan.roots.retain(|id| *id != protected.id);
+        plan.nodes.remove(&protected.id);
+        assert!(elevated_subset(&plan).is_none());
+    }
+
+    #[test]
+    fn test_argument_list_quotes_paths() {
+        let args = helper_args(Path::new("C:\\My Plans\\it's.plan.json"), Path::new("C:\\journal.jsonl"));
+        let list = argument_list(&args);
+        assert!(list.starts_with("\"--quiet\" \"apply\" \"--plan\" \"C:\\My Plans\\it's.plan.json\""));
+        assert_eq!(powershell_quote(&list).matches("''").count(), 1);
+    }
+}
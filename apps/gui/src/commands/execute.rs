@@ -1,10 +1,15 @@
 use std::path::PathBuf;
+use std::time::Instant;
+use chrono::Utc;
 use tauri::State;
 use tracing::{info, debug, warn};
 use uuid::Uuid;
 
-use crate::state::{AppState, SessionStatus};
+use filemover_types::{CancellationToken, JournalFormat, OpKind, PlanNodeId, Progress};
+use crate::state::{AppState, NodeResult, NodeStatus, SessionStatus};
 use crate::error::{GuiResult, GuiError, gui_error};
+use crate::events::{ExecutionProgressEvent, NodeStatusEvent, EXECUTE_PROGRESS_EVENT, NODE_STATUS_EVENT};
+use crate::notifications::{notify_task_finished, TaskFinishedEvent};
 
 #[tauri::command]
 pub async fn execute_plan(
@@ -29,48 +34,72 @@ pub async fn execute_plan(
     
     // Determine journal path
     let journal_file = journal_path.unwrap_or_else(|| {
-        PathBuf::from(format!(
-            "filemover_journal_{}.jsonl",
-"default_journal"
-        ))
+        state.journal_dir().join(filemover_journal::journal_file_name(JournalFormat::Jsonl, Utc::now()))
     });
     
+    let cancel_token = state.register_cancel_token(execution_session_id);
+    
     // Clone necessary data for the async task
     let state_clone = state.inner().clone();
     
     // Start execution in background
     tokio::spawn(async move {
-        // Update session status to running
-        state_clone.update_execution_session(execution_session_id, |session| {
-            session.status = SessionStatus::Running;
-            session.journal_path = Some(journal_file.clone());
-        });
-        
-        match perform_execution(plan_id, journal_file, &state_clone).await {
-            Ok(_) => {
-                state_clone.update_execution_session(execution_session_id, |session| {
-                    session.status = SessionStatus::Completed;
-                });
-                info!("Execution completed successfully for session {}", execution_session_id);
-            }
-            Err(error) => {
-                state_clone.update_execution_session(execution_session_id, |session| {
-                    session.status = SessionStatus::Failed;
-                    session.error = Some(error.to_string());
-                });
-                warn!("Execution failed for session {}: {}", execution_session_id, error);
-            }
-        }
+        run_execution(&state_clone, plan_id, execution_session_id, journal_file, cancel_token).await;
     });
     
     debug!("Execution session {} created and started", execution_session_id);
     Ok(execution_session_id)
 }
 
+/// Execute a plan to completion and record the outcome in the session
+pub(crate) async fn run_execution(
+    state: &AppState,
+    plan_id: Uuid,
+    execution_session_id: Uuid,
+    journal_file: PathBuf,
+    cancel_token: CancellationToken,
+) {
+    // Update session status to running
+    state.update_execution_session(execution_session_id, |session| {
+        session.status = SessionStatus::Running;
+        session.journal_path = Some(journal_file.clone());
+    });
+    
+    match perform_execution(plan_id, execution_session_id, journal_file, state, &cancel_token).await {
+        Ok(_) if cancel_token.is_cancelled() => {
+            state.update_execution_session(execution_session_id, |session| {
+                session.status = SessionStatus::Cancelled;
+            });
+            info!("Execution cancelled for session {}", execution_session_id);
+        }
+        Ok(_) => {
+            state.update_execution_session(execution_session_id, |session| {
+                session.status = SessionStatus::Completed;
+            });
+            info!("Execution completed successfully for session {}", execution_session_id);
+        }
+        Err(error) => {
+            state.update_execution_session(execution_session_id, |session| {
+                session.status = SessionStatus::Failed;
+                session.error = Some(error.to_string());
+            });
+            warn!("Execution failed for session {}: {}", execution_session_id, error);
+        }
+    }
+    
+    state.remove_cancel_token(execution_session_id);
+    emit_execution_progress(state, execution_session_id);
+    if let Some(session) = state.get_execution_session(execution_session_id) {
+        notify_task_finished(state, TaskFinishedEvent::apply(&session));
+    }
+}
+
 async fn perform_execution(
     plan_id: Uuid,
+    session_id: Uuid,
     journal_path: PathBuf,
     state: &AppState,
+    cancel_token: &CancellationToken,
 ) -> GuiResult<()> {
     // Get the plan
     let plan_session = state.get_plan_session(plan_id)
@@ -84,12 +113,36 @@ async fn perform_execution(
     
     info!("Simulating execution of {} operations", plan.nodes.len());
     
+    // Merged folders and skipped nodes are not counted, as they are passed over below
+    let operations = plan.nodes.values().filter(|node| !matches!(node.kind, OpKind::Skip | OpKind::None)).count();
+    let mut progress = Progress::new(operations as u64, plan.summary.total_bytes);
+    let started = Instant::now();
+    
     // Create journal file
-    std::fs::write(&journal_path, "")
+    let mut journal = filemover_journal::create_journal(&journal_path, JournalFormat::Jsonl)
         .map_err(|e| gui_error!(execution, format!("Failed to create journal file: {}", e)))?;
+    journal.link_plan(plan.content_hash());
     
-    // Simulate processing each operation
-    for (i, (_node_id, node)) in plan.nodes.iter().enumerate() {
+    // Skipped nodes are left out of the execution order, so they are reported up front
+    for node in plan.nodes.values().filter(|node| node.kind == OpKind::Skip) {
+        record_node_result(state, session_id, node.id, NodeStatus::Skipped, None);
+    }
+    
+    // Simulate processing each operation in the planner's dependency order
+    for (i, node) in filemover_planner::nodes_in_execution_order(&plan).into_iter().enumerate() {
+        // Stop between operations so the journal only contains completed work
+        if cancel_token.is_cancelled() {
+            info!("Execution cancelled after {} of {} operations", i, plan.nodes.len());
+            journal.finish()
+                .map_err(|e| gui_error!(execution, format!("Failed to finalize journal: {}", e)))?;
+            return Ok(());
+        }
+        
+        // Merged folders only hold their children's operations, and skipped nodes have nothing to undo
+        if matches!(node.kind, OpKind::Skip | OpKind::None) {
+            continue;
+        }
+        
         // Simulate progress update
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
         
@@ -100,27 +153,62 @@ async fn perform_execution(
                node.path_after.display());
         
         // Write journal entry
-        let entry = filemover_types::JournalEntry::new(
+        let mut entry = filemover_types::JournalEntry::new(
             node.path_before.clone(),
             node.path_after.clone(),
             node.kind
-        );
+        ).with_rule_id(node.rule_id).with_tags(node.tags.clone());
         
-        let json_line = serde_json::to_string(&entry)
-            .map_err(|e| gui_error!(execution, format!("Failed to serialize journal entry: {}", e)))?;
+        // Record the pre-operation state so undo can restore it
+        if let Ok(metadata) = filemover_types::EntryMetadata::capture(&node.path_before) {
+            let metadata = match node.size_bytes {
+                Some(size) => metadata.with_size(size),
+                None => metadata,
+            };
+            entry = entry.with_metadata(metadata);
+        }
         
-        std::fs::write(&journal_path, format!("{}\n", json_line))
-            .map_err(|e| gui_error!(execution, format!("Failed to write journal entry: {}", e)))?;
+        if let Err(e) = journal.append(&entry) {
+            let message = format!("Failed to write journal entry: {}", e);
+            record_node_result(state, session_id, node.id, NodeStatus::Failed, Some(message.clone()));
+            return Err(gui_error!(execution, message));
+        }
+        record_node_result(state, session_id, node.id, NodeStatus::Succeeded, None);
         
-        // Update progress (would be more sophisticated in real implementation)
-        let progress = filemover_types::Progress::new(plan.nodes.len() as u64, plan.summary.total_bytes);
-        // TODO: Update execution session with progress
+        progress.complete_item(
+            node.path_before.display().to_string(),
+            node.size_bytes.unwrap_or(0),
+            started.elapsed(),
+        );
+        state.update_execution_session(session_id, |session| {
+            session.progress = Some(progress.clone());
+        });
+        emit_execution_progress(state, session_id);
     }
     
+    journal.finish()
+        .map_err(|e| gui_error!(execution, format!("Failed to finalize journal: {}", e)))?;
+    
     info!("Execution simulation completed, journal written to: {}", journal_path.display());
     Ok(())
 }
 
+/// Keep the node's outcome in the session and send it to the frontend as an `execute://node` event
+fn record_node_result(state: &AppState, session_id: Uuid, node_id: PlanNodeId, status: NodeStatus, error: Option<String>) {
+    let result = NodeResult { node_id, status, error };
+    state.update_execution_session(session_id, |session| session.record_node_result(result.clone()));
+    state.events.emit(NODE_STATUS_EVENT, &NodeStatusEvent { session_id, result });
+}
+
+/// Push the session's progress and status to the frontend as an `execute://progress` event
+pub(crate) fn emit_execution_progress(state: &AppState, session_id: Uuid) {
+    let Some(session) = state.get_execution_session(session_id) else {
+        return;
+    };
+    let progress = session.progress.unwrap_or_else(|| Progress::new(0, None));
+    state.events.emit(EXECUTE_PROGRESS_EVENT, &ExecutionProgressEvent::new(session_id, session.status, &progress));
+}
+
 #[tauri::command]
 pub async fn get_execution_progress(
     execution_session_id: String,
@@ -140,6 +228,9 @@ pub async fn cancel_execution(
     let id = Uuid::parse_str(&execution_session_id)
         .map_err(|_| gui_error!(execution, "Invalid execution session ID format"))?;
     
+    // Signal the executor to stop before the next operation
+    state.cancel_session(id);
+    
     // Update session status to cancelled
     state.update_execution_session(id, |session| {
         if session.status == SessionStatus::Running {
@@ -147,9 +238,6 @@ pub async fn cancel_execution(
         }
     });
     
-    // TODO: Implement actual cancellation logic
-    // This would need to signal the execution task to stop
-    
     info!("Execution session {} cancelled", id);
     Ok(())
 }
@@ -157,8 +245,9 @@ pub async fn cancel_execution(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use filemover_types::{MovePlan, PlanSummary};
+    use filemover_types::{MovePlan, PlanNode, PlanSummary};
     use std::collections::HashMap;
+    use tempfile::TempDir;
 
     #[tokio::test]
     async fn test_execute_plan_invalid_session() {
@@ -207,4 +296,48 @@ mod tests {
         let session = state.get_execution_session(execution_session_id).unwrap();
         assert_eq!(session.status, SessionStatus::Cancelled);
     }
-}
\ No newline at end of file
+
+    fn node(name: &str, kind: OpKind) -> PlanNode {
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: name.to_string(),
+            path_before: PathBuf::from("/source").join(name),
+            name_after: name.to_string(),
+            path_after: PathBuf::from("/dest").join(name),
+            kind,
+            size_bytes: None,
+            warnings: vec![],
+            conflicts: vec![],
+            children: vec![],
+            rule_id: None,
+            tags: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_execution_records_node_results() {
+        let state = AppState::new();
+        let moved = node("moved", OpKind::Move);
+        let skipped = node("skipped", OpKind::Skip);
+        let plan = MovePlan {
+            roots: vec![moved.id, skipped.id],
+            nodes: [moved.clone(), skipped.clone()].into_iter().map(|n| (n.id, n)).collect(),
+            summary: PlanSummary::default(),
+            execution_order: vec![],
+        };
+        let plan_id = state.create_plan_session(None);
+        state.update_plan_session(plan_id, |session| session.plan = Some(plan));
+
+        let dir = TempDir::new().unwrap();
+        let session_id = state.create_execution_session(plan_id);
+        run_execution(&state, plan_id, session_id, dir.path().join("journal.jsonl"), CancellationToken::new()).await;
+
+        let session = state.get_execution_session(session_id).unwrap();
+        assert_eq!(session.status, SessionStatus::Completed);
+        assert_eq!(session.node_results_total, 2);
+        let status_of = |id| session.node_results.iter().find(|result| result.node_id == id).map(|result| result.status);
+        assert_eq!(status_of(moved.id), Some(NodeStatus::Succeeded));
+        assert_eq!(status_of(skipped.id), Some(NodeStatus::Skipped));
+    }
+}
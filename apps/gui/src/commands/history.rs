@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::State;
+use tracing::{info, warn};
+
+use filemover_types::{JournalFormat, ResultKind};
+use crate::state::AppState;
+use crate::error::{GuiResult, GuiError, gui_error};
+use super::{analyze_undo_feasibility, load_checked_journal};
+
+/// One run in the history, read from its journal
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalRunSummary {
+    pub path: PathBuf,
+    pub format: JournalFormat,
+    /// Time of the first entry, or of the file for an empty journal
+    pub run_date: DateTime<Utc>,
+    /// From the summary record; None when the run was interrupted
+    pub completed: Option<DateTime<Utc>>,
+    pub total_operations: usize,
+    pub successful_operations: usize,
+    pub failed_operations: usize,
+    pub skipped_operations: usize,
+    /// Operations whose destination is still there and whose source is free again
+    pub undoable_operations: usize,
+    /// `MovePlan::content_hash` of the plan that was run, when the journal records it
+    pub plan_hash: Option<String>,
+    /// Journal of an undo of this run, if one was made
+    pub undo_journal: Option<PathBuf>,
+    pub problems: Vec<String>,
+}
+
+/// Runs in the journal directory (`AppState::journal_dir` by default), newest first;
+/// undo journals are reported with their run
+#[tauri::command]
+pub async fn list_journals(
+    journal_dir: Option<PathBuf>,
+    state: State<'_, AppState>,
+) -> GuiResult<Vec<JournalRunSummary>> {
+    let dir = journal_dir.unwrap_or_else(|| state.journal_dir());
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    // Every entry is read and checked against the file system, so keep it off the async runtime
+    let summaries = tokio::task::spawn_blocking(move || read_history(&dir))
+        .await
+        .map_err(|e| gui_error!(invalid_op, format!("Reading the journal history failed: {}", e)))??;
+    info!("Found {} journals", summaries.len());
+    Ok(summaries)
+}
+
+#[tauri::command]
+pub async fn get_journal_summary(path: PathBuf) -> GuiResult<JournalRunSummary> {
+    if !path.exists() {
+        return Err(gui_error!(execution, format!("Journal file does not exist: {}", path.display())));
+    }
+    tokio::task::spawn_blocking(move || summarize_journal(&path))
+        .await
+        .map_err(|e| gui_error!(invalid_op, format!("Reading the journal failed: {}", e)))?
+}
+
+fn read_history(dir: &Path) -> GuiResult<Vec<JournalRunSummary>> {
+    let journals = filemover_journal::list_journals(dir)
+        .map_err(|e| gui_error!(execution, format!("Failed to list journals in {}: {}", dir.display(), e)))?;
+
+    let mut summaries = Vec::new();
+    for journal in &journals {
+        // One unreadable file should not hide the rest of the history
+        match summarize_journal(&journal.path) {
+            Ok(summary) => summaries.push(summary),
+            Err(e) => warn!("Skipping journal {}: {}", journal.path.display(), e),
+        }
+    }
+    Ok(summaries)
+}
+
+fn summarize_journal(path: &PathBuf) -> GuiResult<JournalRunSummary> {
+    let format = filemover_journal::detect_format(path)
+        .map_err(|e| gui_error!(execution, format!("Failed to open journal file: {}", e)))?;
+    let report = load_checked_journal(path)?;
+    let (undoable, _) = analyze_undo_feasibility(&report.entries);
+    let count = |result: ResultKind| report.entries.iter().filter(|entry| entry.result == result).count();
+
+    let run_date = match report.entries.first() {
+        Some(entry) => entry.when_utc,
+        None => std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now()),
+    };
+    let undo_journal = filemover_journal::undo_journal_path(path);
+
+    Ok(JournalRunSummary {
+        path: path.clone(),
+        format,
+        run_date,
+        completed: report.summary.as_ref().map(|summary| summary.completed_utc),
+        total_operations: report.entries.len(),
+        successful_operations: count(ResultKind::Ok),
+        failed_operations: count(ResultKind::Failed),
+        skipped_operations: count(ResultKind::Skip),
+        undoable_operations: undoable.len(),
+        plan_hash: report.summary.as_ref().and_then(|summary| summary.plan_hash.clone()),
+        undo_journal: undo_journal.exists().then_some(undo_journal),
+        problems: report.problems.iter().map(|problem| problem.to_string()).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::{JournalEntry, OpKind};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_history_lists_runs_with_counts() {
+        let dir = TempDir::new().unwrap();
+        let dest = dir.path().join("moved");
+        std::fs::create_dir(&dest).unwrap();
+
+        let path = dir.path().join(filemover_journal::journal_file_name(JournalFormat::Jsonl, Utc::now()));
+        let mut journal = filemover_journal::create_journal(&path, JournalFormat::Jsonl).unwrap();
+        journal.link_plan("1234abcd".to_string());
+        journal.append(&JournalEntry::new(dir.path().join("gone"), dest, OpKind::Move)).unwrap();
+        journal.append(&JournalEntry::new(dir.path().join("a"), dir.path().join("b"), OpKind::Move).failed("denied")).unwrap();
+        journal.finish().unwrap();
+
+        // The undo journal belongs to the run and is not listed on its own
+        let undo = filemover_journal::undo_journal_path(&path);
+        filemover_journal::create_journal(&undo, JournalFormat::Jsonl).unwrap().finish().unwrap();
+
+        let history = read_history(dir.path()).unwrap();
+        assert_eq!(history.len(), 1);
+        let run = &history[0];
+        assert_eq!(run.total_operations, 2);
+        assert_eq!(run.failed_operations, 1);
+        assert_eq!(run.undoable_operations, 1);
+        assert_eq!(run.plan_hash.as_deref(), Some("1234abcd"));
+        assert_eq!(run.undo_journal.as_ref(), Some(&undo));
+        assert!(run.completed.is_some());
+        assert!(run.problems.is_empty());
+    }
+}
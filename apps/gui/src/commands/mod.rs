@@ -1,13 +1,25 @@
 mod config;
+mod settings;
 mod scan;
 mod plan;
+mod conflicts;
+mod queue;
+mod rules;
 mod execute;
+mod elevation;
 mod undo;
+mod history;
 mod utils;
 
 pub use config::*;
+pub use settings::*;
 pub use scan::*;
 pub use plan::*;
+pub use conflicts::*;
+pub use queue::*;
+pub use rules::*;
 pub use execute::*;
+pub use elevation::*;
 pub use undo::*;
+pub use history::*;
 pub use utils::*;
\ No newline at end of file
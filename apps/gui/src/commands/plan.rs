@@ -1,10 +1,15 @@
+use std::sync::Arc;
 use tauri::State;
 use tracing::{info, debug, warn};
 use uuid::Uuid;
 
-use filemover_types::{MovePlan, PlanOptions, NodeChange, ValidationDelta};
+use filemover_types::{MovePlan, PlanOptions, NodeChange, PlanNodeId, ValidationDelta};
 use crate::state::{AppState, SessionStatus};
 use crate::error::{GuiResult, GuiError, gui_error};
+use crate::plan_index::{PlanIndex, PlanNodeFilter, PlanNodePage, PlanNodeRow, PlanNodeSort};
+
+/// Largest page of plan nodes returned by one request
+const MAX_PAGE_SIZE: usize = 1000;
 
 #[tauri::command]
 pub fn create_move_plan(
@@ -88,7 +93,10 @@ fn perform_plan_creation(
         .map_err(|_| gui_error!(planning, "Failed to access planner"))?;
     
     // Create plan
-    let plan_options = PlanOptions::default();
+    let plan_options = PlanOptions {
+        rename_suffix: config.rename_suffix.clone(),
+        ..Default::default()
+    };
     let plan = planner.create_plan(&hits, &config.rules, plan_options)
         .map_err(|e| gui_error!(planning, format!("Failed to create plan: {}", e)))?;
     
@@ -128,11 +136,20 @@ pub async fn update_plan_node(
     change: NodeChange,
     state: State<'_, AppState>,
 ) -> GuiResult<ValidationDelta> {
-    let id = Uuid::parse_str(&plan_session_id)
+    apply_node_change(&state, &plan_session_id, change)
+}
+
+/// Apply an edit to the session's plan and return what it changed
+pub(crate) fn apply_node_change(
+    state: &AppState,
+    plan_session_id: &str,
+    change: NodeChange,
+) -> GuiResult<ValidationDelta> {
+    let id = Uuid::parse_str(plan_session_id)
         .map_err(|_| gui_error!(planning, "Invalid plan session ID format"))?;
     
-    let mut plan_session = state.get_plan_session(id)
-        .ok_or_else(|| gui_error!(session_not_found, plan_session_id))?;
+    let plan_session = state.get_plan_session(id)
+        .ok_or_else(|| gui_error!(session_not_found, plan_session_id.to_string()))?;
     
     let mut plan = plan_session.plan
         .ok_or_else(|| gui_error!(planning, "Plan session has no plan"))?;
@@ -148,6 +165,7 @@ pub async fn update_plan_node(
     // Update the plan in the session
     state.update_plan_session(id, |session| {
         session.plan = Some(plan);
+        session.index = None;
     });
     
     debug!("Plan node updated for session {}", id);
@@ -166,6 +184,53 @@ pub async fn get_plan_session(
         .ok_or_else(|| gui_error!(session_not_found, plan_session_id))
 }
 
+/// One page of plan nodes, so the tree view does not need the whole plan at once
+#[tauri::command]
+pub async fn get_plan_nodes(
+    plan_session_id: String,
+    offset: usize,
+    limit: usize,
+    filter: Option<PlanNodeFilter>,
+    sort: Option<PlanNodeSort>,
+    state: State<'_, AppState>,
+) -> GuiResult<PlanNodePage> {
+    let filter = filter.unwrap_or_default();
+    with_plan_index(&state, &plan_session_id, |plan, index| {
+        index.page(plan, &filter, sort.unwrap_or_default(), offset, limit.min(MAX_PAGE_SIZE))
+    })
+}
+
+/// Children of a plan node, or the plan roots when no node is given, for expanding the tree lazily
+#[tauri::command]
+pub async fn get_plan_node_children(
+    plan_session_id: String,
+    node_id: Option<PlanNodeId>,
+    state: State<'_, AppState>,
+) -> GuiResult<Vec<PlanNodeRow>> {
+    with_plan_index(&state, &plan_session_id, |plan, index| index.children(plan, node_id))?
+        .ok_or_else(|| gui_error!(planning, format!("Plan node {} not found", node_id.map(|id| id.to_string()).unwrap_or_default())))
+}
+
+/// Run `f` on the session's plan without copying it, building the index on first use
+pub(crate) fn with_plan_index<R>(
+    state: &AppState,
+    plan_session_id: &str,
+    f: impl FnOnce(&MovePlan, &PlanIndex) -> R,
+) -> GuiResult<R> {
+    let id = Uuid::parse_str(plan_session_id)
+        .map_err(|_| gui_error!(planning, "Invalid plan session ID format"))?;
+    
+    let mut sessions = state.plan_sessions.lock()
+        .map_err(|_| gui_error!(planning, "Failed to access plan sessions"))?;
+    let session = sessions.get_mut(&id)
+        .ok_or_else(|| gui_error!(session_not_found, plan_session_id.to_string()))?;
+    let plan = session.plan.as_ref()
+        .ok_or_else(|| gui_error!(planning, "Plan session has no plan"))?;
+    let index = session.index.get_or_insert_with(|| Arc::new(PlanIndex::build(plan)));
+    
+    Ok(f(plan, index))
+}
+
 #[tauri::command]
 pub async fn export_plan(
     plan_session_id: String,
@@ -182,7 +247,7 @@ pub async fn export_plan(
         .ok_or_else(|| gui_error!(planning, "Plan session has no plan"))?;
     
     // Serialize and save plan
-    let json = serde_json::to_string_pretty(&plan)
+    let json = filemover_types::to_versioned_json(&plan, true)
         .map_err(|e| gui_error!(planning, format!("Failed to serialize plan: {}", e)))?;
     
     std::fs::write(&file_path, json)
@@ -207,9 +272,13 @@ mod tests {
                 path: PathBuf::from("C:\\Test\\folder1"),
                 name: "folder1".to_string(),
                 matched_rule: None,
+                matched_rules: Vec::new(),
+                tags: Vec::new(),
                 dest_preview: None,
                 warnings: vec![],
                 size_bytes: Some(1024),
+                item_count: None,
+                is_dir: true,
             }
         ];
         
@@ -233,6 +302,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_get_plan_nodes_pages_through_plan() {
+        let state = AppState::new();
+        let hits = (0..3).map(|i| FolderHit {
+            path: PathBuf::from(format!("C:\\Test\\folder{}", i)),
+            name: format!("folder{}", i),
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: vec![],
+            size_bytes: None,
+            item_count: None,
+            is_dir: true,
+        }).collect();
+        let session_id = create_move_plan(None, Some(hits), State::from(&state)).unwrap();
+        let total = state.get_plan_session(session_id).unwrap().plan.unwrap().nodes.len();
+        
+        let page = get_plan_nodes(session_id.to_string(), 1, 1, None, None, State::from(&state)).await.unwrap();
+        assert_eq!(page.total, total);
+        assert_eq!(page.nodes.len(), total.saturating_sub(1).min(1));
+        
+        let roots = get_plan_node_children(session_id.to_string(), None, State::from(&state)).await.unwrap();
+        assert!(roots.len() <= total);
+        assert!(get_plan_node_children(session_id.to_string(), Some(PlanNodeId::new()), State::from(&state)).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_get_plan_session_invalid_id() {
         let state = AppState::new();
@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+use tauri::State;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::queue::{OperationQueue, QueuedTask};
+use crate::state::AppState;
+use crate::error::{GuiResult, GuiError, gui_error};
+
+#[tauri::command]
+pub async fn get_queue(
+    state: State<'_, AppState>,
+) -> GuiResult<OperationQueue> {
+    state.queue.lock()
+        .map(|queue| queue.clone())
+        .map_err(|_| gui_error!(invalid_op, "Failed to access the operation queue"))
+}
+
+/// Queue a scan to run in the background after the operations before it
+#[tauri::command]
+pub async fn enqueue_scan(
+    roots: Vec<PathBuf>,
+    state: State<'_, AppState>,
+) -> GuiResult<Uuid> {
+    if let Some(root) = roots.iter().find(|root| !root.is_dir()) {
+        return Err(gui_error!(scan, format!("Root path is not a directory: {}", root.display())));
+    }
+    enqueue(&state, QueuedTask::Scan { roots })
+}
+
+/// Queue a saved plan file to be executed in the background
+#[tauri::command]
+pub async fn enqueue_apply(
+    plan_path: PathBuf,
+    journal_path: Option<PathBuf>,
+    state: State<'_, AppState>,
+) -> GuiResult<Uuid> {
+    if !plan_path.is_file() {
+        return Err(gui_error!(execution, format!("Plan file does not exist: {}", plan_path.display())));
+    }
+    enqueue(&state, QueuedTask::Apply { plan_path, journal_path })
+}
+
+fn enqueue(state: &AppState, task: QueuedTask) -> GuiResult<Uuid> {
+    let id = state.queue.lock()
+        .map_err(|_| gui_error!(invalid_op, "Failed to access the operation queue"))?
+        .enqueue(task);
+    info!("Queued operation {}", id);
+    Ok(id)
+}
+
+/// Pause or resume the queue; a running operation is not interrupted
+#[tauri::command]
+pub async fn set_queue_paused(
+    paused: bool,
+    state: State<'_, AppState>,
+) -> GuiResult<()> {
+    state.queue.lock()
+        .map_err(|_| gui_error!(invalid_op, "Failed to access the operation queue"))?
+        .set_paused(paused);
+    info!("Operation queue {}", if paused { "paused" } else { "resumed" });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_queued_operation(
+    operation_id: Uuid,
+    state: State<'_, AppState>,
+) -> GuiResult<()> {
+    let removed = state.queue.lock()
+        .map_err(|_| gui_error!(invalid_op, "Failed to access the operation queue"))?
+        .remove(operation_id);
+    if removed {
+        Ok(())
+    } else {
+        Err(gui_error!(invalid_op, format!("Operation {} is running or not in the queue", operation_id)))
+    }
+}
@@ -0,0 +1,294 @@
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use tauri::State;
+use tracing::debug;
+use uuid::Uuid;
+
+use filemover_types::{is_valid_date_format, FolderHit, Rule, ScanOptions};
+use filemover_matcher::MatchingEngine;
+use filemover_planner::TemplateEngine;
+use filemover_scanner::FolderScanner;
+use crate::state::AppState;
+use crate::error::{GuiResult, GuiError, gui_error};
+
+/// Most matching folders returned by one preview; the rest are only counted
+const PREVIEW_LIMIT: usize = 200;
+
+/// Most sample folders expanded by one destination preview
+const DESTINATION_SAMPLE_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RuleIssueSeverity {
+    /// The rule cannot be saved as it is
+    Error,
+    Warning,
+}
+
+/// The part of the rule editor an issue belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RuleField {
+    Pattern,
+    DateFormats,
+    Template,
+    DestRoot,
+    Enabled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleIssue {
+    pub field: RuleField,
+    pub severity: RuleIssueSeverity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleValidation {
+    /// True when there are no errors (warnings do not block saving)
+    pub valid: bool,
+    pub issues: Vec<RuleIssue>,
+    /// Variables used by the template, for highlighting in the editor
+    pub template_variables: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleMatchPreview {
+    /// Folders of the scan that were checked
+    pub checked: usize,
+    /// Folders the rule would match
+    pub matched: usize,
+    /// Folders that no longer exist and could not be checked
+    pub missing: usize,
+    /// The first matching folders, with their destination under this rule
+    pub matches: Vec<FolderHit>,
+}
+
+/// Check a rule from the editor the same way scans and plans will use it
+#[tauri::command]
+pub async fn validate_rule(
+    rule: Rule,
+    state: State<'_, AppState>,
+) -> GuiResult<RuleValidation> {
+    let normalization = state.config.lock()
+        .map_err(|_| gui_error!(config, "Failed to access configuration"))?
+        .options.normalization.clone();
+
+    let mut issues = Vec::new();
+    let mut issue = |field, severity, message: String| issues.push(RuleIssue { field, severity, message });
+
+    if !rule.enabled {
+        issue(RuleField::Enabled, RuleIssueSeverity::Warning, "The rule is disabled".to_string());
+    }
+    // Compiles the pattern, expression and conditions the same way scans do
+    if let Err(e) = MatchingEngine::new(vec![rule.clone()], normalization) {
+        issue(RuleField::Pattern, RuleIssueSeverity::Error, format!("The pattern does not compile: {}", e));
+    }
+    for format in &rule.date_formats {
+        if !is_valid_date_format(format) {
+            issue(RuleField::DateFormats, RuleIssueSeverity::Error, format!("Invalid date format \"{}\"", format));
+        }
+    }
+
+    // Exclusions and tag-only rules do not move anything
+    let mut template_variables = Vec::new();
+    if !rule.pattern.is_exclude && !rule.is_tag_only() {
+        match TemplateEngine::validate_template(&rule.template) {
+            Ok(variables) => template_variables = variables,
+            Err(e) => issue(RuleField::Template, RuleIssueSeverity::Error, format!("Invalid template: {}", e)),
+        }
+        if let Some((severity, message)) = check_dest_root(&rule.dest_root) {
+            issue(RuleField::DestRoot, severity, message);
+        }
+    }
+
+    let valid = issues.iter().all(|issue| issue.severity != RuleIssueSeverity::Error);
+    Ok(RuleValidation { valid, issues, template_variables })
+}
+
+fn check_dest_root(dest_root: &Path) -> Option<(RuleIssueSeverity, String)> {
+    if dest_root.as_os_str().is_empty() {
+        return Some((RuleIssueSeverity::Error, "No destination folder".to_string()));
+    }
+    if !dest_root.is_absolute() {
+        return Some((RuleIssueSeverity::Error, format!("The destination must be an absolute path: {}", dest_root.display())));
+    }
+    if dest_root.is_dir() {
+        return None;
+    }
+    if dest_root.exists() {
+        return Some((RuleIssueSeverity::Error, format!("The destination is not a folder: {}", dest_root.display())));
+    }
+    // Missing folders are created when the plan runs, but the drive or share has to be there
+    match dest_root.ancestors().skip(1).find(|dir| dir.is_dir()) {
+        Some(_) => Some((RuleIssueSeverity::Warning, format!("The destination does not exist yet: {}", dest_root.display()))),
+        None => Some((RuleIssueSeverity::Error, format!("The destination is unreachable: {}", dest_root.display()))),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DestinationSample {
+    pub source: PathBuf,
+    pub destination: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DestinationPreview {
+    /// Set when the template itself is invalid; no samples are expanded then
+    pub template_error: Option<String>,
+    pub samples: Vec<DestinationSample>,
+}
+
+/// Expand the rule's template for a few scan hits while it is being typed
+#[tauri::command]
+pub async fn preview_destination(
+    rule: Rule,
+    sample_paths: Vec<PathBuf>,
+) -> GuiResult<DestinationPreview> {
+    if let Err(e) = TemplateEngine::validate_template(&rule.template) {
+        return Ok(DestinationPreview { template_error: Some(e.to_string()), samples: Vec::new() });
+    }
+
+    // One engine for all samples, so {seq} counts up the way it would in a plan
+    let mut engine = TemplateEngine::new();
+    let mut taken: Vec<PathBuf> = Vec::new();
+    let mut samples = Vec::new();
+    for source in sample_paths.into_iter().take(DESTINATION_SAMPLE_LIMIT) {
+        let expanded = engine.expand_template_with(&rule, &source, |path| path.exists() || taken.iter().any(|t| t == path));
+        let sample = match expanded {
+            Ok(destination) => {
+                taken.push(destination.clone());
+                DestinationSample { source, destination: Some(destination), error: None }
+            }
+            Err(e) => DestinationSample { source, destination: None, error: Some(e.to_string()) },
+        };
+        samples.push(sample);
+    }
+
+    Ok(DestinationPreview { template_error: None, samples })
+}
+
+/// Which folders of a finished scan the rule being edited would match, on its own
+#[tauri::command]
+pub async fn preview_rule_matches(
+    rule: Rule,
+    scan_session_id: String,
+    state: State<'_, AppState>,
+) -> GuiResult<RuleMatchPreview> {
+    let id = Uuid::parse_str(&scan_session_id)
+        .map_err(|_| gui_error!(scan, "Invalid session ID format"))?;
+    let session = state.get_scan_session(id)
+        .ok_or_else(|| gui_error!(session_not_found, scan_session_id))?;
+    let config = state.config.lock()
+        .map_err(|_| gui_error!(config, "Failed to access configuration"))?
+        .clone();
+
+    // Sizes are left out so the preview stays quick; size conditions still read them
+    let options = ScanOptions { calculate_sizes: false, ..config.options.clone() };
+    let scanner = FolderScanner::with_exclude_patterns(vec![rule], config.exclude_patterns.clone(), options)
+        .map_err(|e| gui_error!(config, format!("The rule does not compile: {}", e)))?;
+
+    let hits = session.results.unwrap_or_default();
+    let roots = session.roots;
+    // Every folder is looked up on disk again, so keep it off the async runtime
+    let preview = tokio::task::spawn_blocking(move || {
+        let mut preview = RuleMatchPreview { checked: 0, matched: 0, missing: 0, matches: Vec::new() };
+        for hit in &hits {
+            let Some(root) = roots.iter().find(|root| hit.path.starts_with(root)) else {
+                continue;
+            };
+            preview.checked += 1;
+            match scanner.evaluate(root, &hit.path) {
+                Ok(Some(matched)) => {
+                    preview.matched += 1;
+                    if preview.matches.len() < PREVIEW_LIMIT {
+                        preview.matches.push(matched);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    debug!("Cannot preview {}: {}", hit.path.display(), e);
+                    preview.missing += 1;
+                }
+            }
+        }
+        preview
+    })
+    .await
+    .map_err(|e| gui_error!(scan, format!("Rule preview failed: {}", e)))?;
+
+    debug!("Rule preview matched {} of {} folders in scan {}", preview.matched, preview.checked, id);
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::PatternSpec;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn rule(pattern: &str, dest_root: PathBuf, template: &str) -> Rule {
+        Rule::new(PatternSpec::new_glob(pattern), dest_root, template.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_validate_rule_reports_fields() {
+        let state = AppState::new();
+        let dest = TempDir::new().unwrap();
+
+        let result = validate_rule(rule("*report*", dest.path().to_path_buf(), "{name}"), State::from(&state)).await.unwrap();
+        assert!(result.valid);
+        assert_eq!(result.template_variables, vec!["name".to_string()]);
+
+        let result = validate_rule(rule("*report*", PathBuf::from("relative"), "{name"), State::from(&state)).await.unwrap();
+        assert!(!result.valid);
+        let fields: Vec<RuleField> = result.issues.iter().map(|issue| issue.field).collect();
+        assert!(fields.contains(&RuleField::Template));
+        assert!(fields.contains(&RuleField::DestRoot));
+    }
+
+    #[tokio::test]
+    async fn test_preview_destination() {
+        let dest = TempDir::new().unwrap();
+        let samples = vec![PathBuf::from("/data/report_2024"), PathBuf::from("/data/notes")];
+
+        let preview = preview_destination(rule("*", dest.path().to_path_buf(), "{name}"), samples.clone()).await.unwrap();
+        assert!(preview.template_error.is_none());
+        assert_eq!(preview.samples[0].destination, Some(dest.path().join("report_2024")));
+        assert_eq!(preview.samples[1].destination, Some(dest.path().join("notes")));
+
+        let preview = preview_destination(rule("*", dest.path().to_path_buf(), "{name"), samples).await.unwrap();
+        assert!(preview.template_error.is_some());
+        assert!(preview.samples.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_preview_rule_matches_scanned_folders() {
+        let state = AppState::new();
+        let root = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        let hits: Vec<FolderHit> = ["report_2024", "photos", "gone_report"].iter().map(|name| FolderHit {
+            path: root.path().join(name),
+            name: name.to_string(),
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: vec![],
+            size_bytes: None,
+            item_count: None,
+            is_dir: true,
+        }).collect();
+        std::fs::create_dir(root.path().join("report_2024")).unwrap();
+        std::fs::create_dir(root.path().join("photos")).unwrap();
+
+        let session_id = state.create_scan_session(vec![root.path().to_path_buf()]);
+        state.update_scan_session(session_id, |session| session.results = Some(hits));
+
+        let preview = preview_rule_matches(rule("*report*", dest.path().to_path_buf(), "{name}"), session_id.to_string(), State::from(&state)).await.unwrap();
+        assert_eq!(preview.checked, 3);
+        assert_eq!(preview.matched, 1);
+        assert_eq!(preview.missing, 1);
+        assert_eq!(preview.matches[0].name, "report_2024");
+    }
+}
@@ -1,15 +1,25 @@
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+use serde::{Deserialize, Serialize};
 use tauri::State;
 use tracing::{info, debug, warn};
 use uuid::Uuid;
 
-use filemover_types::{FolderHit, ScanOptions};
-use filemover_scanner::FolderScanner;
+use globset::{GlobBuilder, GlobMatcher};
+use filemover_types::{FolderHit, PatternSpec, ScanOptions, CancellationToken, Warning};
+use filemover_matcher::{ComparedText, MatchExplanation, RuleOutcome};
+use filemover_scanner::{FolderScanner, ScanEvent, ScanEventCallback, ScanOutcome};
 use crate::state::{AppState, SessionStatus};
 use crate::error::{GuiResult, GuiError, gui_error};
+use crate::events::{ScanProgressEvent, SCAN_PROGRESS_EVENT};
+use crate::notifications::{notify_task_finished, TaskFinishedEvent};
+
+/// Largest page of scan hits returned by one request
+const MAX_PAGE_SIZE: usize = 1000;
 
 #[tauri::command]
-pub fn scan_folders(
+pub async fn scan_folders(
     roots: Vec<PathBuf>,
     state: State<'_, AppState>,
 ) -> GuiResult<Uuid> {
@@ -33,15 +43,47 @@ pub fn scan_folders(
         session.status = SessionStatus::Running;
     });
     
-    // Perform scan directly (synchronous for simplicity)
-    match perform_scan(roots, &state) {
-        Ok(results) => {
+    let cancel_token = state.register_cancel_token(session_id);
+    
+    // Clone necessary data for the background task
+    let state_clone = state.inner().clone();
+    
+    // The scanner walks the file system synchronously, so it runs on the blocking pool
+    tokio::task::spawn_blocking(move || {
+        run_scan(roots, session_id, &state_clone, &cancel_token);
+    });
+    
+    debug!("Scan session {} created and started", session_id);
+    Ok(session_id)
+}
+
+/// Scan to completion and record the outcome in the session
+pub(crate) fn run_scan(roots: Vec<PathBuf>, session_id: Uuid, state: &AppState, cancel_token: &CancellationToken) {
+    let started = Instant::now();
+    
+    match perform_scan(roots, session_id, state, cancel_token, started) {
+        Ok(outcome) if outcome.cancelled => {
+            // Keep what was found before the cancel so the user can still review it
+            let found = outcome.hits.len();
+            state.update_scan_session(session_id, |session| {
+                session.status = SessionStatus::Cancelled;
+                session.results = Some(outcome.hits);
+            });
+            info!("Scan cancelled for session {} with {} partial results", session_id, found);
+        }
+        Ok(outcome) => {
             state.update_scan_session(session_id, |session| {
                 session.status = SessionStatus::Completed;
-                session.results = Some(results);
+                session.results = Some(outcome.hits);
             });
             info!("Scan completed successfully for session {}", session_id);
         }
+        Err(_) if cancel_token.is_cancelled() => {
+            state.update_scan_session(session_id, |session| {
+                session.status = SessionStatus::Cancelled;
+            });
+            info!("Scan cancelled for session {}", session_id);
+        }
         Err(error) => {
             state.update_scan_session(session_id, |session| {
                 session.status = SessionStatus::Failed;
@@ -51,26 +93,74 @@ pub fn scan_folders(
         }
     }
     
-    debug!("Scan session {} created and started", session_id);
-    Ok(session_id)
+    state.remove_cancel_token(session_id);
+    
+    // Final event with the outcome, so the frontend does not have to poll for it
+    if let Some(session) = state.get_scan_session(session_id) {
+        state.events.emit(SCAN_PROGRESS_EVENT, &ScanProgressEvent::new(&session, started.elapsed()));
+        notify_task_finished(state, TaskFinishedEvent::scan(&session));
+    }
 }
 
-fn perform_scan(roots: Vec<PathBuf>, state: &AppState) -> GuiResult<Vec<FolderHit>> {
+fn perform_scan(
+    roots: Vec<PathBuf>,
+    session_id: Uuid,
+    state: &AppState,
+    cancel_token: &CancellationToken,
+    started: Instant,
+) -> GuiResult<ScanOutcome> {
     // Get current configuration
     let config = state.config.lock()
         .map_err(|_| gui_error!(scan, "Failed to access configuration"))?
         .clone();
     
     // Create scanner
-    let scanner = FolderScanner::new(config.rules, config.options)
-        .map_err(|e| gui_error!(scan, format!("Failed to create scanner: {}", e)))?;
+    let scanner = FolderScanner::from_config_cached(&config, &state.engine_cache)
+        .map_err(|e| gui_error!(scan, format!("Failed to create scanner: {}", e)))?
+        .with_cancellation(cancel_token.clone())
+        .with_progress(session_progress_callback(state, session_id, started));
     
     // Perform scan
-    let results = scanner.scan_roots(&roots)
+    let outcome = scanner.scan(&roots)
         .map_err(|e| gui_error!(scan, format!("Scan failed: {}", e)))?;
     
-    info!("Scan found {} matching folders", results.len());
-    Ok(results)
+    info!("Scan found {} matching folders", outcome.hits.len());
+    for root_warning in &outcome.root_warnings {
+        warn!("Skipped {}: {}", root_warning.root.display(), root_warning.message);
+    }
+    Ok(outcome)
+}
+
+/// Stream progress and hits into the session so `get_scan_progress` sees them while scanning,
+/// and push each progress report to the frontend as a `scan://progress` event
+fn session_progress_callback(state: &AppState, session_id: Uuid, started: Instant) -> ScanEventCallback {
+    let sessions = state.scan_sessions.clone();
+    let events = state.events.clone();
+    
+    Arc::new(move |event: &ScanEvent| {
+        let Ok(mut sessions) = sessions.lock() else {
+            return;
+        };
+        let Some(session) = sessions.get_mut(&session_id) else {
+            return;
+        };
+        
+        let progress_event = match event {
+            ScanEvent::Progress(progress) => {
+                session.progress = Some(progress.clone());
+                Some(ScanProgressEvent::new(session, started.elapsed()))
+            }
+            ScanEvent::Hit(hit) => {
+                session.results.get_or_insert_with(Vec::new).push(hit.clone());
+                None
+            }
+        };
+        // Emit without holding the session lock
+        drop(sessions);
+        if let Some(progress_event) = progress_event {
+            events.emit(SCAN_PROGRESS_EVENT, &progress_event);
+        }
+    })
 }
 
 #[tauri::command]
@@ -92,6 +182,9 @@ pub async fn cancel_scan(
     let id = Uuid::parse_str(&session_id)
         .map_err(|_| gui_error!(scan, "Invalid session ID format"))?;
     
+    // Signal the scanner to stop; the scan task records the final status
+    state.cancel_session(id);
+    
     // Update session status to cancelled
     state.update_scan_session(id, |session| {
         if session.status == SessionStatus::Running {
@@ -99,9 +192,6 @@ pub async fn cancel_scan(
         }
     });
     
-    // TODO: Implement actual cancellation logic
-    // This would need to signal the scanning task to stop
-    
     info!("Scan session {} cancelled", id);
     Ok(())
 }
@@ -128,7 +218,8 @@ pub async fn get_scan_results(
             Err(gui_error!(scan, "Scan is still running"))
         }
         SessionStatus::Cancelled => {
-            Err(gui_error!(scan, "Scan was cancelled"))
+            // Partial results are kept when the scan was stopped midway
+            session.results.ok_or_else(|| gui_error!(scan, "Scan was cancelled"))
         }
         SessionStatus::Created => {
             Err(gui_error!(scan, "Scan has not started yet"))
@@ -136,6 +227,264 @@ pub async fn get_scan_results(
     }
 }
 
+/// Which scan hits a page includes; an empty query matches every hit
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ScanResultQuery {
+    /// Case-insensitive text that the folder name or path must contain
+    pub text: Option<String>,
+    /// Case-insensitive glob that the folder name or the whole path must match
+    pub path_glob: Option<String>,
+    /// Only hits matched by this rule
+    pub rule_id: Option<Uuid>,
+    /// Only hits carrying this warning
+    pub warning: Option<Warning>,
+    pub warnings_only: bool,
+    /// Size range in bytes; hits of unknown size are left out when either bound is set
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+}
+
+/// A query prepared once per request, so each hit is only compared
+struct ScanResultMatcher<'a> {
+    query: &'a ScanResultQuery,
+    text: Option<String>,
+    glob: Option<GlobMatcher>,
+}
+
+impl<'a> ScanResultMatcher<'a> {
+    fn new(query: &'a ScanResultQuery) -> GuiResult<Self> {
+        let text = query.text.as_ref()
+            .filter(|text| !text.is_empty())
+            .map(|text| text.to_lowercase());
+        let glob = match query.path_glob.as_deref().filter(|glob| !glob.is_empty()) {
+            Some(pattern) => Some(
+                GlobBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| gui_error!(scan, format!("Invalid path pattern '{}': {}", pattern, e)))?
+                    .compile_matcher(),
+            ),
+            None => None,
+        };
+        Ok(Self { query, text, glob })
+    }
+
+    fn matches(&self, hit: &FolderHit) -> bool {
+        let query = self.query;
+        if let Some(rule_id) = query.rule_id {
+            if hit.matched_rule != Some(rule_id) && !hit.matched_rules.contains(&rule_id) {
+                return false;
+            }
+        }
+        if let Some(warning) = query.warning {
+            if !hit.warnings.contains(&warning) {
+                return false;
+            }
+        }
+        if query.warnings_only && hit.warnings.is_empty() {
+            return false;
+        }
+        if query.min_size.is_some() || query.max_size.is_some() {
+            let Some(size) = hit.size_bytes else {
+                return false;
+            };
+            if query.min_size.is_some_and(|min| size < min) || query.max_size.is_some_and(|max| size > max) {
+                return false;
+            }
+        }
+        if let Some(glob) = &self.glob {
+            if !glob.is_match(&hit.name) && !glob.is_match(&hit.path) {
+                return false;
+            }
+        }
+        match &self.text {
+            Some(text) => {
+                hit.name.to_lowercase().contains(text.as_str())
+                    || hit.path.to_string_lossy().to_lowercase().contains(text.as_str())
+            }
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanResultPage {
+    pub status: SessionStatus,
+    /// Number of hits matching the query, across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub hits: Vec<FolderHit>,
+}
+
+/// One page of scan hits, filtered on the backend so large scans are not sent to the window at once.
+/// Hits found so far are returned while the scan is still running.
+#[tauri::command]
+pub async fn get_scan_results_page(
+    session_id: String,
+    offset: usize,
+    limit: usize,
+    query: Option<ScanResultQuery>,
+    state: State<'_, AppState>,
+) -> GuiResult<ScanResultPage> {
+    let id = Uuid::parse_str(&session_id)
+        .map_err(|_| gui_error!(scan, "Invalid session ID format"))?;
+    let query = query.unwrap_or_default();
+    let matcher = ScanResultMatcher::new(&query)?;
+    let limit = limit.min(MAX_PAGE_SIZE);
+    
+    // Filter under the lock instead of copying every hit out of the session
+    let sessions = state.scan_sessions.lock()
+        .map_err(|_| gui_error!(scan, "Failed to access scan sessions"))?;
+    let session = sessions.get(&id)
+        .ok_or_else(|| gui_error!(session_not_found, session_id))?;
+    if session.status == SessionStatus::Failed {
+        return Err(gui_error!(scan, session.error.clone().unwrap_or_else(|| "Unknown scan error".to_string())));
+    }
+    
+    let mut total = 0;
+    let mut hits = Vec::new();
+    for hit in session.results.iter().flatten().filter(|hit| matcher.matches(hit)) {
+        if total >= offset && hits.len() < limit {
+            hits.push(hit.clone());
+        }
+        total += 1;
+    }
+    
+    Ok(ScanResultPage { status: session.status.clone(), total, offset, hits })
+}
+
+/// Every hit of the session that matches `criteria`, for searching the whole scan at once;
+/// use `get_scan_results_page` with the same criteria to show them a page at a time
+#[tauri::command]
+pub async fn filter_scan_results(
+    session_id: String,
+    criteria: ScanResultQuery,
+    state: State<'_, AppState>,
+) -> GuiResult<Vec<FolderHit>> {
+    let id = Uuid::parse_str(&session_id)
+        .map_err(|_| gui_error!(scan, "Invalid session ID format"))?;
+    let matcher = ScanResultMatcher::new(&criteria)?;
+    
+    let sessions = state.scan_sessions.lock()
+        .map_err(|_| gui_error!(scan, "Failed to access scan sessions"))?;
+    let session = sessions.get(&id)
+        .ok_or_else(|| gui_error!(session_not_found, session_id))?;
+    if session.status == SessionStatus::Failed {
+        return Err(gui_error!(scan, session.error.clone().unwrap_or_else(|| "Unknown scan error".to_string())));
+    }
+    
+    let hits: Vec<FolderHit> = session.results.iter().flatten()
+        .filter(|hit| matcher.matches(hit))
+        .cloned()
+        .collect();
+    debug!("{} hits of scan {} match the filter", hits.len(), id);
+    Ok(hits)
+}
+
+/// How each rule was evaluated for one scan hit
+#[derive(Debug, Clone, Serialize)]
+pub struct HitExplanation {
+    pub path: PathBuf,
+    pub evaluations: Vec<RuleEvaluationInfo>,
+    pub matched_rule: Option<Uuid>,
+    pub excluded_by: Option<Uuid>,
+    pub excluded_by_pattern: Option<PatternSpec>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleEvaluationInfo {
+    pub rule_id: Uuid,
+    pub label: Option<String>,
+    pub priority: u32,
+    pub pattern: PatternSpec,
+    pub outcome: RuleOutcome,
+    pub compared: Option<ComparedText>,
+}
+
+impl HitExplanation {
+    fn new(path: PathBuf, explanation: &MatchExplanation) -> Self {
+        Self {
+            path,
+            evaluations: explanation.evaluations.iter().map(|evaluation| RuleEvaluationInfo {
+                rule_id: evaluation.rule.id,
+                label: evaluation.rule.label.clone(),
+                priority: evaluation.rule.priority,
+                pattern: evaluation.rule.pattern.clone(),
+                outcome: evaluation.outcome,
+                compared: evaluation.compared.clone(),
+            }).collect(),
+            matched_rule: explanation.matched_rule.map(|rule| rule.id),
+            excluded_by: explanation.excluded_by.map(|rule| rule.id),
+            excluded_by_pattern: explanation.excluded_by_pattern.cloned(),
+        }
+    }
+}
+
+/// Explain why a folder from a scan session matched, using the current rules
+#[tauri::command]
+pub async fn explain_scan_hit(
+    session_id: String,
+    path: PathBuf,
+    state: State<'_, AppState>,
+) -> GuiResult<HitExplanation> {
+    let id = Uuid::parse_str(&session_id)
+        .map_err(|_| gui_error!(scan, "Invalid session ID format"))?;
+    
+    let session = state.get_scan_session(id)
+        .ok_or_else(|| gui_error!(session_not_found, session_id))?;
+    let root = session.roots.iter()
+        .find(|root| path.starts_with(root))
+        .ok_or_else(|| gui_error!(scan, format!("{} is not under a scanned root", path.display())))?;
+    
+    let config = state.config.lock()
+        .map_err(|_| gui_error!(scan, "Failed to access configuration"))?
+        .clone();
+    let scanner = FolderScanner::from_config_cached(&config, &state.engine_cache)
+        .map_err(|e| gui_error!(scan, format!("Failed to create scanner: {}", e)))?;
+    
+    let explanation = scanner.explain(root, &path)
+        .map_err(|e| gui_error!(scan, format!("Failed to explain {}: {}", path.display(), e)))?;
+    Ok(HitExplanation::new(path, &explanation))
+}
+
+/// Choose which of the matching rules a scan hit uses, when several rules matched it
+#[tauri::command]
+pub async fn resolve_scan_hit_rule(
+    session_id: String,
+    path: PathBuf,
+    rule_id: Uuid,
+    state: State<'_, AppState>,
+) -> GuiResult<FolderHit> {
+    let id = Uuid::parse_str(&session_id)
+        .map_err(|_| gui_error!(scan, "Invalid session ID format"))?;
+    
+    let session = state.get_scan_session(id)
+        .ok_or_else(|| gui_error!(session_not_found, session_id))?;
+    let mut hit = session.results.unwrap_or_default().into_iter()
+        .find(|hit| hit.path == path)
+        .ok_or_else(|| gui_error!(scan, format!("{} is not in the scan results", path.display())))?;
+    
+    let config = state.config.lock()
+        .map_err(|_| gui_error!(scan, "Failed to access configuration"))?
+        .clone();
+    let rule = config.rules.iter()
+        .find(|rule| rule.id == rule_id)
+        .ok_or_else(|| gui_error!(scan, format!("Rule {} not found", rule_id)))?;
+    let scanner = FolderScanner::from_config_cached(&config, &state.engine_cache)
+        .map_err(|e| gui_error!(scan, format!("Failed to create scanner: {}", e)))?;
+    scanner.reassign_rule(&mut hit, rule)
+        .map_err(|e| gui_error!(scan, e.to_string()))?;
+    
+    let updated = hit.clone();
+    state.update_scan_session(id, move |session| {
+        if let Some(existing) = session.results.iter_mut().flatten().find(|h| h.path == updated.path) {
+            *existing = updated;
+        }
+    });
+    Ok(hit)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,9 +496,18 @@ mod tests {
         let state = AppState::new();
         
         let roots = vec![temp_dir.path().to_path_buf()];
-        let result = scan_folders(roots, State::from(&state)).await;
+        let session_id = scan_folders(roots, State::from(&state)).await.unwrap();
         
-        assert!(result.is_ok());
+        // The scan finishes in the background
+        let mut status = SessionStatus::Running;
+        for _ in 0..100 {
+            status = state.get_scan_session(session_id).unwrap().status;
+            if status != SessionStatus::Running {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(status, SessionStatus::Completed);
     }
 
     #[tokio::test]
@@ -170,6 +528,133 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_session_progress_callback_streams_hits() {
+        let state = AppState::new();
+        let session_id = state.create_scan_session(vec![PathBuf::from("/tmp")]);
+        let emitted = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = emitted.clone();
+        state.events.connect(Arc::new(move |event, payload| {
+            sink.lock().unwrap().push((event.to_string(), payload));
+        }));
+        let callback = session_progress_callback(&state, session_id, Instant::now());
+        
+        callback(&ScanEvent::Progress(filemover_types::ScanProgress {
+            dirs_visited: 10,
+            hits_found: 0,
+            current_path: None,
+        }));
+        callback(&ScanEvent::Hit(FolderHit {
+            path: PathBuf::from("/tmp/project"),
+            name: "project".to_string(),
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: vec![],
+            size_bytes: None,
+            item_count: None,
+            is_dir: true,
+        }));
+        
+        let session = state.get_scan_session(session_id).unwrap();
+        assert_eq!(session.progress.unwrap().dirs_visited, 10);
+        assert_eq!(session.results.unwrap().len(), 1);
+        
+        // Only progress reports are pushed to the frontend
+        let emitted = emitted.lock().unwrap();
+        assert_eq!(emitted.len(), 1);
+        assert_eq!(emitted[0].0, SCAN_PROGRESS_EVENT);
+        assert_eq!(emitted[0].1["dirs_visited"], 10);
+    }
+
+    #[tokio::test]
+    async fn test_get_scan_results_page_filters_and_pages() {
+        let state = AppState::new();
+        let session_id = state.create_scan_session(vec![PathBuf::from("/data")]);
+        let rule_id = Uuid::new_v4();
+        state.update_scan_session(session_id, |session| {
+            session.status = SessionStatus::Completed;
+            session.results = Some((0..5).map(|i| FolderHit {
+                path: PathBuf::from(format!("/data/Project{}", i)),
+                name: format!("Project{}", i),
+                matched_rule: (i % 2 == 0).then_some(rule_id),
+                matched_rules: Vec::new(),
+                tags: Vec::new(),
+                dest_preview: None,
+                warnings: if i == 4 { vec![Warning::LongPath] } else { vec![] },
+                size_bytes: None,
+                item_count: None,
+                is_dir: true,
+            }).collect());
+        });
+        
+        let page = get_scan_results_page(session_id.to_string(), 1, 2, None, State::from(&state)).await.unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.hits.iter().map(|h| h.name.as_str()).collect::<Vec<_>>(), vec!["Project1", "Project2"]);
+        
+        let query = ScanResultQuery { rule_id: Some(rule_id), text: Some("project".to_string()), ..Default::default() };
+        let page = get_scan_results_page(session_id.to_string(), 0, 10, Some(query), State::from(&state)).await.unwrap();
+        assert_eq!(page.total, 3);
+        
+        let query = ScanResultQuery { warning: Some(Warning::LongPath), ..Default::default() };
+        let page = get_scan_results_page(session_id.to_string(), 0, 10, Some(query), State::from(&state)).await.unwrap();
+        assert_eq!(page.hits.len(), 1);
+        assert_eq!(page.hits[0].name, "Project4");
+    }
+
+    #[tokio::test]
+    async fn test_filter_scan_results_by_glob_and_size() {
+        let state = AppState::new();
+        let session_id = state.create_scan_session(vec![PathBuf::from("/data")]);
+        state.update_scan_session(session_id, |session| {
+            session.status = SessionStatus::Completed;
+            session.results = Some(["Backup_2023", "backup_2024", "Photos"].iter().enumerate().map(|(i, name)| FolderHit {
+                path: PathBuf::from("/data").join(name),
+                name: name.to_string(),
+                matched_rule: None,
+                matched_rules: Vec::new(),
+                tags: Vec::new(),
+                dest_preview: None,
+                warnings: vec![],
+                size_bytes: (i < 2).then_some((i as u64 + 1) * 1000),
+                item_count: None,
+                is_dir: true,
+            }).collect());
+        });
+        let filter = |criteria: ScanResultQuery| filter_scan_results(session_id.to_string(), criteria, State::from(&state));
+        
+        let hits = filter(ScanResultQuery { path_glob: Some("backup_*".to_string()), ..Default::default() }).await.unwrap();
+        assert_eq!(hits.len(), 2);
+        let hits = filter(ScanResultQuery { path_glob: Some("/data/**/photos".to_string()), ..Default::default() }).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        
+        // Hits of unknown size are left out of a size range
+        let hits = filter(ScanResultQuery { min_size: Some(1500), ..Default::default() }).await.unwrap();
+        assert_eq!(hits.iter().map(|h| h.name.as_str()).collect::<Vec<_>>(), vec!["backup_2024"]);
+        assert_eq!(filter(ScanResultQuery::default()).await.unwrap().len(), 3);
+        
+        assert!(filter(ScanResultQuery { path_glob: Some("[".to_string()), ..Default::default() }).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_explain_scan_hit_outside_roots() {
+        let state = AppState::new();
+        let session_id = state.create_scan_session(vec![PathBuf::from("/data")]);
+        
+        let result = explain_scan_hit(session_id.to_string(), PathBuf::from("/other/folder"), State::from(&state)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_scan_hit_rule_unknown_path() {
+        let state = AppState::new();
+        let session_id = state.create_scan_session(vec![PathBuf::from("/data")]);
+        
+        let result = resolve_scan_hit_rule(session_id.to_string(), PathBuf::from("/data/missing"), Uuid::new_v4(), State::from(&state)).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_cancel_scan() {
         let state = AppState::new();
@@ -0,0 +1,66 @@
+use tauri::State;
+use tracing::info;
+
+use crate::settings::{AppSettings, SETTINGS_FILE};
+use crate::state::AppState;
+use crate::error::{GuiResult, GuiError, gui_error};
+
+#[tauri::command]
+pub async fn get_app_settings(
+    state: State<'_, AppState>,
+) -> GuiResult<AppSettings> {
+    state.settings.lock()
+        .map(|settings| settings.clone())
+        .map_err(|_| gui_error!(config, "Failed to access settings"))
+}
+
+/// Save the preferences to the config directory; they take effect immediately
+#[tauri::command]
+pub async fn save_app_settings(
+    settings: AppSettings,
+    state: State<'_, AppState>,
+) -> GuiResult<()> {
+    validate_settings(&state, &settings)?;
+
+    let path = state.config_dir.join(SETTINGS_FILE);
+    settings.save(&path)
+        .map_err(|e| gui_error!(config, format!("Failed to save settings to {}: {}", path.display(), e)))?;
+    *state.settings.lock()
+        .map_err(|_| gui_error!(config, "Failed to access settings"))? = settings;
+
+    info!("Settings saved to {}", path.display());
+    Ok(())
+}
+
+fn validate_settings(state: &AppState, settings: &AppSettings) -> GuiResult<()> {
+    let profile = &settings.default_profile;
+    if profile != "default" && !state.profile_path(profile).is_file() {
+        return Err(gui_error!(config, format!("Profile '{}' does not exist", profile)));
+    }
+    if let Some(dir) = &settings.journal_dir {
+        if dir.exists() && !dir.is_dir() {
+            return Err(gui_error!(config, format!("Journal directory is a file: {}", dir.display())));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_validate_settings_rejects_missing_profile() {
+        let state = AppState::new();
+        assert!(validate_settings(&state, &AppSettings::default()).is_ok());
+
+        let settings = AppSettings { default_profile: "no-such-profile-xyz".to_string(), ..AppSettings::default() };
+        assert!(validate_settings(&state, &settings).is_err());
+
+        let settings = AppSettings { journal_dir: Some(PathBuf::from("/journals")), ..AppSettings::default() };
+        assert!(validate_settings(&state, &settings).is_ok());
+        *state.settings.lock().unwrap() = settings;
+        assert_eq!(state.journal_dir(), PathBuf::from("/journals"));
+    }
+}
@@ -2,9 +2,10 @@ use std::path::PathBuf;
 use tauri::State;
 use tracing::{info, debug, warn};
 
-use filemover_types::{JournalEntry, ResultKind};
+use filemover_types::{JournalEntry, JournalReadReport, ResultKind};
 use crate::state::AppState;
 use crate::error::{GuiResult, GuiError, gui_error};
+use crate::notifications::{notify_task_finished, TaskFinishedEvent};
 
 #[tauri::command]
 pub async fn undo_operation(
@@ -37,36 +38,36 @@ pub async fn undo_operation(
     info!("Found {} undoable operations out of {} total", undoable.len(), entries.len());
     
     // Perform undo operations
-    let result = perform_undo_operations(undoable).await;
+    let result = perform_undo_operations(undoable, &journal_path).await?;
     
     info!("Undo operation completed: {} succeeded, {} failed", 
           result.undone_operations, result.failed_operations);
+    notify_task_finished(&state, TaskFinishedEvent::undo(journal_path, &result));
     
     Ok(result)
 }
 
-fn load_journal_entries(path: &PathBuf) -> GuiResult<Vec<JournalEntry>> {
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| gui_error!(execution, format!("Failed to read journal file: {}", e)))?;
+pub(crate) fn load_checked_journal(path: &PathBuf) -> GuiResult<JournalReadReport> {
+    // Detects JSONL or SQLite journals and accepts all journal format versions
+    let journal = filemover_journal::open_journal(path)
+        .map_err(|e| gui_error!(execution, format!("Failed to open journal file: {}", e)))?;
     
-    let mut entries = Vec::new();
+    journal.read_checked()
+        .map_err(|e| gui_error!(execution, format!("Failed to read journal entries: {}", e)))
+}
+
+fn load_journal_entries(path: &PathBuf) -> GuiResult<Vec<JournalEntry>> {
+    let report = load_checked_journal(path)?;
     
-    for (line_num, line) in content.lines().enumerate() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        
-        let entry: JournalEntry = serde_json::from_str(line)
-            .map_err(|e| gui_error!(execution, 
-                format!("Failed to parse journal entry at line {}: {}", line_num + 1, e)))?;
-        
-        entries.push(entry);
+    // Damaged entries are reported and left out rather than failing the whole undo
+    for problem in &report.problems {
+        warn!("Journal {}: {}", path.display(), problem);
     }
     
-    Ok(entries)
+    Ok(report.entries)
 }
 
-fn analyze_undo_feasibility(entries: &[JournalEntry]) -> (Vec<&JournalEntry>, Vec<String>) {
+pub(crate) fn analyze_undo_feasibility(entries: &[JournalEntry]) -> (Vec<&JournalEntry>, Vec<String>) {
     let mut undoable = Vec::new();
     let mut issues = Vec::new();
     
@@ -100,7 +101,7 @@ fn analyze_undo_feasibility(entries: &[JournalEntry]) -> (Vec<&JournalEntry>, Ve
     (undoable, issues)
 }
 
-async fn perform_undo_operations(entries: Vec<&JournalEntry>) -> UndoResult {
+async fn perform_undo_operations(entries: Vec<&JournalEntry>, journal_path: &PathBuf) -> GuiResult<UndoResult> {
     let mut result = UndoResult {
         total_operations: entries.len(),
         undone_operations: 0,
@@ -109,55 +110,51 @@ async fn perform_undo_operations(entries: Vec<&JournalEntry>) -> UndoResult {
         errors: vec![],
     };
     
+    // Record what was restored next to the original journal, in the same format
+    let undo_journal_path = filemover_journal::undo_journal_path(journal_path);
+    let format = filemover_journal::detect_format(journal_path)
+        .map_err(|e| gui_error!(execution, format!("Failed to read journal format: {}", e)))?;
+    let mut undo_journal = filemover_journal::create_or_open_journal(&undo_journal_path, format)
+        .map_err(|e| gui_error!(execution, format!("Failed to create undo journal: {}", e)))?;
+    
     for entry in entries {
-        match perform_single_undo(entry).await {
-            Ok(_) => {
+        let record = perform_single_undo(entry).await;
+        
+        match record.result {
+            ResultKind::Ok => {
                 result.undone_operations += 1;
                 debug!("Undone: {} <- {}", entry.source.display(), entry.dest.display());
             }
-            Err(error) => {
+            _ => {
+                let error = record.message.as_deref().unwrap_or("unknown error");
                 result.failed_operations += 1;
                 result.errors.push(format!("Failed to undo {}: {}", entry.dest.display(), error));
                 warn!("Failed to undo {}: {}", entry.dest.display(), error);
             }
         }
         
-        // Small delay to prevent overwhelming the file system
-        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        undo_journal.append(&record)
+            .map_err(|e| gui_error!(execution, format!("Failed to write undo journal: {}", e)))?;
     }
     
-    result
+    undo_journal.finish()
+        .map_err(|e| gui_error!(execution, format!("Failed to finalize undo journal: {}", e)))?;
+    
+    info!("Undo journal written to: {}", undo_journal_path.display());
+    Ok(result)
 }
 
-async fn perform_single_undo(entry: &JournalEntry) -> GuiResult<()> {
-    // Stub implementation for single undo operation
-    // In real implementation, this would:
+/// Reverse a single operation and return the record for the undo journal
+async fn perform_single_undo(entry: &JournalEntry) -> JournalEntry {
+    let owned = entry.clone();
     
-    match entry.op {
-        filemover_types::OpKind::Move => {
-            // Move dest back to source
-            debug!("Would move {} back to {}", entry.dest.display(), entry.source.display());
-            // TODO: Implement actual file move
-        }
-        filemover_types::OpKind::CopyDelete => {
-            // Copy dest back to source, then delete dest
-            debug!("Would copy {} back to {} and delete dest", entry.dest.display(), entry.source.display());
-            // TODO: Implement actual file copy and delete
-        }
-        filemover_types::OpKind::Rename => {
-            // Rename dest back to source name
-            debug!("Would rename {} back to {}", entry.dest.display(), entry.source.display());
-            // TODO: Implement actual file rename
-        }
-        _ => {
-            return Err(gui_error!(execution, format!("Unsupported operation type for undo: {:?}", entry.op)));
-        }
-    }
-    
-    // Simulate some processing time
-    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
-    
-    Ok(())
+    // File system work is blocking; keep it off the async worker threads
+    tokio::task::spawn_blocking(move || filemover_journal::undo_entry(&owned))
+        .await
+        .unwrap_or_else(|e| {
+            JournalEntry::new(entry.dest.clone(), entry.source.clone(), entry.op)
+                .failed(format!("Undo task failed: {}", e))
+        })
 }
 
 #[tauri::command]
@@ -178,8 +175,8 @@ pub async fn validate_journal(
         });
     }
     
-    let entries = match load_journal_entries(&journal_path) {
-        Ok(entries) => entries,
+    let report = match load_checked_journal(&journal_path) {
+        Ok(report) => report,
         Err(e) => {
             return Ok(JournalValidation {
                 is_valid: false,
@@ -193,7 +190,13 @@ pub async fn validate_journal(
         }
     };
     
-    let (undoable, issues) = analyze_undo_feasibility(&entries);
+    let is_valid = report.is_intact();
+    let entries = report.entries;
+    let (undoable, feasibility_issues) = analyze_undo_feasibility(&entries);
+    
+    // Integrity problems come first so damaged entries are easy to spot
+    let mut issues: Vec<String> = report.problems.iter().map(|p| p.to_string()).collect();
+    issues.extend(feasibility_issues);
     
     let successful_entries = entries.iter()
         .filter(|e| matches!(e.result, ResultKind::Ok))
@@ -208,7 +211,7 @@ pub async fn validate_journal(
         .count();
     
     Ok(JournalValidation {
-        is_valid: true,
+        is_valid,
         total_entries: entries.len(),
         successful_entries,
         failed_entries,
@@ -304,4 +307,19 @@ mod tests {
         let (undoable, _issues) = analyze_undo_feasibility(&entries);
         assert_eq!(undoable.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_perform_single_undo_moves_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        let dest = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest).unwrap();
+        
+        let entry = JournalEntry::new(source.clone(), dest.clone(), OpKind::Move);
+        let record = perform_single_undo(&entry).await;
+        
+        assert_eq!(record.result, ResultKind::Ok);
+        assert!(source.exists());
+        assert!(!dest.exists());
+    }
 }
\ No newline at end of file
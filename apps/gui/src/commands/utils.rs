@@ -1,4 +1,5 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use tauri::api::dialog;
 use serde::{Deserialize, Serialize};
 
@@ -77,6 +78,61 @@ pub async fn validate_path(path: PathBuf) -> GuiResult<PathValidation> {
     Ok(validation)
 }
 
+/// Show a path in the system file manager, with the item selected where the platform supports it
+#[tauri::command]
+pub async fn open_in_explorer(path: PathBuf) -> GuiResult<()> {
+    check_revealable(&path)?;
+    
+    // The file manager keeps running on its own, so it is not waited for
+    reveal_command(&path)
+        .spawn()
+        .map_err(|e| gui_error!(invalid_op, format!("Failed to open {}: {}", path.display(), e)))?;
+    Ok(())
+}
+
+fn check_revealable(path: &PathBuf) -> GuiResult<()> {
+    if is_system_protected_path(path) {
+        return Err(gui_error!(invalid_op, format!("Refusing to open a system protected path: {}", path.display())));
+    }
+    if !path.is_absolute() {
+        return Err(gui_error!(invalid_op, format!("Path is not absolute: {}", path.display())));
+    }
+    if !path.exists() {
+        return Err(gui_error!(invalid_op, format!("Path does not exist: {}", path.display())));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn reveal_command(path: &Path) -> Command {
+    use std::os::windows::process::CommandExt;
+    
+    // Explorer parses its own command line and needs the path quoted after the comma
+    let mut command = Command::new("explorer");
+    command.raw_arg(format!("/select,\"{}\"", path.display()));
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn reveal_command(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    command.arg("-R").arg(path);
+    command
+}
+
+/// xdg-open cannot select an item, so a folder is opened itself and a file in its parent
+#[cfg(all(unix, not(target_os = "macos")))]
+fn reveal_command(path: &Path) -> Command {
+    let target = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    };
+    let mut command = Command::new("xdg-open");
+    command.arg(target);
+    command
+}
+
 fn is_system_protected_path(path: &PathBuf) -> bool {
     let path_str = path.to_string_lossy().to_uppercase();
     
@@ -165,14 +221,7 @@ fn get_available_drives() -> Vec<DriveInfo> {
             let path = PathBuf::from(&drive_path);
             
             if path.exists() {
-                let drive_type = get_drive_type(&path);
-                drives.push(DriveInfo {
-                    path: path.clone(),
-                    label: drive_path,
-                    drive_type,
-                    total_space: None,
-                    free_space: None,
-                });
+                drives.push(drive_info(path, drive_path));
             }
         }
     }
@@ -180,20 +229,43 @@ fn get_available_drives() -> Vec<DriveInfo> {
     #[cfg(not(windows))]
     {
         // For Unix-like systems, add root and common mount points
-        drives.push(DriveInfo {
-            path: PathBuf::from("/"),
-            label: "Root".to_string(),
-            drive_type: DriveType::Fixed,
-            total_space: None,
-            free_space: None,
-        });
+        drives.push(drive_info(PathBuf::from("/"), "Root".to_string()));
     }
     
     drives
 }
 
+fn drive_info(path: PathBuf, label: String) -> DriveInfo {
+    // Unavailable drives (e.g. a disconnected network share) are listed without sizes
+    let space = filemover_planner::volume_space(&path);
+    DriveInfo {
+        drive_type: get_drive_type(&path),
+        total_space: space.map(|space| space.total),
+        free_space: space.map(|space| space.available),
+        path,
+        label,
+    }
+}
+
+#[cfg(windows)]
+fn get_drive_type(path: &PathBuf) -> DriveType {
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::um::fileapi::GetDriveTypeW;
+    use winapi::um::winbase::{DRIVE_CDROM, DRIVE_FIXED, DRIVE_RAMDISK, DRIVE_REMOTE, DRIVE_REMOVABLE};
+    
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    match unsafe { GetDriveTypeW(wide.as_ptr()) } {
+        DRIVE_FIXED => DriveType::Fixed,
+        DRIVE_REMOVABLE => DriveType::Removable,
+        DRIVE_REMOTE => DriveType::Network,
+        DRIVE_CDROM => DriveType::CD,
+        DRIVE_RAMDISK => DriveType::Ram,
+        _ => DriveType::Unknown,
+    }
+}
+
+#[cfg(not(windows))]
 fn get_drive_type(_path: &PathBuf) -> DriveType {
-    // Simplified drive type detection
     DriveType::Fixed
 }
 
@@ -276,6 +348,14 @@ mod tests {
         assert!(!is_system_protected_path(&PathBuf::from("C:\\Users\\Test")));
     }
 
+    #[test]
+    fn test_check_revealable() {
+        assert!(check_revealable(&std::env::temp_dir()).is_ok());
+        assert!(check_revealable(&PathBuf::from("C:\\Windows\\System32")).is_err());
+        assert!(check_revealable(&PathBuf::from("relative/folder")).is_err());
+        assert!(check_revealable(&std::env::temp_dir().join("filemover-no-such-folder")).is_err());
+    }
+
     #[tokio::test]
     async fn test_get_system_info() {
         let result = get_system_info().await;
@@ -290,5 +370,6 @@ mod tests {
     fn test_get_available_drives() {
         let drives = get_available_drives();
         assert!(!drives.is_empty());
+        assert!(drives.iter().any(|drive| drive.total_space.is_some()));
     }
 }
\ No newline at end of file
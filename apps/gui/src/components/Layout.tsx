@@ -1,10 +1,35 @@
-import React from 'react';
+import React, { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { open } from '@tauri-apps/api/shell';
+import { TaskFinishedEvent } from '../types';
 
 interface LayoutProps {
   children: React.ReactNode;
 }
 
 function Layout({ children }: LayoutProps) {
+  const [finished, setFinished] = useState<TaskFinishedEvent | null>(null);
+
+  // Scans, moves and undos report here when they end, even if another page is open
+  useEffect(() => {
+    const unlisten = listen<TaskFinishedEvent>('task-finished', event => {
+      setFinished(event.payload);
+    });
+    return () => {
+      unlisten.then(stop => stop());
+    };
+  }, []);
+
+  const openJournal = async () => {
+    if (finished?.journal_path) {
+      try {
+        await open(finished.journal_path);
+      } catch (error) {
+        console.error('Failed to open journal:', error);
+      }
+    }
+  };
+
   return (
     <div className="min-h-screen bg-gray-50">
       <header className="bg-white shadow-sm border-b border-gray-200">
@@ -20,6 +45,27 @@ function Layout({ children }: LayoutProps) {
         </div>
       </header>
       
+      {finished && (
+        <div className={finished.succeeded ? 'bg-green-50 border-b border-green-200' : 'bg-red-50 border-b border-red-200'}>
+          <div className="max-w-7xl mx-auto px-4 sm:px-6 lg:px-8 py-3 flex items-center justify-between">
+            <div className="text-sm">
+              <span className="font-medium text-gray-900">{finished.title}</span>
+              {finished.body && <span className="ml-2 text-gray-600">{finished.body}</span>}
+            </div>
+            <div className="flex items-center space-x-3 text-sm">
+              {finished.journal_path && (
+                <button onClick={openJournal} className="text-primary-600 hover:underline">
+                  ジャーナルを開く
+                </button>
+              )}
+              <button onClick={() => setFinished(null)} className="text-gray-500 hover:underline">
+                閉じる
+              </button>
+            </div>
+          </div>
+        </div>
+      )}
+      
       <main className="max-w-7xl mx-auto px-4 sm:px-6 lg:px-8 py-8">
         {children}
       </main>
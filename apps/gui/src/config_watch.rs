@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::{debug, info, warn};
+
+use filemover_types::Config;
+use filemover_scanner::FileChangeWatcher;
+use crate::commands::read_profile;
+use crate::state::AppState;
+
+/// Event sent to the frontend after the active profile changed on disk
+pub const CONFIG_RELOADED_EVENT: &str = "config-reloaded";
+
+/// How long the watcher waits before checking whether another profile became active
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Editors often save in several writes; the profile is reloaded once they stop for this long
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReloadedEvent {
+    pub profile: String,
+    /// The reloaded configuration, or None when the file could not be read
+    pub config: Option<Config>,
+    pub error: Option<String>,
+}
+
+/// Reload the active profile whenever its file changes, for as long as the app runs
+pub fn spawn_config_watcher(app: AppHandle) {
+    std::thread::spawn(move || watch_active_profile(app));
+}
+
+fn watch_active_profile(app: AppHandle) {
+    let mut watched: Option<(PathBuf, FileChangeWatcher)> = None;
+    loop {
+        let state = app.state::<AppState>();
+        let profile = state.current_profile_name();
+        let path = state.profile_path(&profile);
+
+        // Follow profile switches; a profile that does not exist yet is retried on the next round
+        if watched.as_ref().map(|(watched_path, _)| watched_path) != Some(&path) {
+            watched = match FileChangeWatcher::new(std::slice::from_ref(&path), RELOAD_DEBOUNCE) {
+                Ok(watcher) => {
+                    debug!("Watching profile {} at {}", profile, path.display());
+                    Some((path.clone(), watcher))
+                }
+                Err(e) => {
+                    debug!("Cannot watch profile {} yet: {}", profile, e);
+                    std::thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+            };
+        }
+
+        let Some((_, watcher)) = watched.as_mut() else {
+            continue;
+        };
+        match watcher.wait(POLL_INTERVAL) {
+            Ok(true) => reload_profile(&app, &state, profile, &path),
+            Ok(false) => {}
+            Err(e) => {
+                warn!("Stopped watching profile {}: {}", path.display(), e);
+                watched = None;
+            }
+        }
+    }
+}
+
+fn reload_profile(app: &AppHandle, state: &AppState, profile: String, path: &Path) {
+    let event = match read_profile(path) {
+        Ok(config) => {
+            if let Ok(mut current) = state.config.lock() {
+                *current = config.clone();
+            }
+            // The next scan compiles the new rules instead of reusing the old engine
+            state.engine_cache.clear();
+            info!("Reloaded profile {} from {}", profile, path.display());
+            ConfigReloadedEvent { profile, config: Some(config), error: None }
+        }
+        Err(e) => {
+            // A half-written or invalid file keeps the rules that are loaded
+            warn!("Failed to reload profile {}: {}", path.display(), e);
+            ConfigReloadedEvent { profile, config: None, error: Some(e.to_string()) }
+        }
+    };
+
+    if let Err(e) = app.emit_all(CONFIG_RELOADED_EVENT, event) {
+        warn!("Failed to notify the window about the reloaded profile: {}", e);
+    }
+}
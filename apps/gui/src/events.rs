@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use serde::Serialize;
+use tracing::warn;
+use uuid::Uuid;
+
+use filemover_types::Progress;
+use crate::state::{NodeResult, ScanSession, SessionStatus};
+
+/// Live scan progress, sent while a scan session runs and once more when it ends
+pub const SCAN_PROGRESS_EVENT: &str = "scan://progress";
+
+/// Live execution progress, sent after every operation and once more when the execution ends
+pub const EXECUTE_PROGRESS_EVENT: &str = "execute://progress";
+
+/// Outcome of one plan node, sent as soon as the executor finishes it
+pub const NODE_STATUS_EVENT: &str = "execute://node";
+
+/// Delivers an event name and its JSON payload to the frontend
+pub type EventSink = Arc<dyn Fn(&str, serde_json::Value) + Send + Sync>;
+
+/// Sends events to the frontend once the window is connected; until then (and in tests) they are dropped
+#[derive(Clone, Default)]
+pub struct EventEmitter {
+    sink: Arc<Mutex<Option<EventSink>>>,
+}
+
+impl EventEmitter {
+    pub fn connect(&self, sink: EventSink) {
+        if let Ok(mut current) = self.sink.lock() {
+            *current = Some(sink);
+        }
+    }
+
+    pub fn emit<T: Serialize>(&self, event: &str, payload: &T) {
+        let Some(sink) = self.sink.lock().ok().and_then(|sink| sink.clone()) else {
+            return;
+        };
+        match serde_json::to_value(payload) {
+            Ok(value) => sink(event, value),
+            Err(e) => warn!("Failed to serialize {} event: {}", event, e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanProgressEvent {
+    pub session_id: Uuid,
+    pub status: SessionStatus,
+    pub dirs_visited: u64,
+    pub hits_found: u64,
+    /// Total size of the hits found so far (only when sizes are calculated)
+    pub bytes_found: u64,
+    pub current_path: Option<PathBuf>,
+    pub dirs_per_second: f64,
+    pub elapsed_ms: u64,
+}
+
+impl ScanProgressEvent {
+    pub fn new(session: &ScanSession, elapsed: Duration) -> Self {
+        let progress = session.progress.clone().unwrap_or_default();
+        let results = session.results.as_deref().unwrap_or_default();
+        let seconds = elapsed.as_secs_f64();
+        Self {
+            session_id: session.id,
+            status: session.status.clone(),
+            dirs_visited: progress.dirs_visited,
+            // Progress reports lag behind the hits streamed into the session
+            hits_found: progress.hits_found.max(results.len() as u64),
+            bytes_found: results.iter().filter_map(|hit| hit.size_bytes).sum(),
+            current_path: progress.current_path,
+            dirs_per_second: if seconds > 0.0 { progress.dirs_visited as f64 / seconds } else { 0.0 },
+            elapsed_ms: elapsed.as_millis() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionProgressEvent {
+    pub session_id: Uuid,
+    pub status: SessionStatus,
+    pub current_item: Option<String>,
+    pub completed_ops: u64,
+    pub total_ops: u64,
+    pub bytes_processed: u64,
+    pub total_bytes: Option<u64>,
+    pub bytes_per_second: Option<u64>,
+    pub eta_seconds: Option<u64>,
+}
+
+impl ExecutionProgressEvent {
+    pub fn new(session_id: Uuid, status: SessionStatus, progress: &Progress) -> Self {
+        Self {
+            session_id,
+            status,
+            current_item: progress.current_item.clone(),
+            completed_ops: progress.completed_ops,
+            total_ops: progress.total_ops,
+            bytes_processed: progress.bytes_processed,
+            total_bytes: progress.total_bytes,
+            bytes_per_second: progress.current_speed,
+            eta_seconds: progress.eta.map(|eta| eta.as_secs()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeStatusEvent {
+    pub session_id: Uuid,
+    #[serde(flatten)]
+    pub result: NodeResult,
+}
@@ -2,10 +2,19 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod commands;
+mod config_watch;
+mod events;
+mod notifications;
+mod queue;
+mod settings;
+mod plan_index;
 mod state;
 mod error;
+mod tray;
 
-use tauri::Manager;
+use std::sync::Arc;
+use tauri::{Manager, WindowEvent};
+use tauri::api::notification::Notification;
 use tracing::{info, error};
 
 use crate::state::AppState;
@@ -19,6 +28,15 @@ fn main() {
 
     tauri::Builder::default()
         .manage(AppState::new())
+        .system_tray(tray::system_tray())
+        .on_system_tray_event(tray::handle_tray_event)
+        // Closing the window keeps the app in the tray so queued operations keep running
+        .on_window_event(|event| {
+            if let WindowEvent::CloseRequested { api, .. } = event.event() {
+                let _ = event.window().hide();
+                api.prevent_close();
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             // Configuration commands
             load_config,
@@ -26,29 +44,56 @@ fn main() {
             list_profiles,
             create_profile,
             delete_profile,
+            get_message_catalog,
+            get_app_settings,
+            save_app_settings,
             
             // Scanning commands
             scan_folders,
             get_scan_progress,
             cancel_scan,
+            get_scan_results_page,
+            filter_scan_results,
+            explain_scan_hit,
+            resolve_scan_hit_rule,
+            
+            // Rule editor commands
+            validate_rule,
+            preview_rule_matches,
+            preview_destination,
             
             // Planning commands
             create_move_plan,
             simulate_plan,
             update_plan_node,
+            get_plan_nodes,
+            get_plan_node_children,
+            list_conflicts,
+            resolve_conflict,
             
             // Execution commands
             execute_plan,
             get_execution_progress,
             cancel_execution,
+            request_elevation,
             
             // Undo commands
             undo_operation,
+            list_journals,
+            get_journal_summary,
+            
+            // Queue commands
+            get_queue,
+            enqueue_scan,
+            enqueue_apply,
+            set_queue_paused,
+            remove_queued_operation,
             
             // Utility commands
             browse_folder,
             validate_path,
-            get_system_info
+            get_system_info,
+            open_in_explorer
         ])
         .setup(|app| {
             let window = app.get_window("main").unwrap();
@@ -58,6 +103,28 @@ fn main() {
                 window.open_devtools();
             }
             
+            // Progress of scans and executions is pushed to the window as it happens
+            let handle = app.handle();
+            app.state::<AppState>().events.connect(Arc::new(move |event, payload| {
+                if let Err(e) = handle.emit_all(event, payload) {
+                    error!("Failed to send {} event: {}", event, e);
+                }
+            }));
+            
+            // Finished tasks show a desktop notification when the profile enables them
+            let identifier = app.config().tauri.bundle.identifier.clone();
+            app.state::<AppState>().notifier.connect(Arc::new(move |title, body| {
+                if let Err(e) = Notification::new(&identifier).title(title).body(body).show() {
+                    error!("Failed to show notification: {}", e);
+                }
+            }));
+            
+            // Rule edits made outside the app (in an editor or with the CLI) show up without a restart
+            config_watch::spawn_config_watcher(app.handle());
+            
+            // Queued scans and applies run in the background while the window is hidden
+            tray::spawn_queue_worker(app.handle());
+            
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::commands::UndoResult;
+use crate::state::{AppState, ExecutionSession, ScanSession, SessionStatus};
+
+/// Sent to the frontend whenever a scan, execution or undo finishes, notification or not
+pub const TASK_FINISHED_EVENT: &str = "task-finished";
+
+/// Shows a desktop notification with a title and body
+pub type NotificationSink = Arc<dyn Fn(&str, &str) + Send + Sync>;
+
+/// Desktop notifications, shown once the app is connected; until then (and in tests) they are dropped
+#[derive(Clone, Default)]
+pub struct Notifier {
+    sink: Arc<Mutex<Option<NotificationSink>>>,
+}
+
+impl Notifier {
+    pub fn connect(&self, sink: NotificationSink) {
+        if let Ok(mut current) = self.sink.lock() {
+            *current = Some(sink);
+        }
+    }
+
+    pub fn show(&self, title: &str, body: &str) {
+        if let Some(sink) = self.sink.lock().ok().and_then(|sink| sink.clone()) {
+            sink(title, body);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TaskKind {
+    Scan,
+    Apply,
+    Undo,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskFinishedEvent {
+    pub kind: TaskKind,
+    /// The scan or execution session (undos have none)
+    pub session_id: Option<Uuid>,
+    pub succeeded: bool,
+    pub title: String,
+    pub body: String,
+    /// Journal the frontend offers to open from the notification
+    pub journal_path: Option<PathBuf>,
+}
+
+impl TaskFinishedEvent {
+    pub fn scan(session: &ScanSession) -> Self {
+        let found = session.results.as_ref().map(|hits| hits.len()).unwrap_or(0);
+        let (succeeded, title, body) = match session.status {
+            SessionStatus::Failed => (false, "Scan failed", session.error.clone().unwrap_or_default()),
+            SessionStatus::Cancelled => (false, "Scan cancelled", format!("{} folders found before the scan stopped", found)),
+            _ => (true, "Scan finished", format!("{} matching folders found", found)),
+        };
+        Self { kind: TaskKind::Scan, session_id: Some(session.id), succeeded, title: title.to_string(), body, journal_path: None }
+    }
+
+    pub fn apply(session: &ExecutionSession) -> Self {
+        let (completed, total) = session.progress.as_ref()
+            .map(|progress| (progress.completed_ops, progress.total_ops))
+            .unwrap_or((0, 0));
+        let (succeeded, title, body) = match session.status {
+            SessionStatus::Failed => (false, "Move failed", session.error.clone().unwrap_or_default()),
+            SessionStatus::Cancelled => (false, "Move cancelled", format!("{} of {} operations done before it stopped", completed, total)),
+            _ => (true, "Move finished", format!("{} of {} operations done", completed, total)),
+        };
+        Self {
+            kind: TaskKind::Apply,
+            session_id: Some(session.id),
+            succeeded,
+            title: title.to_string(),
+            body,
+            journal_path: session.journal_path.clone(),
+        }
+    }
+
+    pub fn undo(journal_path: PathBuf, result: &UndoResult) -> Self {
+        let succeeded = result.failed_operations == 0;
+        let title = if succeeded { "Undo finished" } else { "Undo finished with errors" };
+        let body = format!(
+            "{} undone, {} failed, {} skipped",
+            result.undone_operations, result.failed_operations, result.skipped_operations
+        );
+        Self { kind: TaskKind::Undo, session_id: None, succeeded, title: title.to_string(), body, journal_path: Some(journal_path) }
+    }
+}
+
+/// Tell the frontend a task finished, and show a desktop notification when they are enabled
+pub fn notify_task_finished(state: &AppState, event: TaskFinishedEvent) {
+    state.events.emit(TASK_FINISHED_EVENT, &event);
+
+    let enabled = state.config.lock()
+        .map(|config| config.notifications.enabled)
+        .unwrap_or(false);
+    if enabled {
+        state.notifier.show(&event.title, &event.body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_follow_config_setting() {
+        let state = AppState::new();
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let sink = shown.clone();
+        state.notifier.connect(Arc::new(move |title, _body| sink.lock().unwrap().push(title.to_string())));
+        let result = UndoResult {
+            total_operations: 2,
+            undone_operations: 2,
+            failed_operations: 0,
+            skipped_operations: 0,
+            errors: vec![],
+        };
+
+        // Notifications are off by default
+        notify_task_finished(&state, TaskFinishedEvent::undo(PathBuf::from("journal.jsonl"), &result));
+        assert!(shown.lock().unwrap().is_empty());
+
+        state.config.lock().unwrap().notifications.enabled = true;
+        notify_task_finished(&state, TaskFinishedEvent::undo(PathBuf::from("journal.jsonl"), &result));
+        assert_eq!(*shown.lock().unwrap(), vec!["Undo finished".to_string()]);
+    }
+}
@@ -1,7 +1,10 @@
 import { useEffect, useState } from 'react';
 import { useParams, useNavigate } from 'react-router-dom';
 import { invoke } from '@tauri-apps/api';
-import { ExecutionSession, Progress } from '../types';
+import { listen } from '@tauri-apps/api/event';
+import { ExecutionProgressEvent, ExecutionSession, NodeStatusEvent, Progress } from '../types';
+
+const NODE_RESULT_LIMIT = 1000;
 
 function ExecutionPage() {
   const { sessionId } = useParams<{ sessionId: string }>();
@@ -103,6 +106,43 @@ function ExecutionPage() {
     pollSession();
   }, [sessionId, navigate]);
 
+  // Live updates pushed by the backend after every operation
+  useEffect(() => {
+    if (!sessionId) return;
+
+    const unlisten = listen<ExecutionProgressEvent>('execute://progress', event => {
+      const update = event.payload;
+      if (update.session_id !== sessionId) return;
+      setSession(prev => prev ? {
+        ...prev,
+        status: update.status,
+        progress: {
+          current_item: update.current_item,
+          completed_ops: update.completed_ops,
+          total_ops: update.total_ops,
+          bytes_processed: update.bytes_processed,
+          total_bytes: update.total_bytes,
+          current_speed: update.bytes_per_second,
+          eta: update.eta_seconds,
+        },
+      } : prev);
+    });
+    // Keep the same bound as the backend so a long run does not grow the page's state
+    const unlistenNodes = listen<NodeStatusEvent>('execute://node', event => {
+      const { session_id, ...result } = event.payload;
+      if (session_id !== sessionId) return;
+      setSession(prev => prev ? {
+        ...prev,
+        node_results: [...prev.node_results, result].slice(-NODE_RESULT_LIMIT),
+        node_results_total: prev.node_results_total + 1,
+      } : prev);
+    });
+    return () => {
+      unlisten.then(stop => stop());
+      unlistenNodes.then(stop => stop());
+    };
+  }, [sessionId]);
+
   const cancelExecution = async () => {
     if (!sessionId || !canCancel) return;
 
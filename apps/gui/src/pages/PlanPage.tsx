@@ -1,7 +1,7 @@
 import { useEffect, useState } from 'react';
 import { useParams, useNavigate } from 'react-router-dom';
 import { invoke } from '@tauri-apps/api';
-import { PlanSession, MovePlan, PlanNode, Conflict } from '../types';
+import { AppSettings, PlanSession, MovePlan, PlanNode, Conflict } from '../types';
 
 function PlanPage() {
   const { sessionId } = useParams<{ sessionId: string }>();
@@ -103,6 +103,9 @@ function PlanPage() {
     if (!sessionId) return;
 
     try {
+      const settings = await invoke<AppSettings>('get_app_settings');
+      if (settings.confirm_before_apply && !confirm('この移動プランを実行しますか？')) return;
+
       setIsLoading(true);
       const executionSessionId = await invoke<string>('create_execution_session', {
         planId: sessionId,
@@ -121,6 +124,14 @@ function PlanPage() {
     }
   };
 
+  const revealPath = async (path: string) => {
+    try {
+      await invoke('open_in_explorer', { path });
+    } catch (error) {
+      alert(`フォルダを開けませんでした: ${error}`);
+    }
+  };
+
   const formatBytes = (bytes?: number) => {
     if (!bytes) return 'N/A';
     const units = ['B', 'KB', 'MB', 'GB', 'TB'];
@@ -225,6 +236,12 @@ function PlanPage() {
                 <div className="mt-1 space-y-1">
                   <div className="text-sm text-gray-900 font-mono break-all">
                     変更前: {node.path_before}
+                    <button
+                      onClick={() => revealPath(node.path_before)}
+                      className="ml-2 text-xs text-blue-600 hover:underline font-sans"
+                    >
+                      エクスプローラーで表示
+                    </button>
                   </div>
                   {node.path_after !== node.path_before && (
                     <div className="text-sm text-green-700 font-mono break-all">
@@ -355,6 +372,15 @@ function PlanPage() {
 
   const hasConflicts = plan.summary.conflicts > 0;
   const hasWarnings = plan.summary.warnings > 0;
+  const preservation = plan.summary.preservation;
+  const preservationLosses = preservation
+    ? ([
+        ['ACL', preservation.acl_lost],
+        ['タイムスタンプの精度', preservation.timestamps_degraded],
+        ['代替データストリーム', preservation.streams_lost],
+        ['圧縮属性', preservation.compression_lost],
+      ] as [string, number][]).filter(([, count]) => count > 0)
+    : [];
   const rootNodes = plan.roots.map(rootId => plan.nodes[rootId]).filter(Boolean);
 
   return (
@@ -413,6 +439,16 @@ function PlanPage() {
             <span className="ml-2 text-gray-900">{plan.roots.length}個</span>
           </div>
         </div>
+        {preservationLosses.length > 0 && (
+          <div className="mt-4 text-sm text-yellow-800">
+            <span className="font-medium">移動先で保持できない情報:</span>
+            <ul className="mt-1 list-disc list-inside">
+              {preservationLosses.map(([label, count]) => (
+                <li key={label}>{label}: {count}件</li>
+              ))}
+            </ul>
+          </div>
+        )}
       </div>
 
       {/* Warnings and Conflicts Alert */}
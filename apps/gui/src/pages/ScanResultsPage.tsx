@@ -1,7 +1,7 @@
 import { useEffect, useState } from 'react';
 import { useParams, useNavigate } from 'react-router-dom';
 import { invoke } from '@tauri-apps/api';
-import { ScanSession } from '../types';
+import { FolderHit, HitExplanation, RuleOutcome, ScanSession } from '../types';
 import { sessionManager } from '../services/sessionManager';
 
 function ScanResultsPage() {
@@ -10,6 +10,7 @@ function ScanResultsPage() {
   const [session, setSession] = useState<ScanSession | null>(null);
   const [isLoading, setIsLoading] = useState(true);
   const [selectedHits, setSelectedHits] = useState<Set<string>>(new Set());
+  const [explanation, setExplanation] = useState<HitExplanation | null>(null);
 
   useEffect(() => {
     if (!sessionId) return;
@@ -112,6 +113,39 @@ function ScanResultsPage() {
     setSelectedHits(new Set());
   };
 
+  const explainHit = async (hit: FolderHit) => {
+    if (!sessionId) return;
+
+    try {
+      const result = await invoke<HitExplanation>('explain_scan_hit', {
+        sessionId,
+        path: hit.path,
+      });
+      setExplanation(result);
+    } catch (error) {
+      console.error('Failed to explain scan hit:', error);
+      setExplanation(null);
+    }
+  };
+
+  const resolveRule = async (hit: FolderHit, ruleId: string) => {
+    if (!sessionId || !session?.results) return;
+
+    try {
+      const updated = await invoke<FolderHit>('resolve_scan_hit_rule', {
+        sessionId,
+        path: hit.path,
+        ruleId,
+      });
+      setSession({
+        ...session,
+        results: session.results.map(h => (h.path === updated.path ? updated : h)),
+      });
+    } catch (error) {
+      console.error('Failed to change the rule for scan hit:', error);
+    }
+  };
+
   const createPlan = async () => {
     if (!sessionId || selectedHits.size === 0) return;
 
@@ -172,6 +206,10 @@ function ScanResultsPage() {
       'AccessDenied': { label: 'アクセス拒否', color: 'badge-error' },
       'Junction': { label: 'ジャンクション', color: 'badge-info' },
       'CrossVolume': { label: 'ボリューム間', color: 'badge-warning' },
+      'NetworkSlow': { label: 'ネットワーク低速', color: 'badge-warning' },
+      'Unreachable': { label: '接続不可', color: 'badge-error' },
+      'Hidden': { label: '隠し', color: 'badge-info' },
+      'System': { label: 'システム', color: 'badge-warning' },
     };
 
     return (
@@ -221,30 +259,14 @@ function ScanResultsPage() {
 
         {session.progress && (
           <div className="card">
-            <div className="mb-4">
-              <div className="flex justify-between text-sm text-gray-600 mb-2">
-                <span>進行状況</span>
-                <span>{session.progress.completed_ops} / {session.progress.total_ops}</span>
-              </div>
-              <div className="w-full bg-gray-200 rounded-full h-2">
-                <div
-                  className="bg-primary-600 h-2 rounded-full transition-all duration-300"
-                  style={{
-                    width: `${(session.progress.completed_ops / session.progress.total_ops) * 100}%`
-                  }}
-                ></div>
-              </div>
+            <div className="flex justify-between text-sm text-gray-600 mb-2">
+              <span>訪問済みフォルダ: {session.progress.dirs_visited.toLocaleString()}</span>
+              <span>一致: {session.progress.hits_found.toLocaleString()}</span>
             </div>
             
-            {session.progress.current_item && (
+            {session.progress.current_path && (
               <p className="text-sm text-gray-700">
-                現在: <span className="font-mono">{session.progress.current_item}</span>
-              </p>
-            )}
-            
-            {session.progress.current_speed && (
-              <p className="text-sm text-gray-500 mt-2">
-                速度: {session.progress.current_speed.toFixed(1)} items/sec
+                現在: <span className="font-mono">{session.progress.current_path}</span>
               </p>
             )}
           </div>
@@ -364,18 +386,44 @@ function ScanResultsPage() {
                     />
                   </td>
                   <td className="px-6 py-4">
-                    <div className="text-sm font-mono text-gray-900 break-all">
+                    <button
+                      type="button"
+                      onClick={() => explainHit(hit)}
+                      title="一致したルールの詳細を表示"
+                      className="text-sm font-mono text-gray-900 break-all text-left hover:underline"
+                    >
                       {hit.path}
-                    </div>
+                    </button>
                     <div className="text-xs text-gray-500 mt-1">
                       フォルダ名: {hit.name}
+                      {hit.is_dir !== false && hit.item_count === 0 && (
+                        <span className="badge-info text-xs ml-2">空フォルダ</span>
+                      )}
+                      {hit.tags?.map((tag) => (
+                        <span key={tag} className="badge-info text-xs ml-2">#{tag}</span>
+                      ))}
                     </div>
                   </td>
                   <td className="px-6 py-4">
                     <div className="text-sm text-gray-900 break-all">
                       {hit.dest_preview || 'N/A'}
                     </div>
-                    {hit.matched_rule && (
+                    {hit.matched_rules && hit.matched_rules.length > 1 ? (
+                      <div className="text-xs text-blue-600 mt-1">
+                        <span className="badge-warning text-xs mr-2">複数一致</span>
+                        <select
+                          value={hit.matched_rule}
+                          onChange={(e) => resolveRule(hit, e.target.value)}
+                          className="input text-xs"
+                        >
+                          {hit.matched_rules.map((ruleId) => (
+                            <option key={ruleId} value={ruleId}>
+                              ルール: {ruleId}
+                            </option>
+                          ))}
+                        </select>
+                      </div>
+                    ) : hit.matched_rule && (
                       <div className="text-xs text-blue-600 mt-1">
                         ルール: {hit.matched_rule}
                       </div>
@@ -402,8 +450,63 @@ function ScanResultsPage() {
           )}
         </div>
       </div>
+
+      {/* Match Explanation */}
+      {explanation && (
+        <div className="card">
+          <div className="flex justify-between items-center mb-4">
+            <div>
+              <h3 className="text-lg font-semibold text-gray-900">ルールの評価結果</h3>
+              <p className="text-sm font-mono text-gray-500 break-all">{explanation.path}</p>
+            </div>
+            <button onClick={() => setExplanation(null)} className="btn-secondary btn-sm">
+              閉じる
+            </button>
+          </div>
+          {explanation.excluded_by_pattern && (
+            <p className="text-sm text-gray-700 mb-3">
+              除外パターン <span className="font-mono">{explanation.excluded_by_pattern.value}</span> に一致したため対象外です
+            </p>
+          )}
+          <ul className="space-y-2">
+            {explanation.evaluations.map((evaluation) => (
+              <li key={evaluation.rule_id} className="text-sm">
+                <span className={evaluation.outcome === 'Matched' ? 'badge-success' : 'badge-info'}>
+                  {outcomeLabel(evaluation.outcome)}
+                </span>
+                <span className="ml-2 text-gray-900">
+                  {evaluation.label || evaluation.pattern.value}
+                  {evaluation.pattern.is_exclude ? ' (除外)' : ` (優先度 ${evaluation.priority})`}
+                </span>
+                {evaluation.compared && (
+                  <div className="text-xs text-gray-500 ml-2 mt-1 font-mono">
+                    "{evaluation.compared.original}" → "{evaluation.compared.normalized}"
+                  </div>
+                )}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
     </div>
   );
 }
 
-export default ScanResultsPage;
\ No newline at end of file
+function outcomeLabel(outcome: RuleOutcome): string {
+  switch (outcome) {
+    case 'Matched':
+      return '一致';
+    case 'ConditionsNotMet':
+      return '条件不一致';
+    case 'NotMatched':
+      return '不一致';
+    case 'SkippedByExclusion':
+      return '除外により未評価';
+    case 'SkippedByPriority':
+      return '優先度により未評価';
+    case 'Disabled':
+      return '無効';
+  }
+}
+
+export default ScanResultsPage;
@@ -1,8 +1,9 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { invoke } from '@tauri-apps/api';
 import { open } from '@tauri-apps/api/dialog';
-import { Config, Rule, PatternKind, ConflictPolicy } from '../types';
+import { listen } from '@tauri-apps/api/event';
+import { Config, ConfigReloadedEvent, Rule, PatternKind, MatchTarget, ConflictPolicy, TieBreakStrategy } from '../types';
 import { sessionManager } from '../services/sessionManager';
 
 function SetupPage() {
@@ -16,6 +17,8 @@ function SetupPage() {
         normalize_width: true,
         strip_diacritics: false,
         normalize_case: false,
+        fold_halfwidth_kana: true,
+        fold_hiragana_katakana: false,
       },
       follow_junctions: false,
       system_protections: true,
@@ -26,6 +29,20 @@ function SetupPage() {
   
   const [isLoading, setIsLoading] = useState(false);
 
+  // Pick up rule edits made to the profile file outside the app
+  useEffect(() => {
+    const unlisten = listen<ConfigReloadedEvent>('config-reloaded', event => {
+      if (event.payload.config) {
+        setConfig(event.payload.config);
+      } else {
+        console.warn(`Failed to reload profile ${event.payload.profile}: ${event.payload.error}`);
+      }
+    });
+    return () => {
+      unlisten.then(stop => stop());
+    };
+  }, []);
+
   const addRoot = async () => {
     try {
       const selected = await open({
@@ -107,6 +124,7 @@ function SetupPage() {
         value: '*',
         is_exclude: false,
         case_insensitive: true,
+        match_target: 'Name' as MatchTarget,
       },
       dest_root: '',
       template: '{name}',
@@ -314,6 +332,8 @@ function SetupPage() {
                     <option value="Glob">Glob</option>
                     <option value="Regex">正規表現</option>
                     <option value="Contains">部分一致</option>
+                    <option value="Fuzzy">あいまい一致</option>
+                    <option value="Extension">拡張子</option>
                   </select>
                 </div>
                 
@@ -328,7 +348,7 @@ function SetupPage() {
                       pattern: { ...rule.pattern, value: e.target.value }
                     })}
                     className="input"
-                    placeholder="*.jpg"
+                    placeholder={rule.pattern.kind === 'Extension' ? 'jpg,png,heic' : '*.jpg'}
                   />
                 </div>
                 
@@ -337,13 +357,15 @@ function SetupPage() {
                     競合処理
                   </label>
                   <select
-                    value={rule.policy}
-                    onChange={(e) => updateRule(index, { policy: e.target.value as ConflictPolicy })}
+                    value={rule.policy ?? ''}
+                    onChange={(e) => updateRule(index, { policy: (e.target.value || undefined) as ConflictPolicy | undefined })}
                     className="input"
                   >
+                    <option value="">既定に従う</option>
                     <option value="AutoRename">自動リネーム</option>
                     <option value="Skip">スキップ</option>
                     <option value="Overwrite">上書き</option>
+                    <option value="Merge">フォルダをまとめる</option>
                   </select>
                 </div>
               </div>
@@ -396,6 +418,20 @@ function SetupPage() {
                   />
                   <span className="ml-2 text-sm text-gray-700">大文字小文字を区別しない</span>
                 </label>
+
+                {rule.pattern.kind === 'Contains' && (
+                  <label className="flex items-center">
+                    <input
+                      type="checkbox"
+                      checked={rule.pattern.whole_word ?? false}
+                      onChange={(e) => updateRule(index, {
+                        pattern: { ...rule.pattern, whole_word: e.target.checked }
+                      })}
+                      className="rounded border-gray-300 text-primary-600 focus:ring-primary-500"
+                    />
+                    <span className="ml-2 text-sm text-gray-700">単語全体で一致</span>
+                  </label>
+                )}
                 
                 <label className="flex items-center">
                   <input
@@ -408,6 +444,18 @@ function SetupPage() {
                   />
                   <span className="ml-2 text-sm text-gray-700">除外パターン</span>
                 </label>
+                
+                <select
+                  value={rule.pattern.match_target ?? 'Name'}
+                  onChange={(e) => updateRule(index, {
+                    pattern: { ...rule.pattern, match_target: e.target.value as MatchTarget }
+                  })}
+                  className="input w-auto text-sm"
+                >
+                  <option value="Name">フォルダ名で照合</option>
+                  <option value="FullPath">フルパスで照合</option>
+                  <option value="RelativePathFromRoot">ルートからの相対パスで照合</option>
+                </select>
               </div>
             </div>
           ))}
@@ -508,6 +556,42 @@ function SetupPage() {
                   className="rounded border-gray-300 text-primary-600 focus:ring-primary-500"
                 />
                 <span className="ml-2 text-sm text-gray-700">大文字小文字正規化</span>
+              </label>              
+              <label className="flex items-center">
+                <input
+                  type="checkbox"
+                  checked={config.options.normalization.fold_halfwidth_kana ?? true}
+                  onChange={(e) => setConfig(prev => ({
+                    ...prev,
+                    options: {
+                      ...prev.options,
+                      normalization: {
+                        ...prev.options.normalization,
+                        fold_halfwidth_kana: e.target.checked,
+                      },
+                    },
+                  }))}
+                  className="rounded border-gray-300 text-primary-600 focus:ring-primary-500"
+                />
+                <span className="ml-2 text-sm text-gray-700">半角カナを全角に統一</span>
+              </label>              
+              <label className="flex items-center">
+                <input
+                  type="checkbox"
+                  checked={config.options.normalization.fold_hiragana_katakana ?? false}
+                  onChange={(e) => setConfig(prev => ({
+                    ...prev,
+                    options: {
+                      ...prev.options,
+                      normalization: {
+                        ...prev.options.normalization,
+                        fold_hiragana_katakana: e.target.checked,
+                      },
+                    },
+                  }))}
+                  className="rounded border-gray-300 text-primary-600 focus:ring-primary-500"
+                />
+                <span className="ml-2 text-sm text-gray-700">ひらがな・カタカナを同一視</span>
               </label>
             </div>
             
@@ -586,6 +670,27 @@ function SetupPage() {
                   max="16"
                 />
               </div>
+              
+              <div>
+                <label className="block text-sm font-medium text-gray-700 mb-1">
+                  複数のルールに一致したとき
+                </label>
+                <select
+                  value={config.options.tie_break ?? 'Priority'}
+                  onChange={(e) => setConfig(prev => ({
+                    ...prev,
+                    options: {
+                      ...prev.options,
+                      tie_break: e.target.value as TieBreakStrategy,
+                    },
+                  }))}
+                  className="input"
+                >
+                  <option value="Priority">優先度の高いルール</option>
+                  <option value="MostSpecific">最も具体的なルール</option>
+                  <option value="Interactive">結果画面で選ぶ</option>
+                </select>
+              </div>
             </div>
           </div>
         </div>
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use filemover_types::{Conflict, Localize, Message, MovePlan, OpKind, PlanNode, PlanNodeId, Warning};
+
+/// Node orderings and parent links of a plan, so the tree view can page through
+/// huge plans without the whole `MovePlan` being sent to the webview
+#[derive(Debug, Default)]
+pub struct PlanIndex {
+    parents: HashMap<PlanNodeId, PlanNodeId>,
+    by_path: Vec<PlanNodeId>,
+    by_name: Vec<PlanNodeId>,
+    by_size: Vec<PlanNodeId>,
+    by_kind: Vec<PlanNodeId>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlanSortField {
+    #[default]
+    Path,
+    Name,
+    Size,
+    Kind,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlanNodeSort {
+    pub field: PlanSortField,
+    pub descending: bool,
+}
+
+/// Which nodes a page includes; an empty filter matches every node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlanNodeFilter {
+    /// Only these operation kinds (all kinds when empty)
+    pub kinds: Vec<OpKind>,
+    /// Case-insensitive text that the source or destination path must contain
+    pub text: Option<String>,
+    pub conflicts_only: bool,
+    pub warnings_only: bool,
+}
+
+/// A plan node as shown in one row of the tree, with its children reduced to a count
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanNodeRow {
+    pub id: PlanNodeId,
+    pub parent: Option<PlanNodeId>,
+    pub is_dir: bool,
+    pub name_before: String,
+    pub path_before: PathBuf,
+    pub name_after: String,
+    pub path_after: PathBuf,
+    pub kind: OpKind,
+    pub size_bytes: Option<u64>,
+    pub warnings: Vec<Warning>,
+    pub conflicts: Vec<Conflict>,
+    pub rule_id: Option<Uuid>,
+    pub tags: Vec<String>,
+    pub child_count: usize,
+    /// Conflicts, then warnings, as catalog messages the frontend renders in its language
+    pub notes: Vec<Message>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanNodePage {
+    /// Number of nodes matching the filter, across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub nodes: Vec<PlanNodeRow>,
+}
+
+impl PlanIndex {
+    pub fn build(plan: &MovePlan) -> Self {
+        let mut parents = HashMap::new();
+        for node in plan.nodes.values() {
+            for child in &node.children {
+                parents.insert(*child, node.id);
+            }
+        }
+
+        let sorted_by = |key: &dyn Fn(&PlanNode, &PlanNode) -> std::cmp::Ordering| {
+            let mut nodes: Vec<&PlanNode> = plan.nodes.values().collect();
+            // Ties fall back to the path so pages stay stable between requests
+            nodes.sort_by(|a, b| key(a, b).then_with(|| a.path_before.cmp(&b.path_before)));
+            nodes.into_iter().map(|node| node.id).collect::<Vec<_>>()
+        };
+
+        Self {
+            parents,
+            by_path: sorted_by(&|_, _| std::cmp::Ordering::Equal),
+            by_name: sorted_by(&|a, b| a.name_before.to_lowercase().cmp(&b.name_before.to_lowercase())),
+            by_size: sorted_by(&|a, b| a.size_bytes.cmp(&b.size_bytes)),
+            by_kind: sorted_by(&|a, b| kind_rank(a.kind).cmp(&kind_rank(b.kind))),
+        }
+    }
+
+    /// One page of the nodes that match `filter`, in the requested order
+    pub fn page(
+        &self,
+        plan: &MovePlan,
+        filter: &PlanNodeFilter,
+        sort: PlanNodeSort,
+        offset: usize,
+        limit: usize,
+    ) -> PlanNodePage {
+        let ordered = match sort.field {
+            PlanSortField::Path => &self.by_path,
+            PlanSortField::Name => &self.by_name,
+            PlanSortField::Size => &self.by_size,
+            PlanSortField::Kind => &self.by_kind,
+        };
+        let text = filter.text.as_ref()
+            .filter(|text| !text.is_empty())
+            .map(|text| text.to_lowercase());
+
+        let ids: Box<dyn Iterator<Item = &PlanNodeId>> = if sort.descending {
+            Box::new(ordered.iter().rev())
+        } else {
+            Box::new(ordered.iter())
+        };
+        let matching = ids
+            .filter_map(|id| plan.nodes.get(id))
+            .filter(|node| filter.matches(node, text.as_deref()));
+
+        // Rows are only built for the requested page; the rest are just counted
+        let mut total = 0;
+        let mut nodes = Vec::new();
+        for node in matching {
+            if total >= offset && nodes.len() < limit {
+                nodes.push(self.row(node));
+            }
+            total += 1;
+        }
+
+        PlanNodePage { total, offset, nodes }
+    }
+
+    /// Children of a node, or the plan roots when `parent` is None
+    pub fn children(&self, plan: &MovePlan, parent: Option<PlanNodeId>) -> Option<Vec<PlanNodeRow>> {
+        let ids = match parent {
+            Some(id) => &plan.nodes.get(&id)?.children,
+            None => &plan.roots,
+        };
+        Some(ids.iter()
+            .filter_map(|id| plan.nodes.get(id))
+            .map(|node| self.row(node))
+            .collect())
+    }
+
+    fn row(&self, node: &PlanNode) -> PlanNodeRow {
+        PlanNodeRow {
+            id: node.id,
+            parent: self.parents.get(&node.id).copied(),
+            is_dir: node.is_dir,
+            name_before: node.name_before.clone(),
+            path_before: node.path_before.clone(),
+            name_after: node.name_after.clone(),
+            path_after: node.path_after.clone(),
+            kind: node.kind,
+            size_bytes: node.size_bytes,
+            warnings: node.warnings.clone(),
+            conflicts: node.conflicts.clone(),
+            rule_id: node.rule_id,
+            tags: node.tags.clone(),
+            child_count: node.children.len(),
+            notes: node.conflicts.iter().map(Localize::message)
+                .chain(node.warnings.iter().map(Localize::message))
+                .collect(),
+        }
+    }
+}
+
+impl PlanNodeFilter {
+    /// `text` is the lowercased search text, prepared once per page
+    fn matches(&self, node: &PlanNode, text: Option<&str>) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&node.kind) {
+            return false;
+        }
+        if self.conflicts_only && node.conflicts.is_empty() {
+            return false;
+        }
+        if self.warnings_only && node.warnings.is_empty() {
+            return false;
+        }
+        match text {
+            Some(text) => {
+                node.path_before.to_string_lossy().to_lowercase().contains(text)
+                    || node.path_after.to_string_lossy().to_lowercase().contains(text)
+            }
+            None => true,
+        }
+    }
+}
+
+fn kind_rank(kind: OpKind) -> u8 {
+    match kind {
+        OpKind::Move => 0,
+        OpKind::CopyDelete => 1,
+        OpKind::Rename => 2,
+        OpKind::Skip => 3,
+        OpKind::None => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::PlanSummary;
+
+    fn node(path: &str, kind: OpKind, size: u64) -> PlanNode {
+        let path = PathBuf::from(path);
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: name.clone(),
+            path_before: path.clone(),
+            name_after: name,
+            path_after: PathBuf::from("/dest").join(path.file_name().unwrap()),
+            kind,
+            size_bytes: Some(size),
+            warnings: vec![],
+            conflicts: vec![],
+            children: vec![],
+            rule_id: None,
+            tags: vec![],
+        }
+    }
+
+    fn plan() -> MovePlan {
+        let mut parent = node("/data/parent", OpKind::Move, 300);
+        let child_a = node("/data/parent/a", OpKind::Skip, 100);
+        let child_b = node("/data/parent/b", OpKind::Move, 200);
+        parent.children = vec![child_a.id, child_b.id];
+        let other = node("/data/other", OpKind::Rename, 50);
+        MovePlan {
+            roots: vec![parent.id, other.id],
+            nodes: [parent, child_a, child_b, other].into_iter().map(|n| (n.id, n)).collect(),
+            summary: PlanSummary::default(),
+            execution_order: vec![],
+        }
+    }
+
+    #[test]
+    fn test_page_filters_sorts_and_counts() {
+        let plan = plan();
+        let index = PlanIndex::build(&plan);
+
+        let sort = PlanNodeSort { field: PlanSortField::Size, descending: true };
+        let page = index.page(&plan, &PlanNodeFilter::default(), sort, 1, 2);
+        assert_eq!(page.total, 4);
+        let names: Vec<_> = page.nodes.iter().map(|n| n.name_before.as_str()).collect();
+        assert_eq!(names, vec!["b", "a"]);
+
+        let filter = PlanNodeFilter { kinds: vec![OpKind::Move], text: Some("PARENT/".to_string()), ..Default::default() };
+        let page = index.page(&plan, &filter, PlanNodeSort::default(), 0, 10);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.nodes[0].name_before, "b");
+        assert!(page.nodes[0].parent.is_some());
+    }
+
+    #[test]
+    fn test_children_of_roots_and_nodes() {
+        let plan = plan();
+        let index = PlanIndex::build(&plan);
+
+        let roots = index.children(&plan, None).unwrap();
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].child_count, 2);
+
+        let children = index.children(&plan, Some(roots[0].id)).unwrap();
+        assert_eq!(children.len(), 2);
+        assert!(index.children(&plan, Some(PlanNodeId::new())).is_none());
+    }
+}
@@ -0,0 +1,321 @@
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use filemover_types::{JournalFormat, MovePlan};
+use crate::commands::{run_execution, run_scan};
+use crate::error::{GuiResult, GuiError, gui_error};
+use crate::state::{AppState, SessionStatus};
+
+/// File in the config directory that keeps the queue across restarts
+pub const QUEUE_FILE: &str = "gui-queue.json";
+
+/// Finished operations kept for the queue view; older ones are dropped
+const HISTORY_LIMIT: usize = 50;
+
+/// Work the queue runs one at a time, in the background
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum QueuedTask {
+    Scan { roots: Vec<PathBuf> },
+    /// Execute a saved plan file; the journal is named automatically when no path is given
+    Apply { plan_path: PathBuf, journal_path: Option<PathBuf> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueueStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedOperation {
+    pub id: Uuid,
+    pub task: QueuedTask,
+    pub status: QueueStatus,
+    /// Scan or execution session, once the operation has started
+    pub session_id: Option<Uuid>,
+    pub enqueued: DateTime<Utc>,
+    pub finished: Option<DateTime<Utc>>,
+    /// Journal written by an apply
+    pub report_path: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OperationQueue {
+    /// No new operation starts while paused; the running one finishes
+    pub paused: bool,
+    pub operations: Vec<QueuedOperation>,
+    /// Report of the most recently finished operation that wrote one
+    pub last_report: Option<PathBuf>,
+    /// Where the queue is saved; an in-memory queue (as in tests) has none
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl OperationQueue {
+    /// Read the saved queue, or start an empty one when there is none
+    pub fn load(path: PathBuf) -> Self {
+        let mut queue = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str::<OperationQueue>(&content).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable queue file {}: {}", path.display(), e);
+                OperationQueue::default()
+            }),
+            Err(_) => OperationQueue::default(),
+        };
+        // Operations that were running when the app closed start again from the beginning
+        for operation in &mut queue.operations {
+            if operation.status == QueueStatus::Running {
+                operation.status = QueueStatus::Pending;
+                operation.session_id = None;
+            }
+        }
+        queue.path = Some(path);
+        queue
+    }
+
+    /// Failures are logged; the queue keeps working in memory
+    pub fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(self)
+            .map_err(|e| e.to_string())
+            .and_then(|json| write_file(path, &json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!("Failed to save the operation queue to {}: {}", path.display(), e);
+        }
+    }
+
+    pub fn enqueue(&mut self, task: QueuedTask) -> Uuid {
+        let id = Uuid::new_v4();
+        self.operations.push(QueuedOperation {
+            id,
+            task,
+            status: QueueStatus::Pending,
+            session_id: None,
+            enqueued: Utc::now(),
+            finished: None,
+            report_path: None,
+            error: None,
+        });
+        self.save();
+        id
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.operations.iter().filter(|op| op.status == QueueStatus::Pending).count()
+    }
+
+    pub fn running(&self) -> Option<&QueuedOperation> {
+        self.operations.iter().find(|op| op.status == QueueStatus::Running)
+    }
+
+    /// Remove an operation that is not running; returns false when there is nothing to remove
+    pub fn remove(&mut self, id: Uuid) -> bool {
+        let before = self.operations.len();
+        self.operations.retain(|op| op.id != id || op.status == QueueStatus::Running);
+        let removed = self.operations.len() != before;
+        if removed {
+            self.save();
+        }
+        removed
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.save();
+    }
+
+    /// Mark the next pending operation as running, unless paused or one is already running
+    fn start_next(&mut self) -> Option<(Uuid, QueuedTask)> {
+        if self.paused || self.running().is_some() {
+            return None;
+        }
+        let operation = self.operations.iter_mut().find(|op| op.status == QueueStatus::Pending)?;
+        operation.status = QueueStatus::Running;
+        let next = (operation.id, operation.task.clone());
+        self.save();
+        Some(next)
+    }
+
+    fn update(&mut self, id: Uuid, updater: impl FnOnce(&mut QueuedOperation)) {
+        if let Some(operation) = self.operations.iter_mut().find(|op| op.id == id) {
+            updater(operation);
+            self.save();
+        }
+    }
+
+    fn finish(&mut self, id: Uuid, status: QueueStatus, report_path: Option<PathBuf>, error: Option<String>) {
+        if report_path.is_some() {
+            self.last_report = report_path.clone();
+        }
+        self.update(id, |operation| {
+            operation.status = status;
+            operation.finished = Some(Utc::now());
+            operation.report_path = report_path;
+            operation.error = error;
+        });
+
+        // Keep only the most recent finished operations
+        let finished = self.operations.iter()
+            .filter(|op| !matches!(op.status, QueueStatus::Pending | QueueStatus::Running))
+            .count();
+        let mut excess = finished.saturating_sub(HISTORY_LIMIT);
+        if excess > 0 {
+            self.operations.retain(|op| {
+                let drop = excess > 0 && !matches!(op.status, QueueStatus::Pending | QueueStatus::Running);
+                if drop {
+                    excess -= 1;
+                }
+                !drop
+            });
+            self.save();
+        }
+    }
+}
+
+fn write_file(path: &Path, content: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)
+}
+
+/// Run the next queued operation to completion; returns false when nothing was started
+pub async fn run_next(state: &AppState) -> bool {
+    let next = match state.queue.lock() {
+        Ok(mut queue) => queue.start_next(),
+        Err(_) => None,
+    };
+    let Some((id, task)) = next else {
+        return false;
+    };
+
+    info!("Starting queued operation {}", id);
+    let outcome = match task {
+        QueuedTask::Scan { roots } => run_queued_scan(state, id, roots).await,
+        QueuedTask::Apply { plan_path, journal_path } => run_queued_apply(state, id, &plan_path, journal_path).await,
+    };
+    let (status, report_path, error) = match outcome {
+        Ok((status, report_path, error)) => (status, report_path, error),
+        Err(e) => (QueueStatus::Failed, None, Some(e.to_string())),
+    };
+
+    info!("Queued operation {} finished: {:?}", id, status);
+    if let Ok(mut queue) = state.queue.lock() {
+        queue.finish(id, status, report_path, error);
+    }
+    true
+}
+
+type QueuedOutcome = (QueueStatus, Option<PathBuf>, Option<String>);
+
+async fn run_queued_scan(state: &AppState, id: Uuid, roots: Vec<PathBuf>) -> GuiResult<QueuedOutcome> {
+    let session_id = state.create_scan_session(roots.clone());
+    state.update_scan_session(session_id, |session| {
+        session.status = SessionStatus::Running;
+    });
+    set_session(state, id, session_id);
+
+    let cancel_token = state.register_cancel_token(session_id);
+    let state_clone = state.clone();
+    tokio::task::spawn_blocking(move || run_scan(roots, session_id, &state_clone, &cancel_token))
+        .await
+        .map_err(|e| gui_error!(scan, format!("Queued scan stopped unexpectedly: {}", e)))?;
+
+    let session = state.get_scan_session(session_id)
+        .ok_or_else(|| gui_error!(session_not_found, session_id.to_string()))?;
+    Ok((queue_status(&session.status), None, session.error))
+}
+
+async fn run_queued_apply(
+    state: &AppState,
+    id: Uuid,
+    plan_path: &Path,
+    journal_path: Option<PathBuf>,
+) -> GuiResult<QueuedOutcome> {
+    let content = std::fs::read_to_string(plan_path)
+        .map_err(|e| gui_error!(execution, format!("Failed to read plan {}: {}", plan_path.display(), e)))?;
+    let plan: MovePlan = filemover_types::from_versioned_json(&content)
+        .map_err(|e| gui_error!(execution, format!("Failed to parse plan {}: {}", plan_path.display(), e)))?;
+
+    let plan_id = state.create_plan_session(None);
+    state.update_plan_session(plan_id, |session| {
+        session.status = SessionStatus::Completed;
+        session.plan = Some(plan);
+    });
+
+    let journal_file = journal_path.unwrap_or_else(|| {
+        state.journal_dir().join(filemover_journal::journal_file_name(JournalFormat::Jsonl, Utc::now()))
+    });
+
+    let execution_id = state.create_execution_session(plan_id);
+    set_session(state, id, execution_id);
+    let cancel_token = state.register_cancel_token(execution_id);
+    run_execution(state, plan_id, execution_id, journal_file.clone(), cancel_token).await;
+
+    let session = state.get_execution_session(execution_id)
+        .ok_or_else(|| gui_error!(session_not_found, execution_id.to_string()))?;
+    let report_path = journal_file.exists().then_some(journal_file);
+    Ok((queue_status(&session.status), report_path, session.error))
+}
+
+fn set_session(state: &AppState, id: Uuid, session_id: Uuid) {
+    if let Ok(mut queue) = state.queue.lock() {
+        queue.update(id, |operation| operation.session_id = Some(session_id));
+    }
+}
+
+fn queue_status(status: &SessionStatus) -> QueueStatus {
+    match status {
+        SessionStatus::Completed => QueueStatus::Completed,
+        SessionStatus::Cancelled => QueueStatus::Cancelled,
+        _ => QueueStatus::Failed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_run_next_runs_pending_scan() {
+        let state = AppState::new();
+        *state.queue.lock().unwrap() = OperationQueue::default();
+        let root = TempDir::new().unwrap();
+        let id = state.queue.lock().unwrap().enqueue(QueuedTask::Scan { roots: vec![root.path().to_path_buf()] });
+
+        // Paused queues do not start anything
+        state.queue.lock().unwrap().set_paused(true);
+        assert!(!run_next(&state).await);
+
+        state.queue.lock().unwrap().set_paused(false);
+        assert!(run_next(&state).await);
+        let queue = state.queue.lock().unwrap();
+        let operation = queue.operations.iter().find(|op| op.id == id).unwrap();
+        assert_eq!(operation.status, QueueStatus::Completed);
+        assert!(operation.session_id.is_some());
+        assert_eq!(queue.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_load_requeues_interrupted_operations() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(QUEUE_FILE);
+        let mut queue = OperationQueue::load(path.clone());
+        queue.enqueue(QueuedTask::Scan { roots: vec![PathBuf::from("/data")] });
+        queue.start_next();
+
+        let reloaded = OperationQueue::load(path);
+        assert_eq!(reloaded.operations.len(), 1);
+        assert_eq!(reloaded.operations[0].status, QueueStatus::Pending);
+    }
+}
@@ -146,7 +146,7 @@ export class FileScanner {
     
     switch (pattern.kind) {
       case 'Contains':
-        return testText.includes(testValue) !== pattern.is_exclude;
+        return this.matchContains(testText, testValue, pattern.whole_word ?? false) !== pattern.is_exclude;
       case 'Glob':
         return this.matchGlob(testText, testValue) !== pattern.is_exclude;
       case 'Regex':
@@ -161,6 +161,19 @@ export class FileScanner {
     }
   }
 
+  private matchContains(text: string, value: string, wholeWord: boolean): boolean {
+    if (!wholeWord) {
+      return text.includes(value);
+    }
+    const isWordChar = (c: string | undefined) => c !== undefined && /[\p{L}\p{N}]/u.test(c);
+    for (let start = text.indexOf(value); start !== -1; start = text.indexOf(value, start + 1)) {
+      if (!isWordChar(text[start - 1]) && !isWordChar(text[start + value.length])) {
+        return true;
+      }
+    }
+    return false;
+  }
+
   private matchGlob(text: string, pattern: string): boolean {
     const regex = pattern
       .replace(/[.+^${}()|[\]\\]/g, '\\$&')
@@ -0,0 +1,13 @@
+import { invoke } from '@tauri-apps/api/tauri';
+import { Locale, Message, MessageCatalog } from '../types';
+
+export function loadMessageCatalog(locale?: Locale): Promise<MessageCatalog> {
+  return invoke<MessageCatalog>('get_message_catalog', { locale });
+}
+
+// Placeholders without an arg are left as they are, like the backend does
+export function renderMessage(catalog: MessageCatalog, message: Message): string {
+  const template = catalog.messages[message.id] ?? message.id;
+  const args = new Map(message.args);
+  return template.replace(/\{([a-z_]+)\}/g, (placeholder, name: string) => args.get(name) ?? placeholder);
+}
@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use filemover_types::Locale;
+
+/// File in the config directory with the GUI's own preferences (profiles stay shared with the CLI)
+pub const SETTINGS_FILE: &str = "gui-settings.json";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Follow the operating system
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub theme: Theme,
+    /// Overrides the profile's `locale`; the system language when neither is set
+    pub language: Option<Locale>,
+    /// Profile selected when the app starts
+    pub default_profile: String,
+    /// Ask before a plan is executed
+    pub confirm_before_apply: bool,
+    /// Where the GUI writes and lists journals; the profile's `journal_dir` when unset
+    pub journal_dir: Option<PathBuf>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::System,
+            language: None,
+            default_profile: "default".to_string(),
+            confirm_before_apply: true,
+            journal_dir: None,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Read the saved settings, or the defaults when there are none
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable settings file {}: {}", path.display(), e);
+                AppSettings::default()
+            }),
+            Err(_) => AppSettings::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_settings_roundtrip_and_defaults() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        assert_eq!(AppSettings::load(&path), AppSettings::default());
+
+        let settings = AppSettings {
+            theme: Theme::Dark,
+            language: Some(Locale::Ja),
+            default_profile: "work".to_string(),
+            confirm_before_apply: false,
+            journal_dir: Some(PathBuf::from("/journals")),
+        };
+        settings.save(&path).unwrap();
+        assert_eq!(AppSettings::load(&path), settings);
+
+        // Fields added later fall back to their defaults
+        std::fs::write(&path, r#"{"theme":"Light"}"#).unwrap();
+        let partial = AppSettings::load(&path);
+        assert_eq!(partial.theme, Theme::Light);
+        assert!(partial.confirm_before_apply);
+    }
+}
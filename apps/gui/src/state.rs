@@ -1,19 +1,25 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 
-use filemover_types::{Config, FolderHit, MovePlan, Progress};
+use filemover_types::{Config, FolderHit, MovePlan, PlanNodeId, Progress, ScanProgress, CancellationToken};
+use filemover_matcher::EngineCache;
 use filemover_scanner::FolderScanner;
 use filemover_planner::MovePlanner;
+use crate::events::EventEmitter;
+use crate::notifications::Notifier;
+use crate::queue::{OperationQueue, QUEUE_FILE};
+use crate::settings::{AppSettings, SETTINGS_FILE};
+use crate::plan_index::PlanIndex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanSession {
     pub id: Uuid,
     pub roots: Vec<PathBuf>,
     pub status: SessionStatus,
-    pub progress: Option<Progress>,
+    pub progress: Option<ScanProgress>,
     pub results: Option<Vec<FolderHit>>,
     pub error: Option<String>,
 }
@@ -25,6 +31,9 @@ pub struct PlanSession {
     pub status: SessionStatus,
     pub plan: Option<MovePlan>,
     pub error: Option<String>,
+    /// Built on the first page request and dropped whenever the plan changes
+    #[serde(skip)]
+    pub index: Option<Arc<PlanIndex>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +44,39 @@ pub struct ExecutionSession {
     pub progress: Option<Progress>,
     pub journal_path: Option<PathBuf>,
     pub error: Option<String>,
+    /// Most recent per-node outcomes, oldest first (at most `NODE_RESULT_LIMIT`)
+    #[serde(default)]
+    pub node_results: VecDeque<NodeResult>,
+    /// Outcomes recorded so far, including those that no longer fit in `node_results`
+    #[serde(default)]
+    pub node_results_total: u64,
+}
+
+/// Per-node outcomes kept in an execution session; the frontend also receives each one as an event
+pub const NODE_RESULT_LIMIT: usize = 1000;
+
+impl ExecutionSession {
+    pub fn record_node_result(&mut self, result: NodeResult) {
+        if self.node_results.len() == NODE_RESULT_LIMIT {
+            self.node_results.pop_front();
+        }
+        self.node_results.push_back(result);
+        self.node_results_total += 1;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeResult {
+    pub node_id: PlanNodeId,
+    pub status: NodeStatus,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -46,6 +88,8 @@ pub enum SessionStatus {
     Cancelled,
 }
 
+/// Cheap to clone: every clone shares the same sessions, so background tasks can hold one
+#[derive(Clone)]
 pub struct AppState {
     pub config: Arc<Mutex<Config>>,
     pub current_profile: Arc<Mutex<String>>,
@@ -54,21 +98,66 @@ pub struct AppState {
     pub execution_sessions: Arc<Mutex<HashMap<Uuid, ExecutionSession>>>,
     pub scanner: Arc<Mutex<Option<FolderScanner>>>,
     pub planner: Arc<Mutex<MovePlanner>>,
+    pub cancel_tokens: Arc<Mutex<HashMap<Uuid, CancellationToken>>>,
+    /// Compiled rule sets, reused across scans and explanations while the rules are unchanged
+    pub engine_cache: Arc<EngineCache>,
+    /// Directory holding the profiles, shared with the CLI
+    pub config_dir: PathBuf,
+    /// Live progress for the frontend (`scan://progress`, `execute://progress`)
+    pub events: EventEmitter,
+    /// Desktop notifications for finished tasks (see `Config::notifications`)
+    pub notifier: Notifier,
+    /// Scans and applies waiting to run in the background, saved in the config directory
+    pub queue: Arc<Mutex<OperationQueue>>,
+    /// GUI preferences, saved in the config directory
+    pub settings: Arc<Mutex<AppSettings>>,
 }
 
 impl AppState {
     pub fn new() -> Self {
+        let config_dir = default_config_dir();
+        let settings = AppSettings::load(&config_dir.join(SETTINGS_FILE));
         Self {
             config: Arc::new(Mutex::new(Config::default())),
-            current_profile: Arc::new(Mutex::new("default".to_string())),
+            current_profile: Arc::new(Mutex::new(settings.default_profile.clone())),
             scan_sessions: Arc::new(Mutex::new(HashMap::new())),
             plan_sessions: Arc::new(Mutex::new(HashMap::new())),
             execution_sessions: Arc::new(Mutex::new(HashMap::new())),
             scanner: Arc::new(Mutex::new(None)),
             planner: Arc::new(Mutex::new(MovePlanner::new())),
+            cancel_tokens: Arc::new(Mutex::new(HashMap::new())),
+            engine_cache: Arc::new(EngineCache::default()),
+            queue: Arc::new(Mutex::new(OperationQueue::load(config_dir.join(QUEUE_FILE)))),
+            settings: Arc::new(Mutex::new(settings)),
+            config_dir,
+            events: EventEmitter::default(),
+            notifier: Notifier::default(),
+        }
+    }
+
+    /// Profile file in the same layout as the CLI (`config.json` for the default profile)
+    pub fn profile_path(&self, profile: &str) -> PathBuf {
+        if profile == "default" {
+            self.config_dir.join("config.json")
+        } else {
+            self.config_dir.join(format!("{}.json", profile))
         }
     }
 
+    /// Journals go to the GUI setting, else the profile's `journal_dir`, else the config directory
+    pub fn journal_dir(&self) -> PathBuf {
+        let from_settings = self.settings.lock().ok().and_then(|settings| settings.journal_dir.clone());
+        from_settings
+            .or_else(|| self.config.lock().ok().and_then(|config| config.journal_dir.clone()))
+            .unwrap_or_else(|| self.config_dir.clone())
+    }
+
+    pub fn current_profile_name(&self) -> String {
+        self.current_profile.lock()
+            .map(|p| p.clone())
+            .unwrap_or_else(|_| "default".to_string())
+    }
+
     pub fn create_scan_session(&self, roots: Vec<PathBuf>) -> Uuid {
         let id = Uuid::new_v4();
         let session = ScanSession {
@@ -95,6 +184,7 @@ impl AppState {
             status: SessionStatus::Created,
             plan: None,
             error: None,
+            index: None,
         };
 
         if let Ok(mut sessions) = self.plan_sessions.lock() {
@@ -113,6 +203,8 @@ impl AppState {
             progress: None,
             journal_path: None,
             error: None,
+            node_results: VecDeque::new(),
+            node_results_total: 0,
         };
 
         if let Ok(mut sessions) = self.execution_sessions.lock() {
@@ -179,6 +271,30 @@ impl AppState {
         }
     }
 
+    pub fn register_cancel_token(&self, session_id: Uuid) -> CancellationToken {
+        let token = CancellationToken::new();
+        if let Ok(mut tokens) = self.cancel_tokens.lock() {
+            tokens.insert(session_id, token.clone());
+        }
+        token
+    }
+
+    pub fn cancel_session(&self, session_id: Uuid) -> bool {
+        if let Ok(tokens) = self.cancel_tokens.lock() {
+            if let Some(token) = tokens.get(&session_id) {
+                token.cancel();
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn remove_cancel_token(&self, session_id: Uuid) {
+        if let Ok(mut tokens) = self.cancel_tokens.lock() {
+            tokens.remove(&session_id);
+        }
+    }
+
     pub fn cleanup_old_sessions(&self) {
         // Clean up sessions older than 1 hour
         // Implementation would check timestamps and remove old sessions
@@ -186,6 +302,19 @@ impl AppState {
     }
 }
 
+/// `%APPDATA%\\FileMover` on Windows, `~/.config/filemover` elsewhere (as in the CLI)
+fn default_config_dir() -> PathBuf {
+    if cfg!(windows) {
+        let app_data = std::env::var("APPDATA")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .unwrap_or_default();
+        PathBuf::from(app_data).join("FileMover")
+    } else {
+        let home = std::env::var("HOME").unwrap_or_default();
+        PathBuf::from(home).join(".config").join("filemover")
+    }
+}
+
 impl Default for AppState {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,110 @@
+use std::time::Duration;
+use tauri::{
+    AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem,
+};
+use tracing::warn;
+
+use crate::queue::run_next;
+use crate::state::AppState;
+
+const SHOW: &str = "show";
+const QUEUE_STATUS: &str = "queue-status";
+const PAUSE: &str = "pause";
+const OPEN_REPORT: &str = "open-report";
+const QUIT: &str = "quit";
+
+/// How often the worker looks for queued operations and refreshes the tray menu
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub fn system_tray() -> SystemTray {
+    let menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new(SHOW, "Show FileMover"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new(QUEUE_STATUS, "Queue: idle").disabled())
+        .add_item(CustomMenuItem::new(PAUSE, "Pause all"))
+        .add_item(CustomMenuItem::new(OPEN_REPORT, "Open last report").disabled())
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new(QUIT, "Quit"));
+    SystemTray::new().with_menu(menu)
+}
+
+pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent) {
+    match event {
+        SystemTrayEvent::LeftClick { .. } => show_window(app),
+        SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
+            SHOW => show_window(app),
+            PAUSE => {
+                let state = app.state::<AppState>();
+                if let Ok(mut queue) = state.queue.lock() {
+                    let paused = !queue.paused;
+                    queue.set_paused(paused);
+                }
+                refresh_tray(app);
+            }
+            OPEN_REPORT => open_last_report(app),
+            QUIT => app.exit(0),
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+fn show_window(app: &AppHandle) {
+    if let Some(window) = app.get_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn open_last_report(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let report = state.queue.lock().ok().and_then(|queue| queue.last_report.clone());
+    if let Some(report) = report {
+        if let Err(e) = tauri::api::shell::open(&app.shell_scope(), report.to_string_lossy(), None) {
+            warn!("Failed to open {}: {}", report.display(), e);
+        }
+    }
+}
+
+/// Keep the tray menu in step with the queue
+fn refresh_tray(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let Ok(queue) = state.queue.lock() else {
+        return;
+    };
+    let pending = queue.pending_count();
+    let status = match (queue.running().is_some(), pending, queue.paused) {
+        (_, _, true) => format!("Queue: paused, {} waiting", pending),
+        (true, 0, false) => "Queue: running".to_string(),
+        (true, _, false) => format!("Queue: running, {} waiting", pending),
+        (false, 0, false) => "Queue: idle".to_string(),
+        (false, _, false) => format!("Queue: {} waiting", pending),
+    };
+
+    let tray = app.tray_handle();
+    let _ = tray.get_item(QUEUE_STATUS).set_title(status);
+    let _ = tray.get_item(PAUSE).set_title(if queue.paused { "Resume all" } else { "Pause all" });
+    let _ = tray.get_item(OPEN_REPORT).set_enabled(queue.last_report.is_some());
+}
+
+/// Run queued operations one after another for as long as the app runs, even while the window is hidden
+pub fn spawn_queue_worker(app: AppHandle) {
+    let worker = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = worker.state::<AppState>();
+            if !run_next(&state).await {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    });
+
+    // Refreshed separately, so the menu shows progress while a long operation runs
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh_tray(&app);
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
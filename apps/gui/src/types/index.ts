@@ -2,19 +2,108 @@
 export interface Config {
   roots: string[];
   rules: Rule[];
+  exclude_patterns?: PatternSpec[];
   options: ScanOptions;
   profiles: string[];
+  rename_suffix?: RenameSuffix;
+  // Desktop notifications when scans, moves and undos finish
+  notifications?: { enabled: boolean };
+  // Message language; the system language when unset
+  locale?: Locale;
 }
 
+export type Locale = 'en' | 'ja';
+
+// GUI preferences (get_app_settings / save_app_settings), kept in the config directory
+export interface AppSettings {
+  theme: 'System' | 'Light' | 'Dark';
+  // Overrides the profile's locale
+  language?: Locale;
+  default_profile: string;
+  confirm_before_apply: boolean;
+  // Overrides the profile's journal_dir for the GUI
+  journal_dir?: string;
+}
+
+// Catalog message: the template is `MessageCatalog.messages[id]`, with `{name}` filled from args
+export interface Message {
+  id: string;
+  args: [string, string][];
+}
+
+export interface MessageCatalog {
+  locale: Locale;
+  messages: Record<string, string>;
+}
+
+// Payload of `task-finished`, sent when a scan, move or undo ends
+export interface TaskFinishedEvent {
+  kind: 'Scan' | 'Apply' | 'Undo';
+  session_id?: string;
+  succeeded: boolean;
+  title: string;
+  body: string;
+  // Journal that can be opened from the banner
+  journal_path?: string;
+}
+
+// Payload of the `config-reloaded` event sent after the active profile changed on disk
+export interface ConfigReloadedEvent {
+  profile: string;
+  config: Config | null;
+  error: string | null;
+}
+
+// Suffix added to auto-renamed destinations (n counts from 1)
+export type RenameSuffix =
+  | { style: 'numeric'; width?: number }
+  | { style: 'date' }
+  | { style: 'copy' }
+  | { style: 'template'; template: string };
+
 export interface Rule {
   id: string;
   enabled: boolean;
   pattern: PatternSpec;
   dest_root: string;
   template: string;
-  policy: ConflictPolicy;
+  // Falls back to the plan's default_conflict_policy when unset
+  policy?: ConflictPolicy;
   label?: string;
   priority: number;
+  conditions?: RuleConditions;
+  // Must also match, in addition to pattern
+  expression?: PatternExpr;
+  // Overrides options.normalization for this rule only
+  normalization?: NormalizationOptions;
+  // Formats such as "%Y-%m" used to read {src_yyyy} etc. from the name
+  date_formats?: string[];
+  // Rules with tags and an empty dest_root only tag folders
+  tags?: string[];
+}
+
+export type PatternExpr =
+  | { Pattern: PatternSpec }
+  | { All: PatternExpr[] }
+  | { Any: PatternExpr[] }
+  | { Not: PatternExpr };
+
+// All set conditions must hold; last_modified_before is an RFC 3339 timestamp
+export interface RuleConditions {
+  min_size_bytes?: number;
+  max_size_bytes?: number;
+  last_modified_before?: string;
+  older_than_days?: number;
+  min_item_count?: number;
+  max_item_count?: number;
+  contains_files?: ContainsFilesCondition;
+}
+
+// File-name glob; max_depth 1 only looks at direct children
+export interface ContainsFilesCondition {
+  pattern: string;
+  min_count?: number;
+  max_depth?: number;
 }
 
 export interface PatternSpec {
@@ -22,10 +111,16 @@ export interface PatternSpec {
   value: string;
   is_exclude: boolean;
   case_insensitive: boolean;
+  match_target?: MatchTarget;
+  // Fuzzy only; defaults to one edit per four characters
+  max_edits?: number;
+  // Contains only; the match must not be part of a longer word
+  whole_word?: boolean;
 }
 
-export type PatternKind = 'Glob' | 'Regex' | 'Contains';
-export type ConflictPolicy = 'AutoRename' | 'Skip' | 'Overwrite';
+export type PatternKind = 'Glob' | 'Regex' | 'Contains' | 'Fuzzy' | 'Extension';
+export type MatchTarget = 'Name' | 'FullPath' | 'RelativePathFromRoot';
+export type ConflictPolicy = 'AutoRename' | 'Skip' | 'Overwrite' | 'Merge';
 
 export interface ScanOptions {
   normalization: NormalizationOptions;
@@ -33,31 +128,106 @@ export interface ScanOptions {
   system_protections: boolean;
   max_depth?: number;
   excluded_paths: string[];
+  excluded_patterns?: string[];
   parallel_threads?: number;
+  parallel_subtrees?: boolean;
+  include_files?: boolean;
+  calculate_sizes?: boolean;
+  size_cache?: string;
+  backend?: ScanBackend;
+  include_hidden?: boolean;
+  include_system?: boolean;
+  traversal?: TraversalOrder;
+  result_order?: ResultOrder;
+  filters?: ScanFilters;
+  network_timeout_secs?: number;
+  network_credentials?: NetworkCredential[];
+  collect_all_matches?: boolean;
+  tie_break?: TieBreakStrategy;
+}
+
+export interface NetworkCredential {
+  root: string;
+  username: string;
+  password_env?: string;
 }
 
+export interface ScanFilters {
+  min_size?: number;
+  max_size?: number;
+  older_than_days?: number;
+  newer_than_days?: number;
+  hidden?: boolean;
+  system?: boolean;
+  readonly?: boolean;
+}
+
+export type ScanBackend = 'Standard' | 'Mft';
+export type TraversalOrder = 'DepthFirst' | 'BreadthFirst';
+export type ResultOrder = 'Discovery' | 'Path' | 'Size' | 'Depth';
+export type TieBreakStrategy = 'Priority' | 'MostSpecific' | 'Interactive';
+
 export interface NormalizationOptions {
   normalize_unicode: boolean;
   normalize_width: boolean;
   strip_diacritics: boolean;
   normalize_case: boolean;
+  fold_halfwidth_kana?: boolean;
+  fold_hiragana_katakana?: boolean;
+}
+
+export type RuleOutcome =
+  | 'Matched'
+  | 'ConditionsNotMet'
+  | 'NotMatched'
+  | 'SkippedByExclusion'
+  | 'SkippedByPriority'
+  | 'Disabled';
+
+export interface ComparedText {
+  target: MatchTarget;
+  original: string;
+  normalized: string;
+}
+
+export interface RuleEvaluationInfo {
+  rule_id: string;
+  label?: string;
+  priority: number;
+  pattern: PatternSpec;
+  outcome: RuleOutcome;
+  compared?: ComparedText;
+}
+
+export interface HitExplanation {
+  path: string;
+  evaluations: RuleEvaluationInfo[];
+  matched_rule?: string;
+  excluded_by?: string;
+  excluded_by_pattern?: PatternSpec;
 }
 
 export interface FolderHit {
   path: string;
   name: string;
   matched_rule?: string;
+  matched_rules?: string[];
+  tags?: string[];
   dest_preview?: string;
   warnings: Warning[];
   size_bytes?: number;
+  item_count?: number;
+  is_dir?: boolean;
 }
 
-export type Warning = 'LongPath' | 'AclDiffers' | 'Offline' | 'AccessDenied' | 'Junction' | 'CrossVolume';
+export type Warning = 'LongPath' | 'AclDiffers' | 'Offline' | 'AccessDenied' | 'Junction' | 'CrossVolume' | 'NetworkSlow' | 'Unreachable' | 'Hidden' | 'System' | 'AclNotPreserved' | 'TimestampsDegraded' | 'StreamsNotPreserved' | 'CompressionNotPreserved';
 
 export interface MovePlan {
   roots: string[];
   nodes: Record<string, PlanNode>;
   summary: PlanSummary;
+  // Node IDs in the order they are executed (missing in older plan files)
+  execution_order?: string[];
 }
 
 export interface PlanNode {
@@ -73,12 +243,13 @@ export interface PlanNode {
   conflicts: Conflict[];
   children: string[];
   rule_id?: string;
+  tags?: string[];
 }
 
 export type OpKind = 'Move' | 'CopyDelete' | 'Rename' | 'Skip' | 'None';
 
 export interface Conflict {
-  type: 'NameExists' | 'CycleDetected' | 'DestInsideSource' | 'NoSpace' | 'Permission';
+  type: 'NameExists' | 'CycleDetected' | 'DestInsideSource' | 'NoSpace' | 'Permission' | 'SourceMissing';
   existing_path?: string;
   required?: number;
   available?: number;
@@ -94,18 +265,101 @@ export interface PlanSummary {
   cross_volume: number;
   conflicts: number;
   warnings: number;
+  // Operations that lose metadata on the destination file system (missing in older plan files)
+  preservation?: PreservationSummary;
+}
+
+export interface PreservationSummary {
+  acl_lost: number;
+  timestamps_degraded: number;
+  streams_lost: number;
+  compression_lost: number;
 }
 
 // Frontend-specific types
+export interface ScanProgress {
+  dirs_visited: number;
+  hits_found: number;
+  current_path?: string;
+}
+
+// Paged access to scan hits (get_scan_results_page), also the criteria of filter_scan_results
+export interface ScanResultQuery {
+  text?: string;
+  // Case-insensitive glob matched against the folder name or the whole path
+  path_glob?: string;
+  rule_id?: string;
+  warning?: Warning;
+  warnings_only?: boolean;
+  // Hits of unknown size are left out when either bound is set
+  min_size?: number;
+  max_size?: number;
+}
+
+export interface ScanResultPage {
+  status: SessionStatus;
+  total: number;
+  offset: number;
+  hits: FolderHit[];
+}
+
 export interface ScanSession {
   id: string;
   roots: string[];
   status: SessionStatus;
-  progress?: Progress;
+  progress?: ScanProgress;
   results?: FolderHit[];
   error?: string;
 }
 
+// Paged access to large plans (get_plan_nodes / get_plan_node_children)
+export type PlanSortField = 'Path' | 'Name' | 'Size' | 'Kind';
+
+export interface PlanNodeSort {
+  field?: PlanSortField;
+  descending?: boolean;
+}
+
+export interface PlanNodeFilter {
+  kinds?: OpKind[];
+  text?: string;
+  conflicts_only?: boolean;
+  warnings_only?: boolean;
+}
+
+// A plan node without its children list; expand it with get_plan_node_children
+export interface PlanNodeRow extends Omit<PlanNode, 'children'> {
+  parent?: string;
+  child_count: number;
+  // Conflicts, then warnings, ready to render with the message catalog
+  notes: Message[];
+}
+
+export interface PlanNodePage {
+  total: number;
+  offset: number;
+  nodes: PlanNodeRow[];
+}
+
+// Conflict wizard (list_conflicts / resolve_conflict)
+export type ConflictResolution = { RenameTo: string } | 'Skip' | 'Overwrite' | 'Merge';
+
+export interface PlanSummaryDiff {
+  count_dirs_delta: number;
+  count_files_delta: number;
+  total_bytes_delta?: number;
+  cross_volume_delta: number;
+  conflicts_delta: number;
+  warnings_delta: number;
+}
+
+export interface ValidationDelta {
+  affected_nodes: string[];
+  new_conflicts: Conflict[];
+  resolved_conflicts: Conflict[];
+  summary_diff: PlanSummaryDiff;
+}
+
 export interface PlanSession {
   id: string;
   scan_id?: string;
@@ -121,10 +375,51 @@ export interface ExecutionSession {
   progress?: Progress;
   journal_path?: string;
   error?: string;
+  // Latest results only; node_results_total counts every node recorded
+  node_results: NodeResult[];
+  node_results_total: number;
+}
+
+export type NodeStatus = 'Succeeded' | 'Failed' | 'Skipped';
+
+export interface NodeResult {
+  node_id: string;
+  status: NodeStatus;
+  error?: string;
 }
 
 export type SessionStatus = 'Created' | 'Running' | 'Completed' | 'Failed' | 'Cancelled';
 
+// Payload of `scan://progress`, sent while a scan runs and once more with the final status
+export interface ScanProgressEvent {
+  session_id: string;
+  status: SessionStatus;
+  dirs_visited: number;
+  hits_found: number;
+  bytes_found: number;
+  current_path?: string;
+  dirs_per_second: number;
+  elapsed_ms: number;
+}
+
+// Payload of `execute://progress`, sent after every operation and once more with the final status
+export interface ExecutionProgressEvent {
+  session_id: string;
+  status: SessionStatus;
+  current_item?: string;
+  completed_ops: number;
+  total_ops: number;
+  bytes_processed: number;
+  total_bytes?: number;
+  bytes_per_second?: number;
+  eta_seconds?: number;
+}
+
+// Payload of `execute://node`, sent as each plan node finishes
+export interface NodeStatusEvent extends NodeResult {
+  session_id: string;
+}
+
 export interface Progress {
   current_item?: string;
   completed_ops: number;
@@ -135,11 +430,33 @@ export interface Progress {
   eta?: number;
 }
 
+export type ThroughputSource = 'Default' | 'Measured' | 'Recorded';
+
+export interface ThroughputModel {
+  copy_bytes_per_sec: number;
+  per_item_secs: number;
+  source: ThroughputSource;
+}
+
+export interface OperationEstimate {
+  kind: OpKind;
+  count: number;
+  bytes: number;
+  copied_bytes: number;
+  estimated_secs: number;
+}
+
 export interface SimulationReport {
   success_estimate: number;
   conflicts_remaining: number;
   skipped_count: number;
-  estimated_duration: number;
+  estimated_duration_secs: number;
+  total_bytes: number;
+  copied_bytes: number;
+  cross_volume_ratio: number;
+  unknown_size_count: number;
+  throughput: ThroughputModel;
+  by_operation: OperationEstimate[];
 }
 
 export interface PathValidation {
@@ -166,7 +483,9 @@ export interface DriveInfo {
   path: string;
   label: string;
   drive_type: DriveType;
+  // Bytes; missing when the drive cannot be queried (e.g. a disconnected share)
   total_space?: number;
+  // Space available to the current user
   free_space?: number;
 }
 
@@ -188,4 +507,86 @@ export interface JournalValidation {
   skipped_entries: number;
   undoable_entries: number;
   issues: string[];
-}
\ No newline at end of file
+}
+
+// One run on the History page (list_journals / get_journal_summary)
+export interface JournalRunSummary {
+  path: string;
+  format: 'jsonl' | 'sqlite';
+  run_date: string;
+  // Missing when the run was interrupted
+  completed?: string;
+  total_operations: number;
+  successful_operations: number;
+  failed_operations: number;
+  skipped_operations: number;
+  undoable_operations: number;
+  plan_hash?: string;
+  undo_journal?: string;
+  problems: string[];
+}
+
+// Rule editor feedback (validate_rule / preview_rule_matches)
+export type RuleField = 'Pattern' | 'DateFormats' | 'Template' | 'DestRoot' | 'Enabled';
+
+export interface RuleIssue {
+  field: RuleField;
+  severity: 'Error' | 'Warning';
+  message: string;
+}
+
+export interface RuleValidation {
+  valid: boolean;
+  issues: RuleIssue[];
+  template_variables: string[];
+}
+
+export interface RuleMatchPreview {
+  checked: number;
+  matched: number;
+  missing: number;
+  matches: FolderHit[];
+}
+
+export interface DestinationSample {
+  source: string;
+  destination?: string;
+  error?: string;
+}
+
+export interface DestinationPreview {
+  template_error?: string;
+  samples: DestinationSample[];
+}
+
+// Background operation queue (get_queue / enqueue_scan / enqueue_apply)
+export type QueuedTask =
+  | { type: 'Scan'; roots: string[] }
+  | { type: 'Apply'; plan_path: string; journal_path?: string };
+
+export type QueueStatus = 'Pending' | 'Running' | 'Completed' | 'Failed' | 'Cancelled';
+
+export interface QueuedOperation {
+  id: string;
+  task: QueuedTask;
+  status: QueueStatus;
+  session_id?: string;
+  enqueued: string;
+  finished?: string;
+  report_path?: string;
+  error?: string;
+}
+
+export interface OperationQueue {
+  paused: boolean;
+  operations: QueuedOperation[];
+  last_report?: string;
+}
+
+// Returned by request_elevation; follow execution_session_id for the helper's progress
+export interface ElevationStarted {
+  execution_session_id: string;
+  operations: number;
+  plan_path: string;
+  journal_path: string;
+}
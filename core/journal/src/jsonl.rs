@@ -0,0 +1,293 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use filemover_types::{
+    FileMoverError, JournalEntry, JournalFormat, JournalProblem, JournalQuery, JournalReadReport,
+    JournalStore, JournalSummary,
+};
+use crate::summary::{check_summary, resumed_summary, SummaryAccumulator};
+
+/// 末尾の完了レコード行
+#[derive(Serialize, Deserialize)]
+struct SummaryLine {
+    summary: JournalSummary,
+}
+
+/// (行番号, UTF-8 として読めた内容またはエラー)
+type RawLine = (usize, Result<String, String>);
+
+enum Line {
+    Entry(JournalEntry),
+    Summary(JournalSummary),
+}
+
+/// 1 行 1 エントリの JSONL ジャーナル
+#[derive(Debug)]
+pub struct JsonlJournal {
+    path: PathBuf,
+    file: File,
+    /// 既存ファイルを開いた場合は None（finish 時に読み直して集計する）
+    accumulator: Option<SummaryAccumulator>,
+    plan_hash: Option<String>,
+}
+
+impl JsonlJournal {
+    pub fn create(path: &Path) -> Result<Self, FileMoverError> {
+        let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        Ok(Self { path: path.to_path_buf(), file, accumulator: Some(SummaryAccumulator::default()), plan_hash: None })
+    }
+
+    pub fn open(path: &Path) -> Result<Self, FileMoverError> {
+        let file = OpenOptions::new().append(true).open(path)?;
+        Ok(Self { path: path.to_path_buf(), file, accumulator: None, plan_hash: None })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 空行を除いた (行番号, 内容) を返す
+    fn read_lines(&self) -> Result<Vec<RawLine>, FileMoverError> {
+        let bytes = std::fs::read(&self.path)?;
+
+        Ok(bytes
+            .split(|&b| b == b'\n')
+            .enumerate()
+            .filter(|(_, line)| !line.iter().all(u8::is_ascii_whitespace))
+            .map(|(i, line)| {
+                let text = String::from_utf8(line.to_vec()).map_err(|e| e.to_string());
+                (i + 1, text)
+            })
+            .collect())
+    }
+}
+
+fn parse_line(line: &str) -> Result<Line, FileMoverError> {
+    if let Ok(summary_line) = serde_json::from_str::<SummaryLine>(line) {
+        return Ok(Line::Summary(summary_line.summary));
+    }
+    JournalEntry::from_json_line(line).map(Line::Entry)
+}
+
+impl JournalStore for JsonlJournal {
+    fn append(&mut self, entry: &JournalEntry) -> Result<(), FileMoverError> {
+        let entry = entry.clone().with_checksum();
+        let line = serde_json::to_string(&entry).map_err(|e| FileMoverError::Journal {
+            message: format!("Failed to serialize journal entry: {}", e),
+        })?;
+        // 途中で中断されても行単位で読めるよう 1 行ずつ書き込む
+        writeln!(self.file, "{}", line)?;
+
+        if let Some(accumulator) = &mut self.accumulator {
+            accumulator.record(&entry);
+        }
+        Ok(())
+    }
+
+    fn link_plan(&mut self, plan_hash: String) {
+        self.plan_hash = Some(plan_hash);
+    }
+
+    fn finish(&mut self) -> Result<JournalSummary, FileMoverError> {
+        let summary = match &self.accumulator {
+            Some(accumulator) => accumulator.summary(),
+            None => resumed_summary(&self.read_checked()?),
+        }
+        .with_plan_hash(self.plan_hash.clone());
+
+        let line = serde_json::to_string(&SummaryLine { summary: summary.clone() })
+            .map_err(|e| FileMoverError::Journal {
+                message: format!("Failed to serialize journal summary: {}", e),
+            })?;
+        writeln!(self.file, "{}", line)?;
+        self.file.sync_all()?;
+
+        Ok(summary)
+    }
+
+    fn read_checked(&self) -> Result<JournalReadReport, FileMoverError> {
+        let mut report = JournalReadReport::default();
+        let mut total_records = 0;
+
+        for (position, text) in self.read_lines()? {
+            let parsed = text
+                .map_err(|message| FileMoverError::Journal { message })
+                .and_then(|line| parse_line(&line));
+
+            match parsed {
+                Ok(Line::Summary(summary)) => report.summary = Some(summary),
+                Ok(Line::Entry(entry)) => {
+                    total_records += 1;
+                    if entry.checksum_matches() == Some(false) {
+                        report.problems.push(JournalProblem::ChecksumMismatch { position });
+                    } else {
+                        report.entries.push(entry);
+                    }
+                }
+                Err(e) => {
+                    total_records += 1;
+                    report.problems.push(JournalProblem::Unreadable {
+                        position,
+                        message: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        check_summary(&mut report, total_records);
+        Ok(report)
+    }
+
+    fn query(&self, query: &JournalQuery) -> Result<Vec<JournalEntry>, FileMoverError> {
+        let mut entries = Vec::new();
+
+        for (position, text) in self.read_lines()? {
+            let line = text.map_err(|message| FileMoverError::Journal {
+                message: format!("line {}: {}", position, message),
+            })?;
+
+            let entry = match parse_line(&line) {
+                Ok(Line::Entry(entry)) => entry,
+                Ok(Line::Summary(_)) => continue,
+                Err(e) => {
+                    return Err(FileMoverError::Journal {
+                        message: format!("line {}: {}", position, e),
+                    });
+                }
+            };
+
+            if query.matches(&entry) {
+                entries.push(entry);
+                if query.limit.is_some_and(|limit| entries.len() >= limit) {
+                    break;
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn format(&self) -> JournalFormat {
+        JournalFormat::Jsonl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::{OpKind, ResultKind};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal.jsonl");
+
+        let mut journal = JsonlJournal::create(&path).unwrap();
+        journal.append(&JournalEntry::new(PathBuf::from("/src/a"), PathBuf::from("/dst/a"), OpKind::Move)).unwrap();
+        journal.append(&JournalEntry::new(PathBuf::from("/src/b"), PathBuf::from("/dst/b"), OpKind::Move).failed("denied")).unwrap();
+
+        assert_eq!(journal.entries().unwrap().len(), 2);
+
+        let failed = journal.query(&JournalQuery {
+            result: Some(ResultKind::Failed),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].source, PathBuf::from("/src/b"));
+    }
+
+    fn write_sample(path: &Path) {
+        let mut journal = JsonlJournal::create(path).unwrap();
+        for name in ["a", "b", "c"] {
+            journal.append(&JournalEntry::new(PathBuf::from(name), PathBuf::from("dst"), OpKind::Move)).unwrap();
+        }
+        journal.finish().unwrap();
+    }
+
+    #[test]
+    fn test_read_checked_intact() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal.jsonl");
+        write_sample(&path);
+
+        let report = JsonlJournal::open(&path).unwrap().read_checked().unwrap();
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.entries.len(), 3);
+        assert_eq!(report.summary.unwrap().entry_count, 3);
+    }
+
+    #[test]
+    fn test_plan_hash_survives_resumed_finish() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal.jsonl");
+
+        let mut journal = JsonlJournal::create(&path).unwrap();
+        journal.link_plan("0badc0de".to_string());
+        journal.append(&JournalEntry::new(PathBuf::from("a"), PathBuf::from("dst"), OpKind::Move)).unwrap();
+        assert_eq!(journal.finish().unwrap().plan_hash.as_deref(), Some("0badc0de"));
+
+        // 開き直して追記しても計画との対応は残る
+        let mut journal = JsonlJournal::open(&path).unwrap();
+        journal.append(&JournalEntry::new(PathBuf::from("b"), PathBuf::from("dst"), OpKind::Move)).unwrap();
+        journal.finish().unwrap();
+
+        let report = journal.read_checked().unwrap();
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.summary.unwrap().plan_hash.as_deref(), Some("0badc0de"));
+    }
+
+    #[test]
+    fn test_read_checked_reports_tampered_and_corrupt_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal.jsonl");
+        write_sample(&path);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        lines[0] = lines[0].replace("\"dst\"", "\"elsewhere\"");
+        lines[1] = "{not json".to_string();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let report = JsonlJournal::open(&path).unwrap().read_checked().unwrap();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.problems[0], JournalProblem::ChecksumMismatch { position: 1 });
+        assert!(matches!(report.problems[1], JournalProblem::Unreadable { position: 2, .. }));
+    }
+
+    #[test]
+    fn test_read_checked_detects_truncation() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal.jsonl");
+        write_sample(&path);
+
+        // 完了レコードと最後のエントリの途中までを切り落とす
+        let content = std::fs::read_to_string(&path).unwrap();
+        let cut = content.lines().take(2).collect::<Vec<_>>().join("\n");
+        let partial = content.lines().nth(2).unwrap();
+        std::fs::write(&path, format!("{}\n{}", cut, &partial[..partial.len() / 2])).unwrap();
+
+        let report = JsonlJournal::open(&path).unwrap().read_checked().unwrap();
+        assert_eq!(report.entries.len(), 2);
+        assert!(matches!(report.problems[0], JournalProblem::Unreadable { position: 3, .. }));
+        assert_eq!(report.problems[1], JournalProblem::MissingSummary);
+    }
+
+    #[test]
+    fn test_read_checked_detects_removed_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal.jsonl");
+        write_sample(&path);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let kept: Vec<&str> = content.lines().enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(_, line)| line)
+            .collect();
+        std::fs::write(&path, kept.join("\n")).unwrap();
+
+        let report = JsonlJournal::open(&path).unwrap().read_checked().unwrap();
+        assert!(matches!(report.problems[0], JournalProblem::SummaryMismatch { .. }));
+    }
+}
@@ -0,0 +1,99 @@
+pub mod jsonl;
+pub mod sqlite;
+pub mod retention;
+pub mod undo;
+pub mod prune;
+mod summary;
+
+pub use jsonl::*;
+pub use sqlite::*;
+pub use retention::*;
+pub use undo::*;
+pub use prune::*;
+
+use std::io::Read;
+use std::path::Path;
+use filemover_types::{FileMoverError, JournalFormat, JournalStore};
+
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// 新しいジャーナルを作成する（既存ファイルがあればエラーにして上書きしない）
+pub fn create_journal(path: &Path, format: JournalFormat) -> Result<Box<dyn JournalStore>, FileMoverError> {
+    match format {
+        JournalFormat::Jsonl => Ok(Box::new(JsonlJournal::create(path)?)),
+        JournalFormat::Sqlite => Ok(Box::new(SqliteJournal::create(path)?)),
+    }
+}
+
+/// 既存のジャーナルがあれば追記し、なければ作成する（undo のやり直しで記録を残すため）
+pub fn create_or_open_journal(path: &Path, format: JournalFormat) -> Result<Box<dyn JournalStore>, FileMoverError> {
+    if path.exists() {
+        open_journal(path)
+    } else {
+        create_journal(path, format)
+    }
+}
+
+/// 既存のジャーナルを形式を判別して開く
+pub fn open_journal(path: &Path) -> Result<Box<dyn JournalStore>, FileMoverError> {
+    match detect_format(path)? {
+        JournalFormat::Jsonl => Ok(Box::new(JsonlJournal::open(path)?)),
+        JournalFormat::Sqlite => Ok(Box::new(SqliteJournal::open(path)?)),
+    }
+}
+
+/// 拡張子ではなくファイル先頭のシグネチャで判別する
+pub fn detect_format(path: &Path) -> Result<JournalFormat, FileMoverError> {
+    let mut header = [0u8; 16];
+    let mut file = std::fs::File::open(path)?;
+    let read = file.read(&mut header)?;
+
+    if read == SQLITE_HEADER.len() && header == SQLITE_HEADER {
+        Ok(JournalFormat::Sqlite)
+    } else {
+        Ok(JournalFormat::Jsonl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use filemover_types::{JournalEntry, OpKind};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_open_detects_format() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for format in [JournalFormat::Jsonl, JournalFormat::Sqlite] {
+            let path = temp_dir.path().join(format!("journal.{}", format.extension()));
+            let mut journal = create_journal(&path, format).unwrap();
+            journal.append(&JournalEntry::new(PathBuf::from("a"), PathBuf::from("b"), OpKind::Move)).unwrap();
+            drop(journal);
+
+            let reopened = open_journal(&path).unwrap();
+            assert_eq!(reopened.format(), format);
+            assert_eq!(reopened.entries().unwrap().len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_create_does_not_overwrite_existing_journal() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for format in [JournalFormat::Jsonl, JournalFormat::Sqlite] {
+            let path = temp_dir.path().join(format!("journal.{}", format.extension()));
+            let mut journal = create_journal(&path, format).unwrap();
+            journal.append(&JournalEntry::new(PathBuf::from("a"), PathBuf::from("b"), OpKind::Move)).unwrap();
+            drop(journal);
+
+            assert!(create_journal(&path, format).is_err());
+
+            // やり直しでは既存の記録に追記する
+            let mut journal = create_or_open_journal(&path, format).unwrap();
+            journal.append(&JournalEntry::new(PathBuf::from("b"), PathBuf::from("a"), OpKind::Move)).unwrap();
+            assert_eq!(journal.entries().unwrap().len(), 2);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, info};
+use filemover_types::{FileMoverError, JournalEntry, OpKind, ResultKind};
+
+/// 空フォルダ削除の結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PruneEmptyReport {
+    /// 削除した（dry_run なら削除対象の）フォルダ。深い順
+    pub removed: Vec<PathBuf>,
+    /// 削除しなかったフォルダと理由
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+/// 移動が済んだ操作の移動元の親フォルダのうち、空になったものを上に向かって削除する
+///
+/// 削除するのは roots のいずれかの内側にあるフォルダだけで、ルート自体とその外側には触れない。
+/// 中身のあるフォルダに当たった時点でその系統の遡りをやめる。
+pub fn prune_empty_sources(
+    entries: &[JournalEntry],
+    roots: &[PathBuf],
+    dry_run: bool,
+) -> Result<PruneEmptyReport, FileMoverError> {
+    if roots.is_empty() {
+        return Err(FileMoverError::Config {
+            message: "Removing empty folders needs at least one root to stay inside".to_string(),
+        });
+    }
+
+    let mut starts: Vec<PathBuf> = entries.iter()
+        .filter(|entry| entry.result == ResultKind::Ok)
+        .filter(|entry| matches!(entry.op, OpKind::Move | OpKind::CopyDelete | OpKind::Rename))
+        .filter(|entry| !entry.source.exists())
+        .filter_map(|entry| entry.source.parent().map(Path::to_path_buf))
+        .collect();
+    // 深いフォルダから見れば、親が空かどうかを一度で判定できる
+    starts.sort_by(|a, b| b.components().count().cmp(&a.components().count()).then_with(|| a.cmp(b)));
+    starts.dedup();
+
+    let mut report = PruneEmptyReport::default();
+    let mut removed: HashSet<PathBuf> = HashSet::new();
+
+    for start in starts {
+        if !is_inside_roots(&start, roots) {
+            if !roots.contains(&start) {
+                report.skipped.push((start, "Outside the roots".to_string()));
+            }
+            continue;
+        }
+
+        let mut dir = start;
+        while is_inside_roots(&dir, roots) {
+            // 削除済みのフォルダは通り抜けて親を見直す（別の系統の削除で空になっていることがある）
+            if !removed.contains(&dir) {
+                match is_empty_dir(&dir, &removed) {
+                    Ok(true) => {
+                        if !dry_run {
+                            // remove_dir は空でないフォルダを消さないので、判定後に中身が増えても安全
+                            if let Err(e) = std::fs::remove_dir(&dir) {
+                                report.skipped.push((dir, e.to_string()));
+                                break;
+                            }
+                            info!("Removed empty folder: {}", dir.display());
+                        } else {
+                            debug!("Would remove empty folder: {}", dir.display());
+                        }
+                        removed.insert(dir.clone());
+                        report.removed.push(dir.clone());
+                    }
+                    Ok(false) => break,
+                    // 既に無いフォルダは親の判定に影響しない
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        removed.insert(dir.clone());
+                    }
+                    Err(e) => {
+                        report.skipped.push((dir, e.to_string()));
+                        break;
+                    }
+                }
+            }
+
+            match dir.parent() {
+                Some(parent) => dir = parent.to_path_buf(),
+                None => break,
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// いずれかのルートの内側（ルート自体は含まない）
+///
+/// starts_with は字句上の比較なので、`..` を含むパスはルートの外を指しうるものとして内側に数えない。
+fn is_inside_roots(dir: &Path, roots: &[PathBuf]) -> bool {
+    !has_parent_dir(dir)
+        && roots.iter().any(|root| !has_parent_dir(root) && dir != root && dir.starts_with(root))
+}
+
+fn has_parent_dir(path: &Path) -> bool {
+    path.components().any(|component| component == Component::ParentDir)
+}
+
+/// 削除済み（dry_run で削除予定）のフォルダだけが残っていれば空とみなす
+fn is_empty_dir(dir: &Path, removed: &HashSet<PathBuf>) -> std::io::Result<bool> {
+    if !std::fs::symlink_metadata(dir)?.is_dir() {
+        return Ok(false);
+    }
+    for entry in std::fs::read_dir(dir)? {
+        if !removed.contains(&entry?.path()) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_prune_empty_sources() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("data");
+        std::fs::create_dir_all(root.join("2023/q1")).unwrap();
+        std::fs::create_dir_all(root.join("2023/q2/keep")).unwrap();
+        std::fs::create_dir_all(root.join("2024/q1")).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("outside")).unwrap();
+
+        let moved = |source: PathBuf| JournalEntry::new(source, temp_dir.path().join("archive"), OpKind::Move);
+        let entries = vec![
+            moved(root.join("2023/q1/project_a")),
+            moved(root.join("2023/q2/project_b")),
+            moved(root.join("2024/q1/project_c")),
+            moved(root.join("top_level")),
+            moved(temp_dir.path().join("outside/project_d")),
+            moved(root.join("2024/failed")).failed("Access denied"),
+        ];
+        let roots = vec![root.clone()];
+
+        let report = prune_empty_sources(&entries, &roots, true).unwrap();
+        assert_eq!(report.removed, vec![root.join("2023/q1"), root.join("2024/q1"), root.join("2024")]);
+        assert!(root.join("2024/q1").exists());
+
+        let report = prune_empty_sources(&entries, &roots, false).unwrap();
+        assert_eq!(report.removed.len(), 3);
+        assert_eq!(report.skipped, vec![(temp_dir.path().join("outside"), "Outside the roots".to_string())]);
+        assert!(!root.join("2024").exists());
+        assert!(!root.join("2023/q1").exists());
+        // 中身のあるフォルダ、ルート、ルートの外側は残る
+        assert!(root.join("2023/q2/keep").exists());
+        assert!(root.exists());
+        assert!(temp_dir.path().join("outside").exists());
+
+        assert!(prune_empty_sources(&entries, &[], false).is_err());
+    }
+
+    #[test]
+    fn test_prune_ignores_parent_dir_escapes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("data");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("other/empty")).unwrap();
+
+        // 字句上は root の内側に見えるが、実際は root の外のフォルダ
+        let escaped = root.join("../other/empty");
+        let entries = vec![JournalEntry::new(escaped.join("project"), temp_dir.path().join("archive"), OpKind::Move)];
+
+        let report = prune_empty_sources(&entries, &[root], false).unwrap();
+        assert!(report.removed.is_empty());
+        assert_eq!(report.skipped, vec![(escaped, "Outside the roots".to_string())]);
+        assert!(temp_dir.path().join("other/empty").exists());
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use chrono::{DateTime, Duration, Utc};
+use tracing::{debug, info};
+use filemover_types::{FileMoverError, JournalFormat, RetentionPolicy};
+
+pub const JOURNAL_FILE_PREFIX: &str = "filemover_journal_";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalFileInfo {
+    pub path: PathBuf,
+    pub modified: DateTime<Utc>,
+}
+
+/// 自動生成するジャーナルのファイル名（同じ秒の実行が重ならないようミリ秒まで含める）
+pub fn journal_file_name(format: JournalFormat, now: DateTime<Utc>) -> String {
+    format!(
+        "{}{}.{}",
+        JOURNAL_FILE_PREFIX,
+        now.format("%Y%m%d_%H%M%S_%3f"),
+        format.extension()
+    )
+}
+
+/// ディレクトリ内の自動生成ジャーナルを新しい順に返す
+pub fn list_journals(dir: &Path) -> Result<Vec<JournalFileInfo>, FileMoverError> {
+    let mut journals = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !is_managed_journal(&path) {
+            continue;
+        }
+
+        let modified = entry.metadata()?.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        journals.push(JournalFileInfo { path, modified: modified.into() });
+    }
+
+    journals.sort_by(|a, b| b.modified.cmp(&a.modified).then_with(|| b.path.cmp(&a.path)));
+    Ok(journals)
+}
+
+fn is_managed_journal(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    // undo ジャーナルは単独では数えず、元のジャーナルと一緒に消す
+    name.starts_with(JOURNAL_FILE_PREFIX)
+        && !name.contains(".undo.")
+        && [JournalFormat::Jsonl, JournalFormat::Sqlite]
+            .iter()
+            .any(|format| name.ends_with(&format!(".{}", format.extension())))
+}
+
+/// 新しい順に並んだ一覧から削除対象を選ぶ
+pub fn select_for_pruning(
+    journals: &[JournalFileInfo],
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<PathBuf> {
+    let cutoff = policy.max_age_days.map(|days| now - Duration::days(days as i64));
+
+    journals.iter()
+        .enumerate()
+        .filter(|(index, journal)| {
+            let beyond_count = policy.keep_last.is_some_and(|keep| *index >= keep);
+            let too_old = cutoff.is_some_and(|cutoff| journal.modified < cutoff);
+            beyond_count || too_old
+        })
+        .map(|(_, journal)| journal.path.clone())
+        .collect()
+}
+
+/// 保持ポリシーを適用し、削除した（dry_run なら削除対象の）ジャーナルを返す
+pub fn prune_journals(
+    dir: &Path,
+    policy: &RetentionPolicy,
+    dry_run: bool,
+) -> Result<Vec<PathBuf>, FileMoverError> {
+    if !policy.is_enabled() || !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let journals = list_journals(dir)?;
+    let targets = select_for_pruning(&journals, policy, Utc::now());
+
+    if dry_run {
+        return Ok(targets);
+    }
+
+    for path in &targets {
+        // SQLite の WAL 付属ファイルと undo ジャーナルも一緒に消す
+        for journal in [path.clone(), crate::undo::undo_journal_path(path)] {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut file = journal.as_os_str().to_owned();
+                file.push(suffix);
+                let file = PathBuf::from(file);
+                if file.exists() {
+                    std::fs::remove_file(&file)?;
+                    debug!("Removed journal file: {}", file.display());
+                }
+            }
+        }
+    }
+
+    if !targets.is_empty() {
+        info!("Pruned {} journal(s) from {}", targets.len(), dir.display());
+    }
+
+    Ok(targets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn journals(days_old: &[i64], now: DateTime<Utc>) -> Vec<JournalFileInfo> {
+        days_old.iter()
+            .enumerate()
+            .map(|(i, days)| JournalFileInfo {
+                path: PathBuf::from(format!("{}{}.jsonl", JOURNAL_FILE_PREFIX, i)),
+                modified: now - Duration::days(*days),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_keep_last() {
+        let now = Utc::now();
+        let list = journals(&[0, 1, 2, 3], now);
+        let policy = RetentionPolicy { keep_last: Some(2), max_age_days: None };
+
+        let pruned = select_for_pruning(&list, &policy, now);
+        assert_eq!(pruned, vec![list[2].path.clone(), list[3].path.clone()]);
+    }
+
+    #[test]
+    fn test_select_max_age() {
+        let now = Utc::now();
+        let list = journals(&[0, 5, 40], now);
+        let policy = RetentionPolicy { keep_last: None, max_age_days: Some(30) };
+
+        let pruned = select_for_pruning(&list, &policy, now);
+        assert_eq!(pruned, vec![list[2].path.clone()]);
+    }
+
+    #[test]
+    fn test_prune_only_touches_managed_journals() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = Utc::now();
+        for i in 0..3 {
+            let name = journal_file_name(JournalFormat::Jsonl, now - Duration::seconds(i));
+            std::fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+        std::fs::write(temp_dir.path().join("notes.jsonl"), "").unwrap();
+
+        let policy = RetentionPolicy { keep_last: Some(1), max_age_days: None };
+
+        let planned = prune_journals(temp_dir.path(), &policy, true).unwrap();
+        assert_eq!(planned.len(), 2);
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 4);
+
+        let removed = prune_journals(temp_dir.path(), &policy, false).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(temp_dir.path().join("notes.jsonl").exists());
+        assert_eq!(list_journals(temp_dir.path()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_prune_removes_undo_journal_with_its_journal() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = Utc::now();
+        let old = temp_dir.path().join(journal_file_name(JournalFormat::Jsonl, now - Duration::seconds(1)));
+        let new = temp_dir.path().join(journal_file_name(JournalFormat::Jsonl, now));
+        for path in [&old, &new] {
+            std::fs::write(path, "").unwrap();
+        }
+        let old_undo = crate::undo::undo_journal_path(&old);
+        std::fs::write(&old_undo, "").unwrap();
+
+        // undo ジャーナルは保持件数に数えない
+        assert_eq!(list_journals(temp_dir.path()).unwrap().len(), 2);
+
+        let policy = RetentionPolicy { keep_last: Some(1), max_age_days: None };
+        let removed = prune_journals(temp_dir.path(), &policy, false).unwrap();
+        assert_eq!(removed, vec![old]);
+        assert!(!old_undo.exists());
+        assert!(new.exists());
+    }
+}
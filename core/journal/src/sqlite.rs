@@ -0,0 +1,378 @@
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use chrono::SecondsFormat;
+use rusqlite::{params, Connection, ToSql};
+use rusqlite::OptionalExtension;
+use filemover_types::{
+    FileMoverError, JournalEntry, JournalFormat, JournalProblem, JournalQuery, JournalReadReport,
+    JournalStore, JournalSummary, ResultKind,
+};
+use crate::summary::{check_summary, resumed_summary, SummaryAccumulator};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS journal_entries (
+        id        INTEGER PRIMARY KEY AUTOINCREMENT,
+        when_utc  TEXT NOT NULL,
+        source    TEXT NOT NULL,
+        dest      TEXT NOT NULL,
+        op        TEXT NOT NULL,
+        result    TEXT NOT NULL,
+        rule_id   TEXT,
+        entry     TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_journal_source ON journal_entries(source);
+    CREATE INDEX IF NOT EXISTS idx_journal_dest ON journal_entries(dest);
+    CREATE INDEX IF NOT EXISTS idx_journal_rule ON journal_entries(rule_id);
+    CREATE INDEX IF NOT EXISTS idx_journal_result ON journal_entries(result);
+    CREATE INDEX IF NOT EXISTS idx_journal_when ON journal_entries(when_utc);
+    CREATE TABLE IF NOT EXISTS journal_summary (
+        id        INTEGER PRIMARY KEY CHECK (id = 1),
+        summary   TEXT NOT NULL
+    );
+";
+
+/// 大量の操作を記録する実行向けの SQLite ジャーナル
+///
+/// 検索用の列にインデックスを張り、エントリ本体は JSON のまま保持する。
+#[derive(Debug)]
+pub struct SqliteJournal {
+    path: PathBuf,
+    conn: Connection,
+    accumulator: Option<SummaryAccumulator>,
+    plan_hash: Option<String>,
+}
+
+impl SqliteJournal {
+    pub fn create(path: &Path) -> Result<Self, FileMoverError> {
+        // 既存のジャーナルは上書きしない（空ファイルは空のデータベースとして開ける）
+        std::fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+        // 本体のない WAL の付属ファイルが残っていれば消す
+        for suffix in ["-wal", "-shm"] {
+            let mut file = path.as_os_str().to_owned();
+            file.push(suffix);
+            let file = PathBuf::from(file);
+            if file.exists() {
+                std::fs::remove_file(&file)?;
+            }
+        }
+        let mut journal = Self::open(path)?;
+        journal.accumulator = Some(SummaryAccumulator::default());
+        Ok(journal)
+    }
+
+    pub fn open(path: &Path) -> Result<Self, FileMoverError> {
+        let conn = Connection::open(path).map_err(sqlite_error)?;
+        // WAL にしておくと 1 件ずつのコミットでも十分な速度が出る
+        conn.pragma_update(None, "journal_mode", "WAL").map_err(sqlite_error)?;
+        conn.pragma_update(None, "synchronous", "NORMAL").map_err(sqlite_error)?;
+        conn.execute_batch(SCHEMA).map_err(sqlite_error)?;
+
+        Ok(Self { path: path.to_path_buf(), conn, accumulator: None, plan_hash: None })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl JournalStore for SqliteJournal {
+    fn append(&mut self, entry: &JournalEntry) -> Result<(), FileMoverError> {
+        let entry = &entry.clone().with_checksum();
+        let json = serde_json::to_string(entry).map_err(|e| FileMoverError::Journal {
+            message: format!("Failed to serialize journal entry: {}", e),
+        })?;
+
+        self.conn.execute(
+            "INSERT INTO journal_entries (when_utc, source, dest, op, result, rule_id, entry)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                format_time(&entry.when_utc),
+                entry.source.to_string_lossy(),
+                entry.dest.to_string_lossy(),
+                format!("{:?}", entry.op),
+                result_name(entry.result),
+                entry.rule_id.map(|id| id.to_string()),
+                json,
+            ],
+        ).map_err(sqlite_error)?;
+
+        if let Some(accumulator) = &mut self.accumulator {
+            accumulator.record(entry);
+        }
+        Ok(())
+    }
+
+    fn link_plan(&mut self, plan_hash: String) {
+        self.plan_hash = Some(plan_hash);
+    }
+
+    fn finish(&mut self) -> Result<JournalSummary, FileMoverError> {
+        let summary = match &self.accumulator {
+            Some(accumulator) => accumulator.summary(),
+            None => resumed_summary(&self.read_checked()?),
+        }
+        .with_plan_hash(self.plan_hash.clone());
+
+        let json = serde_json::to_string(&summary).map_err(|e| FileMoverError::Journal {
+            message: format!("Failed to serialize journal summary: {}", e),
+        })?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO journal_summary (id, summary) VALUES (1, ?1)",
+            params![json],
+        ).map_err(sqlite_error)?;
+
+        Ok(summary)
+    }
+
+    fn read_checked(&self) -> Result<JournalReadReport, FileMoverError> {
+        let mut report = JournalReadReport::default();
+
+        let mut stmt = self.conn
+            .prepare("SELECT id, entry FROM journal_entries ORDER BY id")
+            .map_err(sqlite_error)?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1))))
+            .map_err(sqlite_error)?;
+
+        let mut total_records = 0;
+        for row in rows {
+            let (id, json) = row.map_err(sqlite_error)?;
+            let position = id as usize;
+            total_records += 1;
+
+            let parsed = json
+                .map_err(sqlite_error)
+                .and_then(|json| JournalEntry::from_json_line(&json));
+            match parsed {
+                Ok(entry) if entry.checksum_matches() == Some(false) => {
+                    report.problems.push(JournalProblem::ChecksumMismatch { position });
+                }
+                Ok(entry) => report.entries.push(entry),
+                Err(e) => report.problems.push(JournalProblem::Unreadable {
+                    position,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        let summary_json: Option<String> = self.conn
+            .query_row("SELECT summary FROM journal_summary WHERE id = 1", [], |row| row.get(0))
+            .optional()
+            .map_err(sqlite_error)?;
+        if let Some(json) = summary_json {
+            match serde_json::from_str(&json) {
+                Ok(summary) => report.summary = Some(summary),
+                Err(e) => report.problems.push(JournalProblem::SummaryMismatch {
+                    message: format!("summary record is unreadable: {}", e),
+                }),
+            }
+        }
+
+        check_summary(&mut report, total_records);
+        Ok(report)
+    }
+
+    fn query(&self, query: &JournalQuery) -> Result<Vec<JournalEntry>, FileMoverError> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<Box<dyn ToSql>> = Vec::new();
+
+        if let Some(path) = &query.path {
+            // 範囲比較にしてインデックスが効くようにする
+            let exact = path.to_string_lossy().to_string();
+            let lower = format!("{}{}", exact.trim_end_matches(MAIN_SEPARATOR), MAIN_SEPARATOR);
+            let upper = format!(
+                "{}{}",
+                exact.trim_end_matches(MAIN_SEPARATOR),
+                (MAIN_SEPARATOR as u8 + 1) as char
+            );
+            let n = values.len();
+            conditions.push(format!(
+                "(source = ?{a} OR (source >= ?{b} AND source < ?{c}) \
+                 OR dest = ?{a} OR (dest >= ?{b} AND dest < ?{c}))",
+                a = n + 1, b = n + 2, c = n + 3
+            ));
+            values.push(Box::new(exact));
+            values.push(Box::new(lower));
+            values.push(Box::new(upper));
+        }
+        if let Some(rule_id) = query.rule_id {
+            values.push(Box::new(rule_id.to_string()));
+            conditions.push(format!("rule_id = ?{}", values.len()));
+        }
+        if let Some(tag) = &query.tag {
+            // タグは検索用の列を持たず、エントリ本体の JSON から探す
+            values.push(Box::new(tag.clone()));
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM json_each(entry, '$.tags') WHERE value = ?{})",
+                values.len()
+            ));
+        }
+        if let Some(result) = query.result {
+            values.push(Box::new(result_name(result)));
+            conditions.push(format!("result = ?{}", values.len()));
+        }
+        if let Some(since) = query.since {
+            values.push(Box::new(format_time(&since)));
+            conditions.push(format!("when_utc >= ?{}", values.len()));
+        }
+        if let Some(until) = query.until {
+            values.push(Box::new(format_time(&until)));
+            conditions.push(format!("when_utc <= ?{}", values.len()));
+        }
+
+        let mut sql = String::from("SELECT entry FROM journal_entries");
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(" ORDER BY id");
+        if let Some(limit) = query.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut stmt = self.conn.prepare(&sql).map_err(sqlite_error)?;
+        let params: Vec<&dyn ToSql> = values.iter().map(|v| v.as_ref()).collect();
+        let rows = stmt
+            .query_map(params.as_slice(), |row| row.get::<_, String>(0))
+            .map_err(sqlite_error)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(JournalEntry::from_json_line(&row.map_err(sqlite_error)?)?);
+        }
+
+        Ok(entries)
+    }
+
+    fn format(&self) -> JournalFormat {
+        JournalFormat::Sqlite
+    }
+}
+
+// 固定桁にして文字列比較で時刻順に並ぶようにする
+fn format_time(time: &chrono::DateTime<chrono::Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn result_name(result: ResultKind) -> &'static str {
+    match result {
+        ResultKind::Ok => "Ok",
+        ResultKind::Skip => "Skip",
+        ResultKind::Failed => "Failed",
+    }
+}
+
+fn sqlite_error(e: rusqlite::Error) -> FileMoverError {
+    FileMoverError::Journal {
+        message: format!("SQLite error: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use filemover_types::OpKind;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn sample_journal(path: &Path) -> SqliteJournal {
+        let mut journal = SqliteJournal::create(path).unwrap();
+        let base = PathBuf::from(MAIN_SEPARATOR.to_string());
+
+        journal.append(&JournalEntry::new(base.join("src").join("a"), base.join("dst").join("a"), OpKind::Move)).unwrap();
+        journal.append(&JournalEntry::new(base.join("src2").join("b"), base.join("dst").join("b"), OpKind::CopyDelete)
+            .failed("denied")).unwrap();
+        journal
+    }
+
+    #[test]
+    fn test_append_and_read_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = sample_journal(&temp_dir.path().join("journal.db"));
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].op, OpKind::Move);
+        assert_eq!(entries[1].result, ResultKind::Failed);
+    }
+
+    #[test]
+    fn test_query_by_path_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = sample_journal(&temp_dir.path().join("journal.db"));
+        let base = PathBuf::from(MAIN_SEPARATOR.to_string());
+
+        let under_src = journal.query(&JournalQuery {
+            path: Some(base.join("src")),
+            ..Default::default()
+        }).unwrap();
+        // "src2" は "src" 配下ではない
+        assert_eq!(under_src.len(), 1);
+
+        let under_dst = journal.query(&JournalQuery {
+            path: Some(base.join("dst")),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(under_dst.len(), 2);
+    }
+
+    #[test]
+    fn test_query_by_result_rule_and_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut journal = sample_journal(&temp_dir.path().join("journal.db"));
+
+        let rule = Some(Uuid::new_v4());
+        journal.append(&JournalEntry::new(PathBuf::from("x"), PathBuf::from("y"), OpKind::Move)
+            .with_rule_id(rule)
+            .with_tags(vec!["photos".to_string(), "2023".to_string()])).unwrap();
+
+        let failed = journal.query(&JournalQuery {
+            result: Some(ResultKind::Failed),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(failed.len(), 1);
+
+        let by_rule = journal.query(&JournalQuery { rule_id: rule, ..Default::default() }).unwrap();
+        assert_eq!(by_rule.len(), 1);
+
+        let by_tag = journal.query(&JournalQuery { tag: Some("photos".to_string()), ..Default::default() }).unwrap();
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag[0].tags, vec!["photos", "2023"]);
+
+        let future = journal.query(&JournalQuery {
+            since: Some(Utc::now() + Duration::hours(1)),
+            ..Default::default()
+        }).unwrap();
+        assert!(future.is_empty());
+
+        let limited = journal.query(&JournalQuery { limit: Some(2), ..Default::default() }).unwrap();
+        assert_eq!(limited.len(), 2);
+    }
+
+    #[test]
+    fn test_read_checked_detects_tampered_row() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal.db");
+        let mut journal = sample_journal(&path);
+        journal.finish().unwrap();
+        assert!(journal.read_checked().unwrap().is_intact());
+
+        journal.conn.execute(
+            "UPDATE journal_entries SET entry = replace(entry, '\"Move\"', '\"Rename\"') WHERE id = 1",
+            [],
+        ).unwrap();
+
+        let report = journal.read_checked().unwrap();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.problems, vec![JournalProblem::ChecksumMismatch { position: 1 }]);
+    }
+
+    #[test]
+    fn test_read_checked_without_summary() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = sample_journal(&temp_dir.path().join("journal.db"));
+
+        let report = journal.read_checked().unwrap();
+        assert_eq!(report.problems, vec![JournalProblem::MissingSummary]);
+    }
+}
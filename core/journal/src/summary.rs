@@ -0,0 +1,86 @@
+use chrono::Utc;
+use filemover_types::{JournalEntry, JournalProblem, JournalReadReport, JournalSummary, ResultKind};
+
+/// 追記しながら完了レコードの内容を集計する
+#[derive(Debug, Default)]
+pub(crate) struct SummaryAccumulator {
+    entry_count: u64,
+    ok_count: u64,
+    skip_count: u64,
+    failed_count: u64,
+    hasher: crc32fast::Hasher,
+}
+
+impl SummaryAccumulator {
+    pub(crate) fn from_entries(entries: &[JournalEntry]) -> Self {
+        let mut accumulator = Self::default();
+        for entry in entries {
+            accumulator.record(entry);
+        }
+        accumulator
+    }
+
+    pub(crate) fn record(&mut self, entry: &JournalEntry) {
+        self.entry_count += 1;
+        match entry.result {
+            ResultKind::Ok => self.ok_count += 1,
+            ResultKind::Skip => self.skip_count += 1,
+            ResultKind::Failed => self.failed_count += 1,
+        }
+        if let Some(checksum) = &entry.checksum {
+            self.hasher.update(checksum.as_bytes());
+        }
+    }
+
+    pub(crate) fn summary(&self) -> JournalSummary {
+        JournalSummary {
+            entry_count: self.entry_count,
+            ok_count: self.ok_count,
+            skip_count: self.skip_count,
+            failed_count: self.failed_count,
+            completed_utc: Utc::now(),
+            digest: format!("{:08x}", self.hasher.clone().finalize()),
+            plan_hash: None,
+        }
+    }
+}
+
+/// 既存のジャーナルを開き直して finish する場合の完了レコード（前回の計画のハッシュを引き継ぐ）
+pub(crate) fn resumed_summary(report: &JournalReadReport) -> JournalSummary {
+    SummaryAccumulator::from_entries(&report.entries)
+        .summary()
+        .with_plan_hash(report.summary.as_ref().and_then(|summary| summary.plan_hash.clone()))
+}
+
+/// 読み込んだエントリと完了レコードを突き合わせ、問題を report に追加する
+pub(crate) fn check_summary(report: &mut JournalReadReport, total_records: usize) {
+    // チェックサム導入前のジャーナルには完了レコードが無いのが正常
+    let has_checksums = report.entries.iter().any(|entry| entry.checksum.is_some());
+
+    let Some(summary) = &report.summary else {
+        if has_checksums || !report.problems.is_empty() {
+            report.problems.push(JournalProblem::MissingSummary);
+        }
+        return;
+    };
+
+    if summary.entry_count != total_records as u64 {
+        report.problems.push(JournalProblem::SummaryMismatch {
+            message: format!(
+                "summary lists {} entries but {} were found",
+                summary.entry_count, total_records
+            ),
+        });
+        return;
+    }
+
+    // 個別の破損が無いときだけ、削除・並べ替えをダイジェストで検出する
+    if report.problems.is_empty() {
+        let actual = SummaryAccumulator::from_entries(&report.entries).summary();
+        if actual.digest != summary.digest {
+            report.problems.push(JournalProblem::SummaryMismatch {
+                message: "entry digest does not match (entries were modified or reordered)".to_string(),
+            });
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+use filemover_types::{FileMoverError, JournalEntry, OpKind};
+
+/// 逆適用の結果を記録する undo ジャーナルのパス（例: run.jsonl → run.undo.jsonl）
+pub fn undo_journal_path(journal_path: &Path) -> PathBuf {
+    let stem = journal_path.file_stem().and_then(|s| s.to_str()).unwrap_or("journal");
+    let name = match journal_path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}.undo.{}", stem, ext),
+        None => format!("{}.undo", stem),
+    };
+    journal_path.with_file_name(name)
+}
+
+/// ジャーナルのエントリ 1 件を逆適用し、undo ジャーナルに書くエントリを返す
+///
+/// 返すエントリは dest → source の向きで、失敗時は result が Failed になる。
+pub fn undo_entry(entry: &JournalEntry) -> JournalEntry {
+    let record = JournalEntry::new(entry.dest.clone(), entry.source.clone(), entry.op)
+        .with_rule_id(entry.rule_id)
+        .with_tags(entry.tags.clone());
+
+    match reverse_operation(entry) {
+        Ok(()) => {
+            restore_metadata(entry);
+            record.with_message("Restored")
+        }
+        Err(e) => record.failed(e.to_string()),
+    }
+}
+
+fn reverse_operation(entry: &JournalEntry) -> Result<(), FileMoverError> {
+    if !entry.dest.exists() {
+        return Err(undo_error(format!("Destination no longer exists: {}", entry.dest.display())));
+    }
+    if entry.source.exists() {
+        return Err(undo_error(format!("Original location is occupied: {}", entry.source.display())));
+    }
+
+    if let Some(parent) = entry.source.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    match entry.op {
+        OpKind::Move | OpKind::Rename => {
+            match std::fs::rename(&entry.dest, &entry.source) {
+                Ok(()) => Ok(()),
+                // ボリュームをまたいでいる場合はコピーで戻す
+                Err(e) if e.kind() == ErrorKind::CrossesDevices => copy_back(&entry.dest, &entry.source),
+                Err(e) => Err(e.into()),
+            }
+        }
+        OpKind::CopyDelete => copy_back(&entry.dest, &entry.source),
+        OpKind::Skip | OpKind::None => {
+            Err(undo_error(format!("Operation {:?} cannot be undone", entry.op)))
+        }
+    }
+}
+
+/// コピーで元の場所に戻し、完全に戻せたことを確認してから移動先を削除する
+fn copy_back(dest: &Path, source: &Path) -> Result<(), FileMoverError> {
+    // 途中で失敗しても元の場所に中途半端なフォルダを残さないよう一時名でコピーする
+    let mut temp_name = source.as_os_str().to_owned();
+    temp_name.push(".filemover-undo");
+    let temp = PathBuf::from(temp_name);
+
+    if temp.exists() {
+        return Err(undo_error(format!("Temporary path already exists: {}", temp.display())));
+    }
+
+    let copied = copy_recursive(dest, &temp).and_then(|bytes| {
+        let expected = tree_size(dest)?;
+        if bytes != expected {
+            return Err(std::io::Error::other(format!(
+                "copied {} of {} bytes",
+                bytes, expected
+            )));
+        }
+        Ok(())
+    });
+
+    if let Err(e) = copied {
+        let _ = remove_path(&temp);
+        return Err(undo_error(format!("Failed to copy {} back: {}", dest.display(), e)));
+    }
+
+    std::fs::rename(&temp, source)?;
+    debug!("Copied {} back to {}", dest.display(), source.display());
+
+    remove_path(dest).map_err(|e| {
+        undo_error(format!(
+            "Restored {} but could not remove {}: {}",
+            source.display(),
+            dest.display(),
+            e
+        ))
+    })
+}
+
+/// リンクはリンクのままコピーし、バイト数には数えない（tree_size と同じ数え方）
+fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<u64> {
+    let metadata = std::fs::symlink_metadata(from)?;
+    if metadata.file_type().is_symlink() {
+        copy_symlink(from, to, &metadata)?;
+        return Ok(0);
+    }
+    if !metadata.is_dir() {
+        return std::fs::copy(from, to);
+    }
+
+    std::fs::create_dir(to)?;
+    let mut total = 0;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        total += copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    Ok(total)
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path, _metadata: &std::fs::Metadata) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(from)?, to)
+}
+
+#[cfg(windows)]
+fn copy_symlink(from: &Path, to: &Path, metadata: &std::fs::Metadata) -> std::io::Result<()> {
+    use std::os::windows::fs::FileTypeExt;
+    let target = std::fs::read_link(from)?;
+    if metadata.file_type().is_symlink_dir() {
+        std::os::windows::fs::symlink_dir(target, to)
+    } else {
+        std::os::windows::fs::symlink_file(target, to)
+    }
+}
+
+fn tree_size(path: &Path) -> std::io::Result<u64> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if metadata.file_type().is_symlink() {
+        return Ok(0);
+    }
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        total += tree_size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if std::fs::symlink_metadata(path)?.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+// 復元自体は成功しているので、属性の書き戻しに失敗しても警告に留める
+fn restore_metadata(entry: &JournalEntry) {
+    if let Some(metadata) = &entry.metadata {
+        if let Err(e) = metadata.restore(&entry.source) {
+            warn!("Restored {} but could not restore its attributes: {}", entry.source.display(), e);
+        }
+    }
+}
+
+fn undo_error(message: String) -> FileMoverError {
+    FileMoverError::Undo { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::{EntryMetadata, ResultKind};
+    use tempfile::TempDir;
+
+    fn moved_folder(temp_dir: &TempDir) -> (PathBuf, PathBuf) {
+        let source = temp_dir.path().join("src").join("project");
+        let dest = temp_dir.path().join("archive").join("project");
+        std::fs::create_dir_all(dest.join("sub")).unwrap();
+        std::fs::write(dest.join("a.txt"), "alpha").unwrap();
+        std::fs::write(dest.join("sub").join("b.txt"), "beta").unwrap();
+        (source, dest)
+    }
+
+    #[test]
+    fn test_undo_journal_path() {
+        assert_eq!(
+            undo_journal_path(Path::new("/tmp/run.jsonl")),
+            PathBuf::from("/tmp/run.undo.jsonl")
+        );
+        assert_eq!(undo_journal_path(Path::new("run")), PathBuf::from("run.undo"));
+    }
+
+    #[test]
+    fn test_undo_move() {
+        let temp_dir = TempDir::new().unwrap();
+        let (source, dest) = moved_folder(&temp_dir);
+
+        let record = undo_entry(&JournalEntry::new(source.clone(), dest.clone(), OpKind::Move));
+
+        assert_eq!(record.result, ResultKind::Ok);
+        assert_eq!(record.source, dest);
+        assert!(source.join("sub").join("b.txt").exists());
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn test_undo_copy_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let (source, dest) = moved_folder(&temp_dir);
+
+        let record = undo_entry(&JournalEntry::new(source.clone(), dest.clone(), OpKind::CopyDelete));
+
+        assert_eq!(record.result, ResultKind::Ok, "{:?}", record.message);
+        assert_eq!(std::fs::read_to_string(source.join("a.txt")).unwrap(), "alpha");
+        assert!(!dest.exists());
+        assert!(!temp_dir.path().join("src").join("project.filemover-undo").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_undo_copy_delete_keeps_symlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let (source, dest) = moved_folder(&temp_dir);
+        std::os::unix::fs::symlink("sub/b.txt", dest.join("link.txt")).unwrap();
+
+        let record = undo_entry(&JournalEntry::new(source.clone(), dest.clone(), OpKind::CopyDelete));
+
+        assert_eq!(record.result, ResultKind::Ok, "{:?}", record.message);
+        // リンク先の中身ではなくリンクとして戻る
+        assert_eq!(std::fs::read_link(source.join("link.txt")).unwrap(), PathBuf::from("sub/b.txt"));
+        assert_eq!(std::fs::read_to_string(source.join("link.txt")).unwrap(), "beta");
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn test_undo_refuses_to_overwrite_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let (source, dest) = moved_folder(&temp_dir);
+        std::fs::create_dir_all(&source).unwrap();
+
+        let record = undo_entry(&JournalEntry::new(source, dest.clone(), OpKind::Move));
+
+        assert_eq!(record.result, ResultKind::Failed);
+        assert!(dest.join("a.txt").exists());
+    }
+
+    #[test]
+    fn test_undo_restores_timestamps() {
+        let temp_dir = TempDir::new().unwrap();
+        let (source, dest) = moved_folder(&temp_dir);
+
+        let original = "2021-03-04T05:06:07Z".parse().unwrap();
+        let metadata = EntryMetadata {
+            modified: Some(original),
+            ..Default::default()
+        };
+        let entry = JournalEntry::new(source.clone(), dest, OpKind::Rename).with_metadata(metadata);
+
+        assert_eq!(undo_entry(&entry).result, ResultKind::Ok);
+        assert_eq!(EntryMetadata::capture(&source).unwrap().modified, Some(original));
+    }
+}
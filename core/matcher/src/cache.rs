@@ -0,0 +1,154 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use filemover_types::{FileMoverError, NormalizationOptions, PatternSpec, Rule};
+use crate::engine::MatchingEngine;
+
+/// エンジンの元になるルール・除外パターン・正規化の指紋（同じ値なら同じエンジンになる）
+///
+/// 正規化した JSON の SHA-256 なので、プロセスやビルドが変わっても同じ値になる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RuleSetFingerprint(pub [u8; 32]);
+
+impl RuleSetFingerprint {
+    pub fn new(
+        rules: &[Rule],
+        exclude_patterns: &[PatternSpec],
+        normalization: &NormalizationOptions,
+    ) -> Result<Self, FileMoverError> {
+        let to_error = |e: serde_json::Error| FileMoverError::Pattern {
+            message: format!("Failed to fingerprint rules: {}", e),
+        };
+        // Value を経由するとオブジェクトのキーが並べ替えられ、表現が一意になる
+        let value = serde_json::to_value((rules, exclude_patterns, normalization)).map_err(to_error)?;
+        let json = serde_json::to_vec(&value).map_err(to_error)?;
+        Ok(Self(Sha256::digest(&json).into()))
+    }
+}
+
+impl fmt::Display for RuleSetFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// コンパイル済みのエンジンを指紋ごとに使い回す（glob・正規表現・Aho-Corasick はシリアライズできないのでメモリ上に持つ）
+///
+/// 新しいものから capacity 個まで保持する。
+pub struct EngineCache {
+    entries: Mutex<Vec<(RuleSetFingerprint, Arc<MatchingEngine>)>>,
+    capacity: usize,
+}
+
+impl EngineCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// 同じルールのエンジンがあればそれを返し、なければ作って保持する
+    pub fn get_or_build(
+        &self,
+        rules: &[Rule],
+        exclude_patterns: &[PatternSpec],
+        normalization: &NormalizationOptions,
+    ) -> Result<Arc<MatchingEngine>, FileMoverError> {
+        let fingerprint = RuleSetFingerprint::new(rules, exclude_patterns, normalization)?;
+        if let Some(engine) = self.get(fingerprint) {
+            return Ok(engine);
+        }
+
+        // コンパイル中はロックを持たない（同時に作られた場合は後のものが残る）
+        let engine = Arc::new(MatchingEngine::with_exclude_patterns(
+            rules.to_vec(),
+            exclude_patterns.to_vec(),
+            normalization.clone(),
+        )?);
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|(existing, _)| *existing != fingerprint);
+        entries.insert(0, (fingerprint, engine.clone()));
+        entries.truncate(self.capacity);
+        Ok(engine)
+    }
+
+    fn get(&self, fingerprint: RuleSetFingerprint) -> Option<Arc<MatchingEngine>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let position = entries.iter().position(|(existing, _)| *existing == fingerprint)?;
+        // 最近使ったものを先頭に移す
+        let entry = entries.remove(position);
+        let engine = entry.1.clone();
+        entries.insert(0, entry);
+        Some(engine)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+impl Default for EngineCache {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn rules(pattern: &str) -> Vec<Rule> {
+        vec![Rule::new(PatternSpec::new_glob(pattern), PathBuf::from("/archive"), "{name}".to_string())]
+    }
+
+    #[test]
+    fn test_engine_is_reused_until_rules_change() {
+        let cache = EngineCache::new(2);
+        let normalization = NormalizationOptions::default();
+        let tmp_rules = rules("*.tmp");
+        let bak_rules = rules("*.bak");
+
+        let first = cache.get_or_build(&tmp_rules, &[], &normalization).unwrap();
+        let again = cache.get_or_build(&tmp_rules.clone(), &[], &normalization).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+
+        let other = cache.get_or_build(&bak_rules, &[], &normalization).unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+
+        let folded = NormalizationOptions { fold_hiragana_katakana: true, ..normalization.clone() };
+        cache.get_or_build(&bak_rules, &[], &folded).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // 容量を超えたので最も古い "*.tmp" は作り直される
+        let rebuilt = cache.get_or_build(&tmp_rules, &[], &normalization).unwrap();
+        assert!(!Arc::ptr_eq(&first, &rebuilt));
+    }
+
+    #[test]
+    fn test_fingerprint_is_sha256_of_canonical_json() {
+        let normalization = NormalizationOptions::default();
+        let tmp_rules = rules("*.tmp");
+
+        let fingerprint = RuleSetFingerprint::new(&tmp_rules, &[], &normalization).unwrap();
+        let json = serde_json::to_vec(&serde_json::to_value((&tmp_rules, &[] as &[PatternSpec], &normalization)).unwrap()).unwrap();
+        assert_eq!(fingerprint.0, <[u8; 32]>::from(Sha256::digest(&json)));
+        assert_eq!(fingerprint.to_string().len(), 64);
+        assert_eq!(fingerprint, RuleSetFingerprint::new(&tmp_rules.clone(), &[], &normalization).unwrap());
+        assert_ne!(fingerprint, RuleSetFingerprint::new(&rules("*.bak"), &[], &normalization).unwrap());
+    }
+}
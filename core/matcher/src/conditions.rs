@@ -0,0 +1,186 @@
+use std::time::{Duration, SystemTime};
+use globset::{GlobBuilder, GlobMatcher};
+use filemover_types::{FileMoverError, RuleConditions};
+use crate::context::EntryMetadata;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// ルールの条件（ファイルの glob はルールごとに一度だけコンパイルする）
+pub struct ConditionEvaluator {
+    conditions: RuleConditions,
+    file_matcher: Option<GlobMatcher>,
+}
+
+impl ConditionEvaluator {
+    pub fn new(conditions: &RuleConditions) -> Result<Self, FileMoverError> {
+        let file_matcher = match &conditions.contains_files {
+            Some(condition) => Some(
+                GlobBuilder::new(&condition.pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| FileMoverError::Pattern {
+                        message: format!("Invalid file pattern '{}': {}", condition.pattern, e),
+                    })?
+                    .compile_matcher(),
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            conditions: conditions.clone(),
+            file_matcher,
+        })
+    }
+
+    /// 条件をすべて満たすか（条件がなければ true）
+    pub fn is_met(&self, metadata: Option<&dyn EntryMetadata>, now: SystemTime) -> bool {
+        if self.conditions.is_empty() {
+            return true;
+        }
+        let Some(metadata) = metadata else {
+            return false;
+        };
+        if !conditions_met(&self.conditions, metadata, now) {
+            return false;
+        }
+
+        // 中身を調べるのは他の条件を満たした場合だけ
+        match (&self.conditions.contains_files, &self.file_matcher) {
+            (Some(condition), Some(matcher)) => {
+                let accept = |name: &str| matcher.is_match(name);
+                metadata.count_files(condition.max_depth, condition.min_count, &accept)
+                    .is_some_and(|count| count >= condition.min_count)
+            }
+            _ => true,
+        }
+    }
+}
+
+fn conditions_met(conditions: &RuleConditions, metadata: &dyn EntryMetadata, now: SystemTime) -> bool {
+
+    if conditions.needs_size() {
+        let Some(size) = metadata.size_bytes() else {
+            return false;
+        };
+        if conditions.min_size_bytes.is_some_and(|min| size < min)
+            || conditions.max_size_bytes.is_some_and(|max| size > max)
+        {
+            return false;
+        }
+    }
+
+    if conditions.last_modified_before.is_some() || conditions.older_than_days.is_some() {
+        let Some(modified) = metadata.modified() else {
+            return false;
+        };
+        if conditions.last_modified_before.is_some_and(|before| modified >= SystemTime::from(before)) {
+            return false;
+        }
+        // 未来の日時は経過 0 として扱う
+        let age = now.duration_since(modified).unwrap_or_default();
+        if conditions.older_than_days.is_some_and(|days| age < Duration::from_secs(days as u64 * SECONDS_PER_DAY)) {
+            return false;
+        }
+    }
+
+    if conditions.min_item_count.is_some() || conditions.max_item_count.is_some() {
+        let Some(count) = metadata.item_count() else {
+            return false;
+        };
+        if conditions.min_item_count.is_some_and(|min| count < min)
+            || conditions.max_item_count.is_some_and(|max| count > max)
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::ContainsFilesCondition;
+
+    #[derive(Default)]
+    struct Metadata {
+        size: Option<u64>,
+        age_days: u64,
+        items: Option<u64>,
+        files: Vec<&'static str>,
+    }
+
+    impl EntryMetadata for Metadata {
+        fn size_bytes(&self) -> Option<u64> {
+            self.size
+        }
+
+        fn modified(&self) -> Option<SystemTime> {
+            SystemTime::now().checked_sub(Duration::from_secs(self.age_days * SECONDS_PER_DAY))
+        }
+
+        fn item_count(&self) -> Option<u64> {
+            self.items
+        }
+
+        fn count_files(&self, _max_depth: u32, _limit: u64, accept: &dyn Fn(&str) -> bool) -> Option<u64> {
+            Some(self.files.iter().filter(|name| accept(name)).count() as u64)
+        }
+    }
+
+    fn is_met(conditions: &RuleConditions, metadata: Option<&dyn EntryMetadata>) -> bool {
+        ConditionEvaluator::new(conditions).unwrap().is_met(metadata, SystemTime::now())
+    }
+
+    #[test]
+    fn test_large_and_stale_folders() {
+        // 5 GB 以上で 1 年以上更新されていないフォルダ
+        let conditions = RuleConditions {
+            min_size_bytes: Some(5 * 1024 * 1024 * 1024),
+            older_than_days: Some(365),
+            ..Default::default()
+        };
+
+        let stale = Metadata { size: Some(6 * 1024 * 1024 * 1024), age_days: 400, ..Default::default() };
+        assert!(is_met(&conditions, Some(&stale)));
+
+        let recent = Metadata { age_days: 30, ..stale };
+        assert!(!is_met(&conditions, Some(&recent)));
+
+        let unknown_size = Metadata { size: None, age_days: 400, ..Default::default() };
+        assert!(!is_met(&conditions, Some(&unknown_size)));
+        assert!(!is_met(&conditions, None));
+    }
+
+    #[test]
+    fn test_item_count_and_empty_conditions() {
+        let non_empty = RuleConditions {
+            min_item_count: Some(1),
+            ..Default::default()
+        };
+
+        assert!(is_met(&non_empty, Some(&Metadata { items: Some(3), ..Default::default() })));
+        assert!(!is_met(&non_empty, Some(&Metadata { items: Some(0), ..Default::default() })));
+        assert!(is_met(&RuleConditions::default(), None));
+    }
+
+    #[test]
+    fn test_contains_files() {
+        let raw_photos = RuleConditions {
+            contains_files: Some(ContainsFilesCondition::new("*.cr2").with_min_count(2)),
+            ..Default::default()
+        };
+
+        let photos = Metadata { files: vec!["IMG_0001.CR2", "IMG_0002.cr2", "notes.txt"], ..Default::default() };
+        assert!(is_met(&raw_photos, Some(&photos)));
+
+        let single = Metadata { files: vec!["IMG_0001.CR2", "IMG_0001.jpg"], ..Default::default() };
+        assert!(!is_met(&raw_photos, Some(&single)));
+
+        let invalid = RuleConditions {
+            contains_files: Some(ContainsFilesCondition::new("[")),
+            ..Default::default()
+        };
+        assert!(ConditionEvaluator::new(&invalid).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+use std::time::SystemTime;
+use filemover_types::MatchTarget;
+
+/// ルールの条件を評価するためのメタデータ
+///
+/// 条件のあるルールのパターンに一致したときだけ呼ばれるので、サイズなどは必要になってから求めてよい。
+pub trait EntryMetadata: Sync {
+    fn size_bytes(&self) -> Option<u64>;
+    fn modified(&self) -> Option<SystemTime>;
+    fn item_count(&self) -> Option<u64>;
+    /// max_depth 階層（1 は直下）までのファイルのうち名前が accept を満たすものの数
+    ///
+    /// limit に達したら数えるのをやめてよい。
+    fn count_files(&self, max_depth: u32, limit: u64, accept: &dyn Fn(&str) -> bool) -> Option<u64>;
+}
+
+/// 照合するフォルダ（名前だけの場合、パスを対象にするパターンは一致しない）
+#[derive(Clone, Copy)]
+pub struct MatchContext<'a> {
+    pub name: &'a str,
+    pub path: Option<&'a Path>,
+    /// スキャンしたルート
+    pub root: Option<&'a Path>,
+    /// ない場合、条件のあるルールは一致しない
+    pub metadata: Option<&'a dyn EntryMetadata>,
+}
+
+impl<'a> MatchContext<'a> {
+    pub fn from_name(name: &'a str) -> Self {
+        Self { name, path: None, root: None, metadata: None }
+    }
+
+    pub fn from_path(name: &'a str, path: &'a Path, root: &'a Path) -> Self {
+        Self { name, path: Some(path), root: Some(root), metadata: None }
+    }
+
+    pub fn with_metadata(mut self, metadata: &'a dyn EntryMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// target に対応する文字列（区切りは `/`）
+    pub fn target_text(&self, target: MatchTarget) -> Option<String> {
+        match target {
+            MatchTarget::Name => Some(self.name.to_string()),
+            MatchTarget::FullPath => self.path.map(slash_path),
+            MatchTarget::RelativePathFromRoot => {
+                let relative = self.path?.strip_prefix(self.root?).ok()?;
+                Some(slash_path(relative))
+            }
+        }
+    }
+}
+
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_text() {
+        let path = Path::new("/data/Projects/app/build");
+        let context = MatchContext::from_path("build", path, Path::new("/data"));
+
+        assert_eq!(context.target_text(MatchTarget::Name).unwrap(), "build");
+        assert_eq!(context.target_text(MatchTarget::FullPath).unwrap(), "/data/Projects/app/build");
+        assert_eq!(context.target_text(MatchTarget::RelativePathFromRoot).unwrap(), "Projects/app/build");
+        assert!(MatchContext::from_name("build").target_text(MatchTarget::FullPath).is_none());
+    }
+}
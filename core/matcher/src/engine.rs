@@ -1,136 +1,418 @@
 use std::collections::HashMap;
-use filemover_types::{Rule, PatternSpec, PatternKind, FileMoverError, NormalizationOptions};
+use std::time::SystemTime;
+use filemover_types::{Rule, PatternExpr, PatternSpec, PatternKind, MatchTarget, FileMoverError, NormalizationOptions, TieBreakStrategy};
+use crate::conditions::ConditionEvaluator;
+use crate::context::MatchContext;
+use crate::explain::{ComparedText, MatchExplanation, RuleEvaluation, RuleOutcome};
 use crate::normalizer::TextNormalizer;
-use crate::matcher::{PatternMatcher, GlobMatcher, RegexMatcher, ContainsMatcher};
+use crate::matcher::{PatternMatcher, GlobMatcher, RegexMatcher, ContainsMatcher, FuzzyMatcher, ExtensionMatcher};
+
+/// パターン種類・照合対象・正規化の組ごとに 1 つのマッチャーを作る
+type MatcherKey = (PatternKind, MatchTarget, NormalizationOptions);
+
+/// マッチャーと、その中でのパターン番号
+type PatternRef = (MatcherKey, usize);
+
+/// ルールの式をマッチャーの参照に置き換えたもの
+enum CompiledExpr {
+    Pattern(PatternRef),
+    All(Vec<CompiledExpr>),
+    Any(Vec<CompiledExpr>),
+    Not(Box<CompiledExpr>),
+}
+
+struct CompiledRule {
+    pattern: PatternRef,
+    expression: Option<CompiledExpr>,
+    conditions: ConditionEvaluator,
+}
 
 pub struct MatchingEngine {
     rules: Vec<Rule>,
-    matchers: HashMap<PatternKind, Box<dyn PatternMatcher>>,
-    normalizer: TextNormalizer,
+    exclude_patterns: Vec<PatternSpec>,
+    matchers: HashMap<MatcherKey, Box<dyn PatternMatcher>>,
+    /// rules と同じ順
+    compiled: Vec<CompiledRule>,
+    /// exclude_patterns と同じ順
+    exclusions: Vec<PatternRef>,
+}
+
+/// 優先度順に並んだ一致ルールから使うものを選ぶ（Interactive は利用者が選び直すまで優先度順）
+pub fn select_rule<'a>(rules: &[&'a Rule], tie_break: TieBreakStrategy) -> Option<&'a Rule> {
+    match tie_break {
+        TieBreakStrategy::Priority | TieBreakStrategy::Interactive => rules.first().copied(),
+        TieBreakStrategy::MostSpecific => {
+            // 同じ具体さなら先（優先度の高い方）を残す
+            rules.iter().copied().fold(None, |best: Option<&Rule>, rule| match best {
+                Some(best) if specificity(best) >= specificity(rule) => Some(best),
+                _ => Some(rule),
+            })
+        }
+    }
+}
+
+/// パターンと式の葉の具体さの合計
+fn specificity(rule: &Rule) -> usize {
+    let leaves = rule.expression.iter().flat_map(|expr| expr.leaves());
+    rule.pattern.literal_len() + leaves.map(PatternSpec::literal_len).sum::<usize>()
 }
 
 impl MatchingEngine {
     pub fn new(rules: Vec<Rule>, normalization: NormalizationOptions) -> Result<Self, FileMoverError> {
-        let normalizer = TextNormalizer::new(normalization.clone());
-        let mut matchers: HashMap<PatternKind, Box<dyn PatternMatcher>> = HashMap::new();
+        Self::with_exclude_patterns(rules, Vec::new(), normalization)
+    }
 
-        // パターン種類別にグループ化
-        let glob_patterns: Vec<_> = rules.iter()
-            .map(|r| &r.pattern)
-            .filter(|p| matches!(p.kind, PatternKind::Glob))
-            .collect();
-        
-        let regex_patterns: Vec<_> = rules.iter()
-            .map(|r| &r.pattern)
-            .filter(|p| matches!(p.kind, PatternKind::Regex))
-            .collect();
-        
-        let contains_patterns: Vec<_> = rules.iter()
-            .map(|r| &r.pattern)
-            .filter(|p| matches!(p.kind, PatternKind::Contains))
+    /// 移動ルールとは別の除外パターン付きで作る（除外パターンは全体の正規化で照合する）
+    pub fn with_exclude_patterns(
+        rules: Vec<Rule>,
+        exclude_patterns: Vec<PatternSpec>,
+        normalization: NormalizationOptions,
+    ) -> Result<Self, FileMoverError> {
+        // パターン種類・照合対象・正規化別にグループ化（式の葉も同じマッチャーに入れる）
+        // 正規化を上書きしていないルールは全体の設定でまとめる
+        let mut groups: HashMap<MatcherKey, Vec<PatternSpec>> = HashMap::new();
+        let mut exclusion_group = PatternGroups { groups: &mut groups, normalization: &normalization };
+        let exclusions = exclude_patterns.iter()
+            .map(|spec| exclusion_group.add_pattern(spec))
             .collect();
 
-        // 各種マッチャーを作成
-        if !glob_patterns.is_empty() {
-            let glob_specs: Vec<PatternSpec> = glob_patterns.into_iter().cloned().collect();
-            let matcher = GlobMatcher::new(&glob_specs, TextNormalizer::new(normalization.clone()))?;
-            matchers.insert(PatternKind::Glob, Box::new(matcher));
-        }
-
-        if !regex_patterns.is_empty() {
-            let regex_specs: Vec<PatternSpec> = regex_patterns.into_iter().cloned().collect();
-            let matcher = RegexMatcher::new(&regex_specs, TextNormalizer::new(normalization.clone()))?;
-            matchers.insert(PatternKind::Regex, Box::new(matcher));
-        }
+        let compiled = rules.iter()
+            .map(|rule| {
+                let normalization = rule.normalization.as_ref().unwrap_or(&normalization);
+                let mut group = PatternGroups { groups: &mut groups, normalization };
+                Ok(CompiledRule {
+                    pattern: group.add_pattern(&rule.pattern),
+                    expression: rule.expression.as_ref().map(|expr| group.compile_expr(expr)),
+                    conditions: ConditionEvaluator::new(&rule.conditions)?,
+                })
+            })
+            .collect::<Result<Vec<_>, FileMoverError>>()?;
 
-        if !contains_patterns.is_empty() {
-            let contains_specs: Vec<PatternSpec> = contains_patterns.into_iter().cloned().collect();
-            let matcher = ContainsMatcher::new(&contains_specs, TextNormalizer::new(normalization.clone()))?;
-            matchers.insert(PatternKind::Contains, Box::new(matcher));
+        // 各種マッチャーを作成
+        let mut matchers: HashMap<MatcherKey, Box<dyn PatternMatcher>> = HashMap::new();
+        for (key, specs) in groups {
+            let normalizer = TextNormalizer::new(key.2.clone());
+            let matcher: Box<dyn PatternMatcher> = match key.0 {
+                PatternKind::Glob => Box::new(GlobMatcher::new(&specs, normalizer)?),
+                PatternKind::Regex => Box::new(RegexMatcher::new(&specs, normalizer)?),
+                PatternKind::Contains => Box::new(ContainsMatcher::new(&specs, normalizer)?),
+                PatternKind::Fuzzy => Box::new(FuzzyMatcher::new(&specs, normalizer)?),
+                PatternKind::Extension => Box::new(ExtensionMatcher::new(&specs, normalizer)?),
+            };
+            matchers.insert(key, matcher);
         }
 
         Ok(Self {
             rules,
+            exclude_patterns,
             matchers,
-            normalizer,
+            compiled,
+            exclusions,
         })
     }
 
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// 名前だけで照合する（パスを対象にするパターンは一致しない）
     pub fn find_matching_rule(&self, folder_name: &str) -> Result<Option<&Rule>, FileMoverError> {
-        // 除外ルールを最初にチェック
-        for rule in &self.rules {
-            if !rule.enabled {
-                continue;
+        self.find_matching_rule_for(&MatchContext::from_name(folder_name))
+    }
+
+    pub fn find_matching_rule_for(&self, context: &MatchContext) -> Result<Option<&Rule>, FileMoverError> {
+        // 同じマッチャーの結果は 1 回の照合の中で使い回す
+        let mut matched: HashMap<&MatcherKey, Vec<usize>> = HashMap::new();
+
+        // 除外パターンと除外ルールを最初にチェック
+        if self.is_excluded(context, &mut matched)? {
+            return Ok(None);
+        }
+
+        // 優先度順でマッチングルールを検索
+        for (index, rule) in self.rules_by_priority() {
+            if self.is_rule_match(index, context, &mut matched)? {
+                return Ok(Some(rule));
             }
+        }
 
-            if rule.pattern.is_exclude {
-                if self.is_pattern_match(&rule.pattern, folder_name)? {
-                    return Ok(None); // 除外対象
-                }
+        Ok(None)
+    }
+
+    /// 一致する移動ルールすべて（優先度順。除外された場合は空）
+    pub fn find_matching_rules_for(&self, context: &MatchContext) -> Result<Vec<&Rule>, FileMoverError> {
+        let mut matched: HashMap<&MatcherKey, Vec<usize>> = HashMap::new();
+        if self.is_excluded(context, &mut matched)? {
+            return Ok(Vec::new());
+        }
+
+        let mut rules = Vec::new();
+        for (index, rule) in self.rules_by_priority() {
+            if self.is_rule_match(index, context, &mut matched)? {
+                rules.push(rule);
             }
         }
+        Ok(rules)
+    }
 
-        // 優先度順でマッチングルールを検索
-        let mut sorted_rules: Vec<&Rule> = self.rules.iter()
-            .filter(|r| r.enabled && !r.pattern.is_exclude)
+    /// 一致したルールすべてのタグ（優先度順、重複なし。除外された場合は空）
+    pub fn tags_for(&self, context: &MatchContext) -> Result<Vec<&str>, FileMoverError> {
+        let mut tagged: Vec<(usize, &Rule)> = self.rules.iter()
+            .enumerate()
+            .filter(|(_, r)| r.enabled && !r.pattern.is_exclude && !r.tags.is_empty())
             .collect();
-        sorted_rules.sort_by_key(|r| r.priority);
+        if tagged.is_empty() {
+            return Ok(Vec::new());
+        }
+        tagged.sort_by_key(|(_, r)| r.priority);
 
-        for rule in sorted_rules {
-            if self.is_pattern_match(&rule.pattern, folder_name)? {
-                return Ok(Some(rule));
+        let mut matched: HashMap<&MatcherKey, Vec<usize>> = HashMap::new();
+        if self.is_excluded(context, &mut matched)? {
+            return Ok(Vec::new());
+        }
+
+        let mut tags: Vec<&str> = Vec::new();
+        for (index, rule) in tagged {
+            if self.is_rule_match(index, context, &mut matched)? {
+                for tag in &rule.tags {
+                    if !tags.contains(&tag.as_str()) {
+                        tags.push(tag);
+                    }
+                }
             }
         }
+        Ok(tags)
+    }
 
+    /// フォルダを対象外にした除外ルール（除外パターンに一致した場合は None）
+    pub fn excluding_rule_for(&self, context: &MatchContext) -> Result<Option<&Rule>, FileMoverError> {
+        if !self.rules.iter().any(|r| r.enabled && r.pattern.is_exclude) {
+            return Ok(None);
+        }
+        let mut matched: HashMap<&MatcherKey, Vec<usize>> = HashMap::new();
+        if self.excluding_pattern(context, &mut matched)?.is_some() {
+            return Ok(None);
+        }
+        self.excluding_rule(context, &mut matched)
+    }
+
+    /// 除外パターンか除外ルールに一致するか
+    fn is_excluded<'a>(
+        &'a self,
+        context: &MatchContext,
+        matched: &mut HashMap<&'a MatcherKey, Vec<usize>>,
+    ) -> Result<bool, FileMoverError> {
+        if self.excluding_pattern(context, matched)?.is_some() {
+            return Ok(true);
+        }
+        Ok(self.excluding_rule(context, matched)?.is_some())
+    }
+
+    /// 一致した最初の除外ルール
+    fn excluding_rule<'a>(
+        &'a self,
+        context: &MatchContext,
+        matched: &mut HashMap<&'a MatcherKey, Vec<usize>>,
+    ) -> Result<Option<&'a Rule>, FileMoverError> {
+        for (index, rule) in self.rules.iter().enumerate() {
+            if rule.enabled && rule.pattern.is_exclude && self.is_rule_match(index, context, matched)? {
+                return Ok(Some(rule));
+            }
+        }
         Ok(None)
     }
 
-    fn is_pattern_match(&self, pattern: &PatternSpec, text: &str) -> Result<bool, FileMoverError> {
-        match self.matchers.get(&pattern.kind) {
-            Some(matcher) => matcher.is_match(text),
-            None => {
-                // フォールバック: 個別マッチング
-                self.match_individual_pattern(pattern, text)
+    /// 名前だけで照合したときの経過
+    pub fn explain(&self, folder_name: &str) -> Result<MatchExplanation<'_>, FileMoverError> {
+        self.explain_for(&MatchContext::from_name(folder_name))
+    }
+
+    /// find_matching_rule_for と同じ順にすべてのルールを評価し、各ルールの結果を返す
+    pub fn explain_for(&self, context: &MatchContext) -> Result<MatchExplanation<'_>, FileMoverError> {
+        let mut matched: HashMap<&MatcherKey, Vec<usize>> = HashMap::new();
+        let mut evaluations = Vec::with_capacity(self.rules.len());
+
+        let excluded_by_pattern = self.excluding_pattern(context, &mut matched)?;
+        let mut excluded = excluded_by_pattern.is_some();
+        for (index, rule) in self.rules.iter().enumerate() {
+            if !rule.enabled || !rule.pattern.is_exclude {
+                continue;
             }
+            let evaluation = if excluded {
+                RuleEvaluation { rule, outcome: RuleOutcome::SkippedByExclusion, compared: None }
+            } else {
+                self.explain_rule(index, context, &mut matched)?
+            };
+            excluded |= evaluation.outcome == RuleOutcome::Matched;
+            evaluations.push(evaluation);
         }
+
+        let mut found = false;
+        for (index, rule) in self.rules_by_priority() {
+            let evaluation = if excluded {
+                RuleEvaluation { rule, outcome: RuleOutcome::SkippedByExclusion, compared: None }
+            } else if found {
+                RuleEvaluation { rule, outcome: RuleOutcome::SkippedByPriority, compared: None }
+            } else {
+                self.explain_rule(index, context, &mut matched)?
+            };
+            found |= evaluation.outcome == RuleOutcome::Matched;
+            evaluations.push(evaluation);
+        }
+
+        evaluations.extend(
+            self.rules.iter()
+                .filter(|r| !r.enabled)
+                .map(|rule| RuleEvaluation { rule, outcome: RuleOutcome::Disabled, compared: None }),
+        );
+
+        Ok(MatchExplanation::new(evaluations, excluded_by_pattern))
     }
 
-    fn match_individual_pattern(&self, pattern: &PatternSpec, text: &str) -> Result<bool, FileMoverError> {
-        let normalized = self.normalizer.normalize(text)?;
-        let pattern_normalized = self.normalizer.normalize(&pattern.value)?;
+    fn explain_rule<'a>(
+        &'a self,
+        index: usize,
+        context: &MatchContext,
+        matched: &mut HashMap<&'a MatcherKey, Vec<usize>>,
+    ) -> Result<RuleEvaluation<'a>, FileMoverError> {
+        let outcome = self.evaluate_rule(index, context, matched)?;
+
+        // ルールのパターンと比べた文字列（式の葉は対象が異なる場合があるので含めない）
+        let ((_, target, normalization), _) = &self.compiled[index].pattern;
+        let compared = match context.target_text(*target) {
+            Some(original) => Some(ComparedText {
+                target: *target,
+                normalized: TextNormalizer::new(normalization.clone()).normalize(&original)?,
+                original,
+            }),
+            None => None,
+        };
+
+        Ok(RuleEvaluation { rule: &self.rules[index], outcome, compared })
+    }
 
-        match pattern.kind {
-            PatternKind::Glob => {
-                use globset::Glob;
-                let glob = Glob::new(&pattern_normalized)
-                    .map_err(|e| FileMoverError::Pattern {
-                        message: format!("Invalid glob pattern: {}", e),
-                    })?;
-                Ok(glob.compile_matcher().is_match(&normalized))
+    /// 一致した最初の除外パターン
+    fn excluding_pattern<'a>(
+        &'a self,
+        context: &MatchContext,
+        matched: &mut HashMap<&'a MatcherKey, Vec<usize>>,
+    ) -> Result<Option<&'a PatternSpec>, FileMoverError> {
+        for (spec, pattern) in self.exclude_patterns.iter().zip(&self.exclusions) {
+            if self.is_pattern_match(pattern, context, matched)? {
+                return Ok(Some(spec));
             }
-            PatternKind::Regex => {
-                use regex::RegexBuilder;
-                let regex = RegexBuilder::new(&pattern_normalized)
-                    .case_insensitive(pattern.case_insensitive)
-                    .build()
-                    .map_err(|e| FileMoverError::Pattern {
-                        message: format!("Invalid regex pattern: {}", e),
-                    })?;
-                Ok(regex.is_match(&normalized))
+        }
+        Ok(None)
+    }
+
+    /// 有効な通常ルール（優先度順、同じ優先度は定義順）
+    fn rules_by_priority(&self) -> Vec<(usize, &Rule)> {
+        let mut sorted_rules: Vec<(usize, &Rule)> = self.rules.iter()
+            .enumerate()
+            .filter(|(_, r)| r.enabled && !r.pattern.is_exclude && !r.is_tag_only())
+            .collect();
+        sorted_rules.sort_by_key(|(_, r)| r.priority);
+        sorted_rules
+    }
+
+    fn is_rule_match<'a>(
+        &'a self,
+        index: usize,
+        context: &MatchContext,
+        matched: &mut HashMap<&'a MatcherKey, Vec<usize>>,
+    ) -> Result<bool, FileMoverError> {
+        Ok(self.evaluate_rule(index, context, matched)? == RuleOutcome::Matched)
+    }
+
+    fn evaluate_rule<'a>(
+        &'a self,
+        index: usize,
+        context: &MatchContext,
+        matched: &mut HashMap<&'a MatcherKey, Vec<usize>>,
+    ) -> Result<RuleOutcome, FileMoverError> {
+        let compiled = &self.compiled[index];
+        if !self.is_pattern_match(&compiled.pattern, context, matched)? {
+            return Ok(RuleOutcome::NotMatched);
+        }
+        if let Some(expression) = &compiled.expression {
+            if !self.is_expr_match(expression, context, matched)? {
+                return Ok(RuleOutcome::NotMatched);
             }
-            PatternKind::Contains => {
-                let haystack = if pattern.case_insensitive {
-                    normalized
-                } else {
-                    text.to_string()
-                };
-                let needle = if pattern.case_insensitive {
-                    pattern_normalized
-                } else {
-                    pattern.value.clone()
-                };
-                Ok(haystack.contains(&needle))
+        }
+
+        // パターンに一致したものだけメタデータ条件を調べる
+        if compiled.conditions.is_met(context.metadata, SystemTime::now()) {
+            Ok(RuleOutcome::Matched)
+        } else {
+            Ok(RuleOutcome::ConditionsNotMet)
+        }
+    }
+
+    fn is_expr_match<'a>(
+        &'a self,
+        expr: &'a CompiledExpr,
+        context: &MatchContext,
+        matched: &mut HashMap<&'a MatcherKey, Vec<usize>>,
+    ) -> Result<bool, FileMoverError> {
+        match expr {
+            CompiledExpr::Pattern(pattern) => self.is_pattern_match(pattern, context, matched),
+            CompiledExpr::All(exprs) => {
+                for expr in exprs {
+                    if !self.is_expr_match(expr, context, matched)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            CompiledExpr::Any(exprs) => {
+                for expr in exprs {
+                    if self.is_expr_match(expr, context, matched)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
             }
+            CompiledExpr::Not(expr) => Ok(!self.is_expr_match(expr, context, matched)?),
+        }
+    }
+
+    fn is_pattern_match<'a>(
+        &'a self,
+        (key, pattern_index): &'a PatternRef,
+        context: &MatchContext,
+        matched: &mut HashMap<&'a MatcherKey, Vec<usize>>,
+    ) -> Result<bool, FileMoverError> {
+        if !matched.contains_key(key) {
+            let patterns = match context.target_text(key.1) {
+                Some(text) => self.matchers[key].matching_patterns(&text)?,
+                None => Vec::new(),
+            };
+            matched.insert(key, patterns);
+        }
+        Ok(matched[key].contains(pattern_index))
+    }
+}
+
+/// 1 つのルールのパターンをマッチャーごとのグループに振り分ける
+struct PatternGroups<'a> {
+    groups: &'a mut HashMap<MatcherKey, Vec<PatternSpec>>,
+    normalization: &'a NormalizationOptions,
+}
+
+impl PatternGroups<'_> {
+    fn add_pattern(&mut self, spec: &PatternSpec) -> PatternRef {
+        let key = (spec.kind.clone(), spec.match_target, self.normalization.clone());
+        let group = self.groups.entry(key.clone()).or_default();
+        group.push(spec.clone());
+        (key, group.len() - 1)
+    }
+
+    fn compile_expr(&mut self, expr: &PatternExpr) -> CompiledExpr {
+        match expr {
+            PatternExpr::Pattern(spec) => CompiledExpr::Pattern(self.add_pattern(spec)),
+            PatternExpr::All(exprs) => CompiledExpr::All(exprs.iter().map(|e| self.compile_expr(e)).collect()),
+            PatternExpr::Any(exprs) => CompiledExpr::Any(exprs.iter().map(|e| self.compile_expr(e)).collect()),
+            PatternExpr::Not(expr) => CompiledExpr::Not(Box::new(self.compile_expr(expr))),
         }
     }
 }
@@ -138,7 +420,7 @@ impl MatchingEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use filemover_types::{ConflictPolicy, PatternSpec};
+    use filemover_types::PatternSpec;
     use std::path::PathBuf;
 
     #[test]
@@ -184,5 +466,190 @@ mod tests {
         assert!(engine.find_matching_rule("temp_folder").unwrap().is_none());
         // その他はマッチする
         assert!(engine.find_matching_rule("normal_folder").unwrap().is_some());
+
+        let excluded_by = engine.excluding_rule_for(&MatchContext::from_name("temp_folder")).unwrap();
+        assert_eq!(excluded_by.unwrap().pattern.value, "temp*");
+        assert!(engine.excluding_rule_for(&MatchContext::from_name("normal_folder")).unwrap().is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_full_path_and_relative_targets() {
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("**/Projects/*/build").with_target(MatchTarget::FullPath),
+                PathBuf::from("D:\\Archive\\Builds"),
+                "{name}".to_string(),
+            ).with_priority(1),
+            Rule::new(
+                PatternSpec::new_regex("^temp/").with_target(MatchTarget::RelativePathFromRoot),
+                PathBuf::from("D:\\Archive\\Temp"),
+                "{name}".to_string(),
+            ).with_priority(2),
+        ];
+        let engine = MatchingEngine::new(rules, NormalizationOptions::default()).unwrap();
+        let root = std::path::Path::new("/data");
+
+        let build = std::path::Path::new("/data/Projects/app/build");
+        let rule = engine.find_matching_rule_for(&MatchContext::from_path("build", build, root)).unwrap();
+        assert_eq!(rule.unwrap().priority, 1);
+
+        let nested = std::path::Path::new("/data/Projects/app/src/build");
+        assert!(engine.find_matching_rule_for(&MatchContext::from_path("build", nested, root)).unwrap().is_none());
+
+        let temp = std::path::Path::new("/data/temp/cache");
+        let rule = engine.find_matching_rule_for(&MatchContext::from_path("cache", temp, root)).unwrap();
+        assert_eq!(rule.unwrap().priority, 2);
+
+        // 名前だけではパスのパターンは一致しない
+        assert!(engine.find_matching_rule("build").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_composite_expression() {
+        // *backup* かつ keep を含まず、old または 2023 を含む
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("*backup*"),
+                PathBuf::from("D:\\Archive\\Backups"),
+                "{name}".to_string(),
+            ).with_expression(PatternExpr::All(vec![
+                PatternExpr::Not(Box::new(PatternSpec::new_contains("keep").into())),
+                PatternExpr::Any(vec![
+                    PatternSpec::new_contains("old").into(),
+                    PatternSpec::new_regex("20(2[0-3])").into(),
+                ]),
+            ])),
+        ];
+        let engine = MatchingEngine::new(rules, NormalizationOptions::default()).unwrap();
+
+        assert!(engine.find_matching_rule("old_backup").unwrap().is_some());
+        assert!(engine.find_matching_rule("backup_2023").unwrap().is_some());
+        assert!(engine.find_matching_rule("backup_2024").unwrap().is_none());
+        assert!(engine.find_matching_rule("old_backup_KEEP").unwrap().is_none());
+        assert!(engine.find_matching_rule("old_photos").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_explain_reports_each_rule() {
+        let rules = vec![
+            Rule::new(PatternSpec::new_glob("*.tmp").exclude(), PathBuf::from("D:\\Trash"), "{name}".to_string()),
+            Rule::new(PatternSpec::new_contains("invoice"), PathBuf::from("D:\\Invoices"), "{name}".to_string())
+                .with_priority(1),
+            Rule::new(PatternSpec::new_glob("*2023*"), PathBuf::from("D:\\2023"), "{name}".to_string())
+                .with_priority(2),
+            Rule::new(PatternSpec::new_glob("*"), PathBuf::from("D:\\Other"), "{name}".to_string())
+                .with_priority(3),
+        ];
+        let engine = MatchingEngine::new(rules, NormalizationOptions::default()).unwrap();
+
+        let explanation = engine.explain("Invoices_2023").unwrap();
+        let outcomes: Vec<RuleOutcome> = explanation.evaluations.iter().map(|e| e.outcome).collect();
+        assert_eq!(outcomes, vec![
+            RuleOutcome::NotMatched,
+            RuleOutcome::Matched,
+            RuleOutcome::SkippedByPriority,
+            RuleOutcome::SkippedByPriority,
+        ]);
+        assert_eq!(explanation.matched_rule.unwrap().priority, 1);
+
+        // 評価したルールには正規化後の文字列が残る
+        let compared = explanation.evaluations[1].compared.as_ref().unwrap();
+        assert_eq!(compared.original, "Invoices_2023");
+        assert_eq!(compared.normalized, "invoices_2023");
+        assert!(explanation.evaluations[2].compared.is_none());
+
+        let explanation = engine.explain("cache.tmp").unwrap();
+        assert!(explanation.excluded_by.is_some());
+        assert!(explanation.matched_rule.is_none());
+        assert!(explanation.evaluations[1..].iter().all(|e| e.outcome == RuleOutcome::SkippedByExclusion));
+    }
+
+    #[test]
+    fn test_per_rule_normalization() {
+        // 大文字小文字を区別し、発音区別記号も残す正規表現ルールと、緩い glob ルールの併用
+        let strict = NormalizationOptions {
+            normalize_case: false,
+            strip_diacritics: false,
+            ..NormalizationOptions::default()
+        };
+        let loose = NormalizationOptions {
+            strip_diacritics: true,
+            ..NormalizationOptions::default()
+        };
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_regex("^Café_[A-Z]+$").case_sensitive(),
+                PathBuf::from("D:\\Strict"),
+                "{name}".to_string(),
+            ).with_priority(1).with_normalization(strict),
+            Rule::new(PatternSpec::new_glob("cafe*"), PathBuf::from("D:\\Loose"), "{name}".to_string())
+                .with_priority(2),
+        ];
+        let engine = MatchingEngine::new(rules, loose).unwrap();
+
+        assert_eq!(engine.find_matching_rule("Café_MENU").unwrap().unwrap().priority, 1);
+        assert_eq!(engine.find_matching_rule("café_menu").unwrap().unwrap().priority, 2);
+        assert_eq!(engine.find_matching_rule("CAFE_MENU").unwrap().unwrap().priority, 2);
+    }
+
+    #[test]
+    fn test_exclude_patterns_are_separate_from_rules() {
+        let rules = vec![
+            Rule::new(PatternSpec::new_glob("*"), PathBuf::from("D:\\Archive"), "{name}".to_string()),
+        ];
+        let excludes = vec![PatternSpec::new_glob("node_modules"), PatternSpec::new_contains("keep")];
+        let engine = MatchingEngine::with_exclude_patterns(rules, excludes, NormalizationOptions::default()).unwrap();
+
+        assert!(engine.find_matching_rule("node_modules").unwrap().is_none());
+        assert!(engine.find_matching_rule("photos_KEEP").unwrap().is_none());
+        assert!(engine.find_matching_rule("photos").unwrap().is_some());
+
+        let explanation = engine.explain("photos_keep").unwrap();
+        assert_eq!(explanation.excluded_by_pattern.unwrap().value, "keep");
+        assert_eq!(explanation.evaluations[0].outcome, RuleOutcome::SkippedByExclusion);
+    }
+
+    #[test]
+    fn test_all_matching_rules_and_tie_break() {
+        let rules = vec![
+            Rule::new(PatternSpec::new_contains("photo"), PathBuf::from("D:\\Photos"), "{name}".to_string())
+                .with_priority(1),
+            Rule::new(PatternSpec::new_glob("photo_raw_*"), PathBuf::from("D:\\Raw"), "{name}".to_string())
+                .with_priority(2),
+            Rule::new(PatternSpec::new_glob("*.tmp"), PathBuf::from("D:\\Temp"), "{name}".to_string()),
+            Rule::new(PatternSpec::new_contains("keep").exclude(), PathBuf::new(), String::new()),
+        ];
+        let engine = MatchingEngine::new(rules, NormalizationOptions::default()).unwrap();
+
+        let matches = engine.find_matching_rules_for(&MatchContext::from_name("photo_raw_2024")).unwrap();
+        let priorities: Vec<u32> = matches.iter().map(|r| r.priority).collect();
+        assert_eq!(priorities, vec![1, 2]);
+
+        assert_eq!(select_rule(&matches, TieBreakStrategy::Priority).unwrap().priority, 1);
+        assert_eq!(select_rule(&matches, TieBreakStrategy::Interactive).unwrap().priority, 1);
+        assert_eq!(select_rule(&matches, TieBreakStrategy::MostSpecific).unwrap().priority, 2);
+
+        assert!(engine.find_matching_rules_for(&MatchContext::from_name("photo_keep")).unwrap().is_empty());
+        assert!(select_rule(&[], TieBreakStrategy::MostSpecific).is_none());
+    }
+
+    #[test]
+    fn test_tag_only_rules_accumulate_tags() {
+        let rules = vec![
+            Rule::new(PatternSpec::new_contains("trip"), PathBuf::from("D:\\Trips"), "{name}".to_string())
+                .with_tags(vec!["travel".to_string()]),
+            Rule::tag_only(PatternSpec::new_contains("photo"), vec!["photos".to_string()]),
+            Rule::tag_only(PatternSpec::new_glob("*20[0-9][0-9]*"), vec!["dated".to_string(), "photos".to_string()])
+                .with_priority(5),
+        ];
+        let engine = MatchingEngine::new(rules, NormalizationOptions::default()).unwrap();
+
+        let context = MatchContext::from_name("trip_photos_2023");
+        assert_eq!(engine.find_matching_rule_for(&context).unwrap().unwrap().priority, 0);
+        assert_eq!(engine.find_matching_rules_for(&context).unwrap().len(), 1);
+        assert_eq!(engine.tags_for(&context).unwrap(), vec!["travel", "photos", "dated"]);
+
+        // タグだけのルールは移動先にならない
+        assert!(engine.find_matching_rule("photos_2023").unwrap().is_none());
+    }
+}
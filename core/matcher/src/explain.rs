@@ -0,0 +1,58 @@
+use serde::Serialize;
+use filemover_types::{MatchTarget, PatternSpec, Rule};
+
+/// ルールを評価した結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RuleOutcome {
+    /// 一致した（除外ルールの場合はフォルダが対象外になる）
+    Matched,
+    /// パターンは一致したが条件を満たさない
+    ConditionsNotMet,
+    NotMatched,
+    /// 除外ルールに一致したため評価しなかった
+    SkippedByExclusion,
+    /// 優先度の高いルールが先に一致したため評価しなかった
+    SkippedByPriority,
+    Disabled,
+}
+
+/// ルールのパターンと比べた文字列
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComparedText {
+    pub target: MatchTarget,
+    pub original: String,
+    /// ルールの正規化を適用した後
+    pub normalized: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleEvaluation<'a> {
+    pub rule: &'a Rule,
+    pub outcome: RuleOutcome,
+    /// 評価しなかった場合や、照合対象の文字列がない場合は None
+    pub compared: Option<ComparedText>,
+}
+
+/// 1 つのフォルダについての照合の経過（評価した順。無効なルールは最後）
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchExplanation<'a> {
+    pub evaluations: Vec<RuleEvaluation<'a>>,
+    /// 一致した除外パターン（一致した場合、ルールはすべて評価しない）
+    pub excluded_by_pattern: Option<&'a PatternSpec>,
+    /// 一致した除外ルール
+    pub excluded_by: Option<&'a Rule>,
+    /// 採用されたルール
+    pub matched_rule: Option<&'a Rule>,
+}
+
+impl<'a> MatchExplanation<'a> {
+    pub(crate) fn new(evaluations: Vec<RuleEvaluation<'a>>, excluded_by_pattern: Option<&'a PatternSpec>) -> Self {
+        let find = |exclude: bool| evaluations.iter()
+            .find(|e| e.rule.pattern.is_exclude == exclude && e.outcome == RuleOutcome::Matched)
+            .map(|e| e.rule);
+        let excluded_by = find(true);
+        let matched_rule = find(false);
+
+        Self { evaluations, excluded_by_pattern, excluded_by, matched_rule }
+    }
+}
@@ -1,7 +1,17 @@
 pub mod normalizer;
 pub mod matcher;
 pub mod engine;
+pub mod context;
+pub mod conditions;
+pub mod explain;
+pub mod lint;
+pub mod cache;
 
 pub use normalizer::*;
 pub use matcher::*;
-pub use engine::*;
\ No newline at end of file
+pub use engine::*;
+pub use context::*;
+pub use conditions::*;
+pub use explain::*;
+pub use lint::*;
+pub use cache::*;
\ No newline at end of file
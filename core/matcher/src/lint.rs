@@ -0,0 +1,354 @@
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use filemover_types::{Rule, PatternSpec, PatternKind, MatchTarget, FileMoverError, NormalizationOptions, RuleConditions};
+use crate::context::MatchContext;
+use crate::engine::MatchingEngine;
+
+/// 何にでも一致するかを調べるための名前
+const CATCH_ALL_PROBES: &[&str] = &["a", "New folder", "2024-01-01", "x_y.z", "写真"];
+
+/// パスを対象にするパターンの例を置くルート
+const PROBE_ROOT: &str = "/probe";
+
+/// ルール同士の衝突の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RuleLintKind {
+    /// 同じ優先度で両方に一致するフォルダがある（どちらになるかは設定の順で決まる）
+    AmbiguousPriority,
+    /// 優先度の高いルールに必ず先に一致するため使われない
+    Shadowed,
+    /// 何にでも一致するルールが、より具体的なルールより先に評価される
+    CatchAllAbove,
+}
+
+/// 衝突の報告
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleLint<'a> {
+    pub kind: RuleLintKind,
+    /// 先に評価されるルール
+    pub rule: &'a Rule,
+    /// 影響を受けるルール
+    pub other: &'a Rule,
+    /// 両方に一致するフォルダの例
+    pub example: String,
+}
+
+/// 照合の対象ごとにパターンから作った例で試すルール
+struct LintedRule<'a> {
+    rule: &'a Rule,
+    /// 条件を除いたルールだけのエンジン（条件はメタデータがないと評価できないため）
+    engine: MatchingEngine,
+    /// ルール自身に一致する例
+    examples: Vec<String>,
+}
+
+impl<'a> LintedRule<'a> {
+    fn new(rule: &'a Rule, normalization: &NormalizationOptions) -> Result<Self, FileMoverError> {
+        let pattern_only = Rule { conditions: RuleConditions::default(), ..rule.clone() };
+        let engine = MatchingEngine::new(vec![pattern_only], normalization.clone())?;
+
+        let mut linted = Self { rule, engine, examples: Vec::new() };
+        let mut examples = Vec::new();
+        for example in example_texts(&rule.pattern) {
+            if !examples.contains(&example) && linted.is_match(&example)? {
+                examples.push(example);
+            }
+        }
+        linted.examples = examples;
+        Ok(linted)
+    }
+
+    fn target(&self) -> MatchTarget {
+        self.rule.pattern.match_target
+    }
+
+    /// 照合対象の文字列（パスの場合はルートからの相対）で照合する
+    fn is_match(&self, text: &str) -> Result<bool, FileMoverError> {
+        let root = Path::new(PROBE_ROOT);
+        let path = match self.target() {
+            MatchTarget::Name => None,
+            MatchTarget::FullPath => Some(PathBuf::from(text)),
+            MatchTarget::RelativePathFromRoot => Some(root.join(text)),
+        };
+        let context = match &path {
+            Some(path) => {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or(text);
+                MatchContext::from_path(name, path, root)
+            }
+            None => MatchContext::from_name(text),
+        };
+        Ok(self.engine.find_matching_rule_for(&context)?.is_some())
+    }
+
+    fn is_catch_all(&self) -> Result<bool, FileMoverError> {
+        for probe in CATCH_ALL_PROBES {
+            let text = match self.target() {
+                MatchTarget::Name => probe.to_string(),
+                MatchTarget::FullPath => format!("{}/{}", PROBE_ROOT, probe),
+                MatchTarget::RelativePathFromRoot => format!("dir/{}", probe),
+            };
+            if !self.is_match(&text)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// other の例のうち self にも一致する最初のもの
+    fn first_shared_example(&self, other: &LintedRule) -> Result<Option<String>, FileMoverError> {
+        for example in &other.examples {
+            if self.is_match(example)? {
+                return Ok(Some(example.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 両方に一致する例（互いの例と、それをつなげたものを試す）
+    fn shared_example(&self, other: &LintedRule) -> Result<Option<String>, FileMoverError> {
+        if let Some(example) = self.first_shared_example(other)? {
+            return Ok(Some(example));
+        }
+        if let Some(example) = other.first_shared_example(self)? {
+            return Ok(Some(example));
+        }
+        for mine in &self.examples {
+            for theirs in &other.examples {
+                // 単語全体で一致するパターンのために区切りを入れたものも試す
+                let candidates = [
+                    format!("{}{}", mine, theirs),
+                    format!("{}{}", theirs, mine),
+                    format!("{}_{}", mine, theirs),
+                    format!("{}_{}", theirs, mine),
+                ];
+                for joined in candidates {
+                    if self.is_match(&joined)? && other.is_match(&joined)? {
+                        return Ok(Some(joined));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn matches_all_examples_of(&self, other: &LintedRule) -> Result<bool, FileMoverError> {
+        for example in &other.examples {
+            if !self.is_match(example)? {
+                return Ok(false);
+            }
+        }
+        Ok(!other.examples.is_empty())
+    }
+}
+
+/// 有効な移動ルール同士の衝突を調べる
+///
+/// パターンから作った例で試すため、見つからない衝突もある（報告されたものには必ず実例がある）。
+pub fn lint_rules<'a>(
+    rules: &'a [Rule],
+    normalization: &NormalizationOptions,
+) -> Result<Vec<RuleLint<'a>>, FileMoverError> {
+    // エンジンと同じ評価順（優先度が同じなら設定の順）
+    let mut active: Vec<&Rule> = rules.iter()
+        .filter(|r| r.enabled && !r.pattern.is_exclude && !r.is_tag_only())
+        .collect();
+    active.sort_by_key(|r| r.priority);
+    let linted = active.into_iter()
+        .map(|rule| LintedRule::new(rule, normalization))
+        .collect::<Result<Vec<_>, FileMoverError>>()?;
+
+    let mut lints = Vec::new();
+    for (i, first) in linted.iter().enumerate() {
+        for second in &linted[i + 1..] {
+            if first.target() != second.target() {
+                continue;
+            }
+
+            if first.rule.priority == second.rule.priority {
+                if let Some(example) = first.shared_example(second)? {
+                    lints.push(RuleLint { kind: RuleLintKind::AmbiguousPriority, rule: first.rule, other: second.rule, example });
+                }
+                continue;
+            }
+
+            // 条件のあるルールは一致しないことがあるので、後のルールを隠さない
+            if !first.rule.conditions.is_empty() {
+                continue;
+            }
+            let Some(example) = first.first_shared_example(second)? else {
+                continue;
+            };
+            if first.is_catch_all()? {
+                lints.push(RuleLint { kind: RuleLintKind::CatchAllAbove, rule: first.rule, other: second.rule, example });
+            } else if first.matches_all_examples_of(second)? {
+                lints.push(RuleLint { kind: RuleLintKind::Shadowed, rule: first.rule, other: second.rule, example });
+            }
+        }
+    }
+
+    Ok(lints)
+}
+
+/// パターンに一致しそうな文字列（一致するかは呼び出し側で確かめる）
+fn example_texts(pattern: &PatternSpec) -> Vec<String> {
+    match pattern.kind {
+        PatternKind::Contains if pattern.whole_word => vec![pattern.value.clone(), format!("x_{}_x", pattern.value)],
+        PatternKind::Contains => vec![pattern.value.clone(), format!("x{}x", pattern.value)],
+        PatternKind::Fuzzy => vec![pattern.value.clone()],
+        PatternKind::Extension => pattern.extensions().map(|ext| format!("x.{}", ext)).collect(),
+        PatternKind::Glob => vec![glob_example(&pattern.value, ""), glob_example(&pattern.value, "x")],
+        PatternKind::Regex => vec![regex_example(&pattern.value)],
+    }
+}
+
+/// `*` を fill に、`?` と文字クラスを 1 文字に、`{a,b}` を最初の候補に置き換える
+fn glob_example(glob: &str, fill: &str) -> String {
+    let mut example = String::new();
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                while chars.peek() == Some(&'*') {
+                    chars.next();
+                }
+                example.push_str(fill);
+            }
+            '?' => example.push('x'),
+            '[' => {
+                if matches!(chars.peek(), Some('!') | Some('^')) {
+                    chars.next();
+                    example.push('_');
+                    chars.by_ref().take_while(|&c| c != ']').for_each(drop);
+                } else if let Some(first) = chars.next() {
+                    example.push(first);
+                    chars.by_ref().take_while(|&c| c != ']').for_each(drop);
+                }
+            }
+            '{' => {
+                let alternatives: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                example.push_str(alternatives.split(',').next().unwrap_or_default());
+            }
+            '\\' => example.extend(chars.next()),
+            _ => example.push(c),
+        }
+    }
+    example
+}
+
+/// 単純な正規表現から一致する文字列を作る（繰り返しは 1 回、選択肢は最初のもの）
+fn regex_example(regex: &str) -> String {
+    let mut example = String::new();
+    let mut chars = regex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '^' | '$' | '*' | '+' | '?' | ')' => {}
+            '(' => {
+                // (?i) や (?:...) の指定部分
+                if chars.peek() == Some(&'?') {
+                    while let Some(&next) = chars.peek() {
+                        if next == ':' || next == ')' {
+                            chars.next();
+                            break;
+                        }
+                        chars.next();
+                    }
+                }
+            }
+            '|' => break,
+            '.' => example.push('x'),
+            '[' => {
+                if chars.peek() == Some(&'^') {
+                    chars.next();
+                    example.push('_');
+                } else if let Some(first) = chars.next() {
+                    example.push(first);
+                }
+                chars.by_ref().take_while(|&c| c != ']').for_each(drop);
+            }
+            '{' => {
+                // 最小の回数だけ繰り返す（直前の 1 文字は出力済み）
+                let repeat: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let min = repeat.split(',').next().and_then(|n| n.trim().parse::<usize>().ok()).unwrap_or(1);
+                if let Some(last) = example.pop() {
+                    example.extend(std::iter::repeat_n(last, min));
+                }
+            }
+            '\\' => match chars.next() {
+                Some('d') => example.push('1'),
+                Some('w') => example.push('a'),
+                Some('s') => example.push(' '),
+                Some('b') | Some('B') => {}
+                Some(other) => example.push(other),
+                None => {}
+            },
+            _ => example.push(c),
+        }
+    }
+    example
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: PatternSpec, priority: u32) -> Rule {
+        Rule::new(pattern, PathBuf::from("/dest"), "{name}".to_string()).with_priority(priority)
+    }
+
+    fn lint(rules: &[Rule]) -> Vec<(RuleLintKind, String)> {
+        lint_rules(rules, &NormalizationOptions::default()).unwrap()
+            .into_iter()
+            .map(|l| (l.kind, l.example))
+            .collect()
+    }
+
+    #[test]
+    fn test_ambiguous_priority() {
+        let rules = vec![
+            rule(PatternSpec::new_contains("invoice"), 10),
+            rule(PatternSpec::new_glob("*2023*"), 10),
+        ];
+        let lints = lint(&rules);
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].0, RuleLintKind::AmbiguousPriority);
+
+        let example = &lints[0].1;
+        assert!(example.to_lowercase().contains("invoice") && example.contains("2023"));
+    }
+
+    #[test]
+    fn test_shadowed_and_catch_all() {
+        let rules = vec![
+            rule(PatternSpec::new_contains("project"), 1),
+            rule(PatternSpec::new_regex(r"^project_\d{4}$"), 2),
+            rule(PatternSpec::new_contains("backup"), 3),
+        ];
+        let lints = lint(&rules);
+        assert_eq!(lints, vec![(RuleLintKind::Shadowed, "project_1111".to_string())]);
+
+        let rules = vec![
+            rule(PatternSpec::new_glob("*"), 1),
+            rule(PatternSpec::new_contains("backup"), 2),
+        ];
+        let lints = lint(&rules);
+        assert_eq!(lints, vec![(RuleLintKind::CatchAllAbove, "backup".to_string())]);
+    }
+
+    #[test]
+    fn test_conditions_and_disjoint_rules_are_not_reported() {
+        let large = RuleConditions { min_size_bytes: Some(1024), ..Default::default() };
+        let rules = vec![
+            rule(PatternSpec::new_glob("*"), 1).with_conditions(large),
+            rule(PatternSpec::new_contains("backup"), 2),
+            rule(PatternSpec::new_glob("photos_*"), 3),
+        ];
+        assert!(lint(&rules).is_empty());
+    }
+
+    #[test]
+    fn test_example_texts() {
+        assert_eq!(glob_example("IMG_[0-9]???.{jpg,png}", "x"), "IMG_0xxx.jpg");
+        assert_eq!(glob_example("**/node_modules", ""), "/node_modules");
+        assert_eq!(regex_example(r"(?i)^backup_\d+(old|new)$"), "backup_1old");
+    }
+}
@@ -1,29 +1,44 @@
 use std::collections::HashMap;
-use globset::{Glob, GlobSet, GlobSetBuilder};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use regex::{Regex, RegexBuilder};
 use aho_corasick::AhoCorasick;
-use filemover_types::{PatternSpec, PatternKind, FileMoverError};
+use filemover_types::{PatternSpec, PatternKind, MatchTarget, FileMoverError};
 use crate::normalizer::TextNormalizer;
 
 pub trait PatternMatcher: Send + Sync {
-    fn is_match(&self, text: &str) -> Result<bool, FileMoverError>;
+    fn is_match(&self, text: &str) -> Result<bool, FileMoverError> {
+        Ok(!self.matching_patterns(text)?.is_empty())
+    }
+
+    /// 一致したパターンの番号（new に渡したスライス内の位置）
+    fn matching_patterns(&self, text: &str) -> Result<Vec<usize>, FileMoverError>;
 }
 
 pub struct GlobMatcher {
     glob_set: GlobSet,
+    /// glob_set 内の番号 → 渡されたパターンの番号
+    indices: Vec<usize>,
     normalizer: TextNormalizer,
 }
 
 impl GlobMatcher {
     pub fn new(patterns: &[PatternSpec], normalizer: TextNormalizer) -> Result<Self, FileMoverError> {
         let mut builder = GlobSetBuilder::new();
+        let mut indices = Vec::new();
         
-        for pattern in patterns {
+        for (index, pattern) in patterns.iter().enumerate() {
             if let PatternKind::Glob = pattern.kind {
-                let glob = Glob::new(&pattern.value).map_err(|e| FileMoverError::Pattern {
-                    message: format!("Invalid glob pattern '{}': {}", pattern.value, e),
-                })?;
+                // パスが対象の場合、* は区切りをまたがない（** はまたぐ）
+                // かなの表記ゆれはパターン側もそろえる
+                let glob = GlobBuilder::new(&normalizer.fold_kana(&pattern.value))
+                    .case_insensitive(pattern.case_insensitive)
+                    .literal_separator(pattern.match_target != MatchTarget::Name)
+                    .build()
+                    .map_err(|e| FileMoverError::Pattern {
+                        message: format!("Invalid glob pattern '{}': {}", pattern.value, e),
+                    })?;
                 builder.add(glob);
+                indices.push(index);
             }
         }
 
@@ -32,19 +47,19 @@ impl GlobMatcher {
                 message: format!("Failed to build glob set: {}", e),
             })?;
 
-        Ok(Self { glob_set, normalizer })
+        Ok(Self { glob_set, indices, normalizer })
     }
 }
 
 impl PatternMatcher for GlobMatcher {
-    fn is_match(&self, text: &str) -> Result<bool, FileMoverError> {
+    fn matching_patterns(&self, text: &str) -> Result<Vec<usize>, FileMoverError> {
         let normalized = self.normalizer.normalize(text)?;
-        Ok(self.glob_set.is_match(&normalized))
+        Ok(self.glob_set.matches(&normalized).into_iter().map(|i| self.indices[i]).collect())
     }
 }
 
 pub struct RegexMatcher {
-    regexes: Vec<Regex>,
+    regexes: Vec<(usize, Regex)>,
     normalizer: TextNormalizer,
 }
 
@@ -52,15 +67,15 @@ impl RegexMatcher {
     pub fn new(patterns: &[PatternSpec], normalizer: TextNormalizer) -> Result<Self, FileMoverError> {
         let mut regexes = Vec::new();
         
-        for pattern in patterns {
+        for (index, pattern) in patterns.iter().enumerate() {
             if let PatternKind::Regex = pattern.kind {
-                let regex = RegexBuilder::new(&pattern.value)
+                let regex = RegexBuilder::new(&normalizer.fold_kana(&pattern.value))
                     .case_insensitive(pattern.case_insensitive)
                     .build()
                     .map_err(|e| FileMoverError::Pattern {
                         message: format!("Invalid regex pattern '{}': {}", pattern.value, e),
                     })?;
-                regexes.push(regex);
+                regexes.push((index, regex));
             }
         }
 
@@ -69,22 +84,31 @@ impl RegexMatcher {
 }
 
 impl PatternMatcher for RegexMatcher {
-    fn is_match(&self, text: &str) -> Result<bool, FileMoverError> {
+    fn matching_patterns(&self, text: &str) -> Result<Vec<usize>, FileMoverError> {
         let normalized = self.normalizer.normalize(text)?;
-        Ok(self.regexes.iter().any(|regex| regex.is_match(&normalized)))
+        Ok(self.regexes.iter()
+            .filter(|(_, regex)| regex.is_match(&normalized))
+            .map(|(index, _)| *index)
+            .collect())
     }
 }
 
 pub struct ContainsMatcher {
     aho_corasick: AhoCorasick,
+    /// キーワードの番号 → 渡されたパターンの番号
+    indices: Vec<usize>,
+    /// キーワードの番号 → 単語全体でだけ一致させるか
+    whole_word: Vec<bool>,
     normalizer: TextNormalizer,
 }
 
 impl ContainsMatcher {
     pub fn new(patterns: &[PatternSpec], normalizer: TextNormalizer) -> Result<Self, FileMoverError> {
         let mut keywords = Vec::new();
+        let mut indices = Vec::new();
+        let mut whole_word = Vec::new();
         
-        for pattern in patterns {
+        for (index, pattern) in patterns.iter().enumerate() {
             if let PatternKind::Contains = pattern.kind {
                 let keyword = if pattern.case_insensitive {
                     normalizer.normalize(&pattern.value)?
@@ -92,6 +116,8 @@ impl ContainsMatcher {
                     pattern.value.clone()
                 };
                 keywords.push(keyword);
+                indices.push(index);
+                whole_word.push(pattern.whole_word);
             }
         }
 
@@ -100,17 +126,161 @@ impl ContainsMatcher {
                 message: format!("Failed to build Aho-Corasick automaton: {}", e),
             })?;
 
-        Ok(Self { aho_corasick, normalizer })
+        Ok(Self { aho_corasick, indices, whole_word, normalizer })
     }
 }
 
+/// start..end の前後が単語の続き（文字や数字）でないか
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
 impl PatternMatcher for ContainsMatcher {
-    fn is_match(&self, text: &str) -> Result<bool, FileMoverError> {
+    fn matching_patterns(&self, text: &str) -> Result<Vec<usize>, FileMoverError> {
         let normalized = self.normalizer.normalize(text)?;
-        Ok(self.aho_corasick.is_match(&normalized))
+        let mut matched: Vec<usize> = self.aho_corasick.find_overlapping_iter(&normalized)
+            .filter(|m| !self.whole_word[m.pattern().as_usize()] || is_word_boundary(&normalized, m.start(), m.end()))
+            .map(|m| self.indices[m.pattern().as_usize()])
+            .collect();
+        matched.sort_unstable();
+        matched.dedup();
+        Ok(matched)
+    }
+}
+
+pub struct FuzzyMatcher {
+    /// (渡されたパターンの番号, 比べる文字列, 許す編集回数)
+    patterns: Vec<(usize, Vec<char>, usize)>,
+    normalizer: TextNormalizer,
+}
+
+impl FuzzyMatcher {
+    pub fn new(patterns: &[PatternSpec], normalizer: TextNormalizer) -> Result<Self, FileMoverError> {
+        let mut fuzzy_patterns = Vec::new();
+
+        for (index, pattern) in patterns.iter().enumerate() {
+            if let PatternKind::Fuzzy = pattern.kind {
+                let value = if pattern.case_insensitive {
+                    normalizer.normalize(&pattern.value)?
+                } else {
+                    pattern.value.clone()
+                };
+                let chars: Vec<char> = value.chars().collect();
+                if chars.is_empty() {
+                    return Err(FileMoverError::Pattern {
+                        message: "Fuzzy pattern must not be empty".to_string(),
+                    });
+                }
+                // 既定は 4 文字につき 1 回（最低 1 回）
+                let max_edits = pattern.max_edits
+                    .map(|edits| edits as usize)
+                    .unwrap_or_else(|| (chars.len() / 4).max(1));
+                fuzzy_patterns.push((index, chars, max_edits));
+            }
+        }
+
+        Ok(Self { patterns: fuzzy_patterns, normalizer })
+    }
+}
+
+impl PatternMatcher for FuzzyMatcher {
+    fn matching_patterns(&self, text: &str) -> Result<Vec<usize>, FileMoverError> {
+        let normalized: Vec<char> = self.normalizer.normalize(text)?.chars().collect();
+        Ok(self.patterns.iter()
+            .filter(|(_, pattern, max_edits)| substring_edit_distance(pattern, &normalized) <= *max_edits)
+            .map(|(index, _, _)| *index)
+            .collect())
+    }
+}
+
+pub struct ExtensionMatcher {
+    /// 拡張子 → 渡されたパターンの番号（正規化して小文字で持つ）
+    case_insensitive: HashMap<String, Vec<usize>>,
+    /// 大文字小文字を区別するものは正規化せずに比べる
+    case_sensitive: HashMap<String, Vec<usize>>,
+    normalizer: TextNormalizer,
+}
+
+impl ExtensionMatcher {
+    pub fn new(patterns: &[PatternSpec], normalizer: TextNormalizer) -> Result<Self, FileMoverError> {
+        let mut case_insensitive: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut case_sensitive: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (index, pattern) in patterns.iter().enumerate() {
+            if let PatternKind::Extension = pattern.kind {
+                let mut found = false;
+                for extension in pattern.extensions() {
+                    let (map, key) = if pattern.case_insensitive {
+                        (&mut case_insensitive, normalizer.normalize(extension)?.to_lowercase())
+                    } else {
+                        (&mut case_sensitive, extension.to_string())
+                    };
+                    map.entry(key).or_default().push(index);
+                    found = true;
+                }
+                if !found {
+                    return Err(FileMoverError::Pattern {
+                        message: format!("Extension pattern '{}' has no extensions", pattern.value),
+                    });
+                }
+            }
+        }
+
+        Ok(Self { case_insensitive, case_sensitive, normalizer })
+    }
+}
+
+impl PatternMatcher for ExtensionMatcher {
+    fn matching_patterns(&self, text: &str) -> Result<Vec<usize>, FileMoverError> {
+        let mut matched = Vec::new();
+        if !self.case_sensitive.is_empty() {
+            for extension in extensions_of(text) {
+                matched.extend(self.case_sensitive.get(extension).into_iter().flatten());
+            }
+        }
+        if !self.case_insensitive.is_empty() {
+            let normalized = self.normalizer.normalize(text)?;
+            for extension in extensions_of(&normalized) {
+                matched.extend(self.case_insensitive.get(&extension.to_lowercase()).into_iter().flatten());
+            }
+        }
+        matched.sort_unstable();
+        matched.dedup();
+        Ok(matched)
     }
 }
 
+/// パスの最後の要素の拡張子の候補（`a.tar.gz` は `tar.gz` と `gz`。先頭の `.` は区切りではない）
+fn extensions_of(text: &str) -> impl Iterator<Item = &str> {
+    let name = text.rsplit(['/', '\\']).next().unwrap_or(text);
+    name.match_indices('.')
+        .filter(|(position, _)| *position > 0)
+        .map(move |(position, _)| &name[position + 1..])
+}
+
+/// text のいずれかの部分文字列と pattern との最小の編集距離（Sellers のアルゴリズム）
+fn substring_edit_distance(pattern: &[char], text: &[char]) -> usize {
+    // column[i] は pattern[..i] と、text の現在位置で終わる部分文字列との距離
+    let mut column: Vec<usize> = (0..=pattern.len()).collect();
+    let mut best = pattern.len();
+
+    for &t in text {
+        // 部分文字列はどこから始めてもよいので先頭は常に 0
+        let mut diagonal = column[0];
+        column[0] = 0;
+        for i in 1..=pattern.len() {
+            let substitution = diagonal + usize::from(pattern[i - 1] != t);
+            diagonal = column[i];
+            column[i] = substitution.min(column[i] + 1).min(column[i - 1] + 1);
+        }
+        best = best.min(column[pattern.len()]);
+    }
+
+    best
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +302,83 @@ mod tests {
         assert!(matcher.is_match("report_january").unwrap());
         assert!(matcher.is_match("backup_2024_03").unwrap());
         assert!(!matcher.is_match("document").unwrap());
+        assert_eq!(matcher.matching_patterns("report_2024").unwrap(), vec![0, 1]);
+        assert_eq!(matcher.matching_patterns("backup_2024_03").unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn test_glob_star_stays_within_path_component() {
+        let patterns = vec![
+            PatternSpec::new_glob("**/Projects/*/build").with_target(MatchTarget::FullPath),
+        ];
+        let matcher = GlobMatcher::new(&patterns, create_test_normalizer()).unwrap();
+
+        assert!(matcher.is_match("/data/projects/app/build").unwrap());
+        assert!(!matcher.is_match("/data/projects/app/nested/build").unwrap());
+    }
+
+    #[test]
+    fn test_kana_spellings_match_the_same_pattern() {
+        let patterns = vec![
+            PatternSpec::new_glob("*でーた*"),
+            PatternSpec::new_regex("^ﾃﾞｰﾀ_\\d+$"),
+        ];
+        let normalizer = TextNormalizer::new(NormalizationOptions {
+            fold_hiragana_katakana: true,
+            ..NormalizationOptions::default()
+        });
+        let glob = GlobMatcher::new(&patterns, normalizer).unwrap();
+
+        assert!(glob.is_match("共有データ").unwrap());
+        assert!(glob.is_match("ﾃﾞｰﾀ置き場").unwrap());
+
+        let regex = RegexMatcher::new(&patterns, create_test_normalizer()).unwrap();
+        assert!(regex.is_match("データ_2023").unwrap());
+    }
+
+    #[test]
+    fn test_fuzzy_matcher() {
+        let patterns = vec![
+            PatternSpec::new_fuzzy("Invoices"),
+            PatternSpec::new_fuzzy("temp").with_max_edits(0),
+        ];
+        let matcher = FuzzyMatcher::new(&patterns, create_test_normalizer()).unwrap();
+
+        assert_eq!(matcher.matching_patterns("Invioces_2022").unwrap(), vec![0]);
+        assert_eq!(matcher.matching_patterns("2021_invoice").unwrap(), vec![0]);
+        assert!(!matcher.is_match("Receipts_2022").unwrap());
+        assert_eq!(matcher.matching_patterns("TEMP_files").unwrap(), vec![1]);
+        assert!(!matcher.is_match("tmp_files").unwrap());
+    }
+
+    #[test]
+    fn test_extension_matcher() {
+        let patterns = vec![
+            PatternSpec::new_extension("jpg, .JPEG,heic"),
+            PatternSpec::new_extension("tar.gz"),
+            PatternSpec::new_extension("RAW").case_sensitive(),
+        ];
+        let matcher = ExtensionMatcher::new(&patterns, create_test_normalizer()).unwrap();
+
+        assert_eq!(matcher.matching_patterns("IMG_0001.JPG").unwrap(), vec![0]);
+        assert_eq!(matcher.matching_patterns("photo.jpeg").unwrap(), vec![0]);
+        assert_eq!(matcher.matching_patterns("backup.2024.tar.gz").unwrap(), vec![1]);
+        assert_eq!(matcher.matching_patterns("DSC_1.RAW").unwrap(), vec![2]);
+        assert!(!matcher.is_match("DSC_1.raw").unwrap());
+        assert!(!matcher.is_match(".jpg").unwrap());
+        assert!(!matcher.is_match("jpg").unwrap());
+
+        assert!(ExtensionMatcher::new(&[PatternSpec::new_extension(" , ")], create_test_normalizer()).is_err());
+    }
+
+    #[test]
+    fn test_substring_edit_distance() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+
+        assert_eq!(substring_edit_distance(&chars("invoices"), &chars("my_invoices_2022")), 0);
+        assert_eq!(substring_edit_distance(&chars("invoices"), &chars("invioces")), 2);
+        assert_eq!(substring_edit_distance(&chars("データ"), &chars("共有デタ")), 1);
+        assert_eq!(substring_edit_distance(&chars("abc"), &chars("")), 3);
     }
 
     #[test]
@@ -159,4 +406,19 @@ mod tests {
         assert!(matcher.is_match("image_backup").unwrap());
         assert!(!matcher.is_match("document_folder").unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_contains_whole_word() {
+        let patterns = vec![
+            PatternSpec::new_contains("art").whole_word(),
+            PatternSpec::new_contains("port"),
+        ];
+        let normalizer = create_test_normalizer();
+        let matcher = ContainsMatcher::new(&patterns, normalizer).unwrap();
+
+        assert_eq!(matcher.matching_patterns("Quarterly report").unwrap(), vec![1]);
+        assert_eq!(matcher.matching_patterns("art_2024").unwrap(), vec![0]);
+        assert_eq!(matcher.matching_patterns("Modern Art").unwrap(), vec![0]);
+        assert!(matcher.matching_patterns("artwork").unwrap().is_empty());
+    }
+}
@@ -25,6 +25,9 @@ impl TextNormalizer {
             result = self.normalize_width(&result);
         }
 
+        // かな正規化
+        result = self.fold_kana(&result);
+
         // ダイアクリティクス除去
         if self.options.strip_diacritics {
             result = self.strip_diacritics(&result);
@@ -52,6 +55,25 @@ impl TextNormalizer {
             .collect()
     }
 
+    /// かなの表記ゆれだけをそろえる（パターン側にも同じ変換をかける）
+    pub fn fold_kana(&self, text: &str) -> String {
+        let mut result = text.to_string();
+
+        if self.options.fold_halfwidth_kana && result.chars().any(is_halfwidth_kana) {
+            // 濁点・半濁点は結合文字にしてから合成する（ﾃﾞ → デ）
+            result = result.chars()
+                .map(|c| if is_halfwidth_kana(c) { fullwidth_kana(c) } else { c })
+                .nfc()
+                .collect();
+        }
+
+        if self.options.fold_hiragana_katakana {
+            result = result.chars().map(hiragana_to_katakana).collect();
+        }
+
+        result
+    }
+
     fn strip_diacritics(&self, text: &str) -> String {
         text.nfd()
             .filter(|&c| !unicode_normalization::char::is_combining_mark(c))
@@ -59,6 +81,30 @@ impl TextNormalizer {
     }
 }
 
+/// U+FF61 から U+FF9D に対応する全角文字
+const FULLWIDTH_KANA: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+
+fn is_halfwidth_kana(c: char) -> bool {
+    ('\u{FF61}'..='\u{FF9F}').contains(&c)
+}
+
+fn fullwidth_kana(c: char) -> char {
+    match c {
+        // 濁点・半濁点は結合文字に
+        '\u{FF9E}' => '\u{3099}',
+        '\u{FF9F}' => '\u{309A}',
+        _ => FULLWIDTH_KANA.chars().nth(c as usize - 0xFF61).unwrap_or(c),
+    }
+}
+
+// ひらがなはカタカナに寄せる（ぁ〜ゖ、ゝゞ）
+fn hiragana_to_katakana(c: char) -> char {
+    match c {
+        '\u{3041}'..='\u{3096}' | '\u{309D}'..='\u{309E}' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +116,8 @@ mod tests {
             normalize_width: true,
             strip_diacritics: false,
             normalize_case: false,
+            fold_halfwidth_kana: false,
+            fold_hiragana_katakana: false,
         };
         let normalizer = TextNormalizer::new(options);
         
@@ -84,6 +132,8 @@ mod tests {
             normalize_width: false,
             strip_diacritics: true,
             normalize_case: false,
+            fold_halfwidth_kana: false,
+            fold_hiragana_katakana: false,
         };
         let normalizer = TextNormalizer::new(options);
         
@@ -99,4 +149,26 @@ mod tests {
         let result = normalizer.normalize("Ｃａｆé　Ｎａïｖｅ").unwrap();
         assert_eq!(result, "cafe naive");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_fold_halfwidth_kana() {
+        let normalizer = TextNormalizer::new(NormalizationOptions::default());
+
+        assert_eq!(normalizer.normalize("ﾃﾞｰﾀ_2023").unwrap(), "データ_2023");
+        assert_eq!(normalizer.normalize("ﾊﾟﾌﾞﾘｯｸ｢ﾃｽﾄ｣").unwrap(), "パブリック「テスト」");
+        assert_eq!(normalizer.normalize("でーた").unwrap(), "でーた");
+    }
+
+    #[test]
+    fn test_fold_hiragana_katakana() {
+        let options = NormalizationOptions {
+            fold_hiragana_katakana: true,
+            ..NormalizationOptions::default()
+        };
+        let normalizer = TextNormalizer::new(options);
+
+        assert_eq!(normalizer.normalize("でーた").unwrap(), "データ");
+        assert_eq!(normalizer.normalize("ﾃﾞｰﾀ").unwrap(), "データ");
+        assert_eq!(normalizer.normalize("ゞ").unwrap(), "ヾ");
+    }
+}
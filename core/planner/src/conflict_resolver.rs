@@ -1,11 +1,19 @@
 use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
-use filemover_types::{Conflict, ConflictPolicy, FileMoverError, Warning, Permission};
-use tracing::{debug, warn};
+use filemover_types::{
+    Conflict, ConflictPolicy, FileMoverError, Permission, RenameSuffix, collision_key, is_case_sensitive,
+};
+use tracing::debug;
 
 pub struct ConflictResolver {
+    /// 計画済みの移動先（衝突判定用のキー）
     existing_paths: HashSet<PathBuf>,
     auto_rename_counters: HashMap<PathBuf, u32>,
+    /// ディレクトリごとの大文字小文字の区別（調べた結果を覚えておく）
+    case_sensitivity: HashMap<PathBuf, bool>,
+    /// 指定されていれば検出せずにこれを使う
+    case_sensitive_override: Option<bool>,
+    rename_suffix: RenameSuffix,
 }
 
 impl ConflictResolver {
@@ -13,11 +21,58 @@ impl ConflictResolver {
         Self {
             existing_paths: HashSet::new(),
             auto_rename_counters: HashMap::new(),
+            case_sensitivity: HashMap::new(),
+            case_sensitive_override: None,
+            rename_suffix: RenameSuffix::default(),
         }
     }
 
+    pub fn with_rename_suffix(mut self, rename_suffix: RenameSuffix) -> Self {
+        self.rename_suffix = rename_suffix;
+        self
+    }
+
+    /// 自動リネームの接尾辞を差し替える（計画ごとのオプションで変わるため）
+    pub fn set_rename_suffix(&mut self, rename_suffix: RenameSuffix) {
+        self.rename_suffix = rename_suffix;
+    }
+
+    /// 移動先のファイルシステムを調べずに、大文字小文字の区別を固定する
+    pub fn with_case_sensitivity(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive_override = Some(case_sensitive);
+        self
+    }
+
+    /// path の名前を置くディレクトリが大文字小文字を区別するか
+    pub fn is_case_sensitive_at(&mut self, path: &Path) -> bool {
+        if let Some(case_sensitive) = self.case_sensitive_override {
+            return case_sensitive;
+        }
+        let dir = path.parent().unwrap_or(path).to_path_buf();
+        *self.case_sensitivity.entry(dir)
+            .or_insert_with_key(|dir| is_case_sensitive(dir))
+    }
+
+    /// 移動先のファイルシステム上で同じ名前になるか
+    pub fn paths_collide(&mut self, a: &Path, b: &Path) -> bool {
+        let case_sensitive = self.is_case_sensitive_at(a);
+        collision_key(a, case_sensitive) == collision_key(b, case_sensitive)
+    }
+
+    /// 既にあるか、この計画の移動先として使われているパスか
+    pub fn is_taken(&mut self, path: &Path) -> bool {
+        let key = self.collision_key(path);
+        self.path_exists(path) || self.existing_paths.contains(&key)
+    }
+
+    fn collision_key(&mut self, path: &Path) -> PathBuf {
+        let case_sensitive = self.is_case_sensitive_at(path);
+        collision_key(path, case_sensitive)
+    }
+
     pub fn resolve_conflicts(
         &mut self,
+        source_path: &Path,
         dest_path: &Path,
         policy: ConflictPolicy,
     ) -> Result<(PathBuf, Vec<Conflict>), FileMoverError> {
@@ -25,7 +80,8 @@ impl ConflictResolver {
         let mut resolved_path = dest_path.to_path_buf();
 
         // 既存パス衝突のチェック
-        if self.path_exists(&resolved_path) || self.existing_paths.contains(&resolved_path) {
+        let key = self.collision_key(&resolved_path);
+        if self.path_exists(&resolved_path) || self.existing_paths.contains(&key) {
             conflicts.push(Conflict::NameExists {
                 existing_path: resolved_path.clone(),
             });
@@ -42,23 +98,26 @@ impl ConflictResolver {
                 ConflictPolicy::Overwrite => {
                     // Overwriteの場合は衝突情報を残すが、パスはそのまま
                 }
+                ConflictPolicy::Merge => {
+                    // 既にあるフォルダには中身をまとめるので衝突としない（計画側で配下の操作に展開する）
+                    if resolved_path.is_dir() {
+                        conflicts.retain(|c| !matches!(c, Conflict::NameExists { .. }));
+                    }
+                }
             }
         }
 
         // 循環参照のチェック
-        if self.is_cyclic_move(dest_path, &resolved_path) {
+        if self.is_cyclic_move(source_path, &resolved_path) {
             conflicts.push(Conflict::CycleDetected);
         }
 
         // 移動先が移動元の子である場合のチェック
-        if self.is_dest_inside_source(dest_path, &resolved_path) {
+        if self.is_dest_inside_source(source_path, &resolved_path) {
             conflicts.push(Conflict::DestInsideSource);
         }
 
-        // ディスク容量のチェック
-        if let Some(space_conflict) = self.check_disk_space(&resolved_path)? {
-            conflicts.push(space_conflict);
-        }
+        // ディスク容量は移動先のボリュームごとにまとめて PlanValidator で調べる
 
         // 権限のチェック
         if let Some(permission_conflict) = self.check_permissions(&resolved_path)? {
@@ -66,12 +125,13 @@ impl ConflictResolver {
         }
 
         // 解決済みパスを記録
-        self.existing_paths.insert(resolved_path.clone());
+        let key = self.collision_key(&resolved_path);
+        self.existing_paths.insert(key);
 
         Ok((resolved_path, conflicts))
     }
 
-    fn generate_auto_renamed_path(&mut self, original: &Path) -> Result<PathBuf, FileMoverError> {
+    pub(crate) fn generate_auto_renamed_path(&mut self, original: &Path) -> Result<PathBuf, FileMoverError> {
         let parent = original.parent().unwrap_or(Path::new(""));
         let stem = original.file_stem()
             .and_then(|s| s.to_str())
@@ -83,22 +143,29 @@ impl ConflictResolver {
             .and_then(|e| e.to_str())
             .unwrap_or("");
 
-        let mut counter_value = *self.auto_rename_counters.entry(original.to_path_buf()).or_insert(0);
+        self.rename_suffix.validate()
+            .map_err(|message| FileMoverError::PlanValidation { message })?;
+        let today = chrono::Local::now().date_naive();
+
+        let counter_key = self.collision_key(original);
+        let mut counter_value = *self.auto_rename_counters.entry(counter_key.clone()).or_insert(0);
         
         loop {
             counter_value += 1;
             
+            let new_stem = self.rename_suffix.apply(stem, counter_value, today);
             let new_name = if extension.is_empty() {
-                format!("{}_{}", stem, counter_value)
+                new_stem
             } else {
-                format!("{}_{}.{}", stem, counter_value, extension)
+                format!("{}.{}", new_stem, extension)
             };
             
             let new_path = parent.join(new_name);
             
-            if !self.path_exists(&new_path) && !self.existing_paths.contains(&new_path) {
+            let new_key = self.collision_key(&new_path);
+            if !self.path_exists(&new_path) && !self.existing_paths.contains(&new_key) {
                 debug!("Auto-renamed {} to {}", original.display(), new_path.display());
-                self.auto_rename_counters.insert(original.to_path_buf(), counter_value);
+                self.auto_rename_counters.insert(counter_key, counter_value);
                 return Ok(new_path);
             }
             
@@ -127,29 +194,6 @@ impl ConflictResolver {
         dest.starts_with(source)
     }
 
-    fn check_disk_space(&self, _path: &Path) -> Result<Option<Conflict>, FileMoverError> {
-        // ディスク容量チェック（Windows固有の実装が必要）
-        #[cfg(windows)]
-        {
-            // Stub implementation - in real code would use GetDiskFreeSpaceExW
-            let free_bytes: u64 = 1024 * 1024 * 1024; // 1GB placeholder
-            
-            {
-                // 仮の必要容量（実際には移動するフォルダのサイズを計算する必要がある）
-                let required_bytes = 1024 * 1024; // 1MB as placeholder
-                
-                if free_bytes < required_bytes {
-                    return Ok(Some(Conflict::NoSpace {
-                        required: required_bytes,
-                        available: free_bytes,
-                    }));
-                }
-            }
-        }
-        
-        Ok(None)
-    }
-
     fn check_permissions(&self, path: &Path) -> Result<Option<Conflict>, FileMoverError> {
         // 権限チェック（簡易実装）
         if let Some(parent) = path.parent() {
@@ -174,24 +218,15 @@ impl ConflictResolver {
         Ok(None)
     }
 
-    #[cfg(windows)]
-    fn get_volume_root(&self, path: &Path) -> PathBuf {
-        // パスからボリュームルートを抽出
-        if let Some(prefix) = path.components().next() {
-            if let std::path::Component::Prefix(prefix_component) = prefix {
-                return PathBuf::from(format!("{}\\", prefix_component.as_os_str().to_string_lossy()));
-            }
-        }
-        PathBuf::from("C:\\") // デフォルト
-    }
-
     pub fn reset(&mut self) {
         self.existing_paths.clear();
         self.auto_rename_counters.clear();
+        self.case_sensitivity.clear();
     }
 
     pub fn add_existing_path(&mut self, path: PathBuf) {
-        self.existing_paths.insert(path);
+        let key = self.collision_key(&path);
+        self.existing_paths.insert(key);
     }
 }
 
@@ -216,7 +251,7 @@ mod tests {
         
         let mut resolver = ConflictResolver::new();
         let (renamed_path, conflicts) = resolver
-            .resolve_conflicts(&test_path, ConflictPolicy::AutoRename)
+            .resolve_conflicts(Path::new("/source"), &test_path, ConflictPolicy::AutoRename)
             .unwrap();
         
         assert_ne!(renamed_path, test_path);
@@ -224,6 +259,34 @@ mod tests {
         assert!(conflicts.is_empty()); // 衝突は解決されているはず
     }
 
+    #[test]
+    fn test_rename_suffix_styles() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_path = temp_dir.path().join("report.txt");
+        std::fs::write(&test_path, "").unwrap();
+        std::fs::write(temp_dir.path().join("report (copy 1).txt"), "").unwrap();
+
+        let rename = |suffix: RenameSuffix| {
+            let mut resolver = ConflictResolver::new().with_rename_suffix(suffix);
+            let (path, _) = resolver.resolve_conflicts(Path::new("/source"), &test_path, ConflictPolicy::AutoRename).unwrap();
+            path.file_name().unwrap().to_string_lossy().into_owned()
+        };
+
+        assert_eq!(rename(RenameSuffix::Numeric { width: 3 }), "report_001.txt");
+        // 既にある名前は飛ばす
+        assert_eq!(rename(RenameSuffix::Copy), "report (copy 2).txt");
+        assert_eq!(
+            rename(RenameSuffix::Template { template: "{n}-{name}".to_string() }),
+            "1-report.txt"
+        );
+        let today = chrono::Local::now().date_naive().format("%Y%m%d").to_string();
+        assert_eq!(rename(RenameSuffix::Date), format!("report_{}.txt", today));
+
+        let mut resolver = ConflictResolver::new()
+            .with_rename_suffix(RenameSuffix::Template { template: "{name}-old".to_string() });
+        assert!(resolver.resolve_conflicts(Path::new("/source"), &test_path, ConflictPolicy::AutoRename).is_err());
+    }
+
     #[test]
     fn test_dest_inside_source_detection() {
         let resolver = ConflictResolver::new();
@@ -236,6 +299,21 @@ mod tests {
         assert!(!resolver.is_dest_inside_source(source, dest_outside));
     }
 
+    #[test]
+    fn test_dest_checked_against_source() {
+        let mut resolver = ConflictResolver::new();
+
+        let (_, conflicts) = resolver
+            .resolve_conflicts(Path::new("/data/project"), Path::new("/archive/project"), ConflictPolicy::Skip)
+            .unwrap();
+        assert!(!conflicts.iter().any(|c| matches!(c, Conflict::DestInsideSource)));
+
+        let (_, conflicts) = resolver
+            .resolve_conflicts(Path::new("/data/project"), Path::new("/data/project/old"), ConflictPolicy::Skip)
+            .unwrap();
+        assert!(conflicts.iter().any(|c| matches!(c, Conflict::DestInsideSource)));
+    }
+
     #[test]
     fn test_skip_policy() {
         let temp_dir = TempDir::new().unwrap();
@@ -246,7 +324,7 @@ mod tests {
         
         let mut resolver = ConflictResolver::new();
         let (resolved_path, conflicts) = resolver
-            .resolve_conflicts(&test_path, ConflictPolicy::Skip)
+            .resolve_conflicts(Path::new("/source"), &test_path, ConflictPolicy::Skip)
             .unwrap();
         
         assert_eq!(resolved_path, test_path); // パスは変更されない
@@ -264,11 +342,53 @@ mod tests {
         
         let mut resolver = ConflictResolver::new();
         let (resolved_path, conflicts) = resolver
-            .resolve_conflicts(&test_path, ConflictPolicy::Overwrite)
+            .resolve_conflicts(Path::new("/source"), &test_path, ConflictPolicy::Overwrite)
             .unwrap();
         
         assert_eq!(resolved_path, test_path); // パスは変更されない
         assert!(!conflicts.is_empty()); // 衝突情報は残る
         assert!(matches!(conflicts[0], Conflict::NameExists { .. }));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_case_insensitive_collision() {
+        let mut resolver = ConflictResolver::new().with_case_sensitivity(false);
+        resolver.add_existing_path(PathBuf::from("/archive/Project"));
+
+        let (resolved_path, conflicts) = resolver
+            .resolve_conflicts(Path::new("/source"), Path::new("/archive/project"), ConflictPolicy::Skip)
+            .unwrap();
+        assert_eq!(resolved_path, Path::new("/archive/project"));
+        assert!(matches!(conflicts[0], Conflict::NameExists { .. }));
+
+        let (renamed_path, _) = resolver
+            .resolve_conflicts(Path::new("/source"), Path::new("/archive/PROJECT"), ConflictPolicy::AutoRename)
+            .unwrap();
+        assert_eq!(renamed_path, Path::new("/archive/PROJECT_1"));
+    }
+
+    #[test]
+    fn test_case_sensitive_no_collision() {
+        let mut resolver = ConflictResolver::new().with_case_sensitivity(true);
+        resolver.add_existing_path(PathBuf::from("/archive/Project"));
+
+        let (resolved_path, conflicts) = resolver
+            .resolve_conflicts(Path::new("/source"), Path::new("/archive/project"), ConflictPolicy::AutoRename)
+            .unwrap();
+        assert_eq!(resolved_path, Path::new("/archive/project"));
+        assert!(!conflicts.iter().any(|c| matches!(c, Conflict::NameExists { .. })));
+    }
+
+    #[test]
+    fn test_detects_case_sensitivity_of_destination() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("Existing")).unwrap();
+
+        let mut resolver = ConflictResolver::new();
+        let dest = temp_dir.path().join("missing").join("folder");
+        // 移動先の親がまだなくても、存在する祖先で判定できる
+        let detected = resolver.is_case_sensitive_at(&dest);
+        let lower_exists = temp_dir.path().join("existing").exists();
+        assert_eq!(detected, !lower_exists);
+    }
+}
@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+
+/// パスが置かれているボリュームの識別子
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VolumeId {
+    /// デバイス番号（Unix）
+    Device(u64),
+    /// ドライブや共有のルート（Windows）
+    Root(PathBuf),
+}
+
+/// path（存在しなければ最も近い存在する祖先）のボリューム
+pub fn volume_id(path: &Path) -> Option<VolumeId> {
+    let existing = nearest_existing(path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        std::fs::metadata(existing).ok().map(|metadata| VolumeId::Device(metadata.dev()))
+    }
+    #[cfg(windows)]
+    {
+        let canonical = std::fs::canonicalize(existing).ok()?;
+        match canonical.components().next()? {
+            std::path::Component::Prefix(prefix) => Some(VolumeId::Root(PathBuf::from(prefix.as_os_str()))),
+            _ => None,
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = existing;
+        None
+    }
+}
+
+/// ボリュームの容量と使える空き容量（バイト）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VolumeSpace {
+    pub total: u64,
+    /// 一般ユーザーが使える分（クォータや root 用の予約は含めない）
+    pub available: u64,
+}
+
+/// path のボリュームで使える空き容量（調べられなければ None）
+pub fn available_space(path: &Path) -> Option<u64> {
+    volume_space(path).map(|space| space.available)
+}
+
+/// path（存在しなければ最も近い存在する祖先）のボリュームの容量と空き容量
+pub fn volume_space(path: &Path) -> Option<VolumeSpace> {
+    let existing = nearest_existing(path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+        Some(VolumeSpace {
+            total: stat.f_blocks as u64 * stat.f_frsize as u64,
+            available: stat.f_bavail as u64 * stat.f_frsize as u64,
+        })
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::shared::ntdef::ULARGE_INTEGER;
+        use winapi::um::fileapi::GetDiskFreeSpaceExW;
+
+        let wide: Vec<u16> = existing.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let mut available: ULARGE_INTEGER = unsafe { std::mem::zeroed() };
+        let mut total: ULARGE_INTEGER = unsafe { std::mem::zeroed() };
+        let ok = unsafe {
+            GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, &mut total, std::ptr::null_mut())
+        };
+        if ok == 0 {
+            return None;
+        }
+        Some(VolumeSpace {
+            total: unsafe { *total.QuadPart() },
+            available: unsafe { *available.QuadPart() },
+        })
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = existing;
+        None
+    }
+}
+
+/// path の実際のサイズ（フォルダは配下のファイルの合計。シンボリックリンクはたどらない）
+pub fn measure_size(path: &Path) -> Option<u64> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    if !metadata.is_dir() {
+        return Some(metadata.len());
+    }
+
+    let mut total = 0u64;
+    for entry in std::fs::read_dir(path).ok()?.flatten() {
+        // 読めない項目は数えない
+        total += measure_size(&entry.path()).unwrap_or(0);
+    }
+    Some(total)
+}
+
+fn nearest_existing(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|p| !p.as_os_str().is_empty() && p.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_measure_and_query_volume() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("folder");
+        std::fs::create_dir_all(folder.join("sub")).unwrap();
+        std::fs::write(folder.join("a.bin"), vec![0u8; 300]).unwrap();
+        std::fs::write(folder.join("sub").join("b.bin"), vec![0u8; 200]).unwrap();
+
+        assert_eq!(measure_size(&folder), Some(500));
+        assert_eq!(measure_size(&folder.join("missing")), None);
+
+        // まだない移動先は存在する祖先のボリュームで調べる
+        let planned = folder.join("not").join("yet");
+        assert_eq!(volume_id(&planned), volume_id(&folder));
+        assert!(available_space(&planned).is_some());
+        let space = volume_space(&planned).unwrap();
+        assert!(space.total >= space.available);
+    }
+}
@@ -2,8 +2,20 @@ pub mod planner;
 pub mod validator;
 pub mod template;
 pub mod conflict_resolver;
+pub mod disk_space;
+pub mod merge;
+pub mod subset;
+pub mod ordering;
+pub mod throughput;
+pub mod preservation;
 
 pub use planner::*;
 pub use validator::*;
 pub use template::*;
-pub use conflict_resolver::*;
\ No newline at end of file
+pub use conflict_resolver::*;
+pub use disk_space::*;
+pub use merge::*;
+pub use subset::*;
+pub use ordering::*;
+pub use throughput::*;
+pub use preservation::*;
\ No newline at end of file
@@ -0,0 +1,222 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use filemover_types::{
+    collision_key, is_case_sensitive, Conflict, MovePlan, OpKind, PlanNode, PlanNodeId,
+};
+use crate::ordering::execution_order;
+use crate::planner::calculate_summary;
+
+/// 計画をまとめた結果の内訳
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeReport {
+    pub plans: usize,
+    /// 他の計画と同じ操作だったので取り除いたノード数
+    pub duplicates_removed: usize,
+    /// ID が他の計画と重なったので振り直したノード数
+    pub ids_reassigned: usize,
+    /// 他の計画のノードと移動先が重なったノード数（NameExists を付ける）
+    pub destination_conflicts: usize,
+}
+
+/// 複数の計画を 1 つにまとめる
+///
+/// 移動元・移動先・操作が同じノードは最初の計画のものだけを残す。別の計画のノードと移動先が
+/// 重なる場合は後の計画のノードに NameExists を付ける。サマリはまとめた後のノードで計算し直す。
+pub fn merge_plans(plans: &[MovePlan]) -> (MovePlan, MergeReport) {
+    let mut merged = MovePlan {
+        roots: Vec::new(),
+        nodes: HashMap::new(),
+        summary: Default::default(),
+        execution_order: Vec::new(),
+    };
+    let mut report = MergeReport {
+        plans: plans.len(),
+        ..Default::default()
+    };
+    // 操作 → 残したノード
+    let mut operations: HashMap<(PathBuf, PathBuf, OpKind), PlanNodeId> = HashMap::new();
+    // ノード → 元の計画の番号
+    let mut origin: HashMap<PlanNodeId, usize> = HashMap::new();
+
+    for (plan_index, plan) in plans.iter().enumerate() {
+        let order = node_order(plan);
+
+        // 先に ID の対応を決める（子の参照を付け替えるため）
+        let mut id_map = HashMap::new();
+        let mut kept = Vec::new();
+        for node in &order {
+            let operation = (node.path_before.clone(), node.path_after.clone(), node.kind);
+            if let Some(existing) = operations.get(&operation) {
+                id_map.insert(node.id, *existing);
+                report.duplicates_removed += 1;
+                continue;
+            }
+
+            let id = if merged.nodes.contains_key(&node.id) {
+                report.ids_reassigned += 1;
+                PlanNodeId::new()
+            } else {
+                node.id
+            };
+            id_map.insert(node.id, id);
+            operations.insert(operation, id);
+            kept.push(*node);
+        }
+
+        for node in kept {
+            let mut node = node.clone();
+            node.id = id_map[&node.id];
+            node.children = dedup_ids(node.children.iter().filter_map(|child| id_map.get(child).copied()));
+            origin.insert(node.id, plan_index);
+            merged.nodes.insert(node.id, node);
+        }
+
+        let roots = plan.roots.iter().filter_map(|root| id_map.get(root).copied());
+        for root in roots {
+            if !merged.roots.contains(&root) {
+                merged.roots.push(root);
+            }
+        }
+    }
+
+    report.destination_conflicts = mark_destination_conflicts(&mut merged, &origin);
+    merged.summary = calculate_summary(&merged.nodes);
+    merged.execution_order = execution_order(&merged).order;
+
+    debug!(
+        "Merged {} plans: {} duplicates removed, {} IDs reassigned, {} destination conflicts",
+        report.plans, report.duplicates_removed, report.ids_reassigned, report.destination_conflicts
+    );
+    (merged, report)
+}
+
+/// ルートからの深さ優先の順（どのルートからもたどれないノードは移動元のパス順で最後に並べる）
+fn node_order(plan: &MovePlan) -> Vec<&PlanNode> {
+    let mut order = Vec::with_capacity(plan.nodes.len());
+    let mut visited = HashSet::new();
+    let mut stack = plan.roots.iter().rev().copied().collect::<Vec<_>>();
+
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        if let Some(node) = plan.nodes.get(&id) {
+            order.push(node);
+            stack.extend(node.children.iter().rev().copied());
+        }
+    }
+
+    let mut rest = plan.nodes.values()
+        .filter(|node| !visited.contains(&node.id))
+        .collect::<Vec<_>>();
+    rest.sort_by(|a, b| a.path_before.cmp(&b.path_before));
+    order.extend(rest);
+    order
+}
+
+fn dedup_ids(ids: impl Iterator<Item = PlanNodeId>) -> Vec<PlanNodeId> {
+    let mut seen = HashSet::new();
+    ids.filter(|id| seen.insert(*id)).collect()
+}
+
+/// 別の計画から来たノードと移動先が重なるノードに NameExists を付け、その数を返す
+fn mark_destination_conflicts(plan: &mut MovePlan, origin: &HashMap<PlanNodeId, usize>) -> usize {
+    let mut case_sensitivity: HashMap<PathBuf, bool> = HashMap::new();
+    let mut nodes = plan.nodes.values()
+        .filter(|node| !matches!(node.kind, OpKind::Skip | OpKind::None))
+        .map(|node| (origin[&node.id], node.path_before.clone(), node.id, node.path_after.clone()))
+        .collect::<Vec<_>>();
+    // 先の計画のノードを既にある移動先として扱う
+    nodes.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+    let mut destinations: HashMap<PathBuf, (usize, PathBuf)> = HashMap::new();
+    let mut conflicts = Vec::new();
+    for (plan_index, _, id, path_after) in nodes {
+        let parent = path_after.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        let case_sensitive = *case_sensitivity.entry(parent.clone())
+            .or_insert_with(|| is_case_sensitive(&parent));
+        let key = collision_key(&path_after, case_sensitive);
+
+        match destinations.get(&key) {
+            Some((other_index, existing_path)) if *other_index != plan_index => {
+                conflicts.push((id, existing_path.clone()));
+            }
+            Some(_) => {}
+            None => {
+                destinations.insert(key, (plan_index, path_after));
+            }
+        }
+    }
+
+    for (id, existing_path) in &conflicts {
+        if let Some(node) = plan.nodes.get_mut(id) {
+            let conflict = Conflict::NameExists { existing_path: existing_path.clone() };
+            if !node.conflicts.contains(&conflict) {
+                node.conflicts.push(conflict);
+            }
+        }
+    }
+    conflicts.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::PlanSummary;
+
+    fn node(before: &str, after: &str) -> PlanNode {
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: before.to_string(),
+            path_before: PathBuf::from("/source").join(before),
+            name_after: after.to_string(),
+            path_after: PathBuf::from("/archive").join(after),
+            kind: OpKind::Move,
+            size_bytes: Some(100),
+            warnings: Vec::new(),
+            conflicts: Vec::new(),
+            children: Vec::new(),
+            rule_id: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn plan_of(nodes: Vec<PlanNode>) -> MovePlan {
+        MovePlan {
+            roots: nodes.iter().map(|node| node.id).collect(),
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+            nodes: nodes.into_iter().map(|node| (node.id, node)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_merge_plans() {
+        let shared = node("shared", "shared");
+        let mut same_id = node("other", "other");
+        same_id.id = shared.id;
+
+        let first = plan_of(vec![shared.clone(), node("a", "target")]);
+        let second = plan_of(vec![shared.clone(), node("b", "target")]);
+        let third = plan_of(vec![same_id]);
+
+        let (merged, report) = merge_plans(&[first, second, third]);
+
+        assert_eq!(report.duplicates_removed, 1);
+        assert_eq!(report.ids_reassigned, 1);
+        assert_eq!(report.destination_conflicts, 1);
+        assert_eq!(merged.nodes.len(), 4);
+        assert_eq!(merged.roots.len(), 4);
+        assert_eq!(merged.summary.count_dirs, 4);
+        assert_eq!(merged.summary.total_bytes, Some(400));
+        assert_eq!(merged.summary.conflicts, 1);
+
+        // 後の計画のノードに衝突が付く
+        let conflicted = merged.nodes.values().find(|node| !node.conflicts.is_empty()).unwrap();
+        assert_eq!(conflicted.name_before, "b");
+        assert_eq!(merged.nodes[&shared.id].name_before, "shared");
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use filemover_types::{MovePlan, OpKind, PlanNode, PlanNodeId};
+
+/// 実行する操作の順番
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecutionOrder {
+    pub order: Vec<PlanNodeId>,
+    /// 互いに相手の移動を待つので順番を決められなかった操作（order の最後に移動元のパス順で並ぶ）
+    pub cyclic: Vec<PlanNodeId>,
+}
+
+/// 操作の依存関係から実行の順番を決める（スキップと何もしないノードは含めない）
+///
+/// - 移動元の中にある項目を先に移動してから、外側のフォルダを移動する
+/// - 移動先のフォルダを作る操作の後に、その中へ移動する
+/// - 移動先に今ある項目を先に移動してから、そこへ移動する
+///
+/// 依存のない操作同士は移動元のパス順にするので、同じ計画なら毎回同じ順番になる。
+pub fn execution_order(plan: &MovePlan) -> ExecutionOrder {
+    let active = plan.nodes.values()
+        .filter(|node| !matches!(node.kind, OpKind::Skip | OpKind::None))
+        .collect::<Vec<_>>();
+    let by_source: HashMap<&Path, PlanNodeId> = active.iter()
+        .map(|node| (node.path_before.as_path(), node.id))
+        .collect();
+    let by_dest: HashMap<&Path, PlanNodeId> = active.iter()
+        .map(|node| (node.path_after.as_path(), node.id))
+        .collect();
+
+    // 先に実行する操作 → 後に実行する操作
+    let mut dependents: HashMap<PlanNodeId, Vec<PlanNodeId>> = HashMap::new();
+    let mut pending: HashMap<PlanNodeId, usize> = active.iter().map(|node| (node.id, 0)).collect();
+    let mut add_edge = |before: PlanNodeId, after: PlanNodeId| {
+        if before != after {
+            dependents.entry(before).or_default().push(after);
+            *pending.get_mut(&after).unwrap() += 1;
+        }
+    };
+
+    for node in &active {
+        // 外側のフォルダの移動より先に、中の項目を移動する
+        for ancestor in node.path_before.ancestors().skip(1) {
+            if let Some(&outer) = by_source.get(ancestor) {
+                add_edge(node.id, outer);
+            }
+        }
+        // 移動先の外側のフォルダを作ってから中へ移動する
+        for ancestor in node.path_after.ancestors().skip(1) {
+            if let Some(&outer) = by_dest.get(ancestor) {
+                add_edge(outer, node.id);
+            }
+        }
+        // 移動先を空けてから移動する
+        if let Some(&occupant) = by_source.get(node.path_after.as_path()) {
+            add_edge(occupant, node.id);
+        }
+    }
+
+    let source_of = |id: &PlanNodeId| -> PathBuf { plan.nodes[id].path_before.clone() };
+    let mut ready = pending.iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| (source_of(id), id.0))
+        .collect::<BTreeSet<_>>();
+
+    let mut order = Vec::with_capacity(active.len());
+    while let Some((_, uuid)) = ready.pop_first() {
+        let id = PlanNodeId(uuid);
+        order.push(id);
+        for dependent in dependents.get(&id).into_iter().flatten() {
+            let count = pending.get_mut(dependent).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                ready.insert((source_of(dependent), dependent.0));
+            }
+        }
+    }
+
+    let mut cyclic = pending.into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    cyclic.sort_by_key(source_of);
+    if !cyclic.is_empty() {
+        warn!("{} operations depend on each other and cannot be ordered", cyclic.len());
+    }
+    order.extend(cyclic.iter().copied());
+
+    ExecutionOrder { order, cyclic }
+}
+
+/// 実行する順番のノード（順番が保存されていない計画では計算する）
+pub fn nodes_in_execution_order(plan: &MovePlan) -> Vec<&PlanNode> {
+    let computed;
+    let order = if plan.execution_order.is_empty() {
+        computed = execution_order(plan).order;
+        &computed
+    } else {
+        &plan.execution_order
+    };
+    order.iter().filter_map(|id| plan.nodes.get(id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filemover_types::PlanSummary;
+
+    fn node(before: &str, after: &str) -> PlanNode {
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: before.to_string(),
+            path_before: PathBuf::from(before),
+            name_after: after.to_string(),
+            path_after: PathBuf::from(after),
+            kind: OpKind::Move,
+            size_bytes: None,
+            warnings: Vec::new(),
+            conflicts: Vec::new(),
+            children: Vec::new(),
+            rule_id: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn plan_of(nodes: &[PlanNode]) -> MovePlan {
+        MovePlan {
+            roots: nodes.iter().map(|node| node.id).collect(),
+            nodes: nodes.iter().map(|node| (node.id, node.clone())).collect(),
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_dependencies_decide_order() {
+        let outer = node("/src/a", "/dst/a");
+        let inner = node("/src/a/inner", "/other/inner");
+        let into_new = node("/src/b", "/dst/a/b");
+        // /src/c に来る前に、今ある /src/c を移動する
+        let vacate = node("/src/c", "/dst/c");
+        let occupy = node("/src/z", "/src/c");
+        let plan = plan_of(&[occupy.clone(), into_new.clone(), vacate.clone(), outer.clone(), inner.clone()]);
+
+        let result = execution_order(&plan);
+        assert!(result.cyclic.is_empty());
+        let position = |node: &PlanNode| result.order.iter().position(|id| *id == node.id).unwrap();
+        assert!(position(&inner) < position(&outer));
+        assert!(position(&outer) < position(&into_new));
+        assert!(position(&vacate) < position(&occupy));
+        assert_eq!(result, execution_order(&plan));
+    }
+
+    #[test]
+    fn test_swap_is_reported_as_cycle() {
+        let first = node("/data/x", "/data/y");
+        let second = node("/data/y", "/data/x");
+        let result = execution_order(&plan_of(&[first, second]));
+        assert_eq!(result.cyclic.len(), 2);
+        assert_eq!(result.order.len(), 2);
+    }
+}
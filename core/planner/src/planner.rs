@@ -1,18 +1,24 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 use filemover_types::{
     MovePlan, PlanNode, PlanNodeId, PlanSummary, OpKind, FolderHit, 
-    Rule, PlanOptions, FileMoverError, ConflictPolicy
+    Rule, PlanOptions, FileMoverError, ConflictPolicy, CancellationToken, Conflict,
+    PlaceholderPolicy, PreservationSummary, Warning, exceeds_max_path_after_move
 };
 use crate::template::TemplateEngine;
 use crate::conflict_resolver::ConflictResolver;
 use crate::validator::PlanValidator;
+use crate::ordering::execution_order;
+use crate::throughput::{is_copy, ThroughputModel};
+use crate::preservation::PreservationChecker;
 
 pub struct MovePlanner {
     template_engine: TemplateEngine,
     conflict_resolver: ConflictResolver,
     validator: PlanValidator,
+    cancel_token: CancellationToken,
+    throughput: ThroughputModel,
 }
 
 impl MovePlanner {
@@ -21,9 +27,22 @@ impl MovePlanner {
             template_engine: TemplateEngine::new(),
             conflict_resolver: ConflictResolver::new(),
             validator: PlanValidator::new(),
+            cancel_token: CancellationToken::new(),
+            throughput: ThroughputModel::default(),
         }
     }
 
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = token;
+        self
+    }
+
+    /// simulate_plan の所要時間の見積もりに使う速度
+    pub fn with_throughput(mut self, throughput: ThroughputModel) -> Self {
+        self.throughput = throughput;
+        self
+    }
+
     pub fn create_plan(
         &mut self,
         folder_hits: &[FolderHit],
@@ -36,6 +55,9 @@ impl MovePlanner {
         let mut roots = Vec::new();
         let mut rule_map: HashMap<uuid::Uuid, &Rule> = HashMap::new();
 
+        self.template_engine.reset_sequences();
+        self.conflict_resolver.set_rename_suffix(options.rename_suffix.clone());
+
         // ルールマップを作成
         for rule in rules {
             rule_map.insert(rule.id, rule);
@@ -43,6 +65,7 @@ impl MovePlanner {
 
         // 各フォルダヒットからプランノードを生成
         for hit in folder_hits {
+            self.cancel_token.check()?;
             let node_id = PlanNodeId::new();
             
             let plan_node = self.create_plan_node(hit, &rule_map, &options, node_id)?;
@@ -52,21 +75,56 @@ impl MovePlanner {
         }
 
         // 衝突解決とバリデーション
-        self.resolve_conflicts_and_validate(&mut nodes, &options)?;
+        self.resolve_conflicts_and_validate(&mut nodes, &rule_map, &options)?;
+
+        // 移動先のファイルシステムで保てない情報
+        let mut preservation = PreservationChecker::new();
+        for node in nodes.values_mut() {
+            if matches!(node.kind, OpKind::Skip | OpKind::None) {
+                continue;
+            }
+            for warning in preservation.losses(&node.path_before, &node.path_after) {
+                if !node.warnings.contains(&warning) {
+                    node.warnings.push(warning);
+                }
+            }
+        }
 
         // サマリを計算
-        let summary = self.calculate_summary(&nodes);
+        let summary = calculate_summary(&nodes);
 
         let mut plan = MovePlan {
             roots,
             nodes,
             summary,
+            execution_order: Vec::new(),
         };
 
         // 最終バリデーション
+        self.cancel_token.check()?;
         let validation_result = self.validator.validate_full_plan(&plan)?;
         self.apply_validation_result(&mut plan, validation_result)?;
 
+        // 移動先のボリュームごとの空き容量
+        for (node_id, conflict) in self.validator.check_disk_space(&plan)? {
+            if let Some(node) = plan.nodes.get_mut(&node_id) {
+                node.conflicts.push(conflict);
+                plan.summary.conflicts += 1;
+            }
+        }
+
+        // 実行する順番（互いの移動を待つ操作は循環として残す）
+        let order = execution_order(&plan);
+        for node_id in &order.cyclic {
+            if let Some(node) = plan.nodes.get_mut(node_id) {
+                if !node.conflicts.contains(&Conflict::CycleDetected) {
+                    node.conflicts.push(Conflict::CycleDetected);
+                    plan.summary.conflicts += 1;
+                }
+            }
+        }
+        plan.execution_order = order.order;
+
         info!("Move plan created successfully with {} nodes", plan.nodes.len());
         Ok(plan)
     }
@@ -84,15 +142,21 @@ impl MovePlanner {
                 message: format!("Rule not found for folder: {}", hit.path.display()),
             })?;
 
-        // テンプレートを展開して移動先パスを生成
-        let dest_path = self.template_engine.expand_template(rule, &hit.path)?;
+        // テンプレートを展開して移動先パスを生成（{seq} は自動リネームと重ならない番号にする）
+        let conflict_resolver = &mut self.conflict_resolver;
+        let dest_path = self.template_engine
+            .expand_template_with(rule, &hit.path, |path| conflict_resolver.is_taken(path))?;
         
         // 操作種別を決定
-        let op_kind = self.determine_operation_kind(&hit.path, &dest_path);
+        let mut op_kind = self.determine_operation_kind(&hit.path, &dest_path);
+        if options.cloud_placeholders == PlaceholderPolicy::Skip && hit.warnings.contains(&Warning::Offline) {
+            debug!("Skipping cloud placeholder: {}", hit.path.display());
+            op_kind = OpKind::Skip;
+        }
 
         let plan_node = PlanNode {
             id: node_id,
-            is_dir: true, // フォルダのみを扱う
+            is_dir: hit.is_dir,
             name_before: hit.name.clone(),
             path_before: hit.path.clone(),
             name_after: dest_path.file_name()
@@ -106,6 +170,7 @@ impl MovePlanner {
             conflicts: Vec::new(), // 後で衝突解決で設定
             children: Vec::new(),   // 単純な実装ではフラット構造
             rule_id: hit.matched_rule,
+            tags: hit.tags.clone(),
         };
 
         Ok(plan_node)
@@ -125,27 +190,34 @@ impl MovePlanner {
     }
 
     fn is_cross_volume(&self, source: &PathBuf, dest: &PathBuf) -> bool {
-        let source_drive = source.components().next();
-        let dest_drive = dest.components().next();
-        source_drive != dest_drive
+        is_cross_volume(source, dest)
     }
 
     fn resolve_conflicts_and_validate(
         &mut self,
         nodes: &mut HashMap<PlanNodeId, PlanNode>,
+        rule_map: &HashMap<uuid::Uuid, &Rule>,
         options: &PlanOptions,
     ) -> Result<(), FileMoverError> {
         debug!("Resolving conflicts for {} nodes", nodes.len());
 
+        // Merge で展開した配下の操作（ループの後で追加する）
+        let mut merged_nodes = Vec::new();
+
         for (node_id, node) in nodes.iter_mut() {
+            self.cancel_token.check()?;
+
             if matches!(node.kind, OpKind::Skip | OpKind::None) {
                 continue;
             }
 
-            // 衝突解決
-            let policy = options.default_conflict_policy;
+            // 衝突解決（ルールに指定があればそちらを優先）
+            let policy = node.rule_id
+                .and_then(|rule_id| rule_map.get(&rule_id))
+                .and_then(|rule| rule.policy)
+                .unwrap_or(options.default_conflict_policy);
             let (resolved_path, conflicts) = self.conflict_resolver
-                .resolve_conflicts(&node.path_after, policy)?;
+                .resolve_conflicts(&node.path_before, &node.path_after, policy)?;
 
             // 解決されたパスを反映
             if resolved_path != node.path_after {
@@ -160,42 +232,98 @@ impl MovePlanner {
             // 衝突を記録
             node.conflicts = conflicts;
 
+            // 既にあるフォルダにまとめる場合は、このノード自体は何もせず配下の項目を移動する
+            if policy == ConflictPolicy::Merge && node.is_dir && node.path_after.is_dir() {
+                node.kind = OpKind::None;
+                let (source, dest) = (node.path_before.clone(), node.path_after.clone());
+                node.children = self.plan_merge(node, &source, &dest, options, &mut merged_nodes)?;
+                debug!("Node {}: merging into existing {}", node_id.0, dest.display());
+                continue;
+            }
+
+            // 衝突回避で名前が長くなることもあるので、最終的な移動先で配下のパスの長さを見積もる
+            if !node.warnings.contains(&Warning::LongPath)
+                && exceeds_max_path_after_move(&node.path_before, &node.path_after)
+            {
+                node.warnings.push(Warning::LongPath);
+            }
+
             debug!("Node {}: {} conflicts resolved", node_id.0, node.conflicts.len());
         }
 
+        nodes.extend(merged_nodes.into_iter().map(|node| (node.id, node)));
+
         Ok(())
     }
 
-    fn calculate_summary(&self, nodes: &HashMap<PlanNodeId, PlanNode>) -> PlanSummary {
-        let mut summary = PlanSummary::default();
-
-        for node in nodes.values() {
-            if matches!(node.kind, OpKind::Skip | OpKind::None) {
-                continue;
-            }
+    /// source の中身を既にある dest に入れる操作を作り、直下の項目のノード ID を返す
+    ///
+    /// dest にない項目は丸ごと移動し、両方にあるフォルダは中へ進む。同名のファイルは
+    /// merge_file_policy で解決する。移動元のフォルダは空になっても残す。
+    fn plan_merge(
+        &mut self,
+        parent: &PlanNode,
+        source: &Path,
+        dest: &Path,
+        options: &PlanOptions,
+        merged_nodes: &mut Vec<PlanNode>,
+    ) -> Result<Vec<PlanNodeId>, FileMoverError> {
+        let file_policy = match options.merge_file_policy {
+            ConflictPolicy::Merge => ConflictPolicy::AutoRename,
+            policy => policy,
+        };
 
-            if node.is_dir {
-                summary.count_dirs += 1;
+        let mut entries = std::fs::read_dir(source)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        let mut children = Vec::with_capacity(entries.len());
+        for entry in entries {
+            self.cancel_token.check()?;
+
+            // パスは OsString のまま組み立て、表示用の名前だけ lossy に変換する
+            let file_name = entry.file_name();
+            let name = file_name.to_string_lossy().into_owned();
+            let path_before = entry.path();
+            let path_after = dest.join(&file_name);
+            let metadata = entry.metadata()?;
+
+            let mut child = PlanNode {
+                id: PlanNodeId::new(),
+                is_dir: metadata.is_dir(),
+                name_before: name.clone(),
+                kind: self.determine_operation_kind(&path_before, &path_after),
+                path_before,
+                name_after: name,
+                path_after,
+                size_bytes: (!metadata.is_dir()).then_some(metadata.len()),
+                warnings: Vec::new(),
+                conflicts: Vec::new(),
+                children: Vec::new(),
+                rule_id: parent.rule_id,
+                tags: parent.tags.clone(),
+            };
+
+            if child.is_dir && child.path_after.is_dir() {
+                child.kind = OpKind::None;
+                let (source, dest) = (child.path_before.clone(), child.path_after.clone());
+                child.children = self.plan_merge(&child, &source, &dest, options, merged_nodes)?;
             } else {
-                summary.count_files += 1;
-            }
-
-            if let Some(size) = node.size_bytes {
-                summary.total_bytes = Some(summary.total_bytes.unwrap_or(0) + size);
+                let (resolved_path, conflicts) = self.conflict_resolver
+                    .resolve_conflicts(&child.path_before, &child.path_after, file_policy)?;
+                if resolved_path != child.path_after {
+                    child.name_after = resolved_path.file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| child.name_before.clone());
+                    child.path_after = resolved_path;
+                }
+                child.conflicts = conflicts;
             }
 
-            summary.conflicts += node.conflicts.len() as u64;
-            summary.warnings += node.warnings.len() as u64;
-
-            if self.is_cross_volume(&node.path_before, &node.path_after) {
-                summary.cross_volume += 1;
-            }
+            children.push(child.id);
+            merged_nodes.push(child);
         }
 
-        debug!("Plan summary: {} dirs, {} conflicts, {} warnings", 
-               summary.count_dirs, summary.conflicts, summary.warnings);
-
-        summary
+        Ok(children)
     }
 
     fn apply_validation_result(
@@ -217,6 +345,7 @@ impl MovePlanner {
 
         // サマリを更新
         self.apply_summary_diff(&mut plan.summary, &validation_result.summary_diff);
+        plan.summary.preservation = PreservationSummary::from_nodes(plan.nodes.values());
 
         Ok(())
     }
@@ -234,27 +363,64 @@ impl MovePlanner {
         }
     }
 
+    /// 計画を実行した場合の結果と所要時間を見積もる
+    ///
+    /// 所要時間は操作ごとの固定の時間と、コピーするバイト数を速度で割った時間の合計。
+    /// 同じボリューム内の移動は名前の変更だけなので、大きさによらず固定の時間だけとする。
     pub fn simulate_plan(&self, plan: &MovePlan) -> Result<SimulationReport, FileMoverError> {
-        let mut report = SimulationReport::new();
+        let mut report = SimulationReport {
+            throughput: self.throughput,
+            ..Default::default()
+        };
+        let mut total_secs = 0.0;
 
         for node in plan.nodes.values() {
-            match node.kind {
-                OpKind::Skip | OpKind::None => {
-                    report.skipped_count += 1;
-                }
-                _ => {
-                    if node.conflicts.is_empty() {
-                        report.success_estimate += 1;
-                    } else {
-                        report.conflicts_remaining += 1;
-                    }
-                }
+            if matches!(node.kind, OpKind::Skip | OpKind::None) {
+                report.skipped_count += 1;
+                continue;
+            }
+            if node.conflicts.is_empty() {
+                report.success_estimate += 1;
+            } else {
+                report.conflicts_remaining += 1;
             }
+
+            let bytes = node.size_bytes.unwrap_or(0);
+            if node.size_bytes.is_none() {
+                report.unknown_size_count += 1;
+            }
+            let copied_bytes = if is_copy(node.kind, &node.path_before, &node.path_after) {
+                bytes
+            } else {
+                0
+            };
+            let secs = self.throughput.estimate_secs(copied_bytes);
+
+            report.total_bytes += bytes;
+            report.copied_bytes += copied_bytes;
+            total_secs += secs;
+
+            let position = match report.by_operation.iter().position(|op| op.kind == node.kind) {
+                Some(position) => position,
+                None => {
+                    report.by_operation.push(OperationEstimate::new(node.kind));
+                    report.by_operation.len() - 1
+                }
+            };
+            let operation = &mut report.by_operation[position];
+            operation.count += 1;
+            operation.bytes += bytes;
+            operation.copied_bytes += copied_bytes;
+            operation.estimated_secs += secs;
         }
 
-        // 推定実行時間（簡易計算）
-        let estimated_seconds = plan.summary.count_dirs * 2; // フォルダあたり2秒と仮定
-        report.estimated_duration_secs = estimated_seconds;
+        report.by_operation.sort_by_key(|op| op.kind as u8);
+        report.cross_volume_ratio = if report.total_bytes > 0 {
+            report.copied_bytes as f64 / report.total_bytes as f64
+        } else {
+            0.0
+        };
+        report.estimated_duration_secs = total_secs.ceil() as u64;
 
         Ok(report)
     }
@@ -264,10 +430,51 @@ impl MovePlanner {
         plan: &mut MovePlan,
         change: filemover_types::NodeChange,
     ) -> Result<filemover_types::ValidationDelta, FileMoverError> {
-        let validation_result = self.validator.validate_incremental_change(plan, change)?;
+        let merge_node = match change {
+            filemover_types::NodeChange::SetConflictPolicy(node_id, ConflictPolicy::Merge) => Some(node_id),
+            _ => None,
+        };
+        let summary_before = plan.summary.clone();
+
+        let mut validation_result = self.validator.validate_incremental_change(plan, change)?;
         self.apply_validation_result(plan, validation_result.clone())?;
+
+        if let Some(node_id) = merge_node {
+            self.expand_merge(plan, node_id, &mut validation_result)?;
+            validation_result.summary_diff = summary_diff(&summary_before, &plan.summary);
+        }
         Ok(validation_result)
     }
+
+    /// 既にあるフォルダへ中身をまとめるノードを、作成時と同じように配下の項目ごとの操作に展開する
+    fn expand_merge(
+        &mut self,
+        plan: &mut MovePlan,
+        node_id: PlanNodeId,
+        delta: &mut filemover_types::ValidationDelta,
+    ) -> Result<(), FileMoverError> {
+        let node = plan.nodes.get(&node_id)
+            .cloned()
+            .ok_or_else(|| FileMoverError::InvalidNodeId(node_id.to_string()))?;
+
+        let mut merged_nodes = Vec::new();
+        let children = self.plan_merge(&node, &node.path_before, &node.path_after, &PlanOptions::default(), &mut merged_nodes)?;
+        for child in &merged_nodes {
+            delta.affected_nodes.push(child.id);
+            delta.new_conflicts.extend(child.conflicts.iter().cloned());
+        }
+
+        if let Some(node) = plan.nodes.get_mut(&node_id) {
+            node.kind = OpKind::None;
+            node.children = children;
+        }
+        plan.nodes.extend(merged_nodes.into_iter().map(|node| (node.id, node)));
+        plan.summary = calculate_summary(&plan.nodes);
+        plan.execution_order = execution_order(plan).order;
+
+        debug!("Node {}: merging into existing {}", node_id.0, node.path_after.display());
+        Ok(())
+    }
 }
 
 impl Default for MovePlanner {
@@ -276,29 +483,112 @@ impl Default for MovePlanner {
     }
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct SimulationReport {
     pub success_estimate: u64,
     pub conflicts_remaining: u64,
     pub skipped_count: u64,
     pub estimated_duration_secs: u64,
+    /// 実行する操作の合計サイズ（サイズ不明のノードは含めない）
+    pub total_bytes: u64,
+    /// そのうちボリュームをまたいでコピーするバイト数
+    pub copied_bytes: u64,
+    /// total_bytes のうちコピーする割合（0.0〜1.0）
+    pub cross_volume_ratio: f64,
+    /// サイズ不明で見積もりがコピー時間を含まない操作の数
+    pub unknown_size_count: u64,
+    pub throughput: ThroughputModel,
+    /// 操作の種類ごとの内訳
+    pub by_operation: Vec<OperationEstimate>,
 }
 
 impl SimulationReport {
     pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// 操作の種類ごとの見積もり
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OperationEstimate {
+    pub kind: OpKind,
+    pub count: u64,
+    pub bytes: u64,
+    pub copied_bytes: u64,
+    pub estimated_secs: f64,
+}
+
+impl OperationEstimate {
+    pub fn new(kind: OpKind) -> Self {
         Self {
-            success_estimate: 0,
-            conflicts_remaining: 0,
-            skipped_count: 0,
-            estimated_duration_secs: 0,
+            kind,
+            count: 0,
+            bytes: 0,
+            copied_bytes: 0,
+            estimated_secs: 0.0,
+        }
+    }
+}
+
+/// ノードからサマリを計算する（スキップと何もしないノードは数えない）
+pub fn calculate_summary(nodes: &HashMap<PlanNodeId, PlanNode>) -> PlanSummary {
+    let mut summary = PlanSummary::default();
+
+    for node in nodes.values() {
+        if matches!(node.kind, OpKind::Skip | OpKind::None) {
+            continue;
+        }
+
+        if node.is_dir {
+            summary.count_dirs += 1;
+        } else {
+            summary.count_files += 1;
+        }
+
+        if let Some(size) = node.size_bytes {
+            summary.total_bytes = Some(summary.total_bytes.unwrap_or(0) + size);
+        }
+
+        summary.conflicts += node.conflicts.len() as u64;
+        summary.warnings += node.warnings.len() as u64;
+
+        if is_cross_volume(&node.path_before, &node.path_after) {
+            summary.cross_volume += 1;
         }
     }
+
+    summary.preservation = PreservationSummary::from_nodes(nodes.values());
+
+    debug!("Plan summary: {} dirs, {} conflicts, {} warnings", 
+           summary.count_dirs, summary.conflicts, summary.warnings);
+
+    summary
+}
+
+fn summary_diff(old: &PlanSummary, new: &PlanSummary) -> filemover_types::PlanSummaryDiff {
+    filemover_types::PlanSummaryDiff {
+        count_dirs_delta: new.count_dirs as i64 - old.count_dirs as i64,
+        count_files_delta: new.count_files as i64 - old.count_files as i64,
+        total_bytes_delta: match (new.total_bytes, old.total_bytes) {
+            (None, None) => None,
+            (new, old) => Some(new.unwrap_or(0) as i64 - old.unwrap_or(0) as i64),
+        },
+        cross_volume_delta: new.cross_volume as i64 - old.cross_volume as i64,
+        conflicts_delta: new.conflicts as i64 - old.conflicts as i64,
+        warnings_delta: new.warnings as i64 - old.warnings as i64,
+    }
+}
+
+pub(crate) fn is_cross_volume(source: &Path, dest: &Path) -> bool {
+    let source_drive = source.components().next();
+    let dest_drive = dest.components().next();
+    source_drive != dest_drive
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use filemover_types::{PatternSpec, Warning};
+    use filemover_types::{Conflict, PatternSpec, Warning};
     use std::path::PathBuf;
 
     fn create_test_rule() -> Rule {
@@ -314,9 +604,13 @@ mod tests {
             path: PathBuf::from("C:\\Source\\test_folder"),
             name: "test_folder".to_string(),
             matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
             dest_preview: None,
             warnings: Vec::new(),
             size_bytes: Some(1024 * 1024), // 1MB
+            item_count: None,
+            is_dir: true,
         }
     }
 
@@ -338,6 +632,223 @@ mod tests {
         assert_eq!(plan.summary.count_dirs, 1);
     }
 
+    #[test]
+    fn test_plan_creation_with_file_hit() {
+        let mut planner = MovePlanner::new();
+        let rule = create_test_rule();
+        let mut hit = create_test_folder_hit();
+        hit.matched_rule = Some(rule.id);
+        hit.is_dir = false;
+
+        let plan = planner.create_plan(&[hit], &[rule], PlanOptions::default()).unwrap();
+
+        assert!(plan.nodes.values().all(|node| !node.is_dir));
+        assert_eq!(plan.summary.count_dirs, 0);
+        assert_eq!(plan.summary.count_files, 1);
+    }
+
+    #[test]
+    fn test_merge_into_existing_folder() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("src").join("test_folder");
+        let archive = temp_dir.path().join("archive");
+        std::fs::create_dir_all(source.join("docs")).unwrap();
+        std::fs::write(source.join("new.txt"), "new").unwrap();
+        std::fs::write(source.join("same.txt"), "mine").unwrap();
+        std::fs::write(source.join("docs").join("a.txt"), "a").unwrap();
+        std::fs::create_dir_all(archive.join("test_folder").join("docs")).unwrap();
+        std::fs::write(archive.join("test_folder").join("same.txt"), "theirs").unwrap();
+
+        let mut planner = MovePlanner::new();
+        let rule = Rule::new(PatternSpec::new_glob("test*"), archive.clone(), "{name}".to_string());
+        let hit = FolderHit {
+            path: source.clone(),
+            matched_rule: Some(rule.id),
+            ..create_test_folder_hit()
+        };
+        let options = PlanOptions {
+            default_conflict_policy: ConflictPolicy::Merge,
+            ..Default::default()
+        };
+        let plan = planner.create_plan(&[hit], &[rule], options).unwrap();
+
+        let node_for = |path: PathBuf| plan.nodes.values().find(|node| node.path_before == path).unwrap();
+        let root = node_for(source.clone());
+        assert_eq!(root.kind, OpKind::None);
+        assert_eq!(root.children.len(), 3);
+        assert!(!root.conflicts.iter().any(|c| matches!(c, Conflict::NameExists { .. })));
+
+        // 両方にあるフォルダは中へ進み、ないものは移動する
+        assert_eq!(node_for(source.join("docs")).kind, OpKind::None);
+        assert_eq!(node_for(source.join("docs").join("a.txt")).kind, OpKind::Move);
+        assert_eq!(node_for(source.join("new.txt")).path_after, archive.join("test_folder").join("new.txt"));
+        // 同名のファイルは既定で自動リネーム
+        assert_eq!(node_for(source.join("same.txt")).path_after, archive.join("test_folder").join("same_1.txt"));
+        assert_eq!(plan.summary.count_files, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_merge_keeps_non_utf8_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("src").join("test_folder");
+        let archive = temp_dir.path().join("archive");
+        let name = OsStr::from_bytes(b"report_\xff.txt");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join(name), "data").unwrap();
+        std::fs::create_dir_all(archive.join("test_folder")).unwrap();
+
+        let mut planner = MovePlanner::new();
+        let rule = Rule::new(PatternSpec::new_glob("test*"), archive.clone(), "{name}".to_string());
+        let hit = FolderHit {
+            path: source.clone(),
+            matched_rule: Some(rule.id),
+            ..create_test_folder_hit()
+        };
+        let options = PlanOptions {
+            default_conflict_policy: ConflictPolicy::Merge,
+            ..Default::default()
+        };
+        let plan = planner.create_plan(&[hit], &[rule], options).unwrap();
+
+        // 移動先のパスは元のバイト列のまま、表示名だけ置換文字になる
+        let node = plan.nodes.values().find(|node| node.path_before == source.join(name)).unwrap();
+        assert_eq!(node.path_after, archive.join("test_folder").join(name));
+        assert_eq!(node.name_after, "report_\u{fffd}.txt");
+    }
+
+    #[test]
+    fn test_merge_chosen_after_planning() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("src").join("test_folder");
+        let archive = temp_dir.path().join("archive");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("new.txt"), "new").unwrap();
+        std::fs::create_dir_all(archive.join("test_folder")).unwrap();
+
+        let mut planner = MovePlanner::new();
+        let rule = Rule::new(PatternSpec::new_glob("test*"), archive.clone(), "{name}".to_string());
+        let hit = FolderHit {
+            path: source.clone(),
+            matched_rule: Some(rule.id),
+            ..create_test_folder_hit()
+        };
+        let options = PlanOptions {
+            default_conflict_policy: ConflictPolicy::Skip,
+            ..Default::default()
+        };
+        let mut plan = planner.create_plan(&[hit], &[rule], options).unwrap();
+        let root_id = plan.roots[0];
+        assert!(plan.nodes[&root_id].conflicts.iter().any(|c| matches!(c, Conflict::NameExists { .. })));
+
+        let delta = planner
+            .update_plan_with_change(&mut plan, filemover_types::NodeChange::SetConflictPolicy(root_id, ConflictPolicy::Merge))
+            .unwrap();
+        assert_eq!(delta.resolved_conflicts.len(), 1);
+        assert_eq!(delta.affected_nodes.len(), 2);
+
+        // 作成時に Merge を選んだ場合と同じく、配下の項目の移動に展開される
+        let root = &plan.nodes[&root_id];
+        assert_eq!(root.kind, OpKind::None);
+        assert!(!root.conflicts.iter().any(|c| matches!(c, Conflict::NameExists { .. })));
+        let child = &plan.nodes[&root.children[0]];
+        assert_eq!(child.path_after, archive.join("test_folder").join("new.txt"));
+        assert_eq!(plan.summary.count_files, 1);
+        assert_eq!(plan.execution_order, vec![child.id]);
+    }
+
+    #[test]
+    fn test_rule_policy_overrides_default() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let archive = temp_dir.path().join("archive");
+        std::fs::create_dir_all(archive.join("downloads")).unwrap();
+        std::fs::create_dir_all(archive.join("client")).unwrap();
+
+        let rename_rule = Rule::new(PatternSpec::new_glob("downloads"), archive.clone(), "{name}".to_string())
+            .with_policy(ConflictPolicy::AutoRename);
+        let default_rule = Rule::new(PatternSpec::new_glob("client"), archive.clone(), "{name}".to_string());
+        let hit_for = |name: &str, rule: &Rule| FolderHit {
+            path: temp_dir.path().join("src").join(name),
+            name: name.to_string(),
+            matched_rule: Some(rule.id),
+            ..create_test_folder_hit()
+        };
+        let hits = [hit_for("downloads", &rename_rule), hit_for("client", &default_rule)];
+
+        let mut planner = MovePlanner::new();
+        let options = PlanOptions {
+            default_conflict_policy: ConflictPolicy::Skip,
+            ..Default::default()
+        };
+        let plan = planner.create_plan(&hits, &[rename_rule, default_rule], options).unwrap();
+
+        let node_for = |name: &str| plan.nodes.values().find(|node| node.name_before == name).unwrap();
+        assert_eq!(node_for("downloads").path_after, archive.join("downloads_1"));
+        // ルールに指定がなければ既定の Skip に従い、衝突が残る
+        assert_eq!(node_for("client").path_after, archive.join("client"));
+        assert!(node_for("client").conflicts.iter().any(|c| matches!(c, Conflict::NameExists { .. })));
+    }
+
+    #[test]
+    fn test_cloud_placeholders_can_be_skipped() {
+        let mut planner = MovePlanner::new();
+        let rule = create_test_rule();
+        let mut hit = create_test_folder_hit();
+        hit.matched_rule = Some(rule.id);
+        hit.warnings.push(Warning::Offline);
+
+        let options = PlanOptions {
+            cloud_placeholders: PlaceholderPolicy::Skip,
+            ..Default::default()
+        };
+        let plan = planner.create_plan(std::slice::from_ref(&hit), std::slice::from_ref(&rule), options).unwrap();
+        assert!(plan.nodes.values().all(|node| node.kind == OpKind::Skip));
+
+        let plan = planner.create_plan(&[hit], &[rule], PlanOptions::default()).unwrap();
+        assert!(plan.nodes.values().all(|node| node.kind != OpKind::Skip));
+    }
+
+    #[test]
+    fn test_long_path_after_move_is_warned() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("test_folder");
+        std::fs::create_dir_all(source.join("a".repeat(100)).join("b".repeat(100))).unwrap();
+
+        let mut planner = MovePlanner::new();
+        let rule = Rule::new(
+            PatternSpec::new_glob("test*"),
+            temp_dir.path().join("c".repeat(80)),
+            "{name}".to_string(),
+        );
+        let hit = FolderHit {
+            path: source,
+            matched_rule: Some(rule.id),
+            matched_rules: vec![rule.id],
+            tags: Vec::new(),
+            ..create_test_folder_hit()
+        };
+
+        let plan = planner.create_plan(&[hit], &[rule], PlanOptions::default()).unwrap();
+        let node = plan.nodes.values().next().unwrap();
+        assert!(node.warnings.contains(&Warning::LongPath));
+    }
+
+    #[test]
+    fn test_plan_creation_cancelled() {
+        let token = CancellationToken::new();
+        let mut planner = MovePlanner::new().with_cancellation(token.clone());
+        let rule = create_test_rule();
+        let mut hit = create_test_folder_hit();
+        hit.matched_rule = Some(rule.id);
+        token.cancel();
+
+        let result = planner.create_plan(&[hit], &[rule], PlanOptions::default());
+        assert!(matches!(result, Err(FileMoverError::Cancelled)));
+    }
+
     #[test]
     fn test_cross_volume_detection() {
         let planner = MovePlanner::new();
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::path::Path;
+use filemover_types::Warning;
+use crate::disk_space::{volume_id, VolumeId};
+
+/// ファイルシステムが保てる情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreservationCaps {
+    pub acl: bool,
+    /// 作成・更新日時を秒未満まで保てるか
+    pub precise_timestamps: bool,
+    pub alternate_streams: bool,
+    pub compression: bool,
+}
+
+impl PreservationCaps {
+    /// ファイルシステム名から（知らない名前は None）
+    pub fn for_filesystem(name: &str) -> Option<Self> {
+        let caps = |acl, precise_timestamps, alternate_streams, compression| Self {
+            acl,
+            precise_timestamps,
+            alternate_streams,
+            compression,
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "ntfs" | "ntfs3" => Some(caps(true, true, true, true)),
+            "refs" => Some(caps(true, true, true, false)),
+            "fat" | "fat12" | "fat16" | "fat32" | "vfat" | "msdos" => Some(caps(false, false, false, false)),
+            "exfat" => Some(caps(false, true, false, false)),
+            "btrfs" => Some(caps(true, true, false, true)),
+            "ext2" | "ext3" | "ext4" | "xfs" | "tmpfs" | "apfs" | "hfs" => Some(caps(true, true, false, false)),
+            _ => None,
+        }
+    }
+
+    /// このファイルシステムから dest へ移すと失われる情報の警告
+    pub fn losses_to(&self, dest: &Self) -> Vec<Warning> {
+        [
+            (self.acl && !dest.acl, Warning::AclNotPreserved),
+            (self.precise_timestamps && !dest.precise_timestamps, Warning::TimestampsDegraded),
+            (self.alternate_streams && !dest.alternate_streams, Warning::StreamsNotPreserved),
+            (self.compression && !dest.compression, Warning::CompressionNotPreserved),
+        ]
+        .into_iter()
+        .filter_map(|(lost, warning)| lost.then_some(warning))
+        .collect()
+    }
+}
+
+/// 移動元と移動先のファイルシステムを比べて、保てない情報を調べる（ボリュームごとに結果を覚える）
+#[derive(Debug, Default)]
+pub struct PreservationChecker {
+    caps: HashMap<VolumeId, Option<PreservationCaps>>,
+}
+
+impl PreservationChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// source を dest へ移すと失われる情報の警告
+    ///
+    /// 同じボリューム内の移動は名前の変更なので何も失われない。ファイルシステムが分からない場合も警告しない。
+    pub fn losses(&mut self, source: &Path, dest: &Path) -> Vec<Warning> {
+        let (Some(source_volume), Some(dest_volume)) = (volume_id(source), volume_id(dest)) else {
+            return Vec::new();
+        };
+        if source_volume == dest_volume {
+            return Vec::new();
+        }
+
+        let source_caps = self.caps_of(source_volume, source);
+        let dest_caps = self.caps_of(dest_volume, dest);
+        match (source_caps, dest_caps) {
+            (Some(source_caps), Some(dest_caps)) => source_caps.losses_to(&dest_caps),
+            _ => Vec::new(),
+        }
+    }
+
+    fn caps_of(&mut self, volume: VolumeId, path: &Path) -> Option<PreservationCaps> {
+        *self.caps.entry(volume)
+            .or_insert_with(|| filesystem_name(path).and_then(|name| PreservationCaps::for_filesystem(&name)))
+    }
+}
+
+/// path（存在しなければ最も近い存在する祖先）のファイルシステム名
+pub fn filesystem_name(path: &Path) -> Option<String> {
+    let existing = path.ancestors().find(|p| !p.as_os_str().is_empty() && p.exists())?;
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+        let name = match stat.f_type as u32 {
+            0xEF53 => "ext4",
+            0x5846_5342 => "xfs",
+            0x9123_683E => "btrfs",
+            0x0102_1994 => "tmpfs",
+            0x4d44 => "vfat",
+            0x2011_BAB0 => "exfat",
+            0x5346_544e | 0x7366_746e => "ntfs",
+            _ => return None,
+        };
+        Some(name.to_string())
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::fileapi::{GetVolumeInformationW, GetVolumePathNameW};
+
+        let wide: Vec<u16> = existing.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let mut root = [0u16; 261];
+        if unsafe { GetVolumePathNameW(wide.as_ptr(), root.as_mut_ptr(), root.len() as u32) } == 0 {
+            return None;
+        }
+        let mut name = [0u16; 64];
+        let ok = unsafe {
+            GetVolumeInformationW(
+                root.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                name.as_mut_ptr(),
+                name.len() as u32,
+            )
+        };
+        if ok == 0 {
+            return None;
+        }
+        let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+        Some(String::from_utf16_lossy(&name[..len]))
+    }
+    #[cfg(not(any(target_os = "linux", windows)))]
+    {
+        let _ = existing;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ntfs_to_exfat_loses_acl_and_streams() {
+        let ntfs = PreservationCaps::for_filesystem("NTFS").unwrap();
+        let exfat = PreservationCaps::for_filesystem("exFAT").unwrap();
+        let fat32 = PreservationCaps::for_filesystem("FAT32").unwrap();
+
+        assert_eq!(
+            ntfs.losses_to(&exfat),
+            vec![Warning::AclNotPreserved, Warning::StreamsNotPreserved, Warning::CompressionNotPreserved]
+        );
+        assert!(ntfs.losses_to(&fat32).contains(&Warning::TimestampsDegraded));
+        // 元々持っていない情報は失われない
+        assert!(exfat.losses_to(&ntfs).is_empty());
+        assert!(PreservationCaps::for_filesystem("unknownfs").is_none());
+    }
+
+    #[test]
+    fn test_same_volume_loses_nothing() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut checker = PreservationChecker::new();
+        assert!(checker.losses(&temp_dir.path().join("a"), &temp_dir.path().join("b")).is_empty());
+    }
+}
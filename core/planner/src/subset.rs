@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+use filemover_types::{MovePlan, PlanNode, PlanNodeId};
+use crate::ordering::execution_order;
+use crate::planner::calculate_summary;
+
+/// keep を満たすルートと、その配下のノードだけの計画を作る（サマリは作り直す）
+///
+/// 判定はルートの操作だけに使う。Merge で展開した配下の項目はルートと一緒に残す。
+pub fn subset_plan<F>(plan: &MovePlan, mut keep: F) -> MovePlan
+where
+    F: FnMut(&PlanNode) -> bool,
+{
+    let roots = plan.roots.iter()
+        .filter(|id| plan.nodes.get(id).is_some_and(&mut keep))
+        .copied()
+        .collect::<Vec<_>>();
+
+    let mut kept: HashSet<PlanNodeId> = HashSet::new();
+    let mut stack = roots.clone();
+    while let Some(id) = stack.pop() {
+        if !kept.insert(id) {
+            continue;
+        }
+        if let Some(node) = plan.nodes.get(&id) {
+            stack.extend(node.children.iter().copied());
+        }
+    }
+
+    let nodes = plan.nodes.iter()
+        .filter(|(id, _)| kept.contains(id))
+        .map(|(id, node)| (*id, node.clone()))
+        .collect();
+    let summary = calculate_summary(&nodes);
+
+    let mut subset = MovePlan { roots, nodes, summary, execution_order: Vec::new() };
+    subset.execution_order = execution_order(&subset).order;
+    subset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use filemover_types::{OpKind, PlanSummary};
+
+    fn node(name: &str, size: u64) -> PlanNode {
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: name.to_string(),
+            path_before: PathBuf::from("/source").join(name),
+            name_after: name.to_string(),
+            path_after: PathBuf::from("/archive").join(name),
+            kind: OpKind::Move,
+            size_bytes: Some(size),
+            warnings: Vec::new(),
+            conflicts: Vec::new(),
+            children: Vec::new(),
+            rule_id: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_subset_keeps_children_of_selected_roots() {
+        let child = node("big/inner", 10);
+        let mut big = node("big", 5000);
+        big.kind = OpKind::None;
+        big.children.push(child.id);
+        let small = node("small", 10);
+
+        let plan = MovePlan {
+            roots: vec![big.id, small.id],
+            nodes: [big.clone(), small, child.clone()].into_iter().map(|n| (n.id, n)).collect(),
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+        };
+
+        let subset = subset_plan(&plan, |node| node.size_bytes.unwrap_or(0) >= 1000);
+        assert_eq!(subset.roots, vec![big.id]);
+        assert_eq!(subset.nodes.len(), 2);
+        assert!(subset.nodes.contains_key(&child.id));
+        // 何もしないルートは数えない
+        assert_eq!(subset.summary.count_dirs, 1);
+        assert_eq!(subset.summary.total_bytes, Some(10));
+    }
+}
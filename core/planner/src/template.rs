@@ -1,38 +1,84 @@
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
-use chrono::{DateTime, Utc};
-use filemover_types::{Rule, FileMoverError};
+use chrono::{DateTime, Datelike, Utc};
+use filemover_types::{expand_placeholders, extract_date, Rule, FileMoverError, TemplateFunction};
+
+/// {seq} の番号を探す上限（ConflictResolver の自動リネームと同じ）
+const MAX_SEQ: u32 = 9999;
 
 pub struct TemplateEngine {
     variables: HashMap<String, String>,
+    /// 移動先ディレクトリごとの最後に使った {seq} の番号
+    seq_counters: HashMap<PathBuf, u32>,
 }
 
 impl TemplateEngine {
     pub fn new() -> Self {
         Self {
             variables: HashMap::new(),
+            seq_counters: HashMap::new(),
         }
     }
 
+    /// {seq} の番号を 1 からやり直す（計画ごとに呼ぶ）
+    pub fn reset_sequences(&mut self) {
+        self.seq_counters.clear();
+    }
+
+    /// 既にあるパスを避けて {seq} を決める
     pub fn expand_template(&mut self, rule: &Rule, source_path: &Path) -> Result<PathBuf, FileMoverError> {
+        self.expand_template_with(rule, source_path, |path| path.exists())
+    }
+
+    /// is_taken が true を返すパスにならないように {seq} を決める（計画済みの移動先も避けるため）
+    pub fn expand_template_with<F>(
+        &mut self,
+        rule: &Rule,
+        source_path: &Path,
+        is_taken: F,
+    ) -> Result<PathBuf, FileMoverError>
+    where
+        F: FnMut(&Path) -> bool,
+    {
         self.prepare_variables(source_path)?;
+        self.prepare_source_date(rule, source_path);
+        self.prepare_source_times(source_path);
         
-        let mut result = rule.template.clone();
-        
-        // 基本変数の置換
-        for (key, value) in &self.variables {
-            let placeholder = format!("{{{}}}", key);
-            result = result.replace(&placeholder, value);
-        }
+        // 変数の置換と関数の適用（ルール固有の {label} はなければ空。{seq} は後で決める）
+        let result = expand_placeholders(&rule.template, |name| match name {
+            "label" => Some(rule.label.clone().unwrap_or_default()),
+            _ => self.variables.get(name).cloned(),
+        })?;
 
-        // ルール固有の変数
-        if let Some(label) = &rule.label {
-            result = result.replace("{label}", label);
-        } else {
-            result = result.replace("{label}", "");
+        self.expand_sequence(&rule.dest_root, &result, is_taken)
+    }
+
+    /// {seq} と {seq:03} を、同じディレクトリの中で次に使える番号にする
+    fn expand_sequence<F>(&mut self, dest_root: &Path, template: &str, mut is_taken: F) -> Result<PathBuf, FileMoverError>
+    where
+        F: FnMut(&Path) -> bool,
+    {
+        let Some(position) = template.find("{seq") else {
+            return Ok(dest_root.join(template));
+        };
+
+        // 番号を含む部分より前が同じなら同じ番号の列を使う
+        let dir = template[..position].rfind(['/', '\\']).map_or("", |end| &template[..end]);
+        let counter = self.seq_counters.entry(dest_root.join(dir)).or_insert(0);
+
+        while *counter < MAX_SEQ {
+            *counter += 1;
+            let seq = *counter;
+            let expanded = expand_placeholders(template, |name| sequence_value(name, seq))?;
+            let path = dest_root.join(expanded);
+            if !is_taken(&path) {
+                return Ok(path);
+            }
         }
 
-        Ok(rule.dest_root.join(result))
+        Err(FileMoverError::PlanValidation {
+            message: format!("No free {{seq}} number left under {}", dest_root.join(dir).display()),
+        })
     }
 
     fn prepare_variables(&mut self, source_path: &Path) -> Result<(), FileMoverError> {
@@ -72,6 +118,37 @@ impl TemplateEngine {
         Ok(())
     }
 
+    /// 名前に含まれる日付（見つからない部分は現在の日付）
+    fn prepare_source_date(&mut self, rule: &Rule, source_path: &Path) {
+        let now = Utc::now();
+        let date = source_path.file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|name| extract_date(name, &rule.date_formats));
+
+        let year = date.map(|d| d.year).unwrap_or_else(|| now.year());
+        let month = date.and_then(|d| d.month).unwrap_or_else(|| now.month());
+        let day = date.and_then(|d| d.day).unwrap_or_else(|| now.day());
+        self.variables.insert("src_yyyy".to_string(), format!("{:04}", year));
+        self.variables.insert("src_yy".to_string(), format!("{:02}", year % 100));
+        self.variables.insert("src_MM".to_string(), format!("{:02}", month));
+        self.variables.insert("src_dd".to_string(), format!("{:02}", day));
+    }
+
+    /// 移動元の更新日時・作成日時（UTC。作成日時が取れなければ更新日時、どちらもなければ現在）
+    fn prepare_source_times(&mut self, source_path: &Path) {
+        let metadata = std::fs::metadata(source_path).ok();
+        let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+        let created = metadata.as_ref().and_then(|m| m.created().ok()).or(modified);
+
+        for (prefix, time) in [("mtime", modified), ("ctime", created)] {
+            let time: DateTime<Utc> = time.map(DateTime::from).unwrap_or_else(Utc::now);
+            self.variables.insert(format!("{}_yyyy", prefix), time.format("%Y").to_string());
+            self.variables.insert(format!("{}_yy", prefix), time.format("%y").to_string());
+            self.variables.insert(format!("{}_MM", prefix), time.format("%m").to_string());
+            self.variables.insert(format!("{}_dd", prefix), time.format("%d").to_string());
+        }
+    }
+
     fn extract_drive_letter(&self, path: &Path) -> String {
         path.components()
             .next()
@@ -95,26 +172,22 @@ impl TemplateEngine {
             .to_string()
     }
 
+    /// 使われている変数の一覧を返す（`{name|slug|truncate:40}` の関数も確かめる）
     pub fn validate_template(template: &str) -> Result<Vec<String>, FileMoverError> {
         let mut variables = Vec::new();
         let mut chars = template.chars().peekable();
         
         while let Some(ch) = chars.next() {
             if ch == '{' {
-                let mut var_name = String::new();
+                let mut placeholder = String::new();
                 let mut found_closing = false;
                 
-                while let Some(ch) = chars.next() {
+                for ch in chars.by_ref() {
                     if ch == '}' {
                         found_closing = true;
                         break;
-                    } else if ch.is_alphanumeric() || ch == '_' {
-                        var_name.push(ch);
-                    } else {
-                        return Err(FileMoverError::Config {
-                            message: format!("Invalid character '{}' in template variable", ch),
-                        });
                     }
+                    placeholder.push(ch);
                 }
                 
                 if !found_closing {
@@ -123,8 +196,28 @@ impl TemplateEngine {
                     });
                 }
                 
+                let mut parts = placeholder.split('|');
+                let mut var_name = parts.next().unwrap_or_default();
+                // {seq:03} は桁数の指定
+                if let Some(width) = var_name.strip_prefix("seq:") {
+                    if sequence_width(width).is_none() {
+                        return Err(FileMoverError::Config {
+                            message: format!("Invalid width '{}' for template variable seq", width),
+                        });
+                    }
+                    var_name = "seq";
+                }
+                if let Some(ch) = var_name.chars().find(|c| !c.is_alphanumeric() && *c != '_') {
+                    return Err(FileMoverError::Config {
+                        message: format!("Invalid character '{}' in template variable", ch),
+                    });
+                }
+                for spec in parts {
+                    TemplateFunction::parse(spec)?;
+                }
+                
                 if !var_name.is_empty() {
-                    variables.push(var_name);
+                    variables.push(var_name.to_string());
                 }
             }
         }
@@ -132,7 +225,10 @@ impl TemplateEngine {
         // サポートされている変数のリスト
         let supported_vars = [
             "name", "yyyy", "yy", "MM", "dd", "yyyyMM", "yyyyMMdd",
-            "drive", "parent", "depth", "ext", "label"
+            "drive", "parent", "depth", "ext", "label",
+            "src_yyyy", "src_yy", "src_MM", "src_dd", "seq",
+            "mtime_yyyy", "mtime_yy", "mtime_MM", "mtime_dd",
+            "ctime_yyyy", "ctime_yy", "ctime_MM", "ctime_dd"
         ];
         
         for var in &variables {
@@ -147,6 +243,21 @@ impl TemplateEngine {
     }
 }
 
+/// `seq` または `seq:03` の値（それ以外の変数は None）
+fn sequence_value(name: &str, seq: u32) -> Option<String> {
+    match name {
+        "seq" => Some(seq.to_string()),
+        _ => {
+            let width = sequence_width(name.strip_prefix("seq:")?)?;
+            Some(format!("{:0width$}", seq, width = width))
+        }
+    }
+}
+
+fn sequence_width(width: &str) -> Option<usize> {
+    width.parse().ok().filter(|w| (1..=9).contains(w))
+}
+
 impl Default for TemplateEngine {
     fn default() -> Self {
         Self::new()
@@ -156,7 +267,7 @@ impl Default for TemplateEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use filemover_types::{PatternSpec, ConflictPolicy};
+    use filemover_types::PatternSpec;
 
     #[test]
     fn test_basic_template_expansion() {
@@ -209,6 +320,99 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_template_functions() {
+        let mut engine = TemplateEngine::new();
+        let rule = Rule::new(
+            PatternSpec::new_glob("*"),
+            PathBuf::from("/archive"),
+            "{parent|upper}/{depth|pad:4}/{name|slug|truncate:12}".to_string(),
+        );
+
+        let result = engine.expand_template(&rule, Path::new("/photos/My Summer Trip (2024)")).unwrap();
+        assert_eq!(result, PathBuf::from("/archive/PHOTOS/0003/my-summer-tr"));
+
+        assert_eq!(TemplateEngine::validate_template("{name|lower|pad:3:_}").unwrap(), vec!["name"]);
+        assert!(TemplateEngine::validate_template("{name|shout}").is_err());
+        assert!(TemplateEngine::validate_template("{name|truncate}").is_err());
+        assert!(TemplateEngine::validate_template("{name|truncate:0}").is_err());
+        assert!(TemplateEngine::validate_template("{name|slug:2}").is_err());
+        assert!(TemplateEngine::validate_template("{name|pad:3:ab}").is_err());
+    }
+
+    #[test]
+    fn test_sequence_per_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dest_root = temp_dir.path().to_path_buf();
+        std::fs::create_dir(dest_root.join("Box_002")).unwrap();
+
+        let mut engine = TemplateEngine::new();
+        let boxes = Rule::new(PatternSpec::new_glob("*"), dest_root.clone(), "Box_{seq:03}".to_string());
+        let expand = |engine: &mut TemplateEngine, rule: &Rule, source: &str| {
+            engine.expand_template(rule, Path::new(source)).unwrap()
+        };
+
+        assert_eq!(expand(&mut engine, &boxes, "/data/a"), dest_root.join("Box_001"));
+        // 既にある Box_002 は飛ばす
+        assert_eq!(expand(&mut engine, &boxes, "/data/b"), dest_root.join("Box_003"));
+
+        // ディレクトリごとに別の番号
+        let by_parent = Rule::new(PatternSpec::new_glob("*"), dest_root.clone(), "{parent}/{seq}_{name}".to_string());
+        assert_eq!(expand(&mut engine, &by_parent, "/x/a"), dest_root.join("x").join("1_a"));
+        assert_eq!(expand(&mut engine, &by_parent, "/y/b"), dest_root.join("y").join("1_b"));
+        assert_eq!(expand(&mut engine, &by_parent, "/x/c"), dest_root.join("x").join("2_c"));
+
+        engine.reset_sequences();
+        assert_eq!(expand(&mut engine, &boxes, "/data/c"), dest_root.join("Box_001"));
+
+        assert_eq!(TemplateEngine::validate_template("Box_{seq:03}").unwrap(), vec!["seq"]);
+        assert!(TemplateEngine::validate_template("Box_{seq:abc}").is_err());
+    }
+
+    #[test]
+    fn test_dates_from_timestamps() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        // ディレクトリのハンドルは Windows では開き方が異なるのでファイルで確かめる
+        let source = temp_dir.path().join("old_report.txt");
+        let modified = DateTime::parse_from_rfc3339("2019-07-15T12:00:00Z").unwrap();
+        std::fs::File::create(&source).unwrap()
+            .set_modified(modified.into())
+            .unwrap();
+
+        let mut engine = TemplateEngine::new();
+        let rule = Rule::new(
+            PatternSpec::new_glob("*"),
+            PathBuf::from("/archive"),
+            "{mtime_yyyy}/{mtime_MM}/{name}".to_string(),
+        );
+        let result = engine.expand_template(&rule, &source).unwrap();
+        assert_eq!(result, PathBuf::from("/archive/2019/07/old_report.txt"));
+
+        assert!(TemplateEngine::validate_template("{ctime_yyyy}/{ctime_dd}").is_ok());
+    }
+
+    #[test]
+    fn test_date_from_folder_name() {
+        let mut engine = TemplateEngine::new();
+        let rule = Rule::new(
+            PatternSpec::new_glob("*"),
+            PathBuf::from("/trips"),
+            "{src_yyyy}/{src_MM}/{name}".to_string(),
+        );
+
+        let result = engine.expand_template(&rule, Path::new("/photos/2023-04 Trip")).unwrap();
+        assert_eq!(result, PathBuf::from("/trips/2023/04/2023-04 Trip"));
+
+        // 数字の一部は日付とみなさない
+        let result = engine.expand_template(&rule, Path::new("/photos/IMG_20231301")).unwrap();
+        let now = Utc::now();
+        assert_eq!(result, PathBuf::from(format!("/trips/{}/{}/IMG_20231301", now.format("%Y"), now.format("%m"))));
+
+        let rule = rule.with_date_formats(vec!["%d.%m.%y".to_string()]);
+        let result = engine.expand_template(&rule, Path::new("/photos/Party 24.12.22")).unwrap();
+        assert_eq!(result, PathBuf::from("/trips/2022/12/Party 24.12.22"));
+    }
+
     #[test]
     fn test_complex_template() {
         let mut engine = TemplateEngine::new();
@@ -0,0 +1,175 @@
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use filemover_types::{JournalEntry, OpKind, ResultKind};
+use crate::planner::is_cross_volume;
+
+/// 計測に使う一時ファイルの大きさ
+const PROBE_BYTES: usize = 16 * 1024 * 1024;
+const PROBE_FILE_NAME: &str = ".filemover_throughput_probe";
+
+/// 速度の出どころ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThroughputSource {
+    /// 計測も記録も無いときの既定値
+    #[default]
+    Default,
+    /// 移動先に一時ファイルを書き込んで計測した
+    Measured,
+    /// 過去のジャーナルの記録から計算した
+    Recorded,
+}
+
+/// 所要時間の見積もりに使う速度
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputModel {
+    /// ボリュームをまたぐコピーの速度（バイト/秒）
+    pub copy_bytes_per_sec: f64,
+    /// 1 操作あたりの固定の時間（同じボリューム内の移動はこれだけ）
+    pub per_item_secs: f64,
+    pub source: ThroughputSource,
+}
+
+impl Default for ThroughputModel {
+    fn default() -> Self {
+        Self {
+            copy_bytes_per_sec: 100.0 * 1024.0 * 1024.0,
+            per_item_secs: 0.05,
+            source: ThroughputSource::Default,
+        }
+    }
+}
+
+impl ThroughputModel {
+    /// 1 操作の見積もり時間（秒）
+    pub fn estimate_secs(&self, copied_bytes: u64) -> f64 {
+        self.per_item_secs + copied_bytes as f64 / self.copy_bytes_per_sec
+    }
+
+    /// dir に一時ファイルを書き込んでコピーの速度を計測する（1 操作の時間は既定値のまま）
+    pub fn measure(dir: &Path) -> std::io::Result<Self> {
+        let probe = dir.join(PROBE_FILE_NAME);
+        let buffer = vec![0u8; PROBE_BYTES];
+
+        let started = Instant::now();
+        let written = (|| {
+            let mut file = std::fs::File::create(&probe)?;
+            file.write_all(&buffer)?;
+            file.sync_all()
+        })();
+        let elapsed = started.elapsed().as_secs_f64();
+        let _ = std::fs::remove_file(&probe);
+        written?;
+
+        Ok(Self {
+            copy_bytes_per_sec: PROBE_BYTES as f64 / elapsed.max(f64::EPSILON),
+            source: ThroughputSource::Measured,
+            ..Self::default()
+        })
+    }
+
+    /// ジャーナルの記録から速度を計算する（使える記録が無ければ None）
+    ///
+    /// エントリは操作が終わった時に書かれるので、直前のエントリとの時刻の差をその操作の時間とする。
+    /// コピーの記録が無い場合は既定の速度、同じボリューム内の記録が無い場合は既定の 1 操作の時間を使う。
+    pub fn from_journal(entries: &[JournalEntry]) -> Option<Self> {
+        let mut sorted = entries.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|entry| entry.when_utc);
+
+        let mut copied_bytes = 0u64;
+        let mut copy_secs = 0.0;
+        let mut item_count = 0u64;
+        let mut item_secs = 0.0;
+        for pair in sorted.windows(2) {
+            let entry = pair[1];
+            if entry.result != ResultKind::Ok {
+                continue;
+            }
+            let secs = (entry.when_utc - pair[0].when_utc).num_milliseconds().max(0) as f64 / 1000.0;
+            let size = entry.metadata.as_ref().and_then(|metadata| metadata.size_bytes);
+
+            match (is_copy(entry.op, &entry.source, &entry.dest), size) {
+                (true, Some(size)) if size > 0 => {
+                    copied_bytes += size;
+                    copy_secs += secs;
+                }
+                (false, _) if matches!(entry.op, OpKind::Move | OpKind::Rename) => {
+                    item_count += 1;
+                    item_secs += secs;
+                }
+                _ => {}
+            }
+        }
+
+        let defaults = Self::default();
+        let has_copies = copied_bytes > 0 && copy_secs > 0.0;
+        if !has_copies && item_count == 0 {
+            return None;
+        }
+
+        Some(Self {
+            copy_bytes_per_sec: if has_copies {
+                copied_bytes as f64 / copy_secs
+            } else {
+                defaults.copy_bytes_per_sec
+            },
+            per_item_secs: if item_count > 0 {
+                item_secs / item_count as f64
+            } else {
+                defaults.per_item_secs
+            },
+            source: ThroughputSource::Recorded,
+        })
+    }
+}
+
+/// 中身をコピーする操作か（ボリュームをまたぐ移動も含む）
+pub(crate) fn is_copy(kind: OpKind, source: &Path, dest: &Path) -> bool {
+    match kind {
+        OpKind::CopyDelete => true,
+        OpKind::Move => is_cross_volume(source, dest),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use chrono::{Duration, Utc};
+    use filemover_types::EntryMetadata;
+
+    fn entry(op: OpKind, dest: &str, size: u64, offset_secs: i64) -> JournalEntry {
+        let mut entry = JournalEntry::new(PathBuf::from("/data/folder"), PathBuf::from(dest), op)
+            .with_metadata(EntryMetadata::default().with_size(size));
+        entry.when_utc = Utc::now() + Duration::seconds(offset_secs);
+        entry
+    }
+
+    #[test]
+    fn test_throughput_from_journal() {
+        let entries = vec![
+            entry(OpKind::Move, "/archive/a", 0, 0),
+            // 2 秒で 200 MB をコピー
+            entry(OpKind::CopyDelete, "/backup/b", 200_000_000, 2),
+            // 同じボリューム内の移動に 1 秒
+            entry(OpKind::Move, "/archive/c", 0, 3),
+        ];
+
+        let model = ThroughputModel::from_journal(&entries).unwrap();
+        assert_eq!(model.source, ThroughputSource::Recorded);
+        assert_eq!(model.copy_bytes_per_sec, 100_000_000.0);
+        assert_eq!(model.per_item_secs, 1.0);
+        assert!(ThroughputModel::from_journal(&entries[..1]).is_none());
+    }
+
+    #[test]
+    fn test_measure_removes_probe() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let model = ThroughputModel::measure(temp_dir.path()).unwrap();
+        assert_eq!(model.source, ThroughputSource::Measured);
+        assert!(model.copy_bytes_per_sec > 0.0);
+        assert!(!temp_dir.path().join(PROBE_FILE_NAME).exists());
+    }
+}
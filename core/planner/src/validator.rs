@@ -1,22 +1,37 @@
 use std::collections::{HashMap, HashSet};
 use filemover_types::{
     MovePlan, PlanNode, PlanNodeId, ValidationDelta, NodeChange, 
-    Conflict, Warning, OpKind, PlanSummary, PlanSummaryDiff, FileMoverError
+    Conflict, OpKind, PlanSummary, PlanSummaryDiff, FileMoverError
 };
 use tracing::{debug, warn};
 use crate::conflict_resolver::ConflictResolver;
+use crate::disk_space::{available_space, measure_size, volume_id, VolumeId};
 
 pub struct PlanValidator {
     conflict_resolver: ConflictResolver,
+    free_space_override: Option<u64>,
 }
 
 impl PlanValidator {
     pub fn new() -> Self {
         Self {
             conflict_resolver: ConflictResolver::new(),
+            free_space_override: None,
         }
     }
 
+    /// 移動先のボリュームを調べずに、空き容量を固定する
+    pub fn with_free_space(mut self, bytes: u64) -> Self {
+        self.free_space_override = Some(bytes);
+        self
+    }
+
+    /// 移動先のファイルシステムを調べずに、大文字小文字の区別を固定する
+    pub fn with_case_sensitivity(mut self, case_sensitive: bool) -> Self {
+        self.conflict_resolver = self.conflict_resolver.with_case_sensitivity(case_sensitive);
+        self
+    }
+
     pub fn validate_full_plan(&mut self, plan: &MovePlan) -> Result<ValidationDelta, FileMoverError> {
         debug!("Starting full plan validation for {} nodes", plan.nodes.len());
         
@@ -44,6 +59,75 @@ impl PlanValidator {
         })
     }
 
+    /// 計画を作った後のファイルシステムの変化を反映する（実行の直前に使う）
+    ///
+    /// 移動元があるか、移動先が空いているか、空き容量が足りるかを調べ直し、変わったノードの衝突を
+    /// 書き換えてサマリを計算し直す。返す差分が空なら計画の前提は変わっていない。
+    pub fn revalidate(&mut self, plan: &mut MovePlan) -> Result<ValidationDelta, FileMoverError> {
+        debug!("Revalidating {} nodes against the file system", plan.nodes.len());
+
+        let mut live: HashMap<PlanNodeId, Vec<Conflict>> = HashMap::new();
+        for node in plan.nodes.values() {
+            if matches!(node.kind, OpKind::Skip | OpKind::None) {
+                continue;
+            }
+            let conflicts = live.entry(node.id).or_default();
+            if std::fs::symlink_metadata(&node.path_before).is_err() {
+                conflicts.push(Conflict::SourceMissing);
+            }
+            // 大文字小文字だけの名前変更では移動先は移動元そのもの
+            if std::fs::symlink_metadata(&node.path_after).is_ok()
+                && !self.conflict_resolver.paths_collide(&node.path_before, &node.path_after)
+            {
+                conflicts.push(Conflict::NameExists { existing_path: node.path_after.clone() });
+            }
+        }
+        for (node_id, conflict) in self.check_disk_space(plan)? {
+            live.entry(node_id).or_default().push(conflict);
+        }
+
+        let mut affected_nodes = Vec::new();
+        let mut new_conflicts = Vec::new();
+        let mut resolved_conflicts = Vec::new();
+        for (node_id, fresh) in live {
+            let Some(node) = plan.nodes.get_mut(&node_id) else {
+                continue;
+            };
+            let (recorded, other): (Vec<_>, Vec<_>) = node.conflicts.drain(..).partition(is_live_conflict);
+
+            let added = fresh.iter()
+                .filter(|conflict| !recorded.iter().any(|old| same_conflict(old, conflict)))
+                .cloned()
+                .collect::<Vec<_>>();
+            let resolved = recorded.iter()
+                .filter(|old| !fresh.iter().any(|conflict| same_conflict(old, conflict)))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            node.conflicts = other;
+            node.conflicts.extend(fresh);
+
+            if !added.is_empty() || !resolved.is_empty() {
+                affected_nodes.push(node_id);
+                new_conflicts.extend(added);
+                resolved_conflicts.extend(resolved);
+            }
+        }
+
+        let summary_diff = self.recalculate_summary(plan)?;
+        plan.summary = crate::planner::calculate_summary(&plan.nodes);
+
+        if !affected_nodes.is_empty() {
+            warn!("{} operations changed since the plan was created", affected_nodes.len());
+        }
+        Ok(ValidationDelta {
+            affected_nodes,
+            new_conflicts,
+            resolved_conflicts,
+            summary_diff,
+        })
+    }
+
     pub fn validate_incremental_change(
         &mut self,
         plan: &mut MovePlan,
@@ -91,13 +175,53 @@ impl PlanValidator {
             conflicts.push(Conflict::DestInsideSource);
         }
 
-        // ディスク容量チェック
-        if let Some(size) = node.size_bytes {
-            if let Some(space_conflict) = self.check_disk_space(&node.path_after, size)? {
-                conflicts.push(space_conflict);
+        Ok(conflicts)
+    }
+
+    /// 移動先のボリュームごとに必要な容量を積み上げ、空き容量を超えさせたノードの衝突を返す
+    ///
+    /// 同じボリューム内の移動は容量を使わないので数えない。サイズが分からないノードは実際に測る。
+    /// ノードは移動元のパス順に積み上げるので、同じ計画なら同じノードが対象になる。
+    pub fn check_disk_space(&self, plan: &MovePlan) -> Result<Vec<(PlanNodeId, Conflict)>, FileMoverError> {
+        let mut nodes = plan.nodes.values()
+            .filter(|node| !matches!(node.kind, OpKind::Skip | OpKind::None))
+            .collect::<Vec<_>>();
+        nodes.sort_by(|a, b| a.path_before.cmp(&b.path_before));
+
+        // ボリュームごとの (空き容量, 必要な容量の累計)
+        let mut volumes: HashMap<VolumeId, (Option<u64>, u64)> = HashMap::new();
+        let mut conflicts = Vec::new();
+
+        for node in nodes {
+            let Some(dest_volume) = volume_id(&node.path_after) else {
+                continue;
+            };
+            if volume_id(&node.path_before).as_ref() == Some(&dest_volume) {
+                continue;
+            }
+
+            let (available, required) = volumes.entry(dest_volume).or_insert_with(|| {
+                let available = self.free_space_override.or_else(|| available_space(&node.path_after));
+                (available, 0)
+            });
+            let Some(available) = *available else {
+                continue;
+            };
+
+            *required += node.size_bytes
+                .or_else(|| measure_size(&node.path_before))
+                .unwrap_or(0);
+            if *required > available {
+                conflicts.push((node.id, Conflict::NoSpace {
+                    required: *required,
+                    available,
+                }));
             }
         }
 
+        if !conflicts.is_empty() {
+            warn!("{} nodes do not fit in the free space of their destination volume", conflicts.len());
+        }
         Ok(conflicts)
     }
 
@@ -138,28 +262,13 @@ impl PlanValidator {
         Ok(false)
     }
 
-    fn check_disk_space(&self, _path: &std::path::Path, required_size: u64) -> Result<Option<Conflict>, FileMoverError> {
-        // 簡易実装: 実際にはstatvfsやGetDiskFreeSpaceExWを使用
-        // ここでは1GB未満の場合に容量不足とする
-        let available_space = 1024 * 1024 * 1024; // 1GB (仮の値)
-        
-        if required_size > available_space {
-            Ok(Some(Conflict::NoSpace {
-                required: required_size,
-                available: available_space,
-            }))
-        } else {
-            Ok(None)
-        }
-    }
-
     fn handle_skip_change(
         &mut self,
         plan: &mut MovePlan,
         node_id: PlanNodeId,
         skip: bool,
     ) -> Result<ValidationDelta, FileMoverError> {
-        let mut affected_nodes = vec![node_id];
+        let affected_nodes = vec![node_id];
         let mut resolved_conflicts = Vec::new();
 
         if let Some(node) = plan.nodes.get_mut(&node_id) {
@@ -193,25 +302,53 @@ impl PlanValidator {
         &mut self,
         plan: &mut MovePlan,
         node_id: PlanNodeId,
-        _policy: filemover_types::ConflictPolicy,
+        policy: filemover_types::ConflictPolicy,
     ) -> Result<ValidationDelta, FileMoverError> {
-        // 衝突ポリシー変更の処理
-        // 実際の実装では、ポリシーに基づいて衝突解決を再実行
-        let affected_nodes = vec![node_id];
-        
-        Ok(ValidationDelta {
-            affected_nodes,
-            new_conflicts: Vec::new(),
-            resolved_conflicts: Vec::new(),
-            summary_diff: PlanSummaryDiff {
-                count_dirs_delta: 0,
-                count_files_delta: 0,
-                total_bytes_delta: None,
-                cross_volume_delta: 0,
-                conflicts_delta: 0,
-                warnings_delta: 0,
-            },
-        })
+        use filemover_types::ConflictPolicy;
+
+        let node = plan.nodes.get_mut(&node_id)
+            .ok_or_else(|| FileMoverError::InvalidNodeId(node_id.to_string()))?;
+
+        match policy {
+            ConflictPolicy::Skip => self.handle_skip_change(plan, node_id, true),
+            ConflictPolicy::AutoRename => {
+                if !node.conflicts.iter().any(is_name_conflict) {
+                    return Ok(ValidationDelta {
+                        affected_nodes: vec![node_id],
+                        new_conflicts: Vec::new(),
+                        resolved_conflicts: Vec::new(),
+                        summary_diff: PlanSummaryDiff::default(),
+                    });
+                }
+                let renamed = self.conflict_resolver.generate_auto_renamed_path(&node.path_after)?;
+                let new_name = renamed.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .ok_or_else(|| FileMoverError::PlanValidation {
+                        message: format!("Cannot rename {}", node.path_after.display()),
+                    })?;
+                self.handle_rename_change(plan, node_id, new_name)
+            }
+            ConflictPolicy::Overwrite | ConflictPolicy::Merge => {
+                // 中身をまとめられるのは、既にあるフォルダにフォルダを移す場合だけ
+                if policy == ConflictPolicy::Merge && !(node.is_dir && node.path_after.is_dir()) {
+                    return Err(FileMoverError::PlanValidation {
+                        message: format!("{} is not a folder that can be merged", node.path_after.display()),
+                    });
+                }
+
+                // 移動先にあるものを受け入れるので、名前の衝突は解決済みとする
+                let resolved_conflicts = take_name_conflicts(node);
+                Ok(ValidationDelta {
+                    affected_nodes: vec![node_id],
+                    new_conflicts: Vec::new(),
+                    summary_diff: PlanSummaryDiff {
+                        conflicts_delta: -(resolved_conflicts.len() as i64),
+                        ..PlanSummaryDiff::default()
+                    },
+                    resolved_conflicts,
+                })
+            }
+        }
     }
 
     fn handle_rename_change(
@@ -223,25 +360,35 @@ impl PlanValidator {
         let affected_nodes = vec![node_id];
         let mut new_conflicts = Vec::new();
 
-        let (old_path, new_path) = if let Some(node) = plan.nodes.get_mut(&node_id) {
-            // 新しいパスを構築
-            let new_path = node.path_after.with_file_name(&new_name);
-            let old_path = node.path_after.clone();
-            
-            node.name_after = new_name;
-            node.path_after = new_path.clone();
+        let node = plan.nodes.get_mut(&node_id)
+            .ok_or_else(|| FileMoverError::InvalidNodeId(node_id.to_string()))?;
+        let old_path = node.path_after.clone();
+        // 名前の衝突は元の移動先についてのものなので、新しい移動先で調べ直す
+        let resolved_conflicts = take_name_conflicts(node);
+        let new_path = old_path.with_file_name(&new_name);
+
+        // 名前変更による新しい衝突をチェック（大文字小文字の違いだけなら同じフォルダを指す場合がある）
+        if new_path.exists() && !self.conflict_resolver.paths_collide(&old_path, &new_path) {
+            new_conflicts.push(Conflict::NameExists {
+                existing_path: new_path.clone(),
+            });
+        }
 
-            // 名前変更による新しい衝突をチェック
-            if new_path.exists() {
-                new_conflicts.push(Conflict::NameExists {
-                    existing_path: new_path.clone(),
-                });
-            }
+        // 計画内の他のノードと同じ移動先にならないか
+        let planned = plan.nodes.values()
+            .filter(|other| other.id != node_id && !matches!(other.kind, OpKind::Skip | OpKind::None))
+            .map(|other| other.path_after.clone())
+            .collect::<Vec<_>>();
+        if let Some(existing_path) = planned.into_iter()
+            .find(|path| self.conflict_resolver.paths_collide(path, &new_path))
+        {
+            new_conflicts.push(Conflict::NameExists { existing_path });
+        }
 
-            (old_path, new_path)
-        } else {
-            return Err(FileMoverError::InvalidNodeId(node_id.to_string()));
-        };
+        if let Some(node) = plan.nodes.get_mut(&node_id) {
+            node.name_after = new_name;
+            node.path_after = new_path.clone();
+        }
 
         // 子ノードのパスも更新が必要（別のスコープで実行）
         self.update_child_paths(plan, node_id, &old_path, &new_path)?;
@@ -251,14 +398,14 @@ impl PlanValidator {
             count_files_delta: 0,
             total_bytes_delta: None,
             cross_volume_delta: 0,
-            conflicts_delta: new_conflicts.len() as i64,
+            conflicts_delta: new_conflicts.len() as i64 - resolved_conflicts.len() as i64,
             warnings_delta: 0,
         };
 
         Ok(ValidationDelta {
             affected_nodes,
             new_conflicts,
-            resolved_conflicts: Vec::new(),
+            resolved_conflicts,
             summary_diff,
         })
     }
@@ -441,9 +588,34 @@ impl Default for PlanValidator {
     }
 }
 
+/// revalidate で調べ直す種類の衝突
+fn is_live_conflict(conflict: &Conflict) -> bool {
+    matches!(conflict, Conflict::SourceMissing | Conflict::NameExists { .. } | Conflict::NoSpace { .. })
+}
+
+fn is_name_conflict(conflict: &Conflict) -> bool {
+    matches!(conflict, Conflict::NameExists { .. })
+}
+
+/// ノードから名前の衝突を取り除いて返す
+fn take_name_conflicts(node: &mut PlanNode) -> Vec<Conflict> {
+    let (names, other): (Vec<_>, Vec<_>) = node.conflicts.drain(..).partition(is_name_conflict);
+    node.conflicts = other;
+    names
+}
+
+/// 空き容量は少し変わっても同じ衝突とみなす
+fn same_conflict(a: &Conflict, b: &Conflict) -> bool {
+    match (a, b) {
+        (Conflict::NoSpace { .. }, Conflict::NoSpace { .. }) => true,
+        _ => a == b,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use filemover_types::ConflictPolicy;
     use std::path::PathBuf;
 
     #[test]
@@ -464,6 +636,7 @@ mod tests {
             roots: Vec::new(),
             nodes: HashMap::new(),
             summary: PlanSummary::default(),
+            execution_order: Vec::new(),
         };
 
         // 循環参照を作成
@@ -483,6 +656,7 @@ mod tests {
             conflicts: Vec::new(),
             children: vec![node2_id],
             rule_id: None,
+            tags: Vec::new(),
         };
 
         let node2 = PlanNode {
@@ -498,6 +672,7 @@ mod tests {
             conflicts: Vec::new(),
             children: vec![node1_id], // 循環参照
             rule_id: None,
+            tags: Vec::new(),
         };
 
         plan.nodes.insert(node1_id, node1);
@@ -507,4 +682,153 @@ mod tests {
         let has_cycle = validator.detect_cycle(plan.nodes.get(&node1_id).unwrap(), &plan).unwrap();
         assert!(has_cycle);
     }
-}
\ No newline at end of file
+
+    fn move_node(name: &str) -> PlanNode {
+        PlanNode {
+            id: PlanNodeId::new(),
+            is_dir: true,
+            name_before: name.to_string(),
+            path_before: PathBuf::from("/source").join(name),
+            name_after: name.to_string(),
+            path_after: PathBuf::from("/archive").join(name),
+            kind: OpKind::Move,
+            size_bytes: None,
+            warnings: Vec::new(),
+            conflicts: Vec::new(),
+            children: Vec::new(),
+            rule_id: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_rename_collision_follows_case_sensitivity() {
+        for (case_sensitive, expected_conflicts) in [(false, 1), (true, 0)] {
+            let alpha = move_node("Alpha");
+            let beta = move_node("beta");
+            let beta_id = beta.id;
+            let mut plan = MovePlan {
+                roots: Vec::new(),
+                nodes: HashMap::from([(alpha.id, alpha), (beta_id, beta)]),
+                summary: PlanSummary::default(),
+                execution_order: Vec::new(),
+            };
+
+            let mut validator = PlanValidator::new().with_case_sensitivity(case_sensitive);
+            let delta = validator
+                .validate_incremental_change(&mut plan, NodeChange::RenameNode(beta_id, "alpha".to_string()))
+                .unwrap();
+            assert_eq!(delta.new_conflicts.len(), expected_conflicts);
+            assert_eq!(plan.nodes[&beta_id].path_after, PathBuf::from("/archive/alpha"));
+        }
+    }
+
+    #[test]
+    fn test_policy_change_resolves_name_conflict() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("taken")).unwrap();
+        let conflicted = || {
+            let mut node = move_node("taken");
+            node.path_after = temp_dir.path().join("taken");
+            node.conflicts = vec![Conflict::NameExists { existing_path: node.path_after.clone() }];
+            node
+        };
+
+        // 上書き: 移動先はそのままで衝突だけ解決
+        let node = conflicted();
+        let node_id = node.id;
+        let mut plan = MovePlan {
+            roots: vec![node_id],
+            nodes: HashMap::from([(node_id, node)]),
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+        };
+        let mut validator = PlanValidator::new();
+        let delta = validator
+            .validate_incremental_change(&mut plan, NodeChange::SetConflictPolicy(node_id, ConflictPolicy::Overwrite))
+            .unwrap();
+        assert_eq!(delta.resolved_conflicts.len(), 1);
+        assert_eq!(delta.summary_diff.conflicts_delta, -1);
+        assert!(plan.nodes[&node_id].conflicts.is_empty());
+
+        // 自動リネーム: 空いている名前に変え、元の移動先の衝突は解決
+        let node = conflicted();
+        let node_id = node.id;
+        plan.nodes = HashMap::from([(node_id, node)]);
+        let delta = validator
+            .validate_incremental_change(&mut plan, NodeChange::SetConflictPolicy(node_id, ConflictPolicy::AutoRename))
+            .unwrap();
+        assert_eq!(delta.resolved_conflicts.len(), 1);
+        assert!(delta.new_conflicts.is_empty());
+        assert_eq!(plan.nodes[&node_id].path_after, temp_dir.path().join("taken_1"));
+    }
+
+    #[test]
+    fn test_disk_space_is_summed_per_volume() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let node_for = |name: &str, size: u64, kind: OpKind| PlanNode {
+            // 移動元のボリュームが分からないので、移動先の容量を使うものとして数える
+            path_before: PathBuf::from("unmounted").join(name),
+            path_after: temp_dir.path().join(name),
+            size_bytes: Some(size),
+            kind,
+            ..move_node(name)
+        };
+        let nodes = [
+            node_for("a", 600, OpKind::Move),
+            node_for("b", 300, OpKind::Skip),
+            node_for("c", 600, OpKind::Move),
+            node_for("d", 100, OpKind::Move),
+        ];
+        let id_of = |name: &str| nodes.iter().find(|node| node.name_before == name).unwrap().id;
+        let (c_id, d_id) = (id_of("c"), id_of("d"));
+        let plan = MovePlan {
+            roots: Vec::new(),
+            nodes: nodes.iter().map(|node| (node.id, node.clone())).collect(),
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+        };
+
+        let conflicts = PlanValidator::new().with_free_space(1000).check_disk_space(&plan).unwrap();
+        assert_eq!(conflicts, vec![
+            (c_id, Conflict::NoSpace { required: 1200, available: 1000 }),
+            (d_id, Conflict::NoSpace { required: 1300, available: 1000 }),
+        ]);
+    }
+
+    #[test]
+    fn test_revalidate_reports_changes_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["src/a", "src/c", "dst/c"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let node_for = |name: &str| PlanNode {
+            path_before: root.join("src").join(name),
+            path_after: root.join("dst").join(name),
+            ..move_node(name)
+        };
+        let nodes = [node_for("a"), node_for("b"), node_for("c")];
+        let mut plan = MovePlan {
+            roots: nodes.iter().map(|node| node.id).collect(),
+            nodes: nodes.iter().map(|node| (node.id, node.clone())).collect(),
+            summary: PlanSummary::default(),
+            execution_order: Vec::new(),
+        };
+
+        let mut validator = PlanValidator::new().with_free_space(u64::MAX);
+        let delta = validator.revalidate(&mut plan).unwrap();
+        assert_eq!(delta.affected_nodes.len(), 2);
+        assert!(delta.new_conflicts.contains(&Conflict::SourceMissing));
+        assert!(delta.new_conflicts.contains(&Conflict::NameExists { existing_path: root.join("dst").join("c") }));
+        assert_eq!(plan.summary.conflicts, 2);
+
+        // 反映した後はもう変化はない
+        let delta = validator.revalidate(&mut plan).unwrap();
+        assert!(delta.affected_nodes.is_empty());
+
+        std::fs::remove_dir(root.join("dst").join("c")).unwrap();
+        let delta = validator.revalidate(&mut plan).unwrap();
+        assert_eq!(delta.resolved_conflicts.len(), 1);
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+use filemover_types::{FileMoverError, FolderHit, Warning};
+use crate::network::is_network_path;
+use crate::scanner::{add_root_warnings, sort_hits, FolderScanner, RootWarning, ScanOutcome};
+use crate::walker::DirectoryWalker;
+
+/// 中断したスキャンを続きから再開するための状態
+///
+/// 作業単位は「ルート直下の列挙」と「ルート直下の各フォルダの配下」。
+/// 終わった単位とそのヒットだけを記録し、途中の単位は再開時にやり直す。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCheckpoint {
+    pub roots: Vec<PathBuf>,
+    /// 直下の列挙が終わったルート
+    pub listed_roots: BTreeSet<PathBuf>,
+    /// 配下の走査が終わったフォルダ
+    pub completed_dirs: BTreeSet<PathBuf>,
+    pub hits: Vec<FolderHit>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ScanCheckpoint {
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        Self {
+            roots,
+            listed_roots: BTreeSet::new(),
+            completed_dirs: BTreeSet::new(),
+            hits: Vec::new(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, FileMoverError> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| FileMoverError::Scan {
+            path: path.to_path_buf(),
+            message: format!("Invalid scan checkpoint: {}", e),
+        })
+    }
+
+    /// 書き込み途中で落ちても前回のチェックポイントが壊れないよう、一時ファイルから置き換える
+    pub fn save(&mut self, path: &Path) -> Result<(), FileMoverError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        self.updated_at = Utc::now();
+        let content = serde_json::to_string(self).map_err(|e| FileMoverError::Scan {
+            path: path.to_path_buf(),
+            message: format!("Failed to serialize scan checkpoint: {}", e),
+        })?;
+
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, content)?;
+        std::fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+}
+
+/// 走査の途中経過を定期的にチェックポイントへ保存するスキャナー
+///
+/// ルートは順番に走査する。結果は FolderScanner::scan と同じ ScanOutcome になる。
+pub struct CheckpointedScanner<'a> {
+    scanner: &'a FolderScanner,
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl<'a> CheckpointedScanner<'a> {
+    pub fn new(scanner: &'a FolderScanner, path: impl Into<PathBuf>) -> Self {
+        Self {
+            scanner,
+            path: path.into(),
+            interval: Duration::from_secs(30),
+        }
+    }
+
+    /// チェックポイントを保存する間隔
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// checkpoint.roots を走査する。終わっている単位は飛ばす
+    ///
+    /// キャンセルされた場合もチェックポイントを保存してからそれまでのヒットを返す。
+    pub fn scan(&self, checkpoint: &mut ScanCheckpoint) -> Result<ScanOutcome, FileMoverError> {
+        self.scanner.install(|| self.scan_in_pool(checkpoint))
+    }
+
+    fn scan_in_pool(&self, checkpoint: &mut ScanCheckpoint) -> Result<ScanOutcome, FileMoverError> {
+        if !checkpoint.hits.is_empty() {
+            info!("Resuming scan with {} hits from {}", checkpoint.hits.len(), self.path.display());
+        }
+        self.scanner.begin_scan();
+        self.scanner.record_previous_hits(&checkpoint.hits);
+
+        let mut last_saved = Instant::now();
+        let mut partial_hits = Vec::new();
+        let mut root_warnings = Vec::new();
+        let roots = checkpoint.roots.clone();
+
+        for root in &roots {
+            if self.scanner.cancel_token().is_cancelled() {
+                break;
+            }
+
+            let result = self.scanner.scan_root_with(root, |walker, warnings| {
+                self.scan_root_units(root, walker, warnings, checkpoint, &mut last_saved)
+            });
+            match result {
+                Ok(hits) => partial_hits.extend(hits),
+                Err(e) if is_network_path(root) => root_warnings.push(RootWarning::unreachable(root, &e)),
+                Err(e) => {
+                    checkpoint.save(&self.path)?;
+                    return Err(e);
+                }
+            }
+        }
+
+        checkpoint.save(&self.path)?;
+        debug!("Scan checkpoint saved to: {}", self.path.display());
+
+        let mut hits = checkpoint.hits.clone();
+        hits.extend(partial_hits);
+        sort_hits(&mut hits, self.scanner.options().result_order);
+
+        self.scanner.finish_scan();
+
+        Ok(ScanOutcome {
+            hits,
+            cancelled: self.scanner.cancel_token().is_cancelled(),
+            root_warnings,
+            rule_stats: self.scanner.take_rule_stats(),
+        })
+    }
+
+    // 終わった単位のヒットはチェックポイントへ、キャンセルで途中になった単位のヒットは戻り値で返す
+    fn scan_root_units(
+        &self,
+        root: &Path,
+        walker: &DirectoryWalker,
+        root_warnings: &[Warning],
+        checkpoint: &mut ScanCheckpoint,
+        last_saved: &mut Instant,
+    ) -> Result<Vec<FolderHit>, FileMoverError> {
+        let cancel_token = self.scanner.cancel_token();
+
+        if !checkpoint.listed_roots.contains(root) {
+            let entries = self.scanner.walk_root_listing(root)?;
+            let mut hits = self.scanner.scan_entries(root, entries)?;
+            add_root_warnings(&mut hits, root_warnings);
+            if cancel_token.is_cancelled() {
+                return Ok(hits);
+            }
+            checkpoint.hits.extend(hits);
+            checkpoint.listed_roots.insert(root.to_path_buf());
+            self.save_if_due(checkpoint, last_saved)?;
+        }
+
+        for dir in walker.child_dirs(root)? {
+            if checkpoint.completed_dirs.contains(&dir) {
+                continue;
+            }
+            if cancel_token.is_cancelled() {
+                break;
+            }
+
+            let entries = self.scanner.walk_subtree(&dir)?;
+            let mut hits = self.scanner.scan_entries(root, entries)?;
+            add_root_warnings(&mut hits, root_warnings);
+            if cancel_token.is_cancelled() {
+                return Ok(hits);
+            }
+            checkpoint.hits.extend(hits);
+            checkpoint.completed_dirs.insert(dir);
+            self.save_if_due(checkpoint, last_saved)?;
+        }
+
+        Ok(vec![])
+    }
+
+    fn save_if_due(&self, checkpoint: &mut ScanCheckpoint, last_saved: &mut Instant) -> Result<(), FileMoverError> {
+        if last_saved.elapsed() >= self.interval {
+            checkpoint.save(&self.path)?;
+            *last_saved = Instant::now();
+            debug!("Scan checkpoint saved ({} units done)", checkpoint.completed_dirs.len() + checkpoint.listed_roots.len());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use filemover_types::{PatternSpec, Rule, ScanOptions};
+
+    fn create_scanner() -> FolderScanner {
+        let rule = Rule::new(
+            PatternSpec::new_glob("*"),
+            PathBuf::from("/dest"),
+            "{name}".to_string(),
+        );
+        FolderScanner::new(vec![rule], ScanOptions::default()).unwrap()
+    }
+
+    fn create_tree(root: &Path) {
+        for dir in ["a/a1", "a/a2/deep", "b/b1", "c"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+    }
+
+    fn names(hits: &[FolderHit]) -> Vec<String> {
+        let mut names: Vec<String> = hits.iter().map(|hit| hit.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_checkpointed_scan_matches_full_scan() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("root");
+        create_tree(&root);
+        let scanner = create_scanner();
+        let checkpoint_path = temp_dir.path().join("checkpoint.json");
+
+        let mut checkpoint = ScanCheckpoint::new(vec![root.clone()]);
+        let outcome = CheckpointedScanner::new(&scanner, &checkpoint_path)
+            .scan(&mut checkpoint)
+            .unwrap();
+
+        let full = scanner.scan_roots(std::slice::from_ref(&root)).unwrap();
+        assert_eq!(names(&outcome.hits), names(&full));
+        assert!(!outcome.cancelled);
+
+        let saved = ScanCheckpoint::load(&checkpoint_path).unwrap();
+        assert_eq!(saved.hits.len(), full.len());
+        assert!(saved.listed_roots.contains(&root));
+        assert_eq!(saved.completed_dirs.len(), 3);
+    }
+
+    #[test]
+    fn test_resume_skips_completed_units() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("root");
+        create_tree(&root);
+        let scanner = create_scanner();
+        let checkpoint_path = temp_dir.path().join("checkpoint.json");
+
+        // "a" の配下は前回終わっている（実際には存在しないヒットで区別する）
+        let mut checkpoint = ScanCheckpoint::new(vec![root.clone()]);
+        checkpoint.completed_dirs.insert(root.join("a"));
+        checkpoint.hits.push(FolderHit {
+            path: root.join("a").join("from_checkpoint"),
+            name: "from_checkpoint".to_string(),
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: Vec::new(),
+            size_bytes: None,
+            item_count: None,
+            is_dir: true,
+        });
+        checkpoint.save(&checkpoint_path).unwrap();
+
+        let mut checkpoint = ScanCheckpoint::load(&checkpoint_path).unwrap();
+        let outcome = CheckpointedScanner::new(&scanner, &checkpoint_path)
+            .scan(&mut checkpoint)
+            .unwrap();
+
+        let names = names(&outcome.hits);
+        for name in ["a", "b", "b1", "c", "from_checkpoint"] {
+            assert!(names.contains(&name.to_string()), "missing {}", name);
+        }
+        for name in ["a1", "a2", "deep"] {
+            assert!(!names.contains(&name.to_string()), "{} was scanned again", name);
+        }
+    }
+
+    #[test]
+    fn test_cancelled_scan_keeps_completed_units() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("root");
+        create_tree(&root);
+        let token = filemover_types::CancellationToken::new();
+        token.cancel();
+        let scanner = create_scanner().with_cancellation(token);
+        let checkpoint_path = temp_dir.path().join("checkpoint.json");
+
+        let mut checkpoint = ScanCheckpoint::new(vec![root.clone()]);
+        let outcome = CheckpointedScanner::new(&scanner, &checkpoint_path)
+            .scan(&mut checkpoint)
+            .unwrap();
+
+        assert!(outcome.cancelled);
+        let saved = ScanCheckpoint::load(&checkpoint_path).unwrap();
+        assert_eq!(saved.roots, vec![root]);
+        assert!(saved.completed_dirs.is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs::DirEntry;
+
+// winapi 0.3 に定義がないため値を直接持つ
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x0000_1000;
+const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+
+// IO_REPARSE_TAG_CLOUD ～ IO_REPARSE_TAG_CLOUD_F は 12-15bit だけが異なる
+const IO_REPARSE_TAG_CLOUD: u32 = 0x9000_001A;
+const IO_REPARSE_TAG_CLOUD_MASK: u32 = 0x0000_F000;
+
+/// クラウドのプレースホルダ（OneDrive のオンライン専用ファイルなど）を示す属性か
+///
+/// データがローカルになく、読むとダウンロード（ハイドレート）が発生する。
+pub fn is_placeholder_attributes(attributes: u32) -> bool {
+    attributes
+        & (FILE_ATTRIBUTE_OFFLINE | FILE_ATTRIBUTE_RECALL_ON_OPEN | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+        != 0
+}
+
+/// クラウド同期プロバイダの再解析タグか（ジャンクションとは区別する）
+pub fn is_cloud_reparse_tag(tag: u32) -> bool {
+    tag & !IO_REPARSE_TAG_CLOUD_MASK == IO_REPARSE_TAG_CLOUD
+}
+
+/// read_dir のエントリがプレースホルダか（Windows 以外では常に false）
+pub(crate) fn is_placeholder_entry(entry: &DirEntry) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        entry.metadata().is_ok_and(|m| is_placeholder_attributes(m.file_attributes()))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = entry;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholder_attributes() {
+        assert!(is_placeholder_attributes(0x0040_0020));
+        assert!(is_placeholder_attributes(FILE_ATTRIBUTE_RECALL_ON_OPEN | 0x10));
+        assert!(!is_placeholder_attributes(0x10 | 0x400));
+    }
+
+    #[test]
+    fn test_cloud_reparse_tag() {
+        assert!(is_cloud_reparse_tag(0x9000_001A));
+        assert!(is_cloud_reparse_tag(0x9000_701A));
+        // IO_REPARSE_TAG_MOUNT_POINT / IO_REPARSE_TAG_SYMLINK
+        assert!(!is_cloud_reparse_tag(0xA000_0003));
+        assert!(!is_cloud_reparse_tag(0xA000_000C));
+    }
+}
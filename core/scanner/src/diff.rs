@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use filemover_types::FolderHit;
+
+/// 2 回のスキャン結果の差分
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanDiff {
+    pub added: Vec<FolderHit>,
+    pub removed: Vec<FolderHit>,
+    pub renamed: Vec<RenamedHit>,
+    pub size_changed: Vec<SizeChange>,
+}
+
+impl ScanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.size_changed.is_empty()
+    }
+}
+
+/// 名前の変更・別フォルダへの移動とみなしたヒット
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenamedHit {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizeChange {
+    pub path: PathBuf,
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+}
+
+/// 2 つのスキャン結果を比較する
+///
+/// スキャン結果にはファイル ID がないため、消えたヒットと増えたヒットのうち
+/// 種類とサイズが同じで、親フォルダか名前のどちらかが同じものを名前の変更とみなす。
+pub fn diff_scans(old: &[FolderHit], new: &[FolderHit]) -> ScanDiff {
+    let old_by_path: HashMap<&PathBuf, &FolderHit> = old.iter().map(|hit| (&hit.path, hit)).collect();
+    let new_by_path: HashMap<&PathBuf, &FolderHit> = new.iter().map(|hit| (&hit.path, hit)).collect();
+
+    let mut diff = ScanDiff::default();
+    let mut removed: Vec<&FolderHit> = Vec::new();
+
+    for hit in old {
+        match new_by_path.get(&hit.path) {
+            Some(current) if current.size_bytes != hit.size_bytes => {
+                diff.size_changed.push(SizeChange {
+                    path: hit.path.clone(),
+                    old_size: hit.size_bytes,
+                    new_size: current.size_bytes,
+                });
+            }
+            Some(_) => {}
+            None => removed.push(hit),
+        }
+    }
+
+    let added: Vec<&FolderHit> = new.iter().filter(|hit| !old_by_path.contains_key(&hit.path)).collect();
+    let mut paired: HashSet<&PathBuf> = HashSet::new();
+
+    for before in removed {
+        let after = added.iter().find(|after| {
+            !paired.contains(&after.path) && looks_like_rename(before, after)
+        });
+
+        match after {
+            Some(after) => {
+                paired.insert(&after.path);
+                diff.renamed.push(RenamedHit {
+                    from: before.path.clone(),
+                    to: after.path.clone(),
+                    size_bytes: after.size_bytes,
+                });
+            }
+            None => diff.removed.push(before.clone()),
+        }
+    }
+
+    diff.added = added.into_iter()
+        .filter(|hit| !paired.contains(&hit.path))
+        .cloned()
+        .collect();
+
+    diff
+}
+
+// サイズが分からないヒットは取り違えやすいので対象にしない
+fn looks_like_rename(before: &FolderHit, after: &FolderHit) -> bool {
+    before.is_dir == after.is_dir
+        && before.size_bytes.is_some()
+        && before.size_bytes == after.size_bytes
+        && (before.path.parent() == after.path.parent() || before.path.file_name() == after.path.file_name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(path: &str, size: Option<u64>) -> FolderHit {
+        let path = PathBuf::from(path);
+        FolderHit {
+            name: path.file_name().unwrap().to_string_lossy().to_string(),
+            path,
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: Vec::new(),
+            size_bytes: size,
+            item_count: None,
+            is_dir: true,
+        }
+    }
+
+    #[test]
+    fn test_diff_scans() {
+        let old = vec![
+            hit("/data/keep", Some(10)),
+            hit("/data/grow", Some(10)),
+            hit("/data/gone", Some(5)),
+            hit("/data/old_name", Some(42)),
+        ];
+        let new = vec![
+            hit("/data/keep", Some(10)),
+            hit("/data/grow", Some(99)),
+            hit("/data/new_name", Some(42)),
+            hit("/other/fresh", Some(5)),
+        ];
+
+        let diff = diff_scans(&old, &new);
+
+        assert_eq!(diff.renamed, vec![RenamedHit {
+            from: PathBuf::from("/data/old_name"),
+            to: PathBuf::from("/data/new_name"),
+            size_bytes: Some(42),
+        }]);
+        assert_eq!(diff.size_changed.len(), 1);
+        assert_eq!(diff.size_changed[0].new_size, Some(99));
+        // サイズは同じでも親も名前も違うので削除 + 追加として扱う
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].path, PathBuf::from("/data/gone"));
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].path, PathBuf::from("/other/fresh"));
+    }
+
+    #[test]
+    fn test_identical_scans_have_no_diff() {
+        let scan = vec![hit("/data/a", None), hit("/data/b", Some(1))];
+        assert!(diff_scans(&scan, &scan).is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::Regex;
+use tracing::warn;
+use filemover_types::{FileMoverError, ScanOptions};
+
+const REGEX_PREFIX: &str = "regex:";
+
+/// 除外パス（前方一致）と除外パターン（glob / 正規表現）をまとめて評価する
+///
+/// 除外されたフォルダの下はたどらない前提なので、`**/node_modules` のように
+/// フォルダ自体に一致すればその中身も除外される。
+pub struct ExclusionSet {
+    paths: Vec<PathBuf>,
+    path_globs: GlobSet,
+    name_globs: GlobSet,
+    regexes: Vec<Regex>,
+}
+
+impl ExclusionSet {
+    pub fn new(options: &ScanOptions) -> Result<Self, FileMoverError> {
+        let mut path_globs = GlobSetBuilder::new();
+        let mut name_globs = GlobSetBuilder::new();
+        let mut regexes = Vec::new();
+
+        for pattern in &options.excluded_patterns {
+            if let Some(regex) = pattern.strip_prefix(REGEX_PREFIX) {
+                let regex = Regex::new(regex).map_err(|e| pattern_error(pattern, e))?;
+                regexes.push(regex);
+                continue;
+            }
+
+            // Windows のパスは大文字小文字を区別しない
+            let glob = GlobBuilder::new(pattern)
+                .case_insensitive(cfg!(windows))
+                .literal_separator(true)
+                .build()
+                .map_err(|e| pattern_error(pattern, e))?;
+
+            if pattern.contains('/') {
+                path_globs.add(glob);
+            } else {
+                name_globs.add(glob);
+            }
+        }
+
+        Ok(Self {
+            paths: options.excluded_paths.clone(),
+            path_globs: path_globs.build().map_err(|e| pattern_error("excluded_patterns", e))?,
+            name_globs: name_globs.build().map_err(|e| pattern_error("excluded_patterns", e))?,
+            regexes,
+        })
+    }
+
+    /// ウォーカー用。パターンが不正な場合は警告して除外パスだけで続ける
+    pub(crate) fn for_walker(options: &ScanOptions) -> Self {
+        Self::new(options).unwrap_or_else(|e| {
+            warn!("Ignoring exclusion patterns: {}", e);
+            Self::paths_only(options)
+        })
+    }
+
+    /// 除外パスだけを評価する（パターンが不正な場合の代替）
+    pub fn paths_only(options: &ScanOptions) -> Self {
+        Self {
+            paths: options.excluded_paths.clone(),
+            path_globs: GlobSet::empty(),
+            name_globs: GlobSet::empty(),
+            regexes: Vec::new(),
+        }
+    }
+
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        if self.paths.iter().any(|excluded| path.starts_with(excluded)) {
+            return true;
+        }
+
+        if self.path_globs.is_match(path) {
+            return true;
+        }
+
+        if let Some(name) = path.file_name() {
+            if self.name_globs.is_match(name) {
+                return true;
+            }
+        }
+
+        if self.regexes.is_empty() {
+            return false;
+        }
+        // 区切り文字は / にそろえてから評価する
+        let normalized = path.to_string_lossy().replace('\\', "/");
+        self.regexes.iter().any(|regex| regex.is_match(&normalized))
+    }
+}
+
+fn pattern_error(pattern: &str, error: impl std::fmt::Display) -> FileMoverError {
+    FileMoverError::Pattern {
+        message: format!("Invalid exclusion pattern '{}': {}", pattern, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exclusions(patterns: &[&str]) -> ExclusionSet {
+        let options = ScanOptions {
+            excluded_paths: vec![PathBuf::from("/data/tmp")],
+            excluded_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        };
+        ExclusionSet::new(&options).unwrap()
+    }
+
+    #[test]
+    fn test_glob_exclusions() {
+        let set = exclusions(&["**/node_modules", ".git", "*.tmp"]);
+
+        assert!(set.is_excluded(Path::new("/work/app/node_modules")));
+        assert!(set.is_excluded(Path::new("/work/app/.git")));
+        assert!(set.is_excluded(Path::new("/work/app/build.tmp")));
+        assert!(set.is_excluded(Path::new("/data/tmp/cache")));
+        assert!(!set.is_excluded(Path::new("/work/app/src")));
+        assert!(!set.is_excluded(Path::new("/work/app/.github")));
+    }
+
+    #[test]
+    fn test_regex_exclusions() {
+        let set = exclusions(&[r"regex:/backup_\d{4}$"]);
+
+        assert!(set.is_excluded(Path::new("/work/backup_2024")));
+        assert!(!set.is_excluded(Path::new("/work/backup_2024/old")));
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_rejected() {
+        let options = ScanOptions {
+            excluded_patterns: vec!["regex:(".to_string()],
+            ..Default::default()
+        };
+
+        assert!(matches!(ExclusionSet::new(&options), Err(FileMoverError::Pattern { .. })));
+        assert!(!ExclusionSet::paths_only(&options).is_excluded(Path::new("/work")));
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::warn;
+use filemover_types::FileMoverError;
+
+/// ボリュームとファイル番号の組（ジャンクションやリンクをたどっても同じ実体なら同じ値）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId {
+    pub volume: u64,
+    pub index: u64,
+}
+
+impl FileId {
+    /// リンク先の実体の ID
+    #[cfg(windows)]
+    pub fn of(path: &Path) -> Result<Self, FileMoverError> {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::fileapi::{CreateFileW, GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION, OPEN_EXISTING};
+        use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+        use winapi::um::winbase::FILE_FLAG_BACKUP_SEMANTICS;
+        use winapi::um::winnt::{FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE};
+
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let handle = unsafe {
+            CreateFileW(
+                wide.as_ptr(),
+                0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+                std::ptr::null_mut(),
+                OPEN_EXISTING,
+                FILE_FLAG_BACKUP_SEMANTICS,
+                std::ptr::null_mut(),
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(FileMoverError::Scan {
+                path: path.to_path_buf(),
+                message: format!("Failed to open directory (error {})", unsafe { GetLastError() }),
+            });
+        }
+
+        let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+        let ok = unsafe { GetFileInformationByHandle(handle, &mut info) };
+        unsafe { CloseHandle(handle) };
+
+        if ok == 0 {
+            return Err(FileMoverError::Scan {
+                path: path.to_path_buf(),
+                message: format!("GetFileInformationByHandle failed with error: {}", unsafe { GetLastError() }),
+            });
+        }
+
+        Ok(Self {
+            volume: info.dwVolumeSerialNumber as u64,
+            index: ((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64,
+        })
+    }
+
+    /// リンク先の実体の ID
+    #[cfg(unix)]
+    pub fn of(path: &Path) -> Result<Self, FileMoverError> {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = std::fs::metadata(path)?;
+        Ok(Self {
+            volume: metadata.dev(),
+            index: metadata.ino(),
+        })
+    }
+}
+
+/// 一度たどったフォルダを覚えておき、ジャンクションの循環や重複をたどらないようにする
+#[derive(Debug, Default)]
+pub(crate) struct VisitedDirs {
+    ids: HashSet<FileId>,
+}
+
+impl VisitedDirs {
+    /// 初めてのフォルダなら true（ID が取れない場合もたどる）
+    pub fn first_visit(&mut self, path: &Path) -> bool {
+        match FileId::of(path) {
+            Ok(id) => self.ids.insert(id),
+            Err(e) => {
+                warn!("Cannot identify {}, cycle detection skipped: {}", path.display(), e);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_visited_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("folder");
+        std::fs::create_dir(&folder).unwrap();
+
+        let mut visited = VisitedDirs::default();
+        assert!(visited.first_visit(&folder));
+        assert!(!visited.first_visit(&folder));
+        assert!(visited.first_visit(temp_dir.path()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_has_same_id_as_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("target");
+        let link = temp_dir.path().join("link");
+        std::fs::create_dir(&target).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert_eq!(FileId::of(&target).unwrap(), FileId::of(&link).unwrap());
+    }
+}
@@ -0,0 +1,195 @@
+use std::fs::Metadata;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use filemover_types::ScanFilters;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// ScanOptions のサイズ・更新日時・属性フィルタを評価する
+pub struct EntryFilter {
+    filters: ScanFilters,
+    now: SystemTime,
+}
+
+impl EntryFilter {
+    pub fn new(filters: ScanFilters) -> Self {
+        Self {
+            filters,
+            now: SystemTime::now(),
+        }
+    }
+
+    /// 更新日時・属性のフィルタを満たすか（メタデータが読めない場合は除外）
+    pub fn matches_path(&self, path: &Path) -> bool {
+        if !self.filters.has_metadata_filter() {
+            return true;
+        }
+
+        match std::fs::symlink_metadata(path) {
+            Ok(metadata) => self.matches_metadata(path, &metadata),
+            Err(_) => false,
+        }
+    }
+
+    pub fn matches_metadata(&self, path: &Path, metadata: &Metadata) -> bool {
+        self.matches_age(metadata) && self.matches_attributes(path, metadata)
+    }
+
+    /// サイズのフィルタを満たすか（サイズが不明な場合は除外）
+    pub fn matches_size(&self, size_bytes: Option<u64>) -> bool {
+        if !self.filters.has_size_filter() {
+            return true;
+        }
+
+        let Some(size) = size_bytes else {
+            return false;
+        };
+        self.filters.min_size.is_none_or(|min| size >= min)
+            && self.filters.max_size.is_none_or(|max| size <= max)
+    }
+
+    fn matches_age(&self, metadata: &Metadata) -> bool {
+        if self.filters.older_than_days.is_none() && self.filters.newer_than_days.is_none() {
+            return true;
+        }
+
+        let Ok(modified) = metadata.modified() else {
+            return false;
+        };
+        // 未来の日時は経過 0 として扱う
+        let age = self.now.duration_since(modified).unwrap_or_default();
+
+        self.filters.older_than_days.is_none_or(|days| age >= days_to_duration(days))
+            && self.filters.newer_than_days.is_none_or(|days| age < days_to_duration(days))
+    }
+
+    fn matches_attributes(&self, path: &Path, metadata: &Metadata) -> bool {
+        let attributes = EntryAttributes::read(path, metadata);
+        self.filters.hidden.is_none_or(|wanted| attributes.hidden == wanted)
+            && self.filters.system.is_none_or(|wanted| attributes.system == wanted)
+            && self.filters.readonly.is_none_or(|wanted| attributes.readonly == wanted)
+    }
+}
+
+fn days_to_duration(days: u32) -> Duration {
+    Duration::from_secs(days as u64 * SECONDS_PER_DAY)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct EntryAttributes {
+    pub hidden: bool,
+    pub system: bool,
+    pub readonly: bool,
+}
+
+impl EntryAttributes {
+    /// dwFileAttributes の値から（MFT・FindFirstFileW の結果用）
+    #[cfg(windows)]
+    pub fn from_raw(attributes: u32) -> Self {
+        use winapi::um::winnt::{FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_SYSTEM};
+
+        Self {
+            hidden: attributes & FILE_ATTRIBUTE_HIDDEN != 0,
+            system: attributes & FILE_ATTRIBUTE_SYSTEM != 0,
+            readonly: attributes & FILE_ATTRIBUTE_READONLY != 0,
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn read(_path: &Path, metadata: &Metadata) -> Self {
+        use std::os::windows::fs::MetadataExt;
+        Self::from_raw(metadata.file_attributes())
+    }
+
+    // Windows 以外ではドットで始まる名前を隠し属性とみなす
+    #[cfg(not(windows))]
+    pub fn read(path: &Path, metadata: &Metadata) -> Self {
+        Self {
+            hidden: is_dot_name(path),
+            system: false,
+            readonly: metadata.permissions().readonly(),
+        }
+    }
+
+    /// read_dir のエントリの属性（Windows 以外ではメタデータを読まずに名前だけで判定する）
+    pub fn from_dir_entry(entry: &std::fs::DirEntry) -> Self {
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::MetadataExt;
+            entry.metadata().map(|m| Self::from_raw(m.file_attributes())).unwrap_or_default()
+        }
+
+        #[cfg(not(windows))]
+        {
+            Self {
+                hidden: is_dot_name(&entry.path()),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+pub(crate) fn is_dot_name(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_size_filter() {
+        let filter = EntryFilter::new(ScanFilters {
+            min_size: Some(100),
+            max_size: Some(1000),
+            ..Default::default()
+        });
+
+        assert!(filter.matches_size(Some(500)));
+        assert!(!filter.matches_size(Some(50)));
+        assert!(!filter.matches_size(Some(5000)));
+        assert!(!filter.matches_size(None));
+        assert!(EntryFilter::new(ScanFilters::default()).matches_size(None));
+    }
+
+    #[test]
+    fn test_age_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("fresh");
+        std::fs::create_dir(&folder).unwrap();
+
+        let older = EntryFilter::new(ScanFilters {
+            older_than_days: Some(30),
+            ..Default::default()
+        });
+        let newer = EntryFilter::new(ScanFilters {
+            newer_than_days: Some(30),
+            ..Default::default()
+        });
+
+        assert!(!older.matches_path(&folder));
+        assert!(newer.matches_path(&folder));
+    }
+
+    #[test]
+    fn test_hidden_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let visible = temp_dir.path().join("visible");
+        let hidden = temp_dir.path().join(".hidden");
+        std::fs::create_dir(&visible).unwrap();
+        std::fs::create_dir(&hidden).unwrap();
+
+        let filter = EntryFilter::new(ScanFilters {
+            hidden: Some(false),
+            ..Default::default()
+        });
+
+        assert!(filter.matches_path(&visible));
+        #[cfg(not(windows))]
+        assert!(!filter.matches_path(&hidden));
+    }
+}
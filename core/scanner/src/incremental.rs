@@ -0,0 +1,357 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+use filemover_types::{FileMoverError, FolderHit};
+use crate::scanner::FolderScanner;
+use crate::walker::DirectoryWalker;
+
+/// ボリュームの変更ジャーナル上の位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VolumeCursor {
+    pub journal_id: u64,
+    pub next_usn: i64,
+}
+
+/// 前回のスキャン以降に変更のあったパス
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeSet {
+    /// 直下のエントリが追加・削除・変更されたフォルダ
+    pub touched_dirs: BTreeSet<PathBuf>,
+    /// 作成・削除・名前変更されたフォルダ（配下ごと見直す）
+    pub replaced_dirs: BTreeSet<PathBuf>,
+}
+
+impl ChangeSet {
+    pub fn is_empty(&self) -> bool {
+        self.touched_dirs.is_empty() && self.replaced_dirs.is_empty()
+    }
+}
+
+/// ボリューム単位の変更ジャーナル（Windows では NTFS の USN ジャーナル）
+pub trait ChangeJournal {
+    /// 現在のジャーナル位置
+    fn current_cursor(&self, volume: &str) -> Result<VolumeCursor, FileMoverError>;
+
+    /// cursor 以降の変更。ジャーナルが作り直された・古い記録が消えた場合は None
+    fn changes_since(&self, volume: &str, cursor: &VolumeCursor) -> Result<Option<ChangeSet>, FileMoverError>;
+}
+
+/// 差分スキャンのために保存する状態
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IncrementalScanState {
+    pub volumes: HashMap<String, VolumeCursor>,
+    pub roots: HashMap<PathBuf, Vec<FolderHit>>,
+}
+
+impl IncrementalScanState {
+    pub fn load(path: &Path) -> Result<Self, FileMoverError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| FileMoverError::Scan {
+            path: path.to_path_buf(),
+            message: format!("Invalid scan state: {}", e),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), FileMoverError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string(self).map_err(|e| FileMoverError::Scan {
+            path: path.to_path_buf(),
+            message: format!("Failed to serialize scan state: {}", e),
+        })?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// 変更ジャーナルを使い、前回から変更のあったフォルダだけを見直すスキャナー
+///
+/// 結果はフルスキャンと同じ FolderHit の一覧になる。
+pub struct IncrementalScanner<'a, J: ChangeJournal> {
+    scanner: &'a FolderScanner,
+    journal: J,
+}
+
+impl<'a, J: ChangeJournal> IncrementalScanner<'a, J> {
+    pub fn new(scanner: &'a FolderScanner, journal: J) -> Self {
+        Self { scanner, journal }
+    }
+
+    pub fn scan_roots(
+        &self,
+        roots: &[PathBuf],
+        state: &mut IncrementalScanState,
+    ) -> Result<Vec<FolderHit>, FileMoverError> {
+        let mut all_hits = Vec::new();
+        let mut cursors = HashMap::new();
+
+        for root in roots {
+            self.scanner.cancel_token().check()?;
+
+            let volume = volume_key(root).ok_or_else(|| FileMoverError::Scan {
+                path: root.clone(),
+                message: "Cannot determine the volume of this path".to_string(),
+            })?;
+
+            // 走査中の変更を取りこぼさないよう、走査前の位置を記録する
+            if !cursors.contains_key(&volume) {
+                cursors.insert(volume.clone(), self.journal.current_cursor(&volume)?);
+            }
+
+            let changes = match (state.volumes.get(&volume), state.roots.contains_key(root)) {
+                (Some(previous), true) => self.journal.changes_since(&volume, previous)?,
+                _ => None,
+            };
+
+            let hits = match changes {
+                Some(changes) => {
+                    let previous = state.roots.remove(root).unwrap_or_default();
+                    self.apply_changes(root, previous, &changes)?
+                }
+                None => {
+                    info!("Full scan of {} (no usable change journal position)", root.display());
+                    self.scanner.scan_single_root(root)?
+                }
+            };
+
+            state.roots.insert(root.clone(), hits.clone());
+            all_hits.extend(hits);
+        }
+
+        state.volumes.extend(cursors);
+        self.scanner.save_size_cache();
+
+        Ok(all_hits)
+    }
+
+    fn apply_changes(
+        &self,
+        root: &Path,
+        previous: Vec<FolderHit>,
+        changes: &ChangeSet,
+    ) -> Result<Vec<FolderHit>, FileMoverError> {
+        let touched: Vec<&PathBuf> = changes.touched_dirs.iter()
+            .filter(|dir| dir.starts_with(root))
+            .collect();
+        let replaced: Vec<&PathBuf> = changes.replaced_dirs.iter()
+            .filter(|dir| dir.starts_with(root))
+            .collect();
+
+        debug!(
+            "Incremental scan of {}: {} touched, {} replaced folders",
+            root.display(),
+            touched.len(),
+            replaced.len()
+        );
+
+        // 変更の影響を受けないヒットはそのまま引き継ぐ
+        let mut hits: HashMap<PathBuf, FolderHit> = previous.into_iter()
+            .filter(|hit| {
+                let parent_touched = hit.path.parent().is_some_and(|p| touched.iter().any(|t| t.as_path() == p));
+                let replaced = replaced.iter().any(|r| hit.path.starts_with(r));
+                !parent_touched && !replaced
+            })
+            .map(|hit| (hit.path.clone(), hit))
+            .collect();
+
+        let options = self.scanner.options();
+        let walker = DirectoryWalker::new(options.clone());
+
+        for dir in &touched {
+            if !dir.is_dir() || !within_depth(root, dir, 1, options.max_depth) || walker.is_excluded_below(root, dir) {
+                continue;
+            }
+            self.scanner.cancel_token().check()?;
+            let entries = walker.walk_children(dir)?;
+            for hit in self.scanner.scan_entries(root, entries)? {
+                hits.insert(hit.path.clone(), hit);
+            }
+        }
+
+        for dir in &replaced {
+            if !dir.is_dir() || !within_depth(root, dir, 0, options.max_depth) || walker.is_excluded_below(root, dir) {
+                continue;
+            }
+            self.scanner.cancel_token().check()?;
+
+            // ルートからの深さを差し引いた範囲だけ配下をたどる
+            let mut subtree_options = options.clone();
+            subtree_options.max_depth = options.max_depth
+                .map(|max| max.saturating_sub(relative_depth(root, dir)));
+            let entries = DirectoryWalker::new(subtree_options).walk(dir)?;
+            for hit in self.scanner.scan_entries(root, entries)? {
+                hits.insert(hit.path.clone(), hit);
+            }
+        }
+
+        // 直下が変わったフォルダは項目数を数え直す
+        for hit in hits.values_mut() {
+            if hit.is_dir && touched.iter().any(|dir| **dir == hit.path) {
+                hit.item_count = std::fs::read_dir(&hit.path).ok().map(|items| items.count() as u64);
+            }
+        }
+
+        // 配下が変わったフォルダはサイズを計算し直す
+        if options.calculate_sizes {
+            for hit in hits.values_mut() {
+                let changed_below = touched.iter().chain(replaced.iter())
+                    .any(|dir| dir.starts_with(&hit.path));
+                if hit.is_dir && changed_below {
+                    hit.size_bytes = self.scanner.directory_size(&hit.path);
+                }
+            }
+        }
+
+        let mut hits: Vec<FolderHit> = hits.into_values().collect();
+        hits.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(hits)
+    }
+}
+
+/// パスが属するボリュームのキー（例: "C:"、"\\server\share"）
+pub fn volume_key(path: &Path) -> Option<String> {
+    match path.components().next()? {
+        Component::Prefix(prefix) => Some(prefix.as_os_str().to_string_lossy().to_uppercase()),
+        Component::RootDir => Some("/".to_string()),
+        _ => None,
+    }
+}
+
+fn relative_depth(root: &Path, path: &Path) -> u32 {
+    path.strip_prefix(root)
+        .map(|rel| rel.components().count() as u32)
+        .unwrap_or(0)
+}
+
+fn within_depth(root: &Path, dir: &Path, extra: u32, max_depth: Option<u32>) -> bool {
+    max_depth.is_none_or(|max| relative_depth(root, dir) + extra <= max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use filemover_types::{PatternSpec, Rule, ScanOptions};
+    use tempfile::TempDir;
+
+    /// テスト用に変更内容を手で与えるジャーナル
+    struct FakeJournal {
+        next_usn: RefCell<i64>,
+        changes: RefCell<Option<ChangeSet>>,
+    }
+
+    impl FakeJournal {
+        fn new() -> Self {
+            Self {
+                next_usn: RefCell::new(1),
+                changes: RefCell::new(None),
+            }
+        }
+    }
+
+    impl ChangeJournal for FakeJournal {
+        fn current_cursor(&self, _volume: &str) -> Result<VolumeCursor, FileMoverError> {
+            Ok(VolumeCursor { journal_id: 7, next_usn: *self.next_usn.borrow() })
+        }
+
+        fn changes_since(&self, _volume: &str, _cursor: &VolumeCursor) -> Result<Option<ChangeSet>, FileMoverError> {
+            Ok(self.changes.borrow_mut().take())
+        }
+    }
+
+    fn create_scanner() -> FolderScanner {
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("test*"),
+                PathBuf::from("/archive"),
+                "{name}".to_string(),
+            ),
+        ];
+        FolderScanner::new(rules, ScanOptions::default()).unwrap()
+    }
+
+    fn names(hits: &[FolderHit]) -> Vec<String> {
+        let mut names: Vec<String> = hits.iter().map(|h| h.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_first_scan_is_full() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("test_a")).unwrap();
+
+        let scanner = create_scanner();
+        let incremental = IncrementalScanner::new(&scanner, FakeJournal::new());
+        let mut state = IncrementalScanState::default();
+
+        let hits = incremental.scan_roots(&[temp_dir.path().to_path_buf()], &mut state).unwrap();
+        assert_eq!(names(&hits), vec!["test_a"]);
+        assert_eq!(state.volumes.len(), 1);
+    }
+
+    #[test]
+    fn test_incremental_matches_full_scan() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::fs::create_dir_all(root.join("test_a")).unwrap();
+        std::fs::create_dir_all(root.join("keep").join("test_b")).unwrap();
+
+        let scanner = create_scanner();
+        let journal = FakeJournal::new();
+        let incremental = IncrementalScanner::new(&scanner, journal);
+        let mut state = IncrementalScanState::default();
+        incremental.scan_roots(std::slice::from_ref(&root), &mut state).unwrap();
+
+        // test_a を削除し、新しいフォルダとその配下を作成
+        std::fs::remove_dir(root.join("test_a")).unwrap();
+        std::fs::create_dir_all(root.join("new").join("test_c")).unwrap();
+        let mut changes = ChangeSet::default();
+        changes.touched_dirs.insert(root.clone());
+        changes.replaced_dirs.insert(root.join("test_a"));
+        changes.replaced_dirs.insert(root.join("new"));
+        *incremental.journal.changes.borrow_mut() = Some(changes);
+
+        let hits = incremental.scan_roots(std::slice::from_ref(&root), &mut state).unwrap();
+        let full = scanner.scan_roots(std::slice::from_ref(&root)).unwrap();
+        assert_eq!(names(&hits), vec!["test_b", "test_c"]);
+        assert_eq!(names(&hits), names(&full));
+    }
+
+    #[test]
+    fn test_missing_changes_fall_back_to_full_scan() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::fs::create_dir_all(root.join("test_a")).unwrap();
+
+        let scanner = create_scanner();
+        let incremental = IncrementalScanner::new(&scanner, FakeJournal::new());
+        let mut state = IncrementalScanState::default();
+        incremental.scan_roots(std::slice::from_ref(&root), &mut state).unwrap();
+
+        // ジャーナルが追跡できない場合（None）は全体を走査し直す
+        std::fs::create_dir_all(root.join("test_b")).unwrap();
+        let hits = incremental.scan_roots(std::slice::from_ref(&root), &mut state).unwrap();
+        assert_eq!(names(&hits), vec!["test_a", "test_b"]);
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state.json");
+
+        let mut state = IncrementalScanState::default();
+        state.volumes.insert("C:".to_string(), VolumeCursor { journal_id: 1, next_usn: 42 });
+        state.save(&path).unwrap();
+
+        let loaded = IncrementalScanState::load(&path).unwrap();
+        assert_eq!(loaded.volumes["C:"].next_usn, 42);
+    }
+}
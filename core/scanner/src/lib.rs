@@ -1,11 +1,47 @@
 pub mod scanner;
 pub mod walker;
+pub mod size;
+pub mod incremental;
+pub mod progress;
+pub mod filter;
+pub mod exclude;
+pub mod network;
+pub mod cloud;
+pub mod file_id;
+pub mod diff;
+pub mod checkpoint;
+pub mod stats;
+pub mod watcher;
 
 #[cfg(windows)]
 pub mod windows_scanner;
 
+#[cfg(windows)]
+pub mod usn;
+
+#[cfg(windows)]
+pub mod mft;
+
 pub use scanner::*;
 pub use walker::*;
+pub use size::*;
+pub use incremental::*;
+pub use progress::{ScanEvent, ScanEventCallback};
+pub use filter::*;
+pub use exclude::*;
+pub use network::*;
+pub use cloud::{is_cloud_reparse_tag, is_placeholder_attributes};
+pub use file_id::FileId;
+pub use diff::*;
+pub use checkpoint::*;
+pub use stats::*;
+pub use watcher::*;
+
+#[cfg(windows)]
+pub use windows_scanner::*;
+
+#[cfg(windows)]
+pub use usn::*;
 
 #[cfg(windows)]
-pub use windows_scanner::*;
\ No newline at end of file
+pub use mft::*;
\ No newline at end of file
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::Path;
+use tracing::debug;
+use winapi::um::winnt::{FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_REPARSE_POINT};
+use filemover_types::{CancellationToken, FileMoverError, ScanOptions};
+use crate::cloud::is_placeholder_attributes;
+use crate::exclude::ExclusionSet;
+use crate::file_id::FileId;
+use crate::filter::EntryAttributes;
+use crate::incremental::volume_key;
+use crate::scanner::DirectoryEntry;
+use crate::usn::{UsnRecord, VolumeHandle};
+
+// ファイル参照番号の下位 48bit が MFT のレコード番号
+const RECORD_NUMBER_MASK: u64 = 0x0000_FFFF_FFFF_FFFF;
+
+/// MFT を直接列挙してフォルダ一覧を作るウォーカー（ボリュームを開くため管理者権限が必要）
+///
+/// FindFirstFileW でフォルダを 1 つずつ開く代わりに、ボリューム全体のレコードを
+/// まとめて読み、親子関係からパスを組み立てる。
+pub struct MftDirectoryWalker {
+    options: ScanOptions,
+    exclusions: ExclusionSet,
+    cancel_token: CancellationToken,
+}
+
+struct MftNode {
+    reference: u64,
+    name: OsString,
+    attributes: u32,
+}
+
+impl MftDirectoryWalker {
+    pub fn new(options: ScanOptions) -> Self {
+        Self {
+            exclusions: ExclusionSet::for_walker(&options),
+            options,
+            cancel_token: CancellationToken::new(),
+        }
+    }
+
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = token;
+        self
+    }
+
+    pub fn walk(&self, root: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
+        let volume = volume_key(root).ok_or_else(|| FileMoverError::Scan {
+            path: root.to_path_buf(),
+            message: "Cannot determine the volume of this path".to_string(),
+        })?;
+
+        let handle = VolumeHandle::open(&volume)?;
+        let root_reference = FileId::of(root)?.index;
+        let children = self.read_tree(&handle)?;
+
+        let mut entries = Vec::new();
+        self.collect(&children, root_reference & RECORD_NUMBER_MASK, root, 0, &mut entries);
+        debug!("MFT enumeration of {} found {} entries", root.display(), entries.len());
+
+        Ok(entries)
+    }
+
+    /// 親のレコード番号ごとに子を分類する
+    fn read_tree(&self, handle: &VolumeHandle) -> Result<HashMap<u64, Vec<MftNode>>, FileMoverError> {
+        let mut children: HashMap<u64, Vec<MftNode>> = HashMap::new();
+        let mut start = 0u64;
+
+        while let Some((next, records)) = handle.enum_records(start)? {
+            // 列挙途中でキャンセルされた場合は読めた範囲だけで組み立てる
+            if self.cancel_token.is_cancelled() {
+                break;
+            }
+
+            for record in records {
+                if !record.is_directory() && !self.options.include_files {
+                    continue;
+                }
+                children
+                    .entry(record.parent_reference & RECORD_NUMBER_MASK)
+                    .or_default()
+                    .push(node_from(record));
+            }
+
+            if next == start {
+                break;
+            }
+            start = next;
+        }
+
+        Ok(children)
+    }
+
+    fn collect(
+        &self,
+        children: &HashMap<u64, Vec<MftNode>>,
+        parent: u64,
+        parent_path: &Path,
+        depth: u32,
+        entries: &mut Vec<DirectoryEntry>,
+    ) {
+        if self.options.max_depth.is_some_and(|max| depth > max) {
+            return;
+        }
+
+        let Some(nodes) = children.get(&parent) else {
+            return;
+        };
+
+        for node in nodes {
+            if self.cancel_token.is_cancelled() {
+                return;
+            }
+
+            let path = parent_path.join(&node.name);
+            let attributes = EntryAttributes::from_raw(node.attributes);
+            if self.is_excluded_path(&path) || self.options.excludes_attributes(attributes.hidden, attributes.system) {
+                continue;
+            }
+
+            let is_directory = node.attributes & FILE_ATTRIBUTE_DIRECTORY != 0;
+            // MFT のレコードには再解析タグがないため、プレースホルダは属性で見分ける
+            let is_placeholder = is_placeholder_attributes(node.attributes);
+            let is_junction = node.attributes & FILE_ATTRIBUTE_REPARSE_POINT != 0 && !is_placeholder;
+
+            entries.push(DirectoryEntry {
+                path: path.clone(),
+                is_directory,
+                is_junction,
+                is_placeholder,
+                is_hidden: attributes.hidden,
+                is_system: attributes.system,
+                access_denied: false,
+                size_bytes: None,
+                item_count: None,
+            });
+
+            // MFT 上の親子関係はジャンクションの先をたどらないので、そのまま再帰してよい
+            if is_directory && !is_junction {
+                self.collect(children, node.reference, &path, depth + 1, entries);
+            }
+        }
+    }
+
+    fn is_excluded_path(&self, path: &Path) -> bool {
+        self.exclusions.is_excluded(path)
+    }
+}
+
+fn node_from(record: UsnRecord) -> MftNode {
+    MftNode {
+        reference: record.file_reference & RECORD_NUMBER_MASK,
+        name: record.name,
+        attributes: record.attributes,
+    }
+}
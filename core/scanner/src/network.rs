@@ -0,0 +1,230 @@
+use std::path::{Component, Path, Prefix};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+use filemover_types::{FileMoverError, NetworkCredential, ScanOptions};
+
+/// タイムアウトに対してこの割合以上かかった応答は「遅い」とみなす
+const SLOW_RESPONSE_RATIO: u32 = 4;
+
+/// UNC パス（`\\server\share\...`）かどうか
+pub fn is_network_path(path: &Path) -> bool {
+    matches!(
+        path.components().next(),
+        Some(Component::Prefix(prefix))
+            if matches!(prefix.kind(), Prefix::UNC(..) | Prefix::VerbatimUNC(..))
+    )
+}
+
+/// 共有のルート（`\\server\share`）
+pub fn share_root(path: &Path) -> Option<&Path> {
+    match path.components().next()? {
+        Component::Prefix(prefix) => match prefix.kind() {
+            Prefix::UNC(..) | Prefix::VerbatimUNC(..) => Some(Path::new(prefix.as_os_str())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// ルートに到達できたときの応答の速さ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkResponse {
+    Normal,
+    Slow,
+}
+
+/// 別スレッドで f を実行し、timeout 以内に終わらなければ None を返す
+///
+/// 応答のない共有へのアクセスは中断できないため、タイムアウトしたスレッドは
+/// 待たずに切り離す（OS 側のタイムアウトで最終的に終了する）。
+pub fn run_with_timeout<T, F>(timeout: Duration, f: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(f());
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+/// ネットワーク上のルートに到達できるかをタイムアウト付きで確認する
+pub fn probe_root(root: &Path, timeout: Duration) -> Result<NetworkResponse, FileMoverError> {
+    let path = root.to_path_buf();
+    let started = Instant::now();
+
+    let result = run_with_timeout(timeout, move || std::fs::read_dir(&path).map(|_| ()));
+    match result {
+        Some(Ok(())) => {
+            let elapsed = started.elapsed();
+            debug!("Network root {} responded in {:?}", root.display(), elapsed);
+            if elapsed * SLOW_RESPONSE_RATIO >= timeout {
+                Ok(NetworkResponse::Slow)
+            } else {
+                Ok(NetworkResponse::Normal)
+            }
+        }
+        Some(Err(e)) => Err(e.into()),
+        None => Err(FileMoverError::NetworkTimeout {
+            path: root.to_path_buf(),
+            timeout_secs: timeout.as_secs(),
+        }),
+    }
+}
+
+/// ルートに対応する資格情報（もっとも長く一致するもの）
+pub fn credential_for<'a>(options: &'a ScanOptions, root: &Path) -> Option<&'a NetworkCredential> {
+    options.network_credentials
+        .iter()
+        .filter(|credential| root.starts_with(&credential.root))
+        .max_by_key(|credential| credential.root.components().count())
+}
+
+/// 資格情報で共有に接続し、drop 時に切断する
+pub struct NetworkConnection {
+    #[cfg(windows)]
+    remote: Option<Vec<u16>>,
+}
+
+impl NetworkConnection {
+    /// 資格情報が設定されていればその共有に接続する
+    pub fn open(options: &ScanOptions, root: &Path) -> Result<Self, FileMoverError> {
+        let Some(credential) = credential_for(options, root) else {
+            return Ok(Self::none());
+        };
+
+        let password = match &credential.password_env {
+            Some(name) => Some(std::env::var(name).map_err(|_| FileMoverError::Config {
+                message: format!("Environment variable {} for {} is not set", name, credential.root.display()),
+            })?),
+            None => None,
+        };
+
+        Self::connect(root, credential, password.as_deref())
+    }
+
+    fn none() -> Self {
+        Self {
+            #[cfg(windows)]
+            remote: None,
+        }
+    }
+
+    #[cfg(windows)]
+    fn connect(root: &Path, credential: &NetworkCredential, password: Option<&str>) -> Result<Self, FileMoverError> {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::shared::winerror::{ERROR_ALREADY_ASSIGNED, ERROR_SESSION_CREDENTIAL_CONFLICT, NO_ERROR};
+        use winapi::um::winnetwk::{WNetAddConnection2W, NETRESOURCEW, RESOURCETYPE_DISK};
+
+        fn wide(s: &std::ffi::OsStr) -> Vec<u16> {
+            s.encode_wide().chain(std::iter::once(0)).collect()
+        }
+
+        let share = share_root(root).unwrap_or(root);
+        let mut remote = wide(share.as_os_str());
+        let username = wide(credential.username.as_ref());
+        let password = password.map(|p| wide(p.as_ref()));
+
+        let mut resource: NETRESOURCEW = unsafe { std::mem::zeroed() };
+        resource.dwType = RESOURCETYPE_DISK;
+        resource.lpRemoteName = remote.as_mut_ptr();
+
+        let result = unsafe {
+            WNetAddConnection2W(
+                &mut resource,
+                password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                username.as_ptr(),
+                0,
+            )
+        };
+
+        match result {
+            NO_ERROR => {
+                debug!("Connected to {} as {}", share.display(), credential.username);
+                Ok(Self { remote: Some(remote) })
+            }
+            // 既に接続済みの場合はその接続をそのまま使う
+            ERROR_ALREADY_ASSIGNED | ERROR_SESSION_CREDENTIAL_CONFLICT => {
+                warn!("{} is already connected, using the existing session", share.display());
+                Ok(Self::none())
+            }
+            code => Err(FileMoverError::Scan {
+                path: root.to_path_buf(),
+                message: format!("Failed to connect to network share (error {})", code),
+            }),
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn connect(root: &Path, _credential: &NetworkCredential, _password: Option<&str>) -> Result<Self, FileMoverError> {
+        warn!("Network credentials are only supported on Windows, ignoring them for {}", root.display());
+        Ok(Self::none())
+    }
+}
+
+#[cfg(windows)]
+impl Drop for NetworkConnection {
+    fn drop(&mut self) {
+        use winapi::um::winnetwk::WNetCancelConnection2W;
+
+        if let Some(remote) = &self.remote {
+            unsafe { WNetCancelConnection2W(remote.as_ptr(), 0, 0) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_run_with_timeout() {
+        assert_eq!(run_with_timeout(Duration::from_secs(5), || 42), Some(42));
+
+        let slow = run_with_timeout(Duration::from_millis(10), || {
+            std::thread::sleep(Duration::from_millis(500));
+        });
+        assert!(slow.is_none());
+    }
+
+    #[test]
+    fn test_probe_local_root() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let response = probe_root(temp_dir.path(), Duration::from_secs(30)).unwrap();
+        assert_eq!(response, NetworkResponse::Normal);
+        assert!(probe_root(&temp_dir.path().join("missing"), Duration::from_secs(30)).is_err());
+    }
+
+    #[test]
+    fn test_credential_for_prefers_longest_root() {
+        let credential = |root: &str, username: &str| NetworkCredential {
+            root: PathBuf::from(root),
+            username: username.to_string(),
+            password_env: None,
+        };
+        let options = ScanOptions {
+            network_credentials: vec![
+                credential("/mnt/server", "reader"),
+                credential("/mnt/server/finance", "accountant"),
+            ],
+            ..Default::default()
+        };
+
+        let found = credential_for(&options, Path::new("/mnt/server/finance/2024")).unwrap();
+        assert_eq!(found.username, "accountant");
+        assert!(credential_for(&options, Path::new("/mnt/other")).is_none());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_network_path_detection() {
+        assert!(is_network_path(Path::new(r"\\server\share\folder")));
+        assert!(!is_network_path(Path::new(r"C:\Users")));
+        assert_eq!(share_root(Path::new(r"\\server\share\folder")), Some(Path::new(r"\\server\share")));
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use filemover_types::{FolderHit, ScanProgress};
+
+// フォルダを訪問するたびに通知すると呼び出し側が追いつかないため間引く
+const REPORT_INTERVAL: u64 = 100;
+
+/// スキャン中に通知されるイベント
+#[derive(Debug, Clone)]
+pub enum ScanEvent {
+    Progress(ScanProgress),
+    Hit(FolderHit),
+}
+
+pub type ScanEventCallback = Arc<dyn Fn(&ScanEvent) + Send + Sync>;
+
+/// 並列に走査するスレッド間で進捗を集計する
+#[derive(Default)]
+pub(crate) struct ProgressReporter {
+    callback: Option<ScanEventCallback>,
+    dirs_visited: AtomicU64,
+    hits_found: AtomicU64,
+}
+
+impl ProgressReporter {
+    pub fn new(callback: ScanEventCallback) -> Self {
+        Self {
+            callback: Some(callback),
+            ..Default::default()
+        }
+    }
+
+    pub fn reset(&self) {
+        self.dirs_visited.store(0, Ordering::Relaxed);
+        self.hits_found.store(0, Ordering::Relaxed);
+    }
+
+    pub fn dir_visited(&self, path: &Path) {
+        let visited = self.dirs_visited.fetch_add(1, Ordering::Relaxed) + 1;
+        if visited.is_multiple_of(REPORT_INTERVAL) {
+            self.report(Some(path));
+        }
+    }
+
+    pub fn hit_found(&self, hit: &FolderHit) {
+        self.hits_found.fetch_add(1, Ordering::Relaxed);
+        if let Some(callback) = &self.callback {
+            callback(&ScanEvent::Hit(hit.clone()));
+        }
+        self.report(Some(&hit.path));
+    }
+
+    /// 現在の集計を通知する
+    pub fn report(&self, current_path: Option<&Path>) {
+        if let Some(callback) = &self.callback {
+            callback(&ScanEvent::Progress(self.snapshot(current_path)));
+        }
+    }
+
+    pub fn snapshot(&self, current_path: Option<&Path>) -> ScanProgress {
+        ScanProgress {
+            dirs_visited: self.dirs_visited.load(Ordering::Relaxed),
+            hits_found: self.hits_found.load(Ordering::Relaxed),
+            current_path: current_path.map(Path::to_path_buf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress_is_throttled() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let reporter = ProgressReporter::new(Arc::new(move |event: &ScanEvent| {
+            if let ScanEvent::Progress(progress) = event {
+                sink.lock().unwrap().push(progress.dirs_visited);
+            }
+        }));
+
+        for _ in 0..250 {
+            reporter.dir_visited(Path::new("/data"));
+        }
+
+        assert_eq!(*events.lock().unwrap(), vec![100, 200]);
+        assert_eq!(reporter.snapshot(None).dirs_visited, 250);
+    }
+
+    #[test]
+    fn test_hits_are_forwarded() {
+        let hits = Arc::new(Mutex::new(Vec::new()));
+        let sink = hits.clone();
+        let reporter = ProgressReporter::new(Arc::new(move |event: &ScanEvent| {
+            if let ScanEvent::Hit(hit) = event {
+                sink.lock().unwrap().push(hit.path.clone());
+            }
+        }));
+
+        let hit = FolderHit {
+            path: PathBuf::from("/data/project"),
+            name: "project".to_string(),
+            matched_rule: None,
+            matched_rules: Vec::new(),
+            tags: Vec::new(),
+            dest_preview: None,
+            warnings: vec![],
+            size_bytes: None,
+            item_count: None,
+            is_dir: true,
+        };
+        reporter.hit_found(&hit);
+
+        assert_eq!(*hits.lock().unwrap(), vec![PathBuf::from("/data/project")]);
+        assert_eq!(reporter.snapshot(None).hits_found, 1);
+    }
+}
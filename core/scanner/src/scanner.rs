@@ -1,45 +1,242 @@
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+use chrono::Datelike;
 use rayon::prelude::*;
-use tracing::{debug, warn, error};
+use tracing::{debug, warn};
 use filemover_types::{
-    ScanOptions, Rule, FolderHit, Warning, FileMoverError, NormalizationOptions
+    Config, ScanOptions, Rule, PatternSpec, FolderHit, Warning, FileMoverError, CancellationToken,
+    ScanBackend, TraversalOrder, exceeds_max_path, exceeds_max_path_after_move, expand_placeholders, extract_date,
+    ResultOrder
 };
-use filemover_matcher::MatchingEngine;
+use filemover_matcher::{select_rule, EngineCache, EntryMetadata, MatchContext, MatchExplanation, MatchingEngine};
 use crate::walker::DirectoryWalker;
+use crate::size::DirectorySizeCalculator;
+use crate::progress::{ProgressReporter, ScanEventCallback};
+use crate::filter::EntryFilter;
+use crate::exclude::ExclusionSet;
+use crate::network::{is_network_path, probe_root, NetworkConnection, NetworkResponse};
+use crate::stats::RuleStats;
 
 pub struct FolderScanner {
     matching_engine: Arc<MatchingEngine>,
     options: ScanOptions,
+    cancel_token: CancellationToken,
+    size_calculator: Option<DirectorySizeCalculator>,
+    filter: EntryFilter,
+    progress: Arc<ProgressReporter>,
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
+    /// 今回のスキャンのルールごとの集計
+    rule_stats: Mutex<RuleStats>,
 }
 
 impl FolderScanner {
     pub fn new(rules: Vec<Rule>, options: ScanOptions) -> Result<Self, FileMoverError> {
+        Self::with_exclude_patterns(rules, Vec::new(), options)
+    }
+
+    /// 設定の移動ルール・除外パターン・スキャンオプションで作る
+    pub fn from_config(config: &Config) -> Result<Self, FileMoverError> {
+        Self::with_exclude_patterns(config.rules.clone(), config.exclude_patterns.clone(), config.options.clone())
+    }
+
+    /// from_config と同じだが、ルールが前回と同じならコンパイル済みのエンジンを使い回す
+    pub fn from_config_cached(config: &Config, cache: &EngineCache) -> Result<Self, FileMoverError> {
+        let engine = cache.get_or_build(&config.rules, &config.exclude_patterns, &config.options.normalization)?;
+        Self::with_engine(engine, config.options.clone())
+    }
+
+    pub fn with_exclude_patterns(
+        rules: Vec<Rule>,
+        exclude_patterns: Vec<PatternSpec>,
+        options: ScanOptions,
+    ) -> Result<Self, FileMoverError> {
         let matching_engine = Arc::new(
-            MatchingEngine::new(rules, options.normalization.clone())?
+            MatchingEngine::with_exclude_patterns(rules, exclude_patterns, options.normalization.clone())?
         );
+        Self::with_engine(matching_engine, options)
+    }
+
+    /// コンパイル済みのエンジンで作る（エンジンは options.normalization で作られている前提）
+    pub fn with_engine(matching_engine: Arc<MatchingEngine>, options: ScanOptions) -> Result<Self, FileMoverError> {
+        let rules_need_sizes = matching_engine.rules().iter()
+            .any(|rule| rule.enabled && rule.conditions.needs_size());
+        // 不正な除外パターンはウォーカーでは無視されるので、ここで先に弾く
+        ExclusionSet::new(&options)?;
+
+        // サイズで絞り込む場合やルールにサイズ条件がある場合も計算が必要
+        let needs_sizes = options.calculate_sizes || options.filters.has_size_filter() || rules_need_sizes;
+        let size_calculator = needs_sizes.then(|| match &options.size_cache {
+            Some(path) => DirectorySizeCalculator::with_cache_file(path.clone()),
+            None => DirectorySizeCalculator::new(),
+        });
+
+        // スレッド数の指定がなければ rayon の既定（CPU コア数）のプールを使う
+        let thread_pool = match options.parallel_threads {
+            Some(threads) => Some(Arc::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .thread_name(|i| format!("filemover-scan-{}", i))
+                    .build()
+                    .map_err(|e| FileMoverError::Config {
+                        message: format!("Failed to create scan thread pool: {}", e),
+                    })?,
+            )),
+            None => None,
+        };
 
         Ok(Self {
             matching_engine,
+            filter: EntryFilter::new(options.filters.clone()),
             options,
+            cancel_token: CancellationToken::new(),
+            size_calculator,
+            progress: Arc::new(ProgressReporter::default()),
+            thread_pool,
+            rule_stats: Mutex::new(RuleStats::new()),
         })
     }
 
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = token;
+        self
+    }
+
+    /// 見つかったヒットと途中経過を callback に通知する
+    pub fn with_progress(mut self, callback: ScanEventCallback) -> Self {
+        self.progress = Arc::new(ProgressReporter::new(callback));
+        self
+    }
+
+    /// キャンセルされた場合は `FileMoverError::Cancelled` を返す
     pub fn scan_roots(&self, roots: &[PathBuf]) -> Result<Vec<FolderHit>, FileMoverError> {
-        debug!("Starting scan of {} root directories", roots.len());
+        let outcome = self.scan(roots)?;
+        if outcome.cancelled {
+            return Err(FileMoverError::Cancelled);
+        }
+        Ok(outcome.hits)
+    }
+
+    /// キャンセルされた場合もそれまでに見つかったヒットを返す
+    pub fn scan(&self, roots: &[PathBuf]) -> Result<ScanOutcome, FileMoverError> {
+        self.install(|| self.scan_in_pool(roots))
+    }
+
+    fn scan_in_pool(&self, roots: &[PathBuf]) -> Result<ScanOutcome, FileMoverError> {
+        debug!("Starting scan of {} root directories (threads: {})", roots.len(), rayon::current_num_threads());
+        self.begin_scan();
         
-        let results: Result<Vec<Vec<FolderHit>>, FileMoverError> = roots
+        let results: Vec<(&PathBuf, Result<Vec<FolderHit>, FileMoverError>)> = roots
             .par_iter()
-            .map(|root| self.scan_single_root(root))
+            .map(|root| {
+                if self.cancel_token.is_cancelled() {
+                    return (root, Ok(vec![]));
+                }
+                (root, self.scan_single_root(root))
+            })
             .collect();
 
-        let all_hits: Vec<FolderHit> = results?.into_iter().flatten().collect();
-        debug!("Scan completed, found {} folder hits", all_hits.len());
+        // 到達できないネットワーク共有はスキップして他のルートの結果を返す
+        let mut hits = Vec::new();
+        let mut root_warnings = Vec::new();
+        for (root, result) in results {
+            match result {
+                Ok(root_hits) => hits.extend(root_hits),
+                Err(e) if is_network_path(root) => root_warnings.push(RootWarning::unreachable(root, &e)),
+                Err(e) => return Err(e),
+            }
+        }
+
+        sort_hits(&mut hits, self.options.result_order);
+
+        let cancelled = self.cancel_token.is_cancelled();
+        if cancelled {
+            debug!("Scan cancelled, returning {} partial folder hits", hits.len());
+        } else {
+            debug!("Scan completed, found {} folder hits", hits.len());
+        }
+
+        self.finish_scan();
         
-        Ok(all_hits)
+        Ok(ScanOutcome { hits, cancelled, root_warnings, rule_stats: self.take_rule_stats() })
+    }
+
+    pub(crate) fn options(&self) -> &ScanOptions {
+        &self.options
+    }
+
+    pub(crate) fn cancel_token(&self) -> &CancellationToken {
+        &self.cancel_token
     }
 
-    fn scan_single_root(&self, root: &Path) -> Result<Vec<FolderHit>, FileMoverError> {
+    /// parallel_threads で作ったプールの中で op を実行する
+    pub(crate) fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match &self.thread_pool {
+            Some(pool) => pool.install(op),
+            None => op(),
+        }
+    }
+
+    pub(crate) fn begin_scan(&self) {
+        self.progress.reset();
+        self.take_rule_stats();
+    }
+
+    /// ここまでの集計を取り出し、集計を空に戻す
+    pub(crate) fn take_rule_stats(&self) -> RuleStats {
+        std::mem::take(&mut *self.rule_stats.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// 前回のスキャンで見つかっていたヒットを集計に加える
+    pub(crate) fn record_previous_hits(&self, hits: &[FolderHit]) {
+        let mut stats = self.rule_stats.lock().unwrap_or_else(|e| e.into_inner());
+        for hit in hits {
+            stats.record_hit(hit);
+        }
+    }
+
+    pub(crate) fn finish_scan(&self) {
+        self.save_size_cache();
+        self.progress.report(None);
+    }
+
+    pub(crate) fn save_size_cache(&self) {
+        if let Some(calculator) = &self.size_calculator {
+            if let Err(e) = calculator.save_cache() {
+                warn!("Failed to save size cache: {}", e);
+            }
+        }
+    }
+
+    pub(crate) fn scan_single_root(&self, root: &Path) -> Result<Vec<FolderHit>, FileMoverError> {
+        self.scan_root_with(root, |walker, root_warnings| {
+            let entries = if self.splits_roots() {
+                self.walk_split(root, walker)?
+            } else {
+                walker.walk(root)?
+            };
+            let mut hits = self.scan_entries(root, entries)?;
+            add_root_warnings(&mut hits, root_warnings);
+            Ok(hits)
+        })
+    }
+
+    /// ルートを確認してから scan で走査・照合する
+    ///
+    /// ネットワークルートの接続と応答確認はここで行い、ルートのすべてのヒットに付ける警告を scan に渡す。
+    pub(crate) fn scan_root_with<F>(&self, root: &Path, scan: F) -> Result<Vec<FolderHit>, FileMoverError>
+    where
+        F: FnOnce(&DirectoryWalker, &[Warning]) -> Result<Vec<FolderHit>, FileMoverError>,
+    {
+        // 応答のない共有で exists() などが固まらないよう、先にタイムアウト付きで確認する
+        let _connection = NetworkConnection::open(&self.options, root)?;
+        let response = if is_network_path(root) {
+            let timeout = Duration::from_secs(self.options.network_timeout_secs);
+            Some(probe_root(root, timeout)?)
+        } else {
+            None
+        };
+
         if !root.exists() {
             warn!("Root path does not exist: {}", root.display());
             return Ok(vec![]);
@@ -51,15 +248,82 @@ impl FolderScanner {
         }
 
         debug!("Scanning root: {}", root.display());
-        
-        let walker = DirectoryWalker::new(self.options.clone());
-        let entries = walker.walk(root)?;
-        
+
+        let mut root_warnings = Vec::new();
+        if response == Some(NetworkResponse::Slow) {
+            warn!("Network root {} is responding slowly", root.display());
+            root_warnings.push(Warning::NetworkSlow);
+        }
+
+        scan(&self.walker(self.options.clone()), &root_warnings)
+    }
+
+    // MFT はボリュームごとにまとめて読むので分けても速くならない
+    fn splits_roots(&self) -> bool {
+        self.options.parallel_subtrees
+            && self.options.backend != ScanBackend::Mft
+            && rayon::current_num_threads() > 1
+    }
+
+    /// ルートを直下のフォルダ単位に分けて並列に走査し、まとめて走査した場合と同じ順に並べる
+    fn walk_split(&self, root: &Path, walker: &DirectoryWalker) -> Result<Vec<DirectoryEntry>, FileMoverError> {
+        let mut entries = self.walk_root_listing(root)?;
+        let subtrees: Vec<Vec<DirectoryEntry>> = walker.child_dirs(root)?
+            .into_par_iter()
+            .map(|dir| self.walk_subtree(&dir))
+            .collect::<Result<_, _>>()?;
+        entries.extend(subtrees.into_iter().flatten());
+
+        // 名前順にたどった深さ優先の順序はパスの順序と同じ。幅優先は深さで安定ソートする
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        if self.options.traversal == TraversalOrder::BreadthFirst {
+            entries.sort_by_key(|entry| entry.path.components().count());
+        }
+        Ok(entries)
+    }
+
+    /// ルートの直下までを走査する（ルート自身を含めるかどうかはバックエンドに合わせる）
+    pub(crate) fn walk_root_listing(&self, root: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
+        let mut options = self.options.clone();
+        options.max_depth = Some(self.options.max_depth.map_or(1, |max| max.min(1)));
+        self.walker(options).walk(root)
+    }
+
+    /// ルート直下のフォルダ dir の配下を走査する（dir 自身は直下の走査に含まれる）
+    pub(crate) fn walk_subtree(&self, dir: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
+        // dir はルートからの深さ 1 なので、その分だけ配下の深さを減らす
+        if self.options.max_depth.is_some_and(|max| max <= 1) {
+            return Ok(vec![]);
+        }
+        let mut options = self.options.clone();
+        options.max_depth = self.options.max_depth.map(|max| max - 1);
+
+        let entries = self.walker(options).walk(dir)?;
+        Ok(entries.into_iter().filter(|entry| entry.path != dir).collect())
+    }
+
+    /// キャンセルと進捗通知を引き継いだウォーカー
+    pub(crate) fn walker(&self, options: ScanOptions) -> DirectoryWalker {
+        DirectoryWalker::new(options)
+            .with_cancellation(self.cancel_token.clone())
+            .with_progress(self.progress.clone())
+    }
+
+    /// 走査済みのエントリをルールと照合する（キャンセル後のエントリは処理しない）
+    pub(crate) fn scan_entries(&self, root: &Path, entries: Vec<DirectoryEntry>) -> Result<Vec<FolderHit>, FileMoverError> {
         let hits: Result<Vec<FolderHit>, FileMoverError> = entries
             .into_par_iter()
             .filter_map(|entry| {
-                match self.process_entry(entry) {
-                    Ok(Some(hit)) => Some(Ok(hit)),
+                if self.cancel_token.is_cancelled() {
+                    return None;
+                }
+
+                match self.process_entry(root, entry) {
+                    Ok(Some(hit)) => {
+                        self.rule_stats.lock().unwrap_or_else(|e| e.into_inner()).record_hit(&hit);
+                        self.progress.hit_found(&hit);
+                        Some(Ok(hit))
+                    }
                     Ok(None) => None,
                     Err(e) => Some(Err(e)),
                 }
@@ -69,8 +333,8 @@ impl FolderScanner {
         hits
     }
 
-    fn process_entry(&self, entry: DirectoryEntry) -> Result<Option<FolderHit>, FileMoverError> {
-        if !entry.is_directory {
+    fn process_entry(&self, root: &Path, entry: DirectoryEntry) -> Result<Option<FolderHit>, FileMoverError> {
+        if !entry.is_directory && !self.options.include_files {
             return Ok(None);
         }
 
@@ -79,30 +343,116 @@ impl FolderScanner {
             .and_then(|n| n.to_str())
             .ok_or_else(|| FileMoverError::Scan {
                 path: entry.path.clone(),
-                message: "Invalid folder name encoding".to_string(),
+                message: "Invalid name encoding".to_string(),
             })?;
 
         // マッチングルールを確認
-        match self.matching_engine.find_matching_rule(folder_name)? {
+        let metadata = LazyEntryMetadata::new(self, &entry);
+        let context = MatchContext::from_path(folder_name, &entry.path, root).with_metadata(&metadata);
+        let (rule, matched_rules) = if self.options.collects_all_matches() {
+            let rules = self.matching_engine.find_matching_rules_for(&context)?;
+            (select_rule(&rules, self.options.tie_break), rules.iter().map(|r| r.id).collect())
+        } else {
+            let rule = self.matching_engine.find_matching_rule_for(&context)?;
+            (rule, rule.iter().map(|r| r.id).collect())
+        };
+        match rule {
             Some(rule) => {
+                let size_bytes = metadata.size_bytes();
+                if !self.filter.matches_size(size_bytes) {
+                    return Ok(None);
+                }
+
                 let dest_preview = self.generate_destination_preview(rule, &entry.path)?;
-                let warnings = self.analyze_warnings(&entry);
+                let warnings = self.analyze_warnings(&entry, &dest_preview);
+                let tags = self.matching_engine.tags_for(&context)?
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
 
                 let hit = FolderHit {
                     path: entry.path.clone(),
                     name: folder_name.to_string(),
                     matched_rule: Some(rule.id),
+                    matched_rules,
+                    tags,
                     dest_preview: Some(dest_preview),
                     warnings,
-                    size_bytes: entry.size_bytes,
+                    size_bytes,
+                    item_count: entry.item_count,
+                    is_dir: entry.is_directory,
                 };
 
                 Ok(Some(hit))
             }
-            None => Ok(None),
+            None => {
+                if let Some(excluded_by) = self.matching_engine.excluding_rule_for(&context)? {
+                    self.rule_stats.lock().unwrap_or_else(|e| e.into_inner()).record_excluded(excluded_by.id);
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// root 配下の path がどのルールにどう照合されるか（スキャン時と同じメタデータで評価する）
+    pub fn explain(&self, root: &Path, path: &Path) -> Result<MatchExplanation<'_>, FileMoverError> {
+        let entry = entry_for(path)?;
+
+        let folder_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let entry_metadata = LazyEntryMetadata::new(self, &entry);
+        let context = MatchContext::from_path(folder_name, path, root).with_metadata(&entry_metadata);
+        self.matching_engine.explain_for(&context)
+    }
+
+    /// root 配下の path 1 つだけをルールと照合する（監視で見つけた新しいフォルダなど）
+    pub fn evaluate(&self, root: &Path, path: &Path) -> Result<Option<FolderHit>, FileMoverError> {
+        let entry = entry_for(path)?;
+        let hit = self.process_entry(root, entry)?;
+        if let Some(hit) = &hit {
+            self.rule_stats.lock().unwrap_or_else(|e| e.into_inner()).record_hit(hit);
+        }
+        Ok(hit)
+    }
+
+    // ヒットしたフォルダについてだけサイズを計算する
+    fn calculate_size(&self, entry: &DirectoryEntry) -> Option<u64> {
+        if !entry.is_directory || entry.access_denied {
+            return None;
+        }
+        self.directory_size(&entry.path)
+    }
+
+    /// サイズ計算が有効な場合のみフォルダサイズを返す
+    pub(crate) fn directory_size(&self, path: &Path) -> Option<u64> {
+        let calculator = self.size_calculator.as_ref()?;
+        match calculator.directory_size(path) {
+            Ok(size) => Some(size),
+            Err(e) => {
+                warn!("Failed to calculate size of {}: {}", path.display(), e);
+                None
+            }
         }
     }
 
+    /// 複数のルールに一致したヒットで使うルールを選び直し、移動先を作り直す
+    pub fn reassign_rule(&self, hit: &mut FolderHit, rule: &Rule) -> Result<(), FileMoverError> {
+        if !hit.matched_rules.contains(&rule.id) {
+            return Err(FileMoverError::Scan {
+                path: hit.path.clone(),
+                message: format!("Rule {} did not match this folder", rule.id),
+            });
+        }
+
+        let dest_preview = self.generate_destination_preview(rule, &hit.path)?;
+        hit.warnings.retain(|w| *w != Warning::LongPath);
+        if exceeds_max_path(&hit.path) || exceeds_max_path_after_move(&hit.path, &dest_preview) {
+            hit.warnings.push(Warning::LongPath);
+        }
+        hit.matched_rule = Some(rule.id);
+        hit.dest_preview = Some(dest_preview);
+        Ok(())
+    }
+
     fn generate_destination_preview(&self, rule: &Rule, source_path: &Path) -> Result<PathBuf, FileMoverError> {
         let template = &rule.template;
         let folder_name = source_path
@@ -110,14 +460,46 @@ impl FolderScanner {
             .and_then(|n| n.to_str())
             .unwrap_or("unknown");
 
-        // テンプレート変数を展開
-        let expanded = template
-            .replace("{name}", folder_name)
-            .replace("{yyyy}", &chrono::Utc::now().format("%Y").to_string())
-            .replace("{yyyyMM}", &chrono::Utc::now().format("%Y%m").to_string())
-            .replace("{drive}", &self.extract_drive_letter(source_path))
-            .replace("{parent}", &self.extract_parent_name(source_path))
-            .replace("{label}", &rule.label.as_deref().unwrap_or(""));
+        // 名前に含まれる日付（見つからない部分は現在の日付）
+        let now = chrono::Utc::now();
+        let date = extract_date(folder_name, &rule.date_formats);
+        let src_yyyy = date.map(|d| d.year).unwrap_or_else(|| now.year());
+        let src_mm = date.and_then(|d| d.month).unwrap_or_else(|| now.month());
+        let src_dd = date.and_then(|d| d.day).unwrap_or_else(|| now.day());
+
+        // 更新日時・作成日時（作成日時が取れなければ更新日時、どちらもなければ現在）
+        let metadata = std::fs::metadata(source_path).ok();
+        let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+        let created = metadata.as_ref().and_then(|m| m.created().ok()).or(modified);
+        let mtime: chrono::DateTime<chrono::Utc> = modified.map(Into::into).unwrap_or(now);
+        let ctime: chrono::DateTime<chrono::Utc> = created.map(Into::into).unwrap_or(now);
+
+        // テンプレート変数を展開（関数も適用する）
+        let expanded = expand_placeholders(template, |name| match name {
+            "name" => Some(folder_name.to_string()),
+            "yyyy" => Some(now.format("%Y").to_string()),
+            "yyyyMM" => Some(now.format("%Y%m").to_string()),
+            "drive" => Some(self.extract_drive_letter(source_path)),
+            "parent" => Some(self.extract_parent_name(source_path)),
+            "label" => Some(rule.label.clone().unwrap_or_default()),
+            "src_yyyy" => Some(format!("{:04}", src_yyyy)),
+            "src_yy" => Some(format!("{:02}", src_yyyy % 100)),
+            "src_MM" => Some(format!("{:02}", src_mm)),
+            "src_dd" => Some(format!("{:02}", src_dd)),
+            "mtime_yyyy" => Some(mtime.format("%Y").to_string()),
+            "mtime_yy" => Some(mtime.format("%y").to_string()),
+            "mtime_MM" => Some(mtime.format("%m").to_string()),
+            "mtime_dd" => Some(mtime.format("%d").to_string()),
+            "ctime_yyyy" => Some(ctime.format("%Y").to_string()),
+            "ctime_yy" => Some(ctime.format("%y").to_string()),
+            "ctime_MM" => Some(ctime.format("%m").to_string()),
+            "ctime_dd" => Some(ctime.format("%d").to_string()),
+            // {seq} の番号は計画を作るときに決まるので、プレビューでは 1 とする
+            "seq" => Some("1".to_string()),
+            _ => name.strip_prefix("seq:")
+                .and_then(|width| width.parse::<usize>().ok())
+                .map(|width| format!("{:0width$}", 1, width = width)),
+        })?;
 
         Ok(rule.dest_root.join(expanded))
     }
@@ -147,11 +529,11 @@ impl FolderScanner {
             .to_string()
     }
 
-    fn analyze_warnings(&self, entry: &DirectoryEntry) -> Vec<Warning> {
+    fn analyze_warnings(&self, entry: &DirectoryEntry, dest_preview: &Path) -> Vec<Warning> {
         let mut warnings = Vec::new();
 
-        // 長パス警告
-        if entry.path.to_string_lossy().len() > 260 {
+        // 長パス警告（移動後の配下のパスが長くなりすぎる場合も含む）
+        if exceeds_max_path(&entry.path) || exceeds_max_path_after_move(&entry.path, dest_preview) {
             warnings.push(Warning::LongPath);
         }
 
@@ -165,22 +547,156 @@ impl FolderScanner {
             warnings.push(Warning::AccessDenied);
         }
 
-        // OneDriveオフライン警告（Windows特有）
-        #[cfg(windows)]
-        if self.is_onedrive_offline(&entry.path) {
+        if entry.is_hidden {
+            warnings.push(Warning::Hidden);
+        }
+        if entry.is_system {
+            warnings.push(Warning::System);
+        }
+
+        // クラウドのプレースホルダ（OneDrive のオンライン専用など）
+        if entry.is_placeholder {
             warnings.push(Warning::Offline);
         }
 
         warnings
     }
+}
+
+/// 走査せずに 1 つのパスのエントリを作る
+fn entry_for(path: &Path) -> Result<DirectoryEntry, FileMoverError> {
+    let metadata = std::fs::symlink_metadata(path).map_err(|e| FileMoverError::Scan {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    let is_directory = metadata.is_dir() || (metadata.file_type().is_symlink() && path.is_dir());
+    Ok(DirectoryEntry {
+        path: path.to_path_buf(),
+        is_directory,
+        is_junction: metadata.file_type().is_symlink(),
+        is_placeholder: false,
+        is_hidden: false,
+        is_system: false,
+        access_denied: false,
+        size_bytes: (!is_directory).then_some(metadata.len()),
+        item_count: is_directory
+            .then(|| std::fs::read_dir(path).ok().map(|items| items.count() as u64))
+            .flatten(),
+    })
+}
+
+pub(crate) fn add_root_warnings(hits: &mut [FolderHit], root_warnings: &[Warning]) {
+    for hit in hits {
+        hit.warnings.extend_from_slice(root_warnings);
+    }
+}
+
+/// ヒットを指定の順に並べる（同順位はパス順にして実行ごとの差をなくす）
+pub fn sort_hits(hits: &mut [FolderHit], order: ResultOrder) {
+    match order {
+        ResultOrder::Discovery => {}
+        ResultOrder::Path => hits.sort_by(|a, b| a.path.cmp(&b.path)),
+        ResultOrder::Size => hits.sort_by(|a, b| {
+            b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path))
+        }),
+        ResultOrder::Depth => hits.sort_by(|a, b| {
+            a.path.components().count()
+                .cmp(&b.path.components().count())
+                .then_with(|| a.path.cmp(&b.path))
+        }),
+    }
+}
+
+/// スキャン結果。cancelled の場合 hits は途中までの結果
+#[derive(Debug, Clone, Default)]
+pub struct ScanOutcome {
+    pub hits: Vec<FolderHit>,
+    pub cancelled: bool,
+    /// スキャンできなかったルートとその理由
+    pub root_warnings: Vec<RootWarning>,
+    /// ルールごとの一致数・サイズ・除外数（再開したスキャンでは前回分の除外数を含まない）
+    pub rule_stats: RuleStats,
+}
+
+#[derive(Debug, Clone)]
+pub struct RootWarning {
+    pub root: PathBuf,
+    pub warning: Warning,
+    pub message: String,
+}
+
+impl RootWarning {
+    /// 到達できないネットワーク共有（スキャンは他のルートで続ける）
+    pub fn unreachable(root: &Path, error: &FileMoverError) -> Self {
+        warn!("Skipping unreachable network root {}: {}", root.display(), error);
+        Self {
+            root: root.to_path_buf(),
+            warning: Warning::Unreachable,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// ルールの条件に使うメタデータ（サイズは条件で必要になったときに 1 回だけ計算する）
+struct LazyEntryMetadata<'a> {
+    scanner: &'a FolderScanner,
+    entry: &'a DirectoryEntry,
+    size: OnceLock<Option<u64>>,
+}
+
+impl<'a> LazyEntryMetadata<'a> {
+    fn new(scanner: &'a FolderScanner, entry: &'a DirectoryEntry) -> Self {
+        Self { scanner, entry, size: OnceLock::new() }
+    }
+}
+
+impl EntryMetadata for LazyEntryMetadata<'_> {
+    fn size_bytes(&self) -> Option<u64> {
+        *self.size.get_or_init(|| self.entry.size_bytes.or_else(|| self.scanner.calculate_size(self.entry)))
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.entry.path).and_then(|m| m.modified()).ok()
+    }
+
+    fn item_count(&self) -> Option<u64> {
+        self.entry.item_count
+    }
+
+    fn count_files(&self, max_depth: u32, limit: u64, accept: &dyn Fn(&str) -> bool) -> Option<u64> {
+        if !self.entry.is_directory || self.entry.access_denied {
+            return None;
+        }
+        let mut count = 0;
+        count_matching_files(&self.entry.path, max_depth, limit, accept, &mut count).ok()?;
+        Some(count)
+    }
+}
 
-    #[cfg(windows)]
-    fn is_onedrive_offline(&self, path: &Path) -> bool {
-        // OneDriveパスパターンの簡易チェック
-        let path_str = path.to_string_lossy().to_lowercase();
-        path_str.contains("onedrive") && 
-        (path_str.contains("personal") || path_str.contains("business"))
+// limit に達した時点でやめる。リンクはたどらず、読めないサブフォルダは飛ばす
+fn count_matching_files(
+    dir: &Path,
+    depth: u32,
+    limit: u64,
+    accept: &dyn Fn(&str) -> bool,
+    count: &mut u64,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        if *count >= limit {
+            break;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_file() {
+            if entry.file_name().to_str().is_some_and(accept) {
+                *count += 1;
+            }
+        } else if file_type.is_dir() && depth > 1 {
+            let _ = count_matching_files(&entry.path(), depth - 1, limit, accept, count);
+        }
     }
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
@@ -188,14 +704,21 @@ pub struct DirectoryEntry {
     pub path: PathBuf,
     pub is_directory: bool,
     pub is_junction: bool,
+    /// データがローカルにないクラウドのプレースホルダ
+    pub is_placeholder: bool,
+    pub is_hidden: bool,
+    pub is_system: bool,
     pub access_denied: bool,
     pub size_bytes: Option<u64>,
+    /// 直下の項目数（フォルダのみ）
+    pub item_count: Option<u64>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use filemover_types::{PatternSpec, ConflictPolicy};
+    use filemover_types::{PatternSpec, MatchTarget, RuleConditions, ContainsFilesCondition, TieBreakStrategy};
+    use crate::progress::ScanEvent;
     use tempfile::TempDir;
 
     fn create_test_scanner() -> FolderScanner {
@@ -227,6 +750,391 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_scan_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("test_folder")).unwrap();
+
+        let token = CancellationToken::new();
+        let scanner = create_test_scanner().with_cancellation(token.clone());
+        token.cancel();
+
+        let result = scanner.scan_roots(&[temp_dir.path().to_path_buf()]);
+        assert!(matches!(result, Err(FileMoverError::Cancelled)));
+    }
+
+    #[test]
+    fn test_cancelled_scan_returns_partial_outcome() {
+        let temp_dir = TempDir::new().unwrap();
+        for i in 0..200 {
+            std::fs::create_dir_all(temp_dir.path().join(format!("test_{}", i))).unwrap();
+        }
+
+        // 最初のヒットが見つかった時点でキャンセルする
+        let token = CancellationToken::new();
+        let trigger = token.clone();
+        let scanner = create_test_scanner()
+            .with_cancellation(token)
+            .with_progress(Arc::new(move |event: &ScanEvent| {
+                if matches!(event, ScanEvent::Hit(_)) {
+                    trigger.cancel();
+                }
+            }));
+
+        let outcome = scanner.scan(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert!(outcome.cancelled);
+        assert!(!outcome.hits.is_empty());
+        assert!(outcome.hits.len() < 200);
+    }
+
+    #[test]
+    fn test_scan_includes_files_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("test_folder")).unwrap();
+        std::fs::write(temp_dir.path().join("test_file.txt"), "data").unwrap();
+
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("test*"),
+                PathBuf::from("/archive"),
+                "{name}".to_string(),
+            ),
+        ];
+        let roots = [temp_dir.path().to_path_buf()];
+
+        let folders_only = FolderScanner::new(rules.clone(), ScanOptions::default()).unwrap();
+        let hits = folders_only.scan_roots(&roots).unwrap();
+        assert!(hits.iter().all(|h| h.is_dir));
+
+        let options = ScanOptions {
+            include_files: true,
+            ..ScanOptions::default()
+        };
+        let with_files = FolderScanner::new(rules, options).unwrap();
+        let hits = with_files.scan_roots(&roots).unwrap();
+        let file_hit = hits.iter().find(|h| h.name == "test_file.txt").unwrap();
+        assert!(!file_hit.is_dir);
+        assert_eq!(file_hit.size_bytes, Some(4));
+    }
+
+    #[test]
+    fn test_scan_matches_relative_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("Projects/app/build")).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("Other/build")).unwrap();
+
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("Projects/*/build").with_target(MatchTarget::RelativePathFromRoot),
+                PathBuf::from("/archive"),
+                "{name}".to_string(),
+            ),
+        ];
+        let scanner = FolderScanner::new(rules, ScanOptions::default()).unwrap();
+
+        let hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, temp_dir.path().join("Projects/app/build"));
+    }
+
+    #[test]
+    fn test_rule_conditions_use_entry_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("cache_empty")).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("cache_small")).unwrap();
+        std::fs::write(temp_dir.path().join("cache_small/a.bin"), vec![0u8; 10]).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("cache_large")).unwrap();
+        std::fs::write(temp_dir.path().join("cache_large/a.bin"), vec![0u8; 4096]).unwrap();
+
+        // 空でなく 1 KB 以上のフォルダだけ
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("cache_*"),
+                PathBuf::from("/archive"),
+                "{name}".to_string(),
+            ).with_conditions(RuleConditions {
+                min_size_bytes: Some(1024),
+                min_item_count: Some(1),
+                ..Default::default()
+            }),
+        ];
+        let scanner = FolderScanner::new(rules, ScanOptions::default()).unwrap();
+
+        let hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "cache_large");
+        assert_eq!(hits[0].size_bytes, Some(4096));
+    }
+
+    #[test]
+    fn test_contains_files_condition_peeks_into_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("trip_raw/day1")).unwrap();
+        std::fs::write(temp_dir.path().join("trip_raw/day1/IMG_0001.CR2"), b"raw").unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("trip_jpeg")).unwrap();
+        std::fs::write(temp_dir.path().join("trip_jpeg/IMG_0001.jpg"), b"jpeg").unwrap();
+
+        let rule = |depth| Rule::new(
+            PatternSpec::new_glob("trip_*"),
+            PathBuf::from("/photos"),
+            "{name}".to_string(),
+        ).with_conditions(RuleConditions {
+            contains_files: Some(ContainsFilesCondition::new("*.cr2").with_max_depth(depth)),
+            ..Default::default()
+        });
+        let root = temp_dir.path().to_path_buf();
+
+        // 直下だけでは見つからない
+        let scanner = FolderScanner::new(vec![rule(1)], ScanOptions::default()).unwrap();
+        assert!(scanner.scan_roots(std::slice::from_ref(&root)).unwrap().is_empty());
+
+        let scanner = FolderScanner::new(vec![rule(2)], ScanOptions::default()).unwrap();
+        let hits = scanner.scan_roots(&[root]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "trip_raw");
+    }
+
+    #[test]
+    fn test_collect_all_matches_and_reassign() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("test_raw_photos")).unwrap();
+
+        let rules = vec![
+            Rule::new(PatternSpec::new_contains("test"), PathBuf::from("/archive"), "{name}".to_string()).with_priority(1),
+            Rule::new(PatternSpec::new_glob("test_raw_*"), PathBuf::from("/photos"), "{name}".to_string()).with_priority(2),
+        ];
+        let options = ScanOptions { tie_break: TieBreakStrategy::MostSpecific, ..Default::default() };
+        let scanner = FolderScanner::new(rules.clone(), options).unwrap();
+
+        let mut hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].matched_rules, vec![rules[0].id, rules[1].id]);
+        assert_eq!(hits[0].matched_rule, Some(rules[1].id));
+        assert!(hits[0].has_multiple_matches());
+
+        scanner.reassign_rule(&mut hits[0], &rules[0]).unwrap();
+        assert_eq!(hits[0].matched_rule, Some(rules[0].id));
+        assert_eq!(hits[0].dest_preview, Some(PathBuf::from("/archive/test_raw_photos")));
+
+        let unrelated = Rule::new(PatternSpec::new_glob("*.tmp"), PathBuf::from("/tmp"), "{name}".to_string());
+        assert!(scanner.reassign_rule(&mut hits[0], &unrelated).is_err());
+    }
+
+    #[test]
+    fn test_from_config_skips_exclude_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("test_project")).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("test_backup")).unwrap();
+
+        let config = Config {
+            rules: vec![Rule::new(PatternSpec::new_glob("test_*"), PathBuf::from("/archive"), "{name}".to_string())],
+            exclude_patterns: vec![PatternSpec::new_contains("backup")],
+            ..Default::default()
+        };
+        let scanner = FolderScanner::from_config(&config).unwrap();
+
+        let hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "test_project");
+    }
+
+    #[test]
+    fn test_explain_hit() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("test_project");
+        std::fs::create_dir_all(&project).unwrap();
+
+        let scanner = create_test_scanner();
+        let explanation = scanner.explain(temp_dir.path(), &project).unwrap();
+
+        assert!(explanation.matched_rule.is_some());
+        let compared = explanation.evaluations[0].compared.as_ref().unwrap();
+        assert_eq!(compared.original, "test_project");
+    }
+
+    #[test]
+    fn test_evaluate_single_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("test_project");
+        let other = temp_dir.path().join("other");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+
+        let scanner = create_test_scanner();
+        let hit = scanner.evaluate(temp_dir.path(), &project).unwrap().unwrap();
+        assert_eq!(hit.name, "test_project");
+        assert!(hit.dest_preview.is_some());
+        assert!(scanner.evaluate(temp_dir.path(), &other).unwrap().is_none());
+        assert!(scanner.evaluate(temp_dir.path(), &temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_long_destination_path_is_predicted() {
+        let temp_dir = TempDir::new().unwrap();
+        let deep = temp_dir.path().join("test_project").join("x".repeat(60)).join("y".repeat(60));
+        std::fs::create_dir_all(&deep).unwrap();
+
+        // 移動元は短くても、移動先の長さに配下の深さを足すと MAX_PATH を超える
+        let dest_root = PathBuf::from("/archive").join("d".repeat(150));
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("test*"),
+                dest_root,
+                "{name}".to_string(),
+            ),
+        ];
+        let scanner = FolderScanner::new(rules, ScanOptions::default()).unwrap();
+
+        let hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].path.to_string_lossy().len() < 260);
+        assert!(hits[0].warnings.contains(&Warning::LongPath));
+    }
+
+    #[test]
+    fn test_scan_calculates_sizes_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let folder = temp_dir.path().join("test_folder");
+        std::fs::create_dir_all(folder.join("nested")).unwrap();
+        std::fs::write(folder.join("a.bin"), vec![0u8; 100]).unwrap();
+        std::fs::write(folder.join("nested").join("b.bin"), vec![0u8; 20]).unwrap();
+
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("test*"),
+                PathBuf::from("/archive"),
+                "{name}".to_string(),
+            ),
+        ];
+        let options = ScanOptions {
+            calculate_sizes: true,
+            ..ScanOptions::default()
+        };
+        let scanner = FolderScanner::new(rules, options).unwrap();
+
+        let hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].size_bytes, Some(120));
+    }
+
+    #[test]
+    fn test_scan_reports_progress_and_hits() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("test_a")).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("other").join("test_b")).unwrap();
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let scanner = create_test_scanner().with_progress(Arc::new(move |event: &ScanEvent| {
+            sink.lock().unwrap().push(event.clone());
+        }));
+
+        let hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        let events = events.lock().unwrap();
+
+        let streamed = events.iter().filter(|e| matches!(e, ScanEvent::Hit(_))).count();
+        assert_eq!(streamed, hits.len());
+        match events.last() {
+            Some(ScanEvent::Progress(progress)) => {
+                assert_eq!(progress.hits_found, 2);
+                assert!(progress.dirs_visited >= 3);
+            }
+            other => panic!("expected final progress event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_scan_applies_size_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let large = temp_dir.path().join("test_large");
+        let small = temp_dir.path().join("test_small");
+        std::fs::create_dir_all(&large).unwrap();
+        std::fs::create_dir_all(&small).unwrap();
+        std::fs::write(large.join("data.bin"), vec![0u8; 2048]).unwrap();
+        std::fs::write(small.join("data.bin"), vec![0u8; 16]).unwrap();
+
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("test*"),
+                PathBuf::from("/archive"),
+                "{name}".to_string(),
+            ),
+        ];
+        let mut options = ScanOptions::default();
+        options.filters.min_size = Some(1024);
+        let scanner = FolderScanner::new(rules, options).unwrap();
+
+        let hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "test_large");
+    }
+
+    #[test]
+    fn test_scan_results_are_sorted_by_size() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, bytes) in [("test_a", 16), ("test_b", 2048), ("test_c", 512)] {
+            let dir = temp_dir.path().join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("data.bin"), vec![0u8; bytes]).unwrap();
+        }
+
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("test*"),
+                PathBuf::from("/archive"),
+                "{name}".to_string(),
+            ),
+        ];
+        let options = ScanOptions {
+            calculate_sizes: true,
+            result_order: ResultOrder::Size,
+            ..Default::default()
+        };
+        let scanner = FolderScanner::new(rules, options).unwrap();
+
+        let hits = scanner.scan_roots(&[temp_dir.path().to_path_buf()]).unwrap();
+        let names: Vec<&str> = hits.iter().map(|hit| hit.name.as_str()).collect();
+        assert_eq!(names, ["test_b", "test_c", "test_a"]);
+    }
+
+    #[test]
+    fn test_parallel_subtrees_keep_discovery_order() {
+        let temp_dir = TempDir::new().unwrap();
+        for dir in ["test_a/test_a1/test_deep", "test_a/test_a2", "test_b", "test_c/test_c1"] {
+            std::fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+        }
+
+        let rules = vec![
+            Rule::new(
+                PatternSpec::new_glob("test*"),
+                PathBuf::from("/archive"),
+                "{name}".to_string(),
+            ),
+        ];
+        let roots = [temp_dir.path().to_path_buf()];
+
+        for traversal in [TraversalOrder::DepthFirst, TraversalOrder::BreadthFirst] {
+            let sequential = ScanOptions {
+                traversal,
+                parallel_threads: Some(1),
+                ..Default::default()
+            };
+            let parallel = ScanOptions {
+                traversal,
+                parallel_threads: Some(4),
+                parallel_subtrees: true,
+                ..Default::default()
+            };
+
+            let expected = FolderScanner::new(rules.clone(), sequential).unwrap().scan_roots(&roots).unwrap();
+            let actual = FolderScanner::new(rules.clone(), parallel).unwrap().scan_roots(&roots).unwrap();
+
+            let paths = |hits: &[FolderHit]| hits.iter().map(|hit| hit.path.clone()).collect::<Vec<_>>();
+            assert_eq!(paths(&actual), paths(&expected), "{:?}", traversal);
+            assert_eq!(actual.len(), 7);
+        }
+    }
+
     #[test]
     fn test_drive_extraction() {
         let scanner = create_test_scanner();
@@ -234,4 +1142,25 @@ mod tests {
         let drive = scanner.extract_drive_letter(&path);
         assert_eq!(drive, "C");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rule_stats_are_collected() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["test_a", "test_b", "test_tmp", "other"] {
+            std::fs::create_dir_all(temp_dir.path().join(name)).unwrap();
+        }
+
+        let exclude = Rule::new(PatternSpec::new_glob("*tmp").exclude(), PathBuf::new(), String::new());
+        let archive = Rule::new(PatternSpec::new_glob("test*"), PathBuf::from("/archive"), "{name}".to_string());
+        let (exclude_id, archive_id) = (exclude.id, archive.id);
+        let scanner = FolderScanner::new(vec![exclude, archive], ScanOptions::default()).unwrap();
+
+        let outcome = scanner.scan(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(outcome.rule_stats.get(&archive_id).unwrap().folders_matched, 2);
+        assert_eq!(outcome.rule_stats.get(&exclude_id).unwrap().folders_excluded, 1);
+
+        // 次のスキャンでは集計をやり直す
+        let outcome = scanner.scan(&[temp_dir.path().to_path_buf()]).unwrap();
+        assert_eq!(outcome.rule_stats.get(&archive_id).unwrap().folders_matched, 2);
+    }
+}
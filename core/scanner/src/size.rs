@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+use filemover_types::FileMoverError;
+
+/// フォルダサイズのキャッシュ（パス + 更新日時をキーにする）
+///
+/// フォルダの更新日時は直下のエントリの追加・削除・名前変更で変わるため、
+/// キャッシュするのは直下のファイルの合計のみで、サブフォルダは毎回たどる。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SizeCache {
+    entries: HashMap<PathBuf, CachedSize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct CachedSize {
+    modified: SystemTime,
+    file_bytes: u64,
+}
+
+impl SizeCache {
+    pub fn load(path: &Path) -> Result<Self, FileMoverError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| FileMoverError::Scan {
+            path: path.to_path_buf(),
+            message: format!("Invalid size cache: {}", e),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), FileMoverError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string(self).map_err(|e| FileMoverError::Scan {
+            path: path.to_path_buf(),
+            message: format!("Failed to serialize size cache: {}", e),
+        })?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn get(&self, path: &Path, modified: SystemTime) -> Option<u64> {
+        self.entries
+            .get(path)
+            .filter(|cached| cached.modified == modified)
+            .map(|cached| cached.file_bytes)
+    }
+
+    fn insert(&mut self, path: PathBuf, modified: SystemTime, file_bytes: u64) {
+        self.entries.insert(path, CachedSize { modified, file_bytes });
+    }
+}
+
+/// サブフォルダを rayon で並列にたどってフォルダサイズを計算する
+pub struct DirectorySizeCalculator {
+    cache: Mutex<SizeCache>,
+    cache_path: Option<PathBuf>,
+}
+
+impl DirectorySizeCalculator {
+    /// キャッシュをメモリ上にだけ持つ計算器
+    pub fn new() -> Self {
+        Self {
+            cache: Mutex::new(SizeCache::default()),
+            cache_path: None,
+        }
+    }
+
+    /// キャッシュファイルを読み込んだ計算器（読めない場合は空のキャッシュで開始）
+    pub fn with_cache_file(cache_path: PathBuf) -> Self {
+        let cache = SizeCache::load(&cache_path).unwrap_or_else(|e| {
+            warn!("Ignoring size cache {}: {}", cache_path.display(), e);
+            SizeCache::default()
+        });
+        debug!("Loaded {} cached folder sizes", cache.len());
+
+        Self {
+            cache: Mutex::new(cache),
+            cache_path: Some(cache_path),
+        }
+    }
+
+    pub fn directory_size(&self, dir: &Path) -> Result<u64, FileMoverError> {
+        let modified = std::fs::symlink_metadata(dir)?.modified()?;
+
+        let mut subdirs = Vec::new();
+        let cached = self.cache.lock().unwrap().get(dir, modified);
+        let file_bytes = match cached {
+            Some(bytes) => {
+                for entry in std::fs::read_dir(dir)? {
+                    let entry = entry?;
+                    if entry.file_type()?.is_dir() {
+                        subdirs.push(entry.path());
+                    }
+                }
+                bytes
+            }
+            None => {
+                let mut bytes = 0;
+                for entry in std::fs::read_dir(dir)? {
+                    let entry = entry?;
+                    let file_type = entry.file_type()?;
+                    if file_type.is_dir() {
+                        subdirs.push(entry.path());
+                    } else if file_type.is_file() {
+                        bytes += entry.metadata()?.len();
+                    }
+                }
+                self.cache.lock().unwrap().insert(dir.to_path_buf(), modified, bytes);
+                bytes
+            }
+        };
+
+        // アクセスできないサブフォルダは 0 として扱い、全体の計算は続ける
+        let subdir_bytes: u64 = subdirs
+            .par_iter()
+            .map(|subdir| {
+                self.directory_size(subdir).unwrap_or_else(|e| {
+                    warn!("Failed to calculate size of {}: {}", subdir.display(), e);
+                    0
+                })
+            })
+            .sum();
+
+        Ok(file_bytes + subdir_bytes)
+    }
+
+    /// キャッシュファイルが設定されていれば書き出す
+    pub fn save_cache(&self) -> Result<(), FileMoverError> {
+        match &self.cache_path {
+            Some(path) => self.cache.lock().unwrap().save(path),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for DirectorySizeCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_tree(root: &Path) {
+        std::fs::create_dir_all(root.join("a").join("b")).unwrap();
+        std::fs::write(root.join("top.txt"), "12345").unwrap();
+        std::fs::write(root.join("a").join("one.txt"), "123").unwrap();
+        std::fs::write(root.join("a").join("b").join("two.txt"), "12").unwrap();
+    }
+
+    #[test]
+    fn test_directory_size() {
+        let temp_dir = TempDir::new().unwrap();
+        create_tree(temp_dir.path());
+
+        let calculator = DirectorySizeCalculator::new();
+        assert_eq!(calculator.directory_size(temp_dir.path()).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_size_cache_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("tree");
+        create_tree(&root);
+        let cache_path = temp_dir.path().join("cache").join("sizes.json");
+
+        let calculator = DirectorySizeCalculator::with_cache_file(cache_path.clone());
+        assert_eq!(calculator.directory_size(&root).unwrap(), 10);
+        calculator.save_cache().unwrap();
+
+        let cache = SizeCache::load(&cache_path).unwrap();
+        assert_eq!(cache.len(), 3);
+
+        let reloaded = DirectorySizeCalculator::with_cache_file(cache_path);
+        assert_eq!(reloaded.directory_size(&root).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_stale_cache_entry_is_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        create_tree(temp_dir.path());
+
+        let calculator = DirectorySizeCalculator::new();
+        let modified = std::fs::metadata(temp_dir.path()).unwrap().modified().unwrap();
+        let stale = modified - std::time::Duration::from_secs(60);
+        calculator.cache.lock().unwrap().insert(temp_dir.path().to_path_buf(), stale, 999);
+
+        assert_eq!(calculator.directory_size(temp_dir.path()).unwrap(), 10);
+    }
+}
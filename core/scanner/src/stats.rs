@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use filemover_types::FolderHit;
+
+/// 1 つのルールについてのスキャン中の集計
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleHitStats {
+    /// 移動対象として採用されたフォルダ数
+    pub folders_matched: u64,
+    /// 採用されたフォルダのサイズの合計（サイズが分からないものは含まない）
+    pub total_bytes: u64,
+    /// 除外ルールとして対象外にしたフォルダ数
+    pub folders_excluded: u64,
+}
+
+/// ルールごとの集計
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleStats {
+    rules: HashMap<Uuid, RuleHitStats>,
+}
+
+impl RuleStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ヒットから移動対象の集計だけを作る（除外数は分からない）
+    pub fn from_hits<'a>(hits: impl IntoIterator<Item = &'a FolderHit>) -> Self {
+        let mut stats = Self::new();
+        for hit in hits {
+            stats.record_hit(hit);
+        }
+        stats
+    }
+
+    pub fn record_hit(&mut self, hit: &FolderHit) {
+        if let Some(rule_id) = hit.matched_rule {
+            let stats = self.rules.entry(rule_id).or_default();
+            stats.folders_matched += 1;
+            stats.total_bytes += hit.size_bytes.unwrap_or(0);
+        }
+    }
+
+    pub fn record_excluded(&mut self, rule_id: Uuid) {
+        self.rules.entry(rule_id).or_default().folders_excluded += 1;
+    }
+
+    /// ヒットの採用ルールを from から今の matched_rule に付け替えた分を反映する
+    pub fn reassign(&mut self, hit: &FolderHit, from: Uuid) {
+        if hit.matched_rule == Some(from) {
+            return;
+        }
+        if let Some(stats) = self.rules.get_mut(&from) {
+            stats.folders_matched = stats.folders_matched.saturating_sub(1);
+            stats.total_bytes = stats.total_bytes.saturating_sub(hit.size_bytes.unwrap_or(0));
+        }
+        self.record_hit(hit);
+    }
+
+    pub fn get(&self, rule_id: &Uuid) -> Option<&RuleHitStats> {
+        self.rules.get(rule_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Uuid, &RuleHitStats)> {
+        self.rules.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
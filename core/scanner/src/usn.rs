@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::path::PathBuf;
+use std::ptr;
+use tracing::{debug, warn};
+use winapi::shared::minwindef::{DWORD, LPVOID};
+use winapi::shared::winerror::{ERROR_HANDLE_EOF, ERROR_JOURNAL_DELETE_IN_PROGRESS, ERROR_JOURNAL_NOT_ACTIVE};
+use winapi::um::errhandlingapi::GetLastError;
+use winapi::um::fileapi::{CreateFileW, GetFinalPathNameByHandleW, OPEN_EXISTING};
+use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+use winapi::um::ioapiset::DeviceIoControl;
+use winapi::um::winbase::{OpenFileById, FILE_FLAG_BACKUP_SEMANTICS, FILE_ID_DESCRIPTOR};
+use winapi::um::winioctl::{
+    FSCTL_ENUM_USN_DATA, FSCTL_QUERY_USN_JOURNAL, FSCTL_READ_USN_JOURNAL, MFT_ENUM_DATA_V0,
+    READ_USN_JOURNAL_DATA_V0, USN_JOURNAL_DATA_V0, USN_RECORD_V2,
+};
+use winapi::um::winnt::{
+    FILE_ATTRIBUTE_DIRECTORY, FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE, GENERIC_READ, HANDLE,
+    USN_REASON_FILE_CREATE, USN_REASON_FILE_DELETE, USN_REASON_RENAME_NEW_NAME, USN_REASON_RENAME_OLD_NAME,
+};
+use filemover_types::FileMoverError;
+use crate::incremental::{ChangeJournal, ChangeSet, VolumeCursor};
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+const STRUCTURE_CHANGE: DWORD = USN_REASON_FILE_CREATE
+    | USN_REASON_FILE_DELETE
+    | USN_REASON_RENAME_OLD_NAME
+    | USN_REASON_RENAME_NEW_NAME;
+
+/// NTFS の USN 変更ジャーナル（ボリュームを開くため管理者権限が必要）
+#[derive(Debug, Default)]
+pub struct UsnChangeJournal;
+
+impl UsnChangeJournal {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ChangeJournal for UsnChangeJournal {
+    fn current_cursor(&self, volume: &str) -> Result<VolumeCursor, FileMoverError> {
+        let handle = VolumeHandle::open(volume)?;
+        let data = handle.query_journal()?;
+        Ok(VolumeCursor {
+            journal_id: data.UsnJournalID,
+            next_usn: data.NextUsn,
+        })
+    }
+
+    fn changes_since(&self, volume: &str, cursor: &VolumeCursor) -> Result<Option<ChangeSet>, FileMoverError> {
+        let handle = VolumeHandle::open(volume)?;
+        let data = handle.query_journal()?;
+
+        // ジャーナルが作り直された、または古い記録が上書きされた場合は差分を出せない
+        if data.UsnJournalID != cursor.journal_id || cursor.next_usn < data.FirstUsn {
+            debug!("USN journal on {} no longer covers the previous scan", volume);
+            return Ok(None);
+        }
+
+        let mut changes = ChangeSet::default();
+        let mut resolver = PathResolver::new(&handle);
+        let mut next_usn = cursor.next_usn;
+
+        while next_usn < data.NextUsn {
+            let records = handle.read_records(next_usn, data.UsnJournalID)?;
+            let Some((following, records)) = records else {
+                break;
+            };
+
+            for record in records {
+                let Some(parent) = resolver.resolve(record.parent_reference) else {
+                    continue;
+                };
+
+                changes.touched_dirs.insert(parent.clone());
+                if record.is_directory() && record.reason & STRUCTURE_CHANGE != 0 {
+                    changes.replaced_dirs.insert(parent.join(&record.name));
+                }
+            }
+
+            if following == next_usn {
+                break;
+            }
+            next_usn = following;
+        }
+
+        debug!(
+            "USN journal on {}: {} touched, {} replaced folders",
+            volume,
+            changes.touched_dirs.len(),
+            changes.replaced_dirs.len()
+        );
+        Ok(Some(changes))
+    }
+}
+
+pub(crate) struct UsnRecord {
+    pub file_reference: u64,
+    pub parent_reference: u64,
+    pub reason: DWORD,
+    pub attributes: DWORD,
+    pub name: OsString,
+}
+
+impl UsnRecord {
+    pub fn is_directory(&self) -> bool {
+        self.attributes & FILE_ATTRIBUTE_DIRECTORY != 0
+    }
+}
+
+pub(crate) struct VolumeHandle {
+    handle: HANDLE,
+    volume: String,
+}
+
+impl VolumeHandle {
+    pub fn open(volume: &str) -> Result<Self, FileMoverError> {
+        let device: Vec<u16> = OsString::from(format!("\\\\.\\{}", volume.trim_end_matches('\\')))
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+
+        let handle = unsafe {
+            CreateFileW(
+                device.as_ptr(),
+                GENERIC_READ,
+                FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+                ptr::null_mut(),
+                OPEN_EXISTING,
+                0,
+                ptr::null_mut(),
+            )
+        };
+
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(usn_error(volume, format!("Failed to open volume (error {})", unsafe { GetLastError() })));
+        }
+
+        Ok(Self {
+            handle,
+            volume: volume.to_string(),
+        })
+    }
+
+    fn query_journal(&self) -> Result<USN_JOURNAL_DATA_V0, FileMoverError> {
+        let mut data: USN_JOURNAL_DATA_V0 = unsafe { std::mem::zeroed() };
+        let mut returned: DWORD = 0;
+
+        let ok = unsafe {
+            DeviceIoControl(
+                self.handle,
+                FSCTL_QUERY_USN_JOURNAL,
+                ptr::null_mut(),
+                0,
+                &mut data as *mut _ as LPVOID,
+                std::mem::size_of::<USN_JOURNAL_DATA_V0>() as DWORD,
+                &mut returned,
+                ptr::null_mut(),
+            )
+        };
+
+        if ok == 0 {
+            let error = unsafe { GetLastError() };
+            let message = match error {
+                ERROR_JOURNAL_NOT_ACTIVE => "USN journal is not active on this volume".to_string(),
+                ERROR_JOURNAL_DELETE_IN_PROGRESS => "USN journal is being deleted".to_string(),
+                _ => format!("FSCTL_QUERY_USN_JOURNAL failed with error: {}", error),
+            };
+            return Err(usn_error(&self.volume, message));
+        }
+
+        Ok(data)
+    }
+
+    /// start_usn 以降のレコードを 1 バッファ分読む。戻り値の USN から続きを読める
+    fn read_records(&self, start_usn: i64, journal_id: u64) -> Result<Option<(i64, Vec<UsnRecord>)>, FileMoverError> {
+        let mut request = READ_USN_JOURNAL_DATA_V0 {
+            StartUsn: start_usn,
+            ReasonMask: 0xFFFF_FFFF,
+            ReturnOnlyOnClose: 0,
+            Timeout: 0,
+            BytesToWaitFor: 0,
+            UsnJournalID: journal_id,
+        };
+
+        let records = self.control_records(FSCTL_READ_USN_JOURNAL, "FSCTL_READ_USN_JOURNAL", &mut request)?;
+        Ok(records.map(|(next, records)| (next as i64, records)))
+    }
+
+    /// MFT 上の全レコードを start 以降から 1 バッファ分列挙する。戻り値の参照番号から続きを読める
+    pub(crate) fn enum_records(&self, start_reference: u64) -> Result<Option<(u64, Vec<UsnRecord>)>, FileMoverError> {
+        let mut request = MFT_ENUM_DATA_V0 {
+            StartFileReferenceNumber: start_reference,
+            LowUsn: 0,
+            HighUsn: i64::MAX,
+        };
+
+        self.control_records(FSCTL_ENUM_USN_DATA, "FSCTL_ENUM_USN_DATA", &mut request)
+    }
+
+    /// USN_RECORD_V2 の並びを返す FSCTL を呼び出す。終端に達した場合は None
+    fn control_records<T>(&self, code: DWORD, name: &str, request: &mut T) -> Result<Option<(u64, Vec<UsnRecord>)>, FileMoverError> {
+        let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+        let mut returned: DWORD = 0;
+
+        let ok = unsafe {
+            DeviceIoControl(
+                self.handle,
+                code,
+                request as *mut T as LPVOID,
+                std::mem::size_of::<T>() as DWORD,
+                buffer.as_mut_ptr() as LPVOID,
+                buffer.len() as DWORD,
+                &mut returned,
+                ptr::null_mut(),
+            )
+        };
+
+        if ok == 0 {
+            let error = unsafe { GetLastError() };
+            if error == ERROR_HANDLE_EOF {
+                return Ok(None);
+            }
+            return Err(usn_error(&self.volume, format!("{} failed with error: {}", name, error)));
+        }
+
+        // 先頭 8 バイトは続きを読むための位置、その後に USN_RECORD_V2 が並ぶ
+        let returned = returned as usize;
+        if returned < std::mem::size_of::<u64>() {
+            return Ok(None);
+        }
+        let next = u64::from_le_bytes(buffer[..8].try_into().unwrap());
+
+        let mut records = Vec::new();
+        let mut offset = std::mem::size_of::<u64>();
+        while offset + std::mem::size_of::<USN_RECORD_V2>() <= returned {
+            let record = unsafe { &*(buffer.as_ptr().add(offset) as *const USN_RECORD_V2) };
+            if record.RecordLength == 0 {
+                break;
+            }
+
+            // V3 以降のレコードはファイル参照番号が 128bit なので扱わない
+            if record.MajorVersion == 2 {
+                let name_start = offset + record.FileNameOffset as usize;
+                let name_len = record.FileNameLength as usize / 2;
+                let name = unsafe {
+                    std::slice::from_raw_parts(buffer.as_ptr().add(name_start) as *const u16, name_len)
+                };
+
+                records.push(UsnRecord {
+                    file_reference: record.FileReferenceNumber,
+                    parent_reference: record.ParentFileReferenceNumber,
+                    reason: record.Reason,
+                    attributes: record.FileAttributes,
+                    name: OsString::from_wide(name),
+                });
+            }
+
+            offset += record.RecordLength as usize;
+        }
+
+        Ok(Some((next, records)))
+    }
+}
+
+impl Drop for VolumeHandle {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.handle) };
+    }
+}
+
+/// ファイル参照番号からフォルダのパスを引く（結果はキャッシュする）
+struct PathResolver<'a> {
+    volume: &'a VolumeHandle,
+    cache: HashMap<u64, Option<PathBuf>>,
+}
+
+impl<'a> PathResolver<'a> {
+    fn new(volume: &'a VolumeHandle) -> Self {
+        Self {
+            volume,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn resolve(&mut self, reference: u64) -> Option<PathBuf> {
+        if let Some(path) = self.cache.get(&reference) {
+            return path.clone();
+        }
+
+        let path = self.open_by_id(reference);
+        if path.is_none() {
+            // 削除済みのフォルダなど。親ごと消えていれば上位の変更で拾える
+            warn!("Could not resolve file reference {:#x} on {}", reference, self.volume.volume);
+        }
+        self.cache.insert(reference, path.clone());
+        path
+    }
+
+    fn open_by_id(&self, reference: u64) -> Option<PathBuf> {
+        let mut descriptor: FILE_ID_DESCRIPTOR = unsafe { std::mem::zeroed() };
+        descriptor.dwSize = std::mem::size_of::<FILE_ID_DESCRIPTOR>() as DWORD;
+        descriptor.Type = 0; // FileIdType
+        unsafe {
+            *descriptor.u.FileId_mut().QuadPart_mut() = reference as i64;
+        }
+
+        let handle = unsafe {
+            OpenFileById(
+                self.volume.handle,
+                &mut descriptor,
+                0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+                ptr::null_mut(),
+                FILE_FLAG_BACKUP_SEMANTICS,
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; 32 * 1024];
+        let len = unsafe { GetFinalPathNameByHandleW(handle, buffer.as_mut_ptr(), buffer.len() as DWORD, 0) };
+        unsafe { CloseHandle(handle) };
+
+        if len == 0 || len as usize >= buffer.len() {
+            return None;
+        }
+
+        let path = OsString::from_wide(&buffer[..len as usize]).into_string().ok()?;
+        // スキャン結果のパスと比較できるよう \\?\ 形式を通常の形式に戻す
+        let path = match path.strip_prefix("\\\\?\\UNC\\") {
+            Some(rest) => format!("\\\\{}", rest),
+            None => path.strip_prefix("\\\\?\\").unwrap_or(&path).to_string(),
+        };
+        Some(PathBuf::from(path))
+    }
+}
+
+fn usn_error(volume: &str, message: String) -> FileMoverError {
+    FileMoverError::Scan {
+        path: PathBuf::from(volume),
+        message,
+    }
+}
@@ -1,18 +1,49 @@
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
-use filemover_types::{ScanOptions, FileMoverError};
+use filemover_types::{CancellationToken, ScanBackend, ScanOptions, FileMoverError, TraversalOrder};
+use std::sync::Arc;
+use rayon::prelude::*;
 use crate::scanner::DirectoryEntry;
+use crate::progress::ProgressReporter;
+use crate::filter::{EntryAttributes, EntryFilter};
+#[cfg(not(windows))]
+use crate::filter::is_dot_name;
+use crate::exclude::ExclusionSet;
+use crate::file_id::VisitedDirs;
+use crate::cloud::is_placeholder_entry;
 
 #[cfg(windows)]
 use crate::windows_scanner::WindowsDirectoryWalker;
+#[cfg(windows)]
+use crate::mft::MftDirectoryWalker;
 
 pub struct DirectoryWalker {
     options: ScanOptions,
+    exclusions: ExclusionSet,
+    progress: Arc<ProgressReporter>,
+    cancel_token: CancellationToken,
 }
 
 impl DirectoryWalker {
     pub fn new(options: ScanOptions) -> Self {
-        Self { options }
+        Self {
+            exclusions: ExclusionSet::for_walker(&options),
+            options,
+            progress: Arc::new(ProgressReporter::default()),
+            cancel_token: CancellationToken::new(),
+        }
+    }
+
+    /// キャンセルされると走査を打ち切り、それまでに見つけたエントリを返す
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = token;
+        self
+    }
+
+    pub(crate) fn with_progress(mut self, progress: Arc<ProgressReporter>) -> Self {
+        self.progress = progress;
+        self
     }
 
     pub fn walk(&self, root: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
@@ -24,10 +55,45 @@ impl DirectoryWalker {
             return Ok(vec![]);
         }
 
+        let mut entries = self.walk_backend(root)?;
+        self.apply_filters(&mut entries);
+        count_items(&mut entries);
+        Ok(entries)
+    }
+
+    fn walk_backend(&self, root: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
+        #[cfg(windows)]
+        if self.options.backend == ScanBackend::Mft {
+            let walker = MftDirectoryWalker::new(self.options.clone())
+                .with_cancellation(self.cancel_token.clone());
+            match walker.walk(root) {
+                Ok(mut entries) => {
+                    // MFT はまとめて読むので、幅優先の場合は深さで並べ直す
+                    if self.options.traversal == TraversalOrder::BreadthFirst {
+                        entries.sort_by_key(|entry| entry.path.components().count());
+                    }
+                    return Ok(entries);
+                }
+                // 管理者権限がない・NTFS でない場合など
+                Err(e) => warn!("MFT enumeration unavailable, falling back to standard scan: {}", e),
+            }
+        }
+
+        #[cfg(not(windows))]
+        if self.options.backend == ScanBackend::Mft {
+            debug!("MFT enumeration is only available on Windows, using standard scan");
+        }
+
+        if self.options.traversal == TraversalOrder::BreadthFirst {
+            return Ok(self.walk_breadth_first(root));
+        }
+
         // プラットフォーム固有のウォーカーを使用
         #[cfg(windows)]
         {
-            let walker = WindowsDirectoryWalker::new(self.options.clone());
+            let walker = WindowsDirectoryWalker::new(self.options.clone())
+                .with_cancellation(self.cancel_token.clone())
+                .with_progress(self.progress.clone());
             walker.walk(root)
         }
 
@@ -37,37 +103,132 @@ impl DirectoryWalker {
         }
     }
 
+    /// 浅い階層から順にたどる（各フォルダの中は名前順）
+    fn walk_breadth_first(&self, root: &Path) -> Vec<DirectoryEntry> {
+        let max_depth = self.options.max_depth.unwrap_or(u32::MAX);
+        let mut entries = Vec::new();
+        let mut visited = VisitedDirs::default();
+        visited.first_visit(root);
+        let mut queue = VecDeque::from([(root.to_path_buf(), 0u32)]);
+
+        while let Some((dir, depth)) = queue.pop_front() {
+            if self.cancel_token.is_cancelled() {
+                debug!("Walk of {} cancelled after {} entries", root.display(), entries.len());
+                break;
+            }
+            if depth >= max_depth {
+                continue;
+            }
+
+            self.progress.dir_visited(&dir);
+            let children = match self.read_children(&dir) {
+                Ok(children) => children,
+                Err(e) => {
+                    warn!("Failed to read directory {}: {}", dir.display(), e);
+                    continue;
+                }
+            };
+
+            for child in children {
+                let follow = !child.is_junction || self.options.follow_junctions;
+                if child.is_directory && follow {
+                    if visited.first_visit(&child.path) {
+                        queue.push_back((child.path.clone(), depth + 1));
+                    } else {
+                        warn!("Skipping already visited folder (junction cycle): {}", child.path.display());
+                    }
+                }
+                entries.push(child);
+            }
+        }
+
+        entries
+    }
+
     #[cfg(not(windows))]
     fn walk_standard(&self, root: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
         use walkdir::WalkDir;
 
         let mut entries = Vec::new();
-        let walker = WalkDir::new(root)
-            .follow_links(!self.options.follow_junctions)
-            .max_depth(self.options.max_depth.map(|d| d as usize).unwrap_or(usize::MAX));
+        let mut visited = VisitedDirs::default();
+        // ルート自体はエントリにしない（ほかの走査方法と同じ）
+        visited.first_visit(root);
+        self.progress.dir_visited(root);
+        let mut walker = WalkDir::new(root)
+            .follow_links(self.options.follow_junctions)
+            .min_depth(1)
+            .max_depth(self.options.max_depth.map(|d| d as usize).unwrap_or(usize::MAX))
+            .sort_by_file_name()
+            .into_iter()
+            // 除外されたフォルダ・隠しフォルダの中はたどらない（ルート自体は対象外）
+            .filter_entry(|entry| {
+                let hidden = entry.depth() > 0 && is_dot_name(entry.path());
+                !self.is_excluded_path(entry.path()) && !self.options.excludes_attributes(hidden, false)
+            });
+
+        while let Some(entry) = walker.next() {
+            if self.cancel_token.is_cancelled() {
+                debug!("Walk of {} cancelled after {} entries", root.display(), entries.len());
+                break;
+            }
 
-        for entry in walker {
             match entry {
                 Ok(entry) => {
-                    if entry.file_type().is_dir() {
-                        let path = entry.path().to_path_buf();
-                        
-                        // 除外パスのチェック
-                        if self.is_excluded_path(&path) {
-                            continue;
+                    let path = entry.path().to_path_buf();
+
+                    let is_hidden = entry.depth() > 0 && is_dot_name(&path);
+                    // リンクをたどらない場合、フォルダへのリンクは中に入らずジャンクションとして記録する
+                    let is_link_to_dir = entry.path_is_symlink() && path.is_dir();
+                    if entry.file_type().is_dir() || is_link_to_dir {
+                        let is_junction = entry.path_is_symlink();
+                        if self.options.follow_junctions && entry.file_type().is_dir() && !visited.first_visit(&path) {
+                            warn!("Skipping already visited folder (junction cycle): {}", path.display());
+                            walker.skip_current_dir();
+                        } else {
+                            self.progress.dir_visited(&path);
                         }
 
-                        let dir_entry = DirectoryEntry {
+                        entries.push(DirectoryEntry {
                             path,
                             is_directory: true,
-                            is_junction: entry.file_type().is_symlink(),
+                            is_junction,
+                            is_placeholder: false,
+                            is_hidden,
+                            is_system: false,
                             access_denied: false,
                             size_bytes: None, // Unix系では一般的にディレクトリサイズは計算しない
-                        };
-
-                        entries.push(dir_entry);
+                            item_count: None,
+                        });
+                    } else if self.options.include_files && entry.file_type().is_file() {
+                        entries.push(DirectoryEntry {
+                            path,
+                            is_directory: false,
+                            is_junction: false,
+                            is_placeholder: false,
+                            is_hidden,
+                            is_system: false,
+                            access_denied: false,
+                            size_bytes: entry.metadata().ok().map(|m| m.len()),
+                            item_count: None,
+                        });
                     }
                 }
+                // 親フォルダへ戻るリンク。中には入らずジャンクションとして記録する
+                Err(e) if e.loop_ancestor().is_some() => {
+                    let Some(path) = e.path() else { continue };
+                    warn!("Skipping junction cycle: {}", path.display());
+                    entries.push(DirectoryEntry {
+                        path: path.to_path_buf(),
+                        is_directory: true,
+                        is_junction: true,
+                        is_placeholder: false,
+                        is_hidden: is_dot_name(path),
+                        is_system: false,
+                        access_denied: false,
+                        size_bytes: None,
+                        item_count: None,
+                    });
+                }
                 Err(e) => {
                     warn!("Failed to access path: {}", e);
                     continue;
@@ -78,6 +239,93 @@ impl DirectoryWalker {
         Ok(entries)
     }
 
+    /// 直下のエントリだけを列挙する（差分スキャンで変更のあったフォルダを見直す用）
+    pub fn walk_children(&self, dir: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
+        if self.options.system_protections && self.is_protected_path(dir) {
+            return Ok(vec![]);
+        }
+
+        let mut entries = self.read_children(dir)?;
+        self.apply_filters(&mut entries);
+        count_items(&mut entries);
+        Ok(entries)
+    }
+
+    /// 配下をたどる直下のフォルダ（チェックポイント付きスキャンの作業単位）
+    pub(crate) fn child_dirs(&self, dir: &Path) -> Result<Vec<PathBuf>, FileMoverError> {
+        if self.options.system_protections && self.is_protected_path(dir) {
+            return Ok(vec![]);
+        }
+
+        Ok(self.read_children(dir)?
+            .into_iter()
+            .filter(|entry| entry.is_directory && (!entry.is_junction || self.options.follow_junctions))
+            .map(|entry| entry.path)
+            .collect())
+    }
+
+    /// 除外されていない直下のエントリを名前順で返す
+    fn read_children(&self, dir: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if self.is_excluded_path(&path) {
+                continue;
+            }
+
+            let attributes = EntryAttributes::from_dir_entry(&entry);
+            if self.options.excludes_attributes(attributes.hidden, attributes.system) {
+                continue;
+            }
+
+            let file_type = entry.file_type()?;
+            let is_placeholder = is_placeholder_entry(&entry);
+            if file_type.is_dir() || (file_type.is_symlink() && path.is_dir()) {
+                entries.push(DirectoryEntry {
+                    path,
+                    is_directory: true,
+                    is_junction: file_type.is_symlink(),
+                    is_placeholder,
+                    is_hidden: attributes.hidden,
+                    is_system: attributes.system,
+                    access_denied: false,
+                    size_bytes: None,
+                    item_count: None,
+                });
+            } else if self.options.include_files && file_type.is_file() {
+                entries.push(DirectoryEntry {
+                    path,
+                    is_directory: false,
+                    is_junction: false,
+                    is_placeholder,
+                    is_hidden: attributes.hidden,
+                    is_system: attributes.system,
+                    access_denied: false,
+                    size_bytes: entry.metadata().ok().map(|m| m.len()),
+                    item_count: None,
+                });
+            }
+        }
+
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(entries)
+    }
+
+    // 更新日時・属性で絞り込む。フォルダのサイズはヒットに対してのみ scanner 側で判定する
+    fn apply_filters(&self, entries: &mut Vec<DirectoryEntry>) {
+        let filters = &self.options.filters;
+        if !filters.has_metadata_filter() && !filters.has_size_filter() {
+            return;
+        }
+
+        let filter = EntryFilter::new(filters.clone());
+        entries.retain(|entry| {
+            let size_ok = entry.is_directory || filter.matches_size(entry.size_bytes);
+            size_ok && filter.matches_path(&entry.path)
+        });
+    }
+
     fn is_protected_path(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy().to_uppercase();
         
@@ -100,12 +348,14 @@ impl DirectoryWalker {
     }
 
     fn is_excluded_path(&self, path: &Path) -> bool {
-        for excluded in &self.options.excluded_paths {
-            if path.starts_with(excluded) {
-                return true;
-            }
-        }
-        false
+        self.exclusions.is_excluded(path)
+    }
+
+    /// root から path までのどこかが除外されているか（全体スキャンならたどらない場所か）
+    pub(crate) fn is_excluded_below(&self, root: &Path, path: &Path) -> bool {
+        path.ancestors()
+            .take_while(|ancestor| ancestor.starts_with(root) && *ancestor != root)
+            .any(|ancestor| self.is_excluded_path(ancestor))
     }
 }
 
@@ -119,6 +369,18 @@ impl DirectoryWalkerTrait for DirectoryWalker {
     }
 }
 
+/// フォルダ直下の項目数を数える（除外・隠し属性に関係なくすべて数える）
+///
+/// 読めないフォルダと、列挙するとダウンロードが発生しうるプレースホルダは None のままにする。
+fn count_items(entries: &mut [DirectoryEntry]) {
+    entries
+        .par_iter_mut()
+        .filter(|entry| entry.is_directory && !entry.is_placeholder)
+        .for_each(|entry| {
+            entry.item_count = std::fs::read_dir(&entry.path).ok().map(|items| items.count() as u64);
+        });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +406,25 @@ mod tests {
         assert!(!walker.is_excluded_path(Path::new("C:\\Users\\Test")));
     }
 
+    #[test]
+    fn test_walk_counts_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("empty")).unwrap();
+        std::fs::create_dir_all(root.join("project/src")).unwrap();
+        std::fs::write(root.join("project/README.md"), "readme").unwrap();
+
+        let walker = DirectoryWalker::new(ScanOptions::default());
+        let entries = walker.walk(root).unwrap();
+        let count_of = |name: &str| entries.iter()
+            .find(|entry| entry.path == root.join(name))
+            .and_then(|entry| entry.item_count);
+
+        assert_eq!(count_of("empty"), Some(0));
+        assert_eq!(count_of("project"), Some(2));
+        assert_eq!(count_of("project/src"), Some(0));
+    }
+
     #[test]
     fn test_directory_walking() {
         let temp_dir = TempDir::new().unwrap();
@@ -162,5 +443,168 @@ mod tests {
         let paths: Vec<_> = entries.iter().map(|e| &e.path).collect();
         assert!(paths.iter().any(|p| p.ends_with("folder1")));
         assert!(paths.iter().any(|p| p.ends_with("folder2")));
+        assert!(entries.iter().all(|e| e.is_directory));
+    }
+
+    #[test]
+    fn test_walking_with_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("folder1")).unwrap();
+        std::fs::write(root.join("folder1").join("report.txt"), "12345").unwrap();
+
+        let options = ScanOptions {
+            include_files: true,
+            ..ScanOptions::default()
+        };
+        let entries = DirectoryWalker::new(options).walk(root).unwrap();
+
+        let file = entries.iter().find(|e| e.path.ends_with("report.txt")).unwrap();
+        assert!(!file.is_directory);
+        assert_eq!(file.size_bytes, Some(5));
+    }
+
+    #[test]
+    fn test_walk_applies_age_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("folder1")).unwrap();
+
+        let mut options = ScanOptions::default();
+        options.filters.older_than_days = Some(365);
+        let entries = DirectoryWalker::new(options).walk(temp_dir.path()).unwrap();
+
+        // 作成直後のフォルダは 1 年以上前の条件に合わない
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_walk_prunes_excluded_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("app").join("node_modules").join("lib")).unwrap();
+        std::fs::create_dir_all(root.join("app").join("src")).unwrap();
+
+        let options = ScanOptions {
+            excluded_patterns: vec!["**/node_modules".to_string()],
+            ..Default::default()
+        };
+        let walker = DirectoryWalker::new(options);
+        let entries = walker.walk(root).unwrap();
+
+        assert!(entries.iter().any(|e| e.path.ends_with("src")));
+        assert!(!entries.iter().any(|e| e.path.components().any(|c| c.as_os_str() == "node_modules")));
+        assert!(walker.is_excluded_below(root, &root.join("app").join("node_modules").join("lib")));
+    }
+
+    #[test]
+    fn test_breadth_first_walk() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("a").join("deep")).unwrap();
+        std::fs::create_dir_all(root.join("b")).unwrap();
+
+        let options = ScanOptions {
+            traversal: TraversalOrder::BreadthFirst,
+            ..Default::default()
+        };
+        let entries = DirectoryWalker::new(options).walk(root).unwrap();
+        let paths: Vec<PathBuf> = entries.into_iter().map(|e| e.path).collect();
+
+        assert_eq!(paths, [root.join("a"), root.join("b"), root.join("a").join("deep")]);
+    }
+
+    #[test]
+    fn test_traversal_orders_find_the_same_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("Invoices_root");
+        std::fs::create_dir_all(root.join("Invoices_a").join("inner")).unwrap();
+        std::fs::create_dir_all(root.join("other")).unwrap();
+
+        let walk = |traversal| {
+            let options = ScanOptions { traversal, ..Default::default() };
+            let mut paths: Vec<PathBuf> = DirectoryWalker::new(options).walk(&root).unwrap()
+                .into_iter().map(|e| e.path).collect();
+            paths.sort();
+            paths
+        };
+
+        // ルート自体はどちらの順番でも含まれない
+        let depth_first = walk(TraversalOrder::DepthFirst);
+        assert_eq!(depth_first, walk(TraversalOrder::BreadthFirst));
+        assert_eq!(depth_first, [root.join("Invoices_a"), root.join("Invoices_a").join("inner"), root.join("other")]);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_hidden_folders_can_be_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join(".cache").join("inner")).unwrap();
+        std::fs::create_dir_all(root.join("visible")).unwrap();
+
+        let entries = DirectoryWalker::new(ScanOptions::default()).walk(root).unwrap();
+        assert!(entries.iter().any(|e| e.path.ends_with(".cache") && e.is_hidden));
+
+        let options = ScanOptions {
+            include_hidden: false,
+            ..Default::default()
+        };
+        for traversal in [TraversalOrder::DepthFirst, TraversalOrder::BreadthFirst] {
+            let options = ScanOptions { traversal, ..options.clone() };
+            let entries = DirectoryWalker::new(options).walk(root).unwrap();
+            assert!(entries.iter().any(|e| e.path.ends_with("visible")));
+            assert!(!entries.iter().any(|e| e.path.starts_with(root.join(".cache"))));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_following_links_stops_at_cycles() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("a").join("b")).unwrap();
+        std::os::unix::fs::symlink(root.join("a"), root.join("a").join("b").join("back")).unwrap();
+        std::os::unix::fs::symlink(root.join("a").join("b"), root.join("shortcut")).unwrap();
+
+        for traversal in [TraversalOrder::DepthFirst, TraversalOrder::BreadthFirst] {
+            let options = ScanOptions {
+                follow_junctions: true,
+                traversal,
+                ..Default::default()
+            };
+            let entries = DirectoryWalker::new(options).walk(root).unwrap();
+
+            // a/b の中身はどちらか一方の経路からだけ列挙される
+            let backs: Vec<_> = entries.iter().filter(|e| e.path.ends_with("back")).collect();
+            assert_eq!(backs.len(), 1, "{:?}", traversal);
+            assert!(backs[0].is_junction);
+            assert!(!entries.iter().any(|e| e.path.components().count() > backs[0].path.components().count()));
+        }
+    }
+
+    #[test]
+    fn test_cancelled_walk_stops_early() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("folder1")).unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let walker = DirectoryWalker::new(ScanOptions::default()).with_cancellation(token);
+
+        assert!(walker.walk(temp_dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mft_backend_falls_back_to_standard() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("folder1")).unwrap();
+
+        let options = ScanOptions {
+            backend: ScanBackend::Mft,
+            ..ScanOptions::default()
+        };
+        let entries = DirectoryWalker::new(options).walk(temp_dir.path()).unwrap();
+
+        assert!(entries.iter().any(|e| e.path.ends_with("folder1")));
     }
 }
\ No newline at end of file
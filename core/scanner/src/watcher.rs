@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, warn};
+use filemover_types::FileMoverError;
+
+/// 書き込みが止まった新しいフォルダ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettledFolder {
+    /// フォルダを見つけた監視対象のルート
+    pub root: PathBuf,
+    pub path: PathBuf,
+}
+
+/// 新しいフォルダの中身の書き込みが止まるまで待つ
+///
+/// 新しいフォルダの中で変更があるたびに待ち時間をやり直す。既に待っているフォルダの中にできたフォルダは
+/// 別のフォルダとして扱わない（コピー中のフォルダの中身を 1 つずつ移動しないように）。
+#[derive(Debug)]
+pub struct SettleTracker {
+    delay: Duration,
+    /// フォルダ → (ルート, 最後に変更があった時刻)
+    pending: HashMap<PathBuf, (PathBuf, Instant)>,
+}
+
+impl SettleTracker {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// root の中に path のフォルダができた
+    pub fn folder_created(&mut self, root: &Path, path: PathBuf, now: Instant) {
+        if self.touched(&path, now) {
+            return;
+        }
+        self.pending.insert(path, (root.to_path_buf(), now));
+    }
+
+    /// path に変更があった（path を含む待ち中のフォルダがあれば待ち時間をやり直して true を返す）
+    pub fn touched(&mut self, path: &Path, now: Instant) -> bool {
+        let mut found = false;
+        for (folder, (_, last_change)) in self.pending.iter_mut() {
+            if path.starts_with(folder) {
+                *last_change = now;
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// path が削除または移動された（待ち中のフォルダ自身なら待つのをやめる）
+    pub fn removed(&mut self, path: &Path, now: Instant) {
+        if self.pending.remove(path).is_none() {
+            self.touched(path, now);
+        }
+    }
+
+    /// 待ち時間を過ぎたフォルダをパス順に取り出す
+    pub fn take_settled(&mut self, now: Instant) -> Vec<SettledFolder> {
+        let delay = self.delay;
+        let mut settled = Vec::new();
+        self.pending.retain(|path, (root, last_change)| {
+            if now.duration_since(*last_change) >= delay {
+                settled.push(SettledFolder { root: root.clone(), path: path.clone() });
+                false
+            } else {
+                true
+            }
+        });
+        settled.sort_by(|a, b| a.path.cmp(&b.path));
+        settled
+    }
+
+    /// 次にフォルダが落ち着く時刻
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().map(|(_, last_change)| *last_change + self.delay).min()
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+/// ルートを監視して、新しくできたフォルダを書き込みが止まってから返す
+///
+/// Windows では ReadDirectoryChangesW、Linux では inotify を使う（notify クレート）。
+pub struct FolderWatcher {
+    // drop すると監視が止まるので持っておく
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    roots: Vec<PathBuf>,
+    tracker: SettleTracker,
+}
+
+impl FolderWatcher {
+    pub fn new(roots: &[PathBuf], settle_delay: Duration) -> Result<Self, FileMoverError> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|e| watch_error(roots.first().map(PathBuf::as_path).unwrap_or(Path::new("")), e))?;
+        for root in roots {
+            watcher.watch(root, RecursiveMode::Recursive)
+                .map_err(|e| watch_error(root, e))?;
+            debug!("Watching {}", root.display());
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            roots: roots.to_vec(),
+            tracker: SettleTracker::new(settle_delay),
+        })
+    }
+
+    /// 落ち着いたフォルダを最大 timeout だけ待つ（無ければ空を返す）
+    pub fn wait(&mut self, timeout: Duration) -> Result<Vec<SettledFolder>, FileMoverError> {
+        let give_up = Instant::now() + timeout;
+        loop {
+            let now = Instant::now();
+            let settled = self.tracker.take_settled(now);
+            if !settled.is_empty() || now >= give_up {
+                return Ok(settled);
+            }
+
+            let wake = self.tracker.next_deadline().map_or(give_up, |deadline| deadline.min(give_up));
+            match self.events.recv_timeout(wake.saturating_duration_since(now)) {
+                Ok(Ok(event)) => self.handle(event),
+                Ok(Err(e)) => warn!("File system watch error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(FileMoverError::Scan {
+                        path: self.roots.first().cloned().unwrap_or_default(),
+                        message: "File system watcher stopped".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// 待っているフォルダの数
+    pub fn pending_count(&self) -> usize {
+        self.tracker.pending_count()
+    }
+
+    fn handle(&mut self, event: Event) {
+        let now = Instant::now();
+        match event.kind {
+            EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                for path in &event.paths {
+                    self.appeared(path, now);
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                self.tracker.removed(&event.paths[0], now);
+                self.appeared(&event.paths[1], now);
+            }
+            EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                for path in &event.paths {
+                    self.tracker.removed(path, now);
+                }
+            }
+            _ => {
+                for path in &event.paths {
+                    self.tracker.touched(path, now);
+                }
+            }
+        }
+    }
+
+    fn appeared(&mut self, path: &Path, now: Instant) {
+        if !path.is_dir() {
+            self.tracker.touched(path, now);
+            return;
+        }
+        if let Some(root) = self.roots.iter().find(|root| path.starts_with(root) && path != root.as_path()) {
+            self.tracker.folder_created(&root.clone(), path.to_path_buf(), now);
+        }
+    }
+}
+
+/// 設定ファイルなどの変更を監視する
+///
+/// エディタは別名で書いてから置き換えることがあるので、ファイルではなく親フォルダを監視してファイル名で絞る。
+pub struct FileChangeWatcher {
+    // drop すると監視が止まるので持っておく
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    files: Vec<PathBuf>,
+    debounce: Duration,
+}
+
+impl FileChangeWatcher {
+    pub fn new(files: &[PathBuf], debounce: Duration) -> Result<Self, FileMoverError> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|e| watch_error(files.first().map(PathBuf::as_path).unwrap_or(Path::new("")), e))?;
+
+        // イベントは正規化したフォルダのパスで届く
+        let mut watched_dirs: Vec<PathBuf> = Vec::new();
+        let mut targets = Vec::new();
+        for file in files {
+            let Some(name) = file.file_name() else {
+                continue;
+            };
+            let parent = file.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+            let dir = parent.canonicalize().map_err(|e| FileMoverError::Scan {
+                path: parent.to_path_buf(),
+                message: format!("Failed to watch for changes: {}", e),
+            })?;
+            if !watched_dirs.contains(&dir) {
+                watcher.watch(&dir, RecursiveMode::NonRecursive)
+                    .map_err(|e| watch_error(&dir, e))?;
+                debug!("Watching {} for changes", dir.display());
+                watched_dirs.push(dir.clone());
+            }
+            targets.push(dir.join(name));
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            files: targets,
+            debounce,
+        })
+    }
+
+    /// 監視中のファイルが変わったら、変更が debounce の間止まってから true を返す（timeout までに変更が無ければ false）
+    pub fn wait(&mut self, timeout: Duration) -> Result<bool, FileMoverError> {
+        let give_up = Instant::now() + timeout;
+        let mut changed_at: Option<Instant> = None;
+        loop {
+            let now = Instant::now();
+            // 保存が何回かの書き込みに分かれても 1 回の変更として返す
+            let deadline = changed_at.map_or(give_up, |at| at + self.debounce);
+            if now >= deadline {
+                return Ok(changed_at.is_some());
+            }
+
+            match self.events.recv_timeout(deadline - now) {
+                Ok(Ok(event)) => {
+                    if self.is_relevant(&event) {
+                        changed_at = Some(Instant::now());
+                    }
+                }
+                Ok(Err(e)) => warn!("File system watch error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(FileMoverError::Scan {
+                        path: self.files.first().cloned().unwrap_or_default(),
+                        message: "File system watcher stopped".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn is_relevant(&self, event: &Event) -> bool {
+        !matches!(event.kind, EventKind::Access(_))
+            && event.paths.iter().any(|path| self.files.contains(path))
+    }
+}
+
+fn watch_error(path: &Path, error: notify::Error) -> FileMoverError {
+    FileMoverError::Scan {
+        path: path.to_path_buf(),
+        message: format!("Failed to watch for changes: {}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_settles_after_last_change() {
+        let delay = Duration::from_secs(5);
+        let start = Instant::now();
+        let root = PathBuf::from("/watch");
+        let mut tracker = SettleTracker::new(delay);
+
+        tracker.folder_created(&root, root.join("new"), start);
+        // 中にできたフォルダとファイルは別のフォルダとして数えず、待ち時間をやり直す
+        tracker.folder_created(&root, root.join("new/inner"), start + Duration::from_secs(3));
+        tracker.touched(&root.join("new/inner/file.txt"), start + Duration::from_secs(4));
+        assert_eq!(tracker.pending_count(), 1);
+
+        assert!(tracker.take_settled(start + Duration::from_secs(6)).is_empty());
+        assert_eq!(tracker.next_deadline(), Some(start + Duration::from_secs(9)));
+
+        let settled = tracker.take_settled(start + Duration::from_secs(9));
+        assert_eq!(settled, vec![SettledFolder { root: root.clone(), path: root.join("new") }]);
+        assert_eq!(tracker.pending_count(), 0);
+
+        tracker.folder_created(&root, root.join("gone"), start);
+        tracker.removed(&root.join("gone"), start);
+        assert!(tracker.take_settled(start + delay).is_empty());
+    }
+
+    #[test]
+    fn test_watcher_reports_new_folder() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let mut watcher = FolderWatcher::new(std::slice::from_ref(&root), Duration::from_millis(200)).unwrap();
+
+        std::fs::create_dir(root.join("incoming")).unwrap();
+        std::fs::write(root.join("incoming/data.txt"), "data").unwrap();
+
+        let mut settled = Vec::new();
+        let give_up = Instant::now() + Duration::from_secs(10);
+        while settled.is_empty() && Instant::now() < give_up {
+            settled = watcher.wait(Duration::from_secs(1)).unwrap();
+        }
+        assert_eq!(settled, vec![SettledFolder { root: root.clone(), path: root.join("incoming") }]);
+    }
+
+    #[test]
+    fn test_file_change_watcher() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let profile = temp_dir.path().join("work.json");
+        std::fs::write(&profile, "{}").unwrap();
+        let mut watcher = FileChangeWatcher::new(std::slice::from_ref(&profile), Duration::from_millis(100)).unwrap();
+
+        // 同じフォルダの別のファイルは無視する
+        std::fs::write(temp_dir.path().join("other.json"), "{}").unwrap();
+        assert!(!watcher.wait(Duration::from_millis(500)).unwrap());
+
+        // 別名で書いてから置き換える保存も変更として扱う
+        std::fs::write(temp_dir.path().join("work.json.tmp"), "{\"roots\": []}").unwrap();
+        std::fs::rename(temp_dir.path().join("work.json.tmp"), &profile).unwrap();
+        assert!(watcher.wait(Duration::from_secs(10)).unwrap());
+        assert!(!watcher.wait(Duration::from_millis(300)).unwrap());
+    }
+}
@@ -15,27 +15,72 @@ use winapi::um::winnt::*;
 #[cfg(windows)]
 use winapi::shared::winerror::*;
 use tracing::{debug, warn, error};
-use filemover_types::{ScanOptions, FileMoverError};
+use filemover_types::{CancellationToken, ScanOptions, FileMoverError};
 use crate::scanner::DirectoryEntry;
+#[cfg(windows)]
+use std::sync::Arc;
+#[cfg(windows)]
+use crate::progress::ProgressReporter;
+#[cfg(windows)]
+use crate::exclude::ExclusionSet;
+#[cfg(windows)]
+use crate::filter::EntryAttributes;
+#[cfg(windows)]
+use crate::file_id::VisitedDirs;
+#[cfg(windows)]
+use crate::cloud::{is_cloud_reparse_tag, is_placeholder_attributes};
 
 #[cfg(windows)]
 pub struct WindowsDirectoryWalker {
     options: ScanOptions,
+    exclusions: ExclusionSet,
+    progress: Arc<ProgressReporter>,
+    cancel_token: CancellationToken,
 }
 
 #[cfg(windows)]
 impl WindowsDirectoryWalker {
     pub fn new(options: ScanOptions) -> Self {
-        Self { options }
+        Self {
+            exclusions: ExclusionSet::for_walker(&options),
+            options,
+            progress: Arc::new(ProgressReporter::default()),
+            cancel_token: CancellationToken::new(),
+        }
+    }
+
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = token;
+        self
+    }
+
+    pub(crate) fn with_progress(mut self, progress: Arc<ProgressReporter>) -> Self {
+        self.progress = progress;
+        self
     }
 
     pub fn walk(&self, root: &Path) -> Result<Vec<DirectoryEntry>, FileMoverError> {
         let mut entries = Vec::new();
-        self.walk_recursive(root, 0, &mut entries)?;
+        let mut visited = VisitedDirs::default();
+        if self.options.follow_junctions {
+            visited.first_visit(root);
+        }
+        self.walk_recursive(root, 0, &mut entries, &mut visited)?;
         Ok(entries)
     }
 
-    fn walk_recursive(&self, dir: &Path, depth: u32, entries: &mut Vec<DirectoryEntry>) -> Result<(), FileMoverError> {
+    fn walk_recursive(
+        &self,
+        dir: &Path,
+        depth: u32,
+        entries: &mut Vec<DirectoryEntry>,
+        visited: &mut VisitedDirs,
+    ) -> Result<(), FileMoverError> {
+        // キャンセル時はそれまでのエントリを残して打ち切る
+        if self.cancel_token.is_cancelled() {
+            return Ok(());
+        }
+
         // 最大深度チェック
         if let Some(max_depth) = self.options.max_depth {
             if depth > max_depth {
@@ -44,6 +89,7 @@ impl WindowsDirectoryWalker {
         }
 
         debug!("Walking Windows directory at depth {}: {}", depth, dir.display());
+        self.progress.dir_visited(dir);
 
         // 長パス対応のためUNCパス形式に変換
         let search_path = self.to_long_path(dir)?;
@@ -83,8 +129,12 @@ impl WindowsDirectoryWalker {
                         path: dir.to_path_buf(),
                         is_directory: true,
                         is_junction: false,
+                        is_placeholder: false,
+                        is_hidden: false,
+                        is_system: false,
                         access_denied: true,
                         size_bytes: None,
+                        item_count: None,
                     });
                     return Ok(());
                 }
@@ -98,6 +148,10 @@ impl WindowsDirectoryWalker {
         }
 
         loop {
+            if self.cancel_token.is_cancelled() {
+                break;
+            }
+
             let filename = self.wide_string_to_path(&find_data.cFileName)?;
             
             // "." と ".." をスキップ
@@ -110,31 +164,36 @@ impl WindowsDirectoryWalker {
 
             let full_path = dir.join(&filename);
             let is_directory = (find_data.dwFileAttributes & FILE_ATTRIBUTE_DIRECTORY) != 0;
-            let is_junction = (find_data.dwFileAttributes & FILE_ATTRIBUTE_REPARSE_POINT) != 0;
+            // 再解析ポイントのタグは dwReserved0 に入る。クラウドのプレースホルダはジャンクションではない
+            let is_reparse_point = (find_data.dwFileAttributes & FILE_ATTRIBUTE_REPARSE_POINT) != 0;
+            let is_junction = is_reparse_point && !is_cloud_reparse_tag(find_data.dwReserved0);
+            let is_placeholder = is_placeholder_attributes(find_data.dwFileAttributes)
+                || (is_reparse_point && is_cloud_reparse_tag(find_data.dwReserved0));
+            let attributes = EntryAttributes::from_raw(find_data.dwFileAttributes);
+            let skipped = self.is_excluded_path(&full_path)
+                || self.options.excludes_attributes(attributes.hidden, attributes.system);
             
-            // ディレクトリの場合のみ処理
+            // ディレクトリは再帰、ファイルは include_files の場合のみ記録
             if is_directory {
-                // 除外パスのチェック
-                if self.is_excluded_path(&full_path) {
+                // 除外パス・隠し/システム属性のチェック
+                if skipped {
                     if unsafe { FindNextFileW(handle, &mut find_data) } == 0 {
                         break;
                     }
                     continue;
                 }
 
-                // サイズ計算（オプション）
-                let size_bytes = if self.should_calculate_size() {
-                    self.calculate_directory_size(&full_path).ok()
-                } else {
-                    None
-                };
-
+                // サイズはヒットしたフォルダについてのみ scanner 側で計算する
                 let entry = DirectoryEntry {
                     path: full_path.clone(),
                     is_directory: true,
                     is_junction,
+                    is_placeholder,
+                    is_hidden: attributes.hidden,
+                    is_system: attributes.system,
                     access_denied: false,
-                    size_bytes,
+                    size_bytes: None,
+                    item_count: None,
                 };
 
                 entries.push(entry);
@@ -142,13 +201,29 @@ impl WindowsDirectoryWalker {
                 // ジャンクション/シンボリックリンクの追跡オプション
                 if is_junction && !self.options.follow_junctions {
                     debug!("Skipping junction: {}", full_path.display());
+                } else if self.options.follow_junctions && !visited.first_visit(&full_path) {
+                    // 循環しているジャンクション、または別の経路でたどり済みのフォルダ
+                    warn!("Skipping already visited folder (junction cycle): {}", full_path.display());
                 } else {
                     // 再帰的にサブディレクトリを走査
-                    if let Err(e) = self.walk_recursive(&full_path, depth + 1, entries) {
+                    if let Err(e) = self.walk_recursive(&full_path, depth + 1, entries, visited) {
                         warn!("Failed to walk subdirectory {}: {}", full_path.display(), e);
                         // エラーがあっても他のディレクトリの処理を継続
                     }
                 }
+            } else if self.options.include_files && !skipped {
+                let size = ((find_data.nFileSizeHigh as u64) << 32) | find_data.nFileSizeLow as u64;
+                entries.push(DirectoryEntry {
+                    path: full_path,
+                    is_directory: false,
+                    is_junction,
+                    is_placeholder,
+                    is_hidden: attributes.hidden,
+                    is_system: attributes.system,
+                    access_denied: false,
+                    size_bytes: Some(size),
+                    item_count: None,
+                });
             }
 
             if unsafe { FindNextFileW(handle, &mut find_data) } == 0 {
@@ -197,23 +272,7 @@ impl WindowsDirectoryWalker {
     }
 
     fn is_excluded_path(&self, path: &Path) -> bool {
-        for excluded in &self.options.excluded_paths {
-            if path.starts_with(excluded) {
-                return true;
-            }
-        }
-        false
-    }
-
-    fn should_calculate_size(&self) -> bool {
-        // 大量のディレクトリがある場合はサイズ計算をスキップしてパフォーマンスを優先
-        false
-    }
-
-    fn calculate_directory_size(&self, _dir: &Path) -> Result<u64, FileMoverError> {
-        // 実装は複雑になるため、必要に応じて後で実装
-        // 現在は簡単なスタブ
-        Ok(0)
+        self.exclusions.is_excluded(path)
     }
 }
 
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::error::FileMoverError;
+
+/// 走査・プラン生成・実行で共有するキャンセル要求フラグ
+///
+/// クローンは同じフラグを共有するため、UI側で `cancel()` を呼ぶと
+/// 処理側の `is_cancelled()` / `check()` に即座に反映される。
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// キャンセル済みなら `FileMoverError::Cancelled` を返す
+    pub fn check(&self) -> Result<(), FileMoverError> {
+        if self.is_cancelled() {
+            Err(FileMoverError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+/// 調べられない場合の既定（Windows と macOS は区別しない）
+pub fn default_case_sensitive() -> bool {
+    cfg!(not(any(windows, target_os = "macos")))
+}
+
+/// dir の中の名前を大文字小文字を区別して扱うか
+///
+/// dir が存在しなければ最も近い存在する祖先で調べる。中の名前の大文字小文字を入れ替えたパスが
+/// 同じものを指すかで判定するので、大文字小文字を区別する NTFS のディレクトリも見分けられる。
+pub fn is_case_sensitive(dir: &Path) -> bool {
+    let Some(existing) = dir.ancestors().find(|p| p.is_dir()) else {
+        return default_case_sensitive();
+    };
+
+    let children = std::fs::read_dir(existing).into_iter()
+        .flat_map(|entries| entries.flatten().map(|entry| entry.path()).take(32));
+    // 中に名前がなければ、祖先の名前で親ディレクトリを調べる
+    let mut candidates = children.chain(existing.ancestors().map(Path::to_path_buf));
+
+    candidates
+        .find_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let swapped = swap_case(name);
+            (swapped != name).then(|| !same_entry(&path, &path.with_file_name(swapped)))
+        })
+        .unwrap_or_else(default_case_sensitive)
+}
+
+/// 衝突の判定に使うキー（区別しない場合は小文字にそろえる）
+pub fn collision_key(path: &Path, case_sensitive: bool) -> PathBuf {
+    if case_sensitive {
+        path.to_path_buf()
+    } else {
+        PathBuf::from(path.to_string_lossy().to_lowercase())
+    }
+}
+
+fn swap_case(name: &str) -> String {
+    name.chars()
+        .flat_map(|c| {
+            if c.is_lowercase() {
+                c.to_uppercase().collect::<Vec<_>>()
+            } else {
+                c.to_lowercase().collect::<Vec<_>>()
+            }
+        })
+        .collect()
+}
+
+fn same_entry(a: &Path, b: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        match (std::fs::symlink_metadata(a), std::fs::symlink_metadata(b)) {
+            (Ok(x), Ok(y)) => x.dev() == y.dev() && x.ino() == y.ino(),
+            _ => false,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        // 正規化したパスは実際の大文字小文字で返る
+        match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+            (Ok(x), Ok(y)) => x == y,
+            _ => false,
+        }
+    }
+}
@@ -1,14 +1,97 @@
 use std::path::PathBuf;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use crate::rule::{Rule, ConflictPolicy};
-use crate::pattern::NormalizationOptions;
+use crate::pattern::{NormalizationOptions, PatternSpec};
+use crate::message::Locale;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     pub roots: Vec<PathBuf>,
     pub rules: Vec<Rule>,
+    /// 参照する共有ルールファイル（相対パスは設定ディレクトリ基準）。読み込み時に rules の後ろへ統合する
+    #[serde(default)]
+    pub rule_packs: Vec<PathBuf>,
+    /// 一致したフォルダを移動対象から外すパターン（移動ルールより先に評価する）
+    #[serde(default)]
+    pub exclude_patterns: Vec<PatternSpec>,
     pub options: ScanOptions,
     pub profiles: Vec<String>,
+    #[serde(default)]
+    pub journal: JournalSettings,
+    /// 自動リネームで付ける接尾辞
+    #[serde(default)]
+    pub rename_suffix: RenameSuffix,
+    #[serde(default)]
+    pub execution: ExecutionSettings,
+    #[serde(default)]
+    pub log: LogSettings,
+    /// 自動で名前を付けるジャーナルの保存先（未指定ならカレントディレクトリ）
+    #[serde(default)]
+    pub journal_dir: Option<PathBuf>,
+    /// 出力先を指定しないスキャン結果の保存先
+    #[serde(default)]
+    pub scan_output_dir: Option<PathBuf>,
+    /// 出力先を指定しない移動プランの保存先
+    #[serde(default)]
+    pub plan_output_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    /// メッセージの言語（未指定ならシステムの言語）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<Locale>,
+}
+
+/// 他の PC や人と共有するために 1 ファイルにまとめたプロファイル（`.fmprofile`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileBundle {
+    /// 書き出したときのプロファイル名（読み込むときの既定の名前）
+    pub name: String,
+    pub exported_utc: Option<DateTime<Utc>>,
+    pub config: Config,
+}
+
+/// プロファイルの rule_packs から参照する共有ルール集（`*.rules.json`）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RulePack {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// パックの版（表示用）
+    #[serde(default)]
+    pub version: Option<String>,
+    pub rules: Vec<Rule>,
+}
+
+impl Config {
+    /// ルールパックのルールを rules の後ろに統合し、追加した数を返す
+    ///
+    /// 優先度が同じならプロファイル自身のルール、先に並べたパックのルールの順に評価される。
+    /// 既にある ID のルールは追加しないので、同じ ID のルールをプロファイルで無効にすればパックのルールを止められる。
+    pub fn merge_rule_packs(&mut self, packs: &[RulePack]) -> usize {
+        let before = self.rules.len();
+        for rule in packs.iter().flat_map(|pack| &pack.rules) {
+            if !self.rules.iter().any(|existing| existing.id == rule.id) {
+                self.rules.push(rule.clone());
+            }
+        }
+        self.rules.len() - before
+    }
+
+    /// 有効な除外ルール（is_exclude のルール）を exclude_patterns に移し、移した数を返す
+    ///
+    /// 無効な除外ルールは有効/無効を表せないのでそのまま残す。
+    pub fn migrate_exclude_rules(&mut self) -> usize {
+        let (excludes, rules): (Vec<Rule>, Vec<Rule>) = std::mem::take(&mut self.rules)
+            .into_iter()
+            .partition(|rule| rule.enabled && rule.pattern.is_exclude);
+        self.rules = rules;
+
+        let migrated = excludes.len();
+        self.exclude_patterns.extend(
+            excludes.into_iter().map(|rule| PatternSpec { is_exclude: false, ..rule.pattern }),
+        );
+        migrated
+    }
 }
 
 impl Default for Config {
@@ -16,8 +99,101 @@ impl Default for Config {
         Self {
             roots: vec![],
             rules: vec![],
+            rule_packs: vec![],
+            exclude_patterns: vec![],
             options: ScanOptions::default(),
             profiles: vec!["Default".to_string()],
+            journal: JournalSettings::default(),
+            rename_suffix: RenameSuffix::default(),
+            execution: ExecutionSettings::default(),
+            log: LogSettings::default(),
+            journal_dir: None,
+            scan_output_dir: None,
+            plan_output_dir: None,
+            notifications: NotificationSettings::default(),
+            locale: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JournalSettings {
+    pub retention: RetentionPolicy,
+}
+
+/// 古いジャーナルの削除条件（どちらかに該当すれば削除）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    pub keep_last: Option<usize>,
+    pub max_age_days: Option<u32>,
+}
+
+impl RetentionPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.keep_last.is_some() || self.max_age_days.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionSettings {
+    /// 操作が失敗したときの扱い（apply の --on-error で上書きできる）
+    #[serde(default)]
+    pub on_error: ErrorPolicy,
+}
+
+/// デスクトップ通知の設定（GUI でスキャン・実行・Undo が終わったとき）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    /// 完了と失敗を OS の通知で知らせる
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// ログファイルの出力設定（CLI の --log-file で上書きできる）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogSettings {
+    /// ログファイルのパス（未指定ならコンソールのみ）。ローテーション時は日時が名前に入る
+    pub file: Option<PathBuf>,
+    #[serde(default)]
+    pub rotation: LogRotation,
+    /// 残す古いログファイルの数（未指定なら削除しない）
+    #[serde(default)]
+    pub keep_files: Option<usize>,
+}
+
+/// ログファイルを切り替える間隔
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogRotation {
+    Hourly,
+    #[default]
+    Daily,
+    /// 1 つのファイルに追記し続ける
+    Never,
+}
+
+/// 実行中に操作が失敗したときの扱い（失敗した操作はどれもジャーナルに Failed で残る）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorPolicy {
+    /// 残りの操作を続ける
+    #[default]
+    Continue,
+    /// 最初の失敗で止める（済んだ操作はそのまま）
+    Stop,
+    /// 最初の失敗で止め、この実行で済んだ操作を元に戻す
+    RollbackBatch,
+}
+
+impl std::str::FromStr for ErrorPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "continue" => Ok(ErrorPolicy::Continue),
+            "stop" => Ok(ErrorPolicy::Stop),
+            "rollback-batch" => Ok(ErrorPolicy::RollbackBatch),
+            other => Err(format!("Unknown error policy '{}' (expected continue, stop or rollback-batch)", other)),
         }
     }
 }
@@ -29,7 +205,143 @@ pub struct ScanOptions {
     pub system_protections: bool,
     pub max_depth: Option<u32>,
     pub excluded_paths: Vec<PathBuf>,
+    /// 除外パターン（glob。`regex:` で始まるものは正規表現。`/` を含まない glob は名前に対して評価）
+    #[serde(default)]
+    pub excluded_patterns: Vec<String>,
+    /// 走査・照合に使うスレッド数（None は CPU コア数）
     pub parallel_threads: Option<usize>,
+    /// 1 つのルートも直下のフォルダ単位に分けて並列に走査する
+    #[serde(default = "default_true")]
+    pub parallel_subtrees: bool,
+    /// フォルダだけでなく個々のファイルもルールの対象にする
+    #[serde(default)]
+    pub include_files: bool,
+    /// ヒットしたフォルダのサイズを計算する（大きなツリーでは時間がかかる）
+    #[serde(default)]
+    pub calculate_sizes: bool,
+    /// フォルダサイズのキャッシュファイル
+    #[serde(default)]
+    pub size_cache: Option<PathBuf>,
+    #[serde(default)]
+    pub backend: ScanBackend,
+    /// 隠しフォルダ（Windows 以外ではドットで始まる名前）を対象にする
+    #[serde(default = "default_true")]
+    pub include_hidden: bool,
+    /// システム属性のフォルダを対象にする
+    #[serde(default = "default_true")]
+    pub include_system: bool,
+    #[serde(default)]
+    pub traversal: TraversalOrder,
+    /// ヒットの並び順（実行ごとに結果を比較できるようにする）
+    #[serde(default)]
+    pub result_order: ResultOrder,
+    #[serde(default)]
+    pub filters: ScanFilters,
+    /// ネットワーク共有への 1 回の問い合わせを待つ秒数
+    #[serde(default = "default_network_timeout_secs")]
+    pub network_timeout_secs: u64,
+    /// UNC ルートごとの接続情報
+    #[serde(default)]
+    pub network_credentials: Vec<NetworkCredential>,
+    /// 一致するルールをすべて FolderHit.matched_rules に記録する
+    #[serde(default)]
+    pub collect_all_matches: bool,
+    /// 複数のルールに一致したときに使うルールの選び方
+    #[serde(default)]
+    pub tie_break: TieBreakStrategy,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_network_timeout_secs() -> u64 {
+    15
+}
+
+/// UNC ルート（例: `\\server\share`）に接続するときの資格情報
+///
+/// パスワードは設定ファイルに書かず、環境変数の名前で指定する。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkCredential {
+    pub root: PathBuf,
+    pub username: String,
+    /// パスワードを持つ環境変数（未指定ならログオン中のパスワードを使う）
+    #[serde(default)]
+    pub password_env: Option<String>,
+}
+
+/// スキャン対象を絞り込む条件（すべて満たすエントリだけがヒットになる）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanFilters {
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    /// 最終更新からの経過日数がこれ以上
+    pub older_than_days: Option<u32>,
+    /// 最終更新からの経過日数がこれ未満
+    pub newer_than_days: Option<u32>,
+    /// Some(true) なら該当するものだけ、Some(false) なら該当しないものだけ
+    pub hidden: Option<bool>,
+    pub system: Option<bool>,
+    pub readonly: Option<bool>,
+}
+
+impl ScanFilters {
+    pub fn has_size_filter(&self) -> bool {
+        self.min_size.is_some() || self.max_size.is_some()
+    }
+
+    pub fn has_metadata_filter(&self) -> bool {
+        self.older_than_days.is_some()
+            || self.newer_than_days.is_some()
+            || self.hidden.is_some()
+            || self.system.is_some()
+            || self.readonly.is_some()
+    }
+}
+
+/// フォルダ列挙の方法
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScanBackend {
+    /// フォルダを 1 つずつ開いて列挙する
+    #[default]
+    Standard,
+    /// NTFS の MFT を直接読む（Windows・管理者権限が必要。使えない場合は Standard に戻る）
+    Mft,
+}
+
+/// フォルダをたどる順序
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraversalOrder {
+    #[default]
+    DepthFirst,
+    /// 浅い階層から順にたどる（キャンセル時に浅いフォルダが優先して残る）
+    BreadthFirst,
+}
+
+/// スキャン結果の並び順
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResultOrder {
+    /// 見つかった順
+    #[default]
+    Discovery,
+    Path,
+    /// サイズの大きい順（サイズ不明は最後）
+    Size,
+    /// 浅い順
+    Depth,
+}
+
+/// 複数のルールに一致したフォルダのルールの選び方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreakStrategy {
+    /// 優先度の最も高いルール
+    #[default]
+    Priority,
+    /// パターンの最も具体的なルール（同じなら優先度順）
+    MostSpecific,
+    /// 優先度順で仮に決め、利用者が選び直す
+    Interactive,
 }
 
 impl Default for ScanOptions {
@@ -40,12 +352,37 @@ impl Default for ScanOptions {
             system_protections: true,
             max_depth: None,
             excluded_paths: Self::default_excluded_paths(),
+            excluded_patterns: Vec::new(),
             parallel_threads: None,
+            parallel_subtrees: true,
+            include_files: false,
+            calculate_sizes: false,
+            size_cache: None,
+            backend: ScanBackend::default(),
+            include_hidden: true,
+            include_system: true,
+            traversal: TraversalOrder::default(),
+            result_order: ResultOrder::default(),
+            filters: ScanFilters::default(),
+            network_timeout_secs: default_network_timeout_secs(),
+            network_credentials: Vec::new(),
+            collect_all_matches: false,
+            tie_break: TieBreakStrategy::default(),
         }
     }
 }
 
 impl ScanOptions {
+    /// 一致するルールをすべて調べる必要があるか（優先度以外の選び方は全件から選ぶ）
+    pub fn collects_all_matches(&self) -> bool {
+        self.collect_all_matches || self.tie_break != TieBreakStrategy::Priority
+    }
+
+    /// 属性の設定によって走査から外すエントリか（外したフォルダの中はたどらない）
+    pub fn excludes_attributes(&self, hidden: bool, system: bool) -> bool {
+        (hidden && !self.include_hidden) || (system && !self.include_system)
+    }
+
     fn default_excluded_paths() -> Vec<PathBuf> {
         vec![
             PathBuf::from("C:\\Windows"),
@@ -64,6 +401,27 @@ pub struct PlanOptions {
     pub preserve_timestamps: bool,
     pub enable_cross_volume: bool,
     pub dry_run_only: bool,
+    #[serde(default)]
+    pub cloud_placeholders: PlaceholderPolicy,
+    /// Merge でまとめるときに同名のファイルがあった場合（Merge を指定した場合は AutoRename）
+    #[serde(default = "default_merge_file_policy")]
+    pub merge_file_policy: ConflictPolicy,
+    #[serde(default)]
+    pub rename_suffix: RenameSuffix,
+}
+
+fn default_merge_file_policy() -> ConflictPolicy {
+    ConflictPolicy::AutoRename
+}
+
+/// データがローカルにないクラウドのプレースホルダの扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlaceholderPolicy {
+    /// そのまま計画に含める（コピー時にダウンロードされる）
+    #[default]
+    HydrateOnCopy,
+    /// 移動せずスキップする
+    Skip,
 }
 
 impl Default for PlanOptions {
@@ -74,10 +432,71 @@ impl Default for PlanOptions {
             preserve_timestamps: true,
             enable_cross_volume: true,
             dry_run_only: false,
+            cloud_placeholders: PlaceholderPolicy::default(),
+            merge_file_policy: default_merge_file_policy(),
+            rename_suffix: RenameSuffix::default(),
+        }
+    }
+}
+
+/// 自動リネームで名前に付ける接尾辞（n は 1 から数える）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "style", rename_all = "snake_case")]
+pub enum RenameSuffix {
+    /// `name_1`、width を指定すると `name_001` のようにゼロで埋める
+    Numeric {
+        #[serde(default)]
+        width: usize,
+    },
+    /// `name_20240131`、2 つ目からは `name_20240131_2`
+    Date,
+    /// `name (copy 1)`
+    Copy,
+    /// `{name}`・`{n}`・`{date}` を置き換える（`{n}` が必要）
+    Template { template: String },
+}
+
+impl Default for RenameSuffix {
+    fn default() -> Self {
+        Self::Numeric { width: 0 }
+    }
+}
+
+impl RenameSuffix {
+    /// `{n}` の無いテンプレートは何度試しても同じ名前になるのでエラーにする
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Template { template } if !template.contains("{n}") => {
+                Err(format!("Rename template '{}' must contain {{n}}", template))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// n 番目の候補の名前（拡張子を除いた部分）
+    pub fn apply(&self, stem: &str, n: u32, date: chrono::NaiveDate) -> String {
+        let date = date.format("%Y%m%d").to_string();
+        match self {
+            Self::Numeric { width } => format!("{}_{:0width$}", stem, n, width = *width),
+            Self::Date if n == 1 => format!("{}_{}", stem, date),
+            Self::Date => format!("{}_{}_{}", stem, date, n),
+            Self::Copy => format!("{} (copy {})", stem, n),
+            Self::Template { template } => template
+                .replace("{name}", stem)
+                .replace("{n}", &n.to_string())
+                .replace("{date}", &date),
         }
     }
 }
 
+/// スキャンの途中経過
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanProgress {
+    pub dirs_visited: u64,
+    pub hits_found: u64,
+    pub current_path: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Progress {
     pub current_item: Option<String>,
@@ -102,6 +521,31 @@ impl Progress {
         }
     }
 
+    /// 操作が 1 つ終わった。開始からの経過時間で速度と残り時間を計算し直す
+    ///
+    /// 残り時間は合計サイズが分かっていればバイト数から、分からなければ操作数から見積もる。
+    pub fn complete_item(&mut self, item: String, bytes: u64, elapsed: std::time::Duration) {
+        self.current_item = Some(item);
+        self.completed_ops += 1;
+        self.bytes_processed += bytes;
+
+        let seconds = elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return;
+        }
+        self.current_speed = Some((self.bytes_processed as f64 / seconds) as u64);
+        self.eta = match self.total_bytes {
+            Some(total) if self.bytes_processed > 0 => {
+                let remaining = total.saturating_sub(self.bytes_processed) as f64;
+                Some(std::time::Duration::from_secs_f64(remaining * seconds / self.bytes_processed as f64))
+            }
+            _ => {
+                let remaining = self.total_ops.saturating_sub(self.completed_ops) as f64;
+                Some(std::time::Duration::from_secs_f64(remaining * seconds / self.completed_ops as f64))
+            }
+        };
+    }
+
     pub fn percentage(&self) -> f64 {
         if self.total_ops == 0 {
             100.0
@@ -109,4 +553,23 @@ impl Progress {
             (self.completed_ops as f64 / self.total_ops as f64) * 100.0
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_progress_speed_and_eta() {
+        let mut progress = Progress::new(4, Some(400));
+        progress.complete_item("a".to_string(), 100, Duration::from_secs(2));
+        assert_eq!(progress.current_speed, Some(50));
+        assert_eq!(progress.eta, Some(Duration::from_secs(6)));
+
+        // 合計サイズが分からなければ操作数で見積もる
+        let mut progress = Progress::new(4, None);
+        progress.complete_item("a".to_string(), 0, Duration::from_secs(3));
+        assert_eq!(progress.completed_ops, 1);
+        assert_eq!(progress.eta, Some(Duration::from_secs(9)));
+    }
+}
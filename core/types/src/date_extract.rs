@@ -0,0 +1,114 @@
+use chrono::NaiveDate;
+
+/// Rule.date_formats を指定しない場合に試す形式（順に試し、最初に見つかったものを使う）
+pub const DEFAULT_DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y_%m_%d", "%Y.%m.%d", "%Y%m%d", "%Y-%m", "%Y_%m", "%Y"];
+
+/// 名前から取り出した日付（形式に含まれない部分は None）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractedDate {
+    pub year: i32,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+}
+
+enum Token {
+    /// 桁数と種類
+    Digits(usize, Field),
+    Literal(char),
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Year,
+    ShortYear,
+    Month,
+    Day,
+}
+
+/// 形式（`%Y` `%y` `%m` `%d` と文字そのもの）を解析する（それ以外の `%` 指定は None）
+fn parse_format(format: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '%' => match chars.next()? {
+                'Y' => Token::Digits(4, Field::Year),
+                'y' => Token::Digits(2, Field::ShortYear),
+                'm' => Token::Digits(2, Field::Month),
+                'd' => Token::Digits(2, Field::Day),
+                '%' => Token::Literal('%'),
+                _ => return None,
+            },
+            _ => Token::Literal(c),
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+/// 日付の形式として使えるか
+pub fn is_valid_date_format(format: &str) -> bool {
+    parse_format(format).is_some_and(|tokens| tokens.iter().any(|t| matches!(t, Token::Digits(..))))
+}
+
+/// name に含まれる日付を formats の順に探す（formats が空なら DEFAULT_DATE_FORMATS）
+///
+/// 数字の途中からは一致させない（`123456` の一部を年とはみなさない）。年は 1900〜2099 のみ。
+pub fn extract_date<S: AsRef<str>>(name: &str, formats: &[S]) -> Option<ExtractedDate> {
+    let chars: Vec<char> = name.chars().collect();
+    let found = |format: &str| {
+        let tokens = parse_format(format)?;
+        (0..chars.len())
+            .filter(|&start| start == 0 || !chars[start - 1].is_ascii_digit())
+            .find_map(|start| match_at(&chars, start, &tokens))
+    };
+
+    if formats.is_empty() {
+        DEFAULT_DATE_FORMATS.iter().find_map(|format| found(format))
+    } else {
+        formats.iter().find_map(|format| found(format.as_ref()))
+    }
+}
+
+fn match_at(chars: &[char], start: usize, tokens: &[Token]) -> Option<ExtractedDate> {
+    let mut position = start;
+    let (mut year, mut month, mut day) = (None, None, None);
+
+    for token in tokens {
+        match token {
+            Token::Literal(c) => {
+                if chars.get(position) != Some(c) {
+                    return None;
+                }
+                position += 1;
+            }
+            Token::Digits(width, field) => {
+                let digits = chars.get(position..position + width)?;
+                if !digits.iter().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                let value: u32 = digits.iter().collect::<String>().parse().ok()?;
+                match field {
+                    Field::Year => year = Some(value as i32),
+                    Field::ShortYear => year = Some(2000 + value as i32),
+                    Field::Month => month = Some(value),
+                    Field::Day => day = Some(value),
+                }
+                position += width;
+            }
+        }
+    }
+
+    if chars.get(position).is_some_and(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let year = year?;
+    if !(1900..=2099).contains(&year) {
+        return None;
+    }
+    // 月や日だけが範囲外のものは日付ではない
+    NaiveDate::from_ymd_opt(year, month.unwrap_or(1), day.unwrap_or(1))?;
+
+    Some(ExtractedDate { year, month, day })
+}
@@ -21,6 +21,9 @@ pub enum FileMoverError {
     #[error("Undo error: {message}")]
     Undo { message: String },
 
+    #[error("Journal error: {message}")]
+    Journal { message: String },
+
     #[error("Configuration error: {message}")]
     Config { message: String },
 
@@ -36,10 +39,16 @@ pub enum FileMoverError {
     #[error("OneDrive offline: {path}")]
     OneDriveOffline { path: PathBuf },
 
+    #[error("Network path did not respond within {timeout_secs}s: {path}")]
+    NetworkTimeout { path: PathBuf, timeout_secs: u64 },
+
     #[error("Insufficient disk space: {path}")]
     InsufficientSpace { path: PathBuf },
 
     #[error("Invalid node ID: {0}")]
     InvalidNodeId(String),
+
+    #[error("Operation cancelled")]
+    Cancelled,
 }
 
@@ -0,0 +1,103 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::{FileMoverError, FolderHit, MovePlan, ProfileBundle};
+
+/// バージョン付きで保存するファイルの種類
+///
+/// 保存する形式は `{"format_version": N, "data": ...}`。バージョンの無い古いファイル（中身だけのもの）は
+/// バージョン 0 として読み、migrate で 1 つずつ今の形式に上げてから読み込む。
+pub trait VersionedFormat: Serialize + DeserializeOwned {
+    /// エラーメッセージに使う名前
+    const KIND: &'static str;
+    const FORMAT_VERSION: u32;
+
+    /// from_version の data を 1 つ新しいバージョンの形にする
+    fn migrate(data: Value, _from_version: u32) -> Result<Value, FileMoverError> {
+        Ok(data)
+    }
+}
+
+/// v1: エンベロープを付けた（中身は v0 と同じ）
+impl VersionedFormat for MovePlan {
+    const KIND: &'static str = "plan";
+    const FORMAT_VERSION: u32 = 1;
+}
+
+/// v1: エンベロープを付けた（中身は v0 と同じ）
+impl VersionedFormat for Vec<FolderHit> {
+    const KIND: &'static str = "scan results";
+    const FORMAT_VERSION: u32 = 1;
+}
+
+/// v1: 最初の形式（v0 はプロファイルの設定ファイルそのもの）
+impl VersionedFormat for ProfileBundle {
+    const KIND: &'static str = "profile";
+    const FORMAT_VERSION: u32 = 1;
+
+    fn migrate(data: Value, _from_version: u32) -> Result<Value, FileMoverError> {
+        Ok(serde_json::json!({ "name": "", "exported_utc": null, "config": data }))
+    }
+}
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    format_version: u32,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    format_version: u32,
+    data: Value,
+}
+
+pub fn to_versioned_json<T: VersionedFormat>(value: &T, pretty: bool) -> Result<String, FileMoverError> {
+    let envelope = EnvelopeRef {
+        format_version: T::FORMAT_VERSION,
+        data: value,
+    };
+    let json = if pretty {
+        serde_json::to_string_pretty(&envelope)
+    } else {
+        serde_json::to_string(&envelope)
+    };
+    json.map_err(|e| FileMoverError::Config {
+        message: format!("Failed to serialize {}: {}", T::KIND, e),
+    })
+}
+
+/// エンベロープの前半（中身を 1 つずつ書き出す場合は、この後に data の JSON と `}` を続ける）
+pub fn versioned_json_prefix<T: VersionedFormat>() -> String {
+    format!("{{\"format_version\":{},\"data\":", T::FORMAT_VERSION)
+}
+
+pub fn from_versioned_json<T: VersionedFormat>(json: &str) -> Result<T, FileMoverError> {
+    let invalid = |e: serde_json::Error| FileMoverError::Config {
+        message: format!("Invalid {} file: {}", T::KIND, e),
+    };
+    let value: Value = serde_json::from_str(json).map_err(invalid)?;
+
+    let is_envelope = value.as_object()
+        .is_some_and(|object| object.contains_key("format_version") && object.contains_key("data"));
+    let (version, mut data) = if is_envelope {
+        let envelope: Envelope = serde_json::from_value(value).map_err(invalid)?;
+        (envelope.format_version, envelope.data)
+    } else {
+        (0, value)
+    };
+
+    if version > T::FORMAT_VERSION {
+        return Err(FileMoverError::Config {
+            message: format!(
+                "This {} file was created by a newer version of FileMover (format {}, this version reads up to {}); please upgrade",
+                T::KIND, version, T::FORMAT_VERSION
+            ),
+        });
+    }
+    for from_version in version..T::FORMAT_VERSION {
+        data = T::migrate(data, from_version)?;
+    }
+
+    serde_json::from_value(data).map_err(invalid)
+}
@@ -1,8 +1,18 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::error::FileMoverError;
 use crate::plan::OpKind;
 
+/// 現在書き出すジャーナル形式のバージョン
+pub const JOURNAL_FORMAT_VERSION: u32 = 3;
+
+fn journal_v1() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ResultKind {
     Ok,
@@ -10,26 +20,361 @@ pub enum ResultKind {
     Failed,
 }
 
+impl FromStr for ResultKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ok" => Ok(ResultKind::Ok),
+            "skip" | "skipped" => Ok(ResultKind::Skip),
+            "failed" | "fail" => Ok(ResultKind::Failed),
+            other => Err(format!("Unknown result '{}' (expected ok, skip or failed)", other)),
+        }
+    }
+}
+
+/// 操作前のフォルダ状態（Undo で元の状態を復元するために記録）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntryMetadata {
+    pub size_bytes: Option<u64>,
+    pub created: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    pub accessed: Option<DateTime<Utc>>,
+    /// Windows のファイル属性（FILE_ATTRIBUTE_*）
+    pub attributes: Option<u32>,
+    pub readonly: bool,
+    /// アクセス権の要約（Unix は mode:755 の形、Windows は DACL の SDDL）
+    pub acl_summary: Option<String>,
+}
+
+impl EntryMetadata {
+    pub fn capture(path: &Path) -> std::io::Result<Self> {
+        let metadata = std::fs::symlink_metadata(path)?;
+
+        Ok(Self {
+            size_bytes: (!metadata.is_dir()).then_some(metadata.len()),
+            created: metadata.created().ok().map(DateTime::<Utc>::from),
+            modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+            accessed: metadata.accessed().ok().map(DateTime::<Utc>::from),
+            attributes: file_attributes(&metadata),
+            readonly: metadata.permissions().readonly(),
+            acl_summary: acl_summary(path, &metadata),
+        })
+    }
+
+    pub fn with_size(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = Some(size_bytes);
+        self
+    }
+
+    /// 記録したタイムスタンプ・属性・アクセス権を `path` に書き戻す
+    ///
+    /// 属性やアクセス権を記録していない古いジャーナルでは読み取り専用属性だけを戻す。
+    pub fn restore(&self, path: &Path) -> std::io::Result<()> {
+        let mut times = std::fs::FileTimes::new();
+        if let Some(modified) = self.modified {
+            times = times.set_modified(modified.into());
+        }
+        if let Some(accessed) = self.accessed {
+            times = times.set_accessed(accessed.into());
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::FileTimesExt;
+            if let Some(created) = self.created {
+                times = times.set_created(created.into());
+            }
+        }
+
+        open_for_metadata(path)?.set_times(times)?;
+
+        // アクセス権を先に戻す（Unix の mode には読み取り専用も含まれる）
+        let acl_restored = match &self.acl_summary {
+            Some(summary) => restore_acl(path, summary)?,
+            None => false,
+        };
+        let attributes_restored = match self.attributes {
+            Some(attributes) => restore_attributes(path, attributes)?,
+            None => false,
+        };
+        if !acl_restored && !attributes_restored {
+            let mut permissions = std::fs::metadata(path)?.permissions();
+            if permissions.readonly() != self.readonly {
+                permissions.set_readonly(self.readonly);
+                std::fs::set_permissions(path, permissions)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// 読み取り専用のファイルも開けるよう属性の書き込みだけを求める。
+// ディレクトリのハンドルを開くには FILE_FLAG_BACKUP_SEMANTICS が必要
+#[cfg(windows)]
+fn open_for_metadata(path: &Path) -> std::io::Result<std::fs::File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use winapi::um::winbase::FILE_FLAG_BACKUP_SEMANTICS;
+    use winapi::um::winnt::FILE_WRITE_ATTRIBUTES;
+    std::fs::OpenOptions::new()
+        .access_mode(FILE_WRITE_ATTRIBUTES)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)
+}
+
+#[cfg(windows)]
+fn wide_path(path: &Path) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+    path.as_os_str().encode_wide().chain(std::iter::once(0)).collect()
+}
+
+/// SetFileAttributesW で設定できる属性だけを戻す。戻したら true
+#[cfg(windows)]
+fn restore_attributes(path: &Path, attributes: u32) -> std::io::Result<bool> {
+    use winapi::um::fileapi::SetFileAttributesW;
+    use winapi::um::winnt::{
+        FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_NORMAL, FILE_ATTRIBUTE_NOT_CONTENT_INDEXED,
+        FILE_ATTRIBUTE_OFFLINE, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_SYSTEM, FILE_ATTRIBUTE_TEMPORARY,
+    };
+    const SETTABLE: u32 = FILE_ATTRIBUTE_ARCHIVE | FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_NORMAL
+        | FILE_ATTRIBUTE_NOT_CONTENT_INDEXED | FILE_ATTRIBUTE_OFFLINE | FILE_ATTRIBUTE_READONLY
+        | FILE_ATTRIBUTE_SYSTEM | FILE_ATTRIBUTE_TEMPORARY;
+
+    let settable = match attributes & SETTABLE {
+        0 => FILE_ATTRIBUTE_NORMAL,
+        settable => settable,
+    };
+    let wide = wide_path(path);
+    if unsafe { SetFileAttributesW(wide.as_ptr(), settable) } == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(true)
+}
+
+#[cfg(not(windows))]
+fn restore_attributes(_path: &Path, _attributes: u32) -> std::io::Result<bool> {
+    Ok(false)
+}
+
+/// 記録した SDDL の DACL を戻す。この OS の形式でない要約なら何もせず false
+#[cfg(windows)]
+fn restore_acl(path: &Path, summary: &str) -> std::io::Result<bool> {
+    use std::ptr::null_mut;
+    use winapi::um::sddl::ConvertStringSecurityDescriptorToSecurityDescriptorW;
+    use winapi::um::winbase::{LocalFree, SetFileSecurityW};
+    use winapi::um::winnt::{DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR};
+    const SDDL_REVISION_1: u32 = 1;
+
+    let Some(sddl) = summary.strip_prefix("sddl:") else {
+        return Ok(false);
+    };
+    let wide_sddl: Vec<u16> = sddl.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut descriptor: PSECURITY_DESCRIPTOR = null_mut();
+    let converted = unsafe {
+        ConvertStringSecurityDescriptorToSecurityDescriptorW(
+            wide_sddl.as_ptr(),
+            SDDL_REVISION_1,
+            &mut descriptor,
+            null_mut(),
+        )
+    };
+    if converted == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let wide = wide_path(path);
+    let applied = unsafe { SetFileSecurityW(wide.as_ptr(), DACL_SECURITY_INFORMATION, descriptor) };
+    let result = if applied == 0 { Err(std::io::Error::last_os_error()) } else { Ok(true) };
+    unsafe {
+        LocalFree(descriptor);
+    }
+    result
+}
+
+/// 記録した mode を戻す。この OS の形式でない要約なら何もせず false
+#[cfg(not(windows))]
+fn restore_acl(path: &Path, summary: &str) -> std::io::Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+    let Some(mode) = summary.strip_prefix("mode:").and_then(|mode| u32::from_str_radix(mode, 8).ok()) else {
+        return Ok(false);
+    };
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+    Ok(true)
+}
+
+#[cfg(not(windows))]
+fn open_for_metadata(path: &Path) -> std::io::Result<std::fs::File> {
+    std::fs::File::open(path)
+}
+
+#[cfg(windows)]
+fn file_attributes(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::windows::fs::MetadataExt;
+    Some(metadata.file_attributes())
+}
+
+#[cfg(not(windows))]
+fn file_attributes(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+/// DACL を SDDL の文字列にした要約
+#[cfg(windows)]
+fn acl_summary(path: &Path, _metadata: &std::fs::Metadata) -> Option<String> {
+    use std::ptr::null_mut;
+    use winapi::shared::winerror::ERROR_SUCCESS;
+    use winapi::um::accctrl::SE_FILE_OBJECT;
+    use winapi::um::aclapi::GetNamedSecurityInfoW;
+    use winapi::um::sddl::ConvertSecurityDescriptorToStringSecurityDescriptorW;
+    use winapi::um::winbase::LocalFree;
+    use winapi::um::winnt::{DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR};
+    const SDDL_REVISION_1: u32 = 1;
+
+    let wide = wide_path(path);
+    let mut descriptor: PSECURITY_DESCRIPTOR = null_mut();
+    let status = unsafe {
+        GetNamedSecurityInfoW(
+            wide.as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            &mut descriptor,
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+
+    let mut sddl: *mut u16 = null_mut();
+    let mut len: u32 = 0;
+    let ok = unsafe {
+        ConvertSecurityDescriptorToStringSecurityDescriptorW(
+            descriptor,
+            SDDL_REVISION_1,
+            DACL_SECURITY_INFORMATION,
+            &mut sddl,
+            &mut len,
+        )
+    };
+    let summary = (ok != 0 && !sddl.is_null()).then(|| {
+        let chars = unsafe { std::slice::from_raw_parts(sddl, len as usize) };
+        let end = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+        format!("sddl:{}", String::from_utf16_lossy(&chars[..end]))
+    });
+
+    unsafe {
+        if !sddl.is_null() {
+            LocalFree(sddl as _);
+        }
+        LocalFree(descriptor);
+    }
+    summary
+}
+
+#[cfg(not(windows))]
+fn acl_summary(_path: &Path, metadata: &std::fs::Metadata) -> Option<String> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(format!("mode:{:o}", metadata.permissions().mode() & 0o7777))
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct JournalEntry {
+    /// v1 のジャーナルにはこのフィールドが無い
+    #[serde(default = "journal_v1")]
+    pub version: u32,
     pub when_utc: DateTime<Utc>,
     pub source: PathBuf,
     pub dest: PathBuf,
     pub op: OpKind,
     pub result: ResultKind,
     pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<Uuid>,
+    /// プランのノードのタグ
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<EntryMetadata>,
+    /// v3 以降: checksum 自身を除いたエントリの CRC32
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
 }
 
 impl JournalEntry {
     pub fn new(source: PathBuf, dest: PathBuf, op: OpKind) -> Self {
         Self {
+            version: JOURNAL_FORMAT_VERSION,
             when_utc: Utc::now(),
             source,
             dest,
             op,
             result: ResultKind::Ok,
             message: None,
+            rule_id: None,
+            tags: Vec::new(),
+            metadata: None,
+            checksum: None,
+        }
+    }
+
+    /// v1・v2 どちらの形式の行も読み込む
+    pub fn from_json_line(line: &str) -> Result<Self, FileMoverError> {
+        let entry: Self = serde_json::from_str(line).map_err(|e| FileMoverError::Journal {
+            message: format!("Invalid journal entry: {}", e),
+        })?;
+
+        if entry.version > JOURNAL_FORMAT_VERSION {
+            return Err(FileMoverError::Journal {
+                message: format!(
+                    "Journal entry was created by a newer version of FileMover (format {}, this version reads up to {}); please upgrade",
+                    entry.version, JOURNAL_FORMAT_VERSION
+                ),
+            });
         }
+        if entry.version == 0 {
+            return Err(FileMoverError::Journal {
+                message: format!("Unsupported journal format version: {}", entry.version),
+            });
+        }
+
+        Ok(entry)
+    }
+
+    pub fn compute_checksum(&self) -> String {
+        let unsigned = Self {
+            checksum: None,
+            ..self.clone()
+        };
+        let json = serde_json::to_string(&unsigned).unwrap_or_default();
+        format!("{:08x}", crc32fast::hash(json.as_bytes()))
+    }
+
+    pub fn with_checksum(mut self) -> Self {
+        self.checksum = Some(self.compute_checksum());
+        self
+    }
+
+    /// チェックサムの無い（v3 より前の）エントリは None
+    pub fn checksum_matches(&self) -> Option<bool> {
+        self.checksum.as_ref().map(|checksum| *checksum == self.compute_checksum())
+    }
+
+    pub fn with_rule_id(mut self, rule_id: Option<Uuid>) -> Self {
+        self.rule_id = rule_id;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: EntryMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
     }
 
     pub fn with_result(mut self, result: ResultKind) -> Self {
@@ -55,6 +400,166 @@ impl JournalEntry {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalFormat {
+    #[default]
+    Jsonl,
+    Sqlite,
+}
+
+impl JournalFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            JournalFormat::Jsonl => "jsonl",
+            JournalFormat::Sqlite => "db",
+        }
+    }
+}
+
+impl FromStr for JournalFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "jsonl" => Ok(JournalFormat::Jsonl),
+            "sqlite" => Ok(JournalFormat::Sqlite),
+            other => Err(format!("Unknown journal format '{}' (expected jsonl or sqlite)", other)),
+        }
+    }
+}
+
+/// ジャーナル検索条件（未指定の項目は絞り込まない）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JournalQuery {
+    /// source または dest がこのパス配下にあるもの
+    pub path: Option<PathBuf>,
+    pub rule_id: Option<Uuid>,
+    pub tag: Option<String>,
+    pub result: Option<ResultKind>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+}
+
+impl JournalQuery {
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        if let Some(path) = &self.path {
+            if !entry.source.starts_with(path) && !entry.dest.starts_with(path) {
+                return false;
+            }
+        }
+        if self.rule_id.is_some() && entry.rule_id != self.rule_id {
+            return false;
+        }
+        if self.tag.as_ref().is_some_and(|tag| !entry.tags.contains(tag)) {
+            return false;
+        }
+        if self.result.is_some_and(|result| entry.result != result) {
+            return false;
+        }
+        if self.since.is_some_and(|since| entry.when_utc < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| entry.when_utc > until) {
+            return false;
+        }
+        true
+    }
+}
+
+/// ジャーナル末尾に書く完了レコード（途中で切れたジャーナルの検出用）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalSummary {
+    pub entry_count: u64,
+    pub ok_count: u64,
+    pub skip_count: u64,
+    pub failed_count: u64,
+    pub completed_utc: DateTime<Utc>,
+    /// 全エントリのチェックサムを順に連結した CRC32
+    pub digest: String,
+    /// 実行した計画の MovePlan::content_hash（計画と結び付いていないジャーナルには無い）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan_hash: Option<String>,
+}
+
+impl JournalSummary {
+    /// None の場合は元のハッシュを残す
+    pub fn with_plan_hash(mut self, plan_hash: Option<String>) -> Self {
+        if plan_hash.is_some() {
+            self.plan_hash = plan_hash;
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum JournalProblem {
+    /// position は JSONL では行番号、SQLite では行 ID
+    Unreadable { position: usize, message: String },
+    ChecksumMismatch { position: usize },
+    MissingSummary,
+    SummaryMismatch { message: String },
+}
+
+impl std::fmt::Display for JournalProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JournalProblem::Unreadable { position, message } => {
+                write!(f, "Entry {} is unreadable: {}", position, message)
+            }
+            JournalProblem::ChecksumMismatch { position } => {
+                write!(f, "Entry {} failed checksum verification", position)
+            }
+            JournalProblem::MissingSummary => {
+                write!(f, "Journal has no summary record (run interrupted or file truncated)")
+            }
+            JournalProblem::SummaryMismatch { message } => {
+                write!(f, "Journal does not match its summary: {}", message)
+            }
+        }
+    }
+}
+
+/// 整合性チェック付きの読み込み結果（読めたエントリだけを entries に含める）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JournalReadReport {
+    pub entries: Vec<JournalEntry>,
+    pub summary: Option<JournalSummary>,
+    pub problems: Vec<JournalProblem>,
+}
+
+impl JournalReadReport {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// ジャーナルの保存先（JSONL・SQLite など）を差し替えるためのトレイト
+pub trait JournalStore: Send {
+    /// チェックサムを付けて追記する
+    fn append(&mut self, entry: &JournalEntry) -> Result<(), FileMoverError>;
+
+    /// finish で完了レコードに書く計画のハッシュ
+    fn link_plan(&mut self, plan_hash: String);
+
+    /// 完了レコードを書き込む
+    fn finish(&mut self) -> Result<JournalSummary, FileMoverError>;
+
+    /// 壊れたエントリで失敗せず、問題点を集めて返す
+    fn read_checked(&self) -> Result<JournalReadReport, FileMoverError>;
+
+    /// 記録順に条件に合うエントリを返す
+    fn query(&self, query: &JournalQuery) -> Result<Vec<JournalEntry>, FileMoverError>;
+
+    fn entries(&self) -> Result<Vec<JournalEntry>, FileMoverError> {
+        self.query(&JournalQuery::default())
+    }
+
+    fn format(&self) -> JournalFormat;
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UndoResult {
     pub restored_count: u64,
@@ -68,4 +573,133 @@ pub struct FailedRestore {
     pub original_source: PathBuf,
     pub original_dest: PathBuf,
     pub error: String,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_v1_entry() {
+        let line = r#"{"when_utc":"2024-01-01T00:00:00Z","source":"C:\\Src","dest":"D:\\Dst","op":"Move","result":"Ok","message":null}"#;
+
+        let entry = JournalEntry::from_json_line(line).unwrap();
+        assert_eq!(entry.version, 1);
+        assert_eq!(entry.op, OpKind::Move);
+        assert!(entry.metadata.is_none());
+    }
+
+    #[test]
+    fn test_v2_roundtrip_with_metadata() {
+        let metadata = EntryMetadata {
+            readonly: true,
+            attributes: Some(0x10),
+            ..Default::default()
+        }.with_size(42);
+
+        let entry = JournalEntry::new(PathBuf::from("a"), PathBuf::from("b"), OpKind::CopyDelete)
+            .with_metadata(metadata.clone());
+        let line = serde_json::to_string(&entry).unwrap();
+
+        let loaded = JournalEntry::from_json_line(&line).unwrap();
+        assert_eq!(loaded.version, JOURNAL_FORMAT_VERSION);
+        assert_eq!(loaded.metadata, Some(metadata));
+    }
+
+    #[test]
+    fn test_rejects_future_version() {
+        let line = r#"{"version":99,"when_utc":"2024-01-01T00:00:00Z","source":"a","dest":"b","op":"Move","result":"Ok","message":null}"#;
+        let error = JournalEntry::from_json_line(line).unwrap_err();
+        assert!(error.to_string().contains("newer version"));
+    }
+
+    #[test]
+    fn test_checksum_detects_tampering() {
+        let entry = JournalEntry::new(PathBuf::from("a"), PathBuf::from("b"), OpKind::Move).with_checksum();
+        assert_eq!(entry.checksum_matches(), Some(true));
+
+        let mut tampered = entry.clone();
+        tampered.dest = PathBuf::from("c");
+        assert_eq!(tampered.checksum_matches(), Some(false));
+
+        let unsigned = JournalEntry::new(PathBuf::from("a"), PathBuf::from("b"), OpKind::Move);
+        assert_eq!(unsigned.checksum_matches(), None);
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let rule_id = Uuid::new_v4();
+        let entry = JournalEntry::new(PathBuf::from("/src/a"), PathBuf::from("/dst/a"), OpKind::Move)
+            .with_rule_id(Some(rule_id))
+            .with_tags(vec!["photos".to_string()]);
+
+        assert!(JournalQuery::default().matches(&entry));
+        assert!(JournalQuery { path: Some(PathBuf::from("/dst")), ..Default::default() }.matches(&entry));
+        assert!(!JournalQuery { path: Some(PathBuf::from("/other")), ..Default::default() }.matches(&entry));
+        assert!(JournalQuery { rule_id: Some(rule_id), ..Default::default() }.matches(&entry));
+        assert!(!JournalQuery { result: Some(ResultKind::Failed), ..Default::default() }.matches(&entry));
+        assert!(JournalQuery { tag: Some("photos".to_string()), ..Default::default() }.matches(&entry));
+        assert!(!JournalQuery { tag: Some("backups".to_string()), ..Default::default() }.matches(&entry));
+    }
+
+    #[test]
+    fn test_result_kind_from_str() {
+        assert_eq!("OK".parse::<ResultKind>(), Ok(ResultKind::Ok));
+        assert_eq!("skipped".parse::<ResultKind>(), Ok(ResultKind::Skip));
+        assert!("done".parse::<ResultKind>().is_err());
+    }
+
+    #[test]
+    fn test_journal_format_from_str() {
+        assert_eq!("sqlite".parse::<JournalFormat>(), Ok(JournalFormat::Sqlite));
+        assert_eq!("JSONL".parse::<JournalFormat>(), Ok(JournalFormat::Jsonl));
+        assert!("xml".parse::<JournalFormat>().is_err());
+    }
+
+    #[test]
+    fn test_capture_metadata() {
+        let path = std::env::temp_dir().join(format!("filemover_meta_{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "hello").unwrap();
+
+        let metadata = EntryMetadata::capture(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(metadata.size_bytes, Some(5));
+        assert!(metadata.modified.is_some());
+    }
+
+    #[test]
+    fn test_restore_modified_time() {
+        let path = std::env::temp_dir().join(format!("filemover_restore_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir(&path).unwrap();
+
+        let original: DateTime<Utc> = "2020-05-01T12:00:00Z".parse().unwrap();
+        let metadata = EntryMetadata {
+            modified: Some(original),
+            ..Default::default()
+        };
+        metadata.restore(&path).unwrap();
+
+        let restored = EntryMetadata::capture(&path).unwrap();
+        std::fs::remove_dir(&path).unwrap();
+
+        assert_eq!(restored.modified, Some(original));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restore_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir().join(format!("filemover_mode_{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "hello").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+        let metadata = EntryMetadata::capture(&path).unwrap();
+
+        // 移動の途中でアクセス権が変わっても記録した値に戻る
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        metadata.restore(&path).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o7777;
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(mode, 0o640);
+    }
+}
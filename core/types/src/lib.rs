@@ -4,10 +4,24 @@ pub mod rule;
 pub mod plan;
 pub mod journal;
 pub mod config;
+pub mod cancellation;
+pub mod path_length;
+pub mod date_extract;
+pub mod case_sensitivity;
+pub mod template_function;
+pub mod format;
+pub mod message;
 
 pub use error::*;
 pub use pattern::*;
 pub use rule::*;
 pub use plan::*;
 pub use journal::*;
-pub use config::*;
\ No newline at end of file
+pub use config::*;
+pub use cancellation::*;
+pub use path_length::*;
+pub use date_extract::*;
+pub use case_sensitivity::*;
+pub use template_function::*;
+pub use format::*;
+pub use message::*;
\ No newline at end of file
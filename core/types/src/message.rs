@@ -0,0 +1,440 @@
+use serde::{Deserialize, Serialize};
+use crate::error::FileMoverError;
+use crate::journal::JournalProblem;
+use crate::plan::{Conflict, Permission};
+use crate::rule::Warning;
+
+/// 画面やコマンド出力の言語
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Ja];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ja => "ja",
+        }
+    }
+
+    /// "ja"・"ja-JP"・"ja_JP.UTF-8" のような言語タグから選ぶ（対応していない言語は None）
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_', '.']).next()?.to_ascii_lowercase();
+        Locale::ALL.into_iter().find(|locale| locale.code() == language)
+    }
+
+    /// FILEMOVER_LANG・LC_ALL・LC_MESSAGES・LANG の順に調べ、決まらなければ英語
+    pub fn from_env() -> Locale {
+        ["FILEMOVER_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_tag(&value))
+            .unwrap_or_default()
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::from_tag(s).ok_or_else(|| format!("Unknown language '{}' (expected en or ja)", s))
+    }
+}
+
+/// カタログの見出し。文言は catalog で言語ごとに引く
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MessageId {
+    ErrorIo,
+    ErrorPattern,
+    ErrorScan,
+    ErrorPlanValidation,
+    ErrorExecution,
+    ErrorUndo,
+    ErrorJournal,
+    ErrorConfig,
+    ErrorPermissionDenied,
+    ErrorPathNotFound,
+    ErrorLongPathNotSupported,
+    ErrorOneDriveOffline,
+    ErrorNetworkTimeout,
+    ErrorInsufficientSpace,
+    ErrorInvalidNodeId,
+    ErrorCancelled,
+    WarningLongPath,
+    WarningAclDiffers,
+    WarningOffline,
+    WarningAccessDenied,
+    WarningJunction,
+    WarningCrossVolume,
+    WarningNetworkSlow,
+    WarningUnreachable,
+    WarningHidden,
+    WarningSystem,
+    WarningAclNotPreserved,
+    WarningTimestampsDegraded,
+    WarningStreamsNotPreserved,
+    WarningCompressionNotPreserved,
+    ConflictNameExists,
+    ConflictCycleDetected,
+    ConflictDestInsideSource,
+    ConflictNoSpace,
+    ConflictPermissionAdministrator,
+    ConflictPermissionFileSystemWrite,
+    ConflictPermissionNetworkAccess,
+    ConflictSourceMissing,
+    JournalUnreadable,
+    JournalChecksumMismatch,
+    JournalMissingSummary,
+    JournalSummaryMismatch,
+}
+
+impl MessageId {
+    pub const ALL: [MessageId; 42] = [
+        MessageId::ErrorIo,
+        MessageId::ErrorPattern,
+        MessageId::ErrorScan,
+        MessageId::ErrorPlanValidation,
+        MessageId::ErrorExecution,
+        MessageId::ErrorUndo,
+        MessageId::ErrorJournal,
+        MessageId::ErrorConfig,
+        MessageId::ErrorPermissionDenied,
+        MessageId::ErrorPathNotFound,
+        MessageId::ErrorLongPathNotSupported,
+        MessageId::ErrorOneDriveOffline,
+        MessageId::ErrorNetworkTimeout,
+        MessageId::ErrorInsufficientSpace,
+        MessageId::ErrorInvalidNodeId,
+        MessageId::ErrorCancelled,
+        MessageId::WarningLongPath,
+        MessageId::WarningAclDiffers,
+        MessageId::WarningOffline,
+        MessageId::WarningAccessDenied,
+        MessageId::WarningJunction,
+        MessageId::WarningCrossVolume,
+        MessageId::WarningNetworkSlow,
+        MessageId::WarningUnreachable,
+        MessageId::WarningHidden,
+        MessageId::WarningSystem,
+        MessageId::WarningAclNotPreserved,
+        MessageId::WarningTimestampsDegraded,
+        MessageId::WarningStreamsNotPreserved,
+        MessageId::WarningCompressionNotPreserved,
+        MessageId::ConflictNameExists,
+        MessageId::ConflictCycleDetected,
+        MessageId::ConflictDestInsideSource,
+        MessageId::ConflictNoSpace,
+        MessageId::ConflictPermissionAdministrator,
+        MessageId::ConflictPermissionFileSystemWrite,
+        MessageId::ConflictPermissionNetworkAccess,
+        MessageId::ConflictSourceMissing,
+        MessageId::JournalUnreadable,
+        MessageId::JournalChecksumMismatch,
+        MessageId::JournalMissingSummary,
+        MessageId::JournalSummaryMismatch,
+    ];
+}
+
+/// 埋め込む値 {name} を持つ文言。言語は表示するときに選ぶ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    pub id: MessageId,
+    pub args: Vec<(String, String)>,
+}
+
+impl Message {
+    pub fn new(id: MessageId) -> Self {
+        Self { id, args: vec![] }
+    }
+
+    pub fn with_arg(mut self, name: &str, value: impl ToString) -> Self {
+        self.args.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// 値の中の {…} はそのまま残す（テンプレートだけを置き換える）
+    pub fn render(&self, locale: Locale) -> String {
+        let template = catalog(locale, self.id);
+        let mut text = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let value = after.find('}').and_then(|end| {
+                let name = &after[..end];
+                self.args.iter().find(|(arg, _)| arg == name).map(|(_, value)| (value, end))
+            });
+            match value {
+                Some((value, end)) => {
+                    text.push_str(value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    text.push('{');
+                    rest = after;
+                }
+            }
+        }
+        text.push_str(rest);
+        text
+    }
+}
+
+/// 言語ごとのカタログから文言のテンプレートを引く
+pub fn catalog(locale: Locale, id: MessageId) -> &'static str {
+    match locale {
+        Locale::En => english(id),
+        Locale::Ja => japanese(id),
+    }
+}
+
+fn english(id: MessageId) -> &'static str {
+    match id {
+        MessageId::ErrorIo => "IO error: {error}",
+        MessageId::ErrorPattern => "Pattern error: {message}",
+        MessageId::ErrorScan => "Scan error at path {path}: {message}",
+        MessageId::ErrorPlanValidation => "Plan validation error: {message}",
+        MessageId::ErrorExecution => "Execution error at {path}: {message}",
+        MessageId::ErrorUndo => "Undo error: {message}",
+        MessageId::ErrorJournal => "Journal error: {message}",
+        MessageId::ErrorConfig => "Configuration error: {message}",
+        MessageId::ErrorPermissionDenied => "Permission denied: {path}",
+        MessageId::ErrorPathNotFound => "Path not found: {path}",
+        MessageId::ErrorLongPathNotSupported => "Long path not supported: {path}",
+        MessageId::ErrorOneDriveOffline => "OneDrive offline: {path}",
+        MessageId::ErrorNetworkTimeout => "Network path did not respond within {timeout_secs}s: {path}",
+        MessageId::ErrorInsufficientSpace => "Insufficient disk space: {path}",
+        MessageId::ErrorInvalidNodeId => "Invalid node ID: {id}",
+        MessageId::ErrorCancelled => "Operation cancelled",
+        MessageId::WarningLongPath => "Path is longer than 260 characters",
+        MessageId::WarningAclDiffers => "Access permissions differ from the parent folder",
+        MessageId::WarningOffline => "Files are offline (cloud placeholders)",
+        MessageId::WarningAccessDenied => "Some contents could not be read",
+        MessageId::WarningJunction => "Folder is a junction or symbolic link",
+        MessageId::WarningCrossVolume => "Moves to another volume (copied, then deleted)",
+        MessageId::WarningNetworkSlow => "Network share is responding slowly",
+        MessageId::WarningUnreachable => "Network share cannot be reached",
+        MessageId::WarningHidden => "Folder is hidden",
+        MessageId::WarningSystem => "Folder is a system folder",
+        MessageId::WarningAclNotPreserved => "Access permissions are lost at the destination",
+        MessageId::WarningTimestampsDegraded => "Timestamps lose precision at the destination",
+        MessageId::WarningStreamsNotPreserved => "Alternate data streams are lost at the destination",
+        MessageId::WarningCompressionNotPreserved => "Compression is lost at the destination",
+        MessageId::ConflictNameExists => "{path} already exists",
+        MessageId::ConflictCycleDetected => "Moves would form a cycle",
+        MessageId::ConflictDestInsideSource => "Destination is inside the source",
+        MessageId::ConflictNoSpace => "needs {required_mb} MB but only {available_mb} MB free",
+        MessageId::ConflictPermissionAdministrator => "Administrator rights are required",
+        MessageId::ConflictPermissionFileSystemWrite => "Destination is not writable",
+        MessageId::ConflictPermissionNetworkAccess => "Network access is required",
+        MessageId::ConflictSourceMissing => "source no longer exists",
+        MessageId::JournalUnreadable => "Entry {position} is unreadable: {message}",
+        MessageId::JournalChecksumMismatch => "Entry {position} failed checksum verification",
+        MessageId::JournalMissingSummary => "Journal has no summary record (run interrupted or file truncated)",
+        MessageId::JournalSummaryMismatch => "Journal does not match its summary: {message}",
+    }
+}
+
+fn japanese(id: MessageId) -> &'static str {
+    match id {
+        MessageId::ErrorIo => "入出力エラー: {error}",
+        MessageId::ErrorPattern => "パターンのエラー: {message}",
+        MessageId::ErrorScan => "スキャン中のエラー（{path}）: {message}",
+        MessageId::ErrorPlanValidation => "移動プランの検証エラー: {message}",
+        MessageId::ErrorExecution => "実行中のエラー（{path}）: {message}",
+        MessageId::ErrorUndo => "Undo のエラー: {message}",
+        MessageId::ErrorJournal => "ジャーナルのエラー: {message}",
+        MessageId::ErrorConfig => "設定のエラー: {message}",
+        MessageId::ErrorPermissionDenied => "アクセスが拒否されました: {path}",
+        MessageId::ErrorPathNotFound => "パスが見つかりません: {path}",
+        MessageId::ErrorLongPathNotSupported => "長いパスには対応していません: {path}",
+        MessageId::ErrorOneDriveOffline => "OneDrive がオフラインです: {path}",
+        MessageId::ErrorNetworkTimeout => "ネットワークパスが {timeout_secs} 秒以内に応答しませんでした: {path}",
+        MessageId::ErrorInsufficientSpace => "ディスクの空き容量が足りません: {path}",
+        MessageId::ErrorInvalidNodeId => "ノード ID が正しくありません: {id}",
+        MessageId::ErrorCancelled => "操作を中止しました",
+        MessageId::WarningLongPath => "パスが 260 文字を超えています",
+        MessageId::WarningAclDiffers => "アクセス権が親フォルダと異なります",
+        MessageId::WarningOffline => "ファイルがオフラインです（クラウドのプレースホルダー）",
+        MessageId::WarningAccessDenied => "読み取れない項目があります",
+        MessageId::WarningJunction => "ジャンクションまたはシンボリックリンクです",
+        MessageId::WarningCrossVolume => "別のボリュームへの移動です（コピーしてから削除）",
+        MessageId::WarningNetworkSlow => "ネットワーク共有の応答が遅くなっています",
+        MessageId::WarningUnreachable => "ネットワーク共有に接続できません",
+        MessageId::WarningHidden => "隠しフォルダです",
+        MessageId::WarningSystem => "システムフォルダです",
+        MessageId::WarningAclNotPreserved => "移動先ではアクセス権が失われます",
+        MessageId::WarningTimestampsDegraded => "移動先ではタイムスタンプの精度が落ちます",
+        MessageId::WarningStreamsNotPreserved => "移動先では代替データストリームが失われます",
+        MessageId::WarningCompressionNotPreserved => "移動先では圧縮属性が失われます",
+        MessageId::ConflictNameExists => "{path} は既に存在します",
+        MessageId::ConflictCycleDetected => "移動が循環しています",
+        MessageId::ConflictDestInsideSource => "移動先が移動元の中にあります",
+        MessageId::ConflictNoSpace => "{required_mb} MB 必要ですが空きは {available_mb} MB です",
+        MessageId::ConflictPermissionAdministrator => "管理者権限が必要です",
+        MessageId::ConflictPermissionFileSystemWrite => "移動先に書き込めません",
+        MessageId::ConflictPermissionNetworkAccess => "ネットワークへのアクセスが必要です",
+        MessageId::ConflictSourceMissing => "移動元がなくなっています",
+        MessageId::JournalUnreadable => "エントリ {position} を読み取れません: {message}",
+        MessageId::JournalChecksumMismatch => "エントリ {position} のチェックサムが一致しません",
+        MessageId::JournalMissingSummary => "ジャーナルに完了レコードがありません（実行が中断されたか、ファイルが途中で切れています）",
+        MessageId::JournalSummaryMismatch => "ジャーナルが完了レコードと一致しません: {message}",
+    }
+}
+
+/// ID と値に分けて、表示する言語を後から選べるようにする
+pub trait Localize {
+    fn message(&self) -> Message;
+
+    fn localize(&self, locale: Locale) -> String {
+        self.message().render(locale)
+    }
+}
+
+impl Localize for FileMoverError {
+    fn message(&self) -> Message {
+        match self {
+            FileMoverError::Io(error) => Message::new(MessageId::ErrorIo).with_arg("error", error),
+            FileMoverError::Pattern { message } => Message::new(MessageId::ErrorPattern).with_arg("message", message),
+            FileMoverError::Scan { path, message } => Message::new(MessageId::ErrorScan)
+                .with_arg("path", path.display())
+                .with_arg("message", message),
+            FileMoverError::PlanValidation { message } => {
+                Message::new(MessageId::ErrorPlanValidation).with_arg("message", message)
+            }
+            FileMoverError::Execution { path, message } => Message::new(MessageId::ErrorExecution)
+                .with_arg("path", path.display())
+                .with_arg("message", message),
+            FileMoverError::Undo { message } => Message::new(MessageId::ErrorUndo).with_arg("message", message),
+            FileMoverError::Journal { message } => Message::new(MessageId::ErrorJournal).with_arg("message", message),
+            FileMoverError::Config { message } => Message::new(MessageId::ErrorConfig).with_arg("message", message),
+            FileMoverError::PermissionDenied { path } => {
+                Message::new(MessageId::ErrorPermissionDenied).with_arg("path", path.display())
+            }
+            FileMoverError::PathNotFound { path } => {
+                Message::new(MessageId::ErrorPathNotFound).with_arg("path", path.display())
+            }
+            FileMoverError::LongPathNotSupported { path } => {
+                Message::new(MessageId::ErrorLongPathNotSupported).with_arg("path", path.display())
+            }
+            FileMoverError::OneDriveOffline { path } => {
+                Message::new(MessageId::ErrorOneDriveOffline).with_arg("path", path.display())
+            }
+            FileMoverError::NetworkTimeout { path, timeout_secs } => Message::new(MessageId::ErrorNetworkTimeout)
+                .with_arg("path", path.display())
+                .with_arg("timeout_secs", timeout_secs),
+            FileMoverError::InsufficientSpace { path } => {
+                Message::new(MessageId::ErrorInsufficientSpace).with_arg("path", path.display())
+            }
+            FileMoverError::InvalidNodeId(id) => Message::new(MessageId::ErrorInvalidNodeId).with_arg("id", id),
+            FileMoverError::Cancelled => Message::new(MessageId::ErrorCancelled),
+        }
+    }
+}
+
+impl Localize for Warning {
+    fn message(&self) -> Message {
+        Message::new(match self {
+            Warning::LongPath => MessageId::WarningLongPath,
+            Warning::AclDiffers => MessageId::WarningAclDiffers,
+            Warning::Offline => MessageId::WarningOffline,
+            Warning::AccessDenied => MessageId::WarningAccessDenied,
+            Warning::Junction => MessageId::WarningJunction,
+            Warning::CrossVolume => MessageId::WarningCrossVolume,
+            Warning::NetworkSlow => MessageId::WarningNetworkSlow,
+            Warning::Unreachable => MessageId::WarningUnreachable,
+            Warning::Hidden => MessageId::WarningHidden,
+            Warning::System => MessageId::WarningSystem,
+            Warning::AclNotPreserved => MessageId::WarningAclNotPreserved,
+            Warning::TimestampsDegraded => MessageId::WarningTimestampsDegraded,
+            Warning::StreamsNotPreserved => MessageId::WarningStreamsNotPreserved,
+            Warning::CompressionNotPreserved => MessageId::WarningCompressionNotPreserved,
+        })
+    }
+}
+
+impl Localize for Conflict {
+    fn message(&self) -> Message {
+        match self {
+            Conflict::NameExists { existing_path } => {
+                Message::new(MessageId::ConflictNameExists).with_arg("path", existing_path.display())
+            }
+            Conflict::CycleDetected => Message::new(MessageId::ConflictCycleDetected),
+            Conflict::DestInsideSource => Message::new(MessageId::ConflictDestInsideSource),
+            Conflict::NoSpace { required, available } => Message::new(MessageId::ConflictNoSpace)
+                .with_arg("required_mb", format!("{:.2}", *required as f64 / (1024.0 * 1024.0)))
+                .with_arg("available_mb", format!("{:.2}", *available as f64 / (1024.0 * 1024.0))),
+            Conflict::Permission { required } => Message::new(match required {
+                Permission::Administrator => MessageId::ConflictPermissionAdministrator,
+                Permission::FileSystemWrite => MessageId::ConflictPermissionFileSystemWrite,
+                Permission::NetworkAccess => MessageId::ConflictPermissionNetworkAccess,
+            }),
+            Conflict::SourceMissing => Message::new(MessageId::ConflictSourceMissing),
+        }
+    }
+}
+
+impl Localize for JournalProblem {
+    fn message(&self) -> Message {
+        match self {
+            JournalProblem::Unreadable { position, message } => Message::new(MessageId::JournalUnreadable)
+                .with_arg("position", position)
+                .with_arg("message", message),
+            JournalProblem::ChecksumMismatch { position } => {
+                Message::new(MessageId::JournalChecksumMismatch).with_arg("position", position)
+            }
+            JournalProblem::MissingSummary => Message::new(MessageId::JournalMissingSummary),
+            JournalProblem::SummaryMismatch { message } => {
+                Message::new(MessageId::JournalSummaryMismatch).with_arg("message", message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_english_catalog_matches_display() {
+        let errors = [
+            FileMoverError::Scan { path: PathBuf::from("/data"), message: "denied".to_string() },
+            FileMoverError::NetworkTimeout { path: PathBuf::from("//nas/share"), timeout_secs: 5 },
+            FileMoverError::Cancelled,
+        ];
+        for error in errors {
+            assert_eq!(error.localize(Locale::En), error.to_string());
+        }
+        let problem = JournalProblem::Unreadable { position: 3, message: "bad".to_string() };
+        assert_eq!(problem.localize(Locale::En), problem.to_string());
+    }
+
+    #[test]
+    fn test_render_japanese_with_args() {
+        let conflict = Conflict::NameExists { existing_path: PathBuf::from("/dest/{seq}") };
+        assert_eq!(conflict.localize(Locale::Ja), "/dest/{seq} は既に存在します");
+
+        // 全ての ID に両方の言語の文言がある
+        for id in MessageId::ALL {
+            for locale in Locale::ALL {
+                assert!(!catalog(locale, id).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("ja_JP.UTF-8"), Some(Locale::Ja));
+        assert_eq!(Locale::from_tag("en-US"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("fr"), None);
+        assert!("de".parse::<Locale>().is_err());
+    }
+}
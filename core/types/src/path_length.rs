@@ -0,0 +1,46 @@
+use std::path::Path;
+
+/// Windows の MAX_PATH（長いパスが有効でない環境ではこれを超えると操作が失敗する）
+pub const MAX_PATH: usize = 260;
+
+/// Windows 上での長さ（UTF-16 の単位数）
+pub fn path_len(path: &Path) -> usize {
+    path.to_string_lossy().encode_utf16().count()
+}
+
+pub fn exceeds_max_path(path: &Path) -> bool {
+    path_len(path) > MAX_PATH
+}
+
+/// source を dest に移動すると MAX_PATH を超えるパスができるか
+///
+/// フォルダの場合は配下で最も長いパスを dest に付け替えた長さで判定する。
+/// 読めないフォルダは判定から外す。
+pub fn exceeds_max_path_after_move(source: &Path, dest: &Path) -> bool {
+    let dest_len = path_len(dest);
+    if dest_len > MAX_PATH {
+        return true;
+    }
+
+    let is_dir = std::fs::symlink_metadata(source).is_ok_and(|m| m.is_dir());
+    if !is_dir {
+        return false;
+    }
+
+    // 配下のパスは source の長さを差し引いた分だけ dest の後ろに付く
+    let limit = MAX_PATH - dest_len + path_len(source);
+    has_path_longer_than(source, limit)
+}
+
+// 超えるものが見つかった時点でやめる。リンクはたどらない
+fn has_path_longer_than(dir: &Path, limit: usize) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        path_len(&path) > limit
+            || (entry.file_type().is_ok_and(|t| t.is_dir()) && has_path_longer_than(&path, limit))
+    })
+}
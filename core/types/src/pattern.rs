@@ -5,6 +5,24 @@ pub enum PatternKind {
     Glob,
     Regex,
     Contains,
+    /// 綴りの誤りを許して含む（編集距離が max_edits 以下の部分がある）
+    Fuzzy,
+    /// 拡張子のいずれか（値はカンマ区切り。例: `jpg,jpeg,heic`）
+    Extension,
+}
+
+/// パターンを何に対して評価するか
+///
+/// パスは区切りを `/` にそろえて評価する（例: `**/Projects/*/build`）。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MatchTarget {
+    /// フォルダ名
+    #[default]
+    Name,
+    /// フルパス
+    FullPath,
+    /// スキャンしたルートからの相対パス
+    RelativePathFromRoot,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -13,6 +31,14 @@ pub struct PatternSpec {
     pub value: String,
     pub is_exclude: bool,
     pub case_insensitive: bool,
+    #[serde(default)]
+    pub match_target: MatchTarget,
+    /// Fuzzy で許す編集回数（None ならパターンの長さから決める）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_edits: Option<u32>,
+    /// Contains で単語全体として一致するものだけを数える（前後が文字や数字でない。"art" は "Quarterly" に一致しない）
+    #[serde(default)]
+    pub whole_word: bool,
 }
 
 impl PatternSpec {
@@ -22,6 +48,9 @@ impl PatternSpec {
             value: pattern.to_string(),
             is_exclude: false,
             case_insensitive: true,
+            match_target: MatchTarget::Name,
+            max_edits: None,
+            whole_word: false,
         }
     }
 
@@ -31,6 +60,9 @@ impl PatternSpec {
             value: pattern.to_string(),
             is_exclude: false,
             case_insensitive: true,
+            match_target: MatchTarget::Name,
+            max_edits: None,
+            whole_word: false,
         }
     }
 
@@ -40,9 +72,44 @@ impl PatternSpec {
             value: pattern.to_string(),
             is_exclude: false,
             case_insensitive: true,
+            match_target: MatchTarget::Name,
+            max_edits: None,
+            whole_word: false,
         }
     }
 
+    pub fn new_fuzzy(pattern: &str) -> Self {
+        Self {
+            kind: PatternKind::Fuzzy,
+            value: pattern.to_string(),
+            is_exclude: false,
+            case_insensitive: true,
+            match_target: MatchTarget::Name,
+            max_edits: None,
+            whole_word: false,
+        }
+    }
+
+    /// 拡張子の一覧（`jpg,png` や `.tar.gz` の形式。大文字小文字は区別しない）
+    pub fn new_extension(extensions: &str) -> Self {
+        Self {
+            kind: PatternKind::Extension,
+            value: extensions.to_string(),
+            is_exclude: false,
+            case_insensitive: true,
+            match_target: MatchTarget::Name,
+            max_edits: None,
+            whole_word: false,
+        }
+    }
+
+    /// Extension の値の各拡張子（先頭の `.` と前後の空白を除く）
+    pub fn extensions(&self) -> impl Iterator<Item = &str> {
+        self.value.split(',')
+            .map(|ext| ext.trim().trim_start_matches('.'))
+            .filter(|ext| !ext.is_empty())
+    }
+
     pub fn exclude(mut self) -> Self {
         self.is_exclude = true;
         self
@@ -52,14 +119,99 @@ impl PatternSpec {
         self.case_insensitive = false;
         self
     }
+
+    pub fn with_target(mut self, target: MatchTarget) -> Self {
+        self.match_target = target;
+        self
+    }
+
+    pub fn with_max_edits(mut self, max_edits: u32) -> Self {
+        self.max_edits = Some(max_edits);
+        self
+    }
+
+    pub fn whole_word(mut self) -> Self {
+        self.whole_word = true;
+        self
+    }
+
+    /// 文字そのものに一致する部分の長さ（ワイルドカードや文字クラスは数えない。具体的なパターンほど長い）
+    pub fn literal_len(&self) -> usize {
+        match self.kind {
+            PatternKind::Contains | PatternKind::Fuzzy => self.value.chars().count(),
+            // 最も短い拡張子と `.`
+            PatternKind::Extension => self.extensions().map(|ext| ext.chars().count() + 1).min().unwrap_or(0),
+            PatternKind::Glob => {
+                let mut in_class = false;
+                self.value.chars().filter(|&c| match c {
+                    '[' => { in_class = true; false }
+                    ']' => { in_class = false; false }
+                    '*' | '?' | '{' | '}' | ',' | '\\' => false,
+                    _ => !in_class,
+                }).count()
+            }
+            PatternKind::Regex => {
+                let mut in_class = false;
+                let mut escaped = false;
+                self.value.chars().filter(|&c| {
+                    if escaped {
+                        escaped = false;
+                        // \d や \w などは文字クラス
+                        return !in_class && !c.is_ascii_alphabetic();
+                    }
+                    match c {
+                        '\\' => { escaped = true; false }
+                        '[' => { in_class = true; false }
+                        ']' => { in_class = false; false }
+                        '.' | '^' | '$' | '*' | '+' | '?' | '(' | ')' | '{' | '}' | '|' => false,
+                        _ => !in_class,
+                    }
+                }).count()
+            }
+        }
+    }
 }
 
+/// パターンを組み合わせた式（葉は通常のパターン。葉の is_exclude は使わない）
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PatternExpr {
+    Pattern(PatternSpec),
+    /// すべて一致（空なら一致）
+    All(Vec<PatternExpr>),
+    /// いずれかが一致（空なら不一致）
+    Any(Vec<PatternExpr>),
+    Not(Box<PatternExpr>),
+}
+
+impl PatternExpr {
+    /// 葉のパターン（左から順）
+    pub fn leaves(&self) -> Vec<&PatternSpec> {
+        match self {
+            Self::Pattern(spec) => vec![spec],
+            Self::All(exprs) | Self::Any(exprs) => exprs.iter().flat_map(|e| e.leaves()).collect(),
+            Self::Not(expr) => expr.leaves(),
+        }
+    }
+}
+
+impl From<PatternSpec> for PatternExpr {
+    fn from(spec: PatternSpec) -> Self {
+        Self::Pattern(spec)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NormalizationOptions {
     pub normalize_unicode: bool,
     pub normalize_width: bool,
     pub strip_diacritics: bool,
     pub normalize_case: bool,
+    /// 半角カタカナを全角にそろえる（ﾃﾞｰﾀ → データ）
+    #[serde(default = "default_true")]
+    pub fold_halfwidth_kana: bool,
+    /// ひらがなとカタカナを同じ文字として扱う（でーた → データ）
+    #[serde(default)]
+    pub fold_hiragana_katakana: bool,
 }
 
 impl Default for NormalizationOptions {
@@ -69,6 +221,12 @@ impl Default for NormalizationOptions {
             normalize_width: true,
             strip_diacritics: false,
             normalize_case: true,
+            fold_halfwidth_kana: true,
+            fold_hiragana_katakana: false,
         }
     }
+}
+
+fn default_true() -> bool {
+    true
 }
\ No newline at end of file
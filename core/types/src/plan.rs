@@ -20,7 +20,7 @@ impl fmt::Display for PlanNodeId {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OpKind {
     Move,
     CopyDelete,
@@ -29,6 +29,21 @@ pub enum OpKind {
     None,
 }
 
+impl std::str::FromStr for OpKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "move" => Ok(OpKind::Move),
+            "copydelete" => Ok(OpKind::CopyDelete),
+            "rename" => Ok(OpKind::Rename),
+            "skip" => Ok(OpKind::Skip),
+            "none" => Ok(OpKind::None),
+            other => Err(format!("Unknown operation '{}' (expected move, copy-delete, rename, skip or none)", other)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlanNode {
     pub id: PlanNodeId,
@@ -43,6 +58,9 @@ pub struct PlanNode {
     pub conflicts: Vec<Conflict>,
     pub children: Vec<PlanNodeId>,
     pub rule_id: Option<Uuid>,
+    /// スキャン時にルールが付けたタグ（レポートの分類用）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -52,6 +70,8 @@ pub enum Conflict {
     DestInsideSource,
     NoSpace { required: u64, available: u64 },
     Permission { required: Permission },
+    /// 計画を作った後に移動元がなくなった
+    SourceMissing,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -66,6 +86,25 @@ pub struct MovePlan {
     pub roots: Vec<PlanNodeId>,
     pub nodes: HashMap<PlanNodeId, PlanNode>,
     pub summary: PlanSummary,
+    /// 実行する操作の順番（依存関係で並べたもの。古い計画ファイルでは空）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub execution_order: Vec<PlanNodeId>,
+}
+
+impl MovePlan {
+    /// 計画の内容の CRC32（ジャーナルと計画ファイルの対応付け用。ノードの並び順には依存しない）
+    pub fn content_hash(&self) -> String {
+        let mut hasher = crc32fast::Hasher::new();
+        for root in &self.roots {
+            hasher.update(root.0.as_bytes());
+        }
+        let mut nodes: Vec<&PlanNode> = self.nodes.values().collect();
+        nodes.sort_by_key(|node| node.id.0);
+        for node in nodes {
+            hasher.update(serde_json::to_string(node).unwrap_or_default().as_bytes());
+        }
+        format!("{:08x}", hasher.finalize())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -76,6 +115,9 @@ pub struct PlanSummary {
     pub cross_volume: u64,
     pub conflicts: u64,
     pub warnings: u64,
+    /// 移動先で保てない情報ごとの操作数（古い計画ファイルには無い）
+    #[serde(default)]
+    pub preservation: PreservationSummary,
 }
 
 impl Default for PlanSummary {
@@ -87,10 +129,49 @@ impl Default for PlanSummary {
             cross_volume: 0,
             conflicts: 0,
             warnings: 0,
+            preservation: PreservationSummary::default(),
         }
     }
 }
 
+/// 移動先のファイルシステムで保てない情報ごとの操作数
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreservationSummary {
+    pub acl_lost: u64,
+    pub timestamps_degraded: u64,
+    pub streams_lost: u64,
+    pub compression_lost: u64,
+}
+
+impl PreservationSummary {
+    /// ノードの警告から数える（スキップと何もしないノードは数えない）
+    pub fn from_nodes<'a>(nodes: impl IntoIterator<Item = &'a PlanNode>) -> Self {
+        let mut summary = Self::default();
+        for node in nodes {
+            if !matches!(node.kind, OpKind::Skip | OpKind::None) {
+                summary.record(&node.warnings);
+            }
+        }
+        summary
+    }
+
+    pub fn record(&mut self, warnings: &[Warning]) {
+        for warning in warnings {
+            match warning {
+                Warning::AclNotPreserved => self.acl_lost += 1,
+                Warning::TimestampsDegraded => self.timestamps_degraded += 1,
+                Warning::StreamsNotPreserved => self.streams_lost += 1,
+                Warning::CompressionNotPreserved => self.compression_lost += 1,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NodeChange {
     SetSkip(PlanNodeId, bool),
@@ -107,7 +188,7 @@ pub struct ValidationDelta {
     pub summary_diff: PlanSummaryDiff,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct PlanSummaryDiff {
     pub count_dirs_delta: i64,
     pub count_files_delta: i64,
@@ -1,13 +1,16 @@
 use std::path::PathBuf;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use crate::pattern::PatternSpec;
+use crate::pattern::{NormalizationOptions, PatternExpr, PatternSpec};
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ConflictPolicy {
     AutoRename,
     Skip,
     Overwrite,
+    /// 移動先に同名のフォルダがあれば中身をまとめる（配下の項目ごとの操作に展開する）
+    Merge,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -17,9 +20,97 @@ pub struct Rule {
     pub pattern: PatternSpec,
     pub dest_root: PathBuf,
     pub template: String,
-    pub policy: ConflictPolicy,
+    /// 衝突時の扱い（None なら PlanOptions.default_conflict_policy に従う）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<ConflictPolicy>,
     pub label: Option<String>,
     pub priority: u32,
+    #[serde(default)]
+    pub conditions: RuleConditions,
+    /// pattern に加えて一致する必要のある式（例: `*backup*` かつ `keep` を含まない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<PatternExpr>,
+    /// このルールだけ ScanOptions と異なる正規化を使う場合
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<NormalizationOptions>,
+    /// 名前から {src_yyyy} などの日付を取り出す形式（例: `%Y-%m`。空なら DEFAULT_DATE_FORMATS）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub date_formats: Vec<String>,
+    /// 一致したフォルダに付けるタグ（dest_root が空のルールは移動先を決めず、タグだけを付ける）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// ルールのメタデータ条件（指定したものをすべて満たす場合だけ一致する。値が分からない条件は満たさない）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleConditions {
+    pub min_size_bytes: Option<u64>,
+    pub max_size_bytes: Option<u64>,
+    /// この日時より前に最終更新された
+    pub last_modified_before: Option<DateTime<Utc>>,
+    /// 最終更新からの経過日数がこれ以上
+    pub older_than_days: Option<u32>,
+    /// 直下の項目数
+    pub min_item_count: Option<u64>,
+    pub max_item_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains_files: Option<ContainsFilesCondition>,
+}
+
+/// 配下に特定の種類のファイルがあるか（例: RAW 写真 `*.cr2` が 1 つ以上）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContainsFilesCondition {
+    /// ファイル名の glob（大文字小文字は区別しない）
+    pub pattern: String,
+    #[serde(default = "default_min_count")]
+    pub min_count: u64,
+    /// 調べる深さ（1 は直下のみ）
+    #[serde(default = "default_peek_depth")]
+    pub max_depth: u32,
+}
+
+impl ContainsFilesCondition {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            min_count: default_min_count(),
+            max_depth: default_peek_depth(),
+        }
+    }
+
+    pub fn with_min_count(mut self, min_count: u64) -> Self {
+        self.min_count = min_count;
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+fn default_min_count() -> u64 {
+    1
+}
+
+fn default_peek_depth() -> u32 {
+    1
+}
+
+impl RuleConditions {
+    pub fn is_empty(&self) -> bool {
+        !self.needs_size()
+            && self.last_modified_before.is_none()
+            && self.older_than_days.is_none()
+            && self.min_item_count.is_none()
+            && self.max_item_count.is_none()
+            && self.contains_files.is_none()
+    }
+
+    /// フォルダサイズの計算が必要か
+    pub fn needs_size(&self) -> bool {
+        self.min_size_bytes.is_some() || self.max_size_bytes.is_some()
+    }
 }
 
 impl Rule {
@@ -34,19 +125,34 @@ impl Rule {
             pattern,
             dest_root,
             template,
-            policy: ConflictPolicy::AutoRename,
+            policy: None,
             label: None,
             priority: 0,
+            conditions: RuleConditions::default(),
+            expression: None,
+            normalization: None,
+            date_formats: Vec::new(),
+            tags: Vec::new(),
         }
     }
 
+    /// タグを付けるだけのルール
+    pub fn tag_only(pattern: PatternSpec, tags: Vec<String>) -> Self {
+        Self::new(pattern, PathBuf::new(), String::new()).with_tags(tags)
+    }
+
+    /// 移動先を決めず、タグだけを付けるルールか
+    pub fn is_tag_only(&self) -> bool {
+        !self.pattern.is_exclude && !self.tags.is_empty() && self.dest_root.as_os_str().is_empty()
+    }
+
     pub fn with_label(mut self, label: String) -> Self {
         self.label = Some(label);
         self
     }
 
     pub fn with_policy(mut self, policy: ConflictPolicy) -> Self {
-        self.policy = policy;
+        self.policy = Some(policy);
         self
     }
 
@@ -54,6 +160,31 @@ impl Rule {
         self.priority = priority;
         self
     }
+
+    pub fn with_conditions(mut self, conditions: RuleConditions) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    pub fn with_expression(mut self, expression: PatternExpr) -> Self {
+        self.expression = Some(expression);
+        self
+    }
+
+    pub fn with_normalization(mut self, normalization: NormalizationOptions) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    pub fn with_date_formats(mut self, date_formats: Vec<String>) -> Self {
+        self.date_formats = date_formats;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -61,9 +192,37 @@ pub struct FolderHit {
     pub path: PathBuf,
     pub name: String,
     pub matched_rule: Option<Uuid>,
+    /// 一致したルールすべて（優先度順。ScanOptions で集めない設定の場合は matched_rule だけ）
+    #[serde(default)]
+    pub matched_rules: Vec<Uuid>,
+    /// 一致したルールすべてのタグ（移動先のルールの選び方には左右されない）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     pub dest_preview: Option<PathBuf>,
     pub warnings: Vec<Warning>,
     pub size_bytes: Option<u64>,
+    /// フォルダ直下の項目数（ファイル・フォルダの合計。分からない場合は None）
+    #[serde(default)]
+    pub item_count: Option<u64>,
+    /// ファイル単位のスキャンで見つかったヒットは false
+    #[serde(default = "default_is_dir")]
+    pub is_dir: bool,
+}
+
+fn default_is_dir() -> bool {
+    true
+}
+
+impl FolderHit {
+    /// 複数のルールに一致し、どれを使うか選べるか
+    pub fn has_multiple_matches(&self) -> bool {
+        self.matched_rules.len() > 1
+    }
+
+    /// 中身のないフォルダか（項目数が分からない場合は false）
+    pub fn is_empty_dir(&self) -> bool {
+        self.is_dir && self.item_count == Some(0)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -74,4 +233,20 @@ pub enum Warning {
     AccessDenied,
     Junction,
     CrossVolume,
+    /// ネットワーク共有の応答が遅い
+    NetworkSlow,
+    /// ネットワーク共有に接続できない（タイムアウトを含む）
+    Unreachable,
+    /// 隠し属性
+    Hidden,
+    /// システム属性
+    System,
+    /// 移動先のファイルシステムが ACL を保てない（NTFS → exFAT など）
+    AclNotPreserved,
+    /// 移動先のファイルシステムではタイムスタンプの精度が落ちる（FAT は 2 秒単位）
+    TimestampsDegraded,
+    /// 移動先のファイルシステムが代替データストリームを保てない
+    StreamsNotPreserved,
+    /// 移動先のファイルシステムが圧縮属性を保てない
+    CompressionNotPreserved,
 }
\ No newline at end of file
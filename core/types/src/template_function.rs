@@ -0,0 +1,109 @@
+use crate::FileMoverError;
+
+/// テンプレート変数の後ろに `|` でつなぐ関数（例: `{name|slug|truncate:40}`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateFunction {
+    Lower,
+    Upper,
+    /// 文字と数字以外の並びを `-` にして小文字にする（`My Photos (2024)` → `my-photos-2024`）
+    Slug,
+    /// 先頭から N 文字にする（末尾の空白と `.` は Windows で使えないので除く）
+    Truncate(usize),
+    /// N 文字になるまで前に埋める（既定は `0`。`pad:3` や `pad:10:_`）
+    Pad(usize, char),
+}
+
+impl TemplateFunction {
+    pub fn parse(spec: &str) -> Result<Self, FileMoverError> {
+        let (name, arg) = match spec.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (spec, None),
+        };
+        let invalid = |message: String| FileMoverError::Config { message };
+        let width = |arg: Option<&str>| -> Result<usize, FileMoverError> {
+            let arg = arg.ok_or_else(|| invalid(format!("Template function '{}' needs a length", name)))?;
+            arg.parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| invalid(format!("Invalid length '{}' for template function '{}'", arg, name)))
+        };
+        let no_arg = |function: Self| match arg {
+            Some(_) => Err(invalid(format!("Template function '{}' takes no argument", name))),
+            None => Ok(function),
+        };
+
+        match name {
+            "lower" => no_arg(Self::Lower),
+            "upper" => no_arg(Self::Upper),
+            "slug" => no_arg(Self::Slug),
+            "truncate" => Ok(Self::Truncate(width(arg)?)),
+            "pad" => {
+                let (length, fill) = match arg.and_then(|a| a.split_once(':')) {
+                    Some((length, fill)) => (Some(length), fill),
+                    None => (arg, "0"),
+                };
+                let mut fill_chars = fill.chars();
+                match (fill_chars.next(), fill_chars.next()) {
+                    (Some(fill), None) => Ok(Self::Pad(width(length)?, fill)),
+                    _ => Err(invalid(format!("Padding for template function 'pad' must be one character: '{}'", fill))),
+                }
+            }
+            _ => Err(invalid(format!("Unknown template function: {}", name))),
+        }
+    }
+
+    pub fn apply(&self, value: &str) -> String {
+        match *self {
+            Self::Lower => value.to_lowercase(),
+            Self::Upper => value.to_uppercase(),
+            Self::Slug => value
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join("-")
+                .to_lowercase(),
+            Self::Truncate(length) => value.chars()
+                .take(length)
+                .collect::<String>()
+                .trim_end_matches([' ', '.'])
+                .to_string(),
+            Self::Pad(length, fill) => {
+                let missing = length.saturating_sub(value.chars().count());
+                std::iter::repeat_n(fill, missing).chain(value.chars()).collect()
+            }
+        }
+    }
+}
+
+/// `{変数|関数...}` を展開する（lookup が None を返す変数はそのまま残す）
+pub fn expand_placeholders<F>(template: &str, mut lookup: F) -> Result<String, FileMoverError>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+
+        let placeholder = &rest[start + 1..end];
+        let mut parts = placeholder.split('|');
+        let name = parts.next().unwrap_or_default();
+        match lookup(name) {
+            Some(mut value) => {
+                for spec in parts {
+                    value = TemplateFunction::parse(spec)?.apply(&value);
+                }
+                result.push_str(&value);
+            }
+            None => result.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}